@@ -195,11 +195,14 @@ func accountConsistencyLevelToCommitment(c uint8) (rpc.CommitmentType, error) {
 }
 
 const (
-	postMessageInstructionMinNumAccounts = 8
-	postMessageInstructionID             = 0x01
-	postMessageUnreliableInstructionID   = 0x08
-	accountPrefixReliable                = "msg"
-	accountPrefixUnreliable              = "msu"
+	postMessageInstructionMinNumAccounts  = 8
+	postMessageInstructionID              = 0x01
+	postMessageUnreliableInstructionID    = 0x08
+	forwardVAAInstructionID               = 0x15
+	finalizeMessageV1InstructionID        = 0x26
+	postMessageDeterministicInstructionID = 0x31
+	accountPrefixReliable                 = "msg"
+	accountPrefixUnreliable               = "msu"
 )
 
 // PostMessageData represents the user-supplied, untrusted instruction data
@@ -210,6 +213,77 @@ type PostMessageData struct {
 	ConsistencyLevel ConsistencyLevel
 }
 
+// FinalizeMessageData represents the user-supplied, untrusted instruction data
+// for publications of draft messages, whose payload was written to a separate account beforehand.
+type FinalizeMessageData struct {
+	Nonce            uint32
+	ConsistencyLevel ConsistencyLevel
+}
+
+// ForwardVAAData represents the user-supplied, untrusted instruction data
+// for re-publications of VAAs verified by the core bridge, forwarded to other SVM networks.
+type ForwardVAAData struct {
+	Nonce            uint32
+	TargetChain      uint16
+	ConsistencyLevel ConsistencyLevel
+}
+
+// PostMessageDeterministicData represents the user-supplied, untrusted instruction data
+// for message publications into accounts derived from the emitter and a seed it picks.
+type PostMessageDeterministicData struct {
+	Seed             [32]byte
+	Nonce            uint32
+	Payload          []byte
+	ConsistencyLevel ConsistencyLevel
+}
+
+// messageInstruction is the part of an instruction publishing a message the watcher acts on.
+type messageInstruction struct {
+	// Decoded instruction data, for logging only.
+	data             interface{}
+	consistencyLevel ConsistencyLevel
+
+	// Position of the message account in the accounts of the instruction.
+	messageAccount int
+}
+
+// decodeMessageInstruction decodes the instruction data (UNTRUSTED) of the core bridge instructions
+// publishing messages. It returns nil for any other instruction.
+func decodeMessageInstruction(data []byte) (*messageInstruction, error) {
+	if len(data) == 0 {
+		return nil, nil
+	}
+
+	var msg messageInstruction
+	var err error
+	switch data[0] {
+	case postMessageInstructionID, postMessageUnreliableInstructionID:
+		var d PostMessageData
+		err = borsh.Deserialize(&d, data[1:])
+		msg = messageInstruction{data: d, consistencyLevel: d.ConsistencyLevel, messageAccount: 1}
+	case finalizeMessageV1InstructionID:
+		var d FinalizeMessageData
+		err = borsh.Deserialize(&d, data[1:])
+		msg = messageInstruction{data: d, consistencyLevel: d.ConsistencyLevel, messageAccount: 1}
+	case postMessageDeterministicInstructionID:
+		var d PostMessageDeterministicData
+		err = borsh.Deserialize(&d, data[1:])
+		msg = messageInstruction{data: d, consistencyLevel: d.ConsistencyLevel, messageAccount: 1}
+	case forwardVAAInstructionID:
+		// The forwarded VAA precedes the message account.
+		var d ForwardVAAData
+		err = borsh.Deserialize(&d, data[1:])
+		msg = messageInstruction{data: d, consistencyLevel: d.ConsistencyLevel, messageAccount: 2}
+	default:
+		return nil, nil
+	}
+
+	if err != nil {
+		return nil, fmt.Errorf("failed to deserialize instruction data: %w", err)
+	}
+	return &msg, nil
+}
+
 func NewSolanaWatcher(
 	rpcUrl string,
 	wsUrl *string,
@@ -668,11 +742,12 @@ func (s *SolanaWatcher) processInstruction(ctx context.Context, logger *zap.Logg
 		return false, nil
 	}
 
-	if len(inst.Data) == 0 {
-		return false, nil
+	// Decode instruction data (UNTRUSTED)
+	msg, err := decodeMessageInstruction(inst.Data)
+	if err != nil {
+		return false, err
 	}
-
-	if inst.Data[0] != postMessageInstructionID && inst.Data[0] != postMessageUnreliableInstructionID {
+	if msg == nil {
 		return false, nil
 	}
 
@@ -681,18 +756,12 @@ func (s *SolanaWatcher) processInstruction(ctx context.Context, logger *zap.Logg
 			len(inst.Accounts), postMessageInstructionMinNumAccounts)
 	}
 
-	// Decode instruction data (UNTRUSTED)
-	var data PostMessageData
-	if err := borsh.Deserialize(&data, inst.Data[1:]); err != nil {
-		return false, fmt.Errorf("failed to deserialize instruction data: %w", err)
-	}
-
 	if logger.Level().Enabled(zapcore.DebugLevel) {
-		logger.Debug("post message data", zap.Any("deserialized_data", data),
+		logger.Debug("message instruction data", zap.Any("deserialized_data", msg.data),
 			zap.Stringer("signature", signature), zap.Uint64("slot", slot), zap.Int("idx", idx))
 	}
 
-	level, err := data.ConsistencyLevel.Commitment()
+	level, err := msg.consistencyLevel.Commitment()
 	if err != nil {
 		return false, fmt.Errorf("failed to determine commitment: %w", err)
 	}
@@ -701,8 +770,7 @@ func (s *SolanaWatcher) processInstruction(ctx context.Context, logger *zap.Logg
 		return true, nil
 	}
 
-	// The second account in a well-formed Wormhole instruction is the VAA program account.
-	acc := tx.Message.AccountKeys[inst.Accounts[1]]
+	acc := tx.Message.AccountKeys[inst.Accounts[msg.messageAccount]]
 
 	if logger.Level().Enabled(zapcore.DebugLevel) {
 		logger.Debug("fetching VAA account", zap.Stringer("acc", acc),
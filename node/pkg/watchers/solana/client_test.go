@@ -0,0 +1,80 @@
+package solana
+
+import (
+	"testing"
+
+	"github.com/near/borsh-go"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func instructionData(t *testing.T, id byte, data interface{}) []byte {
+	t.Helper()
+	encoded, err := borsh.Serialize(data)
+	require.NoError(t, err)
+	return append([]byte{id}, encoded...)
+}
+
+func TestDecodeMessageInstructionPostMessage(t *testing.T) {
+	for _, id := range []byte{postMessageInstructionID, postMessageUnreliableInstructionID} {
+		data := instructionData(t, id, PostMessageData{Nonce: 7, Payload: []byte{1, 2, 3}, ConsistencyLevel: consistencyLevelFinalized})
+
+		msg, err := decodeMessageInstruction(data)
+		require.NoError(t, err)
+		require.NotNil(t, msg)
+		assert.Equal(t, consistencyLevelFinalized, msg.consistencyLevel)
+		assert.Equal(t, 1, msg.messageAccount)
+	}
+}
+
+func TestDecodeMessageInstructionFinalizeMessage(t *testing.T) {
+	data := instructionData(t, finalizeMessageV1InstructionID, FinalizeMessageData{Nonce: 7, ConsistencyLevel: consistencyLevelConfirmed})
+
+	msg, err := decodeMessageInstruction(data)
+	require.NoError(t, err)
+	require.NotNil(t, msg)
+	assert.Equal(t, consistencyLevelConfirmed, msg.consistencyLevel)
+	assert.Equal(t, 1, msg.messageAccount)
+}
+
+func TestDecodeMessageInstructionForwardVAA(t *testing.T) {
+	data := instructionData(t, forwardVAAInstructionID, ForwardVAAData{Nonce: 7, TargetChain: 2, ConsistencyLevel: consistencyLevelFinalized})
+
+	msg, err := decodeMessageInstruction(data)
+	require.NoError(t, err)
+	require.NotNil(t, msg)
+	assert.Equal(t, consistencyLevelFinalized, msg.consistencyLevel)
+	assert.Equal(t, 2, msg.messageAccount)
+}
+
+func TestDecodeMessageInstructionIgnoresOtherInstructions(t *testing.T) {
+	for _, data := range [][]byte{nil, {0x00}, {0x02, 0xff}} {
+		msg, err := decodeMessageInstruction(data)
+		require.NoError(t, err)
+		assert.Nil(t, msg)
+	}
+}
+
+// Posts resolving their accounts by key are rejected by the program, named tags are never observed.
+func TestDecodeMessageInstructionIgnoresNamedAccounts(t *testing.T) {
+	data := instructionData(t, postMessageInstructionID|0x80, PostMessageData{Nonce: 7, Payload: []byte{1, 2, 3}, ConsistencyLevel: consistencyLevelFinalized})
+
+	msg, err := decodeMessageInstruction(data)
+	require.NoError(t, err)
+	assert.Nil(t, msg)
+}
+
+func TestDecodeMessageInstructionMalformed(t *testing.T) {
+	_, err := decodeMessageInstruction([]byte{postMessageInstructionID, 0x01})
+	assert.Error(t, err)
+}
+
+func TestDecodeMessageInstructionPostMessageDeterministic(t *testing.T) {
+	data := instructionData(t, postMessageDeterministicInstructionID, PostMessageDeterministicData{Seed: [32]byte{9}, Nonce: 7, Payload: []byte{1, 2, 3}, ConsistencyLevel: consistencyLevelFinalized})
+
+	msg, err := decodeMessageInstruction(data)
+	require.NoError(t, err)
+	require.NotNil(t, msg)
+	assert.Equal(t, consistencyLevelFinalized, msg.consistencyLevel)
+	assert.Equal(t, 1, msg.messageAccount)
+}
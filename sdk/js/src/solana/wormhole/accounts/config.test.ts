@@ -0,0 +1,90 @@
+import { describe, expect, test } from "@jest/globals";
+import { PublicKey } from "@solana/web3.js";
+import { BridgeData, PAUSE_EMISSION, PAUSE_VERIFICATION } from "./config";
+
+function header(): Buffer {
+  const data = Buffer.alloc(24);
+  data.writeUInt32LE(3, 0);
+  data.writeBigUInt64LE(BigInt(1000), 4);
+  data.writeUInt32LE(86400, 12);
+  data.writeBigUInt64LE(BigInt(100), 16);
+  return data;
+}
+
+describe("BridgeData", () => {
+  test("deserializes the original layout", () => {
+    const bridge = BridgeData.deserialize(header());
+    expect(bridge.guardianSetIndex).toBe(3);
+    expect(bridge.lastLamports).toBe(BigInt(1000));
+    expect(bridge.config.guardianSetExpirationTime).toBe(86400);
+    expect(bridge.config.fee).toBe(BigInt(100));
+    expect(bridge.config.quorum).toEqual({ numerator: 2, denominator: 3 });
+    expect(bridge.config.paused).toBe(0);
+    expect(bridge.config.successor.equals(PublicKey.default)).toBe(true);
+    expect(bridge.config.feeMint.equals(PublicKey.default)).toBe(true);
+  });
+
+  test("deserializes a paused bridge with a custom quorum", () => {
+    const bridge = BridgeData.deserialize(
+      Buffer.concat([header(), Buffer.from([3, 4, 1])])
+    );
+    expect(bridge.config.quorum).toEqual({ numerator: 3, denominator: 4 });
+    expect(bridge.config.paused).toBe(PAUSE_EMISSION);
+    expect(bridge.config.successor.equals(PublicKey.default)).toBe(true);
+  });
+
+  test("deserializes the paused families", () => {
+    const bridge = BridgeData.deserialize(
+      Buffer.concat([
+        header(),
+        Buffer.from([2, 3, PAUSE_EMISSION | PAUSE_VERIFICATION]),
+      ])
+    );
+    expect(bridge.config.paused).toBe(PAUSE_EMISSION | PAUSE_VERIFICATION);
+  });
+
+  test("deserializes every field", () => {
+    const successor = PublicKey.unique();
+    const claimRecipient = PublicKey.unique();
+    const feeMint = PublicKey.unique();
+    const feeTokenAccount = PublicKey.unique();
+    const numbers = Buffer.alloc(8);
+    numbers.writeUInt32LE(5, 0);
+    numbers.writeUInt32LE(600, 4);
+    const tokenFee = Buffer.alloc(8);
+    tokenFee.writeBigUInt64LE(BigInt(42));
+    const lastFeeTokens = Buffer.alloc(8);
+    lastFeeTokens.writeBigUInt64LE(BigInt(84));
+
+    const bridge = BridgeData.deserialize(
+      Buffer.concat([
+        header(),
+        Buffer.from([2, 3, 0]),
+        successor.toBuffer(),
+        numbers,
+        claimRecipient.toBuffer(),
+        feeMint.toBuffer(),
+        tokenFee,
+        feeTokenAccount.toBuffer(),
+        lastFeeTokens,
+      ])
+    );
+    const config = bridge.config;
+    expect(config.paused).toBe(0);
+    expect(config.successor.equals(successor)).toBe(true);
+    expect(config.emissionBreakerMultiple).toBe(5);
+    expect(config.claimGracePeriod).toBe(600);
+    expect(config.claimRecipient.equals(claimRecipient)).toBe(true);
+    expect(config.feeMint.equals(feeMint)).toBe(true);
+    expect(config.tokenFee).toBe(BigInt(42));
+    expect(config.feeTokenAccount.equals(feeTokenAccount)).toBe(true);
+    expect(config.lastFeeTokens).toBe(BigInt(84));
+  });
+
+  test("rejects truncated data", () => {
+    expect(() => BridgeData.deserialize(header().subarray(0, 20))).toThrow();
+    // A successor cut short.
+    const partial = Buffer.concat([header(), Buffer.from([2, 3, 0, 1])]);
+    expect(() => BridgeData.deserialize(partial)).toThrow();
+  });
+});
@@ -22,22 +22,115 @@ export async function getWormholeBridgeData(
     .then((info) => BridgeData.deserialize(getAccountData(info)));
 }
 
+export class Quorum {
+  numerator: number;
+  denominator: number;
+
+  constructor(numerator: number, denominator: number) {
+    this.numerator = numerator;
+    this.denominator = denominator;
+  }
+}
+
+// Instruction families governance can pause, bits of `BridgeConfig.paused`.
+export const PAUSE_EMISSION = 1 << 0;
+export const PAUSE_VERIFICATION = 1 << 1;
+export const PAUSE_INBOUND_TRANSFERS = 1 << 2;
+export const PAUSE_OUTBOUND_TRANSFERS = 1 << 3;
+
 export class BridgeConfig {
   guardianSetExpirationTime: number;
   fee: bigint;
 
+  // Fields appended by later versions of the core bridge. The bridge leaves
+  // out every field following the last one it set, those keep their defaults.
+  quorum: Quorum;
+  paused: number;
+  successor: PublicKey;
+  emissionBreakerMultiple: number;
+  claimGracePeriod: number;
+  claimRecipient: PublicKey;
+  feeMint: PublicKey;
+  tokenFee: bigint;
+  feeTokenAccount: PublicKey;
+  lastFeeTokens: bigint;
+
   constructor(guardianSetExpirationTime: number, fee: bigint) {
     this.guardianSetExpirationTime = guardianSetExpirationTime;
     this.fee = fee;
+    this.quorum = new Quorum(2, 3);
+    this.paused = 0;
+    this.successor = PublicKey.default;
+    this.emissionBreakerMultiple = 0;
+    this.claimGracePeriod = 0;
+    this.claimRecipient = PublicKey.default;
+    this.feeMint = PublicKey.default;
+    this.tokenFee = BigInt(0);
+    this.feeTokenAccount = PublicKey.default;
+    this.lastFeeTokens = BigInt(0);
   }
 
   static deserialize(data: Buffer): BridgeConfig {
-    if (data.length != 12) {
-      throw new Error("data.length != 12");
+    if (data.length < 12) {
+      throw new Error("data.length < 12");
     }
     const guardianSetExpirationTime = data.readUInt32LE(0);
     const fee = data.readBigUInt64LE(4);
-    return new BridgeConfig(guardianSetExpirationTime, fee);
+    const config = new BridgeConfig(guardianSetExpirationTime, fee);
+
+    // Reads past the end throw, like truncated fields do on chain.
+    let offset = 12;
+    const readPublicKey = () => {
+      if (offset + 32 > data.length) {
+        throw new RangeError("public key out of range");
+      }
+      const key = new PublicKey(data.subarray(offset, offset + 32));
+      offset += 32;
+      return key;
+    };
+    if (offset < data.length) {
+      config.quorum = new Quorum(
+        data.readUInt8(offset),
+        data.readUInt8(offset + 1)
+      );
+      offset += 2;
+    }
+    if (offset < data.length) {
+      config.paused = data.readUInt8(offset);
+      offset += 1;
+    }
+    if (offset < data.length) {
+      config.successor = readPublicKey();
+    }
+    if (offset < data.length) {
+      config.emissionBreakerMultiple = data.readUInt32LE(offset);
+      offset += 4;
+    }
+    if (offset < data.length) {
+      config.claimGracePeriod = data.readUInt32LE(offset);
+      offset += 4;
+    }
+    if (offset < data.length) {
+      config.claimRecipient = readPublicKey();
+    }
+    if (offset < data.length) {
+      config.feeMint = readPublicKey();
+    }
+    if (offset < data.length) {
+      config.tokenFee = data.readBigUInt64LE(offset);
+      offset += 8;
+    }
+    if (offset < data.length) {
+      config.feeTokenAccount = readPublicKey();
+    }
+    if (offset < data.length) {
+      config.lastFeeTokens = data.readBigUInt64LE(offset);
+      offset += 8;
+    }
+    if (offset != data.length) {
+      throw new Error("data.length > " + offset);
+    }
+    return config;
   }
 }
 
@@ -57,8 +150,8 @@ export class BridgeData {
   }
 
   static deserialize(data: Buffer): BridgeData {
-    if (data.length != 24) {
-      throw new Error("data.length != 24");
+    if (data.length < 24) {
+      throw new Error("data.length < 24");
     }
     const guardianSetIndex = data.readUInt32LE(0);
     const lastLamports = data.readBigUInt64LE(4);
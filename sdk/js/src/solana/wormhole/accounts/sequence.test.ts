@@ -0,0 +1,74 @@
+import { describe, expect, test } from "@jest/globals";
+import { SequenceTracker } from "./sequence";
+
+function u64(value: number): Buffer {
+  const data = Buffer.alloc(8);
+  data.writeBigUInt64LE(BigInt(value));
+  return data;
+}
+
+function metadata(name: string): Buffer {
+  const length = Buffer.alloc(4);
+  length.writeUInt32LE(Buffer.byteLength(name));
+  return Buffer.concat([
+    length,
+    Buffer.from(name),
+    Buffer.alloc(32, 1),
+    Buffer.alloc(32, 2),
+  ]);
+}
+
+describe("SequenceTracker", () => {
+  test("deserializes a sequence", () => {
+    const tracker = SequenceTracker.deserialize(u64(7));
+    expect(tracker.value()).toBe(BigInt(7));
+    expect(tracker.minUnreliableNonce).toBeUndefined();
+    expect(tracker.metadata).toBeUndefined();
+  });
+
+  test("deserializes a bump and emitter type", () => {
+    const tracker = SequenceTracker.deserialize(
+      Buffer.concat([u64(7), Buffer.from([254, 1])])
+    );
+    expect(tracker.value()).toBe(BigInt(7));
+    expect(tracker.bump).toBe(254);
+    expect(tracker.emitterType).toBe(1);
+  });
+
+  test("deserializes strict nonces", () => {
+    const tracker = SequenceTracker.deserialize(
+      Buffer.concat([u64(7), u64(3)])
+    );
+    expect(tracker.value()).toBe(BigInt(7));
+    expect(tracker.minUnreliableNonce).toBe(BigInt(3));
+    expect(tracker.metadata).toBeUndefined();
+  });
+
+  test("deserializes metadata", () => {
+    const tracker = SequenceTracker.deserialize(
+      Buffer.concat([u64(7), Buffer.from([0]), metadata("oracle")])
+    );
+    expect(tracker.value()).toBe(BigInt(7));
+    expect(tracker.minUnreliableNonce).toBeUndefined();
+    expect(tracker.metadata?.name).toBe("oracle");
+    expect(tracker.metadata?.urlHash).toEqual(Buffer.alloc(32, 1));
+    expect(tracker.metadata?.contactHash).toEqual(Buffer.alloc(32, 2));
+  });
+
+  test("deserializes strict nonces and metadata", () => {
+    const tracker = SequenceTracker.deserialize(
+      Buffer.concat([u64(7), Buffer.from([1]), u64(3), metadata("oracle")])
+    );
+    expect(tracker.minUnreliableNonce).toBe(BigInt(3));
+    expect(tracker.metadata?.name).toBe("oracle");
+  });
+
+  test("rejects other lengths", () => {
+    expect(() => SequenceTracker.deserialize(Buffer.alloc(9))).toThrow();
+    expect(() => SequenceTracker.deserialize(Buffer.alloc(12))).toThrow();
+    const truncated = Buffer.concat([u64(7), Buffer.from([0]), metadata("x")]);
+    expect(() =>
+      SequenceTracker.deserialize(truncated.subarray(0, truncated.length - 1))
+    ).toThrow();
+  });
+});
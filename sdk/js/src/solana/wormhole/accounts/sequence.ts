@@ -30,31 +30,71 @@ export async function getSequenceTracker(
     .then((info) => SequenceTracker.deserialize(getAccountData(info)));
 }
 
+export class EmitterMetadata {
+  name: string;
+  urlHash: Buffer;
+  contactHash: Buffer;
+
+  constructor(name: string, urlHash: Buffer, contactHash: Buffer) {
+    this.name = name;
+    this.urlHash = urlHash;
+    this.contactHash = contactHash;
+  }
+}
+
 export class SequenceTracker {
   sequence: bigint;
   bump?: number;
   emitterType?: number;
 
+  // Set once the emitter opted into strict nonces or registered its metadata,
+  // which extends the tracker.
+  minUnreliableNonce?: bigint;
+  metadata?: EmitterMetadata;
+
   constructor(sequence: bigint, bump?: number, emitterType?: number) {
     this.sequence = sequence;
     this.bump = bump;
     this.emitterType = emitterType;
   }
 
+  // Trackers are 8 bytes, 10 with a bump and emitter type, 16 with the lowest
+  // unreliable nonce, or longer with an optional nonce followed by metadata.
   static deserialize(data: Buffer): SequenceTracker {
-    if (data.length !== 8 && data.length !== 10) {
-      throw new Error("data.length != 8 or data.length != 10");
+    if (data.length !== 8 && data.length !== 10 && data.length < 16) {
+      throw new Error(`invalid data.length: ${data.length}`);
     }
 
-    let bump, emitterType;
-    const sequence = data.readBigUInt64LE(0);
-
+    const tracker = new SequenceTracker(data.readBigUInt64LE(0));
     if (data.length === 10) {
-      bump = data[8];
-      emitterType = data[9];
+      tracker.bump = data[8];
+      tracker.emitterType = data[9];
+    } else if (data.length === 16) {
+      tracker.minUnreliableNonce = data.readBigUInt64LE(8);
+    } else if (data.length > 16) {
+      let offset = 9;
+      if (data[8] === 1) {
+        tracker.minUnreliableNonce = data.readBigUInt64LE(offset);
+        offset += 8;
+      } else if (data[8] !== 0) {
+        throw new Error(`invalid option tag: ${data[8]}`);
+      }
+
+      const nameLength = data.readUInt32LE(offset);
+      offset += 4;
+      if (offset + nameLength + 64 !== data.length) {
+        throw new Error(`invalid data.length: ${data.length}`);
+      }
+      const name = data.subarray(offset, offset + nameLength).toString("utf8");
+      offset += nameLength;
+      tracker.metadata = new EmitterMetadata(
+        name,
+        data.subarray(offset, offset + 32),
+        data.subarray(offset + 32, offset + 64)
+      );
     }
 
-    return new SequenceTracker(sequence, bump, emitterType);
+    return tracker;
   }
 
   value(): bigint {
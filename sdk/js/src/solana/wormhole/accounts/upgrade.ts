@@ -6,3 +6,9 @@ export function deriveUpgradeAuthorityKey(
 ): PublicKey {
   return deriveAddress([Buffer.from("upgrade")], wormholeProgramId);
 }
+
+export function deriveVersionRegistryKey(
+  wormholeProgramId: PublicKeyInitData
+): PublicKey {
+  return deriveAddress([Buffer.from("VersionRegistry")], wormholeProgramId);
+}
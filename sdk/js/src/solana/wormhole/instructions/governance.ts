@@ -1,4 +1,5 @@
 import {
+  AccountMeta,
   PublicKey,
   PublicKeyInitData,
   SystemProgram,
@@ -20,6 +21,7 @@ import {
   deriveGuardianSetKey,
   derivePostedVaaKey,
   deriveUpgradeAuthorityKey,
+  deriveVersionRegistryKey,
 } from "../accounts";
 import { BpfLoaderUpgradeable, deriveUpgradeableProgramKey } from "../../utils";
 
@@ -195,7 +197,8 @@ export function getUpgradeGuardianSetAccounts(
 export function createUpgradeContractInstruction(
   wormholeProgramId: PublicKeyInitData,
   payer: PublicKeyInitData,
-  vaa: SignedVaa | ParsedGovernanceVaa
+  vaa: SignedVaa | ParsedGovernanceVaa,
+  spill?: PublicKeyInitData
 ): TransactionInstruction {
   const methods =
     createReadOnlyWormholeProgramInterface(
@@ -204,9 +207,17 @@ export function createUpgradeContractInstruction(
 
   // @ts-ignore
   return methods._ixFn(...methods._args, {
-    accounts: getUpgradeContractAccounts(wormholeProgramId, payer, vaa) as any,
+    accounts: getUpgradeContractAccounts(
+      wormholeProgramId,
+      payer,
+      vaa,
+      spill
+    ) as any,
     signers: undefined,
-    remainingAccounts: undefined,
+    remainingAccounts: getUpgradeContractRemainingAccounts(
+      wormholeProgramId,
+      vaa
+    ),
     preInstructions: undefined,
     postInstructions: undefined,
   });
@@ -226,8 +237,14 @@ export interface UpgradeContractAccounts {
   clock: PublicKey;
   bpfLoaderUpgradeable: PublicKey;
   systemProgram: PublicKey;
+  feeCollector: PublicKey;
 }
 
+/**
+ * The spill account receiving the lamports left in the buffer defaults to the
+ * payer, the only other spill account the core bridge accepts is its fee
+ * collector.
+ */
 export function getUpgradeContractAccounts(
   wormholeProgramId: PublicKeyInitData,
   payer: PublicKeyInitData,
@@ -235,7 +252,8 @@ export function getUpgradeContractAccounts(
   spill?: PublicKeyInitData
 ): UpgradeContractAccounts {
   const parsed = isBytes(vaa) ? parseGovernanceVaa(vaa) : vaa;
-  const implementation = parsed.orderPayload;
+  // Core upgrades may carry a version and buffer hash after the implementation.
+  const implementation = parsed.orderPayload.subarray(0, 32);
   if (implementation.length != 32) {
     throw new Error("implementation.length != 32");
   }
@@ -258,5 +276,28 @@ export function getUpgradeContractAccounts(
     clock: SYSVAR_CLOCK_PUBKEY,
     bpfLoaderUpgradeable: BpfLoaderUpgradeable.programId,
     systemProgram: SystemProgram.programId,
+    feeCollector: deriveFeeCollectorKey(wormholeProgramId),
   };
 }
+
+/**
+ * Upgrades naming a version are recorded in the version registry, which
+ * trails the other accounts.
+ */
+export function getUpgradeContractRemainingAccounts(
+  wormholeProgramId: PublicKeyInitData,
+  vaa: SignedVaa | ParsedGovernanceVaa
+): AccountMeta[] | undefined {
+  const parsed = isBytes(vaa) ? parseGovernanceVaa(vaa) : vaa;
+  const versionLength = parsed.orderPayload[32];
+  if (versionLength === undefined || versionLength === 0) {
+    return undefined;
+  }
+  return [
+    {
+      pubkey: deriveVersionRegistryKey(wormholeProgramId),
+      isSigner: false,
+      isWritable: true,
+    },
+  ];
+}
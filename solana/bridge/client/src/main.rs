@@ -61,6 +61,7 @@ use solitaire::{
     Derive,
     Info,
 };
+use wormhole_solana_consts::seeds;
 
 struct Config {
     rpc_client: RpcClient,
@@ -382,7 +383,7 @@ fn main() {
         }
         ("upgrade-authority", Some(arg_matches)) => {
             let bridge = pubkey_of(arg_matches, "bridge").unwrap();
-            let upgrade_auth = <Derive<Info<'_>, "upgrade">>::key(None, &bridge);
+            let upgrade_auth = <Derive<Info<'_>, { seeds::UPGRADE }>>::key(None, &bridge);
             println!("Upgrade Key: {}", upgrade_auth);
 
             Ok(None)
@@ -1,5 +1,7 @@
-use bridge::types::ConsistencyLevel;
-use solana_program::program::invoke;
+use bridge::{
+    sdk::cpi::PostMessageAccounts,
+    types::ConsistencyLevel,
+};
 use solitaire::{
     trace,
     *,
@@ -28,6 +30,10 @@ pub struct PostMessage<'b> {
     pub clock: Info<'b>,
 
     pub bridge_program: Info<'b>,
+
+    pub rent: Info<'b>,
+
+    pub system_program: Info<'b>,
 }
 
 #[derive(BorshDeserialize, BorshSerialize)]
@@ -43,21 +49,28 @@ pub struct PostMessageData {
 }
 
 pub fn post_message(
-    ctx: &ExecutionContext,
+    _ctx: &ExecutionContext,
     accs: &mut PostMessage,
     data: PostMessageData,
 ) -> Result<()> {
-    let ix = bridge::instructions::post_message(
-        *accs.bridge_program.key,
-        *accs.payer.key,
-        *accs.emitter.key,
-        *accs.message.key,
+    bridge::sdk::cpi::post_message(
+        &PostMessageAccounts {
+            bridge: &accs.bridge,
+            message: &accs.message,
+            emitter: &accs.emitter,
+            sequence: &accs.sequence,
+            payer: &accs.payer,
+            fee_collector: &accs.fee_collector,
+            clock: &accs.clock,
+            rent: &accs.rent,
+            system_program: &accs.system_program,
+            bridge_program: &accs.bridge_program,
+        },
         data.nonce,
         data.payload,
         data.consistency_level,
-    )
-    .unwrap();
-    invoke(&ix, ctx.accounts)?;
+        &[],
+    )?;
 
     Ok(())
 }
@@ -0,0 +1,181 @@
+//! Anchor compatible IDL for the Wormhole core bridge.
+//!
+//! The core bridge is not an Anchor program, but its instructions are encoded as a single byte
+//! discriminator (the instruction's position in the program's `solitaire!` table) followed by the
+//! Borsh encoded instruction data. Anchor IDLs allow custom discriminators, so describing every
+//! instruction with its one byte discriminator is enough for `declare_program!` to generate a CPI
+//! client that produces the exact instruction data the program expects. Posted accounts are
+//! described the same way, using their three byte magic as the account discriminator.
+//!
+//! The hand maintained legacy IDL under `solana/idl` stays the source of truth, instructions must
+//! be listed there in dispatch order.
+
+use serde_json::{
+    json,
+    Map,
+    Value,
+};
+
+/// Legacy IDL, as consumed by the JS SDK.
+pub const LEGACY_IDL: &str = include_str!("../../../idl/wormhole.json");
+
+/// Anchor program name of the core bridge.
+pub const PROGRAM_NAME: &str = "wormhole_core_bridge";
+
+/// Version of the Anchor IDL specification produced.
+pub const SPEC_VERSION: &str = "0.1.0";
+
+/// Magic prefixes of the posted account types, which serve as their account discriminators.
+const ACCOUNT_MAGIC: &[(&str, &[u8; 3])] = &[
+    ("PostedMessage", b"msg"),
+    ("PostedMessageUnreliable", b"msu"),
+    ("PostedVAA", b"vaa"),
+    ("PostedVAACompact", b"vac"),
+];
+
+/// Produce the Anchor IDL of the core bridge deployed at `address`.
+pub fn anchor_idl(address: &str) -> Value {
+    let legacy: Value = serde_json::from_str(LEGACY_IDL).expect("legacy IDL is valid JSON");
+
+    let instructions: Vec<Value> = legacy["instructions"]
+        .as_array()
+        .expect("legacy IDL lists instructions")
+        .iter()
+        .enumerate()
+        .map(|(discriminator, ix)| instruction(discriminator as u8, ix))
+        .collect();
+
+    let legacy_accounts = legacy["accounts"].as_array().cloned().unwrap_or_default();
+    let accounts: Vec<Value> = legacy_accounts
+        .iter()
+        .filter_map(|account| {
+            let name = account["name"].as_str()?;
+            let (_, magic) = ACCOUNT_MAGIC.iter().find(|(n, _)| *n == name)?;
+            Some(json!({ "name": name, "discriminator": magic.to_vec() }))
+        })
+        .collect();
+    let legacy_types = legacy["types"].as_array().cloned().unwrap_or_default();
+    let types: Vec<Value> = legacy_accounts
+        .iter()
+        .chain(legacy_types.iter())
+        .map(|account| {
+            json!({
+                "name": account["name"],
+                "type": ty(&account["type"]),
+            })
+        })
+        .collect();
+
+    json!({
+        "address": address,
+        "metadata": {
+            "name": PROGRAM_NAME,
+            "version": legacy["version"],
+            "spec": SPEC_VERSION,
+            "description": "Wormhole core bridge (legacy instruction encoding)",
+        },
+        "instructions": instructions,
+        "accounts": accounts,
+        "types": types,
+    })
+}
+
+fn instruction(discriminator: u8, ix: &Value) -> Value {
+    let accounts: Vec<Value> = ix["accounts"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default()
+        .iter()
+        .map(|account| {
+            let mut meta = Map::new();
+            meta.insert("name".into(), snake_case(&account["name"]).into());
+            if account["isMut"].as_bool().unwrap_or(false) {
+                meta.insert("writable".into(), true.into());
+            }
+            if account["isSigner"].as_bool().unwrap_or(false) {
+                meta.insert("signer".into(), true.into());
+            }
+            Value::Object(meta)
+        })
+        .collect();
+
+    json!({
+        "name": snake_case(&ix["name"]),
+        "discriminator": [discriminator],
+        "accounts": accounts,
+        "args": fields(&ix["args"]),
+    })
+}
+
+fn fields(fields: &Value) -> Vec<Value> {
+    fields
+        .as_array()
+        .cloned()
+        .unwrap_or_default()
+        .iter()
+        .map(|field| {
+            json!({
+                "name": snake_case(&field["name"]),
+                "type": ty(&field["type"]),
+            })
+        })
+        .collect()
+}
+
+/// Translate a legacy IDL type into its Anchor IDL spelling.
+fn ty(ty: &Value) -> Value {
+    match ty {
+        Value::String(s) if s == "publicKey" => "pubkey".into(),
+        Value::Object(o) => {
+            if let Some(defined) = o.get("defined") {
+                return json!({ "defined": { "name": defined } });
+            }
+            if o.get("kind").and_then(Value::as_str) == Some("struct") {
+                return json!({ "kind": "struct", "fields": fields(&o["fields"]) });
+            }
+            if o.get("kind").and_then(Value::as_str) == Some("enum") {
+                let variants: Vec<Value> = o["variants"]
+                    .as_array()
+                    .cloned()
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|variant| match variant.get("fields") {
+                        Some(f) => json!({ "name": variant["name"], "fields": fields(f) }),
+                        None => json!({ "name": variant["name"] }),
+                    })
+                    .collect();
+                return json!({ "kind": "enum", "variants": variants });
+            }
+            o.iter()
+                .map(|(k, v)| {
+                    let v = match v {
+                        // Arrays are spelled as [type, length].
+                        Value::Array(a) if k == "array" => {
+                            Value::Array(vec![self::ty(&a[0]), a[1].clone()])
+                        }
+                        _ => self::ty(v),
+                    };
+                    (k.clone(), v)
+                })
+                .collect::<Map<String, Value>>()
+                .into()
+        }
+        _ => ty.clone(),
+    }
+}
+
+fn snake_case(name: &Value) -> String {
+    let name = name.as_str().unwrap_or_default();
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
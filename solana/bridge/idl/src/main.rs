@@ -0,0 +1,11 @@
+//! Print the Anchor IDL of the core bridge deployed at the given address.
+
+use std::env;
+
+fn main() {
+    let address = env::args()
+        .nth(1)
+        .expect("usage: wormhole-core-bridge-idl <bridge address>");
+    let idl = wormhole_core_bridge_idl::anchor_idl(&address);
+    println!("{}", serde_json::to_string_pretty(&idl).unwrap());
+}
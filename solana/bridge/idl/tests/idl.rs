@@ -0,0 +1,142 @@
+use bridge::instruction::Instruction;
+use wormhole_core_bridge_idl::anchor_idl;
+
+/// Every instruction must carry the discriminator the program dispatches it on.
+#[test]
+fn discriminators_match_dispatch_order() {
+    let idl = anchor_idl("Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o");
+    let expected = [
+        ("initialize", Instruction::Initialize),
+        ("post_message", Instruction::PostMessage),
+        ("post_vaa", Instruction::PostVAA),
+        ("set_fees", Instruction::SetFees),
+        ("transfer_fees", Instruction::TransferFees),
+        ("upgrade_contract", Instruction::UpgradeContract),
+        ("upgrade_guardian_set", Instruction::UpgradeGuardianSet),
+        ("verify_signatures", Instruction::VerifySignatures),
+        ("post_message_unreliable", Instruction::PostMessageUnreliable),
+        ("set_guardian_weights", Instruction::SetGuardianWeights),
+        ("post_vaa_compact", Instruction::PostVAACompact),
+        ("enable_strict_nonce", Instruction::EnableStrictNonce),
+        ("announce_governance", Instruction::AnnounceGovernance),
+        ("register_emitter", Instruction::RegisterEmitter),
+        ("initiate_migration", Instruction::InitiateMigration),
+        ("migrate_account", Instruction::MigrateAccount),
+        ("accept_migration", Instruction::AcceptMigration),
+        ("set_guardian_set_floor", Instruction::SetGuardianSetFloor),
+        (
+            "confirm_guardian_set_downgrade",
+            Instruction::ConfirmGuardianSetDowngrade,
+        ),
+        ("set_fee_burn", Instruction::SetFeeBurn),
+        ("burn_fees", Instruction::BurnFees),
+        ("forward_vaa", Instruction::ForwardVAA),
+        (
+            "set_secp256r1_verification",
+            Instruction::SetSecp256r1Verification,
+        ),
+        ("set_fee_oracle", Instruction::SetFeeOracle),
+        ("replay_governance", Instruction::ReplayGovernance),
+        ("export_guardian_set", Instruction::ExportGuardianSet),
+        (
+            "verify_signatures_batch",
+            Instruction::VerifySignaturesBatch,
+        ),
+        (
+            "validate_upgrade_buffer",
+            Instruction::ValidateUpgradeBuffer,
+        ),
+        ("verify_vaa_inline", Instruction::VerifyVAAInline),
+        ("init_encoded_vaa", Instruction::InitEncodedVaa),
+        ("write_encoded_vaa", Instruction::WriteEncodedVaa),
+        ("finalize_encoded_vaa", Instruction::FinalizeEncodedVaa),
+        ("attest_sequence_range", Instruction::AttestSequenceRange),
+        ("close_posted_vaa", Instruction::ClosePostedVAA),
+        ("close_signature_set", Instruction::CloseSignatureSet),
+        ("set_guardian_set_expiry", Instruction::SetGuardianSetExpiry),
+        ("init_message_v1", Instruction::InitMessageV1),
+        ("write_message_v1", Instruction::WriteMessageV1),
+        ("finalize_message_v1", Instruction::FinalizeMessageV1),
+        ("set_quorum", Instruction::SetQuorum),
+        ("create_fee_invoice", Instruction::CreateFeeInvoice),
+        ("close_fee_invoice", Instruction::CloseFeeInvoice),
+        ("commit_state", Instruction::CommitState),
+        ("repair_derivations", Instruction::RepairDerivations),
+        ("set_paused", Instruction::SetPaused),
+        ("verify_vaa_cached", Instruction::VerifyVAACached),
+        ("set_successor", Instruction::SetSuccessor),
+        ("forward_to_successor", Instruction::ForwardToSuccessor),
+        ("queue_governance", Instruction::QueueGovernance),
+        ("post_message_deterministic", Instruction::PostMessageDeterministic),
+        ("render_vaa", Instruction::RenderVAA),
+        ("dry_run_guardian_set_upgrade", Instruction::DryRunGuardianSetUpgrade),
+        ("set_emission_breaker", Instruction::SetEmissionBreaker),
+        ("release_emission_breaker", Instruction::ReleaseEmissionBreaker),
+        ("clear_emission_breaker", Instruction::ClearEmissionBreaker),
+        ("set_claim_grace_period", Instruction::SetClaimGracePeriod),
+        ("close_claim", Instruction::CloseClaim),
+        ("forge_posted_vaa", Instruction::ForgePostedVAA),
+        ("set_token_fee", Instruction::SetTokenFee),
+        ("event_schemas", Instruction::EventSchemas),
+        ("set_fee_exemption", Instruction::SetFeeExemption),
+        ("set_claim_consumer", Instruction::SetClaimConsumer),
+        ("set_verifier", Instruction::SetVerifier),
+    ];
+
+    let instructions = idl["instructions"].as_array().unwrap();
+    assert_eq!(instructions.len(), expected.len());
+    for (ix, (name, variant)) in instructions.iter().zip(expected) {
+        assert_eq!(ix["name"], name);
+        assert_eq!(ix["discriminator"][0], variant as u8);
+        assert_eq!(ix["discriminator"].as_array().unwrap().len(), 1);
+    }
+}
+
+#[test]
+fn posted_accounts_use_magic_discriminators() {
+    let idl = anchor_idl("Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o");
+    let accounts = idl["accounts"].as_array().unwrap();
+    let posted_vaa = accounts.iter().find(|a| a["name"] == "PostedVAA").unwrap();
+    assert_eq!(posted_vaa["discriminator"], serde_json::json!(b"vaa".to_vec()));
+
+    // Every account is also described as a type, with Anchor type spelling.
+    let types = idl["types"].as_array().unwrap();
+    let posted_vaa = types.iter().find(|t| t["name"] == "PostedVAA").unwrap();
+    let signature_account = &posted_vaa["type"]["fields"][3];
+    assert_eq!(signature_account["name"], "vaa_signature_account");
+    assert_eq!(signature_account["type"], "pubkey");
+}
+
+#[test]
+fn events_match_schema_registry() {
+    let idl = anchor_idl("Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o");
+    let events = idl["events"].as_array().unwrap();
+    let types = idl["types"].as_array().unwrap();
+    for schema in bridge::api::event_registry() {
+        let event = events.iter().find(|e| e["name"] == schema.name).unwrap();
+        assert_eq!(
+            event["discriminator"],
+            serde_json::json!(schema.discriminator)
+        );
+
+        // The schema spells the fields of the event as the IDL describes them.
+        let event = types.iter().find(|t| t["name"] == schema.name).unwrap();
+        let fields: Vec<String> = event["type"]["fields"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|field| match &field["type"] {
+                serde_json::Value::String(ty) => {
+                    format!("{}:{}", field["name"].as_str().unwrap(), ty)
+                }
+                ty => format!(
+                    "{}:[{};{}]",
+                    field["name"].as_str().unwrap(),
+                    ty["array"][0].as_str().unwrap(),
+                    ty["array"][1]
+                ),
+            })
+            .collect();
+        assert_eq!(fields.join(","), schema.schema);
+    }
+}
@@ -0,0 +1,164 @@
+//! Fuzzes the core bridge dispatcher with raw instruction data and randomized accounts.
+//!
+//! Every input is an arbitrary instruction payload together with a list of accounts whose keys,
+//! owners, flags, lamports and data are all fuzzer controlled. Keys and owners are either random
+//! or picked from the addresses the bridge actually checks against (its PDAs, sysvars and the
+//! system program), so inputs get past the cheap account checks and into deserialization and the
+//! handlers. Errors are the expected outcome for almost every input; only panics, including
+//! arithmetic overflow, are findings.
+//!
+//! CPIs always fail, so the harness covers everything a handler does before its first
+//! invocation. Accounts sharing a key are passed as distinct `AccountInfo`s, which the runtime
+//! would have deduplicated.
+//!
+//! Run from this directory with:
+//!
+//! ```sh
+//! BRIDGE_ADDRESS=Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o cargo fuzz run dispatcher
+//! ```
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use once_cell::sync::Lazy;
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::Instruction,
+    program_error::ProgramError,
+    program_stubs::{
+        set_syscall_stubs,
+        SyscallStubs,
+    },
+    pubkey::Pubkey,
+    system_program,
+    sysvar,
+};
+use std::{
+    str::FromStr,
+    sync::Once,
+};
+use wormhole_solana_consts::seeds;
+
+/// No instruction takes more accounts than this, larger inputs only slow iterations down.
+const MAX_ACCOUNTS: usize = 16;
+
+/// Large enough for a guardian set of 255 and a maximally sized message header.
+const MAX_ACCOUNT_DATA: usize = 8192;
+
+static STUBS: Once = Once::new();
+
+static PROGRAM_ID: Lazy<Pubkey> = Lazy::new(|| Pubkey::from_str(env!("BRIDGE_ADDRESS")).unwrap());
+
+/// Addresses the dispatcher compares account keys and owners against.
+static KNOWN_KEYS: Lazy<Vec<Pubkey>> = Lazy::new(|| {
+    let pda = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &PROGRAM_ID).0;
+    vec![
+        *PROGRAM_ID,
+        pda(&[seeds::BRIDGE.as_bytes()]),
+        pda(&[seeds::FEE_COLLECTOR.as_bytes()]),
+        pda(&[seeds::GUARDIAN_SET.as_bytes(), &0u32.to_be_bytes()]),
+        pda(&[seeds::GUARDIAN_SET_WEIGHTS.as_bytes(), &0u32.to_be_bytes()]),
+        pda(&[seeds::MIGRATION.as_bytes()]),
+        pda(&[seeds::FORWARDER.as_bytes()]),
+        pda(&[seeds::SECP256R1_VERIFICATION.as_bytes()]),
+        pda(&[seeds::FEE_ORACLE.as_bytes()]),
+        sysvar::clock::id(),
+        sysvar::rent::id(),
+        sysvar::instructions::id(),
+        system_program::id(),
+    ]
+});
+
+/// Syscall stubs that keep the fuzzer output quiet and refuse every CPI, rather than pretending
+/// it succeeded and leaving handlers to continue on accounts that were never created.
+struct FuzzStubs;
+
+impl SyscallStubs for FuzzStubs {
+    fn sol_log(&self, _message: &str) {
+    }
+
+    fn sol_log_data(&self, _data: &[&[u8]]) {
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        _instruction: &Instruction,
+        _account_infos: &[AccountInfo],
+        _signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        Err(ProgramError::InvalidArgument)
+    }
+}
+
+#[derive(Arbitrary, Debug)]
+enum FuzzKey {
+    Known(u8),
+    Random([u8; 32]),
+}
+
+impl FuzzKey {
+    fn resolve(&self) -> Pubkey {
+        match self {
+            FuzzKey::Known(i) => KNOWN_KEYS[*i as usize % KNOWN_KEYS.len()],
+            FuzzKey::Random(bytes) => Pubkey::new_from_array(*bytes),
+        }
+    }
+}
+
+#[derive(Arbitrary, Debug)]
+struct FuzzAccount {
+    key: FuzzKey,
+    owner: FuzzKey,
+    is_signer: bool,
+    is_writable: bool,
+    executable: bool,
+    lamports: u64,
+    data: Vec<u8>,
+}
+
+#[derive(Arbitrary, Debug)]
+struct FuzzInput {
+    data: Vec<u8>,
+    accounts: Vec<FuzzAccount>,
+}
+
+fuzz_target!(|input: FuzzInput| {
+    STUBS.call_once(|| {
+        set_syscall_stubs(Box::new(FuzzStubs));
+    });
+
+    let accounts = &input.accounts[..input.accounts.len().min(MAX_ACCOUNTS)];
+
+    let mut storage: Vec<(Pubkey, Pubkey, u64, Vec<u8>)> = accounts
+        .iter()
+        .map(|account| {
+            let len = account.data.len().min(MAX_ACCOUNT_DATA);
+            (
+                account.key.resolve(),
+                account.owner.resolve(),
+                account.lamports,
+                account.data[..len].to_vec(),
+            )
+        })
+        .collect();
+
+    let infos: Vec<AccountInfo> = storage
+        .iter_mut()
+        .zip(accounts)
+        .map(|((key, owner, lamports, data), account)| {
+            AccountInfo::new(
+                key,
+                account.is_signer,
+                account.is_writable,
+                lamports,
+                data,
+                owner,
+                account.executable,
+                0,
+            )
+        })
+        .collect();
+
+    let _ = bridge::solitaire(&PROGRAM_ID, &infos, &input.data);
+});
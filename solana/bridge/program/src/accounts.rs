@@ -1,19 +1,67 @@
 pub mod bridge;
 pub mod claim;
+pub mod claim_consumer;
+pub mod claim_tree;
+pub mod consumed_unreliable;
+pub mod digest_cache;
+pub mod draft_message;
+pub mod emitter_stats;
+pub mod encoded_vaa;
+pub mod fee_burn;
 pub mod fee_collector;
+pub mod fee_exemption;
+pub mod fee_invoice;
+pub mod fee_oracle;
+pub mod governance_announcement;
 pub mod guardian_set;
+pub mod guardian_set_expiry;
+pub mod guardian_set_export;
+pub mod guardian_set_floor;
+pub mod guardian_set_weights;
+pub mod migration;
+pub mod pending_governance;
 pub mod posted_message;
 pub mod posted_vaa;
+pub mod posted_vaa_compact;
+pub mod secp256r1_verification;
 pub mod sequence;
+pub mod sequence_range;
 pub mod signature_set;
+pub mod state_commitment;
+pub mod verifier;
+pub mod version_registry;
 
 pub use self::{
     bridge::*,
     claim::*,
+    claim_consumer::*,
+    claim_tree::*,
+    consumed_unreliable::*,
+    digest_cache::*,
+    draft_message::*,
+    emitter_stats::*,
+    encoded_vaa::*,
+    fee_burn::*,
     fee_collector::*,
+    fee_exemption::*,
+    fee_invoice::*,
+    fee_oracle::*,
+    governance_announcement::*,
     guardian_set::*,
+    guardian_set_expiry::*,
+    guardian_set_export::*,
+    guardian_set_floor::*,
+    guardian_set_weights::*,
+    migration::*,
+    pending_governance::*,
     posted_message::*,
     posted_vaa::*,
+    posted_vaa_compact::*,
+    secp256r1_verification::*,
     sequence::*,
+    sequence_range::*,
     signature_set::*,
+    state_commitment::*,
+    verifier::*,
+    version_registry::*,
 };
@@ -1,6 +1,7 @@
 //! The Bridge account contains the main state for the wormhole bridge, as well as tracking
 //! configuration options for how the bridge should behave.
 
+use crate::accounts::Quorum;
 use borsh::{
     BorshDeserialize,
     BorshSerialize,
@@ -9,6 +10,7 @@ use serde::{
     Deserialize,
     Serialize,
 };
+use solana_program::pubkey::Pubkey;
 use solitaire::{
     AccountOwner,
     AccountState,
@@ -16,8 +18,14 @@ use solitaire::{
     Derive,
     Owned,
 };
+use std::io::{
+    Result,
+    Write,
+};
+use wormhole_solana_consts::seeds;
 
-pub type Bridge<'a, const State: AccountState> = Derive<Data<'a, BridgeData, { State }>, "Bridge">;
+pub type Bridge<'a, const State: AccountState> =
+    Derive<Data<'a, BridgeData, { State }>, { seeds::BRIDGE }>;
 
 #[derive(Clone, Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 pub struct BridgeData {
@@ -41,13 +49,12 @@ impl Owned for BridgeData {
 #[cfg(feature = "cpi")]
 impl Owned for BridgeData {
     fn owner(&self) -> AccountOwner {
-        use solana_program::pubkey::Pubkey;
         use std::str::FromStr;
         AccountOwner::Other(Pubkey::from_str(env!("BRIDGE_ADDRESS")).unwrap())
     }
 }
 
-#[derive(Clone, Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct BridgeConfig {
     /// Period for how long a guardian set is valid after it has been replaced by a new one.  This
     /// guarantees that VAAs issued by that set can still be submitted for a certain period.  In
@@ -56,4 +63,163 @@ pub struct BridgeConfig {
 
     /// Amount of lamports that needs to be paid to the protocol to post a message
     pub fee: u64,
+
+    /// Quorum of the active guardian set and those upgraded to. Bridges with the default quorum
+    /// leave it out, which keeps the original layout.
+    #[serde(default)]
+    pub quorum: Quorum,
+
+    /// Instruction families governance paused, a combination of the `PAUSE_*` bits, see
+    /// `set_paused`. Follows the quorum, unpaused bridges leave it out.
+    #[serde(default)]
+    pub paused: u8,
+
+    /// Program governance wired as the successor of the core bridge, see `forward_to_successor`.
+    /// Follows the paused flag, bridges without a successor or emission breaker leave it out.
+    #[serde(default)]
+    pub successor: Pubkey,
+
+    /// Multiple of its trailing average a registered emitter may post in an epoch before the
+    /// emission breaker trips, see `set_emission_breaker`. Follows the successor, 0 disables the
+    /// breaker and leaves it out.
+    #[serde(default)]
+    pub emission_breaker_multiple: u32,
+
+    /// Seconds after the expiry of the guardian set that signed a VAA its claim can be closed,
+    /// see `close_claim`. Follows the emission breaker, bridges that never enabled claim
+    /// collection leave it out.
+    #[serde(default)]
+    pub claim_grace_period: u32,
+
+    /// Account governance designated to receive the rent of closed claims, claims can't be closed
+    /// while it is the default pubkey. Follows the grace period.
+    #[serde(default)]
+    pub claim_recipient: Pubkey,
+
+    /// SPL mint governance designated for paying message fees in tokens, see `set_token_fee`.
+    /// Follows the claim recipient, bridges without token fees leave the token fee fields out.
+    #[serde(default)]
+    pub fee_mint: Pubkey,
+
+    /// Amount of `fee_mint` tokens that needs to be paid to post a message in tokens
+    #[serde(default)]
+    pub token_fee: u64,
+
+    /// Token account of `fee_mint` owned by the fee collector that collects token fees
+    #[serde(default)]
+    pub fee_token_account: Pubkey,
+
+    /// Tokens in the fee token account, like `last_lamports` for lamport fees
+    #[serde(default)]
+    pub last_fee_tokens: u64,
+}
+
+impl BridgeConfig {
+    /// Posting messages. Bridges paused before families could be paused separately stored `true`,
+    /// which reads as this bit.
+    pub const PAUSE_EMISSION: u8 = 1 << 0;
+
+    /// Posting VAAs, other than those of governance so that it can lift the pause.
+    pub const PAUSE_VERIFICATION: u8 = 1 << 1;
+
+    /// Redeeming token bridge transfers on Solana.
+    pub const PAUSE_INBOUND_TRANSFERS: u8 = 1 << 2;
+
+    /// Token bridge transfers out of Solana.
+    pub const PAUSE_OUTBOUND_TRANSFERS: u8 = 1 << 3;
+
+    /// Every family that can be paused.
+    pub const PAUSE_ALL: u8 = Self::PAUSE_EMISSION
+        | Self::PAUSE_VERIFICATION
+        | Self::PAUSE_INBOUND_TRANSFERS
+        | Self::PAUSE_OUTBOUND_TRANSFERS;
+
+    /// Whether governance paused any of `families`, a combination of the `PAUSE_*` bits.
+    pub fn is_paused(&self, families: u8) -> bool {
+        self.paused & families != 0
+    }
+}
+
+impl BorshSerialize for BridgeConfig {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.guardian_set_expiration_time.serialize(writer)?;
+        self.fee.serialize(writer)?;
+        let token_fees = self.fee_mint != Pubkey::default();
+        let claims =
+            self.claim_recipient != Pubkey::default() || self.claim_grace_period != 0 || token_fees;
+        let breaker = self.emission_breaker_multiple != 0 || claims;
+        let successor = self.successor != Pubkey::default() || breaker;
+        if self.quorum != Quorum::default() || self.paused != 0 || successor {
+            self.quorum.serialize(writer)?;
+        }
+        if self.paused != 0 || successor {
+            self.paused.serialize(writer)?;
+        }
+        if successor {
+            self.successor.serialize(writer)?;
+        }
+        if breaker {
+            self.emission_breaker_multiple.serialize(writer)?;
+        }
+        if claims {
+            self.claim_grace_period.serialize(writer)?;
+            self.claim_recipient.serialize(writer)?;
+        }
+        if token_fees {
+            self.fee_mint.serialize(writer)?;
+            self.token_fee.serialize(writer)?;
+            self.fee_token_account.serialize(writer)?;
+            self.last_fee_tokens.serialize(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for BridgeConfig {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        Ok(BridgeConfig {
+            guardian_set_expiration_time: u32::deserialize(buf)?,
+            fee: u64::deserialize(buf)?,
+            quorum: match buf.len() {
+                0 => Quorum::default(),
+                _ => Quorum::deserialize(buf)?,
+            },
+            paused: match buf.len() {
+                0 => 0,
+                _ => u8::deserialize(buf)?,
+            },
+            successor: match buf.len() {
+                0 => Pubkey::default(),
+                _ => Pubkey::deserialize(buf)?,
+            },
+            emission_breaker_multiple: match buf.len() {
+                0 => 0,
+                _ => u32::deserialize(buf)?,
+            },
+            claim_grace_period: match buf.len() {
+                0 => 0,
+                _ => u32::deserialize(buf)?,
+            },
+            claim_recipient: match buf.len() {
+                0 => Pubkey::default(),
+                _ => Pubkey::deserialize(buf)?,
+            },
+            fee_mint: match buf.len() {
+                0 => Pubkey::default(),
+                _ => Pubkey::deserialize(buf)?,
+            },
+            token_fee: match buf.len() {
+                0 => 0,
+                _ => u64::deserialize(buf)?,
+            },
+            fee_token_account: match buf.len() {
+                0 => Pubkey::default(),
+                _ => Pubkey::deserialize(buf)?,
+            },
+            last_fee_tokens: match buf.len() {
+                0 => 0,
+                _ => u64::deserialize(buf)?,
+            },
+        })
+    }
 }
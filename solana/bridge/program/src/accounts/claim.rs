@@ -32,7 +32,12 @@ use serde::{
     Deserialize,
     Serialize,
 };
-use solana_program::pubkey::Pubkey;
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::AccountMeta,
+    program_error::ProgramError::NotEnoughAccountKeys,
+    pubkey::Pubkey,
+};
 use solitaire::{
     processors::seeded::Seeded,
     AccountOwner,
@@ -43,14 +48,23 @@ use solitaire::{
     Result,
     *,
 };
+use std::io::Write;
 
 use crate::{
+    error::Error::{
+        ClaimPayloadTooLarge,
+        InvalidLegacyClaim,
+        LegacyClaimConsumed,
+    },
     DeserializePayload,
     PayloadMessage,
 };
 
 pub type Claim<'a> = Data<'a, ClaimData, { Uninitialized }>;
 
+/// Largest payload a consumer can store in a claim.
+pub const MAX_CLAIM_PAYLOAD_LEN: usize = 64;
+
 /// Consume a claim by initializing the account. Initialized claims act as an indicator proving
 /// that a message has been consumed.
 pub fn consume<T>(
@@ -62,6 +76,29 @@ pub fn consume<T>(
 where
     T: DeserializePayload,
 {
+    consume_with_payload(ctx, payer, claim, message, &[])
+}
+
+/// Consume a claim like [`consume`], storing `payload` in it. Consumers can record the outcome of
+/// processing the message, such as a hash of what was redeemed, and read it back from the claim
+/// later without keeping a second account.
+pub fn consume_with_payload<T>(
+    ctx: &ExecutionContext,
+    payer: &Pubkey,
+    claim: &mut Claim,
+    message: &PayloadMessage<T>,
+    payload: &[u8],
+) -> Result<()>
+where
+    T: DeserializePayload,
+{
+    if payload.len() > MAX_CLAIM_PAYLOAD_LEN {
+        return Err(ClaimPayloadTooLarge.into());
+    }
+
+    // VAAs another program verified are only redeemable once governance approved the program.
+    message.verify_owner(ctx)?;
+
     // Verify that the claim account is derived correctly before claiming.
     claim.verify_derivation(
         ctx.program_id,
@@ -72,6 +109,9 @@ where
         },
     )?;
 
+    // The account is sized to the payload when created.
+    claim.payload = payload.to_vec();
+
     // Claim the account by initializing it with a value.
     claim.create(
         &ClaimDerivationData {
@@ -89,9 +129,36 @@ where
     Ok(())
 }
 
-#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct ClaimData {
     pub claimed: bool,
+
+    /// Data stored by the consumer with [`consume_with_payload`]. Empty for claims without a
+    /// payload, which keep the original single byte layout.
+    #[serde(default)]
+    pub payload: Vec<u8>,
+}
+
+impl BorshSerialize for ClaimData {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.claimed.serialize(writer)?;
+        if !self.payload.is_empty() {
+            self.payload.serialize(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for ClaimData {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        Ok(ClaimData {
+            claimed: bool::deserialize(buf)?,
+            payload: match buf.len() {
+                0 => Vec::new(),
+                _ => Vec::<u8>::deserialize(buf)?,
+            },
+        })
+    }
 }
 
 impl Owned for ClaimData {
@@ -100,6 +167,7 @@ impl Owned for ClaimData {
     }
 }
 
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy)]
 pub struct ClaimDerivationData {
     pub emitter_address: [u8; 32],
     pub emitter_chain: u16,
@@ -115,3 +183,146 @@ impl<'b> Seeded<&ClaimDerivationData> for Claim<'b> {
         ];
     }
 }
+
+/// Claims of a consumer keeping separate replay protection per namespace, such as per emitter or
+/// per message kind. Namespaced claims are derived from the namespace followed by the seeds of the
+/// legacy claim of the same message.
+pub struct NamespacedClaimDerivationData {
+    pub namespace: [u8; 32],
+    pub claim: ClaimDerivationData,
+}
+
+impl<'b> Seeded<&NamespacedClaimDerivationData> for Claim<'b> {
+    fn seeds(data: &NamespacedClaimDerivationData) -> Vec<Vec<u8>> {
+        let mut seeds = vec![data.namespace.to_vec()];
+        seeds.extend(Self::seeds(&data.claim));
+        seeds
+    }
+}
+
+/// Consume a namespaced claim like [`consume_with_payload`]. Messages consumed with their legacy
+/// claim before the consumer namespaced its claims are rejected until the legacy claim is migrated
+/// with [`migrate`], `legacy` must be the legacy claim of the message.
+pub fn consume_namespaced<T>(
+    ctx: &ExecutionContext,
+    payer: &Pubkey,
+    claim: &mut Claim,
+    legacy: &AccountInfo,
+    message: &PayloadMessage<T>,
+    namespace: [u8; 32],
+    payload: &[u8],
+) -> Result<()>
+where
+    T: DeserializePayload,
+{
+    if payload.len() > MAX_CLAIM_PAYLOAD_LEN {
+        return Err(ClaimPayloadTooLarge.into());
+    }
+    message.verify_owner(ctx)?;
+
+    let derivation = NamespacedClaimDerivationData {
+        namespace,
+        claim: ClaimDerivationData {
+            emitter_address: message.meta().emitter_address,
+            emitter_chain: message.meta().emitter_chain,
+            sequence: message.meta().sequence,
+        },
+    };
+    let legacy_key = Claim::key(&derivation.claim, ctx.program_id);
+    if *legacy.key != legacy_key {
+        return Err(SolitaireError::InvalidDerive(*legacy.key, legacy_key));
+    }
+    if !legacy.data_is_empty() {
+        return Err(LegacyClaimConsumed.into());
+    }
+
+    claim.verify_derivation(ctx.program_id, &derivation)?;
+    claim.payload = payload.to_vec();
+    claim.create(&derivation, ctx, payer, Exempt)?;
+    claim.claimed = true;
+
+    Ok(())
+}
+
+/// Move the legacy claims of `claims` into `namespace`. Each legacy claim proves that its message
+/// was consumed: it is closed, and recreated with the same data at its namespaced derivation, paid
+/// for from the rent it held. Both claims of every message are looked up among the instruction
+/// accounts, see [`migration_accounts`].
+///
+/// Consumers must only migrate once they consume namespaced claims exclusively, a migrated message
+/// could otherwise be consumed again with its closed legacy claim.
+pub fn migrate(
+    ctx: &ExecutionContext,
+    payer: &AccountInfo,
+    namespace: [u8; 32],
+    claims: &[ClaimDerivationData],
+) -> Result<()> {
+    let find = |key: Pubkey| {
+        ctx.accounts
+            .iter()
+            .find(|info| *info.key == key)
+            .ok_or(NotEnoughAccountKeys)
+    };
+
+    for claim in claims {
+        let legacy = find(Claim::key(claim, ctx.program_id))?;
+        if legacy.owner != ctx.program_id || legacy.data_is_empty() {
+            return Err(InvalidLegacyClaim.into());
+        }
+        let data = ClaimData::try_from_slice(&legacy.data.borrow())?;
+        if !data.claimed {
+            return Err(InvalidLegacyClaim.into());
+        }
+
+        let derivation = NamespacedClaimDerivationData {
+            namespace,
+            claim: *claim,
+        };
+        let namespaced = find(Claim::key(&derivation, ctx.program_id))?;
+        if !namespaced.data_is_empty() {
+            return Err(SolitaireError::AlreadyInitialized(*namespaced.key));
+        }
+
+        // Close the legacy claim into the payer, which funds the namespaced claim.
+        **payer.lamports.borrow_mut() += legacy.lamports();
+        **legacy.lamports.borrow_mut() = 0;
+        legacy.data.borrow_mut().fill(0);
+
+        let seeds = Claim::bumped_seeds(&derivation, ctx.program_id);
+        let s: Vec<&[u8]> = seeds.iter().map(|item| item.as_slice()).collect();
+        let size = data.try_to_vec()?.len();
+        create_account(
+            ctx,
+            namespaced,
+            payer.key,
+            Exempt,
+            size,
+            ctx.program_id,
+            IsSigned::SignedWithSeeds(&[&s]),
+        )?;
+        data.serialize(&mut *namespaced.data.borrow_mut())?;
+    }
+
+    Ok(())
+}
+
+/// Legacy and namespaced claim accounts [`migrate`] looks up for `claims` of `program_id`.
+pub fn migration_accounts(
+    program_id: &Pubkey,
+    namespace: [u8; 32],
+    claims: &[ClaimDerivationData],
+) -> Vec<AccountMeta> {
+    claims
+        .iter()
+        .flat_map(|claim| {
+            let namespaced = NamespacedClaimDerivationData {
+                namespace,
+                claim: *claim,
+            };
+            vec![
+                AccountMeta::new(Claim::key(claim, program_id), false),
+                AccountMeta::new(Claim::key(&namespaced, program_id), false),
+            ]
+        })
+        .collect()
+}
@@ -0,0 +1,51 @@
+//! ClaimConsumer records that governance approved a program whose claims prove that posted VAAs
+//! were redeemed. `close_posted_vaa` only trusts claims of approved programs, as anyone can deploy
+//! a program claiming VAAs it never redeemed. Revoked approvals keep their account with `approved`
+//! cleared.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solana_program::pubkey::Pubkey;
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountOwner,
+    AccountState,
+    Data,
+    Owned,
+};
+use wormhole_solana_consts::seeds;
+
+pub type ClaimConsumer<'b, const State: AccountState> = Data<'b, ClaimConsumerData, { State }>;
+
+#[derive(Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct ClaimConsumerData {
+    /// Whether claims of the program prove redemptions.
+    pub approved: bool,
+}
+
+pub struct ClaimConsumerDerivationData<'a> {
+    pub program: &'a Pubkey,
+}
+
+impl<'a, const State: AccountState> Seeded<&ClaimConsumerDerivationData<'_>>
+    for ClaimConsumer<'a, { State }>
+{
+    fn seeds(data: &ClaimConsumerDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            seeds::CLAIM_CONSUMER.as_bytes().to_vec(),
+            data.program.to_bytes().to_vec(),
+        ]
+    }
+}
+
+impl Owned for ClaimConsumerData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
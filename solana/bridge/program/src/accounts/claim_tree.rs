@@ -0,0 +1,310 @@
+//! ClaimTree accounts add replay protection to messages without an account per message.
+//!
+//! A claim costs the rent of an account for every consumed message. Consumers redeeming many
+//! messages of an emitter can instead record them in a merkle tree per bucket of
+//! [`CLAIM_TREE_CAPACITY`] sequences, derived under the consuming program. The leaf at the
+//! position of a sequence within its bucket is zero until the message is consumed, and then the
+//! [`claim_tree_leaf`] of its digest. Consuming a message takes a proof that its leaf is still
+//! zero, and the bucket only pays rent once for all of its sequences.
+//!
+//! Proofs are built off-chain against the root the client last saw. The tree remembers its last
+//! [`CLAIM_TREE_CHANGELOG`] changes, so a proof against any of their roots is brought up to date
+//! with the leaves consumed since, rather than failing because another message of the bucket was
+//! consumed in the meantime. The changes also carry every consumed leaf, clients watching the
+//! account rebuild the tree from them, see [`claim_tree_proof`].
+//!
+//! Leaves are hashed like state commitments: nodes are the keccak256 hash of `0x01` followed by
+//! both children.
+//!
+//! Example usage:
+//!
+//! ```rust,noplayground,no_run
+//! struct ExampleAccounts {
+//!     message: PayloadMessage<'info, Example>,
+//!     tree:    Mut<ClaimTree<'info, { AccountState::MaybeInitialized }>>,
+//!     claim:   Info<'info>,
+//!     payer:   Mut<Signer<'info>>,
+//! }
+//!
+//! fn read_message(
+//!    ctx:  &ExecutionContext,
+//!    accs: &mut ExampleAccounts,
+//!    data: ExampleData,
+//! ) {
+//!    let (payer, legacy) = (accs.payer.key, &accs.claim);
+//!    consume_in_tree(ctx, payer, &mut accs.tree, legacy, &accs.message, &data.proof)?;
+//! }
+//! ```
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    keccak,
+    pubkey::Pubkey,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountOwner,
+    AccountState::*,
+    CreationLamports::Exempt,
+    Data,
+    Owned,
+    Result,
+    *,
+};
+use wormhole_solana_consts::seeds;
+
+use crate::{
+    accounts::{
+        commitment_leaf,
+        Claim,
+        ClaimDerivationData,
+    },
+    api::governance_digest,
+    error::Error::{
+        ClaimTreeLeafConsumed,
+        ClaimTreeProofInvalid,
+        LegacyClaimConsumed,
+    },
+    DeserializePayload,
+    PayloadMessage,
+};
+
+/// Depth of a claim tree, proofs hold a node per level.
+pub const CLAIM_TREE_DEPTH: usize = 14;
+
+/// Number of sequences a claim tree covers.
+pub const CLAIM_TREE_CAPACITY: u64 = 1 << CLAIM_TREE_DEPTH;
+
+/// Number of changes a claim tree remembers, proofs against older roots are rejected.
+pub const CLAIM_TREE_CHANGELOG: usize = 16;
+
+pub type ClaimTree<'a, const State: AccountState> = Data<'a, ClaimTreeData, { State }>;
+
+/// Consume a message by setting its leaf in the claim tree of its bucket, creating the tree with
+/// the first message of the bucket. `proof` holds the siblings of the leaf from the bottom up,
+/// proving that it is still zero. Fails if the message was consumed already.
+///
+/// Consumers switching from claims keep replay protection for the messages they consumed before:
+/// `legacy` must be the claim of the message, which must not exist. Once a consumer records
+/// messages in trees, it must not consume claims anymore.
+pub fn consume_in_tree<T>(
+    ctx: &ExecutionContext,
+    payer: &Pubkey,
+    tree: &mut ClaimTree<'_, { MaybeInitialized }>,
+    legacy: &AccountInfo,
+    message: &PayloadMessage<T>,
+    proof: &[[u8; 32]],
+) -> Result<()>
+where
+    T: DeserializePayload,
+{
+    message.verify_owner(ctx)?;
+
+    let meta = message.meta();
+    let legacy_key = Claim::key(
+        &ClaimDerivationData {
+            emitter_address: meta.emitter_address,
+            emitter_chain: meta.emitter_chain,
+            sequence: meta.sequence,
+        },
+        ctx.program_id,
+    );
+    if *legacy.key != legacy_key {
+        return Err(SolitaireError::InvalidDerive(*legacy.key, legacy_key));
+    }
+    if !legacy.data_is_empty() {
+        return Err(LegacyClaimConsumed.into());
+    }
+
+    let derivation_data = ClaimTreeDerivationData {
+        emitter_chain: meta.emitter_chain,
+        emitter_address: meta.emitter_address,
+        bucket: meta.sequence / CLAIM_TREE_CAPACITY,
+    };
+    tree.verify_derivation(ctx.program_id, &derivation_data)?;
+    if !tree.is_initialized() {
+        tree.root = empty_root();
+    }
+
+    let index = (meta.sequence % CLAIM_TREE_CAPACITY) as u32;
+    let leaf = claim_tree_leaf(&governance_digest(meta)?);
+    tree.consume(index, leaf, proof)?;
+
+    // The changelog grows with every message until it is full.
+    if tree.is_initialized() {
+        ensure_account_size(ctx, tree.info(), payer, tree.size())?;
+    } else {
+        tree.create(&derivation_data, ctx, payer, Exempt)?;
+    }
+
+    Ok(())
+}
+
+/// Leaf of a consumed message with `digest`, the keccak256 hash of its VAA body.
+pub fn claim_tree_leaf(digest: &[u8; 32]) -> [u8; 32] {
+    commitment_leaf(digest)
+}
+
+/// Proof for the leaf at `index` of a claim tree holding the `leaves` consumed so far, by their
+/// index, as [`consume_in_tree`] takes it.
+pub fn claim_tree_proof(leaves: &[(u32, [u8; 32])], index: u32) -> Vec<[u8; 32]> {
+    let mut level = vec![[0u8; 32]; CLAIM_TREE_CAPACITY as usize];
+    for (i, leaf) in leaves {
+        level[*i as usize] = *leaf;
+    }
+
+    let mut proof = Vec::with_capacity(CLAIM_TREE_DEPTH);
+    let mut index = index as usize;
+    while level.len() > 1 {
+        proof.push(level[index ^ 1]);
+        level = level
+            .chunks(2)
+            .map(|pair| node(&pair[0], &pair[1]))
+            .collect();
+        index /= 2;
+    }
+    proof
+}
+
+/// A leaf consumed in a claim tree.
+#[derive(Default, Clone, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+pub struct ClaimTreeChange {
+    /// Root of the tree before the change.
+    pub previous_root: [u8; 32],
+
+    /// Index of the consumed leaf.
+    pub index: u32,
+
+    /// Nodes from the consumed leaf up to the root, the leaf first and the root excluded.
+    pub path: [[u8; 32]; CLAIM_TREE_DEPTH],
+}
+
+#[derive(Default, Clone, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+pub struct ClaimTreeData {
+    /// Current root of the tree.
+    pub root: [u8; 32],
+
+    /// Number of consumed leaves.
+    pub count: u32,
+
+    /// Index of the oldest change, overwritten next once the changelog is full.
+    pub next: u8,
+
+    /// Most recent changes, oldest first until the changelog is full.
+    pub changes: Vec<ClaimTreeChange>,
+}
+
+impl ClaimTreeData {
+    /// Set the zero leaf at `index` to `leaf`, `proof` holding its siblings against the current
+    /// root or a root of the changelog.
+    fn consume(&mut self, index: u32, leaf: [u8; 32], proof: &[[u8; 32]]) -> Result<()> {
+        if proof.len() != CLAIM_TREE_DEPTH || index as u64 >= CLAIM_TREE_CAPACITY {
+            return Err(ClaimTreeProofInvalid.into());
+        }
+        let mut proof = proof.to_vec();
+
+        // Bring a proof against a previous root up to date with the changes made since, each
+        // replacing the sibling at the level its path joins the path of the leaf.
+        let root = path(index, [0u8; 32], &proof).1;
+        if root != self.root {
+            let changes = self.chronological();
+            let since = changes
+                .iter()
+                .position(|change| change.previous_root == root)
+                .ok_or(ClaimTreeProofInvalid)?;
+            for change in &changes[since..] {
+                if change.index == index {
+                    return Err(ClaimTreeLeafConsumed.into());
+                }
+                let level = (31 - (change.index ^ index).leading_zeros()) as usize;
+                proof[level] = change.path[level];
+            }
+            if path(index, [0u8; 32], &proof).1 != self.root {
+                return Err(ClaimTreeProofInvalid.into());
+            }
+        }
+
+        let (path, root) = path(index, leaf, &proof);
+        let change = ClaimTreeChange {
+            previous_root: self.root,
+            index,
+            path,
+        };
+        if self.changes.len() < CLAIM_TREE_CHANGELOG {
+            self.changes.push(change);
+        } else {
+            let next = self.next as usize % CLAIM_TREE_CHANGELOG;
+            self.changes[next] = change;
+            self.next = ((next + 1) % CLAIM_TREE_CHANGELOG) as u8;
+        }
+        self.root = root;
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Changes of the changelog, oldest first.
+    pub fn chronological(&self) -> Vec<&ClaimTreeChange> {
+        let (newer, older) = self.changes.split_at(self.next as usize);
+        older.iter().chain(newer).collect()
+    }
+}
+
+impl Owned for ClaimTreeData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+pub struct ClaimTreeDerivationData {
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+
+    /// Sequence of the messages divided by [`CLAIM_TREE_CAPACITY`].
+    pub bucket: u64,
+}
+
+impl<'b, const State: AccountState> Seeded<&ClaimTreeDerivationData> for ClaimTree<'b, { State }> {
+    fn seeds(data: &ClaimTreeDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            seeds::CLAIM_TREE.as_bytes().to_vec(),
+            data.emitter_chain.to_be_bytes().to_vec(),
+            data.emitter_address.to_vec(),
+            data.bucket.to_be_bytes().to_vec(),
+        ]
+    }
+}
+
+fn node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    keccak::hashv(&[&[1], left, right]).to_bytes()
+}
+
+/// Nodes from `leaf` at `index` up to the root, and the root.
+fn path(
+    index: u32,
+    leaf: [u8; 32],
+    proof: &[[u8; 32]],
+) -> ([[u8; 32]; CLAIM_TREE_DEPTH], [u8; 32]) {
+    let mut path = [[0u8; 32]; CLAIM_TREE_DEPTH];
+    let mut current = leaf;
+    for (level, sibling) in proof.iter().enumerate() {
+        path[level] = current;
+        current = match (index >> level) & 1 {
+            0 => node(&current, sibling),
+            _ => node(sibling, &current),
+        };
+    }
+    (path, current)
+}
+
+/// Root of a claim tree without consumed leaves.
+fn empty_root() -> [u8; 32] {
+    (0..CLAIM_TREE_DEPTH).fold([0u8; 32], |current, _| node(&current, &current))
+}
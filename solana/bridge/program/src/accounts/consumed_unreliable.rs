@@ -0,0 +1,133 @@
+//! ConsumedUnreliable accounts add best-effort replay protection to unreliable messages.
+//!
+//! Consumers of unreliable messages may not want to pay rent for a claim per message. Instead they
+//! can track the nonces they recently consumed from an emitter in a ring buffer of
+//! [`CONSUMED_UNRELIABLE_CAPACITY`] entries, derived under the consuming program. A message whose
+//! nonce is in the buffer is rejected, but once evicted a nonce can be consumed again: emitters
+//! must not reuse nonces (see `enable_strict_nonce`), and consumers must tolerate replays of
+//! messages older than the window.
+//!
+//! Example usage:
+//!
+//! ```rust,noplayground,no_run
+//! struct ExampleAccounts {
+//!     message:  PayloadMessage<'info, Example>,
+//!     consumed: Mut<ConsumedUnreliable<'info, { AccountState::MaybeInitialized }>>,
+//!     payer:    Mut<Signer<'info>>,
+//! }
+//!
+//! fn read_message(
+//!    ctx:  &ExecutionContext,
+//!    accs: &mut ExampleAccounts,
+//!    data: (),
+//! ) {
+//!    consume_unreliable(ctx, &accs.payer.key, &mut accs.consumed, &accs.message)?;
+//! }
+//! ```
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solana_program::pubkey::Pubkey;
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountOwner,
+    AccountState::*,
+    CreationLamports::Exempt,
+    Data,
+    Owned,
+    Result,
+    *,
+};
+use wormhole_solana_consts::seeds;
+
+use crate::{
+    error::Error::UnreliableMessageConsumed,
+    DeserializePayload,
+    PayloadMessage,
+};
+
+/// Number of nonces remembered per emitter.
+pub const CONSUMED_UNRELIABLE_CAPACITY: usize = 32;
+
+pub type ConsumedUnreliable<'a, const State: AccountState> =
+    Data<'a, ConsumedUnreliableData, { State }>;
+
+/// Consume an unreliable message by recording its nonce, evicting the oldest one once the buffer
+/// is full. Fails if the nonce was recently consumed.
+pub fn consume_unreliable<T>(
+    ctx: &ExecutionContext,
+    payer: &Pubkey,
+    consumed: &mut ConsumedUnreliable<'_, { MaybeInitialized }>,
+    message: &PayloadMessage<T>,
+) -> Result<()>
+where
+    T: DeserializePayload,
+{
+    message.verify_owner(ctx)?;
+
+    let derivation_data = ConsumedUnreliableDerivationData {
+        emitter_chain: message.meta().emitter_chain,
+        emitter_address: message.meta().emitter_address,
+    };
+    consumed.verify_derivation(ctx.program_id, &derivation_data)?;
+
+    let nonce = message.meta().nonce;
+    if consumed.nonces.contains(&nonce) {
+        return Err(UnreliableMessageConsumed.into());
+    }
+
+    if consumed.nonces.len() < CONSUMED_UNRELIABLE_CAPACITY {
+        consumed.nonces.push(nonce);
+    } else {
+        let next = consumed.next as usize % CONSUMED_UNRELIABLE_CAPACITY;
+        consumed.nonces[next] = nonce;
+        consumed.next = ((next + 1) % CONSUMED_UNRELIABLE_CAPACITY) as u8;
+    }
+
+    // The buffer grows with every nonce until it is full.
+    if consumed.is_initialized() {
+        ensure_account_size(ctx, consumed.info(), payer, consumed.size())?;
+    } else {
+        consumed.create(&derivation_data, ctx, payer, Exempt)?;
+    }
+
+    Ok(())
+}
+
+#[derive(Default, Clone, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+pub struct ConsumedUnreliableData {
+    /// Index of the oldest nonce, overwritten next once the buffer is full.
+    pub next: u8,
+
+    /// Recently consumed nonces.
+    pub nonces: Vec<u32>,
+}
+
+impl Owned for ConsumedUnreliableData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+pub struct ConsumedUnreliableDerivationData {
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+}
+
+impl<'b, const State: AccountState> Seeded<&ConsumedUnreliableDerivationData>
+    for ConsumedUnreliable<'b, { State }>
+{
+    fn seeds(data: &ConsumedUnreliableDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            seeds::CONSUMED_UNRELIABLE.as_bytes().to_vec(),
+            data.emitter_chain.to_be_bytes().to_vec(),
+            data.emitter_address.to_vec(),
+        ]
+    }
+}
@@ -0,0 +1,64 @@
+//! DigestCache records that a VAA body hash reached consensus of a guardian set, so that every
+//! consumer of a VAA after the first one skips signature verification, see `verify_vaa_cached`.
+//! Broadcast VAAs such as price updates are consumed by many programs each, paying for the secp
+//! instruction only once. Consumers check the account is owned by the bridge at the derivation of
+//! the hash, the way they check posted VAAs.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountOwner,
+    AccountState,
+    Data,
+    Owned,
+};
+use wormhole_solana_consts::seeds;
+
+pub type DigestCache<'b, const State: AccountState> = Data<'b, DigestCacheData, { State }>;
+
+#[derive(Default, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct DigestCacheData {
+    /// Keccak256 hash of the VAA body the guardians signed.
+    pub hash: [u8; 32],
+
+    /// Guardian set the signatures were verified against.
+    pub guardian_set_index: u32,
+
+    /// Slot of the verification.
+    pub slot: u64,
+}
+
+pub struct DigestCacheDerivationData {
+    pub hash: [u8; 32],
+}
+
+impl<'a, const State: AccountState> Seeded<&DigestCacheDerivationData>
+    for DigestCache<'a, { State }>
+{
+    fn seeds(data: &DigestCacheDerivationData) -> Vec<Vec<u8>> {
+        vec![seeds::DIGEST_CACHE.as_bytes().to_vec(), data.hash.to_vec()]
+    }
+}
+
+#[cfg(not(feature = "cpi"))]
+impl Owned for DigestCacheData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+#[cfg(feature = "cpi")]
+impl Owned for DigestCacheData {
+    fn owner(&self) -> AccountOwner {
+        use solana_program::pubkey::Pubkey;
+        use std::str::FromStr;
+        AccountOwner::Other(Pubkey::from_str(env!("BRIDGE_ADDRESS")).unwrap())
+    }
+}
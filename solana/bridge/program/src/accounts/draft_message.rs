@@ -0,0 +1,67 @@
+//! DraftMessage holds the payload of a message too large to be posted with one `post_message`,
+//! written in chunks by `write_message_v1` into an account of the emitter's choosing and posted by
+//! `finalize_message_v1`. The account is sized for the payload up front, the payload follows a
+//! fixed little endian header:
+//!
+//! | field       | size        | encoding      |
+//! |-------------+-------------+---------------|
+//! | magic       | 4           | "WDMS"        |
+//! | emitter     | 32          | pubkey        |
+//! | payload_len | 4           | little endian |
+//! | payload     | payload_len | as posted     |
+
+use crate::codec::{
+    ReadBytes,
+    WriteBytes,
+};
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use solana_program::pubkey::Pubkey;
+use std::io::{
+    Error,
+    ErrorKind::InvalidData,
+    Result,
+    Write,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DraftMessage {
+    /// Only key allowed to write and finalize the message, which is posted from it.
+    pub emitter: Pubkey,
+
+    /// Length of the payload following the header.
+    pub payload_len: u32,
+}
+
+impl DraftMessage {
+    pub const MAGIC: [u8; 4] = *b"WDMS";
+    pub const HEADER_LEN: usize = 40;
+
+    /// The payload in the data of a draft message account.
+    pub fn payload<'a>(&self, data: &'a [u8]) -> Option<&'a [u8]> {
+        data.get(Self::HEADER_LEN..Self::HEADER_LEN + self.payload_len as usize)
+    }
+}
+
+impl BorshSerialize for DraftMessage {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&Self::MAGIC)?;
+        self.emitter.serialize(writer)?;
+        writer.write_u32_le(self.payload_len)?;
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for DraftMessage {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        if buf.read_array::<4>()? != Self::MAGIC {
+            return Err(Error::new(InvalidData, "not a draft message"));
+        }
+        Ok(DraftMessage {
+            emitter: Pubkey::new_from_array(buf.read_array()?),
+            payload_len: buf.read_u32_le()?,
+        })
+    }
+}
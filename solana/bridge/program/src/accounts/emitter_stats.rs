@@ -0,0 +1,101 @@
+//! EmitterStats counts the messages a registered emitter posts per epoch, for the emission breaker
+//! governance enables with `set_emission_breaker`. An emitter whose count in an epoch exceeds the
+//! governance-set multiple of its trailing average trips the breaker, after which its posts fail
+//! until the emitter or governance clears it. See [`crate::api::clear_emission_breaker`] and
+//! [`crate::api::release_emission_breaker`].
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solana_program::pubkey::Pubkey;
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountOwner,
+    AccountState,
+    Data,
+    Owned,
+};
+use wormhole_solana_consts::seeds;
+
+/// Number of epochs the trailing average covers, and stats must have covered before the breaker
+/// can trip.
+pub const EMITTER_STATS_WINDOW: u64 = 8;
+
+pub type EmitterStats<'b, const State: AccountState> = Data<'b, EmitterStatsData, { State }>;
+
+#[derive(Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct EmitterStatsData {
+    /// Epoch `count` is for.
+    pub epoch: u64,
+
+    /// Messages posted in `epoch`.
+    pub count: u64,
+
+    /// Trailing average of the messages posted per epoch, before `epoch`.
+    pub average: u64,
+
+    /// Epochs the average covers, saturating.
+    pub epochs: u64,
+
+    /// Whether the breaker tripped, failing posts until it is cleared.
+    pub tripped: bool,
+}
+
+impl EmitterStatsData {
+    /// Count a message posted in `epoch`, tripping the breaker if the count of the epoch exceeds
+    /// `multiple` times the trailing average. Returns whether this message tripped it.
+    pub fn record(&mut self, epoch: u64, multiple: u32) -> bool {
+        if epoch > self.epoch {
+            // Epochs without messages count as empty. Averages have decayed long before eight
+            // windows passed, which bounds the loop.
+            let elapsed = epoch - self.epoch;
+            let mut count = self.count;
+            for _ in 0..elapsed.min(8 * EMITTER_STATS_WINDOW) {
+                self.average =
+                    (self.average * (EMITTER_STATS_WINDOW - 1) + count) / EMITTER_STATS_WINDOW;
+                count = 0;
+            }
+            self.epochs = self.epochs.saturating_add(elapsed);
+            self.epoch = epoch;
+            self.count = 0;
+        }
+        self.count += 1;
+
+        let limit = u64::from(multiple).saturating_mul(self.average.max(1));
+        let trips = self.epochs >= EMITTER_STATS_WINDOW && self.count > limit;
+        self.tripped |= trips;
+        trips
+    }
+
+    /// Clear the breaker, counting the current epoch afresh.
+    pub fn clear(&mut self) {
+        self.tripped = false;
+        self.count = 0;
+    }
+}
+
+pub struct EmitterStatsDerivationData<'a> {
+    pub emitter: &'a Pubkey,
+}
+
+impl<'a, const State: AccountState> Seeded<&EmitterStatsDerivationData<'_>>
+    for EmitterStats<'a, { State }>
+{
+    fn seeds(data: &EmitterStatsDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            seeds::EMITTER_STATS.as_bytes().to_vec(),
+            data.emitter.to_bytes().to_vec(),
+        ]
+    }
+}
+
+impl Owned for EmitterStatsData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
@@ -0,0 +1,84 @@
+//! EncodedVaa holds a serialized VAA too large to be posted in one transaction, uploaded in chunks
+//! by `write_encoded_vaa` into an account of the relayer's choosing. The account is sized for the
+//! VAA up front, the VAA follows a fixed little endian header:
+//!
+//! | field           | size    | encoding             |
+//! |-----------------+---------+----------------------|
+//! | magic           | 4       | "WEVA"               |
+//! | status          | 1       | [`ProcessingStatus`] |
+//! | write_authority | 32      | pubkey               |
+//! | vaa_len         | 4       | little endian        |
+//! | vaa             | vaa_len | as signed            |
+
+use crate::codec::{
+    ReadBytes,
+    WriteBytes,
+};
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use solana_program::pubkey::Pubkey;
+use std::io::{
+    Error,
+    ErrorKind::InvalidData,
+    Result,
+    Write,
+};
+
+/// Progress of an encoded VAA, only verified VAAs may be consumed.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProcessingStatus {
+    /// Zeroed account, not initialized yet.
+    Unset,
+
+    /// The write authority is uploading the VAA.
+    Writing,
+
+    /// The VAA reached consensus and can no longer be written to.
+    Verified,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EncodedVaa {
+    pub status: ProcessingStatus,
+
+    /// Only key allowed to write and finalize the VAA.
+    pub write_authority: Pubkey,
+
+    /// Length of the VAA following the header.
+    pub vaa_len: u32,
+}
+
+impl EncodedVaa {
+    pub const MAGIC: [u8; 4] = *b"WEVA";
+    pub const HEADER_LEN: usize = 41;
+
+    /// The VAA in the data of an encoded VAA account.
+    pub fn vaa(&self, data: &[u8]) -> Option<&[u8]> {
+        data.get(Self::HEADER_LEN..Self::HEADER_LEN + self.vaa_len as usize)
+    }
+}
+
+impl BorshSerialize for EncodedVaa {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&Self::MAGIC)?;
+        self.status.serialize(writer)?;
+        self.write_authority.serialize(writer)?;
+        writer.write_u32_le(self.vaa_len)?;
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for EncodedVaa {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        if buf.read_array::<4>()? != Self::MAGIC {
+            return Err(Error::new(InvalidData, "not an encoded vaa"));
+        }
+        Ok(EncodedVaa {
+            status: ProcessingStatus::deserialize(buf)?,
+            write_authority: Pubkey::new_from_array(buf.read_array()?),
+            vaa_len: buf.read_u32_le()?,
+        })
+    }
+}
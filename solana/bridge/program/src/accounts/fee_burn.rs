@@ -0,0 +1,39 @@
+//! FeeBurn records whether governance chose to burn collected message fees instead of
+//! accumulating them for [`crate::api::transfer_fees`]. While enabled, anyone can move the fees
+//! the collector holds above its rent-exempt minimum to the incinerator, see
+//! [`crate::api::burn_fees`].
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solitaire::{
+    AccountOwner,
+    AccountState,
+    Data,
+    Derive,
+    Owned,
+};
+use wormhole_solana_consts::seeds;
+
+pub type FeeBurn<'a, const State: AccountState> =
+    Derive<Data<'a, FeeBurnData, { State }>, { seeds::FEE_BURN }>;
+
+#[derive(Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct FeeBurnData {
+    /// Whether collected fees may be burned.
+    pub enabled: bool,
+
+    /// Lamports burned so far.
+    pub burned: u64,
+}
+
+impl Owned for FeeBurnData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
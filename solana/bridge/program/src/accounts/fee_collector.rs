@@ -1,8 +1,82 @@
 //! The FeeCollector is a simple account that collects SOL fees.
+//!
+//! Bridges that accept fees in tokens also collect them in a token account of the fee mint owned
+//! by the FeeCollector, see [`fee_token_balance`]. Emitters may approve the FeeCollector as the
+//! delegate of their own token account to have the fee pulled from it, see [`fee_delegator`].
 
+use solana_program::{
+    account_info::AccountInfo,
+    pubkey::Pubkey,
+};
 use solitaire::{
+    processors::seeded::Seeded,
     Derive,
     Info,
+    Result,
+    SolitaireError,
 };
+use std::str::FromStr;
+use wormhole_solana_consts::seeds;
+
+use crate::error::Error::InvalidFeeTokenAccount;
+
+pub type FeeCollector<'a> = Derive<Info<'a>, { seeds::FEE_COLLECTOR }>;
+
+/// SPL token programs holding wrapped SOL, the legacy program and token-2022.
+const TOKEN_PROGRAMS: [&str; 2] = [
+    "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+    "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb",
+];
+
+/// Length of a token account without token-2022 extensions.
+const TOKEN_ACCOUNT_LEN: usize = 165;
+
+pub(crate) fn is_token_program(key: &Pubkey) -> bool {
+    TOKEN_PROGRAMS
+        .iter()
+        .any(|program| *key == Pubkey::from_str(program).unwrap())
+}
+
+/// Balance of `account`, which must be a token account of `mint` owned by the fee collector of
+/// the bridge at `program_id`. Both token programs share the layout of the account base, the mint
+/// followed by the owner and the amount.
+pub fn fee_token_balance(account: &AccountInfo, mint: &Pubkey, program_id: &Pubkey) -> Result<u64> {
+    if !is_token_program(account.owner) {
+        return Err(SolitaireError::InvalidOwner(*account.owner));
+    }
+    let data = account.data.borrow();
+    if data.len() < TOKEN_ACCOUNT_LEN {
+        return Err(InvalidFeeTokenAccount.into());
+    }
+    let collector = FeeCollector::key(None, program_id);
+    if data[..32] != mint.to_bytes() || data[32..64] != collector.to_bytes() {
+        return Err(InvalidFeeTokenAccount.into());
+    }
+    let mut amount = [0u8; 8];
+    amount.copy_from_slice(&data[64..72]);
+    Ok(u64::from_le_bytes(amount))
+}
 
-pub type FeeCollector<'a> = Derive<Info<'a>, "fee_collector">;
+/// Owner of `account` if it is a token account of `mint` that approved the fee collector of the
+/// bridge at `program_id` as its delegate, see [`with_token_fee_delegate`]. The delegate option
+/// follows the amount in the account base.
+///
+/// [`with_token_fee_delegate`]: crate::instructions::with_token_fee_delegate
+pub(crate) fn fee_delegator(
+    account: &AccountInfo,
+    mint: &Pubkey,
+    program_id: &Pubkey,
+) -> Option<Pubkey> {
+    if !is_token_program(account.owner) {
+        return None;
+    }
+    let data = account.data.borrow();
+    if data.len() < TOKEN_ACCOUNT_LEN || data[..32] != mint.to_bytes() {
+        return None;
+    }
+    let collector = FeeCollector::key(None, program_id);
+    if data[72..76] != 1u32.to_le_bytes() || data[76..108] != collector.to_bytes() {
+        return None;
+    }
+    Some(Pubkey::new(&data[32..64]))
+}
@@ -0,0 +1,52 @@
+//! FeeExemption records that governance exempted an emitter from the message fee, for
+//! public-goods integrations such as price oracles that should post for free without the fee
+//! being zero for everyone. Posts passing the exemption of their emitter pay neither the lamport
+//! nor the token fee, see [`crate::api::set_fee_exemption`]. Revoked exemptions keep their account
+//! with `exempt` cleared.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solana_program::pubkey::Pubkey;
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountOwner,
+    AccountState,
+    Data,
+    Owned,
+};
+use wormhole_solana_consts::seeds;
+
+pub type FeeExemption<'b, const State: AccountState> = Data<'b, FeeExemptionData, { State }>;
+
+#[derive(Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct FeeExemptionData {
+    /// Whether posts of the emitter are exempted from the message fee.
+    pub exempt: bool,
+}
+
+pub struct FeeExemptionDerivationData<'a> {
+    pub emitter: &'a Pubkey,
+}
+
+impl<'a, const State: AccountState> Seeded<&FeeExemptionDerivationData<'_>>
+    for FeeExemption<'a, { State }>
+{
+    fn seeds(data: &FeeExemptionDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            seeds::FEE_EXEMPTION.as_bytes().to_vec(),
+            data.emitter.to_bytes().to_vec(),
+        ]
+    }
+}
+
+impl Owned for FeeExemptionData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
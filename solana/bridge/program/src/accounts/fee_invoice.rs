@@ -0,0 +1,80 @@
+//! FeeInvoice quotes the fee of a pending message, so that relayers can offer a fixed price even if
+//! governance or the fee oracle change the fee before the message is posted. Invoices are derived
+//! from the message account, which signs their creation, and bind the payload length and
+//! consistency level of the message. Until the invoice expires, posts passing it pay the quoted fee
+//! instead of the current one, see [`crate::api::create_fee_invoice`].
+
+use crate::types::ConsistencyLevel;
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solana_program::pubkey::Pubkey;
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountOwner,
+    AccountState,
+    Data,
+    Owned,
+};
+use wormhole_solana_consts::seeds;
+
+/// Number of slots an invoice may be valid for, about an hour.
+pub const FEE_INVOICE_MAX_SLOTS: u64 = 9_000;
+
+pub type FeeInvoice<'b, const State: AccountState> = Data<'b, FeeInvoiceData, { State }>;
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct FeeInvoiceData {
+    /// Creator of the invoice, refunded its rent once it expired.
+    pub creator: Pubkey,
+
+    /// Length of the payload of the message.
+    pub payload_len: u32,
+
+    /// Consistency level of the message.
+    pub consistency_level: ConsistencyLevel,
+
+    /// Fee charged for the message, in lamports.
+    pub fee: u64,
+
+    /// Last slot the fee is charged at.
+    pub expiry_slot: u64,
+}
+
+impl Default for FeeInvoiceData {
+    fn default() -> Self {
+        FeeInvoiceData {
+            creator: Pubkey::default(),
+            payload_len: 0,
+            consistency_level: ConsistencyLevel::Finalized,
+            fee: 0,
+            expiry_slot: 0,
+        }
+    }
+}
+
+pub struct FeeInvoiceDerivationData<'a> {
+    pub message: &'a Pubkey,
+}
+
+impl<'a, const State: AccountState> Seeded<&FeeInvoiceDerivationData<'_>>
+    for FeeInvoice<'a, { State }>
+{
+    fn seeds(data: &FeeInvoiceDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            seeds::FEE_INVOICE.as_bytes().to_vec(),
+            data.message.to_bytes().to_vec(),
+        ]
+    }
+}
+
+impl Owned for FeeInvoiceData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
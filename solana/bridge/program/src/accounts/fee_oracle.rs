@@ -0,0 +1,52 @@
+//! FeeOracle references the account message fees track when the bridge is built with the
+//! `fee-oracle` feature, together with the governance-set bounds the fee is clamped to. The oracle
+//! account, maintained by a priority-fee or congestion feed, holds the fee it suggests in lamports
+//! as a little-endian u64 at the start of its data. See [`crate::api::post_message`].
+
+use crate::codec::ReadBytes;
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solana_program::pubkey::Pubkey;
+use solitaire::{
+    AccountOwner,
+    AccountState,
+    Data,
+    Derive,
+    Owned,
+};
+use wormhole_solana_consts::seeds;
+
+pub type FeeOracle<'a, const State: AccountState> =
+    Derive<Data<'a, FeeOracleData, { State }>, { seeds::FEE_ORACLE }>;
+
+#[derive(Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct FeeOracleData {
+    /// Account holding the suggested fee, the default pubkey while dynamic fees are disabled.
+    pub oracle: Pubkey,
+
+    /// Lowest fee charged, in lamports.
+    pub min_fee: u64,
+
+    /// Highest fee charged, in lamports.
+    pub max_fee: u64,
+}
+
+impl FeeOracleData {
+    /// Fee of a message given the data of the oracle account.
+    pub fn fee(&self, oracle_data: &[u8]) -> Option<u64> {
+        let suggested = (&oracle_data[..]).read_u64_le().ok()?;
+        Some(suggested.clamp(self.min_fee, self.max_fee))
+    }
+}
+
+impl Owned for FeeOracleData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
@@ -0,0 +1,66 @@
+//! GovernanceAnnouncement records that the digest of a governance VAA body was published on-chain.
+//! With the `governance-announcement` feature enabled, governance VAAs can only be executed once
+//! their digest has been announced for at least [`GOVERNANCE_ANNOUNCEMENT_DELAY`] slots, giving
+//! an early warning window before upgrades and guardian set changes take effect.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountOwner,
+    AccountState,
+    Data,
+    Owned,
+};
+use wormhole_solana_consts::seeds;
+
+/// Number of slots an announced governance VAA has to wait before it can be executed, roughly a
+/// day at 400ms slots.
+pub const GOVERNANCE_ANNOUNCEMENT_DELAY: u64 = 216_000;
+
+pub type GovernanceAnnouncement<'b, const State: AccountState> =
+    Data<'b, GovernanceAnnouncementData, { State }>;
+
+#[derive(Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct GovernanceAnnouncementData {
+    /// Keccak256 digest of the announced VAA body.
+    pub digest: [u8; 32],
+
+    /// Slot the digest was announced at.
+    pub slot: u64,
+}
+
+/// GovernanceAnnouncement account PDAs are indexed by the digest they announce.
+pub struct GovernanceAnnouncementDerivationData {
+    pub digest: [u8; 32],
+}
+
+impl<'a, const State: AccountState> Seeded<&GovernanceAnnouncementDerivationData>
+    for GovernanceAnnouncement<'a, { State }>
+{
+    fn seeds(data: &GovernanceAnnouncementDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            seeds::GOVERNANCE_ANNOUNCEMENT.as_bytes().to_vec(),
+            data.digest.to_vec(),
+        ]
+    }
+}
+
+impl GovernanceAnnouncementData {
+    /// Whether the announcement delay has passed at `slot`.
+    pub fn matured(&self, slot: u64) -> bool {
+        slot >= self.slot.saturating_add(GOVERNANCE_ANNOUNCEMENT_DELAY)
+    }
+}
+
+impl Owned for GovernanceAnnouncementData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
@@ -1,7 +1,10 @@
 //! GuardianSet represents an account containing information about the current active guardians
 //! responsible for signing wormhole VAAs.
 
-use crate::types::GuardianPublicKey;
+use crate::types::{
+    GuardianPublicKey,
+    Secp256r1PublicKey,
+};
 use borsh::{
     BorshDeserialize,
     BorshSerialize,
@@ -10,6 +13,7 @@ use serde::{
     Deserialize,
     Serialize,
 };
+use sha3::Digest;
 use solitaire::{
     processors::seeded::Seeded,
     AccountOwner,
@@ -17,10 +21,15 @@ use solitaire::{
     Data,
     Owned,
 };
+use std::io::{
+    Result,
+    Write,
+};
+use wormhole_solana_consts::seeds;
 
 pub type GuardianSet<'b, const State: AccountState> = Data<'b, GuardianSetData, { State }>;
 
-#[derive(Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct GuardianSetData {
     /// Index representing an incrementing version number for this guardian set.
     pub index: u32,
@@ -33,6 +42,98 @@ pub struct GuardianSetData {
 
     /// Expiration time when VAAs issued by this set are no longer valid.
     pub expiration_time: u32,
+
+    /// Secp256r1 keys indexed like `keys`, zeroed for guardians signing with secp256k1. Empty for
+    /// sets without secp256r1 guardians, which keeps the original layout. The corresponding entry
+    /// of `keys` holds the [`secp256r1_address`] of the key.
+    #[serde(skip)]
+    pub secp256r1_keys: Vec<Secp256r1PublicKey>,
+
+    /// Fraction of the set that must sign, taken from the bridge config when the set was created.
+    /// Sets with the default quorum leave it out, which keeps the original layout.
+    #[serde(default)]
+    pub quorum: Quorum,
+}
+
+/// Fraction of a guardian set that must sign for consensus, more than `numerator / denominator`
+/// of its guardians. Two thirds unless governance changed it with `set_quorum`.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct Quorum {
+    pub numerator: u8,
+    pub denominator: u8,
+}
+
+impl Default for Quorum {
+    fn default() -> Self {
+        Quorum {
+            numerator: 2,
+            denominator: 3,
+        }
+    }
+}
+
+impl Quorum {
+    /// Number of signatures required of a set of `len` guardians. This calculation is in expanded
+    /// form to ease auditing, it is exact for sets of up to `MAX_GUARDIANS`.
+    pub fn required(&self, len: usize) -> usize {
+        let len = len * self.numerator as usize;
+        // Integer division rounds down, one more signature is needed to exceed the fraction.
+        len / self.denominator as usize + 1
+    }
+
+    /// Whether governance may set the quorum: at least half of the set, so that no two disjoint
+    /// halves of the guardians reach consensus, and less than the whole set, so that every set can.
+    pub fn is_valid(&self) -> bool {
+        self.numerator < self.denominator && 2 * self.numerator as u16 >= self.denominator as u16
+    }
+}
+
+impl BorshSerialize for GuardianSetData {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.index.serialize(writer)?;
+        self.keys.serialize(writer)?;
+        self.creation_time.serialize(writer)?;
+        self.expiration_time.serialize(writer)?;
+        // A custom quorum follows the secp256r1 keys, which are then written even if empty.
+        let custom_quorum = self.quorum != Quorum::default();
+        if !self.secp256r1_keys.is_empty() || custom_quorum {
+            self.secp256r1_keys.serialize(writer)?;
+        }
+        if custom_quorum {
+            self.quorum.serialize(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for GuardianSetData {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        Ok(GuardianSetData {
+            index: u32::deserialize(buf)?,
+            keys: Vec::<GuardianPublicKey>::deserialize(buf)?,
+            creation_time: u32::deserialize(buf)?,
+            expiration_time: u32::deserialize(buf)?,
+            secp256r1_keys: match buf.len() {
+                0 => Vec::new(),
+                _ => Vec::<Secp256r1PublicKey>::deserialize(buf)?,
+            },
+            quorum: match buf.len() {
+                0 => Quorum::default(),
+                _ => Quorum::deserialize(buf)?,
+            },
+        })
+    }
+}
+
+/// 20 byte guardian address standing in for a secp256r1 key in the key list of a guardian set: the
+/// last 20 bytes of the keccak256 hash of the compressed key.
+pub fn secp256r1_address(key: &Secp256r1PublicKey) -> GuardianPublicKey {
+    let hash: [u8; 32] = sha3::Keccak256::digest(key).into();
+    let mut address = GuardianPublicKey::default();
+    address.copy_from_slice(&hash[12..]);
+    address
 }
 
 /// GuardianSet account PDAs are indexed by their version number.
@@ -45,7 +146,7 @@ impl<'a, const State: AccountState> Seeded<&GuardianSetDerivationData>
 {
     fn seeds(data: &GuardianSetDerivationData) -> Vec<Vec<u8>> {
         vec![
-            "GuardianSet".as_bytes().to_vec(),
+            seeds::GUARDIAN_SET.as_bytes().to_vec(),
             data.index.to_be_bytes().to_vec(),
         ]
     }
@@ -56,6 +157,61 @@ impl GuardianSetData {
     pub fn num_guardians(&self) -> u8 {
         self.keys.iter().filter(|v| **v != [0u8; 20]).count() as u8
     }
+
+    /// Number of signatures required to reach consensus, more than the [`Quorum`] of the set.
+    pub fn quorum(&self) -> usize {
+        self.quorum.required(self.keys.len())
+    }
+
+    /// Keccak256 hash of the concatenated keys, identifying the set to light clients.
+    pub fn keys_hash(&self) -> [u8; 32] {
+        let mut hasher = sha3::Keccak256::new();
+        for key in &self.keys {
+            hasher.update(key);
+        }
+        hasher.finalize().into()
+    }
+
+    /// Secp256r1 key of the guardian at `index`, if it signs with secp256r1.
+    pub fn secp256r1_key(&self, index: u8) -> Option<&Secp256r1PublicKey> {
+        self.secp256r1_keys
+            .get(index as usize)
+            .filter(|key| **key != [0u8; 33])
+    }
+}
+
+/// Keys that changed between two guardian sets, logged as `GuardianSetChanged` when the bridge
+/// moves to a new set. Guardians are compared by key, regardless of their position in the sets.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GuardianSetDiff {
+    /// Keys of the new set missing from the old one, in the order of the new set.
+    pub added: Vec<GuardianPublicKey>,
+
+    /// Keys of the old set missing from the new one, in the order of the old set.
+    pub removed: Vec<GuardianPublicKey>,
+
+    /// Number of keys of the new set that were also part of the old one.
+    pub retained: usize,
+}
+
+impl GuardianSetDiff {
+    pub fn new(old: &[GuardianPublicKey], new: &[GuardianPublicKey]) -> Self {
+        let added: Vec<GuardianPublicKey> = new
+            .iter()
+            .filter(|key| !old.contains(key))
+            .copied()
+            .collect();
+        let removed = old
+            .iter()
+            .filter(|key| !new.contains(key))
+            .copied()
+            .collect();
+        GuardianSetDiff {
+            retained: new.len() - added.len(),
+            added,
+            removed,
+        }
+    }
 }
 
 impl Owned for GuardianSetData {
@@ -0,0 +1,47 @@
+//! GuardianSetExpiry records how governance wants VAAs of expired guardian sets treated. Under hard
+//! expiry, the default, they are rejected once the set expired. Under soft expiry they are still
+//! accepted for `soft_window` seconds after, with a `GuardianSetExpiredAccepted` event, so that
+//! VAAs signed just before a rotation can still be delivered. Consumers that can't tolerate this
+//! insist on hard expiry with [`crate::PayloadMessage::verify_hard_expiry`].
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solitaire::{
+    AccountOwner,
+    AccountState,
+    Data,
+    Derive,
+    Owned,
+};
+use wormhole_solana_consts::seeds;
+
+pub type GuardianSetExpiry<'a, const State: AccountState> =
+    Derive<Data<'a, GuardianSetExpiryData, { State }>, { seeds::GUARDIAN_SET_EXPIRY }>;
+
+#[derive(Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct GuardianSetExpiryData {
+    /// Whether VAAs of expired guardian sets are accepted within the soft window.
+    pub soft: bool,
+
+    /// Seconds after its expiration a guardian set is still accepted under soft expiry.
+    pub soft_window: u32,
+}
+
+impl GuardianSetExpiryData {
+    /// Whether a guardian set expiring at `expiration_time` is still accepted at `now`.
+    pub fn accepts(&self, expiration_time: u32, now: i64) -> bool {
+        self.soft && expiration_time as i64 + self.soft_window as i64 >= now
+    }
+}
+
+impl Owned for GuardianSetExpiryData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
@@ -0,0 +1,77 @@
+//! GuardianSetExport is the light client view of the current guardian set, written by
+//! `export_guardian_set` into an account of the caller's choosing. External verifiers sync the
+//! guardians of the bridge with a single read of that account, without deserializing bridge state.
+//!
+//! The layout is fixed and big endian, like the payloads light clients verify on other chains:
+//!
+//! | field              | size | encoding   |
+//! |--------------------+------+------------|
+//! | magic              | 4    | "WGSX"     |
+//! | version            | 1    | 1          |
+//! | guardian_set_index | 4    | big endian |
+//! | keys_hash          | 32   | keccak256  |
+//! | expiration_time    | 4    | big endian |
+//! | slot               | 8    | big endian |
+
+use crate::codec::{
+    ReadBytes,
+    WriteBytes,
+};
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use std::io::{
+    Error,
+    ErrorKind::InvalidData,
+    Result,
+    Write,
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GuardianSetExport {
+    /// Index of the current guardian set.
+    pub guardian_set_index: u32,
+
+    /// Keccak256 hash of the concatenated 20 byte keys of the set, see
+    /// [`GuardianSetData::keys_hash`](super::GuardianSetData::keys_hash).
+    pub keys_hash: [u8; 32],
+
+    /// Expiration time of the set, zero while it does not expire.
+    pub expiration_time: u32,
+
+    /// Slot of the export.
+    pub slot: u64,
+}
+
+impl GuardianSetExport {
+    pub const MAGIC: [u8; 4] = *b"WGSX";
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 53;
+}
+
+impl BorshSerialize for GuardianSetExport {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&Self::MAGIC)?;
+        writer.write_u8(Self::VERSION)?;
+        writer.write_u32_be(self.guardian_set_index)?;
+        writer.write_all(&self.keys_hash)?;
+        writer.write_u32_be(self.expiration_time)?;
+        writer.write_u64_be(self.slot)?;
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for GuardianSetExport {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        if buf.read_array::<4>()? != Self::MAGIC || buf.read_u8()? != Self::VERSION {
+            return Err(Error::new(InvalidData, "not a guardian set export"));
+        }
+        Ok(GuardianSetExport {
+            guardian_set_index: buf.read_u32_be()?,
+            keys_hash: buf.read_array()?,
+            expiration_time: buf.read_u32_be()?,
+            slot: buf.read_u64_be()?,
+        })
+    }
+}
@@ -0,0 +1,55 @@
+//! GuardianSetFloor is the governance-configured minimum size of the guardian set. With the
+//! `guardian-set-floor` feature enabled, a guardian set upgrade that shrinks the set below the
+//! floor only takes effect once governance confirmed it with a second VAA, and
+//! [`GUARDIAN_SET_DOWNGRADE_DELAY`] slots have passed since. A compromised quorum can then no
+//! longer replace the guardian set with a handful of its own keys in a single step.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solitaire::{
+    AccountOwner,
+    AccountState,
+    Data,
+    Derive,
+    Owned,
+};
+use wormhole_solana_consts::seeds;
+
+/// Number of slots between confirming a downgrade and executing it, roughly a day at 400ms slots.
+pub const GUARDIAN_SET_DOWNGRADE_DELAY: u64 = 216_000;
+
+pub type GuardianSetFloor<'a, const State: AccountState> =
+    Derive<Data<'a, GuardianSetFloorData, { State }>, { seeds::GUARDIAN_SET_FLOOR }>;
+
+#[derive(Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct GuardianSetFloorData {
+    /// Guardian sets with fewer keys than this need a confirmed downgrade.
+    pub min_keys: u8,
+
+    /// Digest of the guardian set upgrade VAA body confirmed to go below the floor, zero if none.
+    pub confirmed_digest: [u8; 32],
+
+    /// Slot the downgrade was confirmed at.
+    pub confirmed_slot: u64,
+}
+
+impl GuardianSetFloorData {
+    /// Whether the upgrade VAA with body digest `digest` was confirmed at least
+    /// [`GUARDIAN_SET_DOWNGRADE_DELAY`] slots before `slot`.
+    pub fn downgrade_matured(&self, digest: &[u8; 32], slot: u64) -> bool {
+        self.confirmed_digest == *digest
+            && slot.saturating_sub(self.confirmed_slot) >= GUARDIAN_SET_DOWNGRADE_DELAY
+    }
+}
+
+impl Owned for GuardianSetFloorData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
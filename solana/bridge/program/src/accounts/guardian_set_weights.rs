@@ -0,0 +1,75 @@
+//! GuardianSetWeights extends a GuardianSet with a per-guardian weight, assigned by governance.
+//! With the `stake-weighted` feature enabled, VAAs signed by a weighted guardian set reach
+//! consensus by weight rather than by signature count.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountOwner,
+    AccountState,
+    Data,
+    Owned,
+};
+use wormhole_solana_consts::seeds;
+
+pub type GuardianSetWeights<'b, const State: AccountState> =
+    Data<'b, GuardianSetWeightsData, { State }>;
+
+#[derive(Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct GuardianSetWeightsData {
+    /// Index of the guardian set these weights apply to.
+    pub guardian_set_index: u32,
+
+    /// Weight of each guardian, in the same order as the guardian set keys.
+    pub weights: Vec<u64>,
+}
+
+/// GuardianSetWeights account PDAs are indexed by the version number of their guardian set.
+pub struct GuardianSetWeightsDerivationData {
+    pub index: u32,
+}
+
+impl<'a, const State: AccountState> Seeded<&GuardianSetWeightsDerivationData>
+    for GuardianSetWeights<'a, { State }>
+{
+    fn seeds(data: &GuardianSetWeightsDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            seeds::GUARDIAN_SET_WEIGHTS.as_bytes().to_vec(),
+            data.index.to_be_bytes().to_vec(),
+        ]
+    }
+}
+
+impl GuardianSetWeightsData {
+    /// Total weight of the guardian set.
+    pub fn total_weight(&self) -> u128 {
+        self.weights.iter().map(|w| *w as u128).sum()
+    }
+
+    /// Whether the guardians flagged in `signatures` carry more than two thirds of the total
+    /// weight. Flags beyond the end of the weights list carry no weight.
+    pub fn quorum_reached(&self, signatures: &[bool]) -> bool {
+        let signed: u128 = self
+            .weights
+            .iter()
+            .zip(signatures.iter())
+            .filter(|(_, signed)| **signed)
+            .map(|(w, _)| *w as u128)
+            .sum();
+
+        signed * 3 > self.total_weight() * 2
+    }
+}
+
+impl Owned for GuardianSetWeightsData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
@@ -0,0 +1,36 @@
+//! The Migration account records the successor program governance handed the core state accounts
+//! over to. Once it exists, anyone can migrate the bridge config, guardian sets and sequence
+//! trackers to the successor, see [`crate::api::migrate_account`].
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solana_program::pubkey::Pubkey;
+use solitaire::{
+    AccountOwner,
+    AccountState,
+    Data,
+    Derive,
+    Owned,
+};
+use wormhole_solana_consts::seeds;
+
+pub type Migration<'a, const State: AccountState> =
+    Derive<Data<'a, MigrationData, { State }>, { seeds::MIGRATION }>;
+
+#[derive(Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct MigrationData {
+    /// Program the core state accounts are migrated to.
+    pub successor: Pubkey,
+}
+
+impl Owned for MigrationData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
@@ -0,0 +1,66 @@
+//! PendingGovernance records that a verified governance VAA was queued for execution. With the
+//! `governance-timelock` feature enabled, governance VAAs can only be executed once they have been
+//! queued for at least [`GOVERNANCE_TIMELOCK_DELAY`] slots, giving integrators a window to react
+//! before the decree takes effect.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountOwner,
+    AccountState,
+    Data,
+    Owned,
+};
+use wormhole_solana_consts::seeds;
+
+/// Number of slots a queued governance VAA has to wait before it can be executed, roughly a day at
+/// 400ms slots.
+pub const GOVERNANCE_TIMELOCK_DELAY: u64 = 216_000;
+
+pub type PendingGovernance<'b, const State: AccountState> =
+    Data<'b, PendingGovernanceData, { State }>;
+
+#[derive(Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct PendingGovernanceData {
+    /// Keccak256 digest of the queued VAA body.
+    pub digest: [u8; 32],
+
+    /// First slot the VAA can be executed at.
+    pub unlock_slot: u64,
+}
+
+/// PendingGovernance account PDAs are indexed by the digest they queue.
+pub struct PendingGovernanceDerivationData {
+    pub digest: [u8; 32],
+}
+
+impl<'a, const State: AccountState> Seeded<&PendingGovernanceDerivationData>
+    for PendingGovernance<'a, { State }>
+{
+    fn seeds(data: &PendingGovernanceDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            seeds::PENDING_GOVERNANCE.as_bytes().to_vec(),
+            data.digest.to_vec(),
+        ]
+    }
+}
+
+impl PendingGovernanceData {
+    /// Whether the VAA can be executed at `slot`.
+    pub fn unlocked(&self, slot: u64) -> bool {
+        slot >= self.unlock_slot
+    }
+}
+
+impl Owned for PendingGovernanceData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
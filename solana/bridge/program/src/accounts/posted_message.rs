@@ -8,6 +8,7 @@ use serde::{
 };
 use solana_program::pubkey::Pubkey;
 use solitaire::{
+    processors::seeded::Seeded,
     AccountOwner,
     AccountState,
     Data,
@@ -24,6 +25,7 @@ use std::{
         DerefMut,
     },
 };
+use wormhole_solana_consts::seeds;
 
 pub type PostedMessage<'a, const State: AccountState> = Data<'a, PostedMessageData, { State }>;
 
@@ -75,6 +77,26 @@ pub struct MessageData {
     pub payload: Vec<u8>,
 }
 
+/// Messages posted with [`crate::api::post_message_deterministic`] are PDAs of their emitter and a
+/// seed it picks, instead of fresh keypairs, so that callers can derive the address ahead of time.
+/// The prefix keeps them apart from the other accounts of the program.
+pub struct PostedMessageDerivationData<'a> {
+    pub emitter: &'a Pubkey,
+    pub seed: &'a [u8; 32],
+}
+
+impl<'a, const State: AccountState> Seeded<&PostedMessageDerivationData<'_>>
+    for PostedMessage<'a, { State }>
+{
+    fn seeds(data: &PostedMessageDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            seeds::DETERMINISTIC_MESSAGE.as_bytes().to_vec(),
+            data.emitter.to_bytes().to_vec(),
+            data.seed.to_vec(),
+        ]
+    }
+}
+
 // PostedMessageData impls
 
 impl BorshSerialize for PostedMessageData {
@@ -21,6 +21,7 @@ use std::{
         DerefMut,
     },
 };
+use wormhole_solana_consts::seeds;
 
 pub type PostedVAA<'b, const State: AccountState> = Data<'b, PostedVAAData, { State }>;
 
@@ -30,7 +31,10 @@ pub struct PostedVAADerivationData {
 
 impl<'a, const State: AccountState> Seeded<&PostedVAADerivationData> for PostedVAA<'a, { State }> {
     fn seeds(data: &PostedVAADerivationData) -> Vec<Vec<u8>> {
-        vec![b"PostedVAA".to_vec(), data.payload_hash.to_vec()]
+        vec![
+            seeds::POSTED_VAA.as_bytes().to_vec(),
+            data.payload_hash.to_vec(),
+        ]
     }
 }
 
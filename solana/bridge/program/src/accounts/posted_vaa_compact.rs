@@ -0,0 +1,193 @@
+//! PostedVAACompact is a posted VAA that only stores the VAA header and a hash of its payload.
+//! Consumers supply the payload at redemption time and verify it against the stored hash, which
+//! prices the rent of a posted VAA independently of its payload size.
+
+use crate::{
+    error::Error::PayloadHashMismatch,
+    DeserializePayload,
+};
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use sha3::Digest;
+use solana_program::pubkey::Pubkey;
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountOwner,
+    AccountState,
+    Data,
+    Owned,
+    SolitaireError,
+};
+use std::{
+    io::{
+        Error,
+        ErrorKind::InvalidData,
+        Write,
+    },
+    ops::{
+        Deref,
+        DerefMut,
+    },
+};
+use wormhole_solana_consts::seeds;
+
+pub type PostedVAACompact<'b, const State: AccountState> =
+    Data<'b, PostedVAACompactData, { State }>;
+
+/// Compact VAAs are derived from the VAA body hash like full VAAs, under their own prefix so both
+/// forms of the same VAA can be posted.
+pub struct PostedVAACompactDerivationData {
+    pub payload_hash: Vec<u8>,
+}
+
+impl<'a, const State: AccountState> Seeded<&PostedVAACompactDerivationData>
+    for PostedVAACompact<'a, { State }>
+{
+    fn seeds(data: &PostedVAACompactDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            seeds::POSTED_VAA_COMPACT.as_bytes().to_vec(),
+            data.payload_hash.to_vec(),
+        ]
+    }
+}
+
+#[derive(Debug, Default, BorshSerialize, BorshDeserialize, Clone, Serialize, Deserialize)]
+pub struct CompactMessageData {
+    /// Header of the posted VAA
+    pub vaa_version: u8,
+
+    /// Level of consistency requested by the emitter
+    pub consistency_level: u8,
+
+    /// Time the vaa was submitted
+    pub vaa_time: u32,
+
+    /// Account where signatures are stored
+    pub vaa_signature_account: Pubkey,
+
+    /// Time the posted message was created
+    pub submission_time: u32,
+
+    /// Unique nonce for this message
+    pub nonce: u32,
+
+    /// Sequence number of this message
+    pub sequence: u64,
+
+    /// Emitter of the message
+    pub emitter_chain: u16,
+
+    /// Emitter of the message
+    pub emitter_address: [u8; 32],
+
+    /// Length of the message payload
+    pub payload_len: u32,
+
+    /// Keccak256 hash of the message payload
+    pub payload_hash: [u8; 32],
+}
+
+impl CompactMessageData {
+    /// Hash a payload the way it is stored in a compact VAA.
+    pub fn hash_payload(payload: &[u8]) -> [u8; 32] {
+        let mut h = sha3::Keccak256::default();
+        h.update(payload);
+        h.finalize().into()
+    }
+
+    /// Fail unless `payload` is the payload this VAA was posted with.
+    pub fn verify_payload(&self, payload: &[u8]) -> Result<(), SolitaireError> {
+        if payload.len() != self.payload_len as usize
+            || Self::hash_payload(payload) != self.payload_hash
+        {
+            return Err(PayloadHashMismatch.into());
+        }
+        Ok(())
+    }
+
+    /// Verify and parse a consumer supplied payload.
+    pub fn payload<T: DeserializePayload>(&self, payload: &[u8]) -> Result<T, SolitaireError> {
+        self.verify_payload(payload)?;
+        T::deserialize(&mut &payload[..])
+    }
+}
+
+#[repr(transparent)]
+#[derive(Default)]
+pub struct PostedVAACompactData {
+    pub message: CompactMessageData,
+}
+
+impl BorshSerialize for PostedVAACompactData {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(b"vac")?;
+        BorshSerialize::serialize(&self.message, writer)
+    }
+}
+
+impl BorshDeserialize for PostedVAACompactData {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        if buf.len() < 3 {
+            return Err(Error::new(InvalidData, "Not enough bytes"));
+        }
+
+        let expected = b"vac";
+        let magic: &[u8] = &buf[0..3];
+        if magic != expected {
+            return Err(Error::new(
+                InvalidData,
+                format!(
+                    "Magic mismatch. Expected {:?} but got {:?}",
+                    expected, magic
+                ),
+            ));
+        };
+        *buf = &buf[3..];
+        Ok(PostedVAACompactData {
+            message: <CompactMessageData as BorshDeserialize>::deserialize(buf)?,
+        })
+    }
+}
+
+impl Deref for PostedVAACompactData {
+    type Target = CompactMessageData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.message
+    }
+}
+
+impl DerefMut for PostedVAACompactData {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.message
+    }
+}
+
+impl Clone for PostedVAACompactData {
+    fn clone(&self) -> Self {
+        PostedVAACompactData {
+            message: self.message.clone(),
+        }
+    }
+}
+
+#[cfg(not(feature = "cpi"))]
+impl Owned for PostedVAACompactData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+#[cfg(feature = "cpi")]
+impl Owned for PostedVAACompactData {
+    fn owner(&self) -> AccountOwner {
+        use std::str::FromStr;
+        AccountOwner::Other(Pubkey::from_str(env!("BRIDGE_ADDRESS")).unwrap())
+    }
+}
@@ -0,0 +1,36 @@
+//! Secp256r1Verification records whether governance enabled guardian signatures verified by the
+//! secp256r1 precompile, for guardians operating HSMs that only support P-256. Only bridges built
+//! with the `secp256r1` feature accept such signatures, see
+//! [`crate::api::verify_signatures`].
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solitaire::{
+    AccountOwner,
+    AccountState,
+    Data,
+    Derive,
+    Owned,
+};
+use wormhole_solana_consts::seeds;
+
+pub type Secp256r1Verification<'a, const State: AccountState> =
+    Derive<Data<'a, Secp256r1VerificationData, { State }>, { seeds::SECP256R1_VERIFICATION }>;
+
+#[derive(Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct Secp256r1VerificationData {
+    /// Whether secp256r1 guardian signatures are accepted.
+    pub enabled: bool,
+}
+
+impl Owned for Secp256r1VerificationData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
@@ -2,6 +2,10 @@ use borsh::{
     BorshDeserialize,
     BorshSerialize,
 };
+use serde::{
+    Deserialize,
+    Serialize,
+};
 use solana_program::pubkey::Pubkey;
 use solitaire::{
     processors::seeded::Seeded,
@@ -10,12 +14,82 @@ use solitaire::{
     Data,
     Owned,
 };
+use std::io::{
+    Result,
+    Write,
+};
+use wormhole_solana_consts::seeds;
 
 pub type Sequence<'b> = Data<'b, SequenceTracker, { AccountState::MaybeInitialized }>;
 
-#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize)]
+#[derive(Default, Clone)]
 pub struct SequenceTracker {
     pub sequence: u64,
+
+    /// Lowest nonce the emitter may publish its next unreliable message with. Only present once the
+    /// emitter opted into strict nonces, in which case the account is extended to hold it.
+    pub min_unreliable_nonce: Option<u64>,
+
+    /// Metadata the emitter registered about itself, see [`EmitterMetadata`].
+    pub metadata: Option<EmitterMetadata>,
+}
+
+/// Human readable information about an emitter, registered by the emitter itself for explorers to
+/// display. Only hashes of the URL and contact are stored on-chain to keep the account small.
+#[derive(Default, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct EmitterMetadata {
+    /// Project name, at most [`MAX_EMITTER_NAME_LEN`] bytes of UTF-8.
+    pub name: String,
+
+    /// Keccak256 hash of the project URL.
+    pub url_hash: [u8; 32],
+
+    /// Keccak256 hash of the project contact.
+    pub contact_hash: [u8; 32],
+}
+
+pub const MAX_EMITTER_NAME_LEN: usize = 32;
+
+// Trackers are extended in place, so their layout depends on what they hold:
+//
+// - `sequence`, as originally created by `post_message`.
+// - `sequence | min_unreliable_nonce`, once strict nonces are enabled.
+// - `sequence | Option<min_unreliable_nonce> | metadata`, once metadata is registered.
+//
+// The metadata is always longer than 8 bytes so the extensions can't be confused.
+
+impl BorshSerialize for SequenceTracker {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.sequence.serialize(writer)?;
+        match (&self.min_unreliable_nonce, &self.metadata) {
+            (None, None) => {}
+            (Some(min_unreliable_nonce), None) => min_unreliable_nonce.serialize(writer)?,
+            (min_unreliable_nonce, Some(metadata)) => {
+                min_unreliable_nonce.serialize(writer)?;
+                metadata.serialize(writer)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for SequenceTracker {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let sequence = u64::deserialize(buf)?;
+        let (min_unreliable_nonce, metadata) = match buf.len() {
+            0 => (None, None),
+            8 => (Some(u64::deserialize(buf)?), None),
+            _ => (
+                Option::<u64>::deserialize(buf)?,
+                Some(EmitterMetadata::deserialize(buf)?),
+            ),
+        };
+        Ok(SequenceTracker {
+            sequence,
+            min_unreliable_nonce,
+            metadata,
+        })
+    }
 }
 
 pub struct SequenceDerivationData<'a> {
@@ -25,7 +99,7 @@ pub struct SequenceDerivationData<'a> {
 impl<'b> Seeded<&SequenceDerivationData<'b>> for Sequence<'b> {
     fn seeds(data: &SequenceDerivationData) -> Vec<Vec<u8>> {
         vec![
-            "Sequence".as_bytes().to_vec(),
+            seeds::SEQUENCE.as_bytes().to_vec(),
             data.emitter_key.to_bytes().to_vec(),
         ]
     }
@@ -0,0 +1,68 @@
+//! SequenceRangeAttestation proves that an emitter posted every sequence of a range, for settlement
+//! programs that must not miss a message of the range. Sequences are handed out in order by the
+//! emitter's sequence tracker, so a range is complete once the tracker moved past its last
+//! sequence. Consumers check the account is owned by the bridge at the derivation of their range.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solana_program::pubkey::Pubkey;
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountOwner,
+    AccountState,
+    Data,
+    Owned,
+};
+use wormhole_solana_consts::seeds;
+
+pub type SequenceRangeAttestation<'b, const State: AccountState> =
+    Data<'b, SequenceRangeAttestationData, { State }>;
+
+#[derive(Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct SequenceRangeAttestationData {
+    /// Emitter the sequences are of.
+    pub emitter: Pubkey,
+
+    /// First sequence of the range.
+    pub first: u64,
+
+    /// Last sequence of the range, inclusive.
+    pub last: u64,
+
+    /// Next sequence of the emitter when the range was attested.
+    pub next_sequence: u64,
+
+    /// Slot of the attestation.
+    pub slot: u64,
+}
+
+pub struct SequenceRangeDerivationData<'a> {
+    pub emitter_key: &'a Pubkey,
+    pub first: u64,
+    pub last: u64,
+}
+
+impl<'a, const State: AccountState> Seeded<&SequenceRangeDerivationData<'_>>
+    for SequenceRangeAttestation<'a, { State }>
+{
+    fn seeds(data: &SequenceRangeDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            seeds::SEQUENCE_RANGE.as_bytes().to_vec(),
+            data.emitter_key.to_bytes().to_vec(),
+            data.first.to_be_bytes().to_vec(),
+            data.last.to_be_bytes().to_vec(),
+        ]
+    }
+}
+
+impl Owned for SequenceRangeAttestationData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
@@ -4,16 +4,18 @@ use borsh::{
     BorshDeserialize,
     BorshSerialize,
 };
+use solana_program::pubkey::Pubkey;
 use solitaire::{
     AccountOwner,
     AccountState,
     Data,
     Owned,
 };
+use std::io::Write;
 
 pub type SignatureSet<'b, const State: AccountState> = Data<'b, SignatureSetData, { State }>;
 
-#[derive(Default, BorshSerialize, BorshDeserialize)]
+#[derive(Default)]
 pub struct SignatureSetData {
     /// Signatures of validators
     pub signatures: Vec<bool>,
@@ -23,6 +25,38 @@ pub struct SignatureSetData {
 
     /// Index of the guardian set
     pub guardian_set_index: u32,
+
+    /// Payer of the account, the only one allowed to close it and the VAA posted with it. Sets
+    /// created before it was recorded can't be closed.
+    pub refund_recipient: Option<Pubkey>,
+}
+
+// The refund recipient is appended without an option tag, so that sets created before it existed
+// keep their layout.
+impl BorshSerialize for SignatureSetData {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.signatures.serialize(writer)?;
+        self.hash.serialize(writer)?;
+        self.guardian_set_index.serialize(writer)?;
+        if let Some(refund_recipient) = self.refund_recipient {
+            refund_recipient.serialize(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for SignatureSetData {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        Ok(SignatureSetData {
+            signatures: Vec::<bool>::deserialize(buf)?,
+            hash: <[u8; 32]>::deserialize(buf)?,
+            guardian_set_index: u32::deserialize(buf)?,
+            refund_recipient: match buf.len() {
+                0 => None,
+                _ => Some(Pubkey::deserialize(buf)?),
+            },
+        })
+    }
 }
 
 impl Owned for SignatureSetData {
@@ -0,0 +1,104 @@
+//! StateCommitment holds the latest commitment to the bridge state, the public input of zk light
+//! clients verifying the bridge on other chains. Commitments are a merkle root over the bridge
+//! config, the active guardian set and the messages posted since the previous commitment, and are
+//! made at most every [`STATE_COMMITMENT_INTERVAL`] slots, see [`crate::api::commit_state`].
+//!
+//! Leaves are the keccak256 hash of `0x00` followed by the leaf data, nodes the hash of `0x01`
+//! followed by both children, and the last node of an odd level is carried up unchanged. The
+//! leaves are, in order:
+//!
+//! - the borsh serialization of the bridge config followed by the big endian index of the active
+//!   guardian set,
+//! - the borsh serialization of the active guardian set,
+//! - the digests of the committed messages in ascending order, each the keccak256 hash of the VAA
+//!   body guardians sign for the message.
+
+use crate::accounts::MessageData;
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solana_program::keccak;
+use solitaire::{
+    AccountOwner,
+    AccountState,
+    Data,
+    Derive,
+    Owned,
+};
+use wormhole_solana_consts::seeds;
+
+/// Slots between two commitments at least, about six minutes.
+pub const STATE_COMMITMENT_INTERVAL: u64 = 900;
+
+pub type StateCommitment<'a, const State: AccountState> =
+    Derive<Data<'a, StateCommitmentData, { State }>, { seeds::STATE_COMMITMENT }>;
+
+#[derive(Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct StateCommitmentData {
+    /// Number of the commitment, counting from zero.
+    pub index: u64,
+
+    /// Merkle root of the committed state.
+    pub root: [u8; 32],
+
+    /// Root of the previous commitment, chaining commitments together.
+    pub previous_root: [u8; 32],
+
+    /// Slot of the commitment.
+    pub slot: u64,
+
+    /// Unix timestamp of the commitment, the next one commits the messages posted from then on.
+    pub timestamp: u32,
+
+    /// Index of the committed guardian set.
+    pub guardian_set_index: u32,
+
+    /// Number of committed messages.
+    pub message_count: u32,
+}
+
+impl Owned for StateCommitmentData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+/// Leaf of `data` in a state commitment.
+pub fn commitment_leaf(data: &[u8]) -> [u8; 32] {
+    keccak::hashv(&[&[0], data]).to_bytes()
+}
+
+/// Merkle root of the leaves of a state commitment.
+pub fn commitment_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => keccak::hashv(&[&[1], left, right]).to_bytes(),
+                [node] => *node,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    level.first().copied().unwrap_or_default()
+}
+
+/// Keccak256 digest of the VAA body guardians sign for `message`, timestamped with its submission.
+pub fn message_digest(message: &MessageData) -> [u8; 32] {
+    keccak::hashv(&[
+        &message.submission_time.to_be_bytes(),
+        &message.nonce.to_be_bytes(),
+        &message.emitter_chain.to_be_bytes(),
+        &message.emitter_address,
+        &message.sequence.to_be_bytes(),
+        &[message.consistency_level],
+        &message.payload,
+    ])
+    .to_bytes()
+}
@@ -0,0 +1,51 @@
+//! Verifier records that governance approved a program verifying VAAs in place of the bridge.
+//! Accounts the program owns in the layout of posted VAAs are accepted as posted VAAs once the
+//! approval is passed along, see [`PayloadMessage`](crate::PayloadMessage). Revoked approvals
+//! keep their account with `approved` cleared.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solana_program::pubkey::Pubkey;
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountOwner,
+    AccountState,
+    Data,
+    Owned,
+};
+use wormhole_solana_consts::seeds;
+
+pub type Verifier<'b, const State: AccountState> = Data<'b, VerifierData, { State }>;
+
+#[derive(Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct VerifierData {
+    /// Whether VAAs the program verified are accepted.
+    pub approved: bool,
+}
+
+pub struct VerifierDerivationData<'a> {
+    pub program: &'a Pubkey,
+}
+
+impl<'a, const State: AccountState> Seeded<&VerifierDerivationData<'_>>
+    for Verifier<'a, { State }>
+{
+    fn seeds(data: &VerifierDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            seeds::VERIFIER.as_bytes().to_vec(),
+            data.program.to_bytes().to_vec(),
+        ]
+    }
+}
+
+impl Owned for VerifierData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
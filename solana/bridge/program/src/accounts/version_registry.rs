@@ -0,0 +1,101 @@
+//! VersionRegistry records the versions the core bridge was upgraded to. Contract upgrade decrees
+//! may carry a semantic version, and every upgrade passed the registry appends that version
+//! together with the hash of the deployed program to it. Upgrades naming a version must pass it.
+//! Programs calling into the bridge can read the registry to gate behaviour on a minimum bridge
+//! version, see [`VersionRegistryData::at_least`].
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solitaire::{
+    AccountOwner,
+    AccountState,
+    Data,
+    Derive,
+    Owned,
+};
+use wormhole_solana_consts::seeds;
+
+/// Longest version string a contract upgrade decree may carry.
+pub const MAX_VERSION_LEN: usize = 32;
+
+pub type VersionRegistry<'a, const State: AccountState> =
+    Derive<Data<'a, VersionRegistryData, { State }>, { seeds::VERSION_REGISTRY }>;
+
+#[derive(Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct VersionRegistryData {
+    /// Every upgrade processed since the registry was created, oldest first.
+    pub versions: Vec<ProgramVersion>,
+}
+
+#[derive(Clone, Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct ProgramVersion {
+    /// `MAJOR.MINOR.PATCH` version of the upgrade, empty if the decree carried none.
+    pub version: String,
+
+    /// Keccak256 of the executable bytes of the program data account after the upgrade, the bytes
+    /// `solana program dump` writes out.
+    pub program_data_hash: [u8; 32],
+
+    /// Slot the upgrade was processed at.
+    pub slot: u64,
+}
+
+impl VersionRegistryData {
+    /// The version currently deployed, if any upgrade was recorded.
+    pub fn current(&self) -> Option<&ProgramVersion> {
+        self.versions.last()
+    }
+
+    /// Hash of the program data registered for `version`.
+    pub fn program_data_hash(&self, version: &str) -> Option<[u8; 32]> {
+        self.versions
+            .iter()
+            .rev()
+            .find(|entry| entry.version == version)
+            .map(|entry| entry.program_data_hash)
+    }
+
+    /// Whether the currently deployed version is at least `minimum`. Deployments without a
+    /// version, or with one that doesn't parse, never satisfy a minimum.
+    pub fn at_least(&self, minimum: &str) -> bool {
+        match (
+            self.current()
+                .and_then(|entry| parse_version(&entry.version)),
+            parse_version(minimum),
+        ) {
+            (Some(current), Some(minimum)) => current >= minimum,
+            _ => false,
+        }
+    }
+}
+
+/// Parse a `MAJOR.MINOR.PATCH` version into its components, which order like the versions do.
+pub fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let mut parts = version.split('.').map(|part| {
+        // Leading signs and zeros would give a version several spellings.
+        if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        if part.len() > 1 && part.starts_with('0') {
+            return None;
+        }
+        part.parse::<u64>().ok()
+    });
+    let version = (parts.next()??, parts.next()??, parts.next()??);
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(version)
+}
+
+impl Owned for VersionRegistryData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
@@ -1,11 +1,49 @@
+pub mod attest_sequence_range;
+pub mod burn_fees;
+pub mod close;
+pub mod commit_state;
+pub mod draft_message;
+pub mod dry_run_guardian_set_upgrade;
+pub mod emission_breaker;
+pub mod encoded_vaa;
+pub mod event_schemas;
+pub mod export_guardian_set;
+pub mod fee_invoice;
+pub mod forge_posted_vaa;
+pub mod forward_vaa;
 pub mod governance;
 pub mod initialize;
+pub mod migration;
 pub mod post_message;
 pub mod post_vaa;
+pub mod post_vaa_compact;
+pub mod register_emitter;
+pub mod render_vaa;
+pub mod repair_derivations;
+pub mod validate_upgrade_buffer;
 pub mod verify_signature;
 
+pub use attest_sequence_range::*;
+pub use burn_fees::*;
+pub use close::*;
+pub use commit_state::*;
+pub use draft_message::*;
+pub use dry_run_guardian_set_upgrade::*;
+pub use emission_breaker::*;
+pub use encoded_vaa::*;
+pub use event_schemas::*;
+pub use export_guardian_set::*;
+pub use fee_invoice::*;
+pub use forge_posted_vaa::*;
+pub use forward_vaa::*;
 pub use governance::*;
 pub use initialize::*;
+pub use migration::*;
 pub use post_message::*;
 pub use post_vaa::*;
+pub use post_vaa_compact::*;
+pub use register_emitter::*;
+pub use render_vaa::*;
+pub use repair_derivations::*;
+pub use validate_upgrade_buffer::*;
 pub use verify_signature::*;
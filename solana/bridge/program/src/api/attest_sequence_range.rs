@@ -0,0 +1,83 @@
+use crate::{
+    accounts::{
+        Sequence,
+        SequenceDerivationData,
+        SequenceRangeAttestation,
+        SequenceRangeDerivationData,
+    },
+    error::Error::{
+        InvalidSequenceRange,
+        SequenceRangeIncomplete,
+    },
+};
+use solana_program::sysvar::clock::Clock;
+use solitaire::{
+    processors::seeded::Seeded,
+    CreationLamports::Exempt,
+    *,
+};
+
+#[derive(FromAccounts)]
+pub struct AttestSequenceRange<'b> {
+    /// Payer for the attestation account
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Emitter the range is of, anyone can attest its ranges
+    pub emitter: Info<'b>,
+
+    /// Tracker of the emitter sequence
+    pub sequence: Sequence<'b>,
+
+    /// Attestation of the range, created once
+    pub attestation: Mut<SequenceRangeAttestation<'b, { AccountState::Uninitialized }>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct AttestSequenceRangeData {
+    pub first: u64,
+
+    /// Last sequence of the range, inclusive
+    pub last: u64,
+}
+
+/// Attest that the emitter posted every sequence from `first` to `last`, once its sequence tracker
+/// moved past `last`. See [`SequenceRangeAttestation`].
+pub fn attest_sequence_range(
+    ctx: &ExecutionContext,
+    accs: &mut AttestSequenceRange,
+    data: AttestSequenceRangeData,
+) -> Result<()> {
+    accs.sequence.verify_derivation(
+        ctx.program_id,
+        &SequenceDerivationData {
+            emitter_key: accs.emitter.key,
+        },
+    )?;
+
+    if data.first > data.last {
+        return Err(InvalidSequenceRange.into());
+    }
+    if !accs.sequence.is_initialized() || accs.sequence.sequence <= data.last {
+        return Err(SequenceRangeIncomplete.into());
+    }
+
+    let derivation_data = SequenceRangeDerivationData {
+        emitter_key: accs.emitter.key,
+        first: data.first,
+        last: data.last,
+    };
+    accs.attestation
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    accs.attestation.emitter = *accs.emitter.key;
+    accs.attestation.first = data.first;
+    accs.attestation.last = data.last;
+    accs.attestation.next_sequence = accs.sequence.sequence;
+    accs.attestation.slot = accs.clock.slot;
+    accs.attestation
+        .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use crate::{
+    accounts::{
+        Bridge,
+        FeeBurn,
+        FeeCollector,
+    },
+    error::Error::{
+        FeeBurnDisabled,
+        InvalidFeeRecipient,
+        MathOverflow,
+    },
+};
+use solana_program::{
+    incinerator,
+    log::sol_log_data,
+    sysvar::rent::Rent,
+};
+use solitaire::{
+    invoke_signed,
+    processors::seeded::Seeded,
+    *,
+};
+
+#[derive(FromAccounts)]
+pub struct BurnFees<'b> {
+    /// Bridge config, tracking the collector balance.
+    pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
+
+    /// Fee burn mode and burned total.
+    pub fee_burn: Mut<FeeBurn<'b, { AccountState::Initialized }>>,
+
+    /// Account collecting tx fees
+    pub fee_collector: Mut<FeeCollector<'b>>,
+
+    /// Incinerator the fees are burned to.
+    pub incinerator: Mut<Info<'b>>,
+
+    /// Rent calculator to keep the collector rent exempt.
+    pub rent: Sysvar<'b, Rent>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct BurnFeesData {}
+
+/// Burn the fees the collector holds above its rent-exempt minimum, if governance enabled fee
+/// burning. Anyone can burn, the fees can only ever go to the incinerator.
+pub fn burn_fees(ctx: &ExecutionContext, accs: &mut BurnFees, _data: BurnFeesData) -> Result<()> {
+    if !accs.fee_burn.enabled {
+        return Err(FeeBurnDisabled.into());
+    }
+    if *accs.incinerator.key != incinerator::id() {
+        return Err(InvalidFeeRecipient.into());
+    }
+
+    let minimum = accs.rent.minimum_balance(accs.fee_collector.data_len());
+    let amount = accs.fee_collector.lamports().saturating_sub(minimum);
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let transfer_ix = solana_program::system_instruction::transfer(
+        accs.fee_collector.key,
+        accs.incinerator.key,
+        amount,
+    );
+    let seeds = accs.fee_collector.self_bumped_seeds(None, ctx.program_id);
+    let seeds: Vec<&[u8]> = seeds.iter().map(|item| item.as_slice()).collect();
+    let seeds = seeds.as_slice();
+    invoke_signed(&transfer_ix, ctx.accounts, &[seeds])?;
+
+    accs.bridge.last_lamports = minimum;
+    accs.fee_burn.burned = accs
+        .fee_burn
+        .burned
+        .checked_add(amount)
+        .ok_or(MathOverflow)?;
+    sol_log_data(&[
+        b"FeesBurned",
+        &amount.to_le_bytes(),
+        &accs.fee_burn.burned.to_le_bytes(),
+    ]);
+
+    Ok(())
+}
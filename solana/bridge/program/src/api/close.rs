@@ -0,0 +1,296 @@
+use crate::{
+    accounts::{
+        Bridge,
+        Claim,
+        ClaimConsumer,
+        ClaimConsumerDerivationData,
+        ClaimData,
+        ClaimDerivationData,
+        GuardianSet,
+        GuardianSetDerivationData,
+        GuardianSetExpiry,
+        PostedVAA,
+        PostedVAAData,
+        PostedVAADerivationData,
+        SignatureSetData,
+    },
+    error::Error::{
+        ClaimCollectionDisabled,
+        ClaimGracePeriodActive,
+        InvalidRefundRecipient,
+        InvalidSignatureSet,
+        SignatureSetInUse,
+        UnapprovedClaimConsumer,
+        VAANotClaimed,
+    },
+};
+use solana_program::{
+    program_error::ProgramError::InvalidAccountData,
+    pubkey::Pubkey,
+    sysvar::clock::Clock,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    *,
+};
+
+#[derive(FromAccounts)]
+pub struct ClosePostedVAA<'b> {
+    /// Refund recipient of the signature set the VAA was posted with
+    pub refund_recipient: Signer<Info<'b>>,
+
+    /// Account the rent is returned to
+    pub recipient: Mut<Info<'b>>,
+
+    pub posted_vaa: Mut<Info<'b>>,
+
+    /// Signature set the VAA was posted with
+    pub signature_set: Info<'b>,
+
+    /// Claim of the VAA, derived from its emitter and sequence under the consuming program
+    pub claim: Info<'b>,
+
+    /// Approval of the consuming program by governance, unused for claims of the bridge itself
+    pub claim_consumer: ClaimConsumer<'b, { AccountState::MaybeInitialized }>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct ClosePostedVAAData {
+    /// Program that redeemed the VAA, owning `claim`
+    pub consumer: Pubkey,
+}
+
+/// Close a posted VAA once a program claimed it, returning its rent to `recipient`. Only the payer
+/// of the signature set the VAA was posted with can close it, which is then closed with
+/// `close_signature_set`.
+///
+/// Anyone can deploy a program claiming VAAs it never redeemed, so only claims of the bridge and of
+/// programs governance approved with `set_claim_consumer` count.
+pub fn close_posted_vaa(
+    ctx: &ExecutionContext,
+    accs: &mut ClosePostedVAA,
+    data: ClosePostedVAAData,
+) -> Result<()> {
+    let signature_set = load_signature_set(ctx, &accs.signature_set, accs.refund_recipient.key)?;
+
+    // Posted messages share the layout of posted VAAs, but are never closed.
+    let posted_vaa =
+        load_posted_vaa(ctx, &accs.posted_vaa, &signature_set.hash)?.ok_or(InvalidAccountData)?;
+    if posted_vaa.vaa_signature_account != *accs.signature_set.key {
+        return Err(InvalidRefundRecipient.into());
+    }
+
+    if data.consumer != *ctx.program_id {
+        accs.claim_consumer.verify_derivation(
+            ctx.program_id,
+            &ClaimConsumerDerivationData {
+                program: &data.consumer,
+            },
+        )?;
+        if !accs.claim_consumer.is_initialized() || !accs.claim_consumer.approved {
+            return Err(UnapprovedClaimConsumer.into());
+        }
+    }
+
+    let claim = Claim::key(
+        &ClaimDerivationData {
+            emitter_address: posted_vaa.emitter_address,
+            emitter_chain: posted_vaa.emitter_chain,
+            sequence: posted_vaa.sequence,
+        },
+        &data.consumer,
+    );
+    let claimed = *accs.claim.key == claim
+        && *accs.claim.owner == data.consumer
+        && ClaimData::try_from_slice(&accs.claim.data.borrow()).map_or(false, |c| c.claimed);
+    if !claimed {
+        return Err(VAANotClaimed.into());
+    }
+
+    close(&accs.posted_vaa, &accs.recipient)
+}
+
+#[derive(FromAccounts)]
+pub struct CloseSignatureSet<'b> {
+    /// Refund recipient of the signature set, its payer
+    pub refund_recipient: Signer<Info<'b>>,
+
+    /// Account the rent is returned to
+    pub recipient: Mut<Info<'b>>,
+
+    pub signature_set: Mut<Info<'b>>,
+
+    /// Posted VAA of the signed hash, closed first if it was posted with this set
+    pub posted_vaa: Info<'b>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct CloseSignatureSetData {}
+
+/// Close a signature set, returning its rent to `recipient`. A VAA posted with the set must be
+/// closed with `close_posted_vaa` first, which needs the set to authorize the refund.
+pub fn close_signature_set(
+    ctx: &ExecutionContext,
+    accs: &mut CloseSignatureSet,
+    _data: CloseSignatureSetData,
+) -> Result<()> {
+    let signature_set = load_signature_set(ctx, &accs.signature_set, accs.refund_recipient.key)?;
+
+    let posted_vaa = load_posted_vaa(ctx, &accs.posted_vaa, &signature_set.hash)?;
+    if let Some(posted_vaa) = posted_vaa {
+        if posted_vaa.vaa_signature_account == *accs.signature_set.key {
+            return Err(SignatureSetInUse.into());
+        }
+    }
+
+    close(&accs.signature_set, &accs.recipient)
+}
+
+#[derive(FromAccounts)]
+pub struct CloseClaim<'b> {
+    /// Bridge config, holding the grace period and the recipient of the rent
+    pub bridge: Bridge<'b, { AccountState::Initialized }>,
+
+    /// Recipient governance designated, see `set_claim_grace_period`
+    pub recipient: Mut<Info<'b>>,
+
+    /// Claim of the VAA, derived from its emitter and sequence
+    pub claim: Mut<Info<'b>>,
+
+    /// Posted VAA of the claim, closed with it
+    pub posted_vaa: Mut<Info<'b>>,
+
+    /// Signature set the VAA was posted with
+    pub signature_set: Info<'b>,
+
+    /// Guardian set that signed the VAA
+    pub guardian_set: GuardianSet<'b, { AccountState::Initialized }>,
+
+    /// Guardian set expiry mode, claims aren't closed while the soft window accepts the set
+    pub guardian_set_expiry: GuardianSetExpiry<'b, { AccountState::MaybeInitialized }>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct CloseClaimData {}
+
+/// Close the claim of a governance VAA together with its posted VAA once the guardian set that
+/// signed it expired more than the grace period in the bridge config ago, returning their rent to
+/// the recipient governance designated. The VAA can't be posted again with an expired set, so it
+/// stays consumed, as long as the set is also past the soft expiry window of `GuardianSetExpiry`:
+/// claims are kept while soft expiry still accepts VAAs of the set, whatever the grace period.
+///
+/// Claims whose posted VAA was already closed with `close_posted_vaa` can't be closed, the
+/// guardian set that signed them is no longer known.
+pub fn close_claim(
+    ctx: &ExecutionContext,
+    accs: &mut CloseClaim,
+    _data: CloseClaimData,
+) -> Result<()> {
+    let config = &accs.bridge.config;
+    if config.claim_recipient == Pubkey::default() {
+        return Err(ClaimCollectionDisabled.into());
+    }
+    if *accs.recipient.key != config.claim_recipient {
+        return Err(InvalidRefundRecipient.into());
+    }
+
+    // Posted messages share the layout of posted VAAs, but are never claimed.
+    if accs.posted_vaa.owner != ctx.program_id || !accs.posted_vaa.data.borrow().starts_with(b"vaa")
+    {
+        return Err(InvalidAccountData.into());
+    }
+    let vaa = PostedVAAData::try_from_slice(&accs.posted_vaa.data.borrow())?;
+    if vaa.vaa_signature_account != *accs.signature_set.key {
+        return Err(InvalidSignatureSet.into());
+    }
+    if accs.signature_set.owner != ctx.program_id {
+        return Err(SolitaireError::InvalidOwner(*accs.signature_set.owner));
+    }
+    let signature_set = SignatureSetData::try_from_slice(&accs.signature_set.data.borrow())?;
+    load_posted_vaa(ctx, &accs.posted_vaa, &signature_set.hash)?;
+
+    let claim = Claim::key(
+        &ClaimDerivationData {
+            emitter_address: vaa.emitter_address,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+        },
+        ctx.program_id,
+    );
+    let claimed = *accs.claim.key == claim
+        && accs.claim.owner == ctx.program_id
+        && ClaimData::try_from_slice(&accs.claim.data.borrow()).map_or(false, |c| c.claimed);
+    if !claimed {
+        return Err(VAANotClaimed.into());
+    }
+
+    accs.guardian_set.verify_derivation(
+        ctx.program_id,
+        &GuardianSetDerivationData {
+            index: signature_set.guardian_set_index,
+        },
+    )?;
+    // Sets that never expired keep their claims, as do sets soft expiry still accepts.
+    let expiration_time = accs.guardian_set.expiration_time;
+    let now = accs.clock.unix_timestamp;
+    let collectable_at = i64::from(expiration_time) + i64::from(config.claim_grace_period);
+    if expiration_time == 0
+        || now <= collectable_at
+        || accs.guardian_set_expiry.accepts(expiration_time, now)
+    {
+        return Err(ClaimGracePeriodActive.into());
+    }
+
+    close(&accs.claim, &accs.recipient)?;
+    close(&accs.posted_vaa, &accs.recipient)
+}
+
+/// Signature set `refund_recipient` may close.
+fn load_signature_set(
+    ctx: &ExecutionContext,
+    info: &Info,
+    refund_recipient: &Pubkey,
+) -> Result<SignatureSetData> {
+    if info.owner != ctx.program_id {
+        return Err(SolitaireError::InvalidOwner(*info.owner));
+    }
+    let signature_set = SignatureSetData::try_from_slice(&info.data.borrow())?;
+    if signature_set.refund_recipient != Some(*refund_recipient) {
+        return Err(InvalidRefundRecipient.into());
+    }
+    Ok(signature_set)
+}
+
+/// Posted VAA of `hash`, if posted and not closed yet.
+fn load_posted_vaa(
+    ctx: &ExecutionContext,
+    info: &Info,
+    hash: &[u8; 32],
+) -> Result<Option<PostedVAAData>> {
+    let key = PostedVAA::<'_, { AccountState::Initialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash.to_vec(),
+        },
+        ctx.program_id,
+    );
+    if *info.key != key {
+        return Err(SolitaireError::InvalidDerive(*info.key, key));
+    }
+    if info.data_is_empty() {
+        return Ok(None);
+    }
+    if info.owner != ctx.program_id || !info.data.borrow().starts_with(b"vaa") {
+        return Err(InvalidAccountData.into());
+    }
+    Ok(Some(PostedVAAData::try_from_slice(&info.data.borrow())?))
+}
+
+/// Move all lamports of `account` to `recipient`, the runtime removes the emptied account.
+pub(crate) fn close(account: &Info, recipient: &Info) -> Result<()> {
+    **recipient.lamports.borrow_mut() += account.lamports();
+    **account.lamports.borrow_mut() = 0;
+    account.data.borrow_mut().fill(0);
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use crate::{
+    accounts::{
+        commitment_leaf,
+        commitment_root,
+        message_digest,
+        Bridge,
+        GuardianSet,
+        GuardianSetDerivationData,
+        MessageData,
+        PostedMessageData,
+        PostedMessageUnreliableData,
+        StateCommitment,
+        STATE_COMMITMENT_INTERVAL,
+    },
+    error::Error::{
+        InvalidStateCommitmentMessage,
+        StateCommitmentTooEarly,
+    },
+};
+use solana_program::{
+    log::sol_log_data,
+    sysvar::clock::Clock,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    CreationLamports::Exempt,
+    *,
+};
+
+#[derive(FromAccounts)]
+pub struct CommitState<'b> {
+    /// Payer for the commitment account
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Bridge config, committed
+    pub bridge: Bridge<'b, { AccountState::Initialized }>,
+
+    /// Active guardian set, committed
+    pub guardian_set: GuardianSet<'b, { AccountState::Initialized }>,
+
+    /// Latest commitment, replaced
+    pub commitment: Mut<StateCommitment<'b, { AccountState::MaybeInitialized }>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct CommitStateData {
+    /// Number of posted message accounts at the end of the instruction accounts, committed
+    pub messages: u8,
+}
+
+/// Commit to the bridge state, see [`StateCommitment`]. Anyone can commit once
+/// [`STATE_COMMITMENT_INTERVAL`] slots passed since the previous commitment, passing the messages
+/// posted since then, reliable or not. Commitments are logged as `StateCommitted`, with their
+/// index, root and slot.
+pub fn commit_state(
+    ctx: &ExecutionContext,
+    accs: &mut CommitState,
+    data: CommitStateData,
+) -> Result<()> {
+    accs.guardian_set.verify_derivation(
+        ctx.program_id,
+        &GuardianSetDerivationData {
+            index: accs.bridge.guardian_set_index,
+        },
+    )?;
+
+    let slot = accs.clock.slot;
+    let initialized = accs.commitment.is_initialized();
+    let next_slot = accs.commitment.slot + STATE_COMMITMENT_INTERVAL;
+    if initialized && slot < next_slot {
+        return Err(StateCommitmentTooEarly.into());
+    }
+
+    let messages = ctx
+        .accounts
+        .len()
+        .checked_sub(data.messages as usize)
+        .map(|start| &ctx.accounts[start..])
+        .ok_or(InvalidStateCommitmentMessage)?;
+    let mut digests = Vec::with_capacity(messages.len());
+    for info in messages {
+        let message = load_message(ctx, info)?;
+        if initialized && message.submission_time < accs.commitment.timestamp {
+            return Err(InvalidStateCommitmentMessage.into());
+        }
+        digests.push(message_digest(&message));
+    }
+    digests.sort_unstable();
+    digests.dedup();
+
+    let mut config = accs.bridge.config.try_to_vec()?;
+    config.extend_from_slice(&accs.bridge.guardian_set_index.to_be_bytes());
+    let mut leaves = vec![
+        commitment_leaf(&config),
+        commitment_leaf(&BorshSerialize::try_to_vec(&*accs.guardian_set)?),
+    ];
+    leaves.extend(digests.iter().map(|digest| commitment_leaf(digest)));
+    let root = commitment_root(&leaves);
+
+    if initialized {
+        accs.commitment.index += 1;
+        accs.commitment.previous_root = accs.commitment.root;
+    }
+    accs.commitment.root = root;
+    accs.commitment.slot = slot;
+    accs.commitment.timestamp = accs.clock.unix_timestamp as u32;
+    accs.commitment.guardian_set_index = accs.guardian_set.index;
+    accs.commitment.message_count = digests.len() as u32;
+    if !initialized {
+        accs.commitment.create(ctx, accs.payer.key, Exempt)?;
+    }
+
+    sol_log_data(&[
+        b"StateCommitted",
+        &accs.commitment.index.to_be_bytes(),
+        &root,
+        &slot.to_be_bytes(),
+    ]);
+
+    Ok(())
+}
+
+/// Message posted to `info`, reliably or not.
+fn load_message(ctx: &ExecutionContext, info: &Info) -> Result<MessageData> {
+    if info.owner != ctx.program_id {
+        return Err(SolitaireError::InvalidOwner(*info.owner));
+    }
+    let data = info.data.borrow();
+    let message = match data.get(..3) {
+        Some(b"msg") => PostedMessageData::deserialize(&mut &data[..])?.message,
+        Some(b"msu") => PostedMessageUnreliableData::deserialize(&mut &data[..])?.message,
+        _ => return Err(InvalidStateCommitmentMessage.into()),
+    };
+    Ok(message)
+}
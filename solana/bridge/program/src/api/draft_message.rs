@@ -0,0 +1,212 @@
+use crate::{
+    accounts::{
+        Bridge,
+        DraftMessage,
+        FeeCollector,
+        Sequence,
+    },
+    api::{
+        close::close,
+        post_message::{
+            emit_message_posted,
+            post_message_internal,
+            PostMessageData,
+            UninitializedMessage,
+        },
+    },
+    error::Error::{
+        DraftMessageEmitterMismatch,
+        DraftMessageWriteOutOfBounds,
+        InvalidDraftMessage,
+    },
+    types::ConsistencyLevel,
+    IsSigned::*,
+};
+use solana_program::{
+    program::set_return_data,
+    pubkey::Pubkey,
+    sysvar::clock::Clock,
+};
+use solitaire::{
+    CreationLamports::Exempt,
+    *,
+};
+
+#[derive(FromAccounts)]
+pub struct InitMessageV1<'b> {
+    /// Emitter the message will be posted from, the only key allowed to write and finalize it.
+    pub emitter: Signer<MaybeMut<Info<'b>>>,
+
+    /// Account the payload is written to, zeroed, owned by the bridge and sized for the payload
+    /// after `DraftMessage::HEADER_LEN` header bytes. It must sign, so that no other bridge account
+    /// can be taken over.
+    pub draft_message: Mut<Signer<Info<'b>>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct InitMessageV1Data {}
+
+/// Start drafting a message with a payload too large for `post_message` in `draft_message`, see
+/// [`DraftMessage`].
+pub fn init_message_v1(
+    ctx: &ExecutionContext,
+    accs: &mut InitMessageV1,
+    _data: InitMessageV1Data,
+) -> Result<()> {
+    if accs.draft_message.owner != ctx.program_id {
+        return Err(InvalidDraftMessage.into());
+    }
+
+    let mut data = accs.draft_message.try_borrow_mut_data()?;
+    let payload_len = data
+        .len()
+        .checked_sub(DraftMessage::HEADER_LEN)
+        .filter(|len| *len <= u32::MAX as usize)
+        .ok_or(InvalidDraftMessage)?;
+    if data.iter().any(|b| *b != 0) {
+        return Err(InvalidDraftMessage.into());
+    }
+
+    let draft_message = DraftMessage {
+        emitter: *accs.emitter.key,
+        payload_len: payload_len as u32,
+    };
+    draft_message.serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct WriteMessageV1<'b> {
+    pub emitter: Signer<MaybeMut<Info<'b>>>,
+
+    pub draft_message: Mut<Info<'b>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct WriteMessageV1Data {
+    /// Offset of the chunk in the payload.
+    pub index: u32,
+
+    pub data: Vec<u8>,
+}
+
+/// Write a chunk of the payload, chunks may be written in any order and rewritten until finalized.
+pub fn write_message_v1(
+    ctx: &ExecutionContext,
+    accs: &mut WriteMessageV1,
+    data: WriteMessageV1Data,
+) -> Result<()> {
+    let draft_message = load_draft(ctx, &accs.draft_message, accs.emitter.key)?;
+
+    let start = DraftMessage::HEADER_LEN + data.index as usize;
+    let end = start + data.data.len();
+    if end > DraftMessage::HEADER_LEN + draft_message.payload_len as usize {
+        return Err(DraftMessageWriteOutOfBounds.into());
+    }
+    accs.draft_message.try_borrow_mut_data()?[start..end].copy_from_slice(&data.data);
+
+    Ok(())
+}
+
+/// Accounts of `post_message` followed by the draft, so that the message account is the second
+/// account like in every instruction guardians observe messages of.
+#[derive(FromAccounts)]
+pub struct FinalizeMessageV1<'b> {
+    /// Bridge config needed for fee calculation.
+    pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
+
+    /// Account to store the posted message
+    pub message: Signer<Mut<UninitializedMessage<'b>>>,
+
+    /// Emitter of the VAA, which drafted the message
+    pub emitter: Signer<MaybeMut<Info<'b>>>,
+
+    /// Tracker for the emitter sequence
+    pub sequence: Mut<Sequence<'b>>,
+
+    /// Payer for account creation, refunded the rent of the draft
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Account to collect tx fee
+    pub fee_collector: Mut<FeeCollector<'b>>,
+
+    pub clock: Sysvar<'b, Clock>,
+
+    /// Draft holding the payload, closed once posted
+    pub draft_message: Mut<Info<'b>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct FinalizeMessageV1Data {
+    /// Unique nonce for this message
+    pub nonce: u32,
+
+    /// Commitment Level required for an attestation to be produced
+    pub consistency_level: ConsistencyLevel,
+}
+
+/// Post the drafted payload as a message from `emitter`, with the same fee and sequence as
+/// `post_message`, and close the draft. Returns the assigned sequence like `post_message`.
+pub fn finalize_message_v1(
+    ctx: &ExecutionContext,
+    accs: &mut FinalizeMessageV1,
+    data: FinalizeMessageV1Data,
+) -> Result<()> {
+    let draft_message = load_draft(ctx, &accs.draft_message, accs.emitter.key)?;
+    let payload = draft_message
+        .payload(&accs.draft_message.data.borrow())
+        .ok_or(InvalidDraftMessage)?
+        .to_vec();
+
+    post_message_internal(
+        ctx,
+        &mut accs.bridge,
+        accs.message.info().key,
+        &mut accs.message,
+        accs.emitter.key,
+        &mut accs.sequence,
+        &mut accs.payer,
+        &mut accs.fee_collector,
+        &mut accs.clock,
+        PostMessageData {
+            nonce: data.nonce,
+            payload,
+            consistency_level: data.consistency_level,
+        },
+    )?;
+
+    // Create message account
+    let size = accs.message.size();
+    create_account(
+        ctx,
+        accs.message.info(),
+        accs.payer.key,
+        Exempt,
+        size,
+        ctx.program_id,
+        NotSigned,
+    )?;
+
+    // Zeroing the draft prevents posting it twice.
+    close(&accs.draft_message, &accs.payer)?;
+    emit_message_posted(ctx, &accs.message)?;
+
+    // Set last, invoking the system program clears the return data.
+    set_return_data(&accs.message.sequence.to_le_bytes());
+
+    Ok(())
+}
+
+/// Header of a message `emitter` is drafting.
+fn load_draft(ctx: &ExecutionContext, info: &Info, emitter: &Pubkey) -> Result<DraftMessage> {
+    if info.owner != ctx.program_id {
+        return Err(InvalidDraftMessage.into());
+    }
+    let draft_message =
+        DraftMessage::deserialize(&mut &info.data.borrow()[..]).map_err(|_| InvalidDraftMessage)?;
+    if draft_message.emitter != *emitter {
+        return Err(DraftMessageEmitterMismatch.into());
+    }
+    Ok(draft_message)
+}
@@ -0,0 +1,100 @@
+use crate::{
+    accounts::{
+        Bridge,
+        PostedVAA,
+        PostedVAADerivationData,
+    },
+    error::Error::InvalidGuardianSetUpgrade,
+    types::GovernancePayloadGuardianSetChange,
+    DeserializePayload,
+};
+use solana_program::{
+    program::set_return_data,
+    program_error::ProgramError,
+    sysvar::clock::Clock,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    *,
+};
+
+#[derive(FromAccounts)]
+pub struct DryRunGuardianSetUpgrade<'b> {
+    /// Bridge config, naming the current guardian set and how long it stays valid once replaced.
+    pub bridge: Bridge<'b, { AccountState::Initialized }>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct DryRunGuardianSetUpgradeData {
+    /// Governance payload of the guardian set upgrade.
+    pub decree: Vec<u8>,
+
+    /// Digests of the pending VAAs, the PostedVAA account of each following the instruction
+    /// accounts in the same order.
+    pub digests: Vec<[u8; 32]>,
+}
+
+/// Outcome of [`dry_run_guardian_set_upgrade`], returned as return data.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct GuardianSetUpgradeDryRun {
+    pub new_guardian_set_index: u32,
+
+    /// When the current guardian set would expire if the upgrade executed now.
+    pub expiration_time: u32,
+
+    /// Digests of the pending VAAs that would become unverifiable once the current set expires.
+    pub unverifiable: Vec<[u8; 32]>,
+}
+
+/// Report which pending VAAs become unverifiable if the guardian set upgrade in `decree` executes
+/// now, so that relayer operators can drain their queues before it does. Pending VAAs are signed
+/// by the current guardian set and can only be posted until the set expires, VAAs already posted
+/// stay consumable. The decree is not verified and no account changes, the instruction is meant
+/// to be simulated.
+pub fn dry_run_guardian_set_upgrade(
+    ctx: &ExecutionContext,
+    accs: &mut DryRunGuardianSetUpgrade,
+    data: DryRunGuardianSetUpgradeData,
+) -> Result<()> {
+    let change = <GovernancePayloadGuardianSetChange as DeserializePayload>::deserialize(
+        &mut &data.decree[..],
+    )?;
+    if change.new_guardian_set_index != accs.bridge.guardian_set_index + 1 {
+        return Err(InvalidGuardianSetUpgrade.into());
+    }
+
+    let accounts = ctx
+        .accounts
+        .len()
+        .checked_sub(data.digests.len())
+        .map(|start| &ctx.accounts[start..])
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let mut unverifiable = Vec::new();
+    for (digest, info) in data.digests.iter().zip(accounts) {
+        let key = PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+            &PostedVAADerivationData {
+                payload_hash: digest.to_vec(),
+            },
+            ctx.program_id,
+        );
+        if *info.key != key {
+            return Err(SolitaireError::InvalidDerive(*info.key, key));
+        }
+        if info.owner != ctx.program_id || info.data_is_empty() {
+            unverifiable.push(*digest);
+        }
+    }
+
+    let report = GuardianSetUpgradeDryRun {
+        new_guardian_set_index: change.new_guardian_set_index,
+        expiration_time: (accs.clock.unix_timestamp as u32)
+            .saturating_add(accs.bridge.config.guardian_set_expiration_time),
+        unverifiable,
+    };
+    set_return_data(&report.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use crate::{
+    accounts::{
+        EmitterStats,
+        EmitterStatsData,
+        EmitterStatsDerivationData,
+        SequenceTracker,
+    },
+    error::Error::{
+        EmissionBreakerTripped,
+        MissingEmitterStats,
+    },
+};
+use solana_program::{
+    log::sol_log_data,
+    pubkey::Pubkey,
+};
+use solitaire::{
+    create_account,
+    processors::seeded::Seeded,
+    CreationLamports::Exempt,
+    IsSigned::SignedWithSeeds,
+    *,
+};
+
+#[derive(FromAccounts)]
+pub struct ClearEmissionBreaker<'b> {
+    /// Emitter whose breaker is cleared
+    pub emitter: Signer<MaybeMut<Info<'b>>>,
+
+    /// Stats of the emitter
+    pub emitter_stats: Mut<EmitterStats<'b, { AccountState::Initialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct ClearEmissionBreakerData {}
+
+/// Clear the tripped emission breaker of the signing emitter, once its owner made sure the burst of
+/// messages was intended. Governance can clear it too, see
+/// [`release_emission_breaker`](crate::api::release_emission_breaker).
+pub fn clear_emission_breaker(
+    ctx: &ExecutionContext,
+    accs: &mut ClearEmissionBreaker,
+    _data: ClearEmissionBreakerData,
+) -> Result<()> {
+    accs.emitter_stats.verify_derivation(
+        ctx.program_id,
+        &EmitterStatsDerivationData {
+            emitter: accs.emitter.key,
+        },
+    )?;
+
+    accs.emitter_stats.clear();
+    sol_log_data(&[b"EmissionBreakerCleared", accs.emitter.key.as_ref()]);
+
+    Ok(())
+}
+
+/// Count a message of `emitter` towards its [`EmitterStats`] while governance enables the emission
+/// breaker, failing if the breaker tripped. Only emitters that registered their metadata are
+/// counted.
+///
+/// The stats are looked up among the instruction accounts so the post account layouts stay
+/// unchanged, see [`with_emitter_stats`](crate::instructions::with_emitter_stats). They are required
+/// for registered emitters, as leaving them out would otherwise skip the check, and created at the
+/// payer's expense by the first post counted. The message that trips the breaker is still posted.
+pub(crate) fn record_emission(
+    ctx: &ExecutionContext,
+    multiple: u32,
+    sequence: &SequenceTracker,
+    emitter: &Pubkey,
+    payer: &Pubkey,
+    epoch: u64,
+) -> Result<()> {
+    if multiple == 0 || sequence.metadata.is_none() {
+        return Ok(());
+    }
+
+    let derivation = EmitterStatsDerivationData { emitter };
+    let key =
+        EmitterStats::<'_, { AccountState::MaybeInitialized }>::key(&derivation, ctx.program_id);
+    let info = ctx
+        .accounts
+        .iter()
+        .find(|info| *info.key == key)
+        .ok_or(MissingEmitterStats)?;
+
+    let mut stats = if info.data_is_empty() {
+        let stats = EmitterStatsData {
+            epoch,
+            ..Default::default()
+        };
+        let seeds = EmitterStats::<'_, { AccountState::MaybeInitialized }>::bumped_seeds(
+            &derivation,
+            ctx.program_id,
+        );
+        let seeds: Vec<&[u8]> = seeds.iter().map(|seed| seed.as_slice()).collect();
+        create_account(
+            ctx,
+            info,
+            payer,
+            Exempt,
+            stats.try_to_vec()?.len(),
+            ctx.program_id,
+            SignedWithSeeds(&[&seeds]),
+        )?;
+        stats
+    } else {
+        if info.owner != ctx.program_id {
+            return Err(SolitaireError::InvalidOwner(*info.owner));
+        }
+        EmitterStatsData::try_from_slice(&info.data.borrow())?
+    };
+
+    if stats.tripped {
+        return Err(EmissionBreakerTripped.into());
+    }
+    if stats.record(epoch, multiple) {
+        sol_log_data(&[
+            b"EmissionBreakerTripped",
+            emitter.as_ref(),
+            &stats.count.to_be_bytes(),
+            &stats.average.to_be_bytes(),
+        ]);
+    }
+    trace_kv!(
+        "check",
+        kind = "emission_breaker",
+        count = stats.count,
+        average = stats.average
+    );
+
+    stats.serialize(&mut &mut info.data.borrow_mut()[..])?;
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use crate::{
+    accounts::{
+        EncodedVaa,
+        GuardianSet,
+        ProcessingStatus,
+        SignatureSet,
+    },
+    api::{
+        post_vaa::{
+            check_active_or_recent,
+            check_consensus,
+            check_valid_sigs,
+            select_guardian_set,
+        },
+        verify_signature::split_signed_vaa,
+    },
+    error::Error::{
+        EncodedVaaNotWriting,
+        EncodedVaaWriteAuthorityMismatch,
+        EncodedVaaWriteOutOfBounds,
+        InvalidEncodedVaa,
+        InvalidHash,
+    },
+};
+use solana_program::{
+    pubkey::Pubkey,
+    sysvar::clock::Clock,
+};
+use solitaire::*;
+
+#[derive(FromAccounts)]
+pub struct InitEncodedVaa<'b> {
+    /// Key allowed to write and finalize the VAA.
+    pub write_authority: Signer<Info<'b>>,
+
+    /// Account the VAA is uploaded to, zeroed, owned by the bridge and sized for the VAA after
+    /// `EncodedVaa::HEADER_LEN` header bytes. It must sign, so that no other bridge account can be
+    /// taken over.
+    pub encoded_vaa: Mut<Signer<Info<'b>>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct InitEncodedVaaData {}
+
+/// Start uploading a VAA too large for `post_vaa` into `encoded_vaa`, see [`EncodedVaa`].
+pub fn init_encoded_vaa(
+    ctx: &ExecutionContext,
+    accs: &mut InitEncodedVaa,
+    _data: InitEncodedVaaData,
+) -> Result<()> {
+    if accs.encoded_vaa.owner != ctx.program_id {
+        return Err(InvalidEncodedVaa.into());
+    }
+
+    let mut data = accs.encoded_vaa.try_borrow_mut_data()?;
+    let vaa_len = data
+        .len()
+        .checked_sub(EncodedVaa::HEADER_LEN)
+        .filter(|len| *len > 0 && *len <= u32::MAX as usize)
+        .ok_or(InvalidEncodedVaa)?;
+    if data.iter().any(|b| *b != 0) {
+        return Err(InvalidEncodedVaa.into());
+    }
+
+    let encoded_vaa = EncodedVaa {
+        status: ProcessingStatus::Writing,
+        write_authority: *accs.write_authority.key,
+        vaa_len: vaa_len as u32,
+    };
+    encoded_vaa.serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct WriteEncodedVaa<'b> {
+    pub write_authority: Signer<Info<'b>>,
+
+    pub encoded_vaa: Mut<Info<'b>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct WriteEncodedVaaData {
+    /// Offset of the chunk in the VAA.
+    pub index: u32,
+
+    pub data: Vec<u8>,
+}
+
+/// Write a chunk of the VAA, chunks may be written in any order and rewritten until finalized.
+pub fn write_encoded_vaa(
+    ctx: &ExecutionContext,
+    accs: &mut WriteEncodedVaa,
+    data: WriteEncodedVaaData,
+) -> Result<()> {
+    let encoded_vaa = load_writing(ctx, &accs.encoded_vaa, accs.write_authority.key)?;
+
+    let start = EncodedVaa::HEADER_LEN + data.index as usize;
+    let end = start + data.data.len();
+    if end > EncodedVaa::HEADER_LEN + encoded_vaa.vaa_len as usize {
+        return Err(EncodedVaaWriteOutOfBounds.into());
+    }
+    accs.encoded_vaa.try_borrow_mut_data()?[start..end].copy_from_slice(&data.data);
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct FinalizeEncodedVaa<'b> {
+    pub write_authority: Signer<Info<'b>>,
+
+    pub encoded_vaa: Mut<Info<'b>>,
+
+    /// Guardian set the VAA names.
+    pub guardian_set: GuardianSet<'b, { AccountState::Initialized }>,
+
+    /// Signatures over the body of the VAA, verified with `verify_signatures`.
+    pub signature_set: SignatureSet<'b, { AccountState::Initialized }>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct FinalizeEncodedVaaData {}
+
+/// Mark the uploaded VAA verified once `signature_set` holds consensus over its body, with the
+/// same checks as `post_vaa`. Verified VAAs can no longer be written to.
+pub fn finalize_encoded_vaa(
+    ctx: &ExecutionContext,
+    accs: &mut FinalizeEncodedVaa,
+    _data: FinalizeEncodedVaaData,
+) -> Result<()> {
+    let mut encoded_vaa = load_writing(ctx, &accs.encoded_vaa, accs.write_authority.key)?;
+
+    {
+        let data = accs.encoded_vaa.data.borrow();
+        let vaa = encoded_vaa.vaa(&data).ok_or(InvalidEncodedVaa)?;
+        let (guardian_set_index, _, body) = split_signed_vaa(vaa)?;
+
+        let guardian_set = select_guardian_set(ctx, &accs.guardian_set, guardian_set_index)?;
+        check_active_or_recent(ctx, &guardian_set, &accs.clock)?;
+        check_valid_sigs(&guardian_set, &accs.signature_set)?;
+
+        let body_hash = wormhole_vaa::digest(body);
+        if body_hash != accs.signature_set.hash {
+            return Err(InvalidHash.into());
+        }
+        check_consensus(ctx, &guardian_set, &accs.signature_set.signatures)?;
+    }
+
+    encoded_vaa.status = ProcessingStatus::Verified;
+    encoded_vaa.serialize(&mut &mut accs.encoded_vaa.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
+
+/// Header of an encoded VAA `write_authority` is still uploading.
+fn load_writing(
+    ctx: &ExecutionContext,
+    info: &Info,
+    write_authority: &Pubkey,
+) -> Result<EncodedVaa> {
+    if info.owner != ctx.program_id {
+        return Err(InvalidEncodedVaa.into());
+    }
+    let encoded_vaa =
+        EncodedVaa::deserialize(&mut &info.data.borrow()[..]).map_err(|_| InvalidEncodedVaa)?;
+    if encoded_vaa.write_authority != *write_authority {
+        return Err(EncodedVaaWriteAuthorityMismatch.into());
+    }
+    if encoded_vaa.status != ProcessingStatus::Writing {
+        return Err(EncodedVaaNotWriting.into());
+    }
+    Ok(encoded_vaa)
+}
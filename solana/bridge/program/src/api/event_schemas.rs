@@ -0,0 +1,32 @@
+use crate::{
+    accounts::Bridge,
+    MessagePostedV1,
+};
+use solana_program::program::set_return_data;
+use solitaire::*;
+
+#[derive(FromAccounts)]
+pub struct EventSchemas<'b> {
+    /// Bridge config, the schemas are reported by initialized bridges only.
+    pub bridge: Bridge<'b, { AccountState::Initialized }>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct EventSchemasData {}
+
+/// Schemas of every event version the bridge emits, the latest version of each event last.
+pub fn event_registry() -> Vec<EventSchema> {
+    vec![EventSchema::of::<MessagePostedV1>()]
+}
+
+/// Return the borsh serialized [`event_registry`], so that indexers can compare the schema hashes
+/// they decode against the ones of the deployed program after an upgrade. The instruction changes
+/// no account and is meant to be simulated.
+pub fn event_schemas(
+    _ctx: &ExecutionContext,
+    _accs: &mut EventSchemas,
+    _data: EventSchemasData,
+) -> Result<()> {
+    set_return_data(&event_registry().try_to_vec()?);
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use crate::{
+    accounts::{
+        Bridge,
+        GuardianSet,
+        GuardianSetDerivationData,
+        GuardianSetExport,
+    },
+    error::Error::InvalidExportAccount,
+};
+use solana_program::sysvar::clock::Clock;
+use solitaire::{
+    processors::seeded::Seeded,
+    *,
+};
+
+#[derive(FromAccounts)]
+pub struct ExportGuardianSet<'b> {
+    /// Bridge config, naming the current guardian set.
+    pub bridge: Bridge<'b, { AccountState::Initialized }>,
+
+    /// Current guardian set.
+    pub guardian_set: GuardianSet<'b, { AccountState::Initialized }>,
+
+    /// Account the export is written to, owned by the bridge and `GuardianSetExport::LEN` bytes
+    /// long. Accounts without an export yet must be zeroed and sign, so that no other bridge
+    /// account can be overwritten.
+    pub export: Mut<Info<'b>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct ExportGuardianSetData {}
+
+/// Write the current guardian set into `export` as a [`GuardianSetExport`]. Anyone can export,
+/// and refresh an existing export after the set changed.
+pub fn export_guardian_set(
+    ctx: &ExecutionContext,
+    accs: &mut ExportGuardianSet,
+    _data: ExportGuardianSetData,
+) -> Result<()> {
+    accs.guardian_set.verify_derivation(
+        ctx.program_id,
+        &GuardianSetDerivationData {
+            index: accs.bridge.guardian_set_index,
+        },
+    )?;
+
+    if accs.export.owner != ctx.program_id || accs.export.data_len() != GuardianSetExport::LEN {
+        return Err(InvalidExportAccount.into());
+    }
+
+    let mut data = accs.export.try_borrow_mut_data()?;
+    let exported = data.starts_with(&GuardianSetExport::MAGIC);
+    let zeroed = data.iter().all(|b| *b == 0);
+    if !exported && !(zeroed && accs.export.is_signer) {
+        return Err(InvalidExportAccount.into());
+    }
+
+    let export = GuardianSetExport {
+        guardian_set_index: accs.guardian_set.index,
+        keys_hash: accs.guardian_set.keys_hash(),
+        expiration_time: accs.guardian_set.expiration_time,
+        slot: accs.clock.slot,
+    };
+    export.serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use crate::{
+    accounts::{
+        Bridge,
+        FeeInvoice,
+        FeeInvoiceData,
+        FeeInvoiceDerivationData,
+        FEE_INVOICE_MAX_SLOTS,
+    },
+    api::{
+        close::close,
+        post_message::message_fee,
+    },
+    error::Error::{
+        FeeInvoiceNotExpired,
+        InvalidFeeInvoice,
+    },
+    types::ConsistencyLevel,
+};
+use solana_program::{
+    log::sol_log_data,
+    sysvar::clock::Clock,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    CreationLamports::Exempt,
+    *,
+};
+
+#[derive(FromAccounts)]
+pub struct CreateFeeInvoice<'b> {
+    /// Creator of the invoice, paying for its account
+    pub creator: Mut<Signer<Info<'b>>>,
+
+    /// Bridge config the fee is quoted from
+    pub bridge: Bridge<'b, { AccountState::Initialized }>,
+
+    /// Keypair of the message account the invoice is for, posted later
+    pub message: Signer<Info<'b>>,
+
+    /// Invoice of the message, created once
+    pub invoice: Mut<FeeInvoice<'b, { AccountState::Uninitialized }>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct CreateFeeInvoiceData {
+    /// Length of the payload of the message
+    pub payload_len: u32,
+
+    /// Consistency level of the message
+    pub consistency_level: ConsistencyLevel,
+
+    /// Last slot the fee is charged at
+    pub expiry_slot: u64,
+}
+
+/// Quote the current message fee for the message posted to `message`, charged instead of the fee
+/// at posting time while the invoice is valid. The message keypair signs, so that only whoever
+/// posts the message can quote it. Invoices are valid for [`FEE_INVOICE_MAX_SLOTS`] at most and
+/// logged as `FeeInvoiceCreated`, with the message key, fee and expiry slot.
+pub fn create_fee_invoice(
+    ctx: &ExecutionContext,
+    accs: &mut CreateFeeInvoice,
+    data: CreateFeeInvoiceData,
+) -> Result<()> {
+    let derivation_data = FeeInvoiceDerivationData {
+        message: accs.message.key,
+    };
+    accs.invoice
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    let slot = accs.clock.slot;
+    if data.expiry_slot < slot || data.expiry_slot - slot > FEE_INVOICE_MAX_SLOTS {
+        return Err(InvalidFeeInvoice.into());
+    }
+
+    let fee = message_fee(ctx, &accs.bridge)?;
+    accs.invoice.creator = *accs.creator.key;
+    accs.invoice.payload_len = data.payload_len;
+    accs.invoice.consistency_level = data.consistency_level;
+    accs.invoice.fee = fee;
+    accs.invoice.expiry_slot = data.expiry_slot;
+    accs.invoice
+        .create(&derivation_data, ctx, accs.creator.key, Exempt)?;
+
+    sol_log_data(&[
+        b"FeeInvoiceCreated",
+        accs.message.key.as_ref(),
+        &fee.to_be_bytes(),
+        &data.expiry_slot.to_be_bytes(),
+    ]);
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct CloseFeeInvoice<'b> {
+    /// Creator of the invoice, refunded its rent
+    pub creator: Mut<Signer<Info<'b>>>,
+
+    /// Message account the invoice is for
+    pub message: Info<'b>,
+
+    /// Expired invoice of the message
+    pub invoice: Mut<Info<'b>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct CloseFeeInvoiceData {}
+
+/// Close an invoice once it expired, returning its rent to its creator. Invoices stay open until
+/// then, so that a message quoted at a fee can't fall back to a higher one.
+pub fn close_fee_invoice(
+    ctx: &ExecutionContext,
+    accs: &mut CloseFeeInvoice,
+    _data: CloseFeeInvoiceData,
+) -> Result<()> {
+    let key = FeeInvoice::<'_, { AccountState::Initialized }>::key(
+        &FeeInvoiceDerivationData {
+            message: accs.message.key,
+        },
+        ctx.program_id,
+    );
+    if *accs.invoice.key != key {
+        return Err(SolitaireError::InvalidDerive(*accs.invoice.key, key));
+    }
+    if accs.invoice.owner != ctx.program_id {
+        return Err(SolitaireError::InvalidOwner(*accs.invoice.owner));
+    }
+
+    let invoice = FeeInvoiceData::try_from_slice(&accs.invoice.data.borrow())?;
+    if invoice.creator != *accs.creator.key {
+        return Err(InvalidFeeInvoice.into());
+    }
+    if invoice.expiry_slot >= accs.clock.slot {
+        return Err(FeeInvoiceNotExpired.into());
+    }
+
+    close(&accs.invoice, &accs.creator)
+}
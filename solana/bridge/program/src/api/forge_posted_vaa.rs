@@ -0,0 +1,140 @@
+use crate::{
+    accounts::PostedVAA,
+    api::ForeignAddress,
+};
+use solana_program::{
+    pubkey::Pubkey,
+    sysvar::clock::Clock,
+};
+use solitaire::*;
+
+// Forged VAAs pass for guardian-signed ones, builds that include the instruction must never be
+// deployed at the mainnet address. Localnet builds must name the address they are built for, and
+// `forge_posted_vaa` checks the address it runs at as well.
+#[cfg(feature = "localnet")]
+const _: () = assert!(
+    !is_mainnet(env!("BRIDGE_ADDRESS")),
+    "the localnet feature must not be enabled for mainnet builds"
+);
+
+#[cfg(feature = "localnet")]
+const fn is_mainnet(address: &str) -> bool {
+    let address = address.as_bytes();
+    let mainnet = wormhole_solana_consts::program_ids::mainnet::CORE_BRIDGE.as_bytes();
+    if address.len() != mainnet.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < address.len() {
+        if address[i] != mainnet[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+#[derive(FromAccounts)]
+pub struct ForgePostedVAA<'b> {
+    /// Payer for the posted VAA
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Posted VAA, derived from the digest of the forged body
+    pub message: Mut<PostedVAA<'b, { AccountState::MaybeInitialized }>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Default)]
+pub struct ForgePostedVAAData {
+    pub version: u8,
+
+    // Body part
+    pub timestamp: u32,
+    pub nonce: u32,
+    pub emitter_chain: u16,
+    pub emitter_address: ForeignAddress,
+    pub sequence: u64,
+    pub consistency_level: u8,
+    pub payload: Vec<u8>,
+
+    /// Recorded as the signature set of the VAA, no signatures are checked
+    pub signature_set: Pubkey,
+}
+
+/// Write a posted VAA with an arbitrary body without any guardian signatures, so that developers
+/// can test how their programs redeem VAAs on a local validator. The account is the one
+/// `post_vaa` would create for the same body, forging an already posted body changes nothing.
+///
+/// Only builds with the `localnet` feature forge VAAs, which can't be built for the mainnet
+/// address and refuse to forge when deployed there anyway. Other builds reject the instruction as
+/// unknown.
+#[cfg(feature = "localnet")]
+pub fn forge_posted_vaa(
+    ctx: &ExecutionContext,
+    accs: &mut ForgePostedVAA,
+    data: ForgePostedVAAData,
+) -> Result<()> {
+    use crate::{
+        accounts::PostedVAADerivationData,
+        api::governance_digest,
+        MessageData,
+    };
+    use solitaire::{
+        processors::seeded::Seeded,
+        CreationLamports::Exempt,
+    };
+    use std::str::FromStr;
+
+    let mainnet =
+        Pubkey::from_str(wormhole_solana_consts::program_ids::mainnet::CORE_BRIDGE).unwrap();
+    if *ctx.program_id == mainnet {
+        return Err(SolitaireError::UnknownInstruction(
+            crate::instruction::Instruction::ForgePostedVAA as u8,
+        ));
+    }
+
+    let message = MessageData {
+        vaa_version: data.version,
+        consistency_level: data.consistency_level,
+        vaa_time: data.timestamp,
+        vaa_signature_account: data.signature_set,
+        submission_time: accs.clock.unix_timestamp as u32,
+        nonce: data.nonce,
+        sequence: data.sequence,
+        emitter_chain: data.emitter_chain,
+        emitter_address: data.emitter_address,
+        payload: data.payload,
+    };
+    let msg_derivation = PostedVAADerivationData {
+        payload_hash: governance_digest(&message)?.to_vec(),
+    };
+    accs.message
+        .verify_derivation(ctx.program_id, &msg_derivation)?;
+
+    if accs.message.is_initialized() {
+        return Ok(());
+    }
+
+    trace_kv!(
+        "forge_posted_vaa",
+        emitter_chain = message.emitter_chain,
+        sequence = message.sequence
+    );
+    accs.message.message = message;
+    accs.message
+        .create(&msg_derivation, ctx, accs.payer.key, Exempt)?;
+
+    Ok(())
+}
+
+#[cfg(not(feature = "localnet"))]
+pub fn forge_posted_vaa(
+    _ctx: &ExecutionContext,
+    _accs: &mut ForgePostedVAA,
+    _data: ForgePostedVAAData,
+) -> Result<()> {
+    Err(SolitaireError::UnknownInstruction(
+        crate::instruction::Instruction::ForgePostedVAA as u8,
+    ))
+}
@@ -0,0 +1,151 @@
+use crate::{
+    accounts::{
+        Bridge,
+        FeeCollector,
+        PostedVAA,
+        PostedVAADerivationData,
+        Sequence,
+    },
+    api::{
+        governance_digest,
+        post_message::post_message_internal,
+        PostMessageData,
+        UninitializedMessage,
+    },
+    error::Error::{
+        InvalidForwardTarget,
+        InvalidForwardedVAA,
+    },
+    types::{
+        ConsistencyLevel,
+        PayloadForwardedVAA,
+    },
+    vaa::SerializePayload,
+    IsSigned::*,
+    MessageData,
+    CHAIN_ID_SOLANA,
+};
+use solana_program::{
+    log::sol_log_data,
+    sysvar::clock::Clock,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    CreationLamports::Exempt,
+    *,
+};
+use wormhole_solana_consts::seeds;
+
+#[derive(FromAccounts)]
+pub struct ForwardVAA<'b> {
+    /// Bridge config needed for fee calculation.
+    pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
+
+    /// VAA verified by this bridge that is forwarded.
+    pub vaa: PostedVAA<'b, { AccountState::Initialized }>,
+
+    /// Account to store the forwarded message
+    pub message: Signer<Mut<UninitializedMessage<'b>>>,
+
+    /// Emitter of all forwarded messages.
+    pub forwarder: Derive<Info<'b>, { seeds::FORWARDER }>,
+
+    /// Tracker for the forwarder sequence
+    pub sequence: Mut<Sequence<'b>>,
+
+    /// Payer for account creation
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Account to collect tx fee
+    pub fee_collector: Mut<FeeCollector<'b>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ForwardVAAData {
+    /// Unique nonce for the forwarded message
+    pub nonce: u32,
+
+    /// Chain ID of the SVM network the VAA is forwarded to
+    pub target_chain: u16,
+
+    /// Commitment Level required for an attestation of the forwarded message
+    pub consistency_level: ConsistencyLevel,
+}
+
+/// Re-emit the digest and emitter metadata of a VAA verified by this bridge as a message of the
+/// forwarder, so that other SVM networks only need to verify messages from Solana to trust VAAs
+/// from any chain. Anyone can forward a VAA, a VAA may be forwarded more than once, so spokes
+/// must replay-protect by the original emitter chain, address and sequence.
+pub fn forward_vaa(
+    ctx: &ExecutionContext,
+    accs: &mut ForwardVAA,
+    data: ForwardVAAData,
+) -> Result<()> {
+    if data.target_chain == 0 || data.target_chain == CHAIN_ID_SOLANA {
+        return Err(InvalidForwardTarget.into());
+    }
+
+    // Posted messages deserialize as VAAs too, only accounts derived from the digest of a VAA body
+    // were created by post_vaa.
+    let digest = governance_digest(&accs.vaa)?;
+    accs.vaa
+        .verify_derivation(
+            ctx.program_id,
+            &PostedVAADerivationData {
+                payload_hash: digest.to_vec(),
+            },
+        )
+        .map_err(|_| InvalidForwardedVAA)?;
+
+    let vaa: &MessageData = &accs.vaa;
+    let payload = PayloadForwardedVAA {
+        target_chain: data.target_chain,
+        digest,
+        emitter_chain: vaa.emitter_chain,
+        emitter_address: vaa.emitter_address,
+        sequence: vaa.sequence,
+        timestamp: vaa.vaa_time,
+        nonce: vaa.nonce,
+        consistency_level: vaa.consistency_level,
+    };
+
+    post_message_internal(
+        ctx,
+        &mut accs.bridge,
+        accs.message.info().key,
+        &mut accs.message,
+        accs.forwarder.key,
+        &mut accs.sequence,
+        &mut accs.payer,
+        &mut accs.fee_collector,
+        &mut accs.clock,
+        PostMessageData {
+            nonce: data.nonce,
+            payload: payload.try_to_vec()?,
+            consistency_level: data.consistency_level,
+        },
+    )?;
+
+    // Create message account
+    let size = accs.message.size();
+    create_account(
+        ctx,
+        accs.message.info(),
+        accs.payer.key,
+        Exempt,
+        size,
+        ctx.program_id,
+        NotSigned,
+    )?;
+
+    sol_log_data(&[
+        b"VAAForwarded",
+        &data.target_chain.to_le_bytes(),
+        &digest,
+        &accs.message.sequence.to_le_bytes(),
+    ]);
+
+    Ok(())
+}
@@ -1,5 +1,17 @@
+use crate::codec::WriteBytes;
+use primitive_types::U256;
+use sha3::Digest;
 use solana_program::{
-    program::invoke_signed,
+    bpf_loader_upgradeable::UpgradeableLoaderState,
+    entrypoint::MAX_PERMITTED_DATA_INCREASE,
+    hash,
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    keccak,
+    log::sol_log_data,
+    program_error::ProgramError::InvalidAccountData,
     pubkey::Pubkey,
     sysvar::{
         clock::Clock,
@@ -7,10 +19,20 @@ use solana_program::{
     },
 };
 use solitaire::{
+    create_account,
+    ensure_account_size,
+    invoke,
+    invoke_signed,
     processors::seeded::Seeded,
     CreationLamports::Exempt,
+    IsSigned::SignedWithSeeds,
     *,
 };
+use std::io::{
+    Cursor,
+    Write,
+};
+use wormhole_solana_consts::seeds;
 
 use crate::{
     accounts::{
@@ -18,39 +40,253 @@ use crate::{
             self,
             Claim,
         },
+        fee_token_balance,
+        is_token_program,
+        parse_version,
+        secp256r1_address,
         Bridge,
+        ClaimConsumer,
+        ClaimConsumerDerivationData,
+        EmitterStats,
+        EmitterStatsDerivationData,
+        FeeBurn,
+        FeeCollector,
+        FeeExemption,
+        FeeExemptionDerivationData,
+        FeeOracle,
+        GovernanceAnnouncement,
+        GovernanceAnnouncementDerivationData,
         GuardianSet,
+        GuardianSetData,
         GuardianSetDerivationData,
+        GuardianSetDiff,
+        GuardianSetExpiry,
+        GuardianSetFloor,
+        GuardianSetWeights,
+        GuardianSetWeightsDerivationData,
+        MessageData,
+        Migration,
+        PendingGovernance,
+        PendingGovernanceDerivationData,
+        PostedVAA,
+        PostedVAADerivationData,
+        ProgramVersion,
+        Secp256r1Verification,
+        SignatureSet,
+        Verifier,
+        VerifierDerivationData,
+        VersionRegistryData,
+        GOVERNANCE_TIMELOCK_DELAY,
+        MAX_VERSION_LEN,
+    },
+    api::post_vaa::{
+        check_active,
+        check_consensus,
+        check_integrity,
+        check_valid_sigs,
+        PostVAAData,
     },
     error::Error::{
+        GovernanceNotHistorical,
+        InvalidFeeOracle,
         InvalidFeeRecipient,
         InvalidGovernanceKey,
         InvalidGovernanceWithdrawal,
         InvalidGuardianSetUpgrade,
+        InvalidGuardianWeights,
+        InvalidQuorum,
+        InvalidSpillAccount,
+        InvalidSuccessor,
+        InvalidUpgradeBuffer,
+        InvalidVersion,
+        MissingVersionRegistry,
+        TooManyGuardians,
+        UpgradeBufferHashMismatch,
+        VersionHashMismatch,
     },
     types::{
+        GovernancePayloadConfirmGuardianSetDowngrade,
         GovernancePayloadGuardianSetChange,
+        GovernancePayloadMigrate,
+        GovernancePayloadReleaseEmissionBreaker,
+        GovernancePayloadSetClaimConsumer,
+        GovernancePayloadSetClaimGracePeriod,
+        GovernancePayloadSetEmissionBreaker,
+        GovernancePayloadSetFeeBurn,
+        GovernancePayloadSetFeeExemption,
+        GovernancePayloadSetFeeOracle,
+        GovernancePayloadSetGuardianSetExpiry,
+        GovernancePayloadSetGuardianSetFloor,
+        GovernancePayloadSetGuardianWeights,
         GovernancePayloadSetMessageFee,
+        GovernancePayloadSetPaused,
+        GovernancePayloadSetQuorum,
+        GovernancePayloadSetSecp256r1Verification,
+        GovernancePayloadSetSuccessor,
+        GovernancePayloadSetTokenFee,
+        GovernancePayloadSetVerifier,
         GovernancePayloadTransferFees,
         GovernancePayloadUpgrade,
     },
     DeserializePayload,
+    EmitterInfo,
     PayloadMessage,
     CHAIN_ID_GOVERANCE,
 };
 
-/// Fail if the emitter is not the known governance key, or the emitting chain is not Solana.
-fn verify_governance<T>(vaa: &PayloadMessage<T>) -> Result<()>
+/// Fail if the emitter is not the known governance key, or the emitting chain is not Solana. With
+/// the `governance-announcement` feature the VAA must also have been announced, see
+/// [`verify_announcement`], and with the `governance-timelock` feature queued, see
+/// [`verify_timelock`].
+fn verify_governance<T>(
+    #[cfg_attr(
+        not(any(feature = "governance-announcement", feature = "governance-timelock")),
+        allow(unused_variables)
+    )]
+    ctx: &ExecutionContext,
+    vaa: &PayloadMessage<T>,
+) -> Result<()>
 where
     T: DeserializePayload,
 {
-    let expected_emitter = std::env!("EMITTER_ADDRESS");
-    let current_emitter = format!("{}", Pubkey::new_from_array(vaa.meta().emitter_address));
-    if expected_emitter != current_emitter || vaa.meta().emitter_chain != CHAIN_ID_GOVERANCE {
-        Err(InvalidGovernanceKey.into())
-    } else {
-        Ok(())
+    trace_kv!(
+        "check",
+        kind = "governance",
+        emitter = Pubkey::new_from_array(vaa.emitter().address),
+        chain = vaa.emitter().chain
+    );
+    if !vaa.try_emitter_eq(CHAIN_ID_GOVERANCE, std::env!("EMITTER_ADDRESS"))? {
+        return Err(InvalidGovernanceKey.into());
+    }
+
+    #[cfg(feature = "governance-announcement")]
+    verify_announcement(ctx, vaa)?;
+
+    #[cfg(feature = "governance-timelock")]
+    verify_timelock(ctx, vaa)?;
+
+    Ok(())
+}
+
+/// Keccak256 digest of a VAA body, the digest PostedVAA accounts are derived from and governance
+/// VAAs are announced by.
+pub fn governance_digest(vaa: &MessageData) -> Result<[u8; 32]> {
+    let body = {
+        let mut v = Cursor::new(Vec::new());
+        v.write_u32_be(vaa.vaa_time)?;
+        v.write_u32_be(vaa.nonce)?;
+        v.write_u16_be(vaa.emitter_chain)?;
+        v.write_all(&vaa.emitter_address)?;
+        v.write_u64_be(vaa.sequence)?;
+        v.write_u8(vaa.consistency_level)?;
+        v.write_all(&vaa.payload)?;
+        v.into_inner()
+    };
+
+    let mut h = sha3::Keccak256::default();
+    h.write_all(body.as_slice())?;
+    Ok(h.finalize().into())
+}
+
+/// The digest of a governance VAA must have been announced at least
+/// [`GOVERNANCE_ANNOUNCEMENT_DELAY`](crate::accounts::GOVERNANCE_ANNOUNCEMENT_DELAY) slots ago. The
+/// announcement is looked up among the instruction accounts so the governance account layouts stay
+/// unchanged, clients append it to the instruction.
+#[cfg(feature = "governance-announcement")]
+fn verify_announcement<T>(ctx: &ExecutionContext, vaa: &PayloadMessage<T>) -> Result<()>
+where
+    T: DeserializePayload,
+{
+    use crate::{
+        accounts::GovernanceAnnouncementData,
+        error::Error::{
+            GovernanceAnnouncementPending,
+            GovernanceNotAnnounced,
+        },
+    };
+    use solana_program::sysvar::Sysvar as _;
+
+    let digest = governance_digest(vaa.meta())?;
+    let key = GovernanceAnnouncement::<'_, { AccountState::Initialized }>::key(
+        &GovernanceAnnouncementDerivationData { digest },
+        ctx.program_id,
+    );
+    let info = ctx
+        .accounts
+        .iter()
+        .find(|info| *info.key == key)
+        .ok_or(GovernanceNotAnnounced)?;
+
+    if info.data_is_empty() {
+        return Err(GovernanceNotAnnounced.into());
+    }
+    if info.owner != ctx.program_id {
+        return Err(SolitaireError::InvalidOwner(*info.owner));
+    }
+
+    let announcement = GovernanceAnnouncementData::try_from_slice(&info.data.borrow())?;
+    let slot = Clock::get()?.slot;
+    trace_kv!(
+        "check",
+        kind = "governance_announcement",
+        announced = announcement.slot,
+        slot = slot
+    );
+    if !announcement.matured(slot) {
+        return Err(GovernanceAnnouncementPending.into());
+    }
+
+    Ok(())
+}
+
+/// A governance VAA must have been queued by [`queue_governance`] and its delay passed. Like the
+/// announcement, the pending account is looked up among the instruction accounts, clients append
+/// it to the instruction.
+#[cfg(feature = "governance-timelock")]
+fn verify_timelock<T>(ctx: &ExecutionContext, vaa: &PayloadMessage<T>) -> Result<()>
+where
+    T: DeserializePayload,
+{
+    use crate::{
+        accounts::PendingGovernanceData,
+        error::Error::{
+            GovernanceNotQueued,
+            GovernanceTimelocked,
+        },
+    };
+    use solana_program::sysvar::Sysvar as _;
+
+    let digest = governance_digest(vaa.meta())?;
+    let key = PendingGovernance::<'_, { AccountState::Initialized }>::key(
+        &PendingGovernanceDerivationData { digest },
+        ctx.program_id,
+    );
+    let info = ctx
+        .accounts
+        .iter()
+        .find(|info| *info.key == key)
+        .ok_or(GovernanceNotQueued)?;
+
+    if info.data_is_empty() {
+        return Err(GovernanceNotQueued.into());
+    }
+    if info.owner != ctx.program_id {
+        return Err(SolitaireError::InvalidOwner(*info.owner));
+    }
+
+    let pending = PendingGovernanceData::try_from_slice(&info.data.borrow())?;
+    let slot = Clock::get()?.slot;
+    trace_kv!(
+        "check",
+        kind = "governance_timelock",
+        unlock = pending.unlock_slot,
+        slot = slot
+    );
+    if !pending.unlocked(slot) {
+        return Err(GovernanceTimelocked.into());
     }
+
+    Ok(())
 }
 
 #[derive(FromAccounts)]
@@ -68,9 +304,9 @@ pub struct UpgradeContract<'b> {
     pub claim: Mut<Claim<'b>>,
 
     /// PDA authority for the loader
-    pub upgrade_authority: Derive<Info<'b>, "upgrade">,
+    pub upgrade_authority: Derive<Info<'b>, { seeds::UPGRADE }>,
 
-    /// Spill address for the upgrade excess lamports
+    /// Spill address for the upgrade excess lamports, see [`verify_spill`].
     pub spill: Mut<Info<'b>>,
 
     /// New contract address.
@@ -87,17 +323,34 @@ pub struct UpgradeContract<'b> {
     pub clock: Sysvar<'b, Clock>,
     pub bpf_loader: Info<'b>,
     pub system: Info<'b>,
+
+    /// Account collecting tx fees, which acts as the treasury for upgrade excess lamports.
+    pub fee_collector: Mut<FeeCollector<'b>>,
+    // The version registry may trail the accounts above, see [`record_version`].
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Default)]
 pub struct UpgradeContractData {}
 
+/// The buffer lamports left over after an upgrade are sent to the spill account. Only the payer of
+/// the upgrade or the fee collector, whose funds can only be moved by governance, are accepted so
+/// the lamports can't be directed to an arbitrary account.
+fn verify_spill(accs: &UpgradeContract) -> Result<()> {
+    if accs.spill.key != accs.payer.key && accs.spill.key != accs.fee_collector.key {
+        return Err(InvalidSpillAccount.into());
+    }
+    Ok(())
+}
+
 pub fn upgrade_contract(
     ctx: &ExecutionContext,
     accs: &mut UpgradeContract,
     _data: UpgradeContractData,
 ) -> Result<()> {
-    verify_governance(&accs.vaa)?;
+    verify_governance(ctx, &accs.vaa)?;
+    verify_spill(accs)?;
+    verify_version(&accs.vaa)?;
+    verify_buffer_hash(accs)?;
     claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
 
     let upgrade_ix = solana_program::bpf_loader_upgradeable::upgrade(
@@ -114,6 +367,114 @@ pub fn upgrade_contract(
     let seeds = seeds.as_slice();
     invoke_signed(&upgrade_ix, ctx.accounts, &[seeds])?;
 
+    // Spilled lamports must not be counted as message fees paid by the next emitter.
+    if accs.spill.key == accs.fee_collector.key {
+        accs.bridge.last_lamports = accs.fee_collector.lamports();
+    }
+
+    record_version(ctx, accs)
+}
+
+/// Decrees carrying a buffer hash bind the upgrade to the program the guardians reviewed, the
+/// SHA-256 of the program in the buffer, after the loader header, must match it.
+fn verify_buffer_hash(accs: &UpgradeContract) -> Result<()> {
+    let expected = match &accs.vaa.buffer_hash {
+        Some(expected) => expected,
+        None => return Ok(()),
+    };
+    if *accs.buffer.key != accs.vaa.new_contract {
+        return Err(InvalidUpgradeBuffer.into());
+    }
+
+    let data = accs.buffer.data.borrow();
+    let offset = UpgradeableLoaderState::buffer_data_offset().map_err(|_| InvalidAccountData)?;
+    let program = data.get(offset..).ok_or(InvalidUpgradeBuffer)?;
+    if hash::hash(program).to_bytes() != *expected {
+        return Err(UpgradeBufferHashMismatch.into());
+    }
+    Ok(())
+}
+
+/// Versions carried by upgrade decrees must be `MAJOR.MINOR.PATCH` versions that fit the registry.
+fn verify_version(vaa: &GovernancePayloadUpgrade) -> Result<()> {
+    if let Some(version) = &vaa.version {
+        if version.len() > MAX_VERSION_LEN || parse_version(version).is_none() {
+            return Err(InvalidVersion.into());
+        }
+    }
+    Ok(())
+}
+
+/// Append the upgrade to the version registry, creating the registry with the first upgrade. The
+/// registry is looked up among the instruction accounts so the upgrade account layout stays
+/// unchanged, clients append it to the instruction. Only decrees naming a version need it, hashing
+/// the program data costs compute in proportion to the program size, see
+/// [`with_version_registry`](crate::instructions::with_version_registry).
+fn record_version(ctx: &ExecutionContext, accs: &UpgradeContract) -> Result<()> {
+    let versioned = accs
+        .vaa
+        .version
+        .as_ref()
+        .map_or(false, |version| !version.is_empty());
+    let (key, bump) =
+        Pubkey::find_program_address(&[seeds::VERSION_REGISTRY.as_bytes()], ctx.program_id);
+    let info = match ctx.accounts.iter().find(|info| *info.key == key) {
+        Some(info) => info,
+        None if versioned => return Err(MissingVersionRegistry.into()),
+        None => return Ok(()),
+    };
+
+    let mut registry = if info.data_is_empty() {
+        VersionRegistryData::default()
+    } else {
+        if info.owner != ctx.program_id {
+            return Err(SolitaireError::InvalidOwner(*info.owner));
+        }
+        VersionRegistryData::try_from_slice(&info.data.borrow())?
+    };
+
+    // The loader checked that this is our program data account, and it now holds the new program.
+    let program_data_hash = {
+        let data = accs.program_data.data.borrow();
+        let offset =
+            UpgradeableLoaderState::programdata_data_offset().map_err(|_| InvalidAccountData)?;
+        keccak::hash(data.get(offset..).ok_or(InvalidAccountData)?).to_bytes()
+    };
+
+    // A version names a single program, re-deploying it must not change what it refers to.
+    let version = accs.vaa.version.clone().unwrap_or_default();
+    if !version.is_empty() {
+        if let Some(registered) = registry.program_data_hash(&version) {
+            if registered != program_data_hash {
+                return Err(VersionHashMismatch.into());
+            }
+        }
+    }
+
+    sol_log_data(&[b"VersionRegistered", version.as_bytes(), &program_data_hash]);
+    registry.versions.push(ProgramVersion {
+        version,
+        program_data_hash,
+        slot: accs.clock.slot,
+    });
+
+    // Grow the registry to fit the new entry, keeping it rent exempt.
+    let data = registry.try_to_vec()?;
+    if info.data_is_empty() {
+        create_account(
+            ctx,
+            info,
+            accs.payer.key,
+            Exempt,
+            data.len(),
+            ctx.program_id,
+            SignedWithSeeds(&[&[seeds::VERSION_REGISTRY.as_bytes(), &[bump]]]),
+        )?;
+    } else {
+        ensure_account_size(ctx, info, accs.payer.key, data.len())?;
+    }
+    info.data.borrow_mut().copy_from_slice(&data);
+
     Ok(())
 }
 
@@ -146,54 +507,248 @@ pub fn upgrade_guardian_set(
     accs: &mut UpgradeGuardianSet,
     _data: UpgradeGuardianSetData,
 ) -> Result<()> {
-    verify_governance(&accs.vaa)?;
+    verify_governance(ctx, &accs.vaa)?;
     claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
 
+    #[cfg(feature = "guardian-set-floor")]
+    verify_guardian_set_floor(ctx, &accs.vaa)?;
+
+    apply_guardian_set_change(
+        ctx,
+        accs.payer.key,
+        &mut accs.bridge,
+        &mut accs.guardian_set_old,
+        &mut accs.guardian_set_new,
+        &accs.vaa,
+        accs.vaa.meta().vaa_time,
+    )
+}
+
+/// Replace the active guardian set with the one introduced by a guardian set change VAA issued at
+/// `vaa_time`, expiring the old set relative to that time.
+fn apply_guardian_set_change(
+    ctx: &ExecutionContext,
+    payer: &Pubkey,
+    bridge: &mut Bridge<'_, { AccountState::Initialized }>,
+    guardian_set_old: &mut GuardianSet<'_, { AccountState::Initialized }>,
+    guardian_set_new: &mut GuardianSet<'_, { AccountState::Uninitialized }>,
+    change: &GovernancePayloadGuardianSetChange,
+    vaa_time: u32,
+) -> Result<()> {
     // Enforce single increments when upgrading.
-    if accs.guardian_set_old.index != accs.vaa.new_guardian_set_index - 1 {
+    if guardian_set_old.index != change.new_guardian_set_index - 1 {
         return Err(InvalidGuardianSetUpgrade.into());
     }
 
     // Confirm that the version the bridge has active is the previous version.
-    if accs.bridge.guardian_set_index != accs.vaa.new_guardian_set_index - 1 {
+    if bridge.guardian_set_index != change.new_guardian_set_index - 1 {
         return Err(InvalidGuardianSetUpgrade.into());
     }
 
-    accs.guardian_set_old.verify_derivation(
+    guardian_set_old.verify_derivation(
         ctx.program_id,
         &GuardianSetDerivationData {
-            index: accs.vaa.new_guardian_set_index - 1,
+            index: change.new_guardian_set_index - 1,
         },
     )?;
-    accs.guardian_set_new.verify_derivation(
+    guardian_set_new.verify_derivation(
         ctx.program_id,
         &GuardianSetDerivationData {
-            index: accs.vaa.new_guardian_set_index,
+            index: change.new_guardian_set_index,
         },
     )?;
 
     // Set expiration time for the old set
-    accs.guardian_set_old.expiration_time =
-        accs.vaa.meta().vaa_time + accs.bridge.config.guardian_set_expiration_time;
+    guardian_set_old.expiration_time = vaa_time + bridge.config.guardian_set_expiration_time;
 
     // Initialize new guardian Set
-    accs.guardian_set_new.index = accs.vaa.new_guardian_set_index;
-    accs.guardian_set_new.creation_time = accs.vaa.meta().vaa_time;
-    accs.guardian_set_new.keys = accs.vaa.new_guardian_set.clone();
+    guardian_set_new.index = change.new_guardian_set_index;
+    guardian_set_new.creation_time = vaa_time;
+    guardian_set_new.keys = change.new_guardian_set.clone();
+
+    // Secp256r1 guardians are listed by the address of their key, so that every guardian keeps a
+    // single identity.
+    for (address, key) in change
+        .new_guardian_set
+        .iter()
+        .zip(change.new_secp256r1_keys.iter())
+    {
+        if *key != [0u8; 33] && *address != secp256r1_address(key) {
+            return Err(InvalidGuardianSetUpgrade.into());
+        }
+    }
+    guardian_set_new.secp256r1_keys = change.new_secp256r1_keys.clone();
+    guardian_set_new.quorum = bridge.config.quorum;
+
+    // Accounts created by a program are limited in size. A full set of 255 secp256k1 guardians
+    // fits, secp256r1 keys take up more space and only fit for smaller sets.
+    if guardian_set_new.size() > MAX_PERMITTED_DATA_INCREASE {
+        return Err(TooManyGuardians.into());
+    }
 
     // Create new guardian set
     // This is done after populating it to properly allocate space according to key vec length.
-    accs.guardian_set_new.create(
+    guardian_set_new.create(
         &GuardianSetDerivationData {
-            index: accs.guardian_set_new.index,
+            index: guardian_set_new.index,
         },
         ctx,
-        accs.payer.key,
+        payer,
         Exempt,
     )?;
 
     // Set guardian set index
-    accs.bridge.guardian_set_index = accs.vaa.new_guardian_set_index;
+    bridge.guardian_set_index = change.new_guardian_set_index;
+
+    // Event listing the rotated guardians, the full sets remain readable from their accounts.
+    let diff = GuardianSetDiff::new(&guardian_set_old.keys, &guardian_set_new.keys);
+    sol_log_data(&[
+        b"GuardianSetChanged",
+        &guardian_set_new.index.to_le_bytes(),
+        &diff.added.concat(),
+        &diff.removed.concat(),
+        &(diff.retained as u32).to_le_bytes(),
+    ]);
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct ReplayGovernance<'b> {
+    /// Payer for the new guardian set
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Bridge config
+    pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
+
+    /// Guardian set the bridge was initialized with, created when this deployment was. Read-only
+    /// as it is also the old guardian set of the first replayed change, passed writable then.
+    pub genesis_guardian_set: ReadOnly<GuardianSet<'b, { AccountState::Initialized }>>,
+
+    /// Signatures of the old guardian set over the guardian set change VAA
+    pub signature_set: SignatureSet<'b, { AccountState::Initialized }>,
+
+    /// Old guardian set
+    pub guardian_set_old: Mut<GuardianSet<'b, { AccountState::Initialized }>>,
+
+    /// New guardian set
+    pub guardian_set_new: Mut<GuardianSet<'b, { AccountState::Uninitialized }>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+/// Bootstrap a fresh deployment from the guardian set it was initialized with to the current one,
+/// by replaying the historical guardian set change VAAs in order instead of initializing with a
+/// hardcoded set.
+///
+/// Each replayed VAA is checked like `post_vaa` would, against signatures verified by
+/// `verify_signatures`, and applied like `upgrade_guardian_set`, but without posting or claiming
+/// it. Only changes issued before the initial guardian set was created are accepted: they were
+/// never announced on this deployment and precede any guardian set floor, so neither is checked.
+/// Later changes go through governance as usual.
+pub fn replay_governance(
+    ctx: &ExecutionContext,
+    accs: &mut ReplayGovernance,
+    vaa: PostVAAData,
+) -> Result<()> {
+    accs.genesis_guardian_set
+        .verify_derivation(ctx.program_id, &GuardianSetDerivationData { index: 0 })?;
+    if vaa.timestamp >= accs.genesis_guardian_set.creation_time {
+        return Err(GovernanceNotHistorical.into());
+    }
+
+    // The VAA must carry the signatures of the active guardian set, as a posted VAA would.
+    accs.guardian_set_old.verify_derivation(
+        ctx.program_id,
+        &GuardianSetDerivationData {
+            index: accs.bridge.guardian_set_index,
+        },
+    )?;
+    check_active(&accs.guardian_set_old, &accs.clock)?;
+    check_valid_sigs(&accs.guardian_set_old, &accs.signature_set)?;
+    check_integrity(&vaa, &accs.signature_set)?;
+    check_consensus(ctx, &accs.guardian_set_old, &accs.signature_set.signatures)?;
+
+    if !EmitterInfo::from(&vaa).try_is(CHAIN_ID_GOVERANCE, std::env!("EMITTER_ADDRESS"))? {
+        return Err(InvalidGovernanceKey.into());
+    }
+    let change = <GovernancePayloadGuardianSetChange as DeserializePayload>::deserialize(
+        &mut &vaa.payload[..],
+    )?;
+
+    trace_kv!(
+        "replay",
+        kind = "guardian_set_change",
+        sequence = vaa.sequence,
+        new_guardian_set = change.new_guardian_set_index
+    );
+
+    apply_guardian_set_change(
+        ctx,
+        accs.payer.key,
+        &mut accs.bridge,
+        &mut accs.guardian_set_old,
+        &mut accs.guardian_set_new,
+        &change,
+        vaa.timestamp,
+    )
+}
+
+/// A guardian set upgrade may only shrink the set below the floor once the downgrade was confirmed
+/// by [`confirm_guardian_set_downgrade`] at least
+/// [`GUARDIAN_SET_DOWNGRADE_DELAY`](crate::accounts::GUARDIAN_SET_DOWNGRADE_DELAY) slots ago.
+///
+/// The floor is looked up among the instruction accounts so the upgrade account layout stays
+/// unchanged. Unlike other trailing accounts it is required, even before governance set a floor,
+/// as leaving it out would otherwise skip the check.
+#[cfg(feature = "guardian-set-floor")]
+fn verify_guardian_set_floor(
+    ctx: &ExecutionContext,
+    vaa: &PayloadMessage<GovernancePayloadGuardianSetChange>,
+) -> Result<()> {
+    use crate::{
+        accounts::GuardianSetFloorData,
+        error::Error::{
+            GuardianSetDowngradeNotConfirmed,
+            GuardianSetDowngradePending,
+            MissingGuardianSetFloor,
+        },
+    };
+    use solana_program::sysvar::Sysvar as _;
+
+    let key = GuardianSetFloor::<'_, { AccountState::Initialized }>::key(None, ctx.program_id);
+    let info = ctx
+        .accounts
+        .iter()
+        .find(|info| *info.key == key)
+        .ok_or(MissingGuardianSetFloor)?;
+
+    // No floor was set yet.
+    if info.data_is_empty() {
+        return Ok(());
+    }
+    if info.owner != ctx.program_id {
+        return Err(SolitaireError::InvalidOwner(*info.owner));
+    }
+
+    let floor = GuardianSetFloorData::try_from_slice(&info.data.borrow())?;
+    trace_kv!(
+        "check",
+        kind = "guardian_set_floor",
+        min_keys = floor.min_keys,
+        keys = vaa.new_guardian_set.len()
+    );
+    if vaa.new_guardian_set.len() >= floor.min_keys as usize {
+        return Ok(());
+    }
+
+    let digest = governance_digest(vaa.meta())?;
+    if floor.confirmed_digest != digest {
+        return Err(GuardianSetDowngradeNotConfirmed.into());
+    }
+    if !floor.downgrade_matured(&digest, Clock::get()?.slot) {
+        return Err(GuardianSetDowngradePending.into());
+    }
 
     Ok(())
 }
@@ -217,7 +772,7 @@ pub struct SetFees<'b> {
 pub struct SetFeesData {}
 
 pub fn set_fees(ctx: &ExecutionContext, accs: &mut SetFees, _data: SetFeesData) -> Result<()> {
-    verify_governance(&accs.vaa)?;
+    verify_governance(ctx, &accs.vaa)?;
     claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
     accs.bridge.config.fee = accs.vaa.fee.as_u64();
     Ok(())
@@ -238,7 +793,7 @@ pub struct TransferFees<'b> {
     pub claim: Mut<Claim<'b>>,
 
     /// Account collecting tx fees
-    pub fee_collector: Mut<Derive<Info<'b>, "fee_collector">>,
+    pub fee_collector: Mut<Derive<Info<'b>, { seeds::FEE_COLLECTOR }>>,
 
     /// Fee recipient
     pub recipient: Mut<Info<'b>>,
@@ -255,7 +810,7 @@ pub fn transfer_fees(
     accs: &mut TransferFees,
     _data: TransferFeesData,
 ) -> Result<()> {
-    verify_governance(&accs.vaa)?;
+    verify_governance(ctx, &accs.vaa)?;
     claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
 
     // Make sure the account loaded to receive funds is equal to the one the VAA requested.
@@ -263,10 +818,12 @@ pub fn transfer_fees(
         return Err(InvalidFeeRecipient.into());
     }
 
-    let new_balance = accs
-        .fee_collector
-        .lamports()
-        .saturating_sub(accs.vaa.amount.as_u64());
+    if accs.vaa.amount > U256::from(u64::MAX) {
+        return Err(InvalidGovernanceWithdrawal.into());
+    }
+    let amount = accs.vaa.amount.as_u64();
+
+    let new_balance = accs.fee_collector.lamports().saturating_sub(amount);
 
     if new_balance < accs.rent.minimum_balance(accs.fee_collector.data_len()) {
         return Err(InvalidGovernanceWithdrawal.into());
@@ -277,7 +834,7 @@ pub fn transfer_fees(
     let transfer_ix = solana_program::system_instruction::transfer(
         accs.fee_collector.key,
         accs.recipient.key,
-        accs.vaa.amount.as_u64(),
+        amount,
     );
 
     let seeds = accs.fee_collector.self_bumped_seeds(None, ctx.program_id);
@@ -285,5 +842,914 @@ pub fn transfer_fees(
     let seeds = seeds.as_slice();
     invoke_signed(&transfer_ix, ctx.accounts, &[seeds])?;
 
+    // Lamports sent to a wrapped SOL token account only count towards its balance once synced.
+    // Other token accounts fail to sync, rather than holding lamports their owner can't withdraw.
+    if is_token_program(accs.recipient.owner) {
+        let sync_ix = Instruction::new_with_bytes(
+            *accs.recipient.owner,
+            &[SYNC_NATIVE],
+            vec![AccountMeta::new(*accs.recipient.key, false)],
+        );
+        invoke(&sync_ix, ctx.accounts)?;
+    }
+
+    Ok(())
+}
+
+/// Tag of the token program instruction syncing a wrapped SOL balance with the account lamports.
+const SYNC_NATIVE: u8 = 17;
+
+#[derive(FromAccounts)]
+pub struct SetGuardianWeights<'b> {
+    /// Payer for account creation (vaa-claim, weights)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetGuardianWeights>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Guardian set the weights are assigned to.
+    pub guardian_set: GuardianSet<'b, { AccountState::Initialized }>,
+
+    /// Weights of the guardian set, created on first assignment.
+    pub guardian_set_weights: Mut<GuardianSetWeights<'b, { AccountState::MaybeInitialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetGuardianWeightsData {}
+
+pub fn set_guardian_weights(
+    ctx: &ExecutionContext,
+    accs: &mut SetGuardianWeights,
+    _data: SetGuardianWeightsData,
+) -> Result<()> {
+    verify_governance(ctx, &accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    let index = accs.vaa.guardian_set_index;
+    accs.guardian_set
+        .verify_derivation(ctx.program_id, &GuardianSetDerivationData { index })?;
+    accs.guardian_set_weights
+        .verify_derivation(ctx.program_id, &GuardianSetWeightsDerivationData { index })?;
+
+    // Every guardian must be weighted, and a set without weight could never reach quorum.
+    if accs.vaa.weights.len() != accs.guardian_set.keys.len()
+        || accs.vaa.weights.iter().all(|w| *w == 0)
+    {
+        return Err(InvalidGuardianWeights.into());
+    }
+
+    // Weights are sized by the guardian set, so re-assigning them never changes the account size.
+    accs.guardian_set_weights.guardian_set_index = index;
+    accs.guardian_set_weights.weights = accs.vaa.weights.clone();
+
+    if !accs.guardian_set_weights.is_initialized() {
+        accs.guardian_set_weights.create(
+            &GuardianSetWeightsDerivationData { index },
+            ctx,
+            accs.payer.key,
+            Exempt,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct InitiateMigration<'b> {
+    /// Payer for account creation (vaa-claim, migration)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadMigrate>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Migration record, created once.
+    pub migration: Mut<Migration<'b, { AccountState::Uninitialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct InitiateMigrationData {}
+
+/// Record the successor program the core state accounts are to be migrated to. A migration can only
+/// be initiated once, the accounts themselves are handed over by
+/// [`migrate_account`](crate::api::migrate_account).
+pub fn initiate_migration(
+    ctx: &ExecutionContext,
+    accs: &mut InitiateMigration,
+    _data: InitiateMigrationData,
+) -> Result<()> {
+    verify_governance(ctx, &accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    accs.migration.successor = accs.vaa.successor;
+    accs.migration.create(ctx, accs.payer.key, Exempt)?;
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct AnnounceGovernance<'b> {
+    /// Payer for the announcement account
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Announcement of the digest, created once
+    pub announcement: Mut<GovernanceAnnouncement<'b, { AccountState::Uninitialized }>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct AnnounceGovernanceData {
+    /// Digest of the governance VAA body, see [`governance_digest`]
+    pub digest: [u8; 32],
+}
+
+/// Publish the digest of an upcoming governance VAA. Anyone can announce a digest, announcing
+/// doesn't make a VAA valid, it only starts the delay the `governance-announcement` feature
+/// requires before the VAA can be executed. A digest can only be announced once so the delay can't
+/// be reset.
+pub fn announce_governance(
+    ctx: &ExecutionContext,
+    accs: &mut AnnounceGovernance,
+    data: AnnounceGovernanceData,
+) -> Result<()> {
+    let derivation_data = GovernanceAnnouncementDerivationData {
+        digest: data.digest,
+    };
+    accs.announcement
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    accs.announcement.digest = data.digest;
+    accs.announcement.slot = accs.clock.slot;
+    trace_kv!("governance_announcement", slot = accs.clock.slot);
+
+    accs.announcement
+        .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct QueueGovernance<'b> {
+    /// Payer for the pending governance account
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Governance VAA, posted by `post_vaa`
+    pub vaa: PostedVAA<'b, { AccountState::Initialized }>,
+
+    /// Queued VAA, created once
+    pub pending: Mut<PendingGovernance<'b, { AccountState::Uninitialized }>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct QueueGovernanceData {}
+
+/// Queue a verified governance VAA, starting the [`GOVERNANCE_TIMELOCK_DELAY`] the
+/// `governance-timelock` feature requires before it can be executed. The VAA is then executed by
+/// the instruction of its decree as usual, which consumes it and must be passed the pending
+/// account, see `instructions::execute_governance`. Anyone can queue a governance VAA, but only
+/// once, so the delay can't be reset.
+pub fn queue_governance(
+    ctx: &ExecutionContext,
+    accs: &mut QueueGovernance,
+    _data: QueueGovernanceData,
+) -> Result<()> {
+    let vaa: &MessageData = &accs.vaa;
+    let emitter = EmitterInfo {
+        chain: vaa.emitter_chain,
+        address: vaa.emitter_address,
+        sequence: vaa.sequence,
+    };
+    if !emitter.try_is(CHAIN_ID_GOVERANCE, std::env!("EMITTER_ADDRESS"))? {
+        return Err(InvalidGovernanceKey.into());
+    }
+
+    // Posted messages deserialize as VAAs too, only accounts derived from the digest of a VAA body
+    // were created by post_vaa.
+    let digest = governance_digest(vaa)?;
+    accs.vaa.verify_derivation(
+        ctx.program_id,
+        &PostedVAADerivationData {
+            payload_hash: digest.to_vec(),
+        },
+    )?;
+
+    let derivation_data = PendingGovernanceDerivationData { digest };
+    accs.pending
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    let unlock_slot = accs.clock.slot.saturating_add(GOVERNANCE_TIMELOCK_DELAY);
+    accs.pending.digest = digest;
+    accs.pending.unlock_slot = unlock_slot;
+    sol_log_data(&[b"GovernanceQueued", &digest, &unlock_slot.to_be_bytes()]);
+
+    accs.pending
+        .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct SetGuardianSetFloor<'b> {
+    /// Payer for account creation (vaa-claim, floor)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetGuardianSetFloor>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Guardian set floor, created when first set.
+    pub floor: Mut<GuardianSetFloor<'b, { AccountState::MaybeInitialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetGuardianSetFloorData {}
+
+/// Set the minimum number of keys a guardian set can be upgraded to without a confirmed
+/// downgrade. A pending confirmation is kept, it is tied to a single upgrade VAA.
+pub fn set_guardian_set_floor(
+    ctx: &ExecutionContext,
+    accs: &mut SetGuardianSetFloor,
+    _data: SetGuardianSetFloorData,
+) -> Result<()> {
+    verify_governance(ctx, &accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    accs.floor.min_keys = accs.vaa.min_keys;
+
+    if !accs.floor.is_initialized() {
+        accs.floor.create(ctx, accs.payer.key, Exempt)?;
+    }
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct ConfirmGuardianSetDowngrade<'b> {
+    /// Payer for account creation (vaa-claim)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadConfirmGuardianSetDowngrade>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Guardian set floor the downgrade is confirmed against.
+    pub floor: Mut<GuardianSetFloor<'b, { AccountState::Initialized }>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct ConfirmGuardianSetDowngradeData {}
+
+/// Confirm the guardian set upgrade VAA with the given body digest, see [`governance_digest`],
+/// allowing it to shrink the guardian set below the floor once the delay passed. A new
+/// confirmation replaces the previous one and restarts the delay.
+pub fn confirm_guardian_set_downgrade(
+    ctx: &ExecutionContext,
+    accs: &mut ConfirmGuardianSetDowngrade,
+    _data: ConfirmGuardianSetDowngradeData,
+) -> Result<()> {
+    verify_governance(ctx, &accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    accs.floor.confirmed_digest = accs.vaa.digest;
+    accs.floor.confirmed_slot = accs.clock.slot;
+    trace_kv!("guardian_set_downgrade", slot = accs.clock.slot);
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct SetFeeBurn<'b> {
+    /// Payer for account creation (vaa-claim, fee burn)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetFeeBurn>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Fee burn mode, created when first set.
+    pub fee_burn: Mut<FeeBurn<'b, { AccountState::MaybeInitialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetFeeBurnData {}
+
+/// Switch between accumulating collected message fees for [`transfer_fees`] and burning them with
+/// [`burn_fees`](crate::api::burn_fees). The burned total is kept across switches.
+pub fn set_fee_burn(
+    ctx: &ExecutionContext,
+    accs: &mut SetFeeBurn,
+    _data: SetFeeBurnData,
+) -> Result<()> {
+    verify_governance(ctx, &accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    accs.fee_burn.enabled = accs.vaa.enabled;
+    sol_log_data(&[b"FeeBurnSet", &[accs.vaa.enabled as u8]]);
+
+    if !accs.fee_burn.is_initialized() {
+        accs.fee_burn.create(ctx, accs.payer.key, Exempt)?;
+    }
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct SetSecp256r1Verification<'b> {
+    /// Payer for account creation (vaa-claim, secp256r1 verification)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetSecp256r1Verification>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Secp256r1 verification flag, created when first set.
+    pub secp256r1_verification: Mut<Secp256r1Verification<'b, { AccountState::MaybeInitialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetSecp256r1VerificationData {}
+
+/// Enable or disable guardian signatures verified by the secp256r1 precompile. Bridges built
+/// without the `secp256r1` feature record the flag but keep accepting secp256k1 signatures only.
+pub fn set_secp256r1_verification(
+    ctx: &ExecutionContext,
+    accs: &mut SetSecp256r1Verification,
+    _data: SetSecp256r1VerificationData,
+) -> Result<()> {
+    verify_governance(ctx, &accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    accs.secp256r1_verification.enabled = accs.vaa.enabled;
+    sol_log_data(&[b"Secp256r1VerificationSet", &[accs.vaa.enabled as u8]]);
+
+    if !accs.secp256r1_verification.is_initialized() {
+        accs.secp256r1_verification
+            .create(ctx, accs.payer.key, Exempt)?;
+    }
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct SetFeeOracle<'b> {
+    /// Payer for account creation (vaa-claim, fee oracle)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetFeeOracle>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Fee oracle config, created when first set.
+    pub fee_oracle: Mut<FeeOracle<'b, { AccountState::MaybeInitialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetFeeOracleData {}
+
+/// Reference the oracle account message fees track, and bound the fees it can set. Bridges built
+/// without the `fee-oracle` feature record the oracle but keep charging the static fee.
+pub fn set_fee_oracle(
+    ctx: &ExecutionContext,
+    accs: &mut SetFeeOracle,
+    _data: SetFeeOracleData,
+) -> Result<()> {
+    verify_governance(ctx, &accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    if accs.vaa.max_fee > u64::MAX.into() || accs.vaa.min_fee > accs.vaa.max_fee {
+        return Err(InvalidFeeOracle.into());
+    }
+
+    accs.fee_oracle.oracle = accs.vaa.oracle;
+    accs.fee_oracle.min_fee = accs.vaa.min_fee.as_u64();
+    accs.fee_oracle.max_fee = accs.vaa.max_fee.as_u64();
+
+    if !accs.fee_oracle.is_initialized() {
+        accs.fee_oracle.create(ctx, accs.payer.key, Exempt)?;
+    }
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct SetGuardianSetExpiry<'b> {
+    /// Payer for account creation (vaa-claim, guardian set expiry)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetGuardianSetExpiry>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Guardian set expiry mode, created when first set.
+    pub guardian_set_expiry: Mut<GuardianSetExpiry<'b, { AccountState::MaybeInitialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetGuardianSetExpiryData {}
+
+/// Select soft or hard expiry of guardian sets replaced by an upgrade. Under soft expiry VAAs of a
+/// set are still accepted up to `soft_window` seconds after it expired, see [`GuardianSetExpiry`].
+pub fn set_guardian_set_expiry(
+    ctx: &ExecutionContext,
+    accs: &mut SetGuardianSetExpiry,
+    _data: SetGuardianSetExpiryData,
+) -> Result<()> {
+    verify_governance(ctx, &accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    accs.guardian_set_expiry.soft = accs.vaa.soft;
+    accs.guardian_set_expiry.soft_window = accs.vaa.soft_window;
+    sol_log_data(&[
+        b"GuardianSetExpirySet",
+        &[accs.vaa.soft as u8],
+        &accs.vaa.soft_window.to_be_bytes(),
+    ]);
+
+    if !accs.guardian_set_expiry.is_initialized() {
+        accs.guardian_set_expiry
+            .create(ctx, accs.payer.key, Exempt)?;
+    }
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct SetQuorum<'b> {
+    /// Payer for account creation and extension (vaa-claim, bridge config, guardian set)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Bridge config
+    pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetQuorum>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Active guardian set, which takes the new quorum right away
+    pub guardian_set: Mut<GuardianSet<'b, { AccountState::Initialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetQuorumData {}
+
+/// Change the fraction of a guardian set that must sign for consensus. The active set and the
+/// sets later upgraded to take the new quorum, sets replaced before keep theirs until they expire.
+/// Weighted guardian sets keep reaching consensus by two thirds of their weight.
+pub fn set_quorum(
+    ctx: &ExecutionContext,
+    accs: &mut SetQuorum,
+    _data: SetQuorumData,
+) -> Result<()> {
+    verify_governance(ctx, &accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    accs.guardian_set.verify_derivation(
+        ctx.program_id,
+        &GuardianSetDerivationData {
+            index: accs.bridge.guardian_set_index,
+        },
+    )?;
+
+    let quorum = accs.vaa.quorum;
+    if !quorum.is_valid() {
+        return Err(InvalidQuorum.into());
+    }
+    accs.bridge.config.quorum = quorum;
+    accs.guardian_set.quorum = quorum;
+    sol_log_data(&[
+        b"QuorumSet",
+        &accs.guardian_set.index.to_be_bytes(),
+        &[quorum.numerator, quorum.denominator],
+    ]);
+
+    // Both accounts are resized to the new layout, which is persisted after this instruction.
+    let size = accs.bridge.size();
+    resize(ctx, accs.bridge.info(), accs.payer.key, size)?;
+    let size = accs.guardian_set.size();
+    resize(ctx, accs.guardian_set.info(), accs.payer.key, size)
+}
+
+#[derive(FromAccounts)]
+pub struct SetPaused<'b> {
+    /// Payer for account creation and extension (vaa-claim, bridge config)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Bridge config
+    pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetPaused>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetPausedData {}
+
+/// Pause the instruction families of the decree and unpause all others, the circuit breaker for
+/// incident response. Paused emission fails every instruction posting a message, and with it
+/// outbound token bridge transfers. Paused verification fails posting VAAs other than those of
+/// governance. The inbound and outbound transfer families are enforced by the token bridge.
+pub fn set_paused(
+    ctx: &ExecutionContext,
+    accs: &mut SetPaused,
+    _data: SetPausedData,
+) -> Result<()> {
+    verify_governance(ctx, &accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    accs.bridge.config.paused = accs.vaa.paused;
+    sol_log_data(&[b"PausedSet", &[accs.vaa.paused]]);
+
+    // The bridge config is resized to the new layout, which is persisted after this instruction.
+    let size = accs.bridge.size();
+    resize(ctx, accs.bridge.info(), accs.payer.key, size)
+}
+
+#[derive(FromAccounts)]
+pub struct SetSuccessor<'b> {
+    /// Payer for account creation and extension (vaa-claim, bridge config)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Bridge config
+    pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetSuccessor>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetSuccessorData {}
+
+/// Wire the successor program that `forward_to_successor` forwards instructions to after a
+/// rewrite of the core bridge, or unwire it with the zero key at the end of the deprecation window.
+/// Only wrapped instructions are forwarded, every other instruction still executes here.
+pub fn set_successor(
+    ctx: &ExecutionContext,
+    accs: &mut SetSuccessor,
+    _data: SetSuccessorData,
+) -> Result<()> {
+    verify_governance(ctx, &accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    if accs.vaa.successor == *ctx.program_id {
+        return Err(InvalidSuccessor.into());
+    }
+    accs.bridge.config.successor = accs.vaa.successor;
+    sol_log_data(&[b"SuccessorSet", accs.vaa.successor.as_ref()]);
+
+    let size = accs.bridge.size();
+    resize(ctx, accs.bridge.info(), accs.payer.key, size)
+}
+
+#[derive(FromAccounts)]
+pub struct SetEmissionBreaker<'b> {
+    /// Payer for account creation and extension (vaa-claim, bridge config)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Bridge config
+    pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetEmissionBreaker>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetEmissionBreakerData {}
+
+/// Set the multiple of their trailing average that registered emitters may post per epoch before
+/// their emission breaker trips, or disable the breaker with 0. See
+/// [`EmitterStats`](crate::accounts::EmitterStats). Breakers that tripped stay tripped.
+pub fn set_emission_breaker(
+    ctx: &ExecutionContext,
+    accs: &mut SetEmissionBreaker,
+    _data: SetEmissionBreakerData,
+) -> Result<()> {
+    verify_governance(ctx, &accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    accs.bridge.config.emission_breaker_multiple = accs.vaa.multiple;
+    sol_log_data(&[b"EmissionBreakerSet", &accs.vaa.multiple.to_be_bytes()]);
+
+    let size = accs.bridge.size();
+    resize(ctx, accs.bridge.info(), accs.payer.key, size)
+}
+
+#[derive(FromAccounts)]
+pub struct ReleaseEmissionBreaker<'b> {
+    /// Payer for account creation (vaa-claim)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadReleaseEmissionBreaker>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Stats of the emitter named by the VAA
+    pub emitter_stats: Mut<EmitterStats<'b, { AccountState::Initialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct ReleaseEmissionBreakerData {}
+
+/// Clear the tripped emission breaker of an emitter on behalf of governance, for emitters that
+/// can't sign for [`clear_emission_breaker`](crate::api::clear_emission_breaker) themselves.
+pub fn release_emission_breaker(
+    ctx: &ExecutionContext,
+    accs: &mut ReleaseEmissionBreaker,
+    _data: ReleaseEmissionBreakerData,
+) -> Result<()> {
+    verify_governance(ctx, &accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    let emitter = accs.vaa.emitter;
+    accs.emitter_stats.verify_derivation(
+        ctx.program_id,
+        &EmitterStatsDerivationData { emitter: &emitter },
+    )?;
+
+    accs.emitter_stats.clear();
+    sol_log_data(&[b"EmissionBreakerCleared", emitter.as_ref()]);
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct SetClaimGracePeriod<'b> {
+    /// Payer for account creation and extension (vaa-claim, bridge config)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Bridge config
+    pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetClaimGracePeriod>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetClaimGracePeriodData {}
+
+/// Set how long after the expiry of the guardian set that signed a VAA its claim can be closed
+/// with [`close_claim`](crate::api::close_claim), and who receives the rent. The default recipient
+/// disables closing claims.
+pub fn set_claim_grace_period(
+    ctx: &ExecutionContext,
+    accs: &mut SetClaimGracePeriod,
+    _data: SetClaimGracePeriodData,
+) -> Result<()> {
+    verify_governance(ctx, &accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    accs.bridge.config.claim_grace_period = accs.vaa.grace_period;
+    accs.bridge.config.claim_recipient = accs.vaa.recipient;
+    sol_log_data(&[
+        b"ClaimGracePeriodSet",
+        &accs.vaa.grace_period.to_be_bytes(),
+        accs.vaa.recipient.as_ref(),
+    ]);
+
+    let size = accs.bridge.size();
+    resize(ctx, accs.bridge.info(), accs.payer.key, size)
+}
+
+#[derive(FromAccounts)]
+pub struct SetTokenFee<'b> {
+    /// Payer for account creation and extension (vaa-claim, bridge config)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Bridge config
+    pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetTokenFee>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Token account of the fee mint owned by the fee collector, collecting token fees. Ignored
+    /// when disabling token fees.
+    pub fee_token_account: Info<'b>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetTokenFeeData {}
+
+/// Designate the SPL mint and the per-message fee in its tokens that emitters may pay the bridge
+/// fee with instead of lamports, see [`post_message`](crate::api::post_message). The fees are
+/// collected in a token account of the mint owned by the fee collector, which must exist. The
+/// default mint disables token fees.
+pub fn set_token_fee(
+    ctx: &ExecutionContext,
+    accs: &mut SetTokenFee,
+    _data: SetTokenFeeData,
+) -> Result<()> {
+    verify_governance(ctx, &accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    let mint = accs.vaa.mint;
+    let config = &mut accs.bridge.config;
+    if mint == Pubkey::default() {
+        config.fee_mint = Pubkey::default();
+        config.token_fee = 0;
+        config.fee_token_account = Pubkey::default();
+        config.last_fee_tokens = 0;
+    } else {
+        // Tokens already in the account don't pay for messages.
+        config.last_fee_tokens = fee_token_balance(&accs.fee_token_account, &mint, ctx.program_id)?;
+        config.fee_mint = mint;
+        config.token_fee = accs.vaa.fee.as_u64();
+        config.fee_token_account = *accs.fee_token_account.key;
+    }
+    sol_log_data(&[
+        b"TokenFeeSet",
+        mint.as_ref(),
+        &config.token_fee.to_be_bytes(),
+    ]);
+
+    let size = accs.bridge.size();
+    resize(ctx, accs.bridge.info(), accs.payer.key, size)
+}
+
+#[derive(FromAccounts)]
+pub struct SetFeeExemption<'b> {
+    /// Payer for account creation (vaa-claim, fee exemption)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetFeeExemption>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Exemption of the emitter named by the VAA, created by its first decree
+    pub fee_exemption: Mut<FeeExemption<'b, { AccountState::MaybeInitialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetFeeExemptionData {}
+
+/// Exempt an emitter from the message fee, or revoke its exemption. Posts of an exempted emitter
+/// that pass its [`FeeExemption`] pay no fee, see
+/// [`with_fee_exemption`](crate::instructions::with_fee_exemption).
+pub fn set_fee_exemption(
+    ctx: &ExecutionContext,
+    accs: &mut SetFeeExemption,
+    _data: SetFeeExemptionData,
+) -> Result<()> {
+    verify_governance(ctx, &accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    let emitter = accs.vaa.emitter;
+    let derivation_data = FeeExemptionDerivationData { emitter: &emitter };
+    accs.fee_exemption
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    accs.fee_exemption.exempt = accs.vaa.exempt;
+    if !accs.fee_exemption.is_initialized() {
+        accs.fee_exemption
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+    }
+    sol_log_data(&[
+        b"FeeExemptionSet",
+        emitter.as_ref(),
+        &[accs.vaa.exempt as u8],
+    ]);
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct SetClaimConsumer<'b> {
+    /// Payer for account creation (vaa-claim, claim consumer)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetClaimConsumer>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Approval of the program named by the VAA, created by its first decree
+    pub claim_consumer: Mut<ClaimConsumer<'b, { AccountState::MaybeInitialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetClaimConsumerData {}
+
+/// Approve a program whose claims prove that posted VAAs were redeemed, or revoke its approval.
+/// Posted VAAs claimed by the program can then be closed with
+/// [`close_posted_vaa`](crate::api::close_posted_vaa).
+pub fn set_claim_consumer(
+    ctx: &ExecutionContext,
+    accs: &mut SetClaimConsumer,
+    _data: SetClaimConsumerData,
+) -> Result<()> {
+    verify_governance(ctx, &accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    let program = accs.vaa.program;
+    let derivation_data = ClaimConsumerDerivationData { program: &program };
+    accs.claim_consumer
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    accs.claim_consumer.approved = accs.vaa.approved;
+    if !accs.claim_consumer.is_initialized() {
+        accs.claim_consumer
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+    }
+    sol_log_data(&[
+        b"ClaimConsumerSet",
+        program.as_ref(),
+        &[accs.vaa.approved as u8],
+    ]);
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct SetVerifier<'b> {
+    /// Payer for account creation (vaa-claim, verifier)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetVerifier>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Approval of the program named by the VAA, created by its first decree
+    pub verifier: Mut<Verifier<'b, { AccountState::MaybeInitialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetVerifierData {}
+
+/// Approve a program verifying VAAs in place of the bridge, or revoke its approval. Processors
+/// then accept the VAAs the program owns as posted VAAs when passed its approval, see
+/// [`with_verifier`](crate::instructions::with_verifier).
+pub fn set_verifier(
+    ctx: &ExecutionContext,
+    accs: &mut SetVerifier,
+    _data: SetVerifierData,
+) -> Result<()> {
+    verify_governance(ctx, &accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    let program = accs.vaa.program;
+    let derivation_data = VerifierDerivationData { program: &program };
+    accs.verifier
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    accs.verifier.approved = accs.vaa.approved;
+    if !accs.verifier.is_initialized() {
+        accs.verifier
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+    }
+    sol_log_data(&[b"VerifierSet", program.as_ref(), &[accs.vaa.approved as u8]]);
+
+    Ok(())
+}
+
+/// Grow or shrink `account` to `size`, topping up its rent from `payer` when it grows.
+fn resize(ctx: &ExecutionContext, account: &Info, payer: &Pubkey, size: usize) -> Result<()> {
+    ensure_account_size(ctx, account, payer, size)?;
+    if account.data_len() > size {
+        account.realloc(size, false)?;
+    }
     Ok(())
 }
@@ -5,11 +5,15 @@ use crate::{
         FeeCollector,
         GuardianSet,
         GuardianSetDerivationData,
+        Quorum,
     },
     error::Error::TooManyGuardians,
-    MAX_LEN_GUARDIAN_KEYS,
+    MAX_GUARDIANS,
+};
+use solana_program::{
+    pubkey::Pubkey,
+    sysvar::clock::Clock,
 };
-use solana_program::sysvar::clock::Clock;
 use solitaire::{
     CreationLamports::Exempt,
     *,
@@ -56,7 +60,7 @@ pub fn initialize(
 ) -> Result<()> {
     let index = 0;
 
-    if data.initial_guardians.len() > MAX_LEN_GUARDIAN_KEYS {
+    if data.initial_guardians.len() > MAX_GUARDIANS {
         return Err(TooManyGuardians.into());
     }
 
@@ -79,6 +83,16 @@ pub fn initialize(
     accs.bridge.config = BridgeConfig {
         guardian_set_expiration_time: data.guardian_set_expiration_time,
         fee: data.fee,
+        quorum: Quorum::default(),
+        paused: 0,
+        successor: Pubkey::default(),
+        emission_breaker_multiple: 0,
+        claim_grace_period: 0,
+        claim_recipient: Pubkey::default(),
+        fee_mint: Pubkey::default(),
+        token_fee: 0,
+        fee_token_account: Pubkey::default(),
+        last_fee_tokens: 0,
     };
 
     // Initialize the fee collector account so it's rent exempt and will keep funds
@@ -0,0 +1,227 @@
+use std::str::FromStr;
+
+use borsh::BorshSerialize;
+use solana_program::{
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    log::sol_log_data,
+    pubkey::Pubkey,
+};
+use solitaire::{
+    processors::seeded::{
+        invoke_seeded,
+        Seeded,
+    },
+    *,
+};
+use wormhole_solana_consts::seeds;
+
+use crate::{
+    accounts::{
+        Bridge,
+        GuardianSet,
+        GuardianSetDerivationData,
+        Migration,
+        Sequence,
+        SequenceDerivationData,
+    },
+    error::Error::{
+        InvalidMigrationAccount,
+        InvalidMigrationSource,
+        InvalidSuccessor,
+        NoSuccessor,
+    },
+};
+
+/// Core state accounts that can be migrated, along with the data they are derived from.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy)]
+pub enum MigratedAccount {
+    Bridge,
+    GuardianSet { index: u32 },
+    Sequence { emitter: Pubkey },
+}
+
+impl MigratedAccount {
+    /// Address of the account under `program_id`.
+    pub fn key(&self, program_id: &Pubkey) -> Pubkey {
+        match self {
+            MigratedAccount::Bridge => {
+                Bridge::<'_, { AccountState::Initialized }>::key(None, program_id)
+            }
+            MigratedAccount::GuardianSet { index } => {
+                GuardianSet::<'_, { AccountState::Initialized }>::key(
+                    &GuardianSetDerivationData { index: *index },
+                    program_id,
+                )
+            }
+            MigratedAccount::Sequence { emitter } => Sequence::key(
+                &SequenceDerivationData {
+                    emitter_key: emitter,
+                },
+                program_id,
+            ),
+        }
+    }
+}
+
+#[derive(FromAccounts)]
+pub struct MigrateAccount<'b> {
+    /// Migration record, signs the handover to the successor.
+    pub migration: Migration<'b, { AccountState::Initialized }>,
+
+    /// Core state account handed over to the successor.
+    pub account: Mut<Info<'b>>,
+
+    /// Successor program, which must acknowledge the handover.
+    pub successor: Info<'b>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct MigrateAccountData {
+    pub account: MigratedAccount,
+}
+
+/// Hand a core state account over to the successor recorded by governance. The runtime only allows
+/// reassigning accounts with zeroed data, so the data is cleared, the account reassigned and its
+/// data passed to the successor's `accept_migration`, which restores it and acknowledges the
+/// handover. Anyone can migrate accounts once governance initiated the migration.
+pub fn migrate_account(
+    ctx: &ExecutionContext,
+    accs: &mut MigrateAccount,
+    data: MigrateAccountData,
+) -> Result<()> {
+    if *accs.account.key != data.account.key(ctx.program_id)
+        || accs.account.owner != ctx.program_id
+    {
+        return Err(InvalidMigrationAccount.into());
+    }
+    if *accs.successor.key != accs.migration.successor {
+        return Err(InvalidMigrationSource.into());
+    }
+
+    let account_data = accs.account.data.borrow().to_vec();
+    accs.account.data.borrow_mut().fill(0);
+    accs.account.assign(accs.successor.key);
+    trace_kv!("migrate", account = accs.account.key, successor = accs.successor.key);
+
+    let ix = Instruction {
+        program_id: *accs.successor.key,
+        accounts: vec![
+            AccountMeta::new_readonly(*accs.migration.info().key, true),
+            AccountMeta::new(*accs.account.key, false),
+        ],
+        data: (
+            crate::instruction::Instruction::AcceptMigration,
+            AcceptMigrationData {
+                account: data.account,
+                data: account_data,
+            },
+        )
+            .try_to_vec()?,
+    };
+    invoke_seeded(&ix, ctx, &accs.migration, None)?;
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct AcceptMigration<'b> {
+    /// Migration record of the predecessor program.
+    pub predecessor_migration: Signer<Info<'b>>,
+
+    /// Account handed over by the predecessor.
+    pub account: Mut<Info<'b>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct AcceptMigrationData {
+    pub account: MigratedAccount,
+
+    /// Data of the account before it was handed over.
+    pub data: Vec<u8>,
+}
+
+/// Successor side of [`migrate_account`], accepting accounts from the predecessor set by the
+/// `MIGRATION_PREDECESSOR` environment variable at build time. Only the predecessor's migration
+/// record can sign for the handover, so accounts can't be injected by anyone else.
+pub fn accept_migration(
+    ctx: &ExecutionContext,
+    accs: &mut AcceptMigration,
+    data: AcceptMigrationData,
+) -> Result<()> {
+    let predecessor = option_env!("MIGRATION_PREDECESSOR")
+        .and_then(|key| Pubkey::from_str(key).ok())
+        .ok_or(InvalidMigrationSource)?;
+    let (expected_signer, _) =
+        Pubkey::find_program_address(&[seeds::MIGRATION.as_bytes()], &predecessor);
+    if *accs.predecessor_migration.key != expected_signer {
+        return Err(InvalidMigrationSource.into());
+    }
+
+    if *accs.account.key != data.account.key(&predecessor)
+        || accs.account.owner != ctx.program_id
+        || accs.account.data_len() != data.data.len()
+    {
+        return Err(InvalidMigrationAccount.into());
+    }
+
+    accs.account.data.borrow_mut().copy_from_slice(&data.data);
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct ForwardToSuccessor<'b> {
+    /// Bridge config naming the successor.
+    pub bridge: Bridge<'b, { AccountState::Initialized }>,
+
+    /// Successor program the instruction is forwarded to.
+    pub successor: Info<'b>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct ForwardToSuccessorData {
+    /// Instruction data for the successor.
+    pub data: Vec<u8>,
+}
+
+/// Forward an instruction built for this program to the successor wired by governance, with the
+/// accounts following the successor and their signer and writable flags. Forwarding is opt-in:
+/// clients reach the successor through this shim by wrapping their instructions with
+/// [`crate::instructions::forward_to_successor`], while instructions sent under their own tags keep
+/// executing here whether or not a successor is wired. Governance unwires the successor at the end
+/// of the deprecation window.
+pub fn forward_to_successor(
+    ctx: &ExecutionContext,
+    accs: &mut ForwardToSuccessor,
+    data: ForwardToSuccessorData,
+) -> Result<()> {
+    let successor = accs.bridge.config.successor;
+    if successor == Pubkey::default() {
+        return Err(NoSuccessor.into());
+    }
+    if *accs.successor.key != successor {
+        return Err(InvalidSuccessor.into());
+    }
+
+    // Forwarded accounts follow the bridge and the successor.
+    let accounts = ctx.accounts[2..]
+        .iter()
+        .map(|info| AccountMeta {
+            pubkey: *info.key,
+            is_signer: info.is_signer,
+            is_writable: info.is_writable,
+        })
+        .collect();
+    let ix = Instruction {
+        program_id: successor,
+        accounts,
+        data: data.data,
+    };
+    sol_log_data(&[b"ForwardedToSuccessor", successor.as_ref()]);
+    invoke(&ix, ctx.accounts)?;
+
+    Ok(())
+}
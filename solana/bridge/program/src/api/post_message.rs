@@ -1,17 +1,32 @@
 use crate::{
     accounts::{
+        fee_delegator,
+        fee_token_balance,
         Bridge,
+        BridgeConfig,
         FeeCollector,
+        FeeExemption,
+        FeeExemptionData,
+        FeeExemptionDerivationData,
+        FeeInvoice,
+        FeeInvoiceData,
+        FeeInvoiceDerivationData,
         PostedMessage,
+        PostedMessageDerivationData,
         PostedMessageUnreliable,
         Sequence,
         SequenceDerivationData,
     },
+    api::record_emission,
     error::Error::{
         EmitterChanged,
+        FeeInvoiceExpired,
         InsufficientFees,
-        InvalidPayloadLength,
+        InvalidFeeInvoice,
         MathOverflow,
+        NamedAccountsUnsupported,
+        NonceRegression,
+        Paused,
     },
     types::ConsistencyLevel,
     IsSigned::*,
@@ -19,7 +34,17 @@ use crate::{
     CHAIN_ID_SOLANA,
 };
 use solana_program::{
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    keccak,
+    log::sol_log_data,
     msg,
+    program::{
+        invoke_signed,
+        set_return_data,
+    },
     pubkey::Pubkey,
     sysvar::clock::Clock,
 };
@@ -78,6 +103,68 @@ pub struct PostMessageUnreliable<'b> {
     pub clock: Sysvar<'b, Clock>,
 }
 
+#[derive(FromAccounts)]
+pub struct PostMessageDeterministic<'b> {
+    /// Bridge config needed for fee calculation.
+    pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
+
+    /// Account to store the posted message, derived from the emitter and seed
+    pub message: Mut<UninitializedMessage<'b>>,
+
+    /// Emitter of the VAA
+    pub emitter: Signer<MaybeMut<Info<'b>>>,
+
+    /// Tracker for the emitter sequence
+    pub sequence: Mut<Sequence<'b>>,
+
+    /// Payer for account creation
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Account to collect tx fee
+    pub fee_collector: Mut<FeeCollector<'b>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(FromAccounts)]
+pub struct EnableStrictNonce<'b> {
+    /// Emitter opting into strict nonces
+    pub emitter: Signer<MaybeMut<Info<'b>>>,
+
+    /// Tracker for the emitter sequence, extended to track nonces
+    pub sequence: Mut<Sequence<'b>>,
+
+    /// Payer for account creation and extension
+    pub payer: Mut<Signer<Info<'b>>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct EnableStrictNonceData {}
+
+/// Event emitted for every posted message, so that indexers don't depend on logs which may be
+/// truncated. See [`with_event_cpi`](crate::instructions::with_event_cpi).
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct MessagePostedV1 {
+    pub emitter: Pubkey,
+    pub sequence: u64,
+    pub consistency_level: u8,
+
+    /// Keccak256 hash of the payload
+    pub payload_hash: [u8; 32],
+}
+
+// The first version keeps the name, and so the discriminator, the event had before versioning.
+impl Event for MessagePostedV1 {
+    const NAME: &'static str = "MessagePosted";
+    const VERSION: u8 = 1;
+    const DISCRIMINATOR: [u8; 8] = [11, 28, 144, 13, 232, 160, 251, 5];
+    const SCHEMA: &'static str =
+        "emitter:pubkey,sequence:u64,consistency_level:u8,payload_hash:[u8;32]";
+}
+
+/// Current version of the event emitted for every posted message.
+pub type MessagePosted = MessagePostedV1;
+
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct PostMessageData {
     /// Unique nonce for this message
@@ -90,6 +177,9 @@ pub struct PostMessageData {
     pub consistency_level: ConsistencyLevel,
 }
 
+/// Post a message from `emitter`. The sequence assigned to it is returned as a little endian u64,
+/// which programs posting through CPI read with
+/// [`posted_sequence`](crate::instructions::posted_sequence).
 pub fn post_message(
     ctx: &ExecutionContext,
     accs: &mut PostMessage,
@@ -100,7 +190,7 @@ pub fn post_message(
         &mut accs.bridge,
         accs.message.info().key,
         &mut accs.message,
-        &mut accs.emitter,
+        accs.emitter.key,
         &mut accs.sequence,
         &mut accs.payer,
         &mut accs.fee_collector,
@@ -120,6 +210,70 @@ pub fn post_message(
         NotSigned,
     )?;
 
+    emit_message_posted(ctx, &accs.message)?;
+
+    // Set last, invoking the system program clears the return data.
+    set_return_data(&accs.message.sequence.to_le_bytes());
+
+    Ok(())
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct PostMessageDeterministicData {
+    /// Seed of the message account, unique per message of the emitter
+    pub seed: [u8; 32],
+
+    /// Unique nonce for this message
+    pub nonce: u32,
+
+    /// Message payload
+    pub payload: Vec<u8>,
+
+    /// Commitment Level required for an attestation to be produced
+    pub consistency_level: ConsistencyLevel,
+}
+
+/// Post a message like [`post_message`], into a message account derived from the emitter and a
+/// seed it picks instead of a fresh keypair, see
+/// [`PostedMessageDerivationData`](crate::accounts::PostedMessageDerivationData). Programs posting
+/// through CPI can derive the message address ahead of time and don't need to pass an extra
+/// signer through their callers. Seeds can't be reused, the message account is never closed.
+pub fn post_message_deterministic(
+    ctx: &ExecutionContext,
+    accs: &mut PostMessageDeterministic,
+    data: PostMessageDeterministicData,
+) -> Result<()> {
+    let seed = data.seed;
+    let derivation = PostedMessageDerivationData {
+        emitter: accs.emitter.key,
+        seed: &seed,
+    };
+    accs.message
+        .verify_derivation(ctx.program_id, &derivation)?;
+
+    post_message_internal(
+        ctx,
+        &mut accs.bridge,
+        accs.message.info().key,
+        &mut accs.message,
+        accs.emitter.key,
+        &mut accs.sequence,
+        &mut accs.payer,
+        &mut accs.fee_collector,
+        &mut accs.clock,
+        PostMessageData {
+            nonce: data.nonce,
+            payload: data.payload,
+            consistency_level: data.consistency_level,
+        },
+    )?;
+
+    accs.message
+        .create(&derivation, ctx, accs.payer.key, Exempt)?;
+
+    emit_message_posted(ctx, &accs.message)?;
+    set_return_data(&accs.message.sequence.to_le_bytes());
+
     Ok(())
 }
 
@@ -129,27 +283,29 @@ pub fn post_message(
 /// been overwritten. This makes this instruction useful for use-cases that require high number of
 /// messages to be published but don't require 100% delivery guarantee.
 /// DO NOT USE THIS FOR USE-CASES THAT MOVE VALUE; MESSAGES MAY NOT BE DELIVERED
+///
+/// Reusing an account with a payload of another length resizes it, growing it at the payer's
+/// expense or refunding the payer the rent it no longer needs.
+///
+/// Returns the assigned sequence like [`post_message`].
 pub fn post_message_unreliable(
     ctx: &ExecutionContext,
     accs: &mut PostMessageUnreliable,
     data: PostMessageData,
 ) -> Result<()> {
-    // Accounts can't be resized so the payload sizes need to match
-    if accs.message.is_initialized() && accs.message.payload.len() != data.payload.len() {
-        return Err(InvalidPayloadLength.into());
-    }
     // The emitter must be identical
     if accs.message.is_initialized() && accs.emitter.key.to_bytes() != accs.message.emitter_address
     {
         return Err(EmitterChanged.into());
     }
 
+    let nonce = data.nonce;
     post_message_internal(
         ctx,
         &mut accs.bridge,
         accs.message.info().key,
         &mut accs.message,
-        &mut accs.emitter,
+        accs.emitter.key,
         &mut accs.sequence,
         &mut accs.payer,
         &mut accs.fee_collector,
@@ -157,6 +313,24 @@ pub fn post_message_unreliable(
         data,
     )?;
 
+    // Emitters that opted into strict nonces can't publish out of order, see
+    // [`enable_strict_nonce`].
+    if let Some(min_nonce) = accs.sequence.min_unreliable_nonce {
+        trace_kv!("check", kind = "nonce", nonce = nonce, min_nonce = min_nonce);
+        if u64::from(nonce) < min_nonce {
+            return Err(NonceRegression.into());
+        }
+        accs.sequence.min_unreliable_nonce = Some(u64::from(nonce) + 1);
+    }
+
+    // Event for consumers following the emitter, as reused accounts are overwritten in place.
+    sol_log_data(&[
+        b"MessageUnreliable",
+        accs.emitter.key.as_ref(),
+        &accs.message.sequence.to_le_bytes(),
+        &nonce.to_le_bytes(),
+    ]);
+
     if !accs.message.is_initialized() {
         // Create message account
         let size = accs.message.size();
@@ -169,18 +343,270 @@ pub fn post_message_unreliable(
             ctx.program_id,
             NotSigned,
         )?;
+    } else {
+        // Payloads may change length, with the payer settling the rent difference.
+        let size = accs.message.size();
+        resize_account(ctx, accs.message.info(), &accs.payer, size)?;
     }
 
+    emit_message_posted(ctx, &accs.message)?;
+    set_return_data(&accs.message.sequence.to_le_bytes());
+
     Ok(())
 }
 
+/// Opt the emitter into strict nonces for its unreliable messages. From then on every unreliable
+/// message must carry a nonce greater than the previous one, giving consumers an ordering and
+/// staleness guarantee at the source. The mode can't be disabled again.
+pub fn enable_strict_nonce(
+    ctx: &ExecutionContext,
+    accs: &mut EnableStrictNonce,
+    _data: EnableStrictNonceData,
+) -> Result<()> {
+    let sequence_derivation = SequenceDerivationData {
+        emitter_key: accs.emitter.key,
+    };
+    accs.sequence
+        .verify_derivation(ctx.program_id, &sequence_derivation)?;
+
+    if accs.sequence.min_unreliable_nonce.is_some() {
+        return Ok(());
+    }
+    accs.sequence.min_unreliable_nonce = Some(0);
+
+    if !accs.sequence.is_initialized() {
+        trace!("Initializing Sequence account with strict nonces.");
+        return accs
+            .sequence
+            .create(&sequence_derivation, ctx, accs.payer.key, Exempt);
+    }
+
+    ensure_account_size(
+        ctx,
+        accs.sequence.info(),
+        accs.payer.key,
+        accs.sequence.size(),
+    )
+}
+
+/// Fee charged for a message. With the `fee-oracle` feature, posts passing the fee oracle config
+/// and the oracle account it references pay the fee the oracle suggests clamped to the
+/// governance-set bounds, see [`with_fee_oracle`](crate::instructions::with_fee_oracle). Other
+/// posts pay the static fee, which governance should keep at the upper bound.
+#[cfg_attr(not(feature = "fee-oracle"), allow(unused_variables))]
+pub(crate) fn message_fee(
+    ctx: &ExecutionContext,
+    bridge: &Bridge<'_, { AccountState::Initialized }>,
+) -> Result<u64> {
+    #[cfg(feature = "fee-oracle")]
+    {
+        use crate::{
+            accounts::{
+                FeeOracle,
+                FeeOracleData,
+            },
+            error::Error::InvalidFeeOracle,
+        };
+
+        let key = FeeOracle::<'_, { AccountState::Initialized }>::key(None, ctx.program_id);
+        if let Some(info) = ctx.accounts.iter().find(|info| *info.key == key) {
+            if info.owner != ctx.program_id {
+                return Err(SolitaireError::InvalidOwner(*info.owner));
+            }
+            let config = FeeOracleData::try_from_slice(&info.data.borrow())?;
+            let oracle = ctx
+                .accounts
+                .iter()
+                .find(|info| config.oracle != Pubkey::default() && *info.key == config.oracle);
+            if let Some(oracle) = oracle {
+                let fee = config.fee(&oracle.data.borrow()).ok_or(InvalidFeeOracle)?;
+                trace_kv!("check", kind = "fee_oracle", oracle = oracle.key, fee = fee);
+                return Ok(fee);
+            }
+        }
+    }
+
+    Ok(bridge.config.fee)
+}
+
+/// Whether governance exempted `emitter` from the message fee and the post passes its exemption,
+/// see [`with_fee_exemption`](crate::instructions::with_fee_exemption).
+fn fee_exempt(ctx: &ExecutionContext, emitter: &Pubkey) -> Result<bool> {
+    let key = FeeExemption::<'_, { AccountState::Initialized }>::key(
+        &FeeExemptionDerivationData { emitter },
+        ctx.program_id,
+    );
+    let info = match ctx.accounts.iter().find(|info| *info.key == key) {
+        Some(info) if !info.data_is_empty() => info,
+        _ => return Ok(false),
+    };
+    if info.owner != ctx.program_id {
+        return Err(SolitaireError::InvalidOwner(*info.owner));
+    }
+
+    let exemption = FeeExemptionData::try_from_slice(&info.data.borrow())?;
+    trace_kv!(
+        "check",
+        kind = "fee_exemption",
+        exemption = info.key,
+        exempt = exemption.exempt
+    );
+    Ok(exemption.exempt)
+}
+
+/// Charge the fee in tokens if governance designated a fee mint and the post passes the fee token
+/// account, see [`with_token_fee`](crate::instructions::with_token_fee). Like lamports, the fee is
+/// found by comparing the balance of the account to the one after the last token fee, so emitters
+/// can transfer it from any token account. Token fees replace the lamport fee, invoices included.
+///
+/// Short of the fee, the bridge pulls the rest from a token account of the fee mint that approved
+/// the fee collector as its delegate, if the post passes one and its owner signed the post. See
+/// [`with_token_fee_delegate`](crate::instructions::with_token_fee_delegate).
+fn pay_token_fee(
+    ctx: &ExecutionContext,
+    bridge: &mut Bridge<'_, { AccountState::Initialized }>,
+) -> Result<bool> {
+    let config = &bridge.config;
+    if config.fee_mint == Pubkey::default() {
+        return Ok(false);
+    }
+    let info = match ctx
+        .accounts
+        .iter()
+        .find(|info| *info.key == config.fee_token_account)
+    {
+        Some(info) => info,
+        None => return Ok(false),
+    };
+
+    let mut balance = fee_token_balance(info, &config.fee_mint, ctx.program_id)?;
+    let owed = config
+        .last_fee_tokens
+        .checked_add(config.token_fee)
+        .ok_or(MathOverflow)?;
+    if balance < owed {
+        let source = ctx.accounts.iter().find(|source| {
+            source.key != info.key
+                && fee_delegator(source, &config.fee_mint, ctx.program_id).map_or(false, |owner| {
+                    ctx.accounts
+                        .iter()
+                        .any(|signer| signer.is_signer && *signer.key == owner)
+                })
+        });
+        if let Some(source) = source {
+            let collector = FeeCollector::key(None, ctx.program_id);
+            let mut data = vec![TOKEN_TRANSFER];
+            data.extend_from_slice(&(owed - balance).to_le_bytes());
+            let transfer_ix = Instruction::new_with_bytes(
+                *source.owner,
+                &data,
+                vec![
+                    AccountMeta::new(*source.key, false),
+                    AccountMeta::new(*info.key, false),
+                    AccountMeta::new_readonly(collector, true),
+                ],
+            );
+            let seeds = FeeCollector::bumped_seeds(None, ctx.program_id);
+            let seeds: Vec<&[u8]> = seeds.iter().map(|item| item.as_slice()).collect();
+            invoke_signed(&transfer_ix, ctx.accounts, &[seeds.as_slice()])?;
+            trace_kv!(
+                "check",
+                kind = "token_fee_delegate",
+                source = source.key,
+                amount = owed - balance
+            );
+            balance = fee_token_balance(info, &config.fee_mint, ctx.program_id)?;
+        }
+    }
+    trace_kv!(
+        "check",
+        kind = "token_fee",
+        fee = config.token_fee,
+        last_fee_tokens = config.last_fee_tokens,
+        balance = balance
+    );
+    if balance
+        .checked_sub(config.last_fee_tokens)
+        .ok_or(MathOverflow)?
+        < config.token_fee
+    {
+        return Err(InsufficientFees.into());
+    }
+    bridge.config.last_fee_tokens = balance;
+    Ok(true)
+}
+
+/// Tag of the token program instruction transferring tokens, signed by the owner or the delegate.
+const TOKEN_TRANSFER: u8 = 3;
+
+/// Fee quoted for the message by its invoice, if the post passes one. See
+/// [`with_fee_invoice`](crate::instructions::with_fee_invoice).
+fn invoice_fee(
+    ctx: &ExecutionContext,
+    message_key: &Pubkey,
+    data: &PostMessageData,
+    slot: u64,
+) -> Result<Option<u64>> {
+    let key = FeeInvoice::<'_, { AccountState::Initialized }>::key(
+        &FeeInvoiceDerivationData {
+            message: message_key,
+        },
+        ctx.program_id,
+    );
+    let info = match ctx.accounts.iter().find(|info| *info.key == key) {
+        Some(info) => info,
+        None => return Ok(None),
+    };
+    if info.owner != ctx.program_id {
+        return Err(SolitaireError::InvalidOwner(*info.owner));
+    }
+
+    let invoice = FeeInvoiceData::try_from_slice(&info.data.borrow())?;
+    if invoice.payload_len as usize != data.payload.len()
+        || invoice.consistency_level != data.consistency_level
+    {
+        return Err(InvalidFeeInvoice.into());
+    }
+    if invoice.expiry_slot < slot {
+        return Err(FeeInvoiceExpired.into());
+    }
+    trace_kv!(
+        "check",
+        kind = "fee_invoice",
+        invoice = info.key,
+        fee = invoice.fee
+    );
+    Ok(Some(invoice.fee))
+}
+
+/// Emit [`MessagePosted`] for `message` if the post passes the event authority.
+pub(crate) fn emit_message_posted(ctx: &ExecutionContext, message: &MessageData) -> Result<()> {
+    emit_cpi(
+        ctx,
+        &MessagePosted {
+            emitter: Pubkey::new_from_array(message.emitter_address),
+            sequence: message.sequence,
+            consistency_level: message.consistency_level,
+            payload_hash: keccak::hash(&message.payload).to_bytes(),
+        },
+    )
+}
+
+/// Record a message from `emitter` in `message`, charging the message fee and advancing the
+/// emitter sequence. Callers create or resize the message account, and can't resolve their
+/// accounts by key.
+///
+/// Sequences need no caching within a transaction. The runtime executes instructions, and the
+/// invocations nested in them, one at a time, and every post reads the sequence from its account
+/// and writes the bump back before returning. Messages an emitter posts in one transaction, at any
+/// CPI depth, therefore carry consecutive sequences in execution order.
 #[allow(clippy::too_many_arguments)]
-fn post_message_internal<'b>(
+pub(crate) fn post_message_internal<'b>(
     ctx: &ExecutionContext,
     bridge: &mut Mut<Bridge<'b, { AccountState::Initialized }>>,
-    #[cfg_attr(not(feature = "trace"), allow(unused_variables))] message_key: &Pubkey,
+    message_key: &Pubkey,
     message: &mut MessageData,
-    emitter: &mut Signer<MaybeMut<Info<'b>>>,
+    emitter: &Pubkey,
     sequence: &mut Mut<Sequence<'b>>,
     payer: &mut Mut<Signer<Info<'b>>>,
     fee_collector: &mut Mut<FeeCollector<'b>>,
@@ -188,32 +614,62 @@ fn post_message_internal<'b>(
     data: PostMessageData,
 ) -> Result<()> {
     trace!("Message Address: {}", message_key);
-    trace!("Emitter Address: {}", emitter.info().key);
+    trace!("Emitter Address: {}", emitter);
     trace!("Nonce: {}", data.nonce);
 
+    // Guardians find the message account by its position in the instruction.
+    if ctx.named {
+        return Err(NamedAccountsUnsupported.into());
+    }
+
+    if bridge.config.is_paused(BridgeConfig::PAUSE_EMISSION) {
+        return Err(Paused.into());
+    }
+
     let sequence_derivation = SequenceDerivationData {
-        emitter_key: emitter.key,
+        emitter_key: emitter,
     };
     sequence.verify_derivation(ctx.program_id, &sequence_derivation)?;
+    record_emission(
+        ctx,
+        bridge.config.emission_breaker_multiple,
+        sequence,
+        emitter,
+        payer.key,
+        clock.epoch,
+    )?;
 
-    let fee = bridge.config.fee;
-    // Fee handling, checking previously known balance allows us to not care who is the payer of
-    // this submission.
-    if fee_collector
-        .lamports()
-        .checked_sub(bridge.last_lamports)
-        .ok_or(MathOverflow)?
-        < fee
-    {
-        trace!(
-            "Expected fee not found: fee, last_lamports, collector: {} {} {}",
-            fee,
-            bridge.last_lamports,
-            fee_collector.lamports(),
+    // Exempted emitters skip both fees, leaving the collected balances to the next post.
+    if !fee_exempt(ctx, emitter)? && !pay_token_fee(ctx, bridge)? {
+        let fee = match invoice_fee(ctx, message_key, &data, clock.slot)? {
+            Some(fee) => fee,
+            None => message_fee(ctx, bridge)?,
+        };
+        trace_kv!(
+            "check",
+            kind = "fee",
+            fee = fee,
+            last_lamports = bridge.last_lamports,
+            collector = fee_collector.lamports()
         );
-        return Err(InsufficientFees.into());
+        // Fee handling, checking previously known balance allows us to not care who is the payer
+        // of this submission.
+        if fee_collector
+            .lamports()
+            .checked_sub(bridge.last_lamports)
+            .ok_or(MathOverflow)?
+            < fee
+        {
+            trace!(
+                "Expected fee not found: fee, last_lamports, collector: {} {} {}",
+                fee,
+                bridge.last_lamports,
+                fee_collector.lamports(),
+            );
+            return Err(InsufficientFees.into());
+        }
+        bridge.last_lamports = fee_collector.lamports();
     }
-    bridge.last_lamports = fee_collector.lamports();
 
     // Init sequence tracker if it does not exist yet.
     if !sequence.is_initialized() {
@@ -228,7 +684,7 @@ fn post_message_internal<'b>(
     trace!("Setting Message Details");
     message.submission_time = clock.unix_timestamp as u32;
     message.emitter_chain = CHAIN_ID_SOLANA;
-    message.emitter_address = emitter.key.to_bytes();
+    message.emitter_address = emitter.to_bytes();
     message.nonce = data.nonce;
     message.payload = data.payload;
     message.sequence = sequence.sequence;
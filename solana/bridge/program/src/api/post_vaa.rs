@@ -6,28 +6,34 @@ use borsh::{
 };
 use solana_program::{
     self,
+    log::sol_log_data,
     sysvar::clock::Clock,
 };
 
 use crate::{
     accounts::{
         Bridge,
+        BridgeConfig,
         GuardianSet,
+        GuardianSetData,
         GuardianSetDerivationData,
+        GuardianSetExpiry,
+        GuardianSetExpiryData,
+        GuardianSetWeightsData,
         PostedVAA,
         PostedVAADerivationData,
         SignatureSet,
     },
+    codec::WriteBytes,
     error::Error::{
         GuardianSetMismatch,
+        Paused,
         PostVAAConsensusFailed,
         PostVAAGuardianSetExpired,
         VAAInvalid,
     },
-};
-use byteorder::{
-    BigEndian,
-    WriteBytesExt,
+    vaa::EmitterInfo,
+    CHAIN_ID_GOVERANCE,
 };
 use serde::{
     Deserialize,
@@ -39,9 +45,12 @@ use solitaire::{
     processors::seeded::Seeded,
     CreationLamports::Exempt,
 };
-use std::io::{
-    Cursor,
-    Write,
+use std::{
+    borrow::Cow,
+    io::{
+        Cursor,
+        Write,
+    },
 };
 
 impl From<&PostVAAData> for GuardianSetDerivationData {
@@ -106,36 +115,18 @@ pub fn post_vaa(ctx: &ExecutionContext, accs: &mut PostVAA, vaa: PostVAAData) ->
 
     accs.message
         .verify_derivation(ctx.program_id, &msg_derivation)?;
-    accs.guardian_set
-        .verify_derivation(ctx.program_id, &(&vaa).into())?;
+    let guardian_set = select_guardian_set(ctx, &accs.guardian_set, vaa.guardian_set_index)?;
 
     if accs.message.is_initialized() {
         return Ok(());
     }
 
     // Verify any required invariants before we process the instruction.
-    check_active(&accs.guardian_set, &accs.clock)?;
-    check_valid_sigs(&accs.guardian_set, &accs.signature_set)?;
+    check_verification_unpaused(&accs.bridge_info.config, &vaa)?;
+    check_active_or_recent(ctx, &guardian_set, &accs.clock)?;
+    check_valid_sigs(&guardian_set, &accs.signature_set)?;
     check_integrity(&vaa, &accs.signature_set)?;
-
-    // Count the number of signatures currently present.
-    let signature_count: usize = accs.signature_set.signatures.iter().filter(|v| **v).count();
-
-    // Calculate how many signatures are required to reach consensus. This calculation is in
-    // expanded form to ease auditing.
-    let required_consensus_count = {
-        let len = accs.guardian_set.keys.len();
-        // Fixed point number transformation with one decimal to deal with rounding.
-        let len = (len * 10) / 3;
-        // Multiplication by two to get a 2/3 quorum.
-        let len = len * 2;
-        // Division to bring number back into range.
-        len / 10 + 1
-    };
-
-    if signature_count < required_consensus_count {
-        return Err(PostVAAConsensusFailed.into());
-    }
+    check_consensus(ctx, &guardian_set, &accs.signature_set.signatures)?;
 
     // Persist VAA data
     accs.message.nonce = vaa.nonce;
@@ -147,18 +138,162 @@ pub fn post_vaa(ctx: &ExecutionContext, accs: &mut PostVAA, vaa: PostVAAData) ->
     accs.message.vaa_version = vaa.version;
     accs.message.vaa_time = vaa.timestamp;
     accs.message.vaa_signature_account = *accs.signature_set.info().key;
+    accs.message.submission_time = accs.clock.unix_timestamp as u32;
     accs.message
         .create(&msg_derivation, ctx, accs.payer.key, Exempt)?;
 
     Ok(())
 }
 
+/// Fail while governance paused verification, unless the VAA is one of governance, which must
+/// still be posted to lift the pause.
+pub(crate) fn check_verification_unpaused(config: &BridgeConfig, vaa: &PostVAAData) -> Result<()> {
+    if config.is_paused(BridgeConfig::PAUSE_VERIFICATION)
+        && !EmitterInfo::from(vaa).try_is(CHAIN_ID_GOVERANCE, std::env!("EMITTER_ADDRESS"))?
+    {
+        return Err(Paused.into());
+    }
+    Ok(())
+}
+
+/// Guardian set with `index`, the given one unless clients unsure whether a guardian set rotation
+/// took effect passed both the current and the previous set, see
+/// [`with_guardian_set`](crate::instructions::with_guardian_set).
+pub(crate) fn select_guardian_set<'a>(
+    ctx: &ExecutionContext,
+    guardian_set: &'a GuardianSet<'_, { AccountState::Initialized }>,
+    index: u32,
+) -> Result<Cow<'a, GuardianSetData>> {
+    if guardian_set.index != index {
+        if let Some(found) = find_guardian_set(ctx, |set| set.index == index) {
+            return Ok(Cow::Owned(found));
+        }
+    }
+    guardian_set.verify_derivation(ctx.program_id, &GuardianSetDerivationData { index })?;
+    Ok(Cow::Borrowed(&**guardian_set))
+}
+
+/// Find a guardian set matching `predicate` among the instruction accounts. Only accounts at the
+/// derivation of the set they hold qualify.
+pub(crate) fn find_guardian_set(
+    ctx: &ExecutionContext,
+    predicate: impl Fn(&GuardianSetData) -> bool,
+) -> Option<GuardianSetData> {
+    ctx.accounts
+        .iter()
+        .filter(|info| info.owner == ctx.program_id)
+        .find_map(|info| {
+            let set = GuardianSetData::try_from_slice(&info.data.borrow()).ok()?;
+            if !predicate(&set) {
+                return None;
+            }
+            let key = GuardianSet::<'_, { AccountState::Initialized }>::key(
+                &GuardianSetDerivationData { index: set.index },
+                ctx.program_id,
+            );
+            (*info.key == key).then(|| set)
+        })
+}
+
+/// Enough guardians of the set must have signed for the VAA to reach consensus.
+#[inline(always)]
+pub(crate) fn check_consensus(
+    #[cfg_attr(not(feature = "stake-weighted"), allow(unused_variables))] ctx: &ExecutionContext,
+    guardian_set: &GuardianSetData,
+    signatures: &[bool],
+) -> Result<()> {
+    // Count the number of signatures currently present.
+    let signature_count: usize = signatures.iter().filter(|v| **v).count();
+
+    // Calculate how many signatures are required to reach consensus.
+    let required_consensus_count = guardian_set.quorum();
+
+    trace_kv!(
+        "check",
+        kind = "quorum",
+        guardian_set = guardian_set.index,
+        signatures = signature_count,
+        required = required_consensus_count
+    );
+
+    // With stake weighting enabled, guardian sets that governance has assigned weights to reach
+    // consensus by weight rather than by signature count.
+    #[cfg(feature = "stake-weighted")]
+    let weights = find_guardian_set_weights(ctx, guardian_set)?;
+    #[cfg(not(feature = "stake-weighted"))]
+    let weights: Option<GuardianSetWeightsData> = None;
+
+    let consensus = match weights {
+        Some(weights) => {
+            trace_kv!(
+                "check",
+                kind = "weighted_quorum",
+                guardian_set = guardian_set.index,
+                total_weight = weights.total_weight()
+            );
+            weights.quorum_reached(signatures)
+        }
+        None => signature_count >= required_consensus_count,
+    };
+
+    if !consensus {
+        return Err(PostVAAConsensusFailed.into());
+    }
+
+    Ok(())
+}
+
+/// Find the weights of a guardian set among the instruction accounts. The weights account must
+/// always be passed, an empty account means the guardian set is unweighted.
+#[cfg(feature = "stake-weighted")]
+fn find_guardian_set_weights(
+    ctx: &ExecutionContext,
+    guardian_set: &GuardianSetData,
+) -> Result<Option<GuardianSetWeightsData>> {
+    use crate::{
+        accounts::{
+            GuardianSetWeights,
+            GuardianSetWeightsDerivationData,
+        },
+        error::Error::InvalidGuardianWeights,
+    };
+
+    let key = GuardianSetWeights::<'_, { AccountState::Initialized }>::key(
+        &GuardianSetWeightsDerivationData {
+            index: guardian_set.index,
+        },
+        ctx.program_id,
+    );
+    let info = ctx
+        .accounts
+        .iter()
+        .find(|info| *info.key == key)
+        .ok_or(InvalidGuardianWeights)?;
+
+    if info.data_is_empty() {
+        return Ok(None);
+    }
+    if info.owner != ctx.program_id {
+        return Err(SolitaireError::InvalidOwner(*info.owner));
+    }
+
+    let weights = GuardianSetWeightsData::try_from_slice(&info.data.borrow())?;
+    Ok(Some(weights))
+}
+
 /// A guardian set must not have expired.
 #[inline(always)]
-fn check_active<'r>(
-    guardian_set: &GuardianSet<'r, { AccountState::Initialized }>,
+pub(crate) fn check_active<'r>(
+    guardian_set: &GuardianSetData,
     clock: &Sysvar<'r, Clock>,
 ) -> Result<()> {
+    trace_kv!(
+        "check",
+        kind = "guardian_set_active",
+        guardian_set = guardian_set.index,
+        expiration_time = guardian_set.expiration_time,
+        now = clock.unix_timestamp
+    );
     // IMPORTANT - this is a fix for mainnet wormhole
     // The initial guardian set was never expired so we block it here.
     if guardian_set.index == 0 && guardian_set.creation_time == 1628099186 {
@@ -172,6 +307,51 @@ fn check_active<'r>(
     Ok(())
 }
 
+/// Like [`check_active`], also accepting a guardian set that expired within the soft window while
+/// governance selected soft expiry, see [`GuardianSetExpiry`]. The mode is looked up among the
+/// instruction accounts so account layouts stay unchanged, expiry is hard without it. VAAs accepted
+/// this way are logged as `GuardianSetExpiredAccepted`, with the guardian set index and expiration.
+pub(crate) fn check_active_or_recent<'r>(
+    ctx: &ExecutionContext,
+    guardian_set: &GuardianSetData,
+    clock: &Sysvar<'r, Clock>,
+) -> Result<()> {
+    let err = match check_active(guardian_set, clock) {
+        Ok(()) => return Ok(()),
+        Err(err) => err,
+    };
+
+    // Sets blocked without ever expiring stay blocked.
+    let expiry = match find_guardian_set_expiry(ctx)? {
+        Some(expiry) if guardian_set.expiration_time != 0 => expiry,
+        _ => return Err(err),
+    };
+    if !expiry.accepts(guardian_set.expiration_time, clock.unix_timestamp) {
+        return Err(err);
+    }
+
+    sol_log_data(&[
+        b"GuardianSetExpiredAccepted",
+        &guardian_set.index.to_be_bytes(),
+        &guardian_set.expiration_time.to_be_bytes(),
+    ]);
+    Ok(())
+}
+
+fn find_guardian_set_expiry(ctx: &ExecutionContext) -> Result<Option<GuardianSetExpiryData>> {
+    let key = GuardianSetExpiry::<'_, { AccountState::Initialized }>::key(None, ctx.program_id);
+    let info = match ctx.accounts.iter().find(|info| *info.key == key) {
+        Some(info) if !info.data_is_empty() => info,
+        _ => return Ok(None),
+    };
+    if info.owner != ctx.program_id {
+        return Err(SolitaireError::InvalidOwner(*info.owner));
+    }
+
+    let expiry = GuardianSetExpiryData::try_from_slice(&info.data.borrow())?;
+    Ok(Some(expiry))
+}
+
 // Static list of invalid signature accounts that are not allowed to post VAAs.
 static INVALID_SIGNATURES: &[&str; 16] = &[
     "18eK1799CaNMGCUnnCt1Kq2uwKkax6T2WmtrDsZuVFQ",
@@ -194,8 +374,8 @@ static INVALID_SIGNATURES: &[&str; 16] = &[
 
 /// The signatures in this instruction must be from the right guardian set.
 #[inline(always)]
-fn check_valid_sigs<'r>(
-    guardian_set: &GuardianSet<'r, { AccountState::Initialized }>,
+pub(crate) fn check_valid_sigs<'r>(
+    guardian_set: &GuardianSetData,
     signatures: &SignatureSet<'r, { AccountState::Initialized }>,
 ) -> Result<()> {
     if signatures.guardian_set_index != guardian_set.index {
@@ -211,18 +391,18 @@ fn check_valid_sigs<'r>(
 }
 
 #[inline(always)]
-fn check_integrity<'r>(
+pub(crate) fn check_integrity<'r>(
     vaa: &PostVAAData,
     signatures: &SignatureSet<'r, { AccountState::Initialized }>,
 ) -> Result<()> {
     // Serialize the VAA body into an array of bytes.
     let body = {
         let mut v = Cursor::new(Vec::new());
-        v.write_u32::<BigEndian>(vaa.timestamp)?;
-        v.write_u32::<BigEndian>(vaa.nonce)?;
-        v.write_u16::<BigEndian>(vaa.emitter_chain)?;
+        v.write_u32_be(vaa.timestamp)?;
+        v.write_u32_be(vaa.nonce)?;
+        v.write_u16_be(vaa.emitter_chain)?;
         v.write_all(&vaa.emitter_address)?;
-        v.write_u64::<BigEndian>(vaa.sequence)?;
+        v.write_u64_be(vaa.sequence)?;
         v.write_u8(vaa.consistency_level)?;
         v.write_all(&vaa.payload)?;
         v.into_inner()
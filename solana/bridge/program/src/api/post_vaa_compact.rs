@@ -0,0 +1,90 @@
+use solitaire::*;
+
+use solana_program::sysvar::clock::Clock;
+
+use crate::{
+    accounts::{
+        Bridge,
+        CompactMessageData,
+        GuardianSet,
+        PostedVAACompact,
+        PostedVAACompactDerivationData,
+        SignatureSet,
+    },
+    api::post_vaa::{
+        check_active_or_recent,
+        check_consensus,
+        check_integrity,
+        check_valid_sigs,
+        check_verification_unpaused,
+        select_guardian_set,
+        PostVAAData,
+    },
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    CreationLamports::Exempt,
+};
+
+#[derive(FromAccounts)]
+pub struct PostVAACompact<'b> {
+    /// Information about the current guardian set.
+    pub guardian_set: GuardianSet<'b, { AccountState::Initialized }>,
+
+    /// Bridge Info
+    pub bridge_info: Bridge<'b, { AccountState::Initialized }>,
+
+    /// Signature Info
+    pub signature_set: SignatureSet<'b, { AccountState::Initialized }>,
+
+    /// Compact message the VAA is associated with.
+    pub message: Mut<PostedVAACompact<'b, { AccountState::MaybeInitialized }>>,
+
+    /// Account used to pay for auxillary instructions.
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Clock used for timestamping.
+    pub clock: Sysvar<'b, Clock>,
+}
+
+/// Post a VAA, verified exactly like `post_vaa`, but only store its header and payload hash.
+pub fn post_vaa_compact(
+    ctx: &ExecutionContext,
+    accs: &mut PostVAACompact,
+    vaa: PostVAAData,
+) -> Result<()> {
+    let msg_derivation = PostedVAACompactDerivationData {
+        payload_hash: accs.signature_set.hash.to_vec(),
+    };
+
+    accs.message
+        .verify_derivation(ctx.program_id, &msg_derivation)?;
+    let guardian_set = select_guardian_set(ctx, &accs.guardian_set, vaa.guardian_set_index)?;
+
+    if accs.message.is_initialized() {
+        return Ok(());
+    }
+
+    // Verify any required invariants before we process the instruction.
+    check_verification_unpaused(&accs.bridge_info.config, &vaa)?;
+    check_active_or_recent(ctx, &guardian_set, &accs.clock)?;
+    check_valid_sigs(&guardian_set, &accs.signature_set)?;
+    check_integrity(&vaa, &accs.signature_set)?;
+    check_consensus(ctx, &guardian_set, &accs.signature_set.signatures)?;
+
+    // Persist VAA header
+    accs.message.nonce = vaa.nonce;
+    accs.message.emitter_chain = vaa.emitter_chain;
+    accs.message.emitter_address = vaa.emitter_address;
+    accs.message.sequence = vaa.sequence;
+    accs.message.payload_len = vaa.payload.len() as u32;
+    accs.message.payload_hash = CompactMessageData::hash_payload(&vaa.payload);
+    accs.message.consistency_level = vaa.consistency_level;
+    accs.message.vaa_version = vaa.version;
+    accs.message.vaa_time = vaa.timestamp;
+    accs.message.vaa_signature_account = *accs.signature_set.info().key;
+    accs.message
+        .create(&msg_derivation, ctx, accs.payer.key, Exempt)?;
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use crate::{
+    accounts::{
+        EmitterMetadata,
+        Sequence,
+        SequenceDerivationData,
+        MAX_EMITTER_NAME_LEN,
+    },
+    error::Error::InvalidEmitterName,
+};
+use solana_program::log::sol_log_data;
+use solitaire::{
+    processors::seeded::Seeded,
+    trace,
+    CreationLamports::Exempt,
+    *,
+};
+
+#[derive(FromAccounts)]
+pub struct RegisterEmitter<'b> {
+    /// Emitter registering its metadata, the only account allowed to update it
+    pub emitter: Signer<MaybeMut<Info<'b>>>,
+
+    /// Tracker for the emitter sequence, extended to hold the metadata
+    pub sequence: Mut<Sequence<'b>>,
+
+    /// Payer for account creation and extension
+    pub payer: Mut<Signer<Info<'b>>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct RegisterEmitterData {
+    /// Project name, at most [`MAX_EMITTER_NAME_LEN`] bytes
+    pub name: String,
+
+    /// Keccak256 hash of the project URL
+    pub url_hash: [u8; 32],
+
+    /// Keccak256 hash of the project contact
+    pub contact_hash: [u8; 32],
+}
+
+/// Register or update the metadata of an emitter. The metadata is stored on the emitter's sequence
+/// tracker so explorers can display it next to the emitter's messages. Only the emitter can sign
+/// for its tracker, so the metadata can't be changed by anyone else.
+pub fn register_emitter(
+    ctx: &ExecutionContext,
+    accs: &mut RegisterEmitter,
+    data: RegisterEmitterData,
+) -> Result<()> {
+    if data.name.len() > MAX_EMITTER_NAME_LEN {
+        return Err(InvalidEmitterName.into());
+    }
+
+    let sequence_derivation = SequenceDerivationData {
+        emitter_key: accs.emitter.key,
+    };
+    accs.sequence
+        .verify_derivation(ctx.program_id, &sequence_derivation)?;
+
+    sol_log_data(&[
+        b"EmitterRegistered",
+        accs.emitter.key.as_ref(),
+        data.name.as_bytes(),
+        &data.url_hash,
+        &data.contact_hash,
+    ]);
+
+    accs.sequence.metadata = Some(EmitterMetadata {
+        name: data.name,
+        url_hash: data.url_hash,
+        contact_hash: data.contact_hash,
+    });
+
+    if !accs.sequence.is_initialized() {
+        trace!("Initializing Sequence account with metadata.");
+        return accs
+            .sequence
+            .create(&sequence_derivation, ctx, accs.payer.key, Exempt);
+    }
+
+    ensure_account_size(
+        ctx,
+        accs.sequence.info(),
+        accs.payer.key,
+        accs.sequence.size(),
+    )
+}
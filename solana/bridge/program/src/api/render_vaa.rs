@@ -0,0 +1,241 @@
+use crate::{
+    accounts::{
+        PostedVAA,
+        PostedVAADerivationData,
+    },
+    api::governance_digest,
+    types::{
+        GovernancePayloadGuardianSetChange,
+        GovernancePayloadSetMessageFee,
+        GovernancePayloadTransferFees,
+        GovernancePayloadUpgrade,
+    },
+    EmitterInfo,
+    MessageData,
+    CHAIN_ID_GOVERANCE,
+};
+use primitive_types::U256;
+use solana_program::program::{
+    set_return_data,
+    MAX_RETURN_DATA,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    *,
+};
+use wormhole_governance::{
+    module,
+    Decree,
+    RegisterChain,
+};
+use wormhole_vaa::GovernanceHeader;
+
+/// Version of the rendering format, the first byte of a rendering.
+pub const RENDERING_VERSION: u8 = 1;
+
+/// Set in the flags of a rendering that had to be cut to fit into the return data.
+pub const RENDERING_TRUNCATED: u8 = 1;
+
+/// How explorers display the value of a rendered field.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldKind {
+    /// Big endian unsigned integer.
+    Uint = 1,
+    Bytes = 2,
+
+    /// 32 byte address, a Solana key or a foreign address.
+    Address = 3,
+
+    /// UTF-8 string.
+    Text = 4,
+}
+
+impl FieldKind {
+    pub fn from_u8(kind: u8) -> Option<Self> {
+        match kind {
+            1 => Some(FieldKind::Uint),
+            2 => Some(FieldKind::Bytes),
+            3 => Some(FieldKind::Address),
+            4 => Some(FieldKind::Text),
+            _ => None,
+        }
+    }
+}
+
+#[derive(FromAccounts)]
+pub struct RenderVAA<'b> {
+    /// VAA verified by this bridge that is rendered.
+    pub vaa: PostedVAA<'b, { AccountState::Initialized }>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct RenderVAAData {}
+
+/// Return the header and payload of a posted VAA as a self-describing rendering, see [`render`],
+/// so that generic explorers can display VAAs without knowing every payload schema. Anyone can
+/// render, the instruction changes no account and is meant to be simulated.
+pub fn render_vaa(
+    ctx: &ExecutionContext,
+    accs: &mut RenderVAA,
+    _data: RenderVAAData,
+) -> Result<()> {
+    // Posted messages deserialize as VAAs too, only accounts derived from the digest of a VAA body
+    // were created by post_vaa.
+    let digest = governance_digest(&accs.vaa)?;
+    accs.vaa.verify_derivation(
+        ctx.program_id,
+        &PostedVAADerivationData {
+            payload_hash: digest.to_vec(),
+        },
+    )?;
+
+    set_return_data(&render(&accs.vaa)?);
+    Ok(())
+}
+
+/// Render `vaa` into at most `MAX_RETURN_DATA` bytes: the [`RENDERING_VERSION`] and flags bytes
+/// followed by fields, each a [`FieldKind`] byte, the length of the name as a byte, the name, the
+/// big endian u16 length of the value and the value.
+///
+/// The header of the VAA is followed by the payload. VAAs of the governance emitter are rendered
+/// with their governance header, the decrees shared through `wormhole_governance` field by field
+/// and other decrees as their body. Other payloads are rendered as they are. Fields that don't fit
+/// are cut if they are bytes and left out otherwise, setting [`RENDERING_TRUNCATED`].
+pub fn render(vaa: &MessageData) -> Result<Vec<u8>> {
+    let mut r = Renderer {
+        out: vec![RENDERING_VERSION, 0],
+    };
+    r.uint("version", &[vaa.vaa_version]);
+    r.uint("timestamp", &vaa.vaa_time.to_be_bytes());
+    r.uint("nonce", &vaa.nonce.to_be_bytes());
+    r.uint("emitter_chain", &vaa.emitter_chain.to_be_bytes());
+    r.address("emitter_address", &vaa.emitter_address);
+    r.uint("sequence", &vaa.sequence.to_be_bytes());
+    r.uint("consistency_level", &[vaa.consistency_level]);
+    r.field(FieldKind::Bytes, "digest", &governance_digest(vaa)?);
+
+    let emitter = EmitterInfo {
+        chain: vaa.emitter_chain,
+        address: vaa.emitter_address,
+        sequence: vaa.sequence,
+    };
+    let governance = emitter.try_is(CHAIN_ID_GOVERANCE, std::env!("EMITTER_ADDRESS"))?;
+    match GovernanceHeader::parse(&vaa.payload) {
+        Ok(header) if governance => render_decree(&mut r, &header),
+        _ => r.field(FieldKind::Bytes, "payload", &vaa.payload),
+    }
+
+    Ok(r.out)
+}
+
+fn render_decree(r: &mut Renderer, header: &GovernanceHeader) {
+    let name_start = header.module.iter().position(|b| *b != 0).unwrap_or(32);
+    let name = &header.module[name_start..];
+    match std::str::from_utf8(name) {
+        Ok(name) => r.field(FieldKind::Text, "module", name.as_bytes()),
+        Err(_) => r.field(FieldKind::Bytes, "module", header.module),
+    }
+    r.uint("action", &[header.action]);
+    r.uint("target_chain", &header.chain.to_be_bytes());
+
+    let core = header.is_module(module::CORE);
+    let bridge = header.is_module(module::TOKEN_BRIDGE) || header.is_module(module::NFT_BRIDGE);
+    let rendered = match header.action {
+        GovernancePayloadUpgrade::ACTION if core => {
+            GovernancePayloadUpgrade::decode_body(header.body).map(|upgrade| {
+                r.address("new_contract", &upgrade.new_contract.to_bytes());
+                if let Some(version) = &upgrade.version {
+                    r.field(FieldKind::Text, "version", version.as_bytes());
+                }
+                if let Some(buffer_hash) = &upgrade.buffer_hash {
+                    r.field(FieldKind::Bytes, "buffer_hash", buffer_hash);
+                }
+            })
+        }
+        GovernancePayloadGuardianSetChange::ACTION if core => {
+            GovernancePayloadGuardianSetChange::decode_body(header.body).map(|change| {
+                r.uint(
+                    "new_guardian_set_index",
+                    &change.new_guardian_set_index.to_be_bytes(),
+                );
+                r.field(
+                    FieldKind::Bytes,
+                    "new_guardian_set",
+                    &change.new_guardian_set.concat(),
+                );
+                if !change.new_secp256r1_keys.is_empty() {
+                    r.field(
+                        FieldKind::Bytes,
+                        "new_secp256r1_keys",
+                        &change.new_secp256r1_keys.concat(),
+                    );
+                }
+            })
+        }
+        GovernancePayloadSetMessageFee::ACTION if core => {
+            GovernancePayloadSetMessageFee::decode_body(header.body)
+                .map(|set_fee| r.uint256("fee", set_fee.fee))
+        }
+        GovernancePayloadTransferFees::ACTION if core => {
+            GovernancePayloadTransferFees::decode_body(header.body).map(|transfer| {
+                r.uint256("amount", transfer.amount);
+                r.address("to", &transfer.to);
+            })
+        }
+        RegisterChain::ACTION if bridge => {
+            RegisterChain::decode_body(header.body).map(|register| {
+                r.uint("chain", &register.chain.to_be_bytes());
+                r.address("endpoint_address", &register.endpoint_address);
+            })
+        }
+        _ => Err(std::io::ErrorKind::InvalidData.into()),
+    };
+
+    // Decrees this bridge doesn't know, or that don't decode, keep the body for the reader.
+    if rendered.is_err() {
+        r.field(FieldKind::Bytes, "body", header.body);
+    }
+}
+
+struct Renderer {
+    out: Vec<u8>,
+}
+
+impl Renderer {
+    fn uint(&mut self, name: &str, value: &[u8]) {
+        self.field(FieldKind::Uint, name, value)
+    }
+
+    fn uint256(&mut self, name: &str, value: U256) {
+        let mut bytes = [0u8; 32];
+        value.to_big_endian(&mut bytes);
+        self.uint(name, &bytes)
+    }
+
+    fn address(&mut self, name: &str, value: &[u8; 32]) {
+        self.field(FieldKind::Address, name, value)
+    }
+
+    fn field(&mut self, kind: FieldKind, name: &str, value: &[u8]) {
+        let header = 1 + 1 + name.len() + 2;
+        let space = MAX_RETURN_DATA.saturating_sub(self.out.len() + header);
+        let value = match kind {
+            _ if value.len() <= space => value,
+            FieldKind::Bytes if space > 0 => {
+                self.out[1] |= RENDERING_TRUNCATED;
+                &value[..space]
+            }
+            _ => {
+                self.out[1] |= RENDERING_TRUNCATED;
+                return;
+            }
+        };
+        self.out.push(kind as u8);
+        self.out.push(name.len() as u8);
+        self.out.extend_from_slice(name.as_bytes());
+        self.out
+            .extend_from_slice(&(value.len() as u16).to_be_bytes());
+        self.out.extend_from_slice(value);
+    }
+}
@@ -0,0 +1,152 @@
+use crate::{
+    accounts::{
+        Bridge,
+        BridgeData,
+        FeeCollector,
+        GuardianSet,
+        GuardianSetData,
+        GuardianSetDerivationData,
+    },
+    error::Error::{
+        InvalidDeployAuthority,
+        InvalidRepairSource,
+    },
+};
+use solana_program::{
+    bpf_loader_upgradeable,
+    log::sol_log_data,
+    pubkey::Pubkey,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    CreationLamports::Exempt,
+    *,
+};
+use wormhole_solana_consts::seeds;
+
+#[derive(FromAccounts)]
+pub struct RepairDerivations<'b> {
+    /// Upgrade authority the bridge was deployed with, paying for account creation
+    pub deploy_authority: Mut<Signer<Info<'b>>>,
+
+    /// Program data of the bridge, holding its upgrade authority
+    pub program_data: Mut<Info<'b>>,
+
+    /// Bridge config of the deployment the state is copied from
+    pub source_bridge: Info<'b>,
+
+    /// Active guardian set of that deployment
+    pub source_guardian_set: Info<'b>,
+
+    /// Bridge config
+    pub bridge: Mut<Bridge<'b, { AccountState::Uninitialized }>>,
+
+    /// Copy of the active guardian set, at the same index
+    pub guardian_set: Mut<GuardianSet<'b, { AccountState::Uninitialized }>>,
+
+    /// Account collecting tx fees
+    pub fee_collector: Mut<FeeCollector<'b>>,
+
+    /// PDA authority for the loader, handed the authority over the bridge
+    pub upgrade_authority: Derive<Info<'b>, { seeds::UPGRADE }>,
+
+    pub bpf_loader: Info<'b>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct RepairDerivationsData {}
+
+/// Set up a bridge deployed under a new program ID from the state of the deployment it forks: the
+/// bridge config and active guardian set are copied to their derivations under the new ID, the fee
+/// collector is created and the upgrade authority of the program moves from the deploy authority to
+/// the upgrade PDA, so that only governance upgrades it from then on. Only the deploy authority can
+/// repair the derivations, once, in place of `initialize`. Repairs are logged as
+/// `DerivationsRepaired`, with the source program and guardian set index.
+pub fn repair_derivations(
+    ctx: &ExecutionContext,
+    accs: &mut RepairDerivations,
+    _data: RepairDerivationsData,
+) -> Result<()> {
+    verify_deploy_authority(ctx, &accs.program_data, accs.deploy_authority.key)?;
+
+    // The source is a deployment of this code under another ID, its accounts share our layout.
+    let source_program = *accs.source_bridge.owner;
+    let (source_bridge, _) =
+        Pubkey::find_program_address(&[seeds::BRIDGE.as_bytes()], &source_program);
+    if source_program == *ctx.program_id || *accs.source_bridge.key != source_bridge {
+        return Err(InvalidRepairSource.into());
+    }
+    let source = BridgeData::try_from_slice(&accs.source_bridge.data.borrow())?;
+
+    let index = source.guardian_set_index;
+    let derivation_data = GuardianSetDerivationData { index };
+    let source_guardian_set =
+        GuardianSet::<'_, { AccountState::Initialized }>::key(&derivation_data, &source_program);
+    if *accs.source_guardian_set.key != source_guardian_set
+        || *accs.source_guardian_set.owner != source_program
+    {
+        return Err(InvalidRepairSource.into());
+    }
+    let guardian_set = GuardianSetData::try_from_slice(&accs.source_guardian_set.data.borrow())?;
+
+    **accs.guardian_set = guardian_set;
+    accs.guardian_set
+        .create(&derivation_data, ctx, accs.deploy_authority.key, Exempt)?;
+
+    accs.bridge.create(ctx, accs.deploy_authority.key, Exempt)?;
+    accs.bridge.guardian_set_index = index;
+    accs.bridge.config = source.config;
+
+    accs.fee_collector.create(
+        ctx,
+        accs.deploy_authority.key,
+        Exempt,
+        0,
+        &solana_program::system_program::id(),
+    )?;
+    accs.bridge.last_lamports = accs.fee_collector.lamports();
+
+    let set_authority = bpf_loader_upgradeable::set_upgrade_authority(
+        ctx.program_id,
+        accs.deploy_authority.key,
+        Some(accs.upgrade_authority.key),
+    );
+    invoke(&set_authority, ctx.accounts)?;
+
+    sol_log_data(&[
+        b"DerivationsRepaired",
+        source_program.as_ref(),
+        &index.to_be_bytes(),
+    ]);
+
+    Ok(())
+}
+
+/// `deploy_authority` must be the upgrade authority recorded in the program data of the bridge.
+fn verify_deploy_authority(
+    ctx: &ExecutionContext,
+    program_data: &Info,
+    deploy_authority: &Pubkey,
+) -> Result<()> {
+    let (key, _) =
+        Pubkey::find_program_address(&[ctx.program_id.as_ref()], &bpf_loader_upgradeable::id());
+    if *program_data.key != key {
+        return Err(SolitaireError::InvalidDerive(*program_data.key, key));
+    }
+    if *program_data.owner != bpf_loader_upgradeable::id() {
+        return Err(InvalidDeployAuthority.into());
+    }
+
+    // Program data starts with the loader state tag, the deployment slot and the optional upgrade
+    // authority.
+    let data = program_data.data.borrow();
+    let authority = data.get(..45).ok_or(InvalidDeployAuthority)?;
+    if authority[..4] != 3u32.to_le_bytes()
+        || authority[12] != 1
+        || authority[13..] != deploy_authority.to_bytes()
+    {
+        return Err(InvalidDeployAuthority.into());
+    }
+
+    Ok(())
+}
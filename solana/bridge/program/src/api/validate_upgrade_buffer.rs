@@ -0,0 +1,111 @@
+use crate::error::Error::{
+    InvalidUpgradeBuffer,
+    InvalidUpgradeBufferAuthority,
+    InvalidUpgradeBufferElf,
+    UpgradeBufferTooLarge,
+};
+use solana_program::{
+    bpf_loader_upgradeable::{
+        self,
+        UpgradeableLoaderState,
+    },
+    log::sol_log_data,
+    program_error::ProgramError::InvalidAccountData,
+    pubkey::Pubkey,
+};
+use solitaire::*;
+use wormhole_solana_consts::seeds;
+
+#[derive(FromAccounts)]
+pub struct ValidateUpgradeBuffer<'b> {
+    /// Buffer staged for an upgrade of the bridge.
+    pub buffer: Info<'b>,
+
+    /// Program data of the bridge, which the new program must fit in.
+    pub program_data: Info<'b>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct ValidateUpgradeBufferData {}
+
+/// Check that `upgrade_contract` could deploy the program staged in `buffer`: it must be a loader
+/// buffer the bridge has authority over, hold a BPF shared object and fit the program data of the
+/// bridge. Anyone can validate a buffer, so that deploy pipelines catch broken buffers before
+/// guardians are asked to sign an upgrade to them. Valid buffers are logged as
+/// `UpgradeBufferValidated`, with the buffer key, program length and the SHA-256 of the program an
+/// upgrade decree can bind to.
+pub fn validate_upgrade_buffer(
+    ctx: &ExecutionContext,
+    accs: &mut ValidateUpgradeBuffer,
+    _data: ValidateUpgradeBufferData,
+) -> Result<()> {
+    let (program_data, _) =
+        Pubkey::find_program_address(&[ctx.program_id.as_ref()], &bpf_loader_upgradeable::id());
+    if *accs.program_data.key != program_data {
+        return Err(SolitaireError::InvalidDerive(
+            *accs.program_data.key,
+            program_data,
+        ));
+    }
+
+    if *accs.buffer.owner != bpf_loader_upgradeable::id() {
+        return Err(InvalidUpgradeBuffer.into());
+    }
+
+    // Buffers start with the loader state tag and their authority, the program follows.
+    let data = accs.buffer.data.borrow();
+    let offset = UpgradeableLoaderState::buffer_data_offset().map_err(|_| InvalidAccountData)?;
+    if data.len() < offset || data[..4] != 1u32.to_le_bytes() {
+        return Err(InvalidUpgradeBuffer.into());
+    }
+
+    let (upgrade_authority, _) =
+        Pubkey::find_program_address(&[seeds::UPGRADE.as_bytes()], ctx.program_id);
+    if data[4] != 1 || data[5..offset] != upgrade_authority.to_bytes() {
+        return Err(InvalidUpgradeBufferAuthority.into());
+    }
+
+    let program = &data[offset..];
+    verify_elf_header(program)?;
+
+    let capacity = UpgradeableLoaderState::programdata_data_offset()
+        .ok()
+        .and_then(|offset| accs.program_data.data_len().checked_sub(offset))
+        .unwrap_or_default();
+    if program.len() > capacity {
+        return Err(UpgradeBufferTooLarge.into());
+    }
+
+    sol_log_data(&[
+        b"UpgradeBufferValidated",
+        accs.buffer.key.as_ref(),
+        &(program.len() as u64).to_be_bytes(),
+        &solana_program::hash::hash(program).to_bytes(),
+    ]);
+
+    Ok(())
+}
+
+/// The loader only deploys little endian ELF64 shared objects for the BPF machine.
+fn verify_elf_header(program: &[u8]) -> Result<()> {
+    const MAGIC: &[u8; 4] = b"\x7fELF";
+    const ELFCLASS64: u8 = 2;
+    const ELFDATA2LSB: u8 = 1;
+    const ET_DYN: u16 = 3;
+    const EM_BPF: u16 = 247;
+    const HEADER_LEN: usize = 64;
+
+    let header = program.get(..HEADER_LEN).ok_or(InvalidUpgradeBufferElf)?;
+    let e_type = u16::from_le_bytes([header[16], header[17]]);
+    let e_machine = u16::from_le_bytes([header[18], header[19]]);
+    if &header[..4] != MAGIC
+        || header[4] != ELFCLASS64
+        || header[5] != ELFDATA2LSB
+        || e_type != ET_DYN
+        || e_machine != EM_BPF
+    {
+        return Err(InvalidUpgradeBufferElf.into());
+    }
+
+    Ok(())
+}
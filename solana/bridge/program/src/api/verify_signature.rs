@@ -2,24 +2,44 @@
 use solitaire::*;
 
 use crate::{
+    accounts::{
+        DigestCache,
+        DigestCacheDerivationData,
+    },
+    api::post_vaa::{
+        check_active_or_recent,
+        check_consensus,
+        find_guardian_set,
+        select_guardian_set,
+    },
     error::Error::{
         GuardianSetMismatch,
         InstructionAtWrongIndex,
         InvalidHash,
         InvalidSecpInstruction,
+        VAAInvalid,
     },
     GuardianSet,
+    GuardianSetData,
     GuardianSetDerivationData,
     IsSigned::*,
     SignatureSet,
+    SignatureSetData,
     MAX_LEN_GUARDIAN_KEYS,
 };
 use byteorder::ByteOrder;
-use solana_program::program_error::ProgramError;
+use solana_program::{
+    program_error::ProgramError,
+    sysvar::clock::Clock,
+};
 use solitaire::{
-    processors::seeded::Seeded,
+    processors::{
+        compute::measure,
+        seeded::Seeded,
+    },
     CreationLamports::Exempt,
 };
+use std::io::Write;
 
 #[derive(FromAccounts)]
 pub struct VerifySignatures<'b> {
@@ -44,16 +64,61 @@ impl From<&VerifySignatures<'_>> for GuardianSetDerivationData {
     }
 }
 
-#[derive(Default, BorshSerialize, BorshDeserialize)]
+#[derive(Default)]
 pub struct VerifySignaturesData {
     /// instruction indices of signers (-1 for missing)
     pub signers: [i8; MAX_LEN_GUARDIAN_KEYS],
+
+    /// Hash the caller expects the signatures to be over, checked early against the hash in the
+    /// secp256k1 instruction data so that a mismatch fails here rather than in `post_vaa`.
+    pub hash: Option<[u8; 32]>,
+
+    /// Guardian set index of the first entry of `signers`. Sets larger than
+    /// `MAX_LEN_GUARDIAN_KEYS` are verified window by window over multiple transactions.
+    pub signer_offset: u8,
+}
+
+// The hash and offset are appended without option tags, so that instructions built before they
+// existed keep the same encoding. The offset is only written when non-zero, a single trailing byte
+// can't be mistaken for a hash.
+impl BorshSerialize for VerifySignaturesData {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.signers.serialize(writer)?;
+        if let Some(hash) = self.hash {
+            hash.serialize(writer)?;
+        }
+        if self.signer_offset != 0 {
+            self.signer_offset.serialize(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for VerifySignaturesData {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let signers = <[i8; MAX_LEN_GUARDIAN_KEYS]>::deserialize(buf)?;
+        let hash = if buf.len() < 32 {
+            None
+        } else {
+            Some(<[u8; 32]>::deserialize(buf)?)
+        };
+        let signer_offset = if buf.is_empty() {
+            0
+        } else {
+            u8::deserialize(buf)?
+        };
+        Ok(VerifySignaturesData {
+            signers,
+            hash,
+            signer_offset,
+        })
+    }
 }
 
 /// SigInfo contains metadata about signers in a VerifySignature ix
 struct SigInfo {
     /// index of the signer in the guardianset
-    signer_index: u8,
+    signer_index: usize,
     /// index of the signature in the secp instruction
     sig_index: u8,
 }
@@ -83,95 +148,64 @@ pub fn verify_signatures(
 
             Some(SigInfo {
                 sig_index: *p as u8,
-                signer_index: i as u8,
+                signer_index: data.signer_offset as usize + i,
             })
         })
         .collect();
 
-    let current_instruction =
-        solana_program::sysvar::instructions::load_current_index_checked(&accs.instruction_acc)?;
-    if current_instruction == 0 {
-        return Err(InstructionAtWrongIndex.into());
-    }
-
-    // The previous ix must be a secp verification instruction
-    let secp_ix_index = (current_instruction - 1) as u8;
-    let secp_ix = solana_program::sysvar::instructions::load_instruction_at_checked(
-        secp_ix_index as usize,
-        &accs.instruction_acc,
-    )
-    .map_err(|_| ProgramError::InvalidAccountData)?;
-
-    // Check that the instruction is actually for the secp program
-    if secp_ix.program_id != solana_program::secp256k1_program::id() {
-        return Err(InvalidSecpInstruction.into());
-    }
+    let (secp_ix, (sig_len, secp_ixs)) = measure("secp_instruction", || -> Result<_> {
+        let (secp_ix_index, secp_ix) = load_secp_instruction(&accs.instruction_acc)?;
 
-    let secp_data_len = secp_ix.data.len();
-    if secp_data_len < 2 {
-        return Err(InvalidSecpInstruction.into());
-    }
-
-    let sig_len = secp_ix.data[0];
-    let mut index = 1;
-
-    let mut secp_ixs: Vec<SecpInstructionPart> = Vec::with_capacity(sig_len as usize);
-    for i in 0..sig_len {
-        let _sig_offset = byteorder::LE::read_u16(&secp_ix.data[index..index + 2]) as usize;
-        index += 2;
-        let sig_ix = secp_ix.data[index];
-        index += 1;
-        let address_offset = byteorder::LE::read_u16(&secp_ix.data[index..index + 2]) as usize;
-        index += 2;
-        let address_ix = secp_ix.data[index];
-        index += 1;
-        let msg_offset = byteorder::LE::read_u16(&secp_ix.data[index..index + 2]);
-        index += 2;
-        let msg_size = byteorder::LE::read_u16(&secp_ix.data[index..index + 2]);
-        index += 2;
-        let msg_ix = secp_ix.data[index];
-        index += 1;
-
-        if address_ix != secp_ix_index || msg_ix != secp_ix_index || sig_ix != secp_ix_index {
-            return Err(InvalidSecpInstruction.into());
-        }
-
-        let address: &[u8] = &secp_ix.data[address_offset..address_offset + 20];
-
-        // Make sure that all messages are equal
-        if i > 0 {
-            if msg_offset != secp_ixs[0].msg_offset || msg_size != secp_ixs[0].msg_size {
-                return Err(InvalidSecpInstruction.into());
+        // Guardians sign with secp256k1, or with secp256r1 where governance enabled it.
+        let parsed = match secp_ix.program_id {
+            id if id == solana_program::secp256k1_program::id() => {
+                parse_secp256k1_instruction(&secp_ix.data, secp_ix_index)?
             }
-        }
-        secp_ixs.push(SecpInstructionPart {
-            address,
-            msg_offset,
-            msg_size,
-        });
-    }
+            #[cfg(feature = "secp256r1")]
+            id if id == secp256r1_program::id() => {
+                verify_secp256r1_enabled(ctx)?;
+                parse_secp256r1_instruction(&secp_ix.data, secp_ix_index)?
+            }
+            _ => return Err(InvalidSecpInstruction.into()),
+        };
+        Ok((secp_ix, parsed))
+    })?;
+    let secp256r1 = secp_ix.program_id != solana_program::secp256k1_program::id();
 
     if sig_infos.len() != secp_ixs.len() {
         return Err(ProgramError::InvalidArgument.into());
     }
 
-    // Data must be a hash
-    if secp_ixs[0].msg_size != 32 {
-        return Err(ProgramError::InvalidArgument.into());
-    }
+    let msg_hash = signed_hash(&secp_ix.data, secp_ixs.iter())?;
 
-    // Extract message which is encoded in Solana Secp256k1 instruction data.
-    let message = &secp_ix.data
-        [secp_ixs[0].msg_offset as usize..(secp_ixs[0].msg_offset + secp_ixs[0].msg_size) as usize];
+    // Callers passing the hash they expect fail early rather than in `post_vaa`.
+    if let Some(hash) = data.hash {
+        if hash != msg_hash {
+            return Err(InvalidHash.into());
+        }
+    }
 
-    // Hash the message part, which contains the serialized VAA body.
-    let mut msg_hash: [u8; 32] = [0u8; 32];
-    msg_hash.copy_from_slice(message);
+    // Clients unsure whether a guardian set rotation took effect pass both the current and the
+    // previous set, see `with_guardian_set`. Signature sets of earlier windows name their set, new
+    // ones are made for the set the first signature belongs to.
+    let signed_by = |set: &GuardianSetData| match accs.signature_set.is_initialized() {
+        true => set.index == accs.signature_set.guardian_set_index,
+        false => sig_infos.first().map_or(true, |s| {
+            let address = secp_ixs.get(s.sig_index as usize).map(|part| part.address);
+            address.is_some() && guardian_key(set, s.signer_index, secp256r1) == address
+        }),
+    };
+    let found = match signed_by(&*accs.guardian_set) {
+        true => None,
+        false => find_guardian_set(ctx, signed_by),
+    };
+    let guardian_set = found.as_ref().unwrap_or(&*accs.guardian_set);
 
     if !accs.signature_set.is_initialized() {
-        accs.signature_set.signatures = vec![false; accs.guardian_set.keys.len()];
-        accs.signature_set.guardian_set_index = accs.guardian_set.index;
+        accs.signature_set.signatures = vec![false; guardian_set.keys.len()];
+        accs.signature_set.guardian_set_index = guardian_set.index;
         accs.signature_set.hash = msg_hash;
+        accs.signature_set.refund_recipient = Some(*accs.payer.key);
 
         let size = accs.signature_set.size();
         create_account(
@@ -185,7 +219,7 @@ pub fn verify_signatures(
         )?;
     } else {
         // If the account already existed, check that the parameters match
-        if accs.signature_set.guardian_set_index != accs.guardian_set.index {
+        if accs.signature_set.guardian_set_index != guardian_set.index {
             return Err(GuardianSetMismatch.into());
         }
 
@@ -194,9 +228,387 @@ pub fn verify_signatures(
         }
     }
 
-    // Write sigs of checked addresses into sig_state
+    measure("write_signatures", || {
+        write_signatures(
+            guardian_set,
+            &sig_infos,
+            &secp_ixs,
+            sig_len,
+            secp256r1,
+            &mut accs.signature_set.signatures,
+        )
+    })
+}
+
+#[derive(FromAccounts)]
+pub struct VerifySignaturesBatch<'b> {
+    /// Payer for account creation
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Guardian set of all signatures in the batch
+    pub guardian_set: GuardianSet<'b, { AccountState::Initialized }>,
+
+    /// Instruction reflection account (special sysvar)
+    pub instruction_acc: Info<'b>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct VerifySignaturesBatchData {
+    /// Instruction indices of signers (-1 for missing), one entry per signature set. Each set
+    /// needs at least one signer, the message its signatures are over is the hash of its VAA.
+    pub signers: Vec<[i8; MAX_LEN_GUARDIAN_KEYS]>,
+}
+
+/// Verify signatures over several VAA bodies with a single secp256k1 instruction, so that relayers
+/// pay the instruction and transaction overhead once for many VAAs. The preceding secp256k1
+/// instruction may check signatures over different messages, the signatures of each signature
+/// set must all be over the same one.
+///
+/// Signature sets follow all other accounts, writable and signing, in the order of
+/// `data.signers`. Like with `verify_signatures`, existing sets are added to and new ones created.
+/// All sets must belong to `guardian_set`, batches don't look for the previous set during a
+/// rotation and don't take secp256r1 signatures.
+pub fn verify_signatures_batch(
+    ctx: &ExecutionContext,
+    accs: &mut VerifySignaturesBatch,
+    data: VerifySignaturesBatchData,
+) -> Result<()> {
+    accs.guardian_set.verify_derivation(
+        ctx.program_id,
+        &GuardianSetDerivationData {
+            index: accs.guardian_set.index,
+        },
+    )?;
+
+    let signature_sets = ctx
+        .accounts
+        .len()
+        .checked_sub(data.signers.len())
+        .map(|start| &ctx.accounts[start..])
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let (secp_ix_index, secp_ix) = load_secp_instruction(&accs.instruction_acc)?;
+    if secp_ix.program_id != solana_program::secp256k1_program::id() {
+        return Err(InvalidSecpInstruction.into());
+    }
+    let (sig_len, secp_ixs) = parse_secp256k1_instruction(&secp_ix.data, secp_ix_index)?;
+
+    // Every signature of the secp instruction is written into exactly one set.
+    let signed: usize = data
+        .signers
+        .iter()
+        .map(|signers| signers.iter().filter(|p| **p != -1).count())
+        .sum();
+    if signed != secp_ixs.len() {
+        return Err(ProgramError::InvalidArgument.into());
+    }
+
+    for (signers, info) in data.signers.iter().zip(signature_sets) {
+        let sig_infos: Vec<SigInfo> = signers
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| **p != -1)
+            .map(|(i, p)| SigInfo {
+                sig_index: *p as u8,
+                signer_index: i,
+            })
+            .collect();
+
+        let parts = sig_infos
+            .iter()
+            .map(|s| secp_ixs.get(s.sig_index as usize))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(ProgramError::InvalidArgument)?;
+        if parts.is_empty() {
+            return Err(ProgramError::InvalidArgument.into());
+        }
+        let msg_hash = signed_hash(&secp_ix.data, parts.into_iter())?;
+
+        if !info.is_signer {
+            return Err(SolitaireError::InvalidSigner(*info.key));
+        }
+        if !info.is_writable {
+            return Err(SolitaireError::InvalidMutability(
+                *info.key,
+                info.is_writable,
+            ));
+        }
+
+        let mut signature_set = if info.data_is_empty() {
+            SignatureSetData {
+                signatures: vec![false; accs.guardian_set.keys.len()],
+                hash: msg_hash,
+                guardian_set_index: accs.guardian_set.index,
+                refund_recipient: Some(*accs.payer.key),
+            }
+        } else {
+            if info.owner != ctx.program_id {
+                return Err(SolitaireError::InvalidOwner(*info.owner));
+            }
+            SignatureSetData::deserialize(&mut &info.data.borrow()[..])?
+        };
+
+        if signature_set.guardian_set_index != accs.guardian_set.index {
+            return Err(GuardianSetMismatch.into());
+        }
+        if signature_set.hash != msg_hash {
+            return Err(InvalidHash.into());
+        }
+
+        write_signatures(
+            &accs.guardian_set,
+            &sig_infos,
+            &secp_ixs,
+            sig_len,
+            false,
+            &mut signature_set.signatures,
+        )?;
+
+        if info.data_is_empty() {
+            create_account(
+                ctx,
+                info,
+                accs.payer.key,
+                Exempt,
+                signature_set.try_to_vec()?.len(),
+                ctx.program_id,
+                NotSigned,
+            )?;
+        }
+        signature_set.serialize(&mut &mut info.data.borrow_mut()[..])?;
+    }
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct VerifyVAAInline<'b> {
+    /// Guardian set the VAA names
+    pub guardian_set: GuardianSet<'b, { AccountState::Initialized }>,
+
+    /// Instruction reflection account (special sysvar)
+    pub instruction_acc: Info<'b>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct VerifyVAAInlineData {
+    /// The VAA as signed by guardians: version, guardian set index, signatures and body.
+    pub vaa: Vec<u8>,
+}
+
+/// Length of a guardian index followed by its signature in a serialized VAA.
+const VAA_SIGNATURE_LEN: usize = wormhole_vaa::Vaa::SIGNATURE_LEN;
+
+/// Verify a serialized VAA against the secp256k1 instruction preceding this one, without signature
+/// sets or posted VAAs, so that integrators consume VAAs in the transaction delivering them. The
+/// secp instruction must check exactly the signatures of the VAA, in its order, over the hash of
+/// its body. The instruction fails unless the VAA reached consensus of an active guardian set.
+///
+/// Programs may invoke this instruction, the secp instruction then precedes their own top-level
+/// instruction. Like `verify_signatures_batch`, only secp256k1 signatures are accepted.
+pub fn verify_vaa_inline(
+    ctx: &ExecutionContext,
+    accs: &mut VerifyVAAInline,
+    data: VerifyVAAInlineData,
+) -> Result<()> {
+    let (guardian_set_index, signatures, body) = split_signed_vaa(&data.vaa)?;
+
+    let guardian_set = select_guardian_set(ctx, &accs.guardian_set, guardian_set_index)?;
+    check_active_or_recent(ctx, &guardian_set, &accs.clock)?;
+
+    let body_hash = wormhole_vaa::digest(body);
+    verify_preceding_signatures(
+        ctx,
+        &guardian_set,
+        &accs.instruction_acc,
+        signatures,
+        body_hash,
+    )
+}
+
+#[derive(FromAccounts)]
+pub struct VerifyVAACached<'b> {
+    /// Payer for the cache account
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Guardian set the VAA names
+    pub guardian_set: GuardianSet<'b, { AccountState::Initialized }>,
+
+    /// Verification result of the VAA body hash, created by the first consumer
+    pub cache: Mut<DigestCache<'b, { AccountState::MaybeInitialized }>>,
+
+    /// Instruction reflection account (special sysvar)
+    pub instruction_acc: Info<'b>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct VerifyVAACachedData {
+    /// The VAA as signed by guardians. Signatures can be left out once the body hash is cached.
+    pub vaa: Vec<u8>,
+}
+
+/// Like `verify_vaa_inline`, but record the verified body hash in a [`DigestCache`] so that later
+/// consumers of the same VAA skip the secp instruction and signatures. Cached hashes verify for as
+/// long as their guardian set is accepted, expiring with it.
+pub fn verify_vaa_cached(
+    ctx: &ExecutionContext,
+    accs: &mut VerifyVAACached,
+    data: VerifyVAACachedData,
+) -> Result<()> {
+    let (guardian_set_index, signatures, body) = split_signed_vaa(&data.vaa)?;
+    let body_hash = wormhole_vaa::digest(body);
+    let derivation_data = DigestCacheDerivationData { hash: body_hash };
+    accs.cache
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    if accs.cache.is_initialized() {
+        if accs.cache.guardian_set_index != accs.guardian_set.index {
+            return Err(GuardianSetMismatch.into());
+        }
+        accs.guardian_set.verify_derivation(
+            ctx.program_id,
+            &GuardianSetDerivationData {
+                index: accs.guardian_set.index,
+            },
+        )?;
+        return check_active_or_recent(ctx, &accs.guardian_set, &accs.clock);
+    }
+
+    let guardian_set = select_guardian_set(ctx, &accs.guardian_set, guardian_set_index)?;
+    check_active_or_recent(ctx, &guardian_set, &accs.clock)?;
+    verify_preceding_signatures(
+        ctx,
+        &guardian_set,
+        &accs.instruction_acc,
+        signatures,
+        body_hash,
+    )?;
+
+    accs.cache.hash = body_hash;
+    accs.cache.guardian_set_index = guardian_set.index;
+    accs.cache.slot = accs.clock.slot;
+    accs.cache
+        .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+
+    Ok(())
+}
+
+/// Fail unless the secp256k1 instruction preceding this one checked exactly `signatures`, in
+/// order, over `body_hash`, and they reach consensus of `guardian_set`.
+fn verify_preceding_signatures(
+    ctx: &ExecutionContext,
+    guardian_set: &GuardianSetData,
+    instruction_acc: &Info,
+    signatures: &[u8],
+    body_hash: [u8; 32],
+) -> Result<()> {
+    let (secp_ix_index, secp_ix) = load_secp_instruction(instruction_acc)?;
+    if secp_ix.program_id != solana_program::secp256k1_program::id() {
+        return Err(InvalidSecpInstruction.into());
+    }
+    let (sig_len, secp_ixs) = parse_secp256k1_instruction(&secp_ix.data, secp_ix_index)?;
+    if secp_ixs.len() != signatures.len() / VAA_SIGNATURE_LEN {
+        return Err(ProgramError::InvalidArgument.into());
+    }
+
+    if signed_hash(&secp_ix.data, secp_ixs.iter())? != body_hash {
+        return Err(InvalidHash.into());
+    }
+
+    // The i-th signature of the VAA is the i-th one checked by the secp instruction.
+    let sig_infos: Vec<SigInfo> = signatures
+        .chunks(VAA_SIGNATURE_LEN)
+        .enumerate()
+        .map(|(i, signature)| SigInfo {
+            sig_index: i as u8,
+            signer_index: signature[0] as usize,
+        })
+        .collect();
+    let mut signed = vec![false; guardian_set.keys.len()];
+    write_signatures(
+        guardian_set,
+        &sig_infos,
+        &secp_ixs,
+        sig_len,
+        false,
+        &mut signed,
+    )?;
+
+    check_consensus(ctx, guardian_set, &signed)
+}
+
+/// Split a VAA as signed by guardians into the index of their set, the signatures and the body.
+pub(crate) fn split_signed_vaa(vaa: &[u8]) -> Result<(u32, &[u8], &[u8])> {
+    let vaa = wormhole_vaa::Vaa::parse(vaa).map_err(|_| VAAInvalid)?;
+    if vaa.version != 1 {
+        return Err(VAAInvalid.into());
+    }
+    Ok((vaa.guardian_set_index, vaa.encoded_signatures(), vaa.body))
+}
+
+/// Load the secp instruction preceding the current one, with its index.
+fn load_secp_instruction(
+    instruction_acc: &Info,
+) -> Result<(u8, solana_program::instruction::Instruction)> {
+    let current_instruction =
+        solana_program::sysvar::instructions::load_current_index_checked(instruction_acc)?;
+    if current_instruction == 0 {
+        return Err(InstructionAtWrongIndex.into());
+    }
+
+    // The previous ix must be a secp verification instruction
+    let secp_ix_index = (current_instruction - 1) as u8;
+    let secp_ix = solana_program::sysvar::instructions::load_instruction_at_checked(
+        secp_ix_index as usize,
+        instruction_acc,
+    )
+    .map_err(|_| ProgramError::InvalidAccountData)?;
+
+    Ok((secp_ix_index, secp_ix))
+}
+
+/// Hash all of `parts` are signatures over, the 32 byte message they share in `data`.
+fn signed_hash<'a, 'b: 'a>(
+    data: &[u8],
+    mut parts: impl Iterator<Item = &'a SecpInstructionPart<'b>>,
+) -> Result<[u8; 32]> {
+    let first = parts.next().ok_or(ProgramError::InvalidArgument)?;
+
+    // Make sure that all messages are equal
+    if parts.any(|part| part.msg_offset != first.msg_offset || part.msg_size != first.msg_size) {
+        return Err(InvalidSecpInstruction.into());
+    }
+
+    // Data must be a hash
+    if first.msg_size != 32 {
+        return Err(ProgramError::InvalidArgument.into());
+    }
+
+    // Extract message which is encoded in Solana Secp256k1 instruction data.
+    let message = data
+        .get(first.msg_offset as usize..first.msg_offset as usize + 32)
+        .ok_or(InvalidSecpInstruction)?;
+
+    let mut msg_hash: [u8; 32] = [0u8; 32];
+    msg_hash.copy_from_slice(message);
+    Ok(msg_hash)
+}
+
+/// Write sigs of checked addresses into `signatures`.
+fn write_signatures(
+    guardian_set: &GuardianSetData,
+    sig_infos: &[SigInfo],
+    secp_ixs: &[SecpInstructionPart],
+    sig_len: u8,
+    secp256r1: bool,
+    signatures: &mut [bool],
+) -> Result<()> {
     for s in sig_infos {
-        if s.signer_index > accs.guardian_set.num_guardians() {
+        if s.signer_index >= guardian_set.keys.len() {
             return Err(ProgramError::InvalidArgument.into());
         }
 
@@ -204,15 +616,156 @@ pub fn verify_signatures(
             return Err(ProgramError::InvalidArgument.into());
         }
 
-        let key = accs.guardian_set.keys[s.signer_index as usize];
+        let key = guardian_key(guardian_set, s.signer_index, secp256r1)
+            .ok_or(ProgramError::InvalidArgument)?;
         // Check key in ix
         if key != secp_ixs[s.sig_index as usize].address {
             return Err(ProgramError::InvalidArgument.into());
         }
 
         // Overwritten content should be zeros except double signs by the signer or harmless replays
-        accs.signature_set.signatures[s.signer_index as usize] = true;
+        signatures[s.signer_index] = true;
+    }
+
+    Ok(())
+}
+
+/// Key the guardian at `index` of the set signs with, its secp256r1 key for secp256r1 signatures.
+fn guardian_key(guardian_set: &GuardianSetData, index: usize, secp256r1: bool) -> Option<&[u8]> {
+    if secp256r1 {
+        guardian_set.secp256r1_key(index as u8).map(|key| &key[..])
+    } else {
+        guardian_set.keys.get(index).map(|key| &key[..])
+    }
+}
+
+/// Parse the signatures checked by a secp256k1 program instruction at `ix_index`, over messages
+/// in that instruction.
+fn parse_secp256k1_instruction(
+    data: &[u8],
+    ix_index: u8,
+) -> Result<(u8, Vec<SecpInstructionPart>)> {
+    if data.len() < 2 {
+        return Err(InvalidSecpInstruction.into());
+    }
+
+    let sig_len = data[0];
+    let mut index = 1;
+
+    let mut secp_ixs: Vec<SecpInstructionPart> = Vec::with_capacity(sig_len as usize);
+    for _ in 0..sig_len {
+        let _sig_offset = byteorder::LE::read_u16(&data[index..index + 2]) as usize;
+        index += 2;
+        let sig_ix = data[index];
+        index += 1;
+        let address_offset = byteorder::LE::read_u16(&data[index..index + 2]) as usize;
+        index += 2;
+        let address_ix = data[index];
+        index += 1;
+        let msg_offset = byteorder::LE::read_u16(&data[index..index + 2]);
+        index += 2;
+        let msg_size = byteorder::LE::read_u16(&data[index..index + 2]);
+        index += 2;
+        let msg_ix = data[index];
+        index += 1;
+
+        if address_ix != ix_index || msg_ix != ix_index || sig_ix != ix_index {
+            return Err(InvalidSecpInstruction.into());
+        }
+
+        let address: &[u8] = &data[address_offset..address_offset + 20];
+        secp_ixs.push(SecpInstructionPart {
+            address,
+            msg_offset,
+            msg_size,
+        });
+    }
+
+    Ok((sig_len, secp_ixs))
+}
+
+#[cfg(feature = "secp256r1")]
+mod secp256r1_program {
+    solana_program::declare_id!("Secp256r1SigVerify1111111111111111111111111");
+}
+
+/// Secp256r1 signatures are only accepted once governance enabled them. The flag is looked up
+/// among the instruction accounts so the account layout stays unchanged, clients append it with
+/// [`with_secp256r1_verification`](crate::instructions::with_secp256r1_verification).
+#[cfg(feature = "secp256r1")]
+fn verify_secp256r1_enabled(ctx: &ExecutionContext) -> Result<()> {
+    use crate::{
+        accounts::{
+            Secp256r1Verification,
+            Secp256r1VerificationData,
+        },
+        error::Error::Secp256r1VerificationDisabled,
+    };
+
+    let key = Secp256r1Verification::<'_, { AccountState::Initialized }>::key(None, ctx.program_id);
+    let info = ctx
+        .accounts
+        .iter()
+        .find(|info| *info.key == key)
+        .ok_or(Secp256r1VerificationDisabled)?;
+    if info.owner != ctx.program_id {
+        return Err(SolitaireError::InvalidOwner(*info.owner));
+    }
+    let verification = Secp256r1VerificationData::try_from_slice(&info.data.borrow())?;
+    if !verification.enabled {
+        return Err(Secp256r1VerificationDisabled.into());
     }
 
     Ok(())
 }
+
+/// Parse the signatures checked by a secp256r1 precompile instruction at `ix_index`, over messages
+/// in that instruction. The precompile verifies signatures over the SHA-256 digest
+/// of the message, which is the 32 byte VAA hash like for secp256k1.
+#[cfg(feature = "secp256r1")]
+fn parse_secp256r1_instruction(
+    data: &[u8],
+    ix_index: u8,
+) -> Result<(u8, Vec<SecpInstructionPart>)> {
+    // Offsets are little-endian u16s, an instruction index of u16::MAX refers to the precompile
+    // instruction itself.
+    const OFFSETS_START: usize = 2;
+    const OFFSETS_LEN: usize = 14;
+    const PUBLIC_KEY_LEN: usize = 33;
+
+    let read_u16 = |at: usize| -> Result<u16> {
+        data.get(at..at + 2)
+            .map(byteorder::LE::read_u16)
+            .ok_or_else(|| InvalidSecpInstruction.into())
+    };
+    let is_local = |ix: u16| ix == u16::MAX || ix == ix_index as u16;
+
+    let sig_len = *data.first().ok_or(InvalidSecpInstruction)?;
+    let mut secp_ixs: Vec<SecpInstructionPart> = Vec::with_capacity(sig_len as usize);
+    for i in 0..sig_len as usize {
+        let offsets = OFFSETS_START + i * OFFSETS_LEN;
+        let sig_ix = read_u16(offsets + 2)?;
+        let address_offset = read_u16(offsets + 4)? as usize;
+        let address_ix = read_u16(offsets + 6)?;
+        let msg_offset = read_u16(offsets + 8)?;
+        let msg_size = read_u16(offsets + 10)?;
+        let msg_ix = read_u16(offsets + 12)?;
+
+        if !is_local(address_ix) || !is_local(msg_ix) || !is_local(sig_ix) {
+            return Err(InvalidSecpInstruction.into());
+        }
+
+        let address = data
+            .get(address_offset..address_offset + PUBLIC_KEY_LEN)
+            .ok_or(InvalidSecpInstruction)?;
+        data.get(msg_offset as usize..msg_offset as usize + msg_size as usize)
+            .ok_or(InvalidSecpInstruction)?;
+        secp_ixs.push(SecpInstructionPart {
+            address,
+            msg_offset,
+            msg_size,
+        });
+    }
+
+    Ok((sig_len, secp_ixs))
+}
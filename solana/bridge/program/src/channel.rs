@@ -0,0 +1,226 @@
+//! Typed one-way channels of messages from an emitter to a receiving program.
+//!
+//! A [`Channel`] names the emitter sending on it, the chain and address of the program receiving
+//! its messages and the [`Codec`] the messages are encoded with. Programs and their off-chain code
+//! share the definition: messages are sent with [`post_message`], received on-chain from a
+//! [`PayloadMessage`] with [`receive`] and read off-chain from a VAA with [`open`].
+//!
+//! Payloads start with the target of the channel, so that receivers can tell messages meant for
+//! them from those of other channels of the same emitter:
+//!
+//! | field          | size | encoding   |
+//! |----------------+------+------------|
+//! | target_chain   | 2    | big endian |
+//! | target_address | 32   |            |
+//! | message        | rest | codec      |
+
+use crate::{
+    accounts::{
+        claim,
+        Claim,
+    },
+    api::ForeignAddress,
+    codec::{
+        ReadBytes,
+        WriteBytes,
+    },
+    error::Error::{
+        InvalidChannelSource,
+        InvalidChannelTarget,
+    },
+    vaa::{
+        DeserializePayload,
+        EmitterInfo,
+        PayloadMessage,
+        SerializePayload,
+    },
+    CHAIN_ID_SOLANA,
+};
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use solana_program::pubkey::Pubkey;
+use solitaire::{
+    ExecutionContext,
+    Result,
+    SolitaireError,
+};
+use std::{
+    io::{
+        Read,
+        Write,
+    },
+    marker::PhantomData,
+};
+
+#[cfg(feature = "instructions")]
+use crate::types::ConsistencyLevel;
+#[cfg(feature = "instructions")]
+use solana_program::instruction::Instruction;
+
+/// Encoding of the messages sent on a channel.
+pub trait Codec {
+    type Message;
+
+    fn encode(message: &Self::Message) -> Result<Vec<u8>>;
+
+    /// Decode a message taking up all of `data`.
+    fn decode(data: &[u8]) -> Result<Self::Message>;
+}
+
+/// Messages encoded by their [`SerializePayload`] and [`DeserializePayload`] implementations.
+pub struct PayloadCodec<T>(PhantomData<T>);
+
+impl<T: SerializePayload + DeserializePayload> Codec for PayloadCodec<T> {
+    type Message = T;
+
+    fn encode(message: &T) -> Result<Vec<u8>> {
+        SerializePayload::try_to_vec(message)
+    }
+
+    fn decode(mut data: &[u8]) -> Result<T> {
+        T::deserialize(&mut data)
+    }
+}
+
+/// Messages encoded with borsh.
+pub struct BorshCodec<T>(PhantomData<T>);
+
+impl<T: BorshSerialize + BorshDeserialize> Codec for BorshCodec<T> {
+    type Message = T;
+
+    fn encode(message: &T) -> Result<Vec<u8>> {
+        Ok(message.try_to_vec()?)
+    }
+
+    fn decode(data: &[u8]) -> Result<T> {
+        Ok(T::try_from_slice(data)?)
+    }
+}
+
+/// Channel of messages from a single emitter to a single program.
+pub trait Channel {
+    /// Encoding of the messages sent on the channel
+    type Codec: Codec;
+
+    /// Chain of the emitter sending on the channel
+    const SOURCE_CHAIN: u16;
+
+    /// Chain of the program receiving the messages
+    const TARGET_CHAIN: u16;
+
+    /// Emitter sending on the channel, the emitter PDA of programs sending from Solana.
+    fn source_address() -> ForeignAddress;
+
+    /// Program receiving the messages, its program id for programs on Solana.
+    fn target_address() -> ForeignAddress;
+}
+
+/// Message sent on the channel `C`.
+pub type Message<C> = <<C as Channel>::Codec as Codec>::Message;
+
+/// Payload of the messages sent on the channel `C`, see the [module docs](self) for the layout.
+pub struct ChannelMessage<C: Channel> {
+    pub target_chain: u16,
+    pub target_address: ForeignAddress,
+    pub message: Message<C>,
+}
+
+impl<C: Channel> ChannelMessage<C> {
+    pub fn new(message: Message<C>) -> Self {
+        ChannelMessage {
+            target_chain: C::TARGET_CHAIN,
+            target_address: C::target_address(),
+            message,
+        }
+    }
+
+    /// Check that the message was sent on `C` by `emitter`.
+    pub fn verify(&self, emitter: &EmitterInfo) -> Result<()> {
+        if !emitter.is(C::SOURCE_CHAIN, &C::source_address()) {
+            return Err(InvalidChannelSource.into());
+        }
+        if self.target_chain != C::TARGET_CHAIN || self.target_address != C::target_address() {
+            return Err(InvalidChannelTarget.into());
+        }
+        Ok(())
+    }
+}
+
+impl<C: Channel> SerializePayload for ChannelMessage<C> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::result::Result<(), SolitaireError> {
+        writer.write_u16_be(self.target_chain)?;
+        writer.write_all(&self.target_address)?;
+        writer.write_all(&<C::Codec as Codec>::encode(&self.message)?)?;
+        Ok(())
+    }
+}
+
+impl<C: Channel> DeserializePayload for ChannelMessage<C> {
+    fn deserialize(buf: &mut &[u8]) -> std::result::Result<Self, SolitaireError> {
+        let target_chain = buf.read_u16_be()?;
+        let mut target_address = ForeignAddress::default();
+        buf.read_exact(&mut target_address)?;
+        let message = <C::Codec as Codec>::decode(buf)?;
+        *buf = &[];
+
+        Ok(ChannelMessage {
+            target_chain,
+            target_address,
+            message,
+        })
+    }
+}
+
+/// Build the `post_message` instruction sending `message` on `C`. `emitter` must be the source of
+/// the channel, programs sign the CPI as their emitter PDA.
+#[cfg(feature = "instructions")]
+pub fn post_message<C: Channel>(
+    program_id: Pubkey,
+    payer: Pubkey,
+    emitter: Pubkey,
+    message_key: Pubkey,
+    nonce: u32,
+    message: Message<C>,
+    consistency_level: ConsistencyLevel,
+) -> Result<Instruction> {
+    if C::SOURCE_CHAIN != CHAIN_ID_SOLANA || emitter.to_bytes() != C::source_address() {
+        return Err(InvalidChannelSource.into());
+    }
+
+    crate::instructions::post_message(
+        program_id,
+        payer,
+        emitter,
+        message_key,
+        nonce,
+        SerializePayload::try_to_vec(&ChannelMessage::<C>::new(message))?,
+        consistency_level,
+    )
+}
+
+/// Receive a message sent on `C` to the executing program, claiming its VAA so that it is only
+/// received once.
+pub fn receive<'a, C: Channel>(
+    ctx: &ExecutionContext,
+    payer: &Pubkey,
+    claim: &mut Claim,
+    vaa: &'a PayloadMessage<ChannelMessage<C>>,
+) -> Result<&'a Message<C>> {
+    if C::TARGET_CHAIN != CHAIN_ID_SOLANA || C::target_address() != ctx.program_id.to_bytes() {
+        return Err(InvalidChannelTarget.into());
+    }
+    vaa.verify(&vaa.emitter())?;
+    claim::consume(ctx, payer, claim, vaa)?;
+
+    Ok(&vaa.message)
+}
+
+/// Decode a message sent on `C` from the payload of a VAA emitted by `emitter`, for off-chain
+/// receivers.
+pub fn open<C: Channel>(emitter: &EmitterInfo, payload: &[u8]) -> Result<Message<C>> {
+    let message = <ChannelMessage<C> as DeserializePayload>::deserialize(&mut &payload[..])?;
+    message.verify(emitter)?;
+    Ok(message.message)
+}
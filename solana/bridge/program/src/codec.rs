@@ -0,0 +1,99 @@
+//! Readers and writers of the numbers in payloads, with the byte order spelled out at every call.
+//!
+//! Messages exchanged with other chains, such as VAA bodies, governance payloads and token bridge
+//! transfers, are big endian like the EVM contracts producing them. Data only ever read by Solana
+//! programs, such as accounts of other programs, is little endian. Parsers use these traits rather
+//! than `from_be_bytes` and byteorder type parameters, so the order of every field is visible where
+//! it is read.
+
+use primitive_types::U256;
+use std::io::{
+    Read,
+    Result,
+    Write,
+};
+
+/// Explicitly ordered reads, implemented for every reader.
+pub trait ReadBytes: Read {
+    fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    fn read_u16_be(&mut self) -> Result<u16> {
+        Ok(u16::from_be_bytes(self.read_array()?))
+    }
+
+    fn read_u32_be(&mut self) -> Result<u32> {
+        Ok(u32::from_be_bytes(self.read_array()?))
+    }
+
+    fn read_u64_be(&mut self) -> Result<u64> {
+        Ok(u64::from_be_bytes(self.read_array()?))
+    }
+
+    fn read_u256_be(&mut self) -> Result<U256> {
+        Ok(U256::from_big_endian(&self.read_array::<32>()?))
+    }
+
+    fn read_u16_le(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_u32_le(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_u64_le(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.read_array()?))
+    }
+
+    /// Fixed size field, such as an address or a hash.
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let mut data = [0u8; N];
+        self.read_exact(&mut data)?;
+        Ok(data)
+    }
+}
+
+impl<R: Read + ?Sized> ReadBytes for R {
+}
+
+/// Explicitly ordered writes, implemented for every writer.
+pub trait WriteBytes: Write {
+    fn write_u8(&mut self, n: u8) -> Result<()> {
+        self.write_all(&[n])
+    }
+
+    fn write_u16_be(&mut self, n: u16) -> Result<()> {
+        self.write_all(&n.to_be_bytes())
+    }
+
+    fn write_u32_be(&mut self, n: u32) -> Result<()> {
+        self.write_all(&n.to_be_bytes())
+    }
+
+    fn write_u64_be(&mut self, n: u64) -> Result<()> {
+        self.write_all(&n.to_be_bytes())
+    }
+
+    fn write_u256_be(&mut self, n: U256) -> Result<()> {
+        let mut data = [0u8; 32];
+        n.to_big_endian(&mut data);
+        self.write_all(&data)
+    }
+
+    fn write_u16_le(&mut self, n: u16) -> Result<()> {
+        self.write_all(&n.to_le_bytes())
+    }
+
+    fn write_u32_le(&mut self, n: u32) -> Result<()> {
+        self.write_all(&n.to_le_bytes())
+    }
+
+    fn write_u64_le(&mut self, n: u64) -> Result<()> {
+        self.write_all(&n.to_le_bytes())
+    }
+}
+
+impl<W: Write + ?Sized> WriteBytes for W {
+}
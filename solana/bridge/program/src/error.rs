@@ -2,9 +2,13 @@
 //! make up the wormhole bridge.
 
 use crate::trace;
+use solana_program::program_error::ProgramError;
 use solitaire::SolitaireError;
 
-#[derive(Debug)]
+/// Errors are returned to the runtime as `ProgramError::Custom(ERROR_CODE_OFFSET + index)`, where
+/// `index` is the position of the variant below. Variants are only ever appended, so the codes stay
+/// stable across releases and clients can decode them with [`Error::from_code`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Error {
     GuardianSetMismatch,
     InstructionAtWrongIndex,
@@ -26,6 +30,285 @@ pub enum Error {
     VAAInvalid,
     InvalidPayloadLength,
     EmitterChanged,
+    InvalidSpillAccount,
+    InvalidGuardianWeights,
+    PayloadHashMismatch,
+    NonceRegression,
+    GovernanceNotAnnounced,
+    GovernanceAnnouncementPending,
+    InvalidEmitterName,
+    InvalidMigrationAccount,
+    InvalidMigrationSource,
+    MissingGuardianSetFloor,
+    GuardianSetDowngradeNotConfirmed,
+    GuardianSetDowngradePending,
+    FeeBurnDisabled,
+    MissingVersionRegistry,
+    InvalidVersion,
+    VersionHashMismatch,
+    InvalidForwardTarget,
+    InvalidForwardedVAA,
+    Secp256r1VerificationDisabled,
+    UnreliableMessageConsumed,
+    InvalidFeeOracle,
+    GovernanceNotHistorical,
+    ClaimPayloadTooLarge,
+    InvalidChannelSource,
+    InvalidChannelTarget,
+    InvalidExportAccount,
+    LegacyClaimConsumed,
+    InvalidLegacyClaim,
+    InvalidUpgradeBuffer,
+    InvalidUpgradeBufferAuthority,
+    InvalidUpgradeBufferElf,
+    UpgradeBufferTooLarge,
+    InvalidEncodedVaa,
+    EncodedVaaWriteAuthorityMismatch,
+    EncodedVaaNotWriting,
+    EncodedVaaWriteOutOfBounds,
+    InvalidSequenceRange,
+    SequenceRangeIncomplete,
+    InvalidRefundRecipient,
+    VAANotClaimed,
+    SignatureSetInUse,
+    InvalidSignatureSet,
+    InvalidDraftMessage,
+    DraftMessageEmitterMismatch,
+    DraftMessageWriteOutOfBounds,
+    InvalidQuorum,
+    InvalidFeeInvoice,
+    FeeInvoiceExpired,
+    FeeInvoiceNotExpired,
+    StateCommitmentTooEarly,
+    InvalidStateCommitmentMessage,
+    InvalidDeployAuthority,
+    InvalidRepairSource,
+    Paused,
+    NoSuccessor,
+    InvalidSuccessor,
+    UpgradeBufferHashMismatch,
+    GovernanceNotQueued,
+    GovernanceTimelocked,
+    MissingEmitterStats,
+    EmissionBreakerTripped,
+    ClaimCollectionDisabled,
+    ClaimGracePeriodActive,
+    ClaimTreeProofInvalid,
+    ClaimTreeLeafConsumed,
+    InvalidFeeTokenAccount,
+    NamedAccountsUnsupported,
+    UnapprovedClaimConsumer,
+    UnapprovedVerifier,
+}
+
+/// Offset of the core bridge error codes. Solitaire reports its own failures, such as account
+/// checks, as `Custom(0)`, bridge errors start well clear of it.
+pub const ERROR_CODE_OFFSET: u32 = 6000;
+
+/// Every variant in declaration order, indexed by `code - ERROR_CODE_OFFSET`.
+const ERRORS: [Error; 89] = [
+    Error::GuardianSetMismatch,
+    Error::InstructionAtWrongIndex,
+    Error::InsufficientFees,
+    Error::InvalidFeeRecipient,
+    Error::InvalidGovernanceAction,
+    Error::InvalidGovernanceChain,
+    Error::InvalidGovernanceKey,
+    Error::InvalidGovernanceModule,
+    Error::InvalidGovernanceWithdrawal,
+    Error::InvalidGuardianSetUpgrade,
+    Error::InvalidHash,
+    Error::InvalidSecpInstruction,
+    Error::MathOverflow,
+    Error::PostVAAConsensusFailed,
+    Error::PostVAAGuardianSetExpired,
+    Error::TooManyGuardians,
+    Error::VAAAlreadyExecuted,
+    Error::VAAInvalid,
+    Error::InvalidPayloadLength,
+    Error::EmitterChanged,
+    Error::InvalidSpillAccount,
+    Error::InvalidGuardianWeights,
+    Error::PayloadHashMismatch,
+    Error::NonceRegression,
+    Error::GovernanceNotAnnounced,
+    Error::GovernanceAnnouncementPending,
+    Error::InvalidEmitterName,
+    Error::InvalidMigrationAccount,
+    Error::InvalidMigrationSource,
+    Error::MissingGuardianSetFloor,
+    Error::GuardianSetDowngradeNotConfirmed,
+    Error::GuardianSetDowngradePending,
+    Error::FeeBurnDisabled,
+    Error::MissingVersionRegistry,
+    Error::InvalidVersion,
+    Error::VersionHashMismatch,
+    Error::InvalidForwardTarget,
+    Error::InvalidForwardedVAA,
+    Error::Secp256r1VerificationDisabled,
+    Error::UnreliableMessageConsumed,
+    Error::InvalidFeeOracle,
+    Error::GovernanceNotHistorical,
+    Error::ClaimPayloadTooLarge,
+    Error::InvalidChannelSource,
+    Error::InvalidChannelTarget,
+    Error::InvalidExportAccount,
+    Error::LegacyClaimConsumed,
+    Error::InvalidLegacyClaim,
+    Error::InvalidUpgradeBuffer,
+    Error::InvalidUpgradeBufferAuthority,
+    Error::InvalidUpgradeBufferElf,
+    Error::UpgradeBufferTooLarge,
+    Error::InvalidEncodedVaa,
+    Error::EncodedVaaWriteAuthorityMismatch,
+    Error::EncodedVaaNotWriting,
+    Error::EncodedVaaWriteOutOfBounds,
+    Error::InvalidSequenceRange,
+    Error::SequenceRangeIncomplete,
+    Error::InvalidRefundRecipient,
+    Error::VAANotClaimed,
+    Error::SignatureSetInUse,
+    Error::InvalidSignatureSet,
+    Error::InvalidDraftMessage,
+    Error::DraftMessageEmitterMismatch,
+    Error::DraftMessageWriteOutOfBounds,
+    Error::InvalidQuorum,
+    Error::InvalidFeeInvoice,
+    Error::FeeInvoiceExpired,
+    Error::FeeInvoiceNotExpired,
+    Error::StateCommitmentTooEarly,
+    Error::InvalidStateCommitmentMessage,
+    Error::InvalidDeployAuthority,
+    Error::InvalidRepairSource,
+    Error::Paused,
+    Error::NoSuccessor,
+    Error::InvalidSuccessor,
+    Error::UpgradeBufferHashMismatch,
+    Error::GovernanceNotQueued,
+    Error::GovernanceTimelocked,
+    Error::MissingEmitterStats,
+    Error::EmissionBreakerTripped,
+    Error::ClaimCollectionDisabled,
+    Error::ClaimGracePeriodActive,
+    Error::ClaimTreeProofInvalid,
+    Error::ClaimTreeLeafConsumed,
+    Error::InvalidFeeTokenAccount,
+    Error::NamedAccountsUnsupported,
+    Error::UnapprovedClaimConsumer,
+    Error::UnapprovedVerifier,
+];
+
+impl Error {
+    /// Code the error is returned to the runtime with.
+    pub const fn code(self) -> u32 {
+        ERROR_CODE_OFFSET + self as u32
+    }
+
+    /// Decode the custom program error code of a failed bridge instruction. A CPI that fails aborts
+    /// the whole transaction, so this is meant for the clients and tests of programs calling into
+    /// the bridge, which see the code in the transaction error.
+    pub fn from_code(code: u32) -> Option<Error> {
+        let index = code.checked_sub(ERROR_CODE_OFFSET)?;
+        ERRORS.get(index as usize).copied()
+    }
+}
+
+/// Stable codes of the core bridge errors, see [`Error::code`].
+#[cfg(feature = "client")]
+pub mod codes {
+    use super::Error;
+
+    pub const GUARDIAN_SET_MISMATCH: u32 = Error::GuardianSetMismatch.code();
+    pub const INSTRUCTION_AT_WRONG_INDEX: u32 = Error::InstructionAtWrongIndex.code();
+    pub const INSUFFICIENT_FEES: u32 = Error::InsufficientFees.code();
+    pub const INVALID_FEE_RECIPIENT: u32 = Error::InvalidFeeRecipient.code();
+    pub const INVALID_GOVERNANCE_ACTION: u32 = Error::InvalidGovernanceAction.code();
+    pub const INVALID_GOVERNANCE_CHAIN: u32 = Error::InvalidGovernanceChain.code();
+    pub const INVALID_GOVERNANCE_KEY: u32 = Error::InvalidGovernanceKey.code();
+    pub const INVALID_GOVERNANCE_MODULE: u32 = Error::InvalidGovernanceModule.code();
+    pub const INVALID_GOVERNANCE_WITHDRAWAL: u32 = Error::InvalidGovernanceWithdrawal.code();
+    pub const INVALID_GUARDIAN_SET_UPGRADE: u32 = Error::InvalidGuardianSetUpgrade.code();
+    pub const INVALID_HASH: u32 = Error::InvalidHash.code();
+    pub const INVALID_SECP_INSTRUCTION: u32 = Error::InvalidSecpInstruction.code();
+    pub const MATH_OVERFLOW: u32 = Error::MathOverflow.code();
+    pub const POST_VAA_CONSENSUS_FAILED: u32 = Error::PostVAAConsensusFailed.code();
+    pub const POST_VAA_GUARDIAN_SET_EXPIRED: u32 = Error::PostVAAGuardianSetExpired.code();
+    pub const TOO_MANY_GUARDIANS: u32 = Error::TooManyGuardians.code();
+    pub const VAA_ALREADY_EXECUTED: u32 = Error::VAAAlreadyExecuted.code();
+    pub const VAA_INVALID: u32 = Error::VAAInvalid.code();
+    pub const INVALID_PAYLOAD_LENGTH: u32 = Error::InvalidPayloadLength.code();
+    pub const EMITTER_CHANGED: u32 = Error::EmitterChanged.code();
+    pub const INVALID_SPILL_ACCOUNT: u32 = Error::InvalidSpillAccount.code();
+    pub const INVALID_GUARDIAN_WEIGHTS: u32 = Error::InvalidGuardianWeights.code();
+    pub const PAYLOAD_HASH_MISMATCH: u32 = Error::PayloadHashMismatch.code();
+    pub const NONCE_REGRESSION: u32 = Error::NonceRegression.code();
+    pub const GOVERNANCE_NOT_ANNOUNCED: u32 = Error::GovernanceNotAnnounced.code();
+    pub const GOVERNANCE_ANNOUNCEMENT_PENDING: u32 = Error::GovernanceAnnouncementPending.code();
+    pub const INVALID_EMITTER_NAME: u32 = Error::InvalidEmitterName.code();
+    pub const INVALID_MIGRATION_ACCOUNT: u32 = Error::InvalidMigrationAccount.code();
+    pub const INVALID_MIGRATION_SOURCE: u32 = Error::InvalidMigrationSource.code();
+    pub const MISSING_GUARDIAN_SET_FLOOR: u32 = Error::MissingGuardianSetFloor.code();
+    pub const GUARDIAN_SET_DOWNGRADE_NOT_CONFIRMED: u32 =
+        Error::GuardianSetDowngradeNotConfirmed.code();
+    pub const GUARDIAN_SET_DOWNGRADE_PENDING: u32 = Error::GuardianSetDowngradePending.code();
+    pub const FEE_BURN_DISABLED: u32 = Error::FeeBurnDisabled.code();
+    pub const MISSING_VERSION_REGISTRY: u32 = Error::MissingVersionRegistry.code();
+    pub const INVALID_VERSION: u32 = Error::InvalidVersion.code();
+    pub const VERSION_HASH_MISMATCH: u32 = Error::VersionHashMismatch.code();
+    pub const INVALID_FORWARD_TARGET: u32 = Error::InvalidForwardTarget.code();
+    pub const INVALID_FORWARDED_VAA: u32 = Error::InvalidForwardedVAA.code();
+    pub const SECP256R1_VERIFICATION_DISABLED: u32 = Error::Secp256r1VerificationDisabled.code();
+    pub const UNRELIABLE_MESSAGE_CONSUMED: u32 = Error::UnreliableMessageConsumed.code();
+    pub const INVALID_FEE_ORACLE: u32 = Error::InvalidFeeOracle.code();
+    pub const GOVERNANCE_NOT_HISTORICAL: u32 = Error::GovernanceNotHistorical.code();
+    pub const CLAIM_PAYLOAD_TOO_LARGE: u32 = Error::ClaimPayloadTooLarge.code();
+    pub const INVALID_CHANNEL_SOURCE: u32 = Error::InvalidChannelSource.code();
+    pub const INVALID_CHANNEL_TARGET: u32 = Error::InvalidChannelTarget.code();
+    pub const INVALID_EXPORT_ACCOUNT: u32 = Error::InvalidExportAccount.code();
+    pub const LEGACY_CLAIM_CONSUMED: u32 = Error::LegacyClaimConsumed.code();
+    pub const INVALID_LEGACY_CLAIM: u32 = Error::InvalidLegacyClaim.code();
+    pub const INVALID_UPGRADE_BUFFER: u32 = Error::InvalidUpgradeBuffer.code();
+    pub const INVALID_UPGRADE_BUFFER_AUTHORITY: u32 = Error::InvalidUpgradeBufferAuthority.code();
+    pub const INVALID_UPGRADE_BUFFER_ELF: u32 = Error::InvalidUpgradeBufferElf.code();
+    pub const UPGRADE_BUFFER_TOO_LARGE: u32 = Error::UpgradeBufferTooLarge.code();
+    pub const INVALID_ENCODED_VAA: u32 = Error::InvalidEncodedVaa.code();
+    pub const ENCODED_VAA_WRITE_AUTHORITY_MISMATCH: u32 =
+        Error::EncodedVaaWriteAuthorityMismatch.code();
+    pub const ENCODED_VAA_NOT_WRITING: u32 = Error::EncodedVaaNotWriting.code();
+    pub const ENCODED_VAA_WRITE_OUT_OF_BOUNDS: u32 = Error::EncodedVaaWriteOutOfBounds.code();
+    pub const INVALID_SEQUENCE_RANGE: u32 = Error::InvalidSequenceRange.code();
+    pub const SEQUENCE_RANGE_INCOMPLETE: u32 = Error::SequenceRangeIncomplete.code();
+    pub const INVALID_REFUND_RECIPIENT: u32 = Error::InvalidRefundRecipient.code();
+    pub const VAA_NOT_CLAIMED: u32 = Error::VAANotClaimed.code();
+    pub const SIGNATURE_SET_IN_USE: u32 = Error::SignatureSetInUse.code();
+    pub const INVALID_SIGNATURE_SET: u32 = Error::InvalidSignatureSet.code();
+    pub const INVALID_DRAFT_MESSAGE: u32 = Error::InvalidDraftMessage.code();
+    pub const DRAFT_MESSAGE_EMITTER_MISMATCH: u32 = Error::DraftMessageEmitterMismatch.code();
+    pub const DRAFT_MESSAGE_WRITE_OUT_OF_BOUNDS: u32 = Error::DraftMessageWriteOutOfBounds.code();
+    pub const INVALID_QUORUM: u32 = Error::InvalidQuorum.code();
+    pub const INVALID_FEE_INVOICE: u32 = Error::InvalidFeeInvoice.code();
+    pub const FEE_INVOICE_EXPIRED: u32 = Error::FeeInvoiceExpired.code();
+    pub const FEE_INVOICE_NOT_EXPIRED: u32 = Error::FeeInvoiceNotExpired.code();
+    pub const STATE_COMMITMENT_TOO_EARLY: u32 = Error::StateCommitmentTooEarly.code();
+    pub const INVALID_STATE_COMMITMENT_MESSAGE: u32 = Error::InvalidStateCommitmentMessage.code();
+    pub const INVALID_DEPLOY_AUTHORITY: u32 = Error::InvalidDeployAuthority.code();
+    pub const INVALID_REPAIR_SOURCE: u32 = Error::InvalidRepairSource.code();
+    pub const PAUSED: u32 = Error::Paused.code();
+    pub const NO_SUCCESSOR: u32 = Error::NoSuccessor.code();
+    pub const INVALID_SUCCESSOR: u32 = Error::InvalidSuccessor.code();
+    pub const UPGRADE_BUFFER_HASH_MISMATCH: u32 = Error::UpgradeBufferHashMismatch.code();
+    pub const GOVERNANCE_NOT_QUEUED: u32 = Error::GovernanceNotQueued.code();
+    pub const GOVERNANCE_TIMELOCKED: u32 = Error::GovernanceTimelocked.code();
+    pub const MISSING_EMITTER_STATS: u32 = Error::MissingEmitterStats.code();
+    pub const EMISSION_BREAKER_TRIPPED: u32 = Error::EmissionBreakerTripped.code();
+    pub const CLAIM_COLLECTION_DISABLED: u32 = Error::ClaimCollectionDisabled.code();
+    pub const CLAIM_GRACE_PERIOD_ACTIVE: u32 = Error::ClaimGracePeriodActive.code();
+    pub const CLAIM_TREE_PROOF_INVALID: u32 = Error::ClaimTreeProofInvalid.code();
+    pub const CLAIM_TREE_LEAF_CONSUMED: u32 = Error::ClaimTreeLeafConsumed.code();
+    pub const INVALID_FEE_TOKEN_ACCOUNT: u32 = Error::InvalidFeeTokenAccount.code();
+    pub const NAMED_ACCOUNTS_UNSUPPORTED: u32 = Error::NamedAccountsUnsupported.code();
+    pub const UNAPPROVED_CLAIM_CONSUMER: u32 = Error::UnapprovedClaimConsumer.code();
+    pub const UNAPPROVED_VERIFIER: u32 = Error::UnapprovedVerifier.code();
 }
 
 /// Errors thrown by the program will bubble up to the solitaire wrapper, which needs a way to
@@ -33,6 +316,6 @@ pub enum Error {
 impl From<Error> for SolitaireError {
     fn from(e: Error) -> SolitaireError {
         trace!("ProgramError: {:?}", e);
-        SolitaireError::Custom(e as u64)
+        SolitaireError::ProgramError(ProgramError::Custom(e.code()))
     }
 }
@@ -1,49 +1,148 @@
-use borsh::BorshSerialize;
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
 use solana_program::{
+    bpf_loader_upgradeable,
+    incinerator,
     instruction::{
         AccountMeta,
         Instruction,
     },
+    program::get_return_data,
     pubkey::Pubkey,
     sysvar,
 };
 
-use byteorder::{
-    BigEndian,
-    WriteBytesExt,
-};
+use crate::codec::WriteBytes;
 use sha3::Digest;
 use solitaire::{
-    processors::seeded::Seeded,
+    processors::{
+        event,
+        seeded::Seeded,
+    },
     AccountState,
 };
-use std::io::{
-    Cursor,
-    Write,
+use std::{
+    convert::TryInto,
+    io::{
+        Cursor,
+        Write,
+    },
 };
+use wormhole_solana_consts::seeds;
 
 use crate::{
     accounts::{
         Bridge,
         Claim,
+        ClaimConsumer,
+        ClaimConsumerDerivationData,
         ClaimDerivationData,
+        DigestCache,
+        DigestCacheDerivationData,
+        EmitterStats,
+        EmitterStatsDerivationData,
+        FeeBurn,
         FeeCollector,
+        FeeExemption,
+        FeeExemptionDerivationData,
+        FeeInvoice,
+        FeeInvoiceDerivationData,
+        FeeOracle,
+        GovernanceAnnouncement,
+        GovernanceAnnouncementDerivationData,
         GuardianSet,
         GuardianSetDerivationData,
+        GuardianSetExpiry,
+        GuardianSetFloor,
+        GuardianSetWeights,
+        GuardianSetWeightsDerivationData,
+        Migration,
+        PendingGovernance,
+        PendingGovernanceDerivationData,
+        PostedMessage,
+        PostedMessageDerivationData,
         PostedVAA,
+        PostedVAACompact,
+        PostedVAACompactDerivationData,
         PostedVAADerivationData,
+        Secp256r1Verification,
         Sequence,
         SequenceDerivationData,
+        SequenceRangeAttestation,
+        SequenceRangeDerivationData,
+        StateCommitment,
+        Verifier,
+        VerifierDerivationData,
+        VersionRegistry,
+    },
+    api::{
+        FieldKind,
+        RENDERING_TRUNCATED,
+        RENDERING_VERSION,
     },
     types::ConsistencyLevel,
+    AnnounceGovernanceData,
+    AttestSequenceRangeData,
+    BurnFeesData,
+    ClearEmissionBreakerData,
+    CloseClaimData,
+    CloseFeeInvoiceData,
+    ClosePostedVAAData,
+    CloseSignatureSetData,
+    CommitStateData,
+    ConfirmGuardianSetDowngradeData,
+    CreateFeeInvoiceData,
+    DryRunGuardianSetUpgradeData,
+    EnableStrictNonceData,
+    EventSchemasData,
+    ExportGuardianSetData,
+    FinalizeEncodedVaaData,
+    FinalizeMessageV1Data,
+    ForgePostedVAAData,
+    ForwardToSuccessorData,
+    ForwardVAAData,
+    InitEncodedVaaData,
+    InitMessageV1Data,
     InitializeData,
+    InitiateMigrationData,
+    MigrateAccountData,
+    MigratedAccount,
     PostMessageData,
+    PostMessageDeterministicData,
     PostVAAData,
+    QueueGovernanceData,
+    RegisterEmitterData,
+    ReleaseEmissionBreakerData,
+    RenderVAAData,
+    RepairDerivationsData,
+    SetClaimConsumerData,
+    SetClaimGracePeriodData,
+    SetEmissionBreakerData,
+    SetFeeBurnData,
+    SetFeeExemptionData,
+    SetFeeOracleData,
     SetFeesData,
+    SetGuardianSetExpiryData,
+    SetGuardianSetFloorData,
+    SetGuardianWeightsData,
+    SetPausedData,
+    SetQuorumData,
+    SetSecp256r1VerificationData,
+    SetSuccessorData,
+    SetTokenFeeData,
+    SetVerifierData,
     TransferFeesData,
     UpgradeContractData,
     UpgradeGuardianSetData,
+    ValidateUpgradeBufferData,
+    VerifySignaturesBatchData,
     VerifySignaturesData,
+    VerifyVAACachedData,
+    VerifyVAAInlineData,
+    WriteEncodedVaaData,
+    WriteMessageV1Data,
     CHAIN_ID_GOVERANCE,
 };
 
@@ -170,6 +269,125 @@ pub fn post_message_unreliable(
     })
 }
 
+/// Post a message into the account derived from `emitter` and `seed`, see
+/// [`PostedMessageDerivationData`].
+pub fn post_message_deterministic(
+    program_id: Pubkey,
+    payer: Pubkey,
+    emitter: Pubkey,
+    seed: [u8; 32],
+    nonce: u32,
+    payload: Vec<u8>,
+    commitment: ConsistencyLevel,
+) -> solitaire::Result<Instruction> {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let fee_collector = FeeCollector::<'_>::key(None, &program_id);
+    let message = PostedMessage::<'_, { AccountState::Uninitialized }>::key(
+        &PostedMessageDerivationData {
+            emitter: &emitter,
+            seed: &seed,
+        },
+        &program_id,
+    );
+    let sequence = Sequence::<'_>::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter,
+        },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(bridge, false),
+            AccountMeta::new(message, false),
+            AccountMeta::new_readonly(emitter, true),
+            AccountMeta::new(sequence, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new(fee_collector, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: (
+            crate::instruction::Instruction::PostMessageDeterministic,
+            PostMessageDeterministicData {
+                seed,
+                nonce,
+                payload,
+                consistency_level: commitment,
+            },
+        )
+            .try_to_vec()?,
+    })
+}
+
+/// Sequence of the message posted by the `post_message`, `post_message_unreliable` or
+/// `post_message_deterministic` CPI that just returned, read from its return data. `None` unless the last program to return data was the
+/// bridge at `program_id`, so it must be called before invoking any other program.
+pub fn posted_sequence(program_id: &Pubkey) -> Option<u64> {
+    let (returned_by, data) = get_return_data()?;
+    if returned_by != *program_id {
+        return None;
+    }
+    Some(u64::from_le_bytes(data.as_slice().try_into().ok()?))
+}
+
+pub fn enable_strict_nonce(
+    program_id: Pubkey,
+    payer: Pubkey,
+    emitter: Pubkey,
+) -> solitaire::Result<Instruction> {
+    let sequence = Sequence::<'_>::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter,
+        },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(emitter, true),
+            AccountMeta::new(sequence, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: (
+            crate::instruction::Instruction::EnableStrictNonce,
+            EnableStrictNonceData {},
+        )
+            .try_to_vec()?,
+    })
+}
+
+pub fn register_emitter(
+    program_id: Pubkey,
+    payer: Pubkey,
+    emitter: Pubkey,
+    data: RegisterEmitterData,
+) -> solitaire::Result<Instruction> {
+    let sequence = Sequence::<'_>::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter,
+        },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(emitter, true),
+            AccountMeta::new(sequence, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: (crate::instruction::Instruction::RegisterEmitter, data).try_to_vec()?,
+    })
+}
+
 pub fn verify_signatures(
     program_id: Pubkey,
     payer: Pubkey,
@@ -200,127 +418,157 @@ pub fn verify_signatures(
     })
 }
 
-pub fn post_vaa(
+/// Verify signatures over several VAA bodies of the same guardian set at once, see
+/// `verify_signatures_batch`. Signature sets sign and are in the order of `data.signers`.
+pub fn verify_signatures_batch(
     program_id: Pubkey,
     payer: Pubkey,
-    signature_set: Pubkey,
-    vaa: PostVAAData,
-) -> Instruction {
-    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    guardian_set_index: u32,
+    signature_sets: &[Pubkey],
+    data: VerifySignaturesBatchData,
+) -> solitaire::Result<Instruction> {
     let guardian_set = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
         &GuardianSetDerivationData {
-            index: vaa.guardian_set_index,
+            index: guardian_set_index,
         },
         &program_id,
     );
 
-    let msg_derivation_data = &PostedVAADerivationData {
-        payload_hash: hash_vaa(&vaa).to_vec(),
-    };
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(guardian_set, false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+    ];
+    accounts.extend(
+        signature_sets
+            .iter()
+            .map(|signature_set| AccountMeta::new(*signature_set, true)),
+    );
 
-    let message =
-        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, &program_id);
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data: (crate::instruction::Instruction::VerifySignaturesBatch, data).try_to_vec()?,
+    })
+}
+
+/// Verify the serialized `vaa` against the preceding secp256k1 instruction, see
+/// `verify_vaa_inline`.
+pub fn verify_vaa_inline(program_id: Pubkey, guardian_set_index: u32, vaa: Vec<u8>) -> Instruction {
+    let guardian_set = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetDerivationData {
+            index: guardian_set_index,
+        },
+        &program_id,
+    );
+
+    // Only read by programs built with stake-weighted consensus.
+    let guardian_set_weights = GuardianSetWeights::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetWeightsDerivationData {
+            index: guardian_set_index,
+        },
+        &program_id,
+    );
 
     Instruction {
         program_id,
 
         accounts: vec![
             AccountMeta::new_readonly(guardian_set, false),
-            AccountMeta::new_readonly(bridge, false),
-            AccountMeta::new_readonly(signature_set, false),
-            AccountMeta::new(message, false),
-            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
             AccountMeta::new_readonly(sysvar::clock::id(), false),
-            AccountMeta::new_readonly(sysvar::rent::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(guardian_set_weights, false),
         ],
 
-        data: (crate::instruction::Instruction::PostVAA, vaa)
+        data: (
+            crate::instruction::Instruction::VerifyVAAInline,
+            VerifyVAAInlineData { vaa },
+        )
             .try_to_vec()
             .unwrap(),
     }
 }
 
-pub fn upgrade_contract(
+/// Verify the serialized `vaa` against the preceding secp256k1 instruction, or against the cached
+/// verification of its body hash, see `verify_vaa_cached`.
+pub fn verify_vaa_cached(
     program_id: Pubkey,
     payer: Pubkey,
-    payload_message: Pubkey,
-    emitter: Pubkey,
-    new_contract: Pubkey,
-    spill: Pubkey,
-    sequence: u64,
-) -> Instruction {
-    let bridge = Bridge::<'_, { AccountState::Initialized }>::key(None, &program_id);
-    let claim = Claim::<'_>::key(
-        &ClaimDerivationData {
-            emitter_address: emitter.to_bytes(),
-            emitter_chain: CHAIN_ID_GOVERANCE,
-            sequence,
+    guardian_set_index: u32,
+    vaa: Vec<u8>,
+) -> solitaire::Result<Instruction> {
+    let (_, _, body) = crate::api::verify_signature::split_signed_vaa(&vaa)?;
+    let cache = DigestCache::<'_, { AccountState::Uninitialized }>::key(
+        &DigestCacheDerivationData {
+            hash: sha3::Keccak256::digest(body).into(),
+        },
+        &program_id,
+    );
+    let guardian_set = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetDerivationData {
+            index: guardian_set_index,
         },
         &program_id,
     );
 
-    let (upgrade_authority, _) = Pubkey::find_program_address(&["upgrade".as_bytes()], &program_id);
-
-    let (program_data, _) = Pubkey::find_program_address(
-        &[program_id.as_ref()],
-        &solana_program::bpf_loader_upgradeable::id(),
+    // Only read by programs built with stake-weighted consensus.
+    let guardian_set_weights = GuardianSetWeights::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetWeightsDerivationData {
+            index: guardian_set_index,
+        },
+        &program_id,
     );
 
-    Instruction {
+    Ok(Instruction {
         program_id,
 
         accounts: vec![
             AccountMeta::new(payer, true),
-            AccountMeta::new(bridge, false),
-            AccountMeta::new_readonly(payload_message, false),
-            AccountMeta::new(claim, false),
-            AccountMeta::new_readonly(upgrade_authority, false),
-            AccountMeta::new(spill, false),
-            AccountMeta::new(new_contract, false),
-            AccountMeta::new(program_data, false),
-            AccountMeta::new(program_id, false),
-            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(guardian_set, false),
+            AccountMeta::new(cache, false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
             AccountMeta::new_readonly(sysvar::clock::id(), false),
-            AccountMeta::new_readonly(solana_program::bpf_loader_upgradeable::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(guardian_set_weights, false),
         ],
 
         data: (
-            crate::instruction::Instruction::UpgradeContract,
-            UpgradeContractData {},
+            crate::instruction::Instruction::VerifyVAACached,
+            VerifyVAACachedData { vaa },
         )
-            .try_to_vec()
-            .unwrap(),
-    }
+            .try_to_vec()?,
+    })
 }
 
-pub fn upgrade_guardian_set(
+pub fn post_vaa(
     program_id: Pubkey,
     payer: Pubkey,
-    payload_message: Pubkey,
-    emitter: Pubkey,
-    old_index: u32,
-    new_index: u32,
-    sequence: u64,
+    signature_set: Pubkey,
+    vaa: PostVAAData,
 ) -> Instruction {
     let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
-    let claim = Claim::<'_>::key(
-        &ClaimDerivationData {
-            emitter_address: emitter.to_bytes(),
-            emitter_chain: CHAIN_ID_GOVERANCE,
-            sequence,
+    let guardian_set = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetDerivationData {
+            index: vaa.guardian_set_index,
         },
         &program_id,
     );
 
-    let guardian_set_old = GuardianSet::<'_, { AccountState::Initialized }>::key(
-        &GuardianSetDerivationData { index: old_index },
-        &program_id,
-    );
+    let msg_derivation_data = &PostedVAADerivationData {
+        payload_hash: hash_vaa(&vaa).to_vec(),
+    };
 
-    let guardian_set_new = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
-        &GuardianSetDerivationData { index: new_index },
+    let message =
+        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, &program_id);
+
+    // Only read by programs built with stake-weighted consensus.
+    let guardian_set_weights = GuardianSetWeights::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetWeightsDerivationData {
+            index: vaa.guardian_set_index,
+        },
         &program_id,
     );
 
@@ -328,37 +576,44 @@ pub fn upgrade_guardian_set(
         program_id,
 
         accounts: vec![
+            AccountMeta::new_readonly(guardian_set, false),
+            AccountMeta::new_readonly(bridge, false),
+            AccountMeta::new_readonly(signature_set, false),
+            AccountMeta::new(message, false),
             AccountMeta::new(payer, true),
-            AccountMeta::new(bridge, false),
-            AccountMeta::new_readonly(payload_message, false),
-            AccountMeta::new(claim, false),
-            AccountMeta::new(guardian_set_old, false),
-            AccountMeta::new(guardian_set_new, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(guardian_set_weights, false),
         ],
 
-        data: (
-            crate::instruction::Instruction::UpgradeGuardianSet,
-            UpgradeGuardianSetData {},
-        )
+        data: (crate::instruction::Instruction::PostVAA, vaa)
             .try_to_vec()
             .unwrap(),
     }
 }
 
-pub fn set_fees(
+/// Forge a posted VAA with the body of `data`, see `api::forge_posted_vaa`. Only bridges built
+/// with the `localnet` feature accept the instruction.
+pub fn forge_posted_vaa(
     program_id: Pubkey,
     payer: Pubkey,
-    message: Pubkey,
-    emitter: Pubkey,
-    sequence: u64,
+    data: ForgePostedVAAData,
 ) -> Instruction {
-    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
-    let claim = Claim::<'_>::key(
-        &ClaimDerivationData {
-            emitter_address: emitter.to_bytes(),
-            emitter_chain: CHAIN_ID_GOVERANCE,
-            sequence,
+    let body = PostVAAData {
+        version: data.version,
+        guardian_set_index: 0,
+        timestamp: data.timestamp,
+        nonce: data.nonce,
+        emitter_chain: data.emitter_chain,
+        emitter_address: data.emitter_address,
+        sequence: data.sequence,
+        consistency_level: data.consistency_level,
+        payload: data.payload.clone(),
+    };
+    let message = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&body).to_vec(),
         },
         &program_id,
     );
@@ -368,27 +623,80 @@ pub fn set_fees(
 
         accounts: vec![
             AccountMeta::new(payer, true),
-            AccountMeta::new(bridge, false),
-            AccountMeta::new_readonly(message, false),
-            AccountMeta::new(claim, false),
+            AccountMeta::new(message, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
         ],
 
-        data: (crate::instruction::Instruction::SetFees, SetFeesData {})
+        data: (crate::instruction::Instruction::ForgePostedVAA, data)
             .try_to_vec()
             .unwrap(),
     }
 }
 
-pub fn transfer_fees(
+pub fn post_vaa_compact(
     program_id: Pubkey,
     payer: Pubkey,
-    message: Pubkey,
+    signature_set: Pubkey,
+    vaa: PostVAAData,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let guardian_set = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetDerivationData {
+            index: vaa.guardian_set_index,
+        },
+        &program_id,
+    );
+
+    let msg_derivation_data = &PostedVAACompactDerivationData {
+        payload_hash: hash_vaa(&vaa).to_vec(),
+    };
+
+    let message = PostedVAACompact::<'_, { AccountState::MaybeInitialized }>::key(
+        msg_derivation_data,
+        &program_id,
+    );
+
+    // Only read by programs built with stake-weighted consensus.
+    let guardian_set_weights = GuardianSetWeights::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetWeightsDerivationData {
+            index: vaa.guardian_set_index,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new_readonly(guardian_set, false),
+            AccountMeta::new_readonly(bridge, false),
+            AccountMeta::new_readonly(signature_set, false),
+            AccountMeta::new(message, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(guardian_set_weights, false),
+        ],
+
+        data: (crate::instruction::Instruction::PostVAACompact, vaa)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn upgrade_contract(
+    program_id: Pubkey,
+    payer: Pubkey,
+    payload_message: Pubkey,
     emitter: Pubkey,
+    new_contract: Pubkey,
+    spill: Pubkey,
     sequence: u64,
-    recipient: Pubkey,
 ) -> Instruction {
-    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let bridge = Bridge::<'_, { AccountState::Initialized }>::key(None, &program_id);
     let claim = Claim::<'_>::key(
         &ClaimDerivationData {
             emitter_address: emitter.to_bytes(),
@@ -398,6 +706,14 @@ pub fn transfer_fees(
         &program_id,
     );
 
+    let (upgrade_authority, _) =
+        Pubkey::find_program_address(&[seeds::UPGRADE.as_bytes()], &program_id);
+
+    let (program_data, _) = Pubkey::find_program_address(
+        &[program_id.as_ref()],
+        &solana_program::bpf_loader_upgradeable::id(),
+    );
+
     let fee_collector = FeeCollector::key(None, &program_id);
 
     Instruction {
@@ -406,32 +722,2130 @@ pub fn transfer_fees(
         accounts: vec![
             AccountMeta::new(payer, true),
             AccountMeta::new(bridge, false),
-            AccountMeta::new_readonly(message, false),
+            AccountMeta::new_readonly(payload_message, false),
             AccountMeta::new(claim, false),
-            AccountMeta::new(fee_collector, false),
-            AccountMeta::new(recipient, false),
+            AccountMeta::new_readonly(upgrade_authority, false),
+            AccountMeta::new(spill, false),
+            AccountMeta::new(new_contract, false),
+            AccountMeta::new(program_data, false),
+            AccountMeta::new(program_id, false),
             AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(solana_program::bpf_loader_upgradeable::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new(fee_collector, false),
         ],
 
         data: (
-            crate::instruction::Instruction::TransferFees,
-            TransferFeesData {},
+            crate::instruction::Instruction::UpgradeContract,
+            UpgradeContractData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Check that `buffer` can be deployed by `upgrade_contract`, see `validate_upgrade_buffer`.
+pub fn validate_upgrade_buffer(program_id: Pubkey, buffer: Pubkey) -> Instruction {
+    let (program_data, _) = Pubkey::find_program_address(
+        &[program_id.as_ref()],
+        &solana_program::bpf_loader_upgradeable::id(),
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new_readonly(buffer, false),
+            AccountMeta::new_readonly(program_data, false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::ValidateUpgradeBuffer,
+            ValidateUpgradeBufferData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn upgrade_guardian_set(
+    program_id: Pubkey,
+    payer: Pubkey,
+    payload_message: Pubkey,
+    emitter: Pubkey,
+    old_index: u32,
+    new_index: u32,
+    sequence: u64,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+
+    let guardian_set_old = GuardianSet::<'_, { AccountState::Initialized }>::key(
+        &GuardianSetDerivationData { index: old_index },
+        &program_id,
+    );
+
+    let guardian_set_new = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetDerivationData { index: new_index },
+        &program_id,
+    );
+
+    // Only read with the `guardian-set-floor` feature, and ignored otherwise.
+    let floor = GuardianSetFloor::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(bridge, false),
+            AccountMeta::new_readonly(payload_message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(guardian_set_old, false),
+            AccountMeta::new(guardian_set_new, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(floor, false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::UpgradeGuardianSet,
+            UpgradeGuardianSetData {},
         )
             .try_to_vec()
             .unwrap(),
     }
 }
 
+/// Replay a historical guardian set change signed by the active guardian set, whose signatures
+/// were verified into `signature_set`. Changes are replayed one per instruction, in order.
+pub fn replay_governance(
+    program_id: Pubkey,
+    payer: Pubkey,
+    signature_set: Pubkey,
+    vaa: PostVAAData,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let genesis_guardian_set = GuardianSet::<'_, { AccountState::Initialized }>::key(
+        &GuardianSetDerivationData { index: 0 },
+        &program_id,
+    );
+    let guardian_set_old = GuardianSet::<'_, { AccountState::Initialized }>::key(
+        &GuardianSetDerivationData {
+            index: vaa.guardian_set_index,
+        },
+        &program_id,
+    );
+    let guardian_set_new = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetDerivationData {
+            index: vaa.guardian_set_index + 1,
+        },
+        &program_id,
+    );
+
+    // Only read by programs built with stake-weighted consensus.
+    let guardian_set_weights = GuardianSetWeights::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetWeightsDerivationData {
+            index: vaa.guardian_set_index,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(bridge, false),
+            AccountMeta::new_readonly(genesis_guardian_set, false),
+            AccountMeta::new_readonly(signature_set, false),
+            AccountMeta::new(guardian_set_old, false),
+            AccountMeta::new(guardian_set_new, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(guardian_set_weights, false),
+        ],
+
+        data: (crate::instruction::Instruction::ReplayGovernance, vaa)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn set_fees(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(bridge, false),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (crate::instruction::Instruction::SetFees, SetFeesData {})
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn set_guardian_weights(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+    guardian_set_index: u32,
+) -> Instruction {
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+    let guardian_set = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetDerivationData {
+            index: guardian_set_index,
+        },
+        &program_id,
+    );
+    let guardian_set_weights = GuardianSetWeights::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetWeightsDerivationData {
+            index: guardian_set_index,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new_readonly(guardian_set, false),
+            AccountMeta::new(guardian_set_weights, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetGuardianWeights,
+            SetGuardianWeightsData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn transfer_fees(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+    recipient: Pubkey,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+
+    let fee_collector = FeeCollector::key(None, &program_id);
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(bridge, false),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(fee_collector, false),
+            AccountMeta::new(recipient, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::TransferFees,
+            TransferFeesData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn initiate_migration(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+) -> Instruction {
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+    let migration = Migration::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(migration, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::InitiateMigration,
+            InitiateMigrationData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn migrate_account(
+    program_id: Pubkey,
+    successor: Pubkey,
+    account: MigratedAccount,
+) -> Instruction {
+    let migration = Migration::<'_, { AccountState::Initialized }>::key(None, &program_id);
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new_readonly(migration, false),
+            AccountMeta::new(account.key(&program_id), false),
+            AccountMeta::new_readonly(successor, false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::MigrateAccount,
+            MigrateAccountData { account },
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn announce_governance(program_id: Pubkey, payer: Pubkey, digest: [u8; 32]) -> Instruction {
+    let announcement = GovernanceAnnouncement::<'_, { AccountState::Uninitialized }>::key(
+        &GovernanceAnnouncementDerivationData { digest },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(announcement, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::AnnounceGovernance,
+            AnnounceGovernanceData { digest },
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+// Queue the governance VAA posted with `digest`, its `hash_vaa`, for execution after the timelock,
+// see `execute_governance`.
+pub fn queue_governance(program_id: Pubkey, payer: Pubkey, digest: [u8; 32]) -> Instruction {
+    let vaa = PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: digest.to_vec(),
+        },
+        &program_id,
+    );
+    let pending = PendingGovernance::<'_, { AccountState::Uninitialized }>::key(
+        &PendingGovernanceDerivationData { digest },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(vaa, false),
+            AccountMeta::new(pending, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::QueueGovernance,
+            QueueGovernanceData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn set_guardian_set_floor(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+) -> Instruction {
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+    let floor = GuardianSetFloor::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(floor, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetGuardianSetFloor,
+            SetGuardianSetFloorData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn confirm_guardian_set_downgrade(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+) -> Instruction {
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+    let floor = GuardianSetFloor::<'_, { AccountState::Initialized }>::key(None, &program_id);
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(floor, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::ConfirmGuardianSetDowngrade,
+            ConfirmGuardianSetDowngradeData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn set_fee_burn(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+) -> Instruction {
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+    let fee_burn = FeeBurn::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(fee_burn, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetFeeBurn,
+            SetFeeBurnData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn burn_fees(program_id: Pubkey) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Initialized }>::key(None, &program_id);
+    let fee_burn = FeeBurn::<'_, { AccountState::Initialized }>::key(None, &program_id);
+    let fee_collector = FeeCollector::key(None, &program_id);
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(bridge, false),
+            AccountMeta::new(fee_burn, false),
+            AccountMeta::new(fee_collector, false),
+            AccountMeta::new(incinerator::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (crate::instruction::Instruction::BurnFees, BurnFeesData {})
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn forward_vaa(
+    program_id: Pubkey,
+    payer: Pubkey,
+    posted_vaa: Pubkey,
+    message: Pubkey,
+    nonce: u32,
+    target_chain: u16,
+    consistency_level: ConsistencyLevel,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Initialized }>::key(None, &program_id);
+    let fee_collector = FeeCollector::<'_>::key(None, &program_id);
+    let (forwarder, _) = Pubkey::find_program_address(&[seeds::FORWARDER.as_bytes()], &program_id);
+    let sequence = Sequence::<'_>::key(
+        &SequenceDerivationData {
+            emitter_key: &forwarder,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(bridge, false),
+            AccountMeta::new_readonly(posted_vaa, false),
+            AccountMeta::new(message, true),
+            AccountMeta::new_readonly(forwarder, false),
+            AccountMeta::new(sequence, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new(fee_collector, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::ForwardVAA,
+            ForwardVAAData {
+                nonce,
+                target_chain,
+                consistency_level,
+            },
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn set_secp256r1_verification(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+) -> Instruction {
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+    let secp256r1_verification =
+        Secp256r1Verification::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(secp256r1_verification, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetSecp256r1Verification,
+            SetSecp256r1VerificationData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn set_fee_oracle(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+) -> Instruction {
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+    let fee_oracle = FeeOracle::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(fee_oracle, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetFeeOracle,
+            SetFeeOracleData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn set_guardian_set_expiry(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+) -> Instruction {
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+    let guardian_set_expiry =
+        GuardianSetExpiry::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(guardian_set_expiry, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetGuardianSetExpiry,
+            SetGuardianSetExpiryData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn set_quorum(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+    guardian_set_index: u32,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+    let guardian_set = GuardianSet::<'_, { AccountState::Initialized }>::key(
+        &GuardianSetDerivationData {
+            index: guardian_set_index,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(bridge, false),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(guardian_set, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (crate::instruction::Instruction::SetQuorum, SetQuorumData {})
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn set_paused(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(bridge, false),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (crate::instruction::Instruction::SetPaused, SetPausedData {})
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn set_emission_breaker(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(bridge, false),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetEmissionBreaker,
+            SetEmissionBreakerData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Clear the emission breaker of `breaker_emitter` with the governance VAA `message`.
+pub fn release_emission_breaker(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+    breaker_emitter: Pubkey,
+) -> Instruction {
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+    let emitter_stats = EmitterStats::<'_, { AccountState::Initialized }>::key(
+        &EmitterStatsDerivationData {
+            emitter: &breaker_emitter,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(emitter_stats, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::ReleaseEmissionBreaker,
+            ReleaseEmissionBreakerData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Clear the emission breaker of `emitter`, which signs.
+pub fn clear_emission_breaker(program_id: Pubkey, emitter: Pubkey) -> Instruction {
+    let emitter_stats = EmitterStats::<'_, { AccountState::Initialized }>::key(
+        &EmitterStatsDerivationData { emitter: &emitter },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new_readonly(emitter, true),
+            AccountMeta::new(emitter_stats, false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::ClearEmissionBreaker,
+            ClearEmissionBreakerData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Set the claim grace period and the recipient of closed claims with the governance VAA `message`.
+pub fn set_claim_grace_period(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(bridge, false),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetClaimGracePeriod,
+            SetClaimGracePeriodData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn set_token_fee(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+    fee_token_account: Pubkey,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(bridge, false),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new_readonly(fee_token_account, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetTokenFee,
+            SetTokenFeeData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn set_fee_exemption(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+    exempted_emitter: Pubkey,
+) -> Instruction {
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+    let fee_exemption = FeeExemption::<'_, { AccountState::MaybeInitialized }>::key(
+        &FeeExemptionDerivationData {
+            emitter: &exempted_emitter,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(fee_exemption, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetFeeExemption,
+            SetFeeExemptionData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn set_claim_consumer(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+    consumer: Pubkey,
+) -> Instruction {
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+    let claim_consumer = ClaimConsumer::<'_, { AccountState::MaybeInitialized }>::key(
+        &ClaimConsumerDerivationData { program: &consumer },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(claim_consumer, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetClaimConsumer,
+            SetClaimConsumerData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn set_verifier(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+    verifier: Pubkey,
+) -> Instruction {
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+    let verifier = Verifier::<'_, { AccountState::MaybeInitialized }>::key(
+        &VerifierDerivationData { program: &verifier },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(verifier, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetVerifier,
+            SetVerifierData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn set_successor(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(bridge, false),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetSuccessor,
+            SetSuccessorData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Wrap `instruction`, built for this program, to be forwarded to the successor governance wired,
+/// see `forward_to_successor`. Instructions that aren't wrapped are never forwarded.
+pub fn forward_to_successor(
+    program_id: Pubkey,
+    successor: Pubkey,
+    instruction: Instruction,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(bridge, false),
+        AccountMeta::new_readonly(successor, false),
+    ];
+    accounts.extend(instruction.accounts);
+
+    Instruction {
+        program_id,
+
+        accounts,
+
+        data: (
+            crate::instruction::Instruction::ForwardToSuccessor,
+            ForwardToSuccessorData {
+                data: instruction.data,
+            },
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Write the current guardian set into `export`, an account owned by the bridge and
+/// `GuardianSetExport::LEN` bytes long. `export` must sign the first export into the account.
+pub fn export_guardian_set(
+    program_id: Pubkey,
+    export: Pubkey,
+    guardian_set_index: u32,
+    signer: bool,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Initialized }>::key(None, &program_id);
+    let guardian_set = GuardianSet::<'_, { AccountState::Initialized }>::key(
+        &GuardianSetDerivationData {
+            index: guardian_set_index,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new_readonly(bridge, false),
+            AccountMeta::new_readonly(guardian_set, false),
+            AccountMeta::new(export, signer),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::ExportGuardianSet,
+            ExportGuardianSetData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn render_vaa(program_id: Pubkey, posted_vaa: Pubkey) -> Instruction {
+    Instruction {
+        program_id,
+
+        accounts: vec![AccountMeta::new_readonly(posted_vaa, false)],
+
+        data: (crate::instruction::Instruction::RenderVAA, RenderVAAData {})
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Field of a VAA rendered by `render_vaa`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RenderedField {
+    pub kind: FieldKind,
+    pub name: String,
+    pub value: Vec<u8>,
+}
+
+/// Fields of a rendering returned by `render_vaa`, see [`render`](crate::api::render), and
+/// whether it was truncated. `None` if the rendering is malformed or of another version.
+pub fn parse_rendering(data: &[u8]) -> Option<(Vec<RenderedField>, bool)> {
+    let (header, mut data) = (data.get(..2)?, data.get(2..)?);
+    if header[0] != RENDERING_VERSION {
+        return None;
+    }
+
+    let mut fields = Vec::new();
+    while !data.is_empty() {
+        let kind = FieldKind::from_u8(data[0])?;
+        let name_len = *data.get(1)? as usize;
+        let name = String::from_utf8(data.get(2..2 + name_len)?.to_vec()).ok()?;
+        data = &data[2 + name_len..];
+        let value_len = u16::from_be_bytes(data.get(..2)?.try_into().ok()?) as usize;
+        let value = data.get(2..2 + value_len)?.to_vec();
+        data = &data[2 + value_len..];
+        fields.push(RenderedField { kind, name, value });
+    }
+    Some((fields, header[1] & RENDERING_TRUNCATED != 0))
+}
+
+/// Report which of the pending VAAs with `digests` become unverifiable if the guardian set upgrade
+/// `decree` executes, see [`GuardianSetUpgradeDryRun`](crate::api::GuardianSetUpgradeDryRun).
+pub fn dry_run_guardian_set_upgrade(
+    program_id: Pubkey,
+    decree: Vec<u8>,
+    digests: Vec<[u8; 32]>,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Initialized }>::key(None, &program_id);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(bridge, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+    accounts.extend(digests.iter().map(|digest| {
+        let posted_vaa = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+            &PostedVAADerivationData {
+                payload_hash: digest.to_vec(),
+            },
+            &program_id,
+        );
+        AccountMeta::new_readonly(posted_vaa, false)
+    }));
+
+    Instruction {
+        program_id,
+
+        accounts,
+
+        data: (
+            crate::instruction::Instruction::DryRunGuardianSetUpgrade,
+            DryRunGuardianSetUpgradeData { decree, digests },
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Report the schemas of the events the bridge emits, see
+/// [`event_registry`](crate::api::event_registry). The return data deserializes as a
+/// `Vec<EventSchema>`.
+pub fn event_schemas(program_id: Pubkey) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Initialized }>::key(None, &program_id);
+
+    Instruction {
+        program_id,
+
+        accounts: vec![AccountMeta::new_readonly(bridge, false)],
+
+        data: (
+            crate::instruction::Instruction::EventSchemas,
+            EventSchemasData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Start uploading a VAA into `encoded_vaa`, a zeroed account owned by the bridge and
+/// `EncodedVaa::HEADER_LEN` bytes longer than the VAA. Both keys sign.
+pub fn init_encoded_vaa(
+    program_id: Pubkey,
+    write_authority: Pubkey,
+    encoded_vaa: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new_readonly(write_authority, true),
+            AccountMeta::new(encoded_vaa, true),
+        ],
+
+        data: (
+            crate::instruction::Instruction::InitEncodedVaa,
+            InitEncodedVaaData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Write `data` at `index` of the VAA uploaded into `encoded_vaa`.
+pub fn write_encoded_vaa(
+    program_id: Pubkey,
+    write_authority: Pubkey,
+    encoded_vaa: Pubkey,
+    index: u32,
+    data: Vec<u8>,
+) -> Instruction {
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new_readonly(write_authority, true),
+            AccountMeta::new(encoded_vaa, false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::WriteEncodedVaa,
+            WriteEncodedVaaData { index, data },
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Verify the VAA uploaded into `encoded_vaa` with the signatures over its body in
+/// `signature_set`.
+pub fn finalize_encoded_vaa(
+    program_id: Pubkey,
+    write_authority: Pubkey,
+    encoded_vaa: Pubkey,
+    signature_set: Pubkey,
+    guardian_set_index: u32,
+) -> Instruction {
+    let guardian_set = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetDerivationData {
+            index: guardian_set_index,
+        },
+        &program_id,
+    );
+
+    // Only read by programs built with stake-weighted consensus.
+    let guardian_set_weights = GuardianSetWeights::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetWeightsDerivationData {
+            index: guardian_set_index,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new_readonly(write_authority, true),
+            AccountMeta::new(encoded_vaa, false),
+            AccountMeta::new_readonly(guardian_set, false),
+            AccountMeta::new_readonly(signature_set, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(guardian_set_weights, false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::FinalizeEncodedVaa,
+            FinalizeEncodedVaaData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Start drafting a message from `emitter` in `draft_message`, a zeroed account owned by the bridge
+/// and `DraftMessage::HEADER_LEN` bytes longer than the payload. Both keys sign.
+pub fn init_message_v1(program_id: Pubkey, emitter: Pubkey, draft_message: Pubkey) -> Instruction {
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new_readonly(emitter, true),
+            AccountMeta::new(draft_message, true),
+        ],
+
+        data: (
+            crate::instruction::Instruction::InitMessageV1,
+            InitMessageV1Data {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Write `data` at `index` of the payload drafted in `draft_message`.
+pub fn write_message_v1(
+    program_id: Pubkey,
+    emitter: Pubkey,
+    draft_message: Pubkey,
+    index: u32,
+    data: Vec<u8>,
+) -> Instruction {
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new_readonly(emitter, true),
+            AccountMeta::new(draft_message, false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::WriteMessageV1,
+            WriteMessageV1Data { index, data },
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Post the payload drafted in `draft_message` into `message`, refunding the draft to `payer`.
+pub fn finalize_message_v1(
+    program_id: Pubkey,
+    payer: Pubkey,
+    emitter: Pubkey,
+    message: Pubkey,
+    draft_message: Pubkey,
+    nonce: u32,
+    commitment: ConsistencyLevel,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let fee_collector = FeeCollector::<'_>::key(None, &program_id);
+    let sequence = Sequence::<'_>::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(bridge, false),
+            AccountMeta::new(message, true),
+            AccountMeta::new_readonly(emitter, true),
+            AccountMeta::new(sequence, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new(fee_collector, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new(draft_message, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::FinalizeMessageV1,
+            FinalizeMessageV1Data {
+                nonce,
+                consistency_level: commitment,
+            },
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Attest that `emitter` posted every sequence from `first` to `last`, see
+/// `attest_sequence_range`.
+pub fn attest_sequence_range(
+    program_id: Pubkey,
+    payer: Pubkey,
+    emitter: Pubkey,
+    first: u64,
+    last: u64,
+) -> Instruction {
+    let sequence = Sequence::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter,
+        },
+        &program_id,
+    );
+    let attestation = SequenceRangeAttestation::<'_, { AccountState::Uninitialized }>::key(
+        &SequenceRangeDerivationData {
+            emitter_key: &emitter,
+            first,
+            last,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(emitter, false),
+            AccountMeta::new_readonly(sequence, false),
+            AccountMeta::new(attestation, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::AttestSequenceRange,
+            AttestSequenceRangeData { first, last },
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Close the VAA posted with `signature_set` over `hash` into `recipient`, once claimed by
+/// `consumer`, the program owning `claim`.
+pub fn close_posted_vaa(
+    program_id: Pubkey,
+    refund_recipient: Pubkey,
+    recipient: Pubkey,
+    signature_set: Pubkey,
+    hash: [u8; 32],
+    claim: Pubkey,
+    consumer: Pubkey,
+) -> Instruction {
+    let posted_vaa = PostedVAA::<'_, { AccountState::Initialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash.to_vec(),
+        },
+        &program_id,
+    );
+    let claim_consumer = ClaimConsumer::<'_, { AccountState::MaybeInitialized }>::key(
+        &ClaimConsumerDerivationData { program: &consumer },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new_readonly(refund_recipient, true),
+            AccountMeta::new(recipient, false),
+            AccountMeta::new(posted_vaa, false),
+            AccountMeta::new_readonly(signature_set, false),
+            AccountMeta::new_readonly(claim, false),
+            AccountMeta::new_readonly(claim_consumer, false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::ClosePostedVAA,
+            ClosePostedVAAData { consumer },
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Close `signature_set`, over `hash`, into `recipient`.
+pub fn close_signature_set(
+    program_id: Pubkey,
+    refund_recipient: Pubkey,
+    recipient: Pubkey,
+    signature_set: Pubkey,
+    hash: [u8; 32],
+) -> Instruction {
+    let posted_vaa = PostedVAA::<'_, { AccountState::Initialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash.to_vec(),
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new_readonly(refund_recipient, true),
+            AccountMeta::new(recipient, false),
+            AccountMeta::new(signature_set, false),
+            AccountMeta::new_readonly(posted_vaa, false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::CloseSignatureSet,
+            CloseSignatureSetData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Close the `claim` of the VAA over `hash`, posted with `signature_set` of the guardian set with
+/// `guardian_set_index`, into the claim `recipient` governance designated.
+pub fn close_claim(
+    program_id: Pubkey,
+    recipient: Pubkey,
+    claim: ClaimDerivationData,
+    signature_set: Pubkey,
+    hash: [u8; 32],
+    guardian_set_index: u32,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Initialized }>::key(None, &program_id);
+    let claim = Claim::<'_>::key(&claim, &program_id);
+    let posted_vaa = PostedVAA::<'_, { AccountState::Initialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash.to_vec(),
+        },
+        &program_id,
+    );
+    let guardian_set = GuardianSet::<'_, { AccountState::Initialized }>::key(
+        &GuardianSetDerivationData {
+            index: guardian_set_index,
+        },
+        &program_id,
+    );
+    let guardian_set_expiry =
+        GuardianSetExpiry::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new_readonly(bridge, false),
+            AccountMeta::new(recipient, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(posted_vaa, false),
+            AccountMeta::new_readonly(signature_set, false),
+            AccountMeta::new_readonly(guardian_set, false),
+            AccountMeta::new_readonly(guardian_set_expiry, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::CloseClaim,
+            CloseClaimData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+// Append the announcement of a governance VAA to the instruction executing it, required when the
+// bridge is built with the `governance-announcement` feature. `digest` is the `hash_vaa` of the
+// governance VAA.
+pub fn with_governance_announcement(mut instruction: Instruction, digest: [u8; 32]) -> Instruction {
+    let announcement = GovernanceAnnouncement::<'_, { AccountState::Uninitialized }>::key(
+        &GovernanceAnnouncementDerivationData { digest },
+        &instruction.program_id,
+    );
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(announcement, false));
+    instruction
+}
+
+// Execute a governance VAA queued by `queue_governance` through the instruction of its decree,
+// appending the pending account, required when the bridge is built with the `governance-timelock`
+// feature. `digest` is the `hash_vaa` of the governance VAA.
+pub fn execute_governance(mut instruction: Instruction, digest: [u8; 32]) -> Instruction {
+    let pending = PendingGovernance::<'_, { AccountState::Uninitialized }>::key(
+        &PendingGovernanceDerivationData { digest },
+        &instruction.program_id,
+    );
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(pending, false));
+    instruction
+}
+
+// Append the token program owning the `recipient` of a `transfer_fees` instruction when it is a
+// wrapped SOL token account, whose token balance is then synced with the fees received.
+pub fn with_token_program(mut instruction: Instruction, token_program: Pubkey) -> Instruction {
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(token_program, false));
+    instruction
+}
+
+// Append the secp256r1 verification flag to a `verify_signatures` instruction following a
+// secp256r1 precompile instruction, required when the bridge is built with the `secp256r1`
+// feature.
+pub fn with_secp256r1_verification(mut instruction: Instruction) -> Instruction {
+    let secp256r1_verification = Secp256r1Verification::<'_, { AccountState::Initialized }>::key(
+        None,
+        &instruction.program_id,
+    );
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(secp256r1_verification, false));
+    instruction
+}
+
+// Append the guardian set expiry mode to a `post_vaa`, `post_vaa_compact`, `verify_vaa_inline` or
+// `finalize_encoded_vaa` instruction, so that VAAs of a recently expired guardian set are accepted
+// while governance selected soft expiry.
+pub fn with_guardian_set_expiry(mut instruction: Instruction) -> Instruction {
+    let guardian_set_expiry =
+        GuardianSetExpiry::<'_, { AccountState::Initialized }>::key(None, &instruction.program_id);
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(guardian_set_expiry, false));
+    instruction
+}
+
+// Append the guardian set with `guardian_set_index` and its weights to a `verify_signatures` or
+// `post_vaa` instruction, for clients that can't tell whether a guardian set rotation took effect.
+// The bridge then verifies against whichever of the passed sets the VAA names.
+pub fn with_guardian_set(mut instruction: Instruction, guardian_set_index: u32) -> Instruction {
+    let guardian_set = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetDerivationData {
+            index: guardian_set_index,
+        },
+        &instruction.program_id,
+    );
+    let guardian_set_weights = GuardianSetWeights::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetWeightsDerivationData {
+            index: guardian_set_index,
+        },
+        &instruction.program_id,
+    );
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(guardian_set, false));
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(guardian_set_weights, false));
+    instruction
+}
+
+pub fn create_fee_invoice(
+    program_id: Pubkey,
+    creator: Pubkey,
+    message: Pubkey,
+    payload_len: u32,
+    consistency_level: ConsistencyLevel,
+    expiry_slot: u64,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Initialized }>::key(None, &program_id);
+    let invoice = FeeInvoice::<'_, { AccountState::Uninitialized }>::key(
+        &FeeInvoiceDerivationData { message: &message },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(creator, true),
+            AccountMeta::new_readonly(bridge, false),
+            AccountMeta::new_readonly(message, true),
+            AccountMeta::new(invoice, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::CreateFeeInvoice,
+            CreateFeeInvoiceData {
+                payload_len,
+                consistency_level,
+                expiry_slot,
+            },
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn close_fee_invoice(program_id: Pubkey, creator: Pubkey, message: Pubkey) -> Instruction {
+    let invoice = FeeInvoice::<'_, { AccountState::Initialized }>::key(
+        &FeeInvoiceDerivationData { message: &message },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(creator, true),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(invoice, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::CloseFeeInvoice,
+            CloseFeeInvoiceData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn commit_state(
+    program_id: Pubkey,
+    payer: Pubkey,
+    guardian_set_index: u32,
+    messages: &[Pubkey],
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Initialized }>::key(None, &program_id);
+    let guardian_set = GuardianSet::<'_, { AccountState::Initialized }>::key(
+        &GuardianSetDerivationData {
+            index: guardian_set_index,
+        },
+        &program_id,
+    );
+    let commitment =
+        StateCommitment::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
+
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(bridge, false),
+        AccountMeta::new_readonly(guardian_set, false),
+        AccountMeta::new(commitment, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+    ];
+    accounts.extend(
+        messages
+            .iter()
+            .map(|message| AccountMeta::new_readonly(*message, false)),
+    );
+
+    Instruction {
+        program_id,
+        accounts,
+        data: (
+            crate::instruction::Instruction::CommitState,
+            CommitStateData {
+                messages: messages.len() as u8,
+            },
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn repair_derivations(
+    program_id: Pubkey,
+    deploy_authority: Pubkey,
+    source_program: Pubkey,
+    guardian_set_index: u32,
+) -> Instruction {
+    let (program_data, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    let source_bridge = Bridge::<'_, { AccountState::Initialized }>::key(None, &source_program);
+    let guardian_set_derivation = GuardianSetDerivationData {
+        index: guardian_set_index,
+    };
+    let source_guardian_set = GuardianSet::<'_, { AccountState::Initialized }>::key(
+        &guardian_set_derivation,
+        &source_program,
+    );
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let guardian_set = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &guardian_set_derivation,
+        &program_id,
+    );
+    let fee_collector = FeeCollector::<'_>::key(None, &program_id);
+    let (upgrade_authority, _) =
+        Pubkey::find_program_address(&[seeds::UPGRADE.as_bytes()], &program_id);
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(deploy_authority, true),
+            AccountMeta::new(program_data, false),
+            AccountMeta::new_readonly(source_bridge, false),
+            AccountMeta::new_readonly(source_guardian_set, false),
+            AccountMeta::new(bridge, false),
+            AccountMeta::new(guardian_set, false),
+            AccountMeta::new(fee_collector, false),
+            AccountMeta::new_readonly(upgrade_authority, false),
+            AccountMeta::new_readonly(bpf_loader_upgradeable::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::RepairDerivations,
+            RepairDerivationsData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+// Append the fee oracle config and the oracle account it references to an instruction posting a
+// message, so that the bridge charges the oracle's fee when built with the `fee-oracle` feature.
+pub fn with_fee_oracle(mut instruction: Instruction, oracle: Pubkey) -> Instruction {
+    let fee_oracle =
+        FeeOracle::<'_, { AccountState::Initialized }>::key(None, &instruction.program_id);
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(fee_oracle, false));
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(oracle, false));
+    instruction
+}
+
+// Append the event authority and the program account to an instruction posting a message, so that
+// the bridge also emits the message as a `MessagePosted` event through a self-invocation.
+pub fn with_event_cpi(mut instruction: Instruction) -> Instruction {
+    let (event_authority, _) = event::event_authority(&instruction.program_id);
+    let program_id = instruction.program_id;
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(event_authority, false));
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(program_id, false));
+    instruction
+}
+
+// Append the fee invoice of `message` to an instruction posting it, so that the bridge charges the
+// quoted fee rather than the current one.
+pub fn with_fee_invoice(mut instruction: Instruction, message: Pubkey) -> Instruction {
+    let invoice = FeeInvoice::<'_, { AccountState::Initialized }>::key(
+        &FeeInvoiceDerivationData { message: &message },
+        &instruction.program_id,
+    );
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(invoice, false));
+    instruction
+}
+
+// Append the fee token account to an instruction posting a message, so that the bridge charges the
+// fee in tokens of the fee mint, transferred to `fee_token_account` beforehand, instead of lamports.
+pub fn with_token_fee(mut instruction: Instruction, fee_token_account: Pubkey) -> Instruction {
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(fee_token_account, false));
+    instruction
+}
+
+// Append the fee token account, `source` and its token program to an instruction posting a
+// message, so that the bridge pulls the token fee from `source`, a token account of the fee mint
+// that approved the fee collector as its delegate. The owner of `source` must sign the post.
+pub fn with_token_fee_delegate(
+    mut instruction: Instruction,
+    fee_token_account: Pubkey,
+    source: Pubkey,
+    token_program: Pubkey,
+) -> Instruction {
+    instruction
+        .accounts
+        .push(AccountMeta::new(fee_token_account, false));
+    instruction.accounts.push(AccountMeta::new(source, false));
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(token_program, false));
+    instruction
+}
+
+// Append the fee exemption of `emitter` to an instruction posting its message, so that the bridge
+// charges no fee while governance exempts the emitter.
+pub fn with_fee_exemption(mut instruction: Instruction, emitter: Pubkey) -> Instruction {
+    let fee_exemption = FeeExemption::<'_, { AccountState::Initialized }>::key(
+        &FeeExemptionDerivationData { emitter: &emitter },
+        &instruction.program_id,
+    );
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(fee_exemption, false));
+    instruction
+}
+
+// Append the approval of `verifier` by the bridge at `bridge_id` to an instruction redeeming a VAA
+// that `verifier` verified in place of the bridge, see `set_verifier`.
+pub fn with_verifier(
+    mut instruction: Instruction,
+    bridge_id: Pubkey,
+    verifier: Pubkey,
+) -> Instruction {
+    let verifier = Verifier::<'_, { AccountState::Initialized }>::key(
+        &VerifierDerivationData { program: &verifier },
+        &bridge_id,
+    );
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(verifier, false));
+    instruction
+}
+
+// Append the stats of `emitter` to an instruction posting its message, required for emitters that
+// registered their metadata while governance enables the emission breaker.
+pub fn with_emitter_stats(mut instruction: Instruction, emitter: Pubkey) -> Instruction {
+    let emitter_stats = EmitterStats::<'_, { AccountState::MaybeInitialized }>::key(
+        &EmitterStatsDerivationData { emitter: &emitter },
+        &instruction.program_id,
+    );
+    instruction
+        .accounts
+        .push(AccountMeta::new(emitter_stats, false));
+    instruction
+}
+
+// Append the version registry to an upgrade, required for decrees naming a version. The upgrade
+// then hashes the new program data, which needs the larger compute unit limit recommended for it.
+pub fn with_version_registry(mut instruction: Instruction) -> Instruction {
+    let version_registry = VersionRegistry::<'_, { AccountState::MaybeInitialized }>::key(
+        None,
+        &instruction.program_id,
+    );
+    instruction
+        .accounts
+        .push(AccountMeta::new(version_registry, false));
+    instruction
+}
+
+// Compute units an instruction built by this module consumes at most, to request as the compute
+// unit limit of its transaction rather than paying priority fees on the runtime default. Limits
+// leave headroom for the largest guardian sets and payloads, `None` for data this program doesn't
+// dispatch.
+pub fn compute_unit_limit(instruction: &Instruction) -> Option<u32> {
+    use crate::instruction::Instruction::*;
+
+    let limit = match crate::instruction::Instruction::deserialize(&mut &instruction.data[..]) {
+        Ok(Initialize) => 50_000,
+        Ok(PostMessage) | Ok(PostMessageUnreliable) | Ok(PostMessageDeterministic) => 60_000,
+        Ok(PostVAA) | Ok(PostVAACompact) => 100_000,
+        Ok(SetFees) | Ok(TransferFees) => 50_000,
+        // Keccak costs a compute unit per two bytes hashed, enough for two megabytes of program
+        // data.
+        Ok(UpgradeContract) if recording_version(instruction) => 1_200_000,
+        Ok(UpgradeContract) => 200_000,
+        Ok(UpgradeGuardianSet) => 120_000,
+        Ok(VerifySignatures) => 100_000,
+        Ok(SetGuardianWeights) => 100_000,
+        Ok(EnableStrictNonce) | Ok(AnnounceGovernance) | Ok(RegisterEmitter) => 30_000,
+        Ok(QueueGovernance) => 30_000,
+        Ok(InitiateMigration) | Ok(MigrateAccount) | Ok(AcceptMigration) => 60_000,
+        Ok(SetGuardianSetFloor) | Ok(ConfirmGuardianSetDowngrade) => 50_000,
+        Ok(SetFeeBurn) | Ok(BurnFees) => 50_000,
+        Ok(ForwardVAA) => 100_000,
+        Ok(SetSecp256r1Verification) | Ok(SetFeeOracle) => 50_000,
+        Ok(ReplayGovernance) => 150_000,
+        Ok(ExportGuardianSet) => 30_000,
+        Ok(VerifySignaturesBatch) => 200_000,
+        Ok(ValidateUpgradeBuffer) => 30_000,
+        Ok(VerifyVAAInline) => 100_000,
+        Ok(InitEncodedVaa) | Ok(WriteEncodedVaa) => 50_000,
+        Ok(FinalizeEncodedVaa) => 150_000,
+        Ok(AttestSequenceRange) => 30_000,
+        Ok(ClosePostedVAA) | Ok(CloseSignatureSet) => 30_000,
+        Ok(SetGuardianSetExpiry) => 50_000,
+        Ok(InitMessageV1) | Ok(WriteMessageV1) => 50_000,
+        Ok(FinalizeMessageV1) => 80_000,
+        Ok(SetQuorum) => 50_000,
+        Ok(CreateFeeInvoice) | Ok(CloseFeeInvoice) => 30_000,
+        Ok(CommitState) => 200_000,
+        Ok(RepairDerivations) => 100_000,
+        Ok(SetPaused) => 50_000,
+        Ok(VerifyVAACached) => 100_000,
+        Ok(SetSuccessor) => 50_000,
+        Ok(RenderVAA) => 50_000,
+        Ok(DryRunGuardianSetUpgrade) => 100_000,
+        Ok(SetEmissionBreaker) | Ok(ReleaseEmissionBreaker) => 50_000,
+        Ok(ClearEmissionBreaker) => 30_000,
+        Ok(SetClaimGracePeriod) => 50_000,
+        Ok(CloseClaim) => 30_000,
+        Ok(ForgePostedVAA) => 50_000,
+        Ok(SetTokenFee) => 50_000,
+        Ok(EventSchemas) => 30_000,
+        Ok(SetFeeExemption) => 50_000,
+        Ok(SetClaimConsumer) => 50_000,
+        Ok(SetVerifier) => 50_000,
+        // Forwarded instructions are priced by the successor.
+        Ok(ForwardToSuccessor) => return None,
+        Err(_) => return None,
+    };
+    Some(limit)
+}
+
+fn recording_version(instruction: &Instruction) -> bool {
+    let version_registry = VersionRegistry::<'_, { AccountState::MaybeInitialized }>::key(
+        None,
+        &instruction.program_id,
+    );
+    instruction
+        .accounts
+        .iter()
+        .any(|account| account.pubkey == version_registry)
+}
+
 // Convert a full VAA structure into the serialization of its unique components, this structure is
 // what is hashed and verified by Guardians.
 pub fn serialize_vaa(vaa: &PostVAAData) -> Vec<u8> {
     let mut v = Cursor::new(Vec::new());
-    v.write_u32::<BigEndian>(vaa.timestamp).unwrap();
-    v.write_u32::<BigEndian>(vaa.nonce).unwrap();
-    v.write_u16::<BigEndian>(vaa.emitter_chain).unwrap();
+    v.write_u32_be(vaa.timestamp).unwrap();
+    v.write_u32_be(vaa.nonce).unwrap();
+    v.write_u16_be(vaa.emitter_chain).unwrap();
     v.write_all(&vaa.emitter_address).unwrap();
-    v.write_u64::<BigEndian>(vaa.sequence).unwrap();
+    v.write_u64_be(vaa.sequence).unwrap();
     v.write_u8(vaa.consistency_level).unwrap();
     v.write_all(&vaa.payload).unwrap();
     v.into_inner()
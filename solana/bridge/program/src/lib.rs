@@ -4,13 +4,25 @@
 
 use solitaire::*;
 
+/// Number of guardians a single `verify_signatures` instruction can cover.
 pub const MAX_LEN_GUARDIAN_KEYS: usize = 19;
-pub const CHAIN_ID_SOLANA: u16 = 1;
-pub const CHAIN_ID_GOVERANCE: u16 = 1;
+/// Largest guardian set, guardians are indexed by a u8 in VAAs.
+pub const MAX_GUARDIANS: usize = 255;
+pub use wormhole_solana_consts::CHAIN_ID_SOLANA;
+pub const CHAIN_ID_GOVERANCE: u16 = wormhole_solana_consts::CHAIN_ID_GOVERNANCE;
+
+/// VAA parsing and hashing without Solana dependencies, for services that only read VAAs.
+pub use wormhole_vaa;
 
 #[cfg(feature = "instructions")]
 pub mod instructions;
 
+#[cfg(feature = "instructions")]
+pub mod sdk;
+
+#[cfg(feature = "client")]
+pub mod lookup_table;
+
 #[cfg(feature = "wasm")]
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 extern crate wasm_bindgen;
@@ -27,45 +39,232 @@ pub use accounts::{
     Claim,
     ClaimData,
     ClaimDerivationData,
+    CompactMessageData,
+    ConsumedUnreliable,
+    ConsumedUnreliableData,
+    ConsumedUnreliableDerivationData,
+    EmitterMetadata,
+    FeeBurn,
+    FeeBurnData,
     FeeCollector,
+    FeeInvoice,
+    FeeInvoiceData,
+    FeeInvoiceDerivationData,
+    FeeOracle,
+    FeeOracleData,
+    GovernanceAnnouncement,
+    GovernanceAnnouncementData,
+    GovernanceAnnouncementDerivationData,
     GuardianSet,
     GuardianSetData,
     GuardianSetDerivationData,
+    GuardianSetExpiry,
+    GuardianSetExpiryData,
+    GuardianSetFloor,
+    GuardianSetFloorData,
+    GuardianSetWeights,
+    GuardianSetWeightsData,
+    GuardianSetWeightsDerivationData,
     MessageData,
+    Migration,
+    MigrationData,
     PostedMessage,
     PostedMessageData,
+    PostedMessageDerivationData,
     PostedMessageUnreliable,
     PostedMessageUnreliableData,
     PostedVAA,
+    PostedVAACompact,
+    PostedVAACompactData,
+    PostedVAACompactDerivationData,
     PostedVAAData,
+    Quorum,
+    Secp256r1Verification,
+    Secp256r1VerificationData,
     Sequence,
     SequenceDerivationData,
     SequenceTracker,
     SignatureSet,
     SignatureSetData,
+    StateCommitment,
+    StateCommitmentData,
+    VersionRegistry,
+    VersionRegistryData,
 };
 
 pub mod api;
 
 pub use api::{
+    accept_migration,
+    announce_governance,
+    attest_sequence_range,
+    burn_fees,
+    clear_emission_breaker,
+    close_claim,
+    close_fee_invoice,
+    close_posted_vaa,
+    close_signature_set,
+    commit_state,
+    confirm_guardian_set_downgrade,
+    create_fee_invoice,
+    dry_run_guardian_set_upgrade,
+    enable_strict_nonce,
+    event_registry,
+    event_schemas,
+    export_guardian_set,
+    finalize_encoded_vaa,
+    finalize_message_v1,
+    forge_posted_vaa,
+    forward_to_successor,
+    forward_vaa,
+    init_encoded_vaa,
+    init_message_v1,
     initialize,
+    initiate_migration,
+    migrate_account,
     post_message,
+    post_message_deterministic,
     post_message_unreliable,
     post_vaa,
+    post_vaa_compact,
+    queue_governance,
+    register_emitter,
+    release_emission_breaker,
+    render_vaa,
+    repair_derivations,
+    replay_governance,
+    set_claim_consumer,
+    set_claim_grace_period,
+    set_emission_breaker,
+    set_fee_burn,
+    set_fee_exemption,
+    set_fee_oracle,
     set_fees,
+    set_guardian_set_expiry,
+    set_guardian_set_floor,
+    set_guardian_weights,
+    set_paused,
+    set_quorum,
+    set_secp256r1_verification,
+    set_successor,
+    set_token_fee,
+    set_verifier,
     transfer_fees,
     upgrade_contract,
     upgrade_guardian_set,
+    validate_upgrade_buffer,
     verify_signatures,
+    verify_signatures_batch,
+    verify_vaa_cached,
+    verify_vaa_inline,
+    write_encoded_vaa,
+    write_message_v1,
+    AcceptMigration,
+    AcceptMigrationData,
+    AnnounceGovernance,
+    AnnounceGovernanceData,
+    AttestSequenceRange,
+    AttestSequenceRangeData,
+    BurnFees,
+    BurnFeesData,
+    ClearEmissionBreaker,
+    ClearEmissionBreakerData,
+    CloseClaim,
+    CloseClaimData,
+    CloseFeeInvoice,
+    CloseFeeInvoiceData,
+    ClosePostedVAA,
+    ClosePostedVAAData,
+    CloseSignatureSet,
+    CloseSignatureSetData,
+    CommitState,
+    CommitStateData,
+    ConfirmGuardianSetDowngrade,
+    ConfirmGuardianSetDowngradeData,
+    CreateFeeInvoice,
+    CreateFeeInvoiceData,
+    DryRunGuardianSetUpgrade,
+    DryRunGuardianSetUpgradeData,
+    EnableStrictNonce,
+    EnableStrictNonceData,
+    EventSchemas,
+    EventSchemasData,
+    ExportGuardianSet,
+    ExportGuardianSetData,
+    FinalizeEncodedVaa,
+    FinalizeEncodedVaaData,
+    FinalizeMessageV1,
+    FinalizeMessageV1Data,
+    ForgePostedVAA,
+    ForgePostedVAAData,
+    ForwardToSuccessor,
+    ForwardToSuccessorData,
+    ForwardVAA,
+    ForwardVAAData,
+    InitEncodedVaa,
+    InitEncodedVaaData,
+    InitMessageV1,
+    InitMessageV1Data,
     Initialize,
     InitializeData,
+    InitiateMigration,
+    InitiateMigrationData,
+    MessagePosted,
+    MessagePostedV1,
+    MigrateAccount,
+    MigrateAccountData,
+    MigratedAccount,
     PostMessage,
     PostMessageData,
+    PostMessageDeterministic,
+    PostMessageDeterministicData,
     PostMessageUnreliable,
     PostVAA,
+    PostVAACompact,
     PostVAAData,
+    QueueGovernance,
+    QueueGovernanceData,
+    RegisterEmitter,
+    RegisterEmitterData,
+    ReleaseEmissionBreaker,
+    ReleaseEmissionBreakerData,
+    RenderVAA,
+    RenderVAAData,
+    RepairDerivations,
+    RepairDerivationsData,
+    ReplayGovernance,
+    SetClaimConsumer,
+    SetClaimConsumerData,
+    SetClaimGracePeriod,
+    SetClaimGracePeriodData,
+    SetEmissionBreaker,
+    SetEmissionBreakerData,
+    SetFeeBurn,
+    SetFeeBurnData,
+    SetFeeExemption,
+    SetFeeExemptionData,
+    SetFeeOracle,
+    SetFeeOracleData,
     SetFees,
     SetFeesData,
+    SetGuardianSetExpiry,
+    SetGuardianSetExpiryData,
+    SetGuardianSetFloor,
+    SetGuardianSetFloorData,
+    SetGuardianWeights,
+    SetGuardianWeightsData,
+    SetPaused,
+    SetPausedData,
+    SetQuorum,
+    SetQuorumData,
+    SetSecp256r1Verification,
+    SetSecp256r1VerificationData,
+    SetSuccessor,
+    SetSuccessorData,
+    SetTokenFee,
+    SetTokenFeeData,
+    SetVerifier,
+    SetVerifierData,
     Signature,
     TransferFees,
     TransferFeesData,
@@ -74,17 +273,33 @@ pub use api::{
     UpgradeContractData,
     UpgradeGuardianSet,
     UpgradeGuardianSetData,
+    ValidateUpgradeBuffer,
+    ValidateUpgradeBufferData,
     VerifySignatures,
+    VerifySignaturesBatch,
+    VerifySignaturesBatchData,
     VerifySignaturesData,
+    VerifyVAACached,
+    VerifyVAACachedData,
+    VerifyVAAInline,
+    VerifyVAAInlineData,
+    WriteEncodedVaa,
+    WriteEncodedVaaData,
+    WriteMessageV1,
+    WriteMessageV1Data,
 };
 
+pub mod channel;
+pub mod codec;
 pub mod error;
 pub mod types;
 pub mod vaa;
 
+pub use error::Error as CoreBridgeError;
 pub use vaa::{
     DeserializeGovernancePayload,
     DeserializePayload,
+    EmitterInfo,
     PayloadMessage,
     SerializeGovernancePayload,
     SerializePayload,
@@ -100,4 +315,58 @@ solitaire! {
     UpgradeGuardianSet => upgrade_guardian_set,
     VerifySignatures   => verify_signatures,
     PostMessageUnreliable        => post_message_unreliable,
+    SetGuardianWeights => set_guardian_weights,
+    PostVAACompact => post_vaa_compact,
+    EnableStrictNonce => enable_strict_nonce,
+    AnnounceGovernance => announce_governance,
+    RegisterEmitter => register_emitter,
+    InitiateMigration => initiate_migration,
+    MigrateAccount => migrate_account,
+    AcceptMigration => accept_migration,
+    SetGuardianSetFloor => set_guardian_set_floor,
+    ConfirmGuardianSetDowngrade => confirm_guardian_set_downgrade,
+    SetFeeBurn => set_fee_burn,
+    BurnFees => burn_fees,
+    ForwardVAA => forward_vaa,
+    SetSecp256r1Verification => set_secp256r1_verification,
+    SetFeeOracle => set_fee_oracle,
+    ReplayGovernance => replay_governance,
+    ExportGuardianSet => export_guardian_set,
+    VerifySignaturesBatch => verify_signatures_batch,
+    ValidateUpgradeBuffer => validate_upgrade_buffer,
+    VerifyVAAInline => verify_vaa_inline,
+    InitEncodedVaa => init_encoded_vaa,
+    WriteEncodedVaa => write_encoded_vaa,
+    FinalizeEncodedVaa => finalize_encoded_vaa,
+    AttestSequenceRange => attest_sequence_range,
+    ClosePostedVAA => close_posted_vaa,
+    CloseSignatureSet => close_signature_set,
+    SetGuardianSetExpiry => set_guardian_set_expiry,
+    InitMessageV1 => init_message_v1,
+    WriteMessageV1 => write_message_v1,
+    FinalizeMessageV1 => finalize_message_v1,
+    SetQuorum => set_quorum,
+    CreateFeeInvoice => create_fee_invoice,
+    CloseFeeInvoice => close_fee_invoice,
+    CommitState => commit_state,
+    RepairDerivations => repair_derivations,
+    SetPaused => set_paused,
+    VerifyVAACached => verify_vaa_cached,
+    SetSuccessor => set_successor,
+    ForwardToSuccessor => forward_to_successor,
+    QueueGovernance => queue_governance,
+    PostMessageDeterministic => post_message_deterministic,
+    RenderVAA => render_vaa,
+    DryRunGuardianSetUpgrade => dry_run_guardian_set_upgrade,
+    SetEmissionBreaker => set_emission_breaker,
+    ReleaseEmissionBreaker => release_emission_breaker,
+    ClearEmissionBreaker => clear_emission_breaker,
+    SetClaimGracePeriod => set_claim_grace_period,
+    CloseClaim => close_claim,
+    ForgePostedVAA => forge_posted_vaa,
+    SetTokenFee => set_token_fee,
+    EventSchemas => event_schemas,
+    SetFeeExemption => set_fee_exemption,
+    SetClaimConsumer => set_claim_consumer,
+    SetVerifier => set_verifier,
 }
@@ -0,0 +1,78 @@
+//! Address lookup tables for transactions of the core bridge.
+//!
+//! Legacy transactions list every account they use, which limits how many VAAs one transaction
+//! can redeem. Versioned (v0) transactions load accounts from address lookup tables instead, as
+//! long as they are neither signers nor invoked programs, which stay in the message. Tables are
+//! populated with [`static_accounts`] once per guardian set, and extended with the
+//! [`lookup_table_addresses`] of the instructions built with [`crate::instructions`] for
+//! accounts specific to a batch.
+
+use crate::accounts::{
+    Bridge,
+    FeeCollector,
+    GuardianSet,
+    GuardianSetDerivationData,
+    GuardianSetExpiry,
+    GuardianSetWeights,
+    GuardianSetWeightsDerivationData,
+    Secp256r1Verification,
+};
+use solana_program::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    sysvar,
+};
+use solitaire::{
+    processors::{
+        event,
+        seeded::Seeded,
+    },
+    AccountState,
+};
+
+/// Accounts the instructions of the bridge at `program_id` share across transactions while the
+/// guardian set with `guardian_set_index` is current: the bridge config, fee collector, guardian
+/// set with its weights, optional config accounts the `with_*` builders append, the event
+/// authority and the sysvars and programs the instructions pass.
+pub fn static_accounts(program_id: Pubkey, guardian_set_index: u32) -> Vec<Pubkey> {
+    vec![
+        Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id),
+        FeeCollector::<'_>::key(None, &program_id),
+        GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+            &GuardianSetDerivationData {
+                index: guardian_set_index,
+            },
+            &program_id,
+        ),
+        GuardianSetWeights::<'_, { AccountState::Uninitialized }>::key(
+            &GuardianSetWeightsDerivationData {
+                index: guardian_set_index,
+            },
+            &program_id,
+        ),
+        GuardianSetExpiry::<'_, { AccountState::Initialized }>::key(None, &program_id),
+        Secp256r1Verification::<'_, { AccountState::Initialized }>::key(None, &program_id),
+        event::event_authority(&program_id).0,
+        sysvar::clock::id(),
+        sysvar::rent::id(),
+        sysvar::instructions::id(),
+        solana_program::system_program::id(),
+    ]
+}
+
+/// Accounts of `instructions` that a v0 message can load from lookup tables, deduplicated in the
+/// order they are first used. Signers and the programs the instructions invoke are left out, v0
+/// messages must list them as static keys.
+pub fn lookup_table_addresses(instructions: &[Instruction]) -> Vec<Pubkey> {
+    let metas = || instructions.iter().flat_map(|ix| ix.accounts.iter());
+    let mut addresses: Vec<Pubkey> = Vec::new();
+    for meta in metas() {
+        // Accounts signing any of the instructions sign the message.
+        let signer = metas().any(|other| other.is_signer && other.pubkey == meta.pubkey);
+        let invoked = instructions.iter().any(|ix| ix.program_id == meta.pubkey);
+        if !signer && !invoked && !addresses.contains(&meta.pubkey) {
+            addresses.push(meta.pubkey);
+        }
+    }
+    addresses
+}
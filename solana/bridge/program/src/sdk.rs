@@ -0,0 +1,3 @@
+//! Helpers for programs integrating with the core bridge.
+
+pub mod cpi;
@@ -0,0 +1,249 @@
+//! Typed cross-program invocations of the legacy core bridge instructions.
+//!
+//! Each helper takes the accounts of its instruction by name, derives the PDAs the instruction
+//! expects from the bridge program and the other accounts, and fails with
+//! [`SolitaireError::InvalidDerive`] naming the expected key if an account doesn't match, rather
+//! than leaving the runtime to reject a misordered account list. Only the accounts of the
+//! instruction are passed to the CPI, callers don't hand over all accounts of their own
+//! instruction.
+//!
+//! ```ignore
+//! bridge::sdk::cpi::post_message(
+//!     &PostMessageAccounts {
+//!         bridge: &accs.bridge,
+//!         message: &accs.message,
+//!         emitter: &accs.emitter,
+//!         sequence: &accs.sequence,
+//!         payer: &accs.payer,
+//!         fee_collector: &accs.fee_collector,
+//!         clock: &accs.clock,
+//!         rent: &accs.rent,
+//!         system_program: &accs.system_program,
+//!         bridge_program: &accs.bridge_program,
+//!     },
+//!     nonce,
+//!     payload,
+//!     ConsistencyLevel::Finalized,
+//!     &[&emitter_seeds],
+//! )?;
+//! ```
+
+use crate::{
+    api::VerifySignaturesData,
+    instructions,
+    types::ConsistencyLevel,
+    PostVAAData,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::Instruction,
+};
+use solitaire::{
+    invoke_signed,
+    Result,
+    SolitaireError,
+};
+
+/// Accounts of `post_message` and `post_message_unreliable`. The message fee must have been paid
+/// to the fee collector earlier in the same instruction.
+pub struct PostMessageAccounts<'a, 'info> {
+    /// Bridge config, needed for the fee.
+    pub bridge: &'a AccountInfo<'info>,
+
+    /// Account the message is posted to, signing.
+    pub message: &'a AccountInfo<'info>,
+
+    /// Emitter of the message, signing. Usually a PDA of the calling program.
+    pub emitter: &'a AccountInfo<'info>,
+
+    /// Sequence tracker of the emitter.
+    pub sequence: &'a AccountInfo<'info>,
+
+    /// Pays for the message account, signing.
+    pub payer: &'a AccountInfo<'info>,
+    pub fee_collector: &'a AccountInfo<'info>,
+    pub clock: &'a AccountInfo<'info>,
+    pub rent: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+
+    /// Core bridge program invoked.
+    pub bridge_program: &'a AccountInfo<'info>,
+}
+
+/// Post a message, see `bridge::api::post_message`. `signer_seeds` sign for the emitter and any
+/// other PDA signers. The sequence assigned to the message is returned as return data, see
+/// [`instructions::posted_sequence`].
+pub fn post_message(
+    accounts: &PostMessageAccounts,
+    nonce: u32,
+    payload: Vec<u8>,
+    consistency_level: ConsistencyLevel,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let ix = instructions::post_message(
+        *accounts.bridge_program.key,
+        *accounts.payer.key,
+        *accounts.emitter.key,
+        *accounts.message.key,
+        nonce,
+        payload,
+        consistency_level,
+    )?;
+    invoke_checked(&ix, &accounts.infos(), signer_seeds)
+}
+
+/// Post a message to a reusable account, see `bridge::api::post_message_unreliable`.
+pub fn post_message_unreliable(
+    accounts: &PostMessageAccounts,
+    nonce: u32,
+    payload: Vec<u8>,
+    consistency_level: ConsistencyLevel,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let ix = instructions::post_message_unreliable(
+        *accounts.bridge_program.key,
+        *accounts.payer.key,
+        *accounts.emitter.key,
+        *accounts.message.key,
+        nonce,
+        payload,
+        consistency_level,
+    )?;
+    invoke_checked(&ix, &accounts.infos(), signer_seeds)
+}
+
+impl<'a, 'info> PostMessageAccounts<'a, 'info> {
+    fn infos(&self) -> [AccountInfo<'info>; 10] {
+        [
+            self.bridge.clone(),
+            self.message.clone(),
+            self.emitter.clone(),
+            self.sequence.clone(),
+            self.payer.clone(),
+            self.fee_collector.clone(),
+            self.clock.clone(),
+            self.rent.clone(),
+            self.system_program.clone(),
+            self.bridge_program.clone(),
+        ]
+    }
+}
+
+/// Accounts of `verify_signatures`.
+pub struct VerifySignaturesAccounts<'a, 'info> {
+    /// Pays for the signature set, signing.
+    pub payer: &'a AccountInfo<'info>,
+
+    /// Guardian set that signed, derived from `guardian_set_index`.
+    pub guardian_set: &'a AccountInfo<'info>,
+
+    /// Signature set recording the verified signatures, signing.
+    pub signature_set: &'a AccountInfo<'info>,
+    pub instructions: &'a AccountInfo<'info>,
+    pub rent: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+
+    /// Core bridge program invoked.
+    pub bridge_program: &'a AccountInfo<'info>,
+}
+
+/// Verify guardian signatures, see `bridge::api::verify_signatures`. The secp256k1 instruction the
+/// signatures are checked against must directly precede the instruction of the caller in the
+/// transaction.
+pub fn verify_signatures(
+    accounts: &VerifySignaturesAccounts,
+    guardian_set_index: u32,
+    data: VerifySignaturesData,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let ix = instructions::verify_signatures(
+        *accounts.bridge_program.key,
+        *accounts.payer.key,
+        guardian_set_index,
+        *accounts.signature_set.key,
+        data,
+    )?;
+    invoke_checked(
+        &ix,
+        &[
+            accounts.payer.clone(),
+            accounts.guardian_set.clone(),
+            accounts.signature_set.clone(),
+            accounts.instructions.clone(),
+            accounts.rent.clone(),
+            accounts.system_program.clone(),
+            accounts.bridge_program.clone(),
+        ],
+        signer_seeds,
+    )
+}
+
+/// Accounts of `post_vaa`.
+pub struct PostVAAAccounts<'a, 'info> {
+    /// Guardian set that signed, derived from the VAA.
+    pub guardian_set: &'a AccountInfo<'info>,
+    pub bridge: &'a AccountInfo<'info>,
+
+    /// Signature set filled by `verify_signatures`.
+    pub signature_set: &'a AccountInfo<'info>,
+
+    /// Account the VAA is posted to, derived from the digest of its body.
+    pub posted_vaa: &'a AccountInfo<'info>,
+
+    /// Pays for the posted VAA, signing.
+    pub payer: &'a AccountInfo<'info>,
+    pub clock: &'a AccountInfo<'info>,
+    pub rent: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+
+    /// Guardian set weights, only read by bridges built with stake-weighted consensus.
+    pub guardian_set_weights: &'a AccountInfo<'info>,
+
+    /// Core bridge program invoked.
+    pub bridge_program: &'a AccountInfo<'info>,
+}
+
+/// Post a VAA whose signatures were verified, see `bridge::api::post_vaa`.
+pub fn post_vaa(
+    accounts: &PostVAAAccounts,
+    vaa: PostVAAData,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let ix = instructions::post_vaa(
+        *accounts.bridge_program.key,
+        *accounts.payer.key,
+        *accounts.signature_set.key,
+        vaa,
+    );
+    invoke_checked(
+        &ix,
+        &[
+            accounts.guardian_set.clone(),
+            accounts.bridge.clone(),
+            accounts.signature_set.clone(),
+            accounts.posted_vaa.clone(),
+            accounts.payer.clone(),
+            accounts.clock.clone(),
+            accounts.rent.clone(),
+            accounts.system_program.clone(),
+            accounts.guardian_set_weights.clone(),
+            accounts.bridge_program.clone(),
+        ],
+        signer_seeds,
+    )
+}
+
+/// Invoke `ix` after checking that `infos` start with its accounts, in order.
+fn invoke_checked(
+    ix: &Instruction,
+    infos: &[AccountInfo],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    for (meta, info) in ix.accounts.iter().zip(infos) {
+        if meta.pubkey != *info.key {
+            return Err(SolitaireError::InvalidDerive(*info.key, meta.pubkey));
+        }
+    }
+    invoke_signed(ix, infos, signer_seeds)?;
+    Ok(())
+}
@@ -1,6 +1,15 @@
 use crate::{
+    accounts::{
+        BridgeConfig,
+        Quorum,
+    },
     api::ForeignAddress,
+    codec::{
+        ReadBytes,
+        WriteBytes,
+    },
     vaa::{
+        deserialize_decree,
         DeserializeGovernancePayload,
         DeserializePayload,
         SerializeGovernancePayload,
@@ -11,10 +20,6 @@ use borsh::{
     BorshDeserialize,
     BorshSerialize,
 };
-use byteorder::{
-    BigEndian,
-    ReadBytesExt,
-};
 use primitive_types::U256;
 use serde::{
     Deserialize,
@@ -33,30 +38,194 @@ use std::{
         Write,
     },
 };
+use wormhole_governance::{
+    module,
+    Decree,
+};
 
 /// Type representing an Ethereum style public key for Guardians.
 pub type GuardianPublicKey = [u8; 20];
 
+/// Compressed secp256r1 (P-256) public key of a guardian signing with an HSM that only supports
+/// P-256.
+pub type Secp256r1PublicKey = [u8; 33];
+
 #[repr(u8)]
-#[derive(BorshSerialize, BorshDeserialize, Clone, Serialize, Deserialize)]
+#[derive(BorshSerialize, BorshDeserialize, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ConsistencyLevel {
     Confirmed,
     Finalized,
 }
 
-pub struct GovernancePayloadUpgrade {
-    // Address of the new Implementation
-    pub new_contract: Pubkey,
-}
+// Upgrades, guardian set changes, fee changes and fee transfers are shared with guardian tooling,
+// see `wormhole_governance`.
+pub use wormhole_governance::{
+    ContractUpgrade as GovernancePayloadUpgrade,
+    GuardianSetUpdate as GovernancePayloadGuardianSetChange,
+    SetFee as GovernancePayloadSetMessageFee,
+    TransferFees as GovernancePayloadTransferFees,
+};
 
 impl SerializePayload for GovernancePayloadUpgrade {
     fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
-        v.write_all(&self.new_contract.to_bytes())?;
-        Ok(())
+        Ok(self.encode(v)?)
     }
 }
 
 impl DeserializePayload for GovernancePayloadUpgrade
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        deserialize_decree::<Self, _>(buf)
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadUpgrade {
+    const MODULE: &'static str = module::CORE;
+    const ACTION: u8 = <Self as Decree>::ACTION;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadUpgrade {
+}
+
+impl SerializePayload for GovernancePayloadGuardianSetChange {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        Ok(self.encode(v)?)
+    }
+}
+
+impl DeserializePayload for GovernancePayloadGuardianSetChange
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        deserialize_decree::<Self, _>(buf)
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadGuardianSetChange {
+    const MODULE: &'static str = module::CORE;
+    const ACTION: u8 = <Self as Decree>::ACTION;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadGuardianSetChange {
+}
+
+impl SerializePayload for GovernancePayloadSetMessageFee {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        Ok(self.encode(v)?)
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetMessageFee
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        deserialize_decree::<Self, _>(buf)
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetMessageFee {
+    const MODULE: &'static str = module::CORE;
+    const ACTION: u8 = <Self as Decree>::ACTION;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetMessageFee {
+}
+
+impl SerializePayload for GovernancePayloadTransferFees {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        Ok(self.encode(v)?)
+    }
+}
+
+impl DeserializePayload for GovernancePayloadTransferFees
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        deserialize_decree::<Self, _>(buf)
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadTransferFees {
+    const MODULE: &'static str = module::CORE;
+    const ACTION: u8 = <Self as Decree>::ACTION;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadTransferFees {
+}
+
+pub struct GovernancePayloadSetGuardianWeights {
+    // Guardian set the weights apply to
+    pub guardian_set_index: u32,
+
+    // Weight per guardian, in guardian set order
+    pub weights: Vec<u64>,
+}
+
+impl SerializePayload for GovernancePayloadSetGuardianWeights {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_u32_be(self.guardian_set_index)?;
+        v.write_u8(self.weights.len() as u8)?;
+        for weight in self.weights.iter() {
+            v.write_u64_be(*weight)?;
+        }
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetGuardianWeights
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let guardian_set_index = c.read_u32_be()?;
+
+        let weights_len = c.read_u8()?;
+        let mut weights = Vec::with_capacity(weights_len as usize);
+        for _ in 0..weights_len {
+            weights.push(c.read_u64_be()?);
+        }
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetGuardianWeights {
+            guardian_set_index,
+            weights,
+        })
+    }
+}
+
+// Action 5 is reserved for RecoverChainId, as used by the other Wormhole runtimes.
+impl SerializeGovernancePayload for GovernancePayloadSetGuardianWeights {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 6;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetGuardianWeights {
+}
+
+pub struct GovernancePayloadMigrate {
+    // Program the core state accounts are handed over to
+    pub successor: Pubkey,
+}
+
+impl SerializePayload for GovernancePayloadMigrate {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_all(&self.successor.to_bytes())?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadMigrate
 where
     Self: DeserializeGovernancePayload,
 {
@@ -71,41 +240,71 @@ where
             return Err(InvalidAccountData.into());
         }
 
-        Ok(GovernancePayloadUpgrade {
-            new_contract: Pubkey::new(&addr[..]),
+        Ok(GovernancePayloadMigrate {
+            successor: Pubkey::new(&addr[..]),
         })
     }
 }
 
-impl SerializeGovernancePayload for GovernancePayloadUpgrade {
+impl SerializeGovernancePayload for GovernancePayloadMigrate {
     const MODULE: &'static str = "Core";
-    const ACTION: u8 = 1;
+    const ACTION: u8 = 7;
 }
 
-impl DeserializeGovernancePayload for GovernancePayloadUpgrade {
+impl DeserializeGovernancePayload for GovernancePayloadMigrate {
 }
 
-pub struct GovernancePayloadGuardianSetChange {
-    // New GuardianSetIndex
-    pub new_guardian_set_index: u32,
-
-    // New GuardianSet
-    pub new_guardian_set: Vec<[u8; 20]>,
+pub struct GovernancePayloadSetGuardianSetFloor {
+    // Minimum number of keys a guardian set can be downgraded to without confirmation
+    pub min_keys: u8,
 }
 
-impl SerializePayload for GovernancePayloadGuardianSetChange {
+impl SerializePayload for GovernancePayloadSetGuardianSetFloor {
     fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
-        use byteorder::WriteBytesExt;
-        v.write_u32::<BigEndian>(self.new_guardian_set_index)?;
-        v.write_u8(self.new_guardian_set.len() as u8)?;
-        for key in self.new_guardian_set.iter() {
-            v.write_all(key)?;
+        v.write_u8(self.min_keys)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetGuardianSetFloor
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let min_keys = c.read_u8()?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
         }
+
+        Ok(GovernancePayloadSetGuardianSetFloor { min_keys })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetGuardianSetFloor {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 8;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetGuardianSetFloor {
+}
+
+pub struct GovernancePayloadConfirmGuardianSetDowngrade {
+    // Digest of the guardian set upgrade VAA body that shrinks the set below the floor
+    pub digest: [u8; 32],
+}
+
+impl SerializePayload for GovernancePayloadConfirmGuardianSetDowngrade {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_all(&self.digest)?;
         Ok(())
     }
 }
 
-impl DeserializePayload for GovernancePayloadGuardianSetChange
+impl DeserializePayload for GovernancePayloadConfirmGuardianSetDowngrade
 where
     Self: DeserializeGovernancePayload,
 {
@@ -113,51 +312,158 @@ where
         let mut c = Cursor::new(buf);
         Self::check_governance_header(&mut c)?;
 
-        let new_index = c.read_u32::<BigEndian>()?;
+        let mut digest = [0u8; 32];
+        c.read_exact(&mut digest)?;
 
-        let keys_len = c.read_u8()?;
-        let mut keys = Vec::with_capacity(keys_len as usize);
-        for _ in 0..keys_len {
-            let mut key: [u8; 20] = [0; 20];
-            c.read_exact(&mut key)?;
-            keys.push(key);
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
         }
 
+        Ok(GovernancePayloadConfirmGuardianSetDowngrade { digest })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadConfirmGuardianSetDowngrade {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 9;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadConfirmGuardianSetDowngrade {
+}
+
+pub struct GovernancePayloadSetFeeBurn {
+    // Whether collected message fees are burned instead of accumulated
+    pub enabled: bool,
+}
+
+impl SerializePayload for GovernancePayloadSetFeeBurn {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_u8(self.enabled as u8)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetFeeBurn
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let enabled = match c.read_u8()? {
+            0 => false,
+            1 => true,
+            _ => return Err(InvalidAccountData.into()),
+        };
+
         if c.position() != c.into_inner().len() as u64 {
             return Err(InvalidAccountData.into());
         }
 
-        Ok(GovernancePayloadGuardianSetChange {
-            new_guardian_set_index: new_index,
-            new_guardian_set: keys,
-        })
+        Ok(GovernancePayloadSetFeeBurn { enabled })
     }
 }
 
-impl SerializeGovernancePayload for GovernancePayloadGuardianSetChange {
+impl SerializeGovernancePayload for GovernancePayloadSetFeeBurn {
     const MODULE: &'static str = "Core";
-    const ACTION: u8 = 2;
+    const ACTION: u8 = 10;
 }
 
-impl DeserializeGovernancePayload for GovernancePayloadGuardianSetChange {
+impl DeserializeGovernancePayload for GovernancePayloadSetFeeBurn {
 }
 
-pub struct GovernancePayloadSetMessageFee {
-    // New fee in lamports
-    pub fee: U256,
+/// Payload of the messages posted by `forward_vaa`, re-emitting a VAA verified on Solana for
+/// another SVM network. Spokes verify the forwarded message and trust the original VAA by its
+/// digest and emitter metadata, without verifying its signatures themselves.
+#[derive(PartialEq, Debug, Clone)]
+pub struct PayloadForwardedVAA {
+    // Chain ID of the SVM network the VAA is forwarded to
+    pub target_chain: u16,
+
+    // Keccak256 digest of the original VAA body
+    pub digest: [u8; 32],
+
+    // Metadata of the original VAA
+    pub emitter_chain: u16,
+    pub emitter_address: ForeignAddress,
+    pub sequence: u64,
+    pub timestamp: u32,
+    pub nonce: u32,
+    pub consistency_level: u8,
 }
 
-impl SerializePayload for GovernancePayloadSetMessageFee {
+impl PayloadForwardedVAA {
+    /// Prefix telling forwarded VAAs apart from other payloads.
+    pub const MAGIC: &'static [u8; 4] = b"FVAA";
+}
+
+impl SerializePayload for PayloadForwardedVAA {
     fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
-        let mut fee_data = [0u8; 32];
-        self.fee.to_big_endian(&mut fee_data);
-        v.write_all(&fee_data[..])?;
+        v.write_all(Self::MAGIC)?;
+        v.write_u16_be(self.target_chain)?;
+        v.write_all(&self.digest)?;
+        v.write_u16_be(self.emitter_chain)?;
+        v.write_all(&self.emitter_address)?;
+        v.write_u64_be(self.sequence)?;
+        v.write_u32_be(self.timestamp)?;
+        v.write_u32_be(self.nonce)?;
+        v.write_u8(self.consistency_level)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for PayloadForwardedVAA {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+
+        let mut magic = [0u8; 4];
+        c.read_exact(&mut magic)?;
+        if &magic != Self::MAGIC {
+            return Err(InvalidAccountData.into());
+        }
+
+        let target_chain = c.read_u16_be()?;
+        let mut digest = [0u8; 32];
+        c.read_exact(&mut digest)?;
+        let emitter_chain = c.read_u16_be()?;
+        let mut emitter_address = ForeignAddress::default();
+        c.read_exact(&mut emitter_address)?;
+        let sequence = c.read_u64_be()?;
+        let timestamp = c.read_u32_be()?;
+        let nonce = c.read_u32_be()?;
+        let consistency_level = c.read_u8()?;
 
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(PayloadForwardedVAA {
+            target_chain,
+            digest,
+            emitter_chain,
+            emitter_address,
+            sequence,
+            timestamp,
+            nonce,
+            consistency_level,
+        })
+    }
+}
+
+pub struct GovernancePayloadSetSecp256r1Verification {
+    // Whether guardian signatures verified by the secp256r1 precompile are accepted
+    pub enabled: bool,
+}
+
+impl SerializePayload for GovernancePayloadSetSecp256r1Verification {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_u8(self.enabled as u8)?;
         Ok(())
     }
 }
 
-impl DeserializePayload for GovernancePayloadSetMessageFee
+impl DeserializePayload for GovernancePayloadSetSecp256r1Verification
 where
     Self: DeserializeGovernancePayload,
 {
@@ -165,45 +471,49 @@ where
         let mut c = Cursor::new(buf);
         Self::check_governance_header(&mut c)?;
 
-        let mut fee_data: [u8; 32] = [0; 32];
-        c.read_exact(&mut fee_data)?;
-        let fee = U256::from_big_endian(&fee_data);
+        let enabled = match c.read_u8()? {
+            0 => false,
+            1 => true,
+            _ => return Err(InvalidAccountData.into()),
+        };
 
         if c.position() != c.into_inner().len() as u64 {
             return Err(InvalidAccountData.into());
         }
 
-        Ok(GovernancePayloadSetMessageFee { fee })
+        Ok(GovernancePayloadSetSecp256r1Verification { enabled })
     }
 }
 
-impl SerializeGovernancePayload for GovernancePayloadSetMessageFee {
+impl SerializeGovernancePayload for GovernancePayloadSetSecp256r1Verification {
     const MODULE: &'static str = "Core";
-    const ACTION: u8 = 3;
+    const ACTION: u8 = 11;
 }
 
-impl DeserializeGovernancePayload for GovernancePayloadSetMessageFee {
+impl DeserializeGovernancePayload for GovernancePayloadSetSecp256r1Verification {
 }
 
-pub struct GovernancePayloadTransferFees {
-    // Amount to be transferred
-    pub amount: U256,
+pub struct GovernancePayloadSetFeeOracle {
+    // Account suggesting message fees, zero to disable dynamic fees
+    pub oracle: Pubkey,
 
-    // Recipient
-    pub to: ForeignAddress,
+    // Lowest fee in lamports
+    pub min_fee: U256,
+
+    // Highest fee in lamports
+    pub max_fee: U256,
 }
 
-impl SerializePayload for GovernancePayloadTransferFees {
+impl SerializePayload for GovernancePayloadSetFeeOracle {
     fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
-        let mut amount_data = [0u8; 32];
-        self.amount.to_big_endian(&mut amount_data);
-        v.write_all(&amount_data)?;
-        v.write_all(&self.to)?;
+        v.write_all(&self.oracle.to_bytes())?;
+        v.write_u256_be(self.min_fee)?;
+        v.write_u256_be(self.max_fee)?;
         Ok(())
     }
 }
 
-impl DeserializePayload for GovernancePayloadTransferFees
+impl DeserializePayload for GovernancePayloadSetFeeOracle
 where
     Self: DeserializeGovernancePayload,
 {
@@ -211,25 +521,525 @@ where
         let mut c = Cursor::new(buf);
         Self::check_governance_header(&mut c)?;
 
-        let mut amount_data: [u8; 32] = [0; 32];
-        c.read_exact(&mut amount_data)?;
-        let amount = U256::from_big_endian(&amount_data);
+        let mut oracle = [0u8; 32];
+        c.read_exact(&mut oracle)?;
+        let min_fee = c.read_u256_be()?;
+        let max_fee = c.read_u256_be()?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetFeeOracle {
+            oracle: Pubkey::new_from_array(oracle),
+            min_fee,
+            max_fee,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetFeeOracle {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 12;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetFeeOracle {
+}
+
+pub struct GovernancePayloadSetGuardianSetExpiry {
+    // Whether VAAs of expired guardian sets are accepted within the soft window
+    pub soft: bool,
+
+    // Seconds after its expiration a guardian set is still accepted when soft
+    pub soft_window: u32,
+}
+
+impl SerializePayload for GovernancePayloadSetGuardianSetExpiry {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_u8(self.soft as u8)?;
+        v.write_u32_be(self.soft_window)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetGuardianSetExpiry
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
 
-        let mut to = ForeignAddress::default();
-        c.read_exact(&mut to)?;
+        let soft = match c.read_u8()? {
+            0 => false,
+            1 => true,
+            _ => return Err(InvalidAccountData.into()),
+        };
+        let soft_window = c.read_u32_be()?;
 
         if c.position() != c.into_inner().len() as u64 {
             return Err(InvalidAccountData.into());
         }
 
-        Ok(GovernancePayloadTransferFees { amount, to })
+        Ok(GovernancePayloadSetGuardianSetExpiry { soft, soft_window })
     }
 }
 
-impl SerializeGovernancePayload for GovernancePayloadTransferFees {
+impl SerializeGovernancePayload for GovernancePayloadSetGuardianSetExpiry {
     const MODULE: &'static str = "Core";
-    const ACTION: u8 = 4;
+    const ACTION: u8 = 13;
 }
 
-impl DeserializeGovernancePayload for GovernancePayloadTransferFees {
+impl DeserializeGovernancePayload for GovernancePayloadSetGuardianSetExpiry {
+}
+
+pub struct GovernancePayloadSetQuorum {
+    // Fraction of a guardian set that must sign for consensus
+    pub quorum: Quorum,
+}
+
+impl SerializePayload for GovernancePayloadSetQuorum {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_u8(self.quorum.numerator)?;
+        v.write_u8(self.quorum.denominator)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetQuorum
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let quorum = Quorum {
+            numerator: c.read_u8()?,
+            denominator: c.read_u8()?,
+        };
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetQuorum { quorum })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetQuorum {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 14;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetQuorum {
+}
+
+pub struct GovernancePayloadSetPaused {
+    // Instruction families paused, a combination of the `BridgeConfig::PAUSE_*` bits. Decrees
+    // from before families could be paused separately carry 1, pausing message posting.
+    pub paused: u8,
+}
+
+impl SerializePayload for GovernancePayloadSetPaused {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_u8(self.paused)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetPaused
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let paused = c.read_u8()?;
+        if paused & !BridgeConfig::PAUSE_ALL != 0 {
+            return Err(InvalidAccountData.into());
+        }
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetPaused { paused })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetPaused {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 15;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetPaused {
+}
+
+pub struct GovernancePayloadSetSuccessor {
+    // Program instructions are forwarded to, zero to stop forwarding
+    pub successor: Pubkey,
+}
+
+impl SerializePayload for GovernancePayloadSetSuccessor {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_all(&self.successor.to_bytes())?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetSuccessor
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let mut successor = [0u8; 32];
+        c.read_exact(&mut successor)?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetSuccessor {
+            successor: Pubkey::new_from_array(successor),
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetSuccessor {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 16;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetSuccessor {
+}
+
+pub struct GovernancePayloadSetEmissionBreaker {
+    // Multiple of their trailing average registered emitters may post per epoch, zero to disable
+    pub multiple: u32,
+}
+
+impl SerializePayload for GovernancePayloadSetEmissionBreaker {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_u32_be(self.multiple)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetEmissionBreaker
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let multiple = c.read_u32_be()?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetEmissionBreaker { multiple })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetEmissionBreaker {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 17;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetEmissionBreaker {
+}
+
+pub struct GovernancePayloadReleaseEmissionBreaker {
+    // Emitter whose tripped breaker is cleared
+    pub emitter: Pubkey,
+}
+
+impl SerializePayload for GovernancePayloadReleaseEmissionBreaker {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_all(&self.emitter.to_bytes())?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadReleaseEmissionBreaker
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let mut emitter = [0u8; 32];
+        c.read_exact(&mut emitter)?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadReleaseEmissionBreaker {
+            emitter: Pubkey::new_from_array(emitter),
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadReleaseEmissionBreaker {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 18;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadReleaseEmissionBreaker {
+}
+
+pub struct GovernancePayloadSetClaimGracePeriod {
+    // Seconds after the expiry of the signing guardian set that claims can be closed
+    pub grace_period: u32,
+
+    // Recipient of the rent of closed claims, the default pubkey disables closing claims
+    pub recipient: Pubkey,
+}
+
+impl SerializePayload for GovernancePayloadSetClaimGracePeriod {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_u32_be(self.grace_period)?;
+        v.write_all(&self.recipient.to_bytes())?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetClaimGracePeriod
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let grace_period = c.read_u32_be()?;
+        let mut recipient = [0u8; 32];
+        c.read_exact(&mut recipient)?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetClaimGracePeriod {
+            grace_period,
+            recipient: Pubkey::new_from_array(recipient),
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetClaimGracePeriod {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 19;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetClaimGracePeriod {
+}
+
+pub struct GovernancePayloadSetTokenFee {
+    // Mint message fees can be paid in, zero to disable token fees
+    pub mint: Pubkey,
+
+    // Fee in tokens of the mint
+    pub fee: U256,
+}
+
+impl SerializePayload for GovernancePayloadSetTokenFee {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_all(&self.mint.to_bytes())?;
+        v.write_u256_be(self.fee)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetTokenFee
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let mut mint = [0u8; 32];
+        c.read_exact(&mut mint)?;
+        let fee = c.read_u256_be()?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetTokenFee {
+            mint: Pubkey::new_from_array(mint),
+            fee,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetTokenFee {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 20;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetTokenFee {
+}
+
+pub struct GovernancePayloadSetFeeExemption {
+    // Emitter exempted from the message fee
+    pub emitter: Pubkey,
+
+    // Whether the emitter is exempted, false revokes the exemption
+    pub exempt: bool,
+}
+
+impl SerializePayload for GovernancePayloadSetFeeExemption {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_all(&self.emitter.to_bytes())?;
+        v.write_u8(self.exempt as u8)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetFeeExemption
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let mut emitter = [0u8; 32];
+        c.read_exact(&mut emitter)?;
+        let exempt = match c.read_u8()? {
+            0 => false,
+            1 => true,
+            _ => return Err(InvalidAccountData.into()),
+        };
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetFeeExemption {
+            emitter: Pubkey::new_from_array(emitter),
+            exempt,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetFeeExemption {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 21;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetFeeExemption {
+}
+
+pub struct GovernancePayloadSetClaimConsumer {
+    // Program whose claims prove redemptions to `close_posted_vaa`
+    pub program: Pubkey,
+
+    // Whether the program is approved, false revokes the approval
+    pub approved: bool,
+}
+
+impl SerializePayload for GovernancePayloadSetClaimConsumer {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_all(&self.program.to_bytes())?;
+        v.write_u8(self.approved as u8)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetClaimConsumer
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let mut program = [0u8; 32];
+        c.read_exact(&mut program)?;
+        let approved = match c.read_u8()? {
+            0 => false,
+            1 => true,
+            _ => return Err(InvalidAccountData.into()),
+        };
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetClaimConsumer {
+            program: Pubkey::new_from_array(program),
+            approved,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetClaimConsumer {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 22;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetClaimConsumer {
+}
+
+pub struct GovernancePayloadSetVerifier {
+    // Program whose accounts carry VAAs it verified in place of the bridge
+    pub program: Pubkey,
+
+    // Whether the program is approved, false revokes the approval
+    pub approved: bool,
+}
+
+impl SerializePayload for GovernancePayloadSetVerifier {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_all(&self.program.to_bytes())?;
+        v.write_u8(self.approved as u8)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetVerifier
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let mut program = [0u8; 32];
+        c.read_exact(&mut program)?;
+        let approved = match c.read_u8()? {
+            0 => false,
+            1 => true,
+            _ => return Err(InvalidAccountData.into()),
+        };
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetVerifier {
+            program: Pubkey::new_from_array(program),
+            approved,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetVerifier {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 23;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetVerifier {
 }
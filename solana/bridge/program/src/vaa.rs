@@ -1,30 +1,42 @@
 use crate::{
+    accounts::{
+        GuardianSet,
+        GuardianSetData,
+        GuardianSetDerivationData,
+        SignatureSetData,
+        Verifier,
+        VerifierData,
+        VerifierDerivationData,
+    },
     api::{
         post_vaa::PostVAAData,
         ForeignAddress,
     },
+    codec::WriteBytes,
     error::Error::{
+        GuardianSetMismatch,
         InvalidGovernanceAction,
         InvalidGovernanceChain,
         InvalidGovernanceModule,
+        InvalidSignatureSet,
+        PostVAAGuardianSetExpired,
+        UnapprovedVerifier,
     },
     PostedVAAData,
     Result,
     CHAIN_ID_SOLANA,
 };
-use byteorder::{
-    BigEndian,
-    ReadBytesExt,
-};
 use serde::{
     Deserialize,
     Serialize,
 };
 use solana_program::{
     account_info::AccountInfo,
+    program_error::ProgramError,
     pubkey::Pubkey,
 };
 use solitaire::{
+    processors::seeded::Seeded,
     Context,
     Data,
     Peel,
@@ -34,10 +46,16 @@ use solitaire::{
 use std::{
     io::{
         Cursor,
-        Read,
+        ErrorKind,
         Write,
     },
     ops::Deref,
+    str::FromStr,
+};
+use wormhole_governance::Decree;
+use wormhole_vaa::{
+    GovernanceHeader,
+    Vaa,
 };
 
 pub trait SerializePayload: Sized {
@@ -69,12 +87,11 @@ pub trait SerializeGovernancePayload: SerializePayload {
         &self,
         c: &mut W,
     ) -> std::result::Result<(), SolitaireError> {
-        use byteorder::WriteBytesExt;
         let module = format!("{:\0>32}", Self::MODULE);
         let module = module.as_bytes();
         c.write_all(module)?;
         c.write_u8(Self::ACTION)?;
-        c.write_u16::<BigEndian>(CHAIN_ID_SOLANA)?;
+        c.write_u16_be(CHAIN_ID_SOLANA)?;
         Ok(())
     }
 }
@@ -83,29 +100,47 @@ pub trait DeserializeGovernancePayload: DeserializePayload + SerializeGovernance
     fn check_governance_header(
         c: &mut Cursor<&mut &[u8]>,
     ) -> std::result::Result<(), SolitaireError> {
-        let mut module = [0u8; 32];
-        c.read_exact(&mut module)?;
-        if module != format!("{:\0>32}", Self::MODULE).as_bytes() {
+        let header =
+            GovernanceHeader::parse(&c.get_ref()[c.position() as usize..]).map_err(truncated)?;
+        if !header.is_module(Self::MODULE) {
             return Err(InvalidGovernanceModule.into());
         }
 
-        let action = c.read_u8()?;
-        if action != Self::ACTION {
+        if header.action != Self::ACTION {
             return Err(InvalidGovernanceAction.into());
         }
 
-        let chain = c.read_u16::<BigEndian>()?;
-        if chain != CHAIN_ID_SOLANA && chain != 0 {
+        if header.chain != CHAIN_ID_SOLANA && header.chain != 0 {
             return Err(InvalidGovernanceChain.into());
         }
 
+        c.set_position(c.position() + GovernanceHeader::LEN as u64);
         Ok(())
     }
 }
 
+/// Parse a governance payload of `T` whose body is a decree shared with guardian tooling, see
+/// `wormhole_governance`. Malformed decrees fail like the payloads parsed by this crate.
+pub fn deserialize_decree<T: DeserializeGovernancePayload, D: Decree>(
+    buf: &mut &[u8],
+) -> std::result::Result<D, SolitaireError> {
+    let mut c = Cursor::new(buf);
+    T::check_governance_header(&mut c)?;
+    let body = &c.get_ref()[c.position() as usize..];
+    D::decode_body(body).map_err(|e| match e.kind() {
+        ErrorKind::InvalidData => ProgramError::InvalidAccountData.into(),
+        _ => e.into(),
+    })
+}
+
+/// Posted VAA with its payload parsed as `T`. Besides the VAAs the bridge posted, accounts in the
+/// layout of posted VAAs owned by another program are loaded as VAAs that program verified. Those
+/// are only accepted once governance approved the program, which consuming the VAA checks, see
+/// [`PayloadMessage::verify_owner`].
 pub struct PayloadMessage<'b, T: DeserializePayload>(
     Data<'b, PostedVAAData, { AccountState::Initialized }>,
     T,
+    bool,
 );
 
 impl<'a, 'b: 'a, T: DeserializePayload> Peel<'a, 'b> for PayloadMessage<'b, T> {
@@ -113,10 +148,25 @@ impl<'a, 'b: 'a, T: DeserializePayload> Peel<'a, 'b> for PayloadMessage<'b, T> {
     where
         Self: Sized,
     {
+        let bridge = PostedVAAData::default().owner_pubkey(ctx.this)?;
+        let posted = *ctx.info.owner == bridge;
+        let data: Data<'b, PostedVAAData, { AccountState::Initialized }> = match posted {
+            true => Data::peel(ctx)?,
+            false => {
+                if ctx.immutable && ctx.info.is_writable {
+                    return Err(SolitaireError::InvalidMutability(
+                        *ctx.info.key,
+                        ctx.info.is_writable,
+                    ));
+                }
+                let vaa = PostedVAAData::try_from_slice(&ctx.info.data.borrow())?;
+                Data(Box::new(ctx.info.clone()), vaa)
+            }
+        };
+
         // Deserialize wrapped payload
-        let data: Data<'b, PostedVAAData, { AccountState::Initialized }> = Data::peel(ctx)?;
         let payload = DeserializePayload::deserialize(&mut &data.payload[..])?;
-        Ok(PayloadMessage(data, payload))
+        Ok(PayloadMessage(data, payload, posted))
     }
 
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
@@ -139,6 +189,124 @@ impl<'b, T: DeserializePayload> PayloadMessage<'b, T> {
     pub fn info(&self) -> AccountInfo<'b> {
         self.0.info().clone()
     }
+
+    /// Fail unless the bridge posted the VAA, or governance approved the program that verified it
+    /// in place of the bridge and the instruction passes the approval, see
+    /// [`with_verifier`](crate::instructions::with_verifier). Consuming the VAA checks this.
+    pub fn verify_owner(&self, ctx: &ExecutionContext) -> Result<()> {
+        if self.2 {
+            return Ok(());
+        }
+
+        let bridge = PostedVAAData::default().owner_pubkey(ctx.program_id)?;
+        let key = Verifier::<'_, { AccountState::Initialized }>::key(
+            &VerifierDerivationData {
+                program: self.0.info().owner,
+            },
+            &bridge,
+        );
+        let info = ctx
+            .accounts
+            .iter()
+            .find(|info| *info.key == key)
+            .ok_or(UnapprovedVerifier)?;
+        if *info.owner != bridge {
+            return Err(SolitaireError::InvalidOwner(*info.owner));
+        }
+        trace_kv!(
+            "check",
+            kind = "verifier",
+            verifier = self.0.info().owner,
+            approval = info.key
+        );
+        if !VerifierData::try_from_slice(&info.data.borrow())?.approved {
+            return Err(UnapprovedVerifier.into());
+        }
+        Ok(())
+    }
+
+    pub fn emitter(&self) -> EmitterInfo {
+        EmitterInfo {
+            chain: self.0.emitter_chain,
+            address: self.0.emitter_address,
+            sequence: self.0.sequence,
+        }
+    }
+
+    /// Whether the message was emitted by `address` on `chain`, with the address base58 encoded
+    /// like the governance keys programs are built with. Fails if the address does not decode.
+    pub fn try_emitter_eq(&self, chain: u16, address: &str) -> Result<bool> {
+        self.emitter().try_is(chain, address)
+    }
+
+    /// Fail if the message was posted after the guardian set that signed it expired, which soft
+    /// guardian set expiry allows, for consumers requiring hard expiry whatever governance selected.
+    /// `signature_set` is the signature set the message was posted with, `guardian_set` the set it
+    /// names. VAAs posted before their submission time was recorded were never accepted this way,
+    /// nor are VAAs another program verified, which carry no signature set of the bridge.
+    pub fn verify_hard_expiry(
+        &self,
+        signature_set: &AccountInfo,
+        guardian_set: &AccountInfo,
+    ) -> Result<()> {
+        if !self.2 {
+            return Err(InvalidSignatureSet.into());
+        }
+        let bridge = self.0.info().owner;
+        if *signature_set.key != self.0.vaa_signature_account || signature_set.owner != bridge {
+            return Err(InvalidSignatureSet.into());
+        }
+        let signature_set = SignatureSetData::try_from_slice(&signature_set.data.borrow())?;
+
+        let key = GuardianSet::<'_, { AccountState::Initialized }>::key(
+            &GuardianSetDerivationData {
+                index: signature_set.guardian_set_index,
+            },
+            bridge,
+        );
+        if *guardian_set.key != key || guardian_set.owner != bridge {
+            return Err(GuardianSetMismatch.into());
+        }
+        let guardian_set = GuardianSetData::try_from_slice(&guardian_set.data.borrow())?;
+
+        if guardian_set.expiration_time != 0
+            && self.0.submission_time > guardian_set.expiration_time
+        {
+            return Err(PostVAAGuardianSetExpired.into());
+        }
+        Ok(())
+    }
+}
+
+/// Emitter of a posted message and the message's position in its sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EmitterInfo {
+    pub chain: u16,
+    pub address: ForeignAddress,
+    pub sequence: u64,
+}
+
+impl EmitterInfo {
+    /// Whether this is the emitter `address` on `chain`.
+    pub fn is(&self, chain: u16, address: &ForeignAddress) -> bool {
+        self.chain == chain && self.address == *address
+    }
+
+    /// Like [`EmitterInfo::is`], with the address base58 encoded.
+    pub fn try_is(&self, chain: u16, address: &str) -> Result<bool> {
+        let address = Pubkey::from_str(address).map_err(|_| ProgramError::InvalidArgument)?;
+        Ok(self.is(chain, &address.to_bytes()))
+    }
+}
+
+impl From<&PostVAAData> for EmitterInfo {
+    fn from(vaa: &PostVAAData) -> Self {
+        EmitterInfo {
+            chain: vaa.emitter_chain,
+            address: vaa.emitter_address,
+            sequence: vaa.sequence,
+        }
+    }
 }
 
 pub struct SignatureItem {
@@ -170,57 +338,40 @@ pub struct VAA {
 }
 
 impl VAA {
-    pub const HEADER_LEN: usize = 6;
-    pub const SIGNATURE_LEN: usize = 66;
+    pub const HEADER_LEN: usize = Vaa::HEADER_LEN;
+    pub const SIGNATURE_LEN: usize = Vaa::SIGNATURE_LEN;
 
     pub fn deserialize(data: &[u8]) -> std::result::Result<VAA, std::io::Error> {
-        let mut rdr = Cursor::new(data);
-
-        let version = rdr.read_u8()?;
-        let guardian_set_index = rdr.read_u32::<BigEndian>()?;
-
-        let len_sig = rdr.read_u8()?;
-        let mut signatures: Vec<VAASignature> = Vec::with_capacity(len_sig as usize);
-        for _i in 0..len_sig {
-            let guardian_index = rdr.read_u8()?;
-            let mut signature_data = [0u8; 65];
-            rdr.read_exact(&mut signature_data)?;
-            let signature = signature_data.to_vec();
-
-            signatures.push(VAASignature {
-                guardian_index,
-                signature,
-            });
-        }
-
-        let timestamp = rdr.read_u32::<BigEndian>()?;
-        let nonce = rdr.read_u32::<BigEndian>()?;
-        let emitter_chain = rdr.read_u16::<BigEndian>()?;
-
-        let mut emitter_address = [0u8; 32];
-        rdr.read_exact(&mut emitter_address)?;
-
-        let sequence = rdr.read_u64::<BigEndian>()?;
-        let consistency_level = rdr.read_u8()?;
-
-        let mut payload = Vec::new();
-        rdr.read_to_end(&mut payload)?;
+        let vaa = Vaa::parse(data).map_err(truncated)?;
+        let body = vaa.parse_body().map_err(truncated)?;
 
         Ok(VAA {
-            version,
-            guardian_set_index,
-            signatures,
-            timestamp,
-            nonce,
-            emitter_chain,
-            emitter_address,
-            sequence,
-            consistency_level,
-            payload,
+            version: vaa.version,
+            guardian_set_index: vaa.guardian_set_index,
+            signatures: vaa
+                .signatures()
+                .map(|signature| VAASignature {
+                    guardian_index: signature.guardian_index,
+                    signature: signature.signature.to_vec(),
+                })
+                .collect(),
+            timestamp: body.timestamp,
+            nonce: body.nonce,
+            emitter_chain: body.emitter_chain,
+            emitter_address: *body.emitter_address,
+            sequence: body.sequence,
+            consistency_level: body.consistency_level,
+            payload: body.payload.to_vec(),
         })
     }
 }
 
+/// VAA parsing shared with `wormhole_vaa` only fails on truncated input, reported as the IO error
+/// reading past the end would have been.
+fn truncated(_: wormhole_vaa::Error) -> std::io::Error {
+    ErrorKind::UnexpectedEof.into()
+}
+
 impl From<VAA> for PostVAAData {
     fn from(vaa: VAA) -> Self {
         PostVAAData {
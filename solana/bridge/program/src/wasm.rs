@@ -40,6 +40,7 @@ use crate::{
         upgrade_contract,
         upgrade_guardian_set,
         verify_signatures,
+        with_version_registry,
     },
     types::{
         ConsistencyLevel,
@@ -51,6 +52,7 @@ use crate::{
     ClaimDerivationData,
     PostVAAData,
     VerifySignaturesData,
+    MAX_LEN_GUARDIAN_KEYS,
 };
 use byteorder::LittleEndian;
 use wasm_bindgen::prelude::*;
@@ -231,6 +233,10 @@ pub fn upgrade_contract_ix(
         spill,
         vaa.sequence,
     );
+    let ix = match payload.version {
+        Some(version) if !version.is_empty() => with_version_registry(ix),
+        _ => ix,
+    };
     return JsValue::from_serde(&ix).unwrap();
 }
 
@@ -270,10 +276,27 @@ pub fn verify_signatures_ix(
         h.finalize().into()
     };
 
+    // Every transaction verifies up to 7 signatures, of guardians within a window of
+    // MAX_LEN_GUARDIAN_KEYS starting at the first signer.
+    let mut chunks: Vec<&[SignatureItem]> = Vec::new();
+    let mut start = 0;
+    for end in 1..=signature_items.len() {
+        let window_start = signature_items[start].index as usize;
+        let window = window_start..window_start + MAX_LEN_GUARDIAN_KEYS;
+        if end == signature_items.len()
+            || end - start == 7
+            || !window.contains(&(signature_items[end].index as usize))
+        {
+            chunks.push(&signature_items[start..end]);
+            start = end;
+        }
+    }
+
     let mut verify_txs: Vec<Vec<Instruction>> = Vec::new();
-    for (_tx_index, chunk) in signature_items.chunks(7).enumerate() {
+    for chunk in chunks {
         let mut secp_payload = Vec::new();
-        let mut signature_status = [-1i8; 19];
+        let mut signature_status = [-1i8; MAX_LEN_GUARDIAN_KEYS];
+        let signer_offset = chunk[0].index;
 
         let data_offset = 1 + chunk.len() * 11;
         let message_offset = data_offset + chunk.len() * 85;
@@ -298,7 +321,7 @@ pub fn verify_signatures_ix(
                 .write_u16::<LittleEndian>(body_hash.len() as u16)
                 .unwrap();
             secp_payload.write_u8(0).unwrap();
-            signature_status[s.index as usize] = i as i8;
+            signature_status[(s.index - signer_offset) as usize] = i as i8;
         }
 
         // Write signatures and addresses
@@ -318,6 +341,8 @@ pub fn verify_signatures_ix(
 
         let payload = VerifySignaturesData {
             signers: signature_status,
+            hash: Some(body_hash),
+            signer_offset,
         };
 
         let verify_ix = match verify_signatures(
@@ -337,6 +362,12 @@ pub fn verify_signatures_ix(
     JsValue::from_serde(&verify_txs).unwrap()
 }
 
+#[wasm_bindgen]
+pub fn compute_unit_limit(data: Vec<u8>) -> Option<u32> {
+    let instruction = Instruction::new_with_bytes(Pubkey::default(), &data, vec![]);
+    crate::instructions::compute_unit_limit(&instruction)
+}
+
 #[wasm_bindgen]
 pub fn guardian_set_address(bridge: String, index: u32) -> Vec<u8> {
     let program_id = Pubkey::from_str(bridge.as_str()).unwrap();
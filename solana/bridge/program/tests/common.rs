@@ -1,6 +1,7 @@
 use borsh::BorshDeserialize;
 use byteorder::{
     BigEndian,
+    LittleEndian,
     WriteBytesExt,
 };
 
@@ -10,41 +11,65 @@ use libsecp256k1::{
 };
 use sha3::Digest;
 use solana_program::{
-    instruction::Instruction,
+    bpf_loader_upgradeable,
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
     pubkey::Pubkey,
+    rent::Rent,
     system_instruction,
 };
 use solana_program_test::{
     BanksClient,
     BanksClientError,
     ProgramTest,
+    ProgramTestContext,
 };
 use solana_sdk::{
+    account::Account,
     commitment_config::CommitmentLevel,
+    instruction::InstructionError,
     secp256k1_instruction::new_secp256k1_instruction,
     signature::{
         Keypair,
         Signer,
     },
     signers::Signers,
-    transaction::Transaction,
+    transaction::{
+        Transaction,
+        TransactionError,
+    },
 };
 use std::{
+    convert::TryInto,
     env,
     io::{
         Cursor,
         Write,
     },
+    str::FromStr,
     time::SystemTime,
 };
 
 use bridge::{
-    accounts::FeeCollector,
+    accounts::{
+        ClaimDerivationData,
+        DraftMessage,
+        EncodedVaa,
+        FeeCollector,
+    },
     instruction,
     instructions,
     types::ConsistencyLevel,
+    CoreBridgeError,
+    ForgePostedVAAData,
+    MigratedAccount,
     PostVAAData,
+    RegisterEmitterData,
+    VerifySignaturesBatchData,
     VerifySignaturesData,
+    MAX_LEN_GUARDIAN_KEYS,
 };
 
 use solitaire::processors::seeded::Seeded;
@@ -72,15 +97,77 @@ mod helpers {
     use super::*;
     use solana_program_test::processor;
 
+    /// Program ID the bridge is tested at, `BRIDGE_PROGRAM` or the devnet address.
+    pub fn bridge_program() -> Pubkey {
+        env::var("BRIDGE_PROGRAM")
+            .unwrap_or_else(|_| "Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o".to_string())
+            .parse::<Pubkey>()
+            .unwrap()
+    }
+
     /// Initialize the test environment, spins up a solana-test-validator in the background so that
     /// each test has a fresh environment to work within.
     pub async fn setup() -> (BanksClient, Keypair, Pubkey) {
-        let program = env::var("BRIDGE_PROGRAM")
-            .unwrap_or_else(|_| "Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o".to_string())
-            .parse::<Pubkey>()
-            .unwrap();
+        setup_at(bridge_program()).await
+    }
+
+    /// Like [`setup`], deploying the bridge at `program`.
+    pub async fn setup_at(program: Pubkey) -> (BanksClient, Keypair, Pubkey) {
+        setup_with_accounts(program, Vec::new()).await
+    }
+
+    /// Like [`setup_at`], starting with `accounts` created, such as accounts of other programs.
+    pub async fn setup_with_accounts(
+        program: Pubkey,
+        accounts: Vec<(Pubkey, Account)>,
+    ) -> (BanksClient, Keypair, Pubkey) {
+        let mut builder = ProgramTest::new("bridge", program, processor!(instruction::solitaire));
+        for (key, account) in accounts {
+            builder.add_account(key, account);
+        }
+
+        let (client, payer, _) = builder.start().await;
+
+        (client, payer, program)
+    }
+
+    /// Like [`setup`], keeping the test context so that the clock can be moved.
+    pub async fn setup_with_context() -> (ProgramTestContext, Pubkey) {
+        let program = bridge_program();
         let builder = ProgramTest::new("bridge", program, processor!(instruction::solitaire));
 
+        (builder.start_with_context().await, program)
+    }
+
+    /// Like [`setup`], with a second deployment of the bridge under `fork`. The program data of the
+    /// fork names `deploy_authority` as its upgrade authority, as for a fresh deployment.
+    pub async fn setup_fork(
+        fork: Pubkey,
+        deploy_authority: Pubkey,
+    ) -> (BanksClient, Keypair, Pubkey) {
+        let program = bridge_program();
+        let mut builder = ProgramTest::new("bridge", program, processor!(instruction::solitaire));
+        builder.add_program("bridge", fork, processor!(instruction::solitaire));
+
+        // Loader state tag, deployment slot and the optional upgrade authority.
+        let mut data = Vec::new();
+        data.write_u32::<LittleEndian>(3).unwrap();
+        data.write_u64::<LittleEndian>(0).unwrap();
+        data.push(1);
+        data.extend_from_slice(deploy_authority.as_ref());
+        let (program_data, _) =
+            Pubkey::find_program_address(&[fork.as_ref()], &bpf_loader_upgradeable::id());
+        builder.add_account(
+            program_data,
+            Account {
+                lamports: Rent::default().minimum_balance(data.len()),
+                data,
+                owner: bpf_loader_upgradeable::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+
         let (client, payer, _) = builder.start().await;
 
         (client, payer, program)
@@ -95,6 +182,17 @@ mod helpers {
         T::try_from_slice(&account.data).unwrap()
     }
 
+    /// Decode the core bridge error a transaction failed with, if any.
+    pub fn bridge_error(result: Result<(), BanksClientError>) -> Option<CoreBridgeError> {
+        match result {
+            Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+                _,
+                InstructionError::Custom(code),
+            ))) => CoreBridgeError::from_code(code),
+            _ => None,
+        }
+    }
+
     /// Fetch account balance
     pub async fn get_account_balance(client: &mut BanksClient, account: Pubkey) -> u64 {
         client.get_account(account).await.unwrap().unwrap().lamports
@@ -135,6 +233,31 @@ mod helpers {
         sequence: u64,
         guardian_set_index: u32,
         emitter_chain: u16,
+    ) -> (PostVAAData, [u8; 32], [u8; 32]) {
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as u32;
+        generate_vaa_at(
+            emitter,
+            data,
+            nonce,
+            sequence,
+            guardian_set_index,
+            emitter_chain,
+            timestamp,
+        )
+    }
+
+    /// Like [`generate_vaa`], for a VAA issued at `timestamp`.
+    pub fn generate_vaa_at(
+        emitter: &Keypair,
+        data: Vec<u8>,
+        nonce: u32,
+        sequence: u64,
+        guardian_set_index: u32,
+        emitter_chain: u16,
+        timestamp: u32,
     ) -> (PostVAAData, [u8; 32], [u8; 32]) {
         let vaa = PostVAAData {
             version: 0,
@@ -145,10 +268,7 @@ mod helpers {
             emitter_address: emitter.pubkey().to_bytes(),
             sequence,
             payload: data,
-            timestamp: SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_secs() as u32,
+            timestamp,
             nonce,
             consistency_level: ConsistencyLevel::Confirmed as u8,
         };
@@ -207,6 +327,29 @@ mod helpers {
         .await
     }
 
+    pub async fn repair_derivations(
+        client: &mut BanksClient,
+        program: Pubkey,
+        payer: &Keypair,
+        deploy_authority: &Keypair,
+        source_program: Pubkey,
+        guardian_set_index: u32,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer, deploy_authority],
+            &[instructions::repair_derivations(
+                program,
+                deploy_authority.pubkey(),
+                source_program,
+                guardian_set_index,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn post_message(
         client: &mut BanksClient,
@@ -295,6 +438,87 @@ mod helpers {
         .await
     }
 
+    /// Post a message into the account derived from the emitter and `seed`, returning its key.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn post_message_deterministic(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        emitter: &Keypair,
+        seed: [u8; 32],
+        nonce: u32,
+        data: Vec<u8>,
+        fee: u64,
+    ) -> Result<Pubkey, BanksClientError> {
+        let fee_collector = FeeCollector::<'_>::key(None, program);
+
+        let instruction = instructions::post_message_deterministic(
+            *program,
+            payer.pubkey(),
+            emitter.pubkey(),
+            seed,
+            nonce,
+            data,
+            ConsistencyLevel::Confirmed,
+        )
+        .unwrap();
+        let message = instruction.accounts[1].pubkey;
+
+        execute(
+            client,
+            payer,
+            &[payer, emitter],
+            &[
+                system_instruction::transfer(&payer.pubkey(), &fee_collector, fee),
+                instruction,
+            ],
+            CommitmentLevel::Processed,
+        )
+        .await?;
+
+        Ok(message)
+    }
+
+    pub async fn enable_strict_nonce(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        emitter: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let instruction =
+            instructions::enable_strict_nonce(*program, payer.pubkey(), emitter.pubkey()).unwrap();
+
+        execute(
+            client,
+            payer,
+            &[payer, emitter],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn register_emitter(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        emitter: &Keypair,
+        data: RegisterEmitterData,
+    ) -> Result<(), BanksClientError> {
+        let instruction =
+            instructions::register_emitter(*program, payer.pubkey(), emitter.pubkey(), data)
+                .unwrap();
+
+        execute(
+            client,
+            payer,
+            &[payer, emitter],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     pub async fn verify_signatures(
         client: &mut BanksClient,
         program: &Pubkey,
@@ -302,30 +526,86 @@ mod helpers {
         body: [u8; 32],
         secret_keys: &[SecretKey],
         guardian_set_version: u32,
+    ) -> Result<Pubkey, BanksClientError> {
+        verify_signatures_with_hash(
+            client,
+            program,
+            payer,
+            body,
+            None,
+            secret_keys,
+            guardian_set_version,
+        )
+        .await
+    }
+
+    /// Verify signatures over `body`, passing `hash` as the precomputed body hash.
+    pub async fn verify_signatures_with_hash(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        body: [u8; 32],
+        hash: Option<[u8; 32]>,
+        secret_keys: &[SecretKey],
+        guardian_set_version: u32,
+    ) -> Result<Pubkey, BanksClientError> {
+        verify_signatures_during_rotation(
+            client,
+            program,
+            payer,
+            body,
+            hash,
+            secret_keys,
+            guardian_set_version,
+            None,
+        )
+        .await
+    }
+
+    /// Verify signatures over `body`, additionally passing the guardian set `other_version` as
+    /// clients unsure whether a rotation took effect do.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn verify_signatures_during_rotation(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        body: [u8; 32],
+        hash: Option<[u8; 32]>,
+        secret_keys: &[SecretKey],
+        guardian_set_version: u32,
+        other_version: Option<u32>,
     ) -> Result<Pubkey, BanksClientError> {
         let signature_set = Keypair::new();
         let tx_signers = [payer, &signature_set];
         // Push Secp256k1 instructions for each signature we want to verify.
         for (i, key) in secret_keys.iter().enumerate() {
-            // Set this signers signature position as present at 0.
-            let mut signers = [-1; 19];
-            signers[i] = 0;
+            // Set this signers signature position as present at 0, in the window of guardians
+            // starting at `signer_offset`.
+            let signer_offset = i - i % MAX_LEN_GUARDIAN_KEYS;
+            let mut signers = [-1; MAX_LEN_GUARDIAN_KEYS];
+            signers[i - signer_offset] = 0;
+
+            let mut instruction = instructions::verify_signatures(
+                *program,
+                payer.pubkey(),
+                guardian_set_version,
+                signature_set.pubkey(),
+                VerifySignaturesData {
+                    signers,
+                    hash,
+                    signer_offset: signer_offset as u8,
+                },
+            )
+            .unwrap();
+            if let Some(other_version) = other_version {
+                instruction = instructions::with_guardian_set(instruction, other_version);
+            }
 
             execute(
                 client,
                 payer,
                 &tx_signers,
-                &[
-                    new_secp256k1_instruction(key, &body),
-                    instructions::verify_signatures(
-                        *program,
-                        payer.pubkey(),
-                        guardian_set_version,
-                        signature_set.pubkey(),
-                        VerifySignaturesData { signers },
-                    )
-                    .unwrap(),
-                ],
+                &[new_secp256k1_instruction(key, &body), instruction],
                 CommitmentLevel::Processed,
             )
             .await?;
@@ -334,6 +614,188 @@ mod helpers {
         Ok(signature_set.pubkey())
     }
 
+    /// Secp256k1 instruction checking signatures by `key` over each of `messages`, as the first
+    /// instruction of its transaction.
+    pub fn new_secp256k1_batch_instruction(key: &SecretKey, messages: &[[u8; 32]]) -> Instruction {
+        let signatures: Vec<_> = messages.iter().map(|message| (key, *message)).collect();
+        new_secp256k1_signatures_instruction(&signatures)
+    }
+
+    /// Secp256k1 instruction checking each signature by a key over its message, in order, as the
+    /// first instruction of its transaction.
+    pub fn new_secp256k1_signatures_instruction(
+        signatures: &[(&SecretKey, [u8; 32])],
+    ) -> Instruction {
+        const OFFSETS_LEN: usize = 11;
+        const ENTRY_LEN: usize = 20 + 65 + 32;
+
+        let start = 1 + signatures.len() * OFFSETS_LEN;
+        let mut offsets = vec![signatures.len() as u8];
+        let mut entries = Vec::new();
+        for (i, (key, message)) in signatures.iter().enumerate() {
+            let address = {
+                let mut h = sha3::Keccak256::default();
+                h.write_all(&PublicKey::from_secret_key(key).serialize()[1..])
+                    .unwrap();
+                let key: [u8; 32] = h.finalize().into();
+                key[12..].to_vec()
+            };
+
+            let mut h = sha3::Keccak256::default();
+            h.write_all(message).unwrap();
+            let hash: [u8; 32] = h.finalize().into();
+            let (signature, recovery_id) =
+                libsecp256k1::sign(&libsecp256k1::Message::parse(&hash), key);
+
+            let entry = (start + i * ENTRY_LEN) as u16;
+            offsets.write_u16::<LittleEndian>(entry + 20).unwrap();
+            offsets.write_u8(0).unwrap();
+            offsets.write_u16::<LittleEndian>(entry).unwrap();
+            offsets.write_u8(0).unwrap();
+            offsets.write_u16::<LittleEndian>(entry + 20 + 65).unwrap();
+            offsets.write_u16::<LittleEndian>(32).unwrap();
+            offsets.write_u8(0).unwrap();
+
+            entries.extend_from_slice(&address);
+            entries.extend_from_slice(&signature.serialize());
+            entries.push(recovery_id.serialize());
+            entries.extend_from_slice(message);
+        }
+        offsets.extend(entries);
+
+        Instruction {
+            program_id: solana_sdk::secp256k1_program::id(),
+            accounts: vec![],
+            data: offsets,
+        }
+    }
+
+    /// Serialize `vaa` as guardians sign it, with signatures of the guardians at `signers`.
+    pub fn serialize_signed_vaa(
+        vaa: &PostVAAData,
+        secret_keys: &[SecretKey],
+        signers: &[u8],
+    ) -> Vec<u8> {
+        let body = instructions::serialize_vaa(vaa);
+        let hash = instructions::hash_vaa(vaa);
+        let mut h = sha3::Keccak256::default();
+        h.write_all(&hash).unwrap();
+        let digest: [u8; 32] = h.finalize().into();
+
+        let mut serialized = vec![1];
+        serialized
+            .write_u32::<BigEndian>(vaa.guardian_set_index)
+            .unwrap();
+        serialized.push(signers.len() as u8);
+        for index in signers {
+            let (signature, recovery_id) = libsecp256k1::sign(
+                &libsecp256k1::Message::parse(&digest),
+                &secret_keys[*index as usize],
+            );
+            serialized.push(*index);
+            serialized.extend_from_slice(&signature.serialize());
+            serialized.push(recovery_id.serialize());
+        }
+        serialized.extend(body);
+        serialized
+    }
+
+    /// Verify the serialized `vaa` in one transaction, after checking `signatures`.
+    pub async fn verify_vaa_inline(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        vaa: Vec<u8>,
+        signatures: &[(&SecretKey, [u8; 32])],
+        guardian_set_index: u32,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[
+                new_secp256k1_signatures_instruction(signatures),
+                instructions::verify_vaa_inline(*program, guardian_set_index, vaa),
+            ],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    /// Like `verify_vaa_inline`, leaving out the secp instruction without `signatures`.
+    pub async fn verify_vaa_cached(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        vaa: Vec<u8>,
+        signatures: &[(&SecretKey, [u8; 32])],
+        guardian_set_index: u32,
+    ) -> Result<(), BanksClientError> {
+        let mut instructions = vec![];
+        if !signatures.is_empty() {
+            instructions.push(new_secp256k1_signatures_instruction(signatures));
+        }
+        instructions.push(
+            instructions::verify_vaa_cached(*program, payer.pubkey(), guardian_set_index, vaa)
+                .unwrap(),
+        );
+        execute(
+            client,
+            payer,
+            &[payer],
+            &instructions,
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    /// Verify signatures over each of `bodies` into signature sets of their own, with one batch
+    /// per guardian.
+    pub async fn verify_signatures_batch(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        bodies: &[[u8; 32]],
+        secret_keys: &[SecretKey],
+        guardian_set_version: u32,
+    ) -> Result<Vec<Pubkey>, BanksClientError> {
+        let signature_sets: Vec<Keypair> = bodies.iter().map(|_| Keypair::new()).collect();
+        let keys: Vec<Pubkey> = signature_sets.iter().map(|set| set.pubkey()).collect();
+        let mut tx_signers = vec![payer];
+        tx_signers.extend(signature_sets.iter());
+
+        for (i, key) in secret_keys.iter().enumerate() {
+            // The signature over the body of each set is at its position in the instruction.
+            let signers = (0..bodies.len())
+                .map(|j| {
+                    let mut signers = [-1; MAX_LEN_GUARDIAN_KEYS];
+                    signers[i] = j as i8;
+                    signers
+                })
+                .collect();
+
+            let instruction = instructions::verify_signatures_batch(
+                *program,
+                payer.pubkey(),
+                guardian_set_version,
+                &keys,
+                VerifySignaturesBatchData { signers },
+            )
+            .unwrap();
+
+            execute(
+                client,
+                payer,
+                &tx_signers,
+                &[new_secp256k1_batch_instruction(key, bodies), instruction],
+                CommitmentLevel::Processed,
+            )
+            .await?;
+        }
+
+        Ok(keys)
+    }
+
     pub async fn post_vaa(
         client: &mut BanksClient,
         program: &Pubkey,
@@ -356,29 +818,22 @@ mod helpers {
         .await
     }
 
-    #[allow(clippy::too_many_arguments)]
-    pub async fn upgrade_guardian_set(
+    pub async fn post_vaa_compact(
         client: &mut BanksClient,
         program: &Pubkey,
         payer: &Keypair,
-        payload_message: Pubkey,
-        emitter: Pubkey,
-        old_index: u32,
-        new_index: u32,
-        sequence: u64,
+        signature_set: Pubkey,
+        vaa: PostVAAData,
     ) -> Result<(), BanksClientError> {
         execute(
             client,
             payer,
             &[payer],
-            &[instructions::upgrade_guardian_set(
+            &[instructions::post_vaa_compact(
                 *program,
                 payer.pubkey(),
-                payload_message,
-                emitter,
-                old_index,
-                new_index,
-                sequence,
+                signature_set,
+                vaa,
             )],
             CommitmentLevel::Processed,
         )
@@ -386,27 +841,27 @@ mod helpers {
     }
 
     #[allow(clippy::too_many_arguments)]
-    pub async fn upgrade_contract(
+    pub async fn upgrade_guardian_set(
         client: &mut BanksClient,
         program: &Pubkey,
         payer: &Keypair,
         payload_message: Pubkey,
         emitter: Pubkey,
-        new_contract: Pubkey,
-        spill: Pubkey,
+        old_index: u32,
+        new_index: u32,
         sequence: u64,
     ) -> Result<(), BanksClientError> {
         execute(
             client,
             payer,
             &[payer],
-            &[instructions::upgrade_contract(
+            &[instructions::upgrade_guardian_set(
                 *program,
                 payer.pubkey(),
                 payload_message,
                 emitter,
-                new_contract,
-                spill,
+                old_index,
+                new_index,
                 sequence,
             )],
             CommitmentLevel::Processed,
@@ -414,11 +869,64 @@ mod helpers {
         .await
     }
 
-    pub async fn set_fees(
+    pub async fn replay_governance(
         client: &mut BanksClient,
         program: &Pubkey,
         payer: &Keypair,
-        message: Pubkey,
+        signature_set: Pubkey,
+        vaa: PostVAAData,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::replay_governance(
+                *program,
+                payer.pubkey(),
+                signature_set,
+                vaa,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn upgrade_contract(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        payload_message: Pubkey,
+        emitter: Pubkey,
+        new_contract: Pubkey,
+        spill: Pubkey,
+        sequence: u64,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::with_version_registry(
+                instructions::upgrade_contract(
+                    *program,
+                    payer.pubkey(),
+                    payload_message,
+                    emitter,
+                    new_contract,
+                    spill,
+                    sequence,
+                ),
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn set_fees(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
         emitter: Pubkey,
         sequence: u64,
     ) -> Result<(), BanksClientError> {
@@ -438,6 +946,32 @@ mod helpers {
         .await
     }
 
+    pub async fn set_guardian_weights(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+        guardian_set_index: u32,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_guardian_weights(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+                guardian_set_index,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     pub async fn transfer_fees(
         client: &mut BanksClient,
         program: &Pubkey,
@@ -463,4 +997,1144 @@ mod helpers {
         )
         .await
     }
+
+    /// Legacy SPL token program and its wrapped SOL mint, loaded by `ProgramTest`.
+    pub fn spl_token() -> (Pubkey, Pubkey) {
+        (
+            Pubkey::from_str("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA").unwrap(),
+            Pubkey::from_str("So11111111111111111111111111111111111111112").unwrap(),
+        )
+    }
+
+    /// Create `account` as a wrapped SOL token account owned by the payer.
+    pub async fn create_wrapped_sol_account(
+        client: &mut BanksClient,
+        payer: &Keypair,
+        account: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        create_wrapped_sol_account_for(client, payer, account, &payer.pubkey()).await
+    }
+
+    /// Create `account` as a wrapped SOL token account owned by `owner`.
+    pub async fn create_wrapped_sol_account_for(
+        client: &mut BanksClient,
+        payer: &Keypair,
+        account: &Keypair,
+        owner: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        const TOKEN_ACCOUNT_LEN: usize = 165;
+        const INITIALIZE_ACCOUNT_2: u8 = 16;
+
+        let (token_program, native_mint) = spl_token();
+        let mut data = vec![INITIALIZE_ACCOUNT_2];
+        data.extend_from_slice(owner.as_ref());
+        execute(
+            client,
+            payer,
+            &[payer, account],
+            &[
+                system_instruction::create_account(
+                    &payer.pubkey(),
+                    &account.pubkey(),
+                    Rent::default().minimum_balance(TOKEN_ACCOUNT_LEN),
+                    TOKEN_ACCOUNT_LEN as u64,
+                    &token_program,
+                ),
+                Instruction::new_with_bytes(
+                    token_program,
+                    &data,
+                    vec![
+                        AccountMeta::new(account.pubkey(), false),
+                        AccountMeta::new_readonly(native_mint, false),
+                        AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+                    ],
+                ),
+            ],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    /// Wrap `lamports` into the wrapped SOL token account `account` by syncing it.
+    pub fn wrap_sol(payer: &Pubkey, account: &Pubkey, lamports: u64) -> Vec<Instruction> {
+        const SYNC_NATIVE: u8 = 17;
+
+        let (token_program, _) = spl_token();
+        vec![
+            system_instruction::transfer(payer, account, lamports),
+            Instruction::new_with_bytes(
+                token_program,
+                &[SYNC_NATIVE],
+                vec![AccountMeta::new(*account, false)],
+            ),
+        ]
+    }
+
+    /// Transfer `amount` tokens of the legacy token program from `from`, owned by `owner`.
+    pub fn token_transfer(from: &Pubkey, to: &Pubkey, owner: &Pubkey, amount: u64) -> Instruction {
+        const TRANSFER: u8 = 3;
+
+        let (token_program, _) = spl_token();
+        let mut data = vec![TRANSFER];
+        data.extend_from_slice(&amount.to_le_bytes());
+        Instruction::new_with_bytes(
+            token_program,
+            &data,
+            vec![
+                AccountMeta::new(*from, false),
+                AccountMeta::new(*to, false),
+                AccountMeta::new_readonly(*owner, true),
+            ],
+        )
+    }
+
+    pub fn token_approve(
+        account: &Pubkey,
+        delegate: &Pubkey,
+        owner: &Pubkey,
+        amount: u64,
+    ) -> Instruction {
+        const APPROVE: u8 = 4;
+
+        let (token_program, _) = spl_token();
+        let mut data = vec![APPROVE];
+        data.extend_from_slice(&amount.to_le_bytes());
+        Instruction::new_with_bytes(
+            token_program,
+            &data,
+            vec![
+                AccountMeta::new(*account, false),
+                AccountMeta::new_readonly(*delegate, false),
+                AccountMeta::new_readonly(*owner, true),
+            ],
+        )
+    }
+
+    /// Token balance of an SPL token account.
+    pub async fn get_token_balance(client: &mut BanksClient, account: Pubkey) -> u64 {
+        let data = client.get_account(account).await.unwrap().unwrap().data;
+        u64::from_le_bytes(data[64..72].try_into().unwrap())
+    }
+
+    pub async fn transfer_fees_to_token_account(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        recipient: Pubkey,
+        sequence: u64,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::with_token_program(
+                instructions::transfer_fees(
+                    *program,
+                    payer.pubkey(),
+                    message,
+                    emitter,
+                    sequence,
+                    recipient,
+                ),
+                spl_token().0,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn announce_governance(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        digest: [u8; 32],
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::announce_governance(
+                *program,
+                payer.pubkey(),
+                digest,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn queue_governance(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        digest: [u8; 32],
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::queue_governance(
+                *program,
+                payer.pubkey(),
+                digest,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn initiate_migration(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::initiate_migration(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn set_guardian_set_floor(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_guardian_set_floor(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn confirm_guardian_set_downgrade(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::confirm_guardian_set_downgrade(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn set_fee_burn(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_fee_burn(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn burn_fees(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::burn_fees(*program)],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    /// Allocate `encoded_vaa` for a VAA of `vaa_len` bytes and start uploading it, with `payer` as
+    /// the write authority.
+    pub async fn init_encoded_vaa(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        encoded_vaa: &Keypair,
+        vaa_len: usize,
+    ) -> Result<(), BanksClientError> {
+        let len = EncodedVaa::HEADER_LEN + vaa_len;
+        execute(
+            client,
+            payer,
+            &[payer, encoded_vaa],
+            &[
+                system_instruction::create_account(
+                    &payer.pubkey(),
+                    &encoded_vaa.pubkey(),
+                    Rent::default().minimum_balance(len),
+                    len as u64,
+                    program,
+                ),
+                instructions::init_encoded_vaa(*program, payer.pubkey(), encoded_vaa.pubkey()),
+            ],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn write_encoded_vaa(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        write_authority: &Keypair,
+        encoded_vaa: &Pubkey,
+        index: u32,
+        data: Vec<u8>,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer, write_authority],
+            &[instructions::write_encoded_vaa(
+                *program,
+                write_authority.pubkey(),
+                *encoded_vaa,
+                index,
+                data,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn finalize_encoded_vaa(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        encoded_vaa: &Pubkey,
+        signature_set: &Pubkey,
+        guardian_set_index: u32,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::finalize_encoded_vaa(
+                *program,
+                payer.pubkey(),
+                *encoded_vaa,
+                *signature_set,
+                guardian_set_index,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    /// Allocate `draft_message` for a payload of `payload_len` bytes and start drafting it from
+    /// `emitter`.
+    pub async fn init_message_v1(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        emitter: &Keypair,
+        draft_message: &Keypair,
+        payload_len: usize,
+    ) -> Result<(), BanksClientError> {
+        let len = DraftMessage::HEADER_LEN + payload_len;
+        execute(
+            client,
+            payer,
+            &[payer, emitter, draft_message],
+            &[
+                system_instruction::create_account(
+                    &payer.pubkey(),
+                    &draft_message.pubkey(),
+                    Rent::default().minimum_balance(len),
+                    len as u64,
+                    program,
+                ),
+                instructions::init_message_v1(*program, emitter.pubkey(), draft_message.pubkey()),
+            ],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn write_message_v1(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        emitter: &Keypair,
+        draft_message: &Pubkey,
+        index: u32,
+        data: Vec<u8>,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer, emitter],
+            &[instructions::write_message_v1(
+                *program,
+                emitter.pubkey(),
+                *draft_message,
+                index,
+                data,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn finalize_message_v1(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        emitter: &Keypair,
+        draft_message: &Pubkey,
+        nonce: u32,
+        fee: u64,
+    ) -> Result<Pubkey, BanksClientError> {
+        let fee_collector = FeeCollector::<'_>::key(None, program);
+        let message = Keypair::new();
+        execute(
+            client,
+            payer,
+            &[payer, emitter, &message],
+            &[
+                system_instruction::transfer(&payer.pubkey(), &fee_collector, fee),
+                instructions::finalize_message_v1(
+                    *program,
+                    payer.pubkey(),
+                    emitter.pubkey(),
+                    message.pubkey(),
+                    *draft_message,
+                    nonce,
+                    ConsistencyLevel::Confirmed,
+                ),
+            ],
+            CommitmentLevel::Processed,
+        )
+        .await?;
+        Ok(message.pubkey())
+    }
+
+    pub async fn attest_sequence_range(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        emitter: &Pubkey,
+        first: u64,
+        last: u64,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::attest_sequence_range(
+                *program,
+                payer.pubkey(),
+                *emitter,
+                first,
+                last,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn close_posted_vaa(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        refund_recipient: &Keypair,
+        signature_set: &Pubkey,
+        hash: [u8; 32],
+        claim: &Pubkey,
+        consumer: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer, refund_recipient],
+            &[instructions::close_posted_vaa(
+                *program,
+                refund_recipient.pubkey(),
+                payer.pubkey(),
+                *signature_set,
+                hash,
+                *claim,
+                *consumer,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn close_signature_set(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        refund_recipient: &Keypair,
+        signature_set: &Pubkey,
+        hash: [u8; 32],
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer, refund_recipient],
+            &[instructions::close_signature_set(
+                *program,
+                refund_recipient.pubkey(),
+                payer.pubkey(),
+                *signature_set,
+                hash,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn validate_upgrade_buffer(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        buffer: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::validate_upgrade_buffer(*program, *buffer)],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn export_guardian_set(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        export: &Keypair,
+        guardian_set_index: u32,
+        signer: bool,
+    ) -> Result<(), BanksClientError> {
+        let signers: &[&Keypair] = if signer { &[payer, export] } else { &[payer] };
+        execute(
+            client,
+            payer,
+            signers,
+            &[instructions::export_guardian_set(
+                *program,
+                export.pubkey(),
+                guardian_set_index,
+                signer,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn render_vaa(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        posted_vaa: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::render_vaa(*program, posted_vaa)],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn event_schemas(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::event_schemas(*program)],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn dry_run_guardian_set_upgrade(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        decree: Vec<u8>,
+        digests: Vec<[u8; 32]>,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::dry_run_guardian_set_upgrade(
+                *program, decree, digests,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn forward_vaa(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        posted_vaa: Pubkey,
+        target_chain: u16,
+        fee: u64,
+    ) -> Result<Pubkey, BanksClientError> {
+        let fee_collector = FeeCollector::<'_>::key(None, program);
+        let message = Keypair::new();
+
+        execute(
+            client,
+            payer,
+            &[payer, &message],
+            &[
+                system_instruction::transfer(&payer.pubkey(), &fee_collector, fee),
+                instructions::forward_vaa(
+                    *program,
+                    payer.pubkey(),
+                    posted_vaa,
+                    message.pubkey(),
+                    0,
+                    target_chain,
+                    ConsistencyLevel::Confirmed,
+                ),
+            ],
+            CommitmentLevel::Processed,
+        )
+        .await?;
+
+        Ok(message.pubkey())
+    }
+
+    pub async fn set_secp256r1_verification(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_secp256r1_verification(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn set_fee_oracle(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_fee_oracle(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn set_guardian_set_expiry(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_guardian_set_expiry(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn set_quorum(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+        guardian_set_index: u32,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_quorum(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+                guardian_set_index,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn set_paused(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_paused(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn set_emission_breaker(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_emission_breaker(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn release_emission_breaker(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+        breaker_emitter: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::release_emission_breaker(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+                breaker_emitter,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn clear_emission_breaker(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        emitter: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer, emitter],
+            &[instructions::clear_emission_breaker(
+                *program,
+                emitter.pubkey(),
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn set_claim_grace_period(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_claim_grace_period(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn close_claim(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        recipient: &Pubkey,
+        claim: ClaimDerivationData,
+        signature_set: &Pubkey,
+        hash: [u8; 32],
+        guardian_set_index: u32,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::close_claim(
+                *program,
+                *recipient,
+                claim,
+                *signature_set,
+                hash,
+                guardian_set_index,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn forge_posted_vaa(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        data: ForgePostedVAAData,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::forge_posted_vaa(
+                *program,
+                payer.pubkey(),
+                data,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn set_token_fee(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+        fee_token_account: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_token_fee(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+                fee_token_account,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn set_fee_exemption(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+        exempted_emitter: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_fee_exemption(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+                exempted_emitter,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn set_claim_consumer(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+        consumer: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_claim_consumer(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+                consumer,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn set_verifier(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+        verifier: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_verifier(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+                verifier,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn set_successor(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_successor(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn forward_to_successor(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        successor: Pubkey,
+        instruction: Instruction,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::forward_to_successor(
+                *program,
+                successor,
+                instruction,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn create_fee_invoice(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: &Keypair,
+        payload_len: u32,
+        expiry_slot: u64,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer, message],
+            &[instructions::create_fee_invoice(
+                *program,
+                payer.pubkey(),
+                message.pubkey(),
+                payload_len,
+                ConsistencyLevel::Confirmed,
+                expiry_slot,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn commit_state(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        guardian_set_index: u32,
+        messages: &[Pubkey],
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::commit_state(
+                *program,
+                payer.pubkey(),
+                guardian_set_index,
+                messages,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn migrate_account(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        successor: Pubkey,
+        account: MigratedAccount,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::migrate_account(*program, successor, account)],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
 }
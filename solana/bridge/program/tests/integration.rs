@@ -1,50 +1,179 @@
+use borsh::BorshDeserialize;
 use libsecp256k1::SecretKey;
 use rand::Rng;
 use solana_program::{
+    bpf_loader_upgradeable::{
+        self,
+        UpgradeableLoaderState,
+    },
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
     pubkey::Pubkey,
     system_instruction,
+    sysvar::clock::Clock,
 };
 use solana_program_test::{
     tokio,
     BanksClient,
 };
 use solana_sdk::{
+    account::Account,
     commitment_config::CommitmentLevel,
+    compute_budget::ComputeBudgetInstruction,
     signature::{
         Keypair,
         Signer,
     },
 };
 use solitaire::{
-    processors::seeded::Seeded,
+    processors::{
+        event::{
+            event_authority,
+            schema_hash,
+            EVENT_IX_TAG,
+        },
+        named::named,
+        seeded::Seeded,
+    },
     AccountState,
+    Event,
 };
 
 use bridge::{
     accounts::{
+        commitment_leaf,
+        commitment_root,
+        message_digest,
+        secp256r1_address,
         Bridge,
+        BridgeConfig,
         BridgeData,
+        Claim,
+        ClaimConsumer,
+        ClaimConsumerData,
+        ClaimConsumerDerivationData,
+        ClaimDerivationData,
+        DigestCache,
+        DigestCacheData,
+        DigestCacheDerivationData,
+        EmitterStats,
+        EmitterStatsData,
+        EmitterStatsDerivationData,
+        EncodedVaa,
         FeeCollector,
+        FeeExemption,
+        FeeExemptionData,
+        FeeExemptionDerivationData,
+        FeeInvoice,
+        FeeInvoiceData,
+        FeeInvoiceDerivationData,
+        FeeOracle,
+        FeeOracleData,
+        GovernanceAnnouncement,
+        GovernanceAnnouncementData,
+        GovernanceAnnouncementDerivationData,
         GuardianSet,
         GuardianSetData,
         GuardianSetDerivationData,
+        GuardianSetDiff,
+        GuardianSetExpiry,
+        GuardianSetExpiryData,
+        GuardianSetExport,
+        GuardianSetFloor,
+        GuardianSetFloorData,
+        GuardianSetWeights,
+        GuardianSetWeightsData,
+        GuardianSetWeightsDerivationData,
+        MessageData,
+        Migration,
+        MigrationData,
+        PendingGovernance,
+        PendingGovernanceData,
+        PendingGovernanceDerivationData,
+        PostedMessage,
+        PostedMessageDerivationData,
         PostedVAA,
+        PostedVAACompact,
+        PostedVAACompactData,
+        PostedVAACompactDerivationData,
         PostedVAAData,
         PostedVAADerivationData,
+        ProcessingStatus,
+        Quorum,
+        Secp256r1Verification,
+        Secp256r1VerificationData,
+        Sequence,
+        SequenceDerivationData,
+        SequenceRangeAttestation,
+        SequenceRangeAttestationData,
+        SequenceRangeDerivationData,
+        SequenceTracker,
         SignatureSetData,
+        StateCommitment,
+        StateCommitmentData,
+        Verifier,
+        VerifierData,
+        VerifierDerivationData,
+        VersionRegistry,
+        VersionRegistryData,
+        FEE_INVOICE_MAX_SLOTS,
+        GOVERNANCE_ANNOUNCEMENT_DELAY,
+        GOVERNANCE_TIMELOCK_DELAY,
+    },
+    api::{
+        event_registry,
+        FieldKind,
+        ForeignAddress,
+    },
+    channel::{
+        self,
+        BorshCodec,
+        Channel,
     },
     instructions,
     types::{
         ConsistencyLevel,
+        GovernancePayloadConfirmGuardianSetDowngrade,
         GovernancePayloadGuardianSetChange,
+        GovernancePayloadMigrate,
+        GovernancePayloadReleaseEmissionBreaker,
+        GovernancePayloadSetClaimConsumer,
+        GovernancePayloadSetClaimGracePeriod,
+        GovernancePayloadSetEmissionBreaker,
+        GovernancePayloadSetFeeBurn,
+        GovernancePayloadSetFeeExemption,
+        GovernancePayloadSetFeeOracle,
+        GovernancePayloadSetGuardianSetExpiry,
+        GovernancePayloadSetGuardianSetFloor,
+        GovernancePayloadSetGuardianWeights,
         GovernancePayloadSetMessageFee,
+        GovernancePayloadSetPaused,
+        GovernancePayloadSetQuorum,
+        GovernancePayloadSetSecp256r1Verification,
+        GovernancePayloadSetSuccessor,
+        GovernancePayloadSetTokenFee,
+        GovernancePayloadSetVerifier,
         GovernancePayloadTransferFees,
         GovernancePayloadUpgrade,
+        PayloadForwardedVAA,
     },
+    CoreBridgeError,
+    DeserializePayload,
+    EmitterInfo,
+    ForgePostedVAAData,
+    MessagePosted,
+    MessagePostedV1,
+    MigratedAccount,
+    RegisterEmitterData,
     SerializeGovernancePayload,
+    VerifySignaturesBatchData,
+    VerifySignaturesData,
 };
 use primitive_types::U256;
 use solana_program::rent::Rent;
+use wormhole_solana_consts::seeds;
 
 mod common;
 
@@ -311,8 +440,7 @@ async fn bridge_messages() {
     }
 }
 
-// Make sure that posting messages with account reuse works and only accepts messages with the same
-// length.
+// Make sure that posting messages with account reuse works, also for messages of other lengths.
 #[tokio::test]
 async fn test_bridge_messages_unreliable() {
     let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
@@ -396,39 +524,129 @@ async fn test_bridge_messages_unreliable() {
         }
     }
 
-    // Make sure that posting a message with a different length fails (<len)
-    let nonce = rand::thread_rng().gen();
-    let message: [u8; 16] = rand::thread_rng().gen();
+    // Make sure that posting messages of other lengths resizes the account, settling its rent with
+    // the payer.
+    for len in [16usize, 128] {
+        let nonce = rand::thread_rng().gen();
+        let message = vec![7u8; len];
+        let sequence = context.seq.next(emitter.pubkey().to_bytes());
 
-    assert!(common::post_message_unreliable(
+        common::post_message_unreliable(
+            client,
+            program,
+            payer,
+            &emitter,
+            &message_key,
+            nonce,
+            message.clone(),
+            10_000,
+        )
+        .await
+        .unwrap();
+
+        let account = client
+            .get_account(message_key.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            account.lamports,
+            Rent::default().minimum_balance(account.data.len())
+        );
+
+        let posted_message: PostedVAAData =
+            common::get_account_data(client, message_key.pubkey()).await;
+        assert_eq!(posted_message.message.sequence, sequence);
+        assert_eq!(posted_message.message.payload, message);
+    }
+}
+
+// Make sure that emitters opting into strict nonces can't publish unreliable messages out of order.
+#[tokio::test]
+async fn test_bridge_messages_unreliable_strict_nonce() {
+    let (ref mut _context, ref mut client, ref payer, ref program) = initialize().await;
+
+    let emitter = Keypair::new();
+    let message_key = Keypair::new();
+
+    // Without strict nonces, any nonce is accepted. Payloads differ so that transactions do too.
+    for nonce in [5, 3] {
+        common::post_message_unreliable(
+            client,
+            program,
+            payer,
+            &emitter,
+            &message_key,
+            nonce,
+            rand::thread_rng().gen::<[u8; 32]>().to_vec(),
+            10_000,
+        )
+        .await
+        .unwrap();
+    }
+
+    // Opting in extends the existing sequence tracker.
+    common::enable_strict_nonce(client, program, payer, &emitter)
+        .await
+        .unwrap();
+    let sequence_key = Sequence::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter.pubkey(),
+        },
+        program,
+    );
+    let sequence: SequenceTracker = common::get_account_data(client, sequence_key).await;
+    assert_eq!(sequence.sequence, 2);
+    assert_eq!(sequence.min_unreliable_nonce, Some(0));
+
+    common::post_message_unreliable(
         client,
         program,
         payer,
         &emitter,
         &message_key,
-        nonce,
-        message.to_vec(),
+        5,
+        rand::thread_rng().gen::<[u8; 32]>().to_vec(),
         10_000,
     )
     .await
-    .is_err());
+    .unwrap();
+    let sequence: SequenceTracker = common::get_account_data(client, sequence_key).await;
+    assert_eq!(sequence.sequence, 3);
+    assert_eq!(sequence.min_unreliable_nonce, Some(6));
 
-    // Make sure that posting a message with a different length fails (>len)
-    let nonce = rand::thread_rng().gen();
-    let message: [u8; 128] = [0u8; 128];
+    // Regressions and repeated nonces are rejected.
+    for nonce in [4, 5] {
+        assert!(common::post_message_unreliable(
+            client,
+            program,
+            payer,
+            &emitter,
+            &message_key,
+            nonce,
+            rand::thread_rng().gen::<[u8; 32]>().to_vec(),
+            10_000,
+        )
+        .await
+        .is_err());
+    }
 
-    assert!(common::post_message_unreliable(
+    common::post_message_unreliable(
         client,
         program,
         payer,
         &emitter,
         &message_key,
-        nonce,
-        message.to_vec(),
+        6,
+        rand::thread_rng().gen::<[u8; 32]>().to_vec(),
         10_000,
     )
     .await
-    .is_err());
+    .unwrap();
+    let posted_message: PostedVAAData =
+        common::get_account_data(client, message_key.pubkey()).await;
+    assert_eq!(posted_message.message.nonce, 6);
+    assert_eq!(posted_message.message.sequence, 3);
 }
 
 #[tokio::test]
@@ -470,6 +688,97 @@ async fn test_bridge_messages_unreliable_do_not_override_reliable() {
     .is_err());
 }
 
+// Make sure that messages posted into accounts derived from the emitter and a seed land at the
+// address clients derive, and that seeds can't be reused.
+#[tokio::test]
+async fn test_bridge_messages_deterministic() {
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+
+    let emitter = Keypair::new();
+    let seed: [u8; 32] = rand::thread_rng().gen();
+    let nonce = rand::thread_rng().gen();
+    let message: [u8; 32] = rand::thread_rng().gen();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    let message_key = common::post_message_deterministic(
+        client,
+        program,
+        payer,
+        &emitter,
+        seed,
+        nonce,
+        message.to_vec(),
+        10_000,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        message_key,
+        PostedMessage::<'_, { AccountState::Uninitialized }>::key(
+            &PostedMessageDerivationData {
+                emitter: &emitter.pubkey(),
+                seed: &seed,
+            },
+            program,
+        )
+    );
+    assert_eq!(
+        message_key,
+        Pubkey::find_program_address(
+            &[
+                seeds::DETERMINISTIC_MESSAGE.as_bytes(),
+                &emitter.pubkey().to_bytes(),
+                &seed,
+            ],
+            program,
+        )
+        .0
+    );
+
+    // Verify on chain Message
+    let posted_message: PostedVAAData = common::get_account_data(client, message_key).await;
+    assert_eq!(posted_message.message.nonce, nonce);
+    assert_eq!(posted_message.message.sequence, sequence);
+    assert_eq!(posted_message.message.payload, message);
+    assert_eq!(
+        posted_message.message.emitter_address,
+        emitter.pubkey().to_bytes()
+    );
+
+    // The message account already exists.
+    assert!(common::post_message_deterministic(
+        client,
+        program,
+        payer,
+        &emitter,
+        seed,
+        nonce,
+        message.to_vec(),
+        10_000,
+    )
+    .await
+    .is_err());
+
+    // Other emitters derive other accounts from the same seed.
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let other_key = common::post_message_deterministic(
+        client,
+        program,
+        payer,
+        &emitter,
+        seed,
+        nonce,
+        message.to_vec(),
+        10_000,
+    )
+    .await
+    .unwrap();
+    assert_ne!(other_key, message_key);
+    let posted_message: PostedVAAData = common::get_account_data(client, other_key).await;
+    assert_eq!(posted_message.message.sequence, sequence);
+}
+
 #[tokio::test]
 async fn bridge_works_after_transfer_fees() {
     // This test aims to ensure that the bridge remains operational after the
@@ -686,6 +995,7 @@ async fn guardian_set_change() {
     let message = GovernancePayloadGuardianSetChange {
         new_guardian_set_index: 1,
         new_guardian_set: new_public_keys.clone(),
+        new_secp256r1_keys: vec![],
     }
     .try_to_vec()
     .unwrap();
@@ -790,6 +1100,21 @@ async fn guardian_set_change() {
     assert_eq!(guardian_set.keys, new_public_keys);
     assert!(guardian_set.creation_time as u64 > now);
 
+    // The logged diff replaces every guardian of the initial set.
+    let diff = GuardianSetDiff::new(&context.public, &guardian_set.keys);
+    assert_eq!(diff.added, new_public_keys);
+    assert_eq!(diff.removed, context.public);
+    assert_eq!(diff.retained, 0);
+
+    // Guardians are matched by key, moving within the set doesn't rotate them.
+    let diff = GuardianSetDiff::new(
+        &[[1u8; 20], [2u8; 20], [3u8; 20]],
+        &[[3u8; 20], [2u8; 20], [4u8; 20]],
+    );
+    assert_eq!(diff.added, vec![[4u8; 20]]);
+    assert_eq!(diff.removed, vec![[1u8; 20]]);
+    assert_eq!(diff.retained, 2);
+
     // Submit the message a second time with a new nonce.
     let nonce = rand::thread_rng().gen();
     let _message_key = common::post_message(
@@ -870,6 +1195,7 @@ async fn guardian_set_change_fails() {
     let message = GovernancePayloadGuardianSetChange {
         new_guardian_set_index: 2,
         new_guardian_set: new_public_keys.clone(),
+        new_secp256r1_keys: vec![],
     }
     .try_to_vec()
     .unwrap();
@@ -901,6 +1227,87 @@ async fn guardian_set_change_fails() {
     .is_err());
 }
 
+#[tokio::test]
+async fn replay_governance() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+
+    let genesis_key = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetDerivationData { index: 0 },
+        program,
+    );
+    let genesis: GuardianSetData = common::get_account_data(client, genesis_key).await;
+
+    // Replay guardian set changes issued before the bridge was deployed, each signed by the set
+    // it replaces.
+    let mut secret_keys = context.secret.clone();
+    for index in 1..=2u32 {
+        let (new_public_keys, new_secret_keys) = common::generate_keys(6);
+        let message = GovernancePayloadGuardianSetChange {
+            new_guardian_set_index: index,
+            new_guardian_set: new_public_keys.clone(),
+            new_secp256r1_keys: vec![],
+        }
+        .try_to_vec()
+        .unwrap();
+
+        let vaa_time = genesis.creation_time - 1000 + index * 100;
+        let (vaa, body, _body_hash) = common::generate_vaa_at(
+            &emitter,
+            message,
+            0,
+            u64::from(index - 1),
+            index - 1,
+            1,
+            vaa_time,
+        );
+        let signature_set =
+            common::verify_signatures(client, program, payer, body, &secret_keys, index - 1)
+                .await
+                .unwrap();
+        common::replay_governance(client, program, payer, signature_set, vaa)
+            .await
+            .unwrap();
+
+        let guardian_set_key = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+            &GuardianSetDerivationData { index },
+            program,
+        );
+        let guardian_set: GuardianSetData =
+            common::get_account_data(client, guardian_set_key).await;
+        assert_eq!(guardian_set.index, index);
+        assert_eq!(guardian_set.keys, new_public_keys);
+        assert_eq!(guardian_set.creation_time, vaa_time);
+
+        secret_keys = new_secret_keys;
+    }
+
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert_eq!(bridge.guardian_set_index, 2);
+
+    // Changes issued since the deployment must go through governance.
+    let (new_public_keys, _new_secret_keys) = common::generate_keys(6);
+    let message = GovernancePayloadGuardianSetChange {
+        new_guardian_set_index: 3,
+        new_guardian_set: new_public_keys,
+        new_secp256r1_keys: vec![],
+    }
+    .try_to_vec()
+    .unwrap();
+    let (vaa, body, _body_hash) = common::generate_vaa(&emitter, message, 0, 2, 2, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &secret_keys, 2)
+        .await
+        .unwrap();
+    assert_eq!(
+        common::bridge_error(
+            common::replay_governance(client, program, payer, signature_set, vaa).await
+        ),
+        Some(CoreBridgeError::GovernanceNotHistorical)
+    );
+}
+
 #[tokio::test]
 async fn set_fees() {
     // Initialize a wormhole bridge on Solana to test with.
@@ -1051,17 +1458,14 @@ async fn set_fees() {
 }
 
 #[tokio::test]
-async fn set_fees_fails() {
-    // Initialize a wormhole bridge on Solana to test with.
+async fn set_fees_named_accounts() {
     let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
-
-    // Use a random key to confirm only the governance key is respected.
-    let emitter = Keypair::new();
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
     let sequence = context.seq.next(emitter.pubkey().to_bytes());
 
     let nonce = rand::thread_rng().gen();
     let message = GovernancePayloadSetMessageFee {
-        fee: U256::from(100u128),
+        fee: U256::from(200u128),
     }
     .try_to_vec()
     .unwrap();
@@ -1087,70 +1491,223 @@ async fn set_fees_fails() {
     common::post_vaa(client, program, payer, signature_set, vaa)
         .await
         .unwrap();
-    assert!(common::set_fees(
-        client,
-        program,
-        payer,
+
+    // Pass the bridge last rather than second.
+    let mut instruction = instructions::set_fees(
+        *program,
+        payer.pubkey(),
         message_key,
         emitter.pubkey(),
         sequence,
+    );
+    let bridge = instruction.accounts.remove(1);
+    instruction.accounts.push(bridge);
+
+    // Out of order accounts fail positionally.
+    assert!(common::execute(
+        client,
+        payer,
+        &[payer],
+        &[instruction.clone()],
+        CommitmentLevel::Processed,
     )
     .await
     .is_err());
-}
-
-#[tokio::test]
-async fn free_fees() {
-    // Initialize a wormhole bridge on Solana to test with.
-    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
-    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
-    let sequence = context.seq.next(emitter.pubkey().to_bytes());
-
-    // Set Fees to 0.
-    let nonce = rand::thread_rng().gen();
-    let message = GovernancePayloadSetMessageFee {
-        fee: U256::from(0u128),
-    }
-    .try_to_vec()
-    .unwrap();
 
-    let message_key = common::post_message(
+    // Named resolution still needs every account.
+    let mut missing = named(instruction.clone());
+    missing.accounts.pop();
+    assert!(common::execute(
         client,
-        program,
         payer,
-        &emitter,
-        None,
-        nonce,
-        message.clone(),
-        10_000,
+        &[payer],
+        &[missing],
+        CommitmentLevel::Processed,
     )
     .await
-    .unwrap();
+    .is_err());
 
-    let (vaa, body, _body_hash) =
-        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
-    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
-        .await
-        .unwrap();
-    common::post_vaa(client, program, payer, signature_set, vaa)
-        .await
-        .unwrap();
-    common::set_fees(
+    // But finds the bridge wherever it is.
+    common::execute(
         client,
-        program,
         payer,
-        message_key,
-        emitter.pubkey(),
-        sequence,
+        &[payer],
+        &[named(instruction)],
+        CommitmentLevel::Processed,
     )
     .await
     .unwrap();
 
-    // Fetch Bridge to check on-state value.
     let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
-    let fee_collector = FeeCollector::key(None, program);
     let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
-    assert_eq!(bridge.config.fee, 0);
+    assert_eq!(bridge.config.fee, 200);
+}
+
+// Guardians find the message account by position, so instructions posting messages refuse named
+// account resolution rather than posting messages guardians miss.
+#[tokio::test]
+async fn post_message_named_accounts() {
+    let (ref mut _context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::new();
+    let message = Keypair::new();
+    let fee_collector = FeeCollector::<'_>::key(None, program);
+
+    let instructions = vec![
+        instructions::post_message(
+            *program,
+            payer.pubkey(),
+            emitter.pubkey(),
+            message.pubkey(),
+            0,
+            b"named".to_vec(),
+            ConsistencyLevel::Confirmed,
+        )
+        .unwrap(),
+        instructions::post_message_unreliable(
+            *program,
+            payer.pubkey(),
+            emitter.pubkey(),
+            message.pubkey(),
+            0,
+            b"named".to_vec(),
+            ConsistencyLevel::Confirmed,
+        )
+        .unwrap(),
+    ];
+    for instruction in instructions {
+        let result = common::execute(
+            client,
+            payer,
+            &[payer, &emitter, &message],
+            &[
+                system_instruction::transfer(&payer.pubkey(), &fee_collector, 10_000),
+                named(instruction),
+            ],
+            CommitmentLevel::Processed,
+        )
+        .await;
+        assert_eq!(
+            common::bridge_error(result),
+            Some(CoreBridgeError::NamedAccountsUnsupported)
+        );
+    }
+
+    // The same message posts positionally.
+    common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        Some(&message),
+        0,
+        b"named".to_vec(),
+        10_000,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn set_fees_fails() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+
+    // Use a random key to confirm only the governance key is respected.
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetMessageFee {
+        fee: U256::from(100u128),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    assert!(common::set_fees(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        sequence,
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn free_fees() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    // Set Fees to 0.
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetMessageFee {
+        fee: U256::from(0u128),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    common::set_fees(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        sequence,
+    )
+    .await
+    .unwrap();
+
+    // Fetch Bridge to check on-state value.
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
+    let fee_collector = FeeCollector::key(None, program);
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert_eq!(bridge.config.fee, 0);
 
     // Check that posting a new message is free.
     let account_balance = common::get_account_balance(client, fee_collector).await;
@@ -1287,18 +1844,21 @@ async fn transfer_fees() {
 }
 
 #[tokio::test]
-async fn transfer_fees_fails() {
+async fn transfer_fees_to_wrapped_sol() {
     // Initialize a wormhole bridge on Solana to test with.
     let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
-
-    // Use an invalid emitter.
-    let emitter = Keypair::new();
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
     let sequence = context.seq.next(emitter.pubkey().to_bytes());
 
+    let recipient = Keypair::new();
+    common::create_wrapped_sol_account(client, payer, &recipient)
+        .await
+        .unwrap();
+
     let nonce = rand::thread_rng().gen();
     let message = GovernancePayloadTransferFees {
         amount: 100u128.into(),
-        to: payer.pubkey().to_bytes(),
+        to: recipient.pubkey().to_bytes(),
     }
     .try_to_vec()
     .unwrap();
@@ -1330,46 +1890,80 @@ async fn transfer_fees_fails() {
 
     let previous_balance = common::get_account_balance(client, fee_collector).await;
 
+    // The token balance only follows the lamports received with the token program to sync it.
     assert!(common::transfer_fees(
         client,
         program,
         payer,
         message_key,
         emitter.pubkey(),
-        payer.pubkey(),
+        recipient.pubkey(),
         sequence,
     )
     .await
     .is_err());
+
+    common::transfer_fees_to_token_account(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        recipient.pubkey(),
+        sequence,
+    )
+    .await
+    .unwrap();
     assert_eq!(
         common::get_account_balance(client, fee_collector).await,
-        previous_balance
+        previous_balance - 100
+    );
+    assert_eq!(
+        common::get_token_balance(client, recipient.pubkey()).await,
+        100
     );
 }
 
 #[tokio::test]
-async fn transfer_too_much() {
-    // Initialize a wormhole bridge on Solana to test with.
+async fn token_fee() {
     let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
-    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
-    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let governance = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
+    let fee_collector = FeeCollector::key(None, program);
+    let (_, native_mint) = common::spl_token();
+
+    // Token fees are collected by a token account of the fee collector, the emitter pays them
+    // from a token account of its own.
+    let fee_tokens = Keypair::new();
+    common::create_wrapped_sol_account_for(client, payer, &fee_tokens, &fee_collector)
+        .await
+        .unwrap();
+    let treasury = Keypair::new();
+    common::create_wrapped_sol_account(client, payer, &treasury)
+        .await
+        .unwrap();
+    common::execute(
+        client,
+        payer,
+        &[payer],
+        &common::wrap_sol(&payer.pubkey(), &treasury.pubkey(), 10_000),
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
 
     let nonce = rand::thread_rng().gen();
-    let message = GovernancePayloadTransferFees {
-        amount: 100_000_000_000u64.into(),
-        to: payer.pubkey().to_bytes(),
+    let message = GovernancePayloadSetTokenFee {
+        mint: native_mint,
+        fee: 500u128.into(),
     }
     .try_to_vec()
     .unwrap();
-
-    // Fetch accounts for chain state checking.
-    let fee_collector = FeeCollector::key(None, program);
-
     let message_key = common::post_message(
         client,
         program,
         payer,
-        &emitter,
+        &governance,
         None,
         nonce,
         message.clone(),
@@ -1377,9 +1971,8 @@ async fn transfer_too_much() {
     )
     .await
     .unwrap();
-
-    let (vaa, body, _body_hash) =
-        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let sequence = context.seq.next(governance.pubkey().to_bytes());
+    let (vaa, body, _) = common::generate_vaa(&governance, message, nonce, sequence, 0, 1);
     let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
         .await
         .unwrap();
@@ -1387,99 +1980,317 @@ async fn transfer_too_much() {
         .await
         .unwrap();
 
-    let previous_balance = common::get_account_balance(client, fee_collector).await;
-
-    // Should fail to transfer.
-    assert!(common::transfer_fees(
+    // Only token accounts owned by the fee collector collect token fees.
+    let result = common::set_token_fee(
         client,
         program,
         payer,
         message_key,
-        emitter.pubkey(),
-        payer.pubkey(),
+        governance.pubkey(),
         sequence,
+        treasury.pubkey(),
     )
-    .await
-    .is_err());
+    .await;
     assert_eq!(
-        common::get_account_balance(client, fee_collector).await,
-        previous_balance
+        common::bridge_error(result),
+        Some(CoreBridgeError::InvalidFeeTokenAccount)
     );
-}
-
-#[tokio::test]
-async fn foreign_bridge_messages() {
-    // Initialize a wormhole bridge on Solana to test with.
-    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
-    let nonce = rand::thread_rng().gen();
-    let message = [0u8; 32].to_vec();
-    let emitter = Keypair::new();
-    let sequence = context.seq.next(emitter.pubkey().to_bytes());
-
-    // Verify the VAA generated on a foreign chain.
-    let (vaa, body, _body_hash) =
-        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 2);
 
-    // Derive where we expect created accounts to be.
-    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
-        &PostedVAADerivationData {
-            payload_hash: body.to_vec(),
-        },
+    common::set_token_fee(
+        client,
         program,
-    );
+        payer,
+        message_key,
+        governance.pubkey(),
+        sequence,
+        fee_tokens.pubkey(),
+    )
+    .await
+    .unwrap();
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert_eq!(bridge.config.fee_mint, native_mint);
+    assert_eq!(bridge.config.token_fee, 500);
+    assert_eq!(bridge.config.fee_token_account, fee_tokens.pubkey());
+    assert_eq!(bridge.config.claim_recipient, Pubkey::default());
 
-    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
-        .await
-        .unwrap();
-    common::post_vaa(client, program, payer, signature_set, vaa)
-        .await
+    let emitter = Keypair::new();
+    let post = |amount: u64| {
+        let message = Keypair::new();
+        let instruction = instructions::post_message(
+            *program,
+            payer.pubkey(),
+            emitter.pubkey(),
+            message.pubkey(),
+            rand::thread_rng().gen(),
+            vec![0u8; 8],
+            ConsistencyLevel::Confirmed,
+        )
         .unwrap();
+        let transfer = common::token_transfer(
+            &treasury.pubkey(),
+            &fee_tokens.pubkey(),
+            &payer.pubkey(),
+            amount,
+        );
+        (
+            message,
+            vec![
+                transfer,
+                instructions::with_token_fee(instruction, fee_tokens.pubkey()),
+            ],
+        )
+    };
 
-    // Fetch chain accounts to verify state.
-    let posted_message: PostedVAAData = common::get_account_data(client, message_key).await;
-    let signatures: SignatureSetData = common::get_account_data(client, signature_set).await;
-
-    assert_eq!(posted_message.message.vaa_version, 0);
-    assert_eq!(posted_message.message.vaa_signature_account, signature_set);
-    assert_eq!(posted_message.message.nonce, nonce);
-    assert_eq!(posted_message.message.sequence, sequence);
-    assert_eq!(posted_message.message.emitter_chain, 2);
-    assert_eq!(posted_message.message.payload, message);
+    // Posts passing the fee token account pay the token fee instead of lamports.
+    let (message, post_instructions) = post(100);
+    let result = common::execute(
+        client,
+        payer,
+        &[payer, &emitter, &message],
+        &post_instructions,
+        CommitmentLevel::Processed,
+    )
+    .await;
     assert_eq!(
-        posted_message.message.emitter_address,
-        emitter.pubkey().to_bytes()
+        common::bridge_error(result),
+        Some(CoreBridgeError::InsufficientFees)
     );
 
-    // Verify on chain Signatures
-    assert_eq!(signatures.hash, body);
-    assert_eq!(signatures.guardian_set_index, 0);
+    let lamports = common::get_account_balance(client, fee_collector).await;
+    let (message, post_instructions) = post(500);
+    common::execute(
+        client,
+        payer,
+        &[payer, &emitter, &message],
+        &post_instructions,
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        common::get_token_balance(client, fee_tokens.pubkey()).await,
+        500
+    );
+    assert_eq!(
+        common::get_account_balance(client, fee_collector).await,
+        lamports
+    );
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert_eq!(bridge.config.last_fee_tokens, 500);
 
-    for (signature, _secret_key) in signatures.signatures.iter().zip(context.secret.iter()) {
-        assert!(*signature);
+    // Token accounts approving the fee collector as their delegate have the rest of the fee
+    // pulled, once the owner signs the post.
+    common::execute(
+        client,
+        payer,
+        &[payer],
+        &[common::token_approve(
+            &treasury.pubkey(),
+            &fee_collector,
+            &payer.pubkey(),
+            800,
+        )],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+    let (token_program, _) = common::spl_token();
+    for (transferred, balance) in [(0, 1000), (200, 1500)].iter() {
+        let (message, mut post_instructions) = post(*transferred);
+        // Swap the fee token account `with_token_fee` appended for the delegated accounts.
+        let mut instruction = post_instructions.pop().unwrap();
+        instruction.accounts.pop();
+        let instruction = instructions::with_token_fee_delegate(
+            instruction,
+            fee_tokens.pubkey(),
+            treasury.pubkey(),
+            token_program,
+        );
+        post_instructions.push(instruction);
+        common::execute(
+            client,
+            payer,
+            &[payer, &emitter, &message],
+            &post_instructions,
+            CommitmentLevel::Processed,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            common::get_token_balance(client, fee_tokens.pubkey()).await,
+            *balance
+        );
+        let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+        assert_eq!(bridge.config.last_fee_tokens, *balance);
     }
+
+    // Other posts still pay lamports.
+    common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        0,
+        vec![0u8; 8],
+        10_000,
+    )
+    .await
+    .unwrap();
 }
 
 #[tokio::test]
-async fn transfer_total_fails() {
-    // Initialize a wormhole bridge on Solana to test with.
+async fn fee_exemption() {
     let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
-    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
-    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let governance = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let fee_collector = FeeCollector::key(None, program);
+    let emitter = Keypair::new();
+    let fee_exemption = FeeExemption::<'_, { AccountState::Initialized }>::key(
+        &FeeExemptionDerivationData {
+            emitter: &emitter.pubkey(),
+        },
+        program,
+    );
 
-    // Be sure any previous tests have fully committed.
+    let post = |exemption: bool| {
+        let message = Keypair::new();
+        let instruction = instructions::post_message(
+            *program,
+            payer.pubkey(),
+            emitter.pubkey(),
+            message.pubkey(),
+            rand::thread_rng().gen(),
+            vec![0u8; 8],
+            ConsistencyLevel::Confirmed,
+        )
+        .unwrap();
+        let instruction = match exemption {
+            true => instructions::with_fee_exemption(instruction, emitter.pubkey()),
+            false => instruction,
+        };
+        (message, instruction)
+    };
 
-    let fee_collector = FeeCollector::key(None, program);
-    let account_balance = common::get_account_balance(client, fee_collector).await;
+    // Emitters without an exemption pay the fee, passing one that doesn't exist changes nothing.
+    for exemption in [false, true].iter() {
+        let (message, instruction) = post(*exemption);
+        let result = common::execute(
+            client,
+            payer,
+            &[payer, &emitter, &message],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await;
+        assert_eq!(
+            common::bridge_error(result),
+            Some(CoreBridgeError::InsufficientFees)
+        );
+    }
+
+    for exempt in [true, false].iter() {
+        let nonce = rand::thread_rng().gen();
+        let message = GovernancePayloadSetFeeExemption {
+            emitter: emitter.pubkey(),
+            exempt: *exempt,
+        }
+        .try_to_vec()
+        .unwrap();
+        let message_key = common::post_message(
+            client,
+            program,
+            payer,
+            &governance,
+            None,
+            nonce,
+            message.clone(),
+            10_000,
+        )
+        .await
+        .unwrap();
+        let sequence = context.seq.next(governance.pubkey().to_bytes());
+        let (vaa, body, _) = common::generate_vaa(&governance, message, nonce, sequence, 0, 1);
+        let signature_set =
+            common::verify_signatures(client, program, payer, body, &context.secret, 0)
+                .await
+                .unwrap();
+        common::post_vaa(client, program, payer, signature_set, vaa)
+            .await
+            .unwrap();
+        common::set_fee_exemption(
+            client,
+            program,
+            payer,
+            message_key,
+            governance.pubkey(),
+            sequence,
+            emitter.pubkey(),
+        )
+        .await
+        .unwrap();
+        let exemption: FeeExemptionData = common::get_account_data(client, fee_exemption).await;
+        assert_eq!(exemption.exempt, *exempt);
+
+        // Exempted emitters post for free, but only when they pass their exemption.
+        let lamports = common::get_account_balance(client, fee_collector).await;
+        let (message, instruction) = post(true);
+        let result = common::execute(
+            client,
+            payer,
+            &[payer, &emitter, &message],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await;
+        if *exempt {
+            result.unwrap();
+        } else {
+            assert_eq!(
+                common::bridge_error(result),
+                Some(CoreBridgeError::InsufficientFees)
+            );
+        }
+        assert_eq!(
+            common::get_account_balance(client, fee_collector).await,
+            lamports
+        );
+
+        let (message, instruction) = post(false);
+        let result = common::execute(
+            client,
+            payer,
+            &[payer, &emitter, &message],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await;
+        assert_eq!(
+            common::bridge_error(result),
+            Some(CoreBridgeError::InsufficientFees)
+        );
+    }
+}
+
+#[tokio::test]
+async fn transfer_fees_fails() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+
+    // Use an invalid emitter.
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
 
-    // Prepare to remove total balance, adding 10_000 to include the fee we're about to pay.
     let nonce = rand::thread_rng().gen();
     let message = GovernancePayloadTransferFees {
-        amount: (account_balance + 10_000).into(),
+        amount: 100u128.into(),
         to: payer.pubkey().to_bytes(),
     }
     .try_to_vec()
     .unwrap();
 
+    // Fetch accounts for chain state checking.
+    let fee_collector = FeeCollector::key(None, program);
+
     let message_key = common::post_message(
         client,
         program,
@@ -1502,7 +2313,8 @@ async fn transfer_total_fails() {
         .await
         .unwrap();
 
-    // Transferring total fees should fail, to prevent the account being de-allocated.
+    let previous_balance = common::get_account_balance(client, fee_collector).await;
+
     assert!(common::transfer_fees(
         client,
         program,
@@ -1514,35 +2326,29 @@ async fn transfer_total_fails() {
     )
     .await
     .is_err());
-
-    // The fee should have been paid, but other than that the balance should be exactly the same,
-    // I.E non-zero.
     assert_eq!(
         common::get_account_balance(client, fee_collector).await,
-        account_balance + 10_000
+        previous_balance
     );
 }
 
-// `solana-program-test` doesn't use an upgradeable loader so it's not currently possible to test
-// the contract upgrade logic this way. See https://github.com/solana-labs/solana/issues/22950 for
-// more details. This test is here mainly as a reference in case the issue above gets fixed, at
-// which point this test can be re-enabled.
 #[tokio::test]
-#[ignore]
-async fn upgrade_contract() {
+async fn transfer_too_much() {
     // Initialize a wormhole bridge on Solana to test with.
     let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
-
-    // New Contract Address
-    // let new_contract = Pubkey::new_unique();
-    let new_contract = *program;
-
-    let nonce = rand::thread_rng().gen();
     let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
     let sequence = context.seq.next(emitter.pubkey().to_bytes());
-    let message = GovernancePayloadUpgrade { new_contract }
-        .try_to_vec()
-        .unwrap();
+
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadTransferFees {
+        amount: 100_000_000_000u64.into(),
+        to: payer.pubkey().to_bytes(),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    // Fetch accounts for chain state checking.
+    let fee_collector = FeeCollector::key(None, program);
 
     let message_key = common::post_message(
         client,
@@ -1565,16 +2371,4306 @@ async fn upgrade_contract() {
     common::post_vaa(client, program, payer, signature_set, vaa)
         .await
         .unwrap();
-    common::upgrade_contract(
+
+    let previous_balance = common::get_account_balance(client, fee_collector).await;
+
+    // Should fail to transfer.
+    assert!(common::transfer_fees(
         client,
         program,
         payer,
         message_key,
         emitter.pubkey(),
-        new_contract,
-        Pubkey::new_unique(),
+        payer.pubkey(),
         sequence,
     )
     .await
-    .unwrap();
+    .is_err());
+    assert_eq!(
+        common::get_account_balance(client, fee_collector).await,
+        previous_balance
+    );
+}
+
+#[tokio::test]
+async fn foreign_bridge_messages() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let nonce = rand::thread_rng().gen();
+    let message = [0u8; 32].to_vec();
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    // Verify the VAA generated on a foreign chain.
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 2);
+
+    // Derive where we expect created accounts to be.
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+
+    // Fetch chain accounts to verify state.
+    let posted_message: PostedVAAData = common::get_account_data(client, message_key).await;
+    let signatures: SignatureSetData = common::get_account_data(client, signature_set).await;
+
+    assert_eq!(posted_message.message.vaa_version, 0);
+    assert_eq!(posted_message.message.vaa_signature_account, signature_set);
+    assert_eq!(posted_message.message.nonce, nonce);
+    assert_eq!(posted_message.message.sequence, sequence);
+    assert_eq!(posted_message.message.emitter_chain, 2);
+    assert_eq!(posted_message.message.payload, message);
+    assert_eq!(
+        posted_message.message.emitter_address,
+        emitter.pubkey().to_bytes()
+    );
+
+    // Verify on chain Signatures
+    assert_eq!(signatures.hash, body);
+    assert_eq!(signatures.guardian_set_index, 0);
+
+    for (signature, _secret_key) in signatures.signatures.iter().zip(context.secret.iter()) {
+        assert!(*signature);
+    }
+}
+
+#[tokio::test]
+async fn verify_signatures_precomputed_hash() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let nonce = rand::thread_rng().gen();
+    let message = [0u8; 32].to_vec();
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 2);
+    let hash = instructions::hash_vaa(&vaa);
+    assert_eq!(hash, body);
+
+    // Without a hash or signer offset the instruction encoding is unchanged, they are appended
+    // otherwise.
+    for (hash, signer_offset, len) in [
+        (None, 0, 1 + 19),
+        (Some(hash), 0, 1 + 19 + 32),
+        (None, 19, 1 + 19 + 1),
+        (Some(hash), 19, 1 + 19 + 32 + 1),
+    ] {
+        let instruction = instructions::verify_signatures(
+            *program,
+            payer.pubkey(),
+            0,
+            Keypair::new().pubkey(),
+            VerifySignaturesData {
+                signers: [-1; 19],
+                hash,
+                signer_offset,
+            },
+        )
+        .unwrap();
+        assert_eq!(instruction.data.len(), len);
+
+        let data = VerifySignaturesData::try_from_slice(&instruction.data[1..]).unwrap();
+        assert_eq!(data.hash, hash);
+        assert_eq!(data.signer_offset, signer_offset);
+    }
+
+    // Passing the precomputed hash verifies the same signature set as not passing it.
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    let signature_set_with_hash = common::verify_signatures_with_hash(
+        client,
+        program,
+        payer,
+        body,
+        Some(hash),
+        &context.secret,
+        0,
+    )
+    .await
+    .unwrap();
+    let signatures: SignatureSetData = common::get_account_data(client, signature_set).await;
+    let signatures_with_hash: SignatureSetData =
+        common::get_account_data(client, signature_set_with_hash).await;
+    assert_eq!(signatures.hash, signatures_with_hash.hash);
+    assert_eq!(signatures.signatures, signatures_with_hash.signatures);
+
+    // A hash that differs from the signed message is rejected.
+    assert!(common::verify_signatures_with_hash(
+        client,
+        program,
+        payer,
+        body,
+        Some([0u8; 32]),
+        &context.secret,
+        0,
+    )
+    .await
+    .is_err());
+
+    // post_vaa recomputes the hash, a body that doesn't match the signatures is rejected.
+    let mut tampered = vaa.clone();
+    tampered.payload = [1u8; 32].to_vec();
+    assert!(
+        common::post_vaa(client, program, payer, signature_set_with_hash, tampered)
+            .await
+            .is_err()
+    );
+    common::post_vaa(client, program, payer, signature_set_with_hash, vaa)
+        .await
+        .unwrap();
+
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash.to_vec(),
+        },
+        program,
+    );
+    let posted_message: PostedVAAData = common::get_account_data(client, message_key).await;
+    assert_eq!(posted_message.message.payload, message);
+    assert_eq!(
+        posted_message.message.vaa_signature_account,
+        signature_set_with_hash
+    );
+}
+
+#[tokio::test]
+async fn verify_signatures_batch() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::new();
+
+    let vaas: Vec<_> = (0..2u8)
+        .map(|i| {
+            let nonce = rand::thread_rng().gen();
+            let sequence = context.seq.next(emitter.pubkey().to_bytes());
+            let (vaa, body, _body_hash) =
+                common::generate_vaa(&emitter, vec![i; 32], nonce, sequence, 0, 2);
+            (vaa, body)
+        })
+        .collect();
+    let bodies: Vec<[u8; 32]> = vaas.iter().map(|(_, body)| *body).collect();
+
+    // Each signature set holds the signatures over its own body, all guardians signed.
+    let signature_sets =
+        common::verify_signatures_batch(client, program, payer, &bodies, &context.secret, 0)
+            .await
+            .unwrap();
+    for (signature_set, body) in signature_sets.iter().zip(&bodies) {
+        let signatures: SignatureSetData = common::get_account_data(client, *signature_set).await;
+        assert_eq!(signatures.hash, *body);
+        assert_eq!(signatures.guardian_set_index, 0);
+        assert!(signatures.signatures.iter().all(|signed| *signed));
+    }
+
+    // Signatures over one body can't be added to the set of another.
+    let mut signers = [-1; 19];
+    signers[0] = 0;
+    let instruction = instructions::verify_signatures_batch(
+        *program,
+        payer.pubkey(),
+        0,
+        &signature_sets[1..],
+        VerifySignaturesBatchData {
+            signers: vec![signers],
+        },
+    )
+    .unwrap();
+    assert!(common::execute(
+        client,
+        payer,
+        &[payer],
+        &[
+            common::new_secp256k1_batch_instruction(&context.secret[0], &bodies[..1]),
+            instruction,
+        ],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .is_err());
+
+    for ((vaa, body), signature_set) in vaas.into_iter().zip(signature_sets) {
+        common::post_vaa(client, program, payer, signature_set, vaa.clone())
+            .await
+            .unwrap();
+
+        let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+            &PostedVAADerivationData {
+                payload_hash: body.to_vec(),
+            },
+            program,
+        );
+        let posted_message: PostedVAAData = common::get_account_data(client, message_key).await;
+        assert_eq!(posted_message.message.payload, vaa.payload);
+        assert_eq!(posted_message.message.vaa_signature_account, signature_set);
+    }
+}
+
+#[tokio::test]
+async fn verify_vaa_inline() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, vec![0; 32], nonce, sequence, 0, 2);
+    let all: Vec<u8> = (0..context.secret.len() as u8).collect();
+    let signatures = |signers: &[u8]| -> Vec<_> {
+        signers
+            .iter()
+            .map(|index| (&context.secret[*index as usize], body))
+            .collect()
+    };
+
+    // A VAA signed by all guardians verifies without signature sets or posted VAAs.
+    let serialized = common::serialize_signed_vaa(&vaa, &context.secret, &all);
+    common::verify_vaa_inline(
+        client,
+        program,
+        payer,
+        serialized.clone(),
+        &signatures(&all),
+        0,
+    )
+    .await
+    .unwrap();
+
+    // Signatures must be over the body of the VAA.
+    let mut tampered = serialized.clone();
+    *tampered.last_mut().unwrap() ^= 1;
+    let result =
+        common::verify_vaa_inline(client, program, payer, tampered, &signatures(&all), 0).await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::InvalidHash)
+    );
+
+    // The secp instruction must check the signatures of the VAA, in order.
+    let mut reversed = all.clone();
+    reversed.reverse();
+    let result = common::verify_vaa_inline(
+        client,
+        program,
+        payer,
+        serialized,
+        &signatures(&reversed),
+        0,
+    )
+    .await;
+    assert!(result.is_err());
+
+    // Four of six guardians don't reach quorum.
+    let serialized = common::serialize_signed_vaa(&vaa, &context.secret, &all[..4]);
+    let result = common::verify_vaa_inline(
+        client,
+        program,
+        payer,
+        serialized,
+        &signatures(&all[..4]),
+        0,
+    )
+    .await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::PostVAAConsensusFailed)
+    );
+}
+
+#[tokio::test]
+async fn verify_vaa_cached() {
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, vec![0; 32], nonce, sequence, 0, 2);
+    let all: Vec<u8> = (0..context.secret.len() as u8).collect();
+    let signatures: Vec<_> = all
+        .iter()
+        .map(|index| (&context.secret[*index as usize], body))
+        .collect();
+    let serialized = common::serialize_signed_vaa(&vaa, &context.secret, &all);
+    let unsigned = common::serialize_signed_vaa(&vaa, &context.secret, &[]);
+
+    // Nothing is cached before the first verification.
+    let result =
+        common::verify_vaa_cached(client, program, payer, serialized.clone(), &[], 0).await;
+    assert!(result.is_err());
+
+    common::verify_vaa_cached(client, program, payer, serialized, &signatures, 0)
+        .await
+        .unwrap();
+    let cache_key = DigestCache::<'_, { AccountState::Initialized }>::key(
+        &DigestCacheDerivationData { hash: body },
+        program,
+    );
+    let cache: DigestCacheData = common::get_account_data(client, cache_key).await;
+    assert_eq!(cache.hash, body);
+    assert_eq!(cache.guardian_set_index, 0);
+
+    // Later consumers verify without signatures.
+    common::verify_vaa_cached(client, program, payer, unsigned.clone(), &[], 0)
+        .await
+        .unwrap();
+
+    // Other VAAs aren't covered by the cache.
+    let mut tampered = unsigned;
+    *tampered.last_mut().unwrap() ^= 1;
+    let result = common::verify_vaa_cached(client, program, payer, tampered, &[], 0).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn transfer_total_fails() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    // Be sure any previous tests have fully committed.
+
+    let fee_collector = FeeCollector::key(None, program);
+    let account_balance = common::get_account_balance(client, fee_collector).await;
+
+    // Prepare to remove total balance, adding 10_000 to include the fee we're about to pay.
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadTransferFees {
+        amount: (account_balance + 10_000).into(),
+        to: payer.pubkey().to_bytes(),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+
+    // Transferring total fees should fail, to prevent the account being de-allocated.
+    assert!(common::transfer_fees(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        payer.pubkey(),
+        sequence,
+    )
+    .await
+    .is_err());
+
+    // The fee should have been paid, but other than that the balance should be exactly the same,
+    // I.E non-zero.
+    assert_eq!(
+        common::get_account_balance(client, fee_collector).await,
+        account_balance + 10_000
+    );
+}
+
+#[tokio::test]
+async fn encoded_vaa() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    // The VAA doesn't fit a transaction, it is uploaded in chunks.
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, vec![7; 2000], nonce, sequence, 0, 2);
+    let all: Vec<u8> = (0..context.secret.len() as u8).collect();
+    let serialized = common::serialize_signed_vaa(&vaa, &context.secret, &all);
+    let encoded_vaa = Keypair::new();
+    common::init_encoded_vaa(client, program, payer, &encoded_vaa, serialized.len())
+        .await
+        .unwrap();
+    for (i, chunk) in serialized.chunks(800).enumerate() {
+        common::write_encoded_vaa(
+            client,
+            program,
+            payer,
+            payer,
+            &encoded_vaa.pubkey(),
+            (i * 800) as u32,
+            chunk.to_vec(),
+        )
+        .await
+        .unwrap();
+    }
+
+    // Writes stay within the VAA and are reserved to the write authority.
+    let result = common::write_encoded_vaa(
+        client,
+        program,
+        payer,
+        payer,
+        &encoded_vaa.pubkey(),
+        serialized.len() as u32,
+        vec![0],
+    )
+    .await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::EncodedVaaWriteOutOfBounds)
+    );
+    let result = common::write_encoded_vaa(
+        client,
+        program,
+        payer,
+        &Keypair::new(),
+        &encoded_vaa.pubkey(),
+        0,
+        vec![0],
+    )
+    .await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::EncodedVaaWriteAuthorityMismatch)
+    );
+
+    // Signatures over another body don't verify the VAA.
+    let (_, other_body, _) = common::generate_vaa(&emitter, vec![8], nonce, sequence, 0, 2);
+    let other_signatures =
+        common::verify_signatures(client, program, payer, other_body, &context.secret, 0)
+            .await
+            .unwrap();
+    let result = common::finalize_encoded_vaa(
+        client,
+        program,
+        payer,
+        &encoded_vaa.pubkey(),
+        &other_signatures,
+        0,
+    )
+    .await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::InvalidHash)
+    );
+
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::finalize_encoded_vaa(
+        client,
+        program,
+        payer,
+        &encoded_vaa.pubkey(),
+        &signature_set,
+        0,
+    )
+    .await
+    .unwrap();
+
+    let account = client
+        .get_account(encoded_vaa.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let header = EncodedVaa::deserialize(&mut &account.data[..]).unwrap();
+    assert_eq!(header.status, ProcessingStatus::Verified);
+    assert_eq!(header.write_authority, payer.pubkey());
+    assert_eq!(header.vaa(&account.data), Some(&serialized[..]));
+
+    // Verified VAAs are final.
+    let result = common::write_encoded_vaa(
+        client,
+        program,
+        payer,
+        payer,
+        &encoded_vaa.pubkey(),
+        0,
+        vec![0],
+    )
+    .await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::EncodedVaaNotWriting)
+    );
+}
+
+#[tokio::test]
+async fn draft_message() {
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+
+    // The payload doesn't fit a transaction, it is drafted in chunks.
+    let payload: Vec<u8> = (0..3000).map(|i| i as u8).collect();
+    let draft_message = Keypair::new();
+    common::init_message_v1(
+        client,
+        program,
+        payer,
+        &emitter,
+        &draft_message,
+        payload.len(),
+    )
+    .await
+    .unwrap();
+    for (i, chunk) in payload.chunks(800).enumerate() {
+        common::write_message_v1(
+            client,
+            program,
+            payer,
+            &emitter,
+            &draft_message.pubkey(),
+            (i * 800) as u32,
+            chunk.to_vec(),
+        )
+        .await
+        .unwrap();
+    }
+
+    // Writes stay within the payload and only the emitter drafts and posts it.
+    let result = common::write_message_v1(
+        client,
+        program,
+        payer,
+        &emitter,
+        &draft_message.pubkey(),
+        payload.len() as u32,
+        vec![0],
+    )
+    .await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::DraftMessageWriteOutOfBounds)
+    );
+    let result = common::write_message_v1(
+        client,
+        program,
+        payer,
+        &Keypair::new(),
+        &draft_message.pubkey(),
+        0,
+        vec![0],
+    )
+    .await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::DraftMessageEmitterMismatch)
+    );
+    let result = common::finalize_message_v1(
+        client,
+        program,
+        payer,
+        &Keypair::new(),
+        &draft_message.pubkey(),
+        nonce,
+        10_000,
+    )
+    .await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::DraftMessageEmitterMismatch)
+    );
+
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let message_key = common::finalize_message_v1(
+        client,
+        program,
+        payer,
+        &emitter,
+        &draft_message.pubkey(),
+        nonce,
+        10_000,
+    )
+    .await
+    .unwrap();
+
+    let posted_message: PostedVAAData = common::get_account_data(client, message_key).await;
+    assert_eq!(posted_message.message.consistency_level, 1);
+    assert_eq!(posted_message.message.nonce, nonce);
+    assert_eq!(posted_message.message.sequence, sequence);
+    assert_eq!(posted_message.message.emitter_chain, 1);
+    assert_eq!(
+        posted_message.message.emitter_address,
+        emitter.pubkey().to_bytes()
+    );
+    assert_eq!(posted_message.message.payload, payload);
+
+    // Posted drafts are closed, their payload can't be posted again.
+    assert!(client
+        .get_account(draft_message.pubkey())
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn attest_sequence_range() {
+    let (_, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::new();
+
+    // Emitters without messages have no complete range.
+    let result =
+        common::attest_sequence_range(client, program, payer, &emitter.pubkey(), 0, 0).await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::SequenceRangeIncomplete)
+    );
+
+    for nonce in 0..3 {
+        common::post_message(
+            client,
+            program,
+            payer,
+            &emitter,
+            None,
+            nonce,
+            vec![0],
+            10_000,
+        )
+        .await
+        .unwrap();
+    }
+
+    // Sequences 0 to 2 were posted, 3 wasn't yet.
+    let result =
+        common::attest_sequence_range(client, program, payer, &emitter.pubkey(), 1, 3).await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::SequenceRangeIncomplete)
+    );
+    let result =
+        common::attest_sequence_range(client, program, payer, &emitter.pubkey(), 2, 1).await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::InvalidSequenceRange)
+    );
+    common::attest_sequence_range(client, program, payer, &emitter.pubkey(), 0, 2)
+        .await
+        .unwrap();
+
+    let attestation_key = SequenceRangeAttestation::<'_, { AccountState::Initialized }>::key(
+        &SequenceRangeDerivationData {
+            emitter_key: &emitter.pubkey(),
+            first: 0,
+            last: 2,
+        },
+        program,
+    );
+    let attestation: SequenceRangeAttestationData =
+        common::get_account_data(client, attestation_key).await;
+    assert_eq!(attestation.emitter, emitter.pubkey());
+    assert_eq!((attestation.first, attestation.last), (0, 2));
+    assert_eq!(attestation.next_sequence, 3);
+}
+
+#[tokio::test]
+async fn close_posted_vaa() {
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetMessageFee {
+        fee: U256::from(100u128),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+
+    let claim = Claim::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.pubkey().to_bytes(),
+            emitter_chain: 1,
+            sequence,
+        },
+        program,
+    );
+    let posted_vaa = PostedVAA::<'_, { AccountState::Initialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+
+    // Neither account can be closed before the VAA was consumed.
+    let result = common::close_posted_vaa(
+        client,
+        program,
+        payer,
+        payer,
+        &signature_set,
+        body,
+        &claim,
+        program,
+    )
+    .await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::VAANotClaimed)
+    );
+    let result =
+        common::close_signature_set(client, program, payer, payer, &signature_set, body).await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::SignatureSetInUse)
+    );
+
+    common::set_fees(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        sequence,
+    )
+    .await
+    .unwrap();
+
+    // Only the payer of the signature set is refunded.
+    let result = common::close_posted_vaa(
+        client,
+        program,
+        payer,
+        &Keypair::new(),
+        &signature_set,
+        body,
+        &claim,
+        program,
+    )
+    .await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::InvalidRefundRecipient)
+    );
+
+    let balance = common::get_account_balance(client, payer.pubkey()).await;
+    let rent = common::get_account_balance(client, posted_vaa).await
+        + common::get_account_balance(client, signature_set).await;
+    common::close_posted_vaa(
+        client,
+        program,
+        payer,
+        payer,
+        &signature_set,
+        body,
+        &claim,
+        program,
+    )
+    .await
+    .unwrap();
+    common::close_signature_set(client, program, payer, payer, &signature_set, body)
+        .await
+        .unwrap();
+    assert!(client.get_account(posted_vaa).await.unwrap().is_none());
+    assert!(client.get_account(signature_set).await.unwrap().is_none());
+    assert!(common::get_account_balance(client, payer.pubkey()).await > balance + rent - 20_000);
+}
+
+#[tokio::test]
+async fn close_posted_vaa_claim_consumer() {
+    let (public_keys, secret_keys) = common::generate_keys(6);
+    let mut context = Context {
+        public: public_keys,
+        secret: secret_keys,
+        seq: Sequencer {
+            sequences: std::collections::HashMap::new(),
+        },
+    };
+    let emitter = Keypair::new();
+    let consumer = Pubkey::new_unique();
+    let claim = Claim::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.pubkey().to_bytes(),
+            emitter_chain: 1,
+            sequence: 0,
+        },
+        &consumer,
+    );
+
+    // Any program can own an account named like a claim, here one that was never approved.
+    let (ref mut client, ref payer, ref program) = common::setup_with_accounts(
+        common::bridge_program(),
+        vec![(
+            claim,
+            Account {
+                lamports: Rent::default().minimum_balance(1),
+                data: vec![1],
+                owner: consumer,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )],
+    )
+    .await;
+    common::initialize(client, *program, payer, &context.public, 500)
+        .await
+        .unwrap();
+
+    let (vaa, body, _) = common::generate_vaa(&emitter, vec![0u8; 8], 0, 0, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+
+    let result = common::close_posted_vaa(
+        client,
+        program,
+        payer,
+        payer,
+        &signature_set,
+        body,
+        &claim,
+        &consumer,
+    )
+    .await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::UnapprovedClaimConsumer)
+    );
+
+    // The claim must belong to the consumer that is named.
+    let result = common::close_posted_vaa(
+        client,
+        program,
+        payer,
+        payer,
+        &signature_set,
+        body,
+        &claim,
+        program,
+    )
+    .await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::VAANotClaimed)
+    );
+
+    let message = GovernancePayloadSetClaimConsumer {
+        program: consumer,
+        approved: true,
+    }
+    .try_to_vec()
+    .unwrap();
+    let (message_key, _, _, sequence) =
+        post_governance_vaa(&mut context, client, program, payer, message).await;
+    common::set_claim_consumer(
+        client,
+        program,
+        payer,
+        message_key,
+        Keypair::from_bytes(&GOVERNANCE_KEY).unwrap().pubkey(),
+        sequence,
+        consumer,
+    )
+    .await
+    .unwrap();
+
+    let claim_consumer = ClaimConsumer::<'_, { AccountState::Initialized }>::key(
+        &ClaimConsumerDerivationData { program: &consumer },
+        program,
+    );
+    let claim_consumer: ClaimConsumerData = common::get_account_data(client, claim_consumer).await;
+    assert!(claim_consumer.approved);
+
+    common::close_posted_vaa(
+        client,
+        program,
+        payer,
+        payer,
+        &signature_set,
+        body,
+        &claim,
+        &consumer,
+    )
+    .await
+    .unwrap();
+    let posted_vaa = PostedVAA::<'_, { AccountState::Initialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    assert!(client.get_account(posted_vaa).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn verifier_owned_vaa() {
+    let (public_keys, secret_keys) = common::generate_keys(6);
+    let mut context = Context {
+        public: public_keys,
+        secret: secret_keys,
+        seq: Sequencer {
+            sequences: std::collections::HashMap::new(),
+        },
+    };
+    let governance = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let verifier = Pubkey::new_unique();
+
+    // A decree another program verified and wrote in the layout of posted VAAs, at a sequence the
+    // bridge never posted.
+    let sequence = 1_000;
+    let vaa = PostedVAAData {
+        message: MessageData {
+            vaa_version: 1,
+            emitter_chain: 1,
+            emitter_address: governance.pubkey().to_bytes(),
+            sequence,
+            payload: GovernancePayloadSetMessageFee {
+                fee: U256::from(1234u128),
+            }
+            .try_to_vec()
+            .unwrap(),
+            ..Default::default()
+        },
+    };
+    let vaa_key = Pubkey::new_unique();
+    let data = borsh::BorshSerialize::try_to_vec(&vaa).unwrap();
+    let (ref mut client, ref payer, ref program) = common::setup_with_accounts(
+        common::bridge_program(),
+        vec![(
+            vaa_key,
+            Account {
+                lamports: Rent::default().minimum_balance(data.len()),
+                data,
+                owner: verifier,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )],
+    )
+    .await;
+    common::initialize(client, *program, payer, &context.public, 500)
+        .await
+        .unwrap();
+
+    let set_fees = || {
+        instructions::with_verifier(
+            instructions::set_fees(
+                *program,
+                payer.pubkey(),
+                vaa_key,
+                governance.pubkey(),
+                sequence,
+            ),
+            *program,
+            verifier,
+        )
+    };
+
+    // Until governance approves the verifier, its VAAs are rejected.
+    let result = common::execute(
+        client,
+        payer,
+        &[payer],
+        &[set_fees()],
+        CommitmentLevel::Processed,
+    )
+    .await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::UnapprovedVerifier)
+    );
+
+    let message = GovernancePayloadSetVerifier {
+        program: verifier,
+        approved: true,
+    }
+    .try_to_vec()
+    .unwrap();
+    let (message_key, _, _, approval_sequence) =
+        post_governance_vaa(&mut context, client, program, payer, message).await;
+    common::set_verifier(
+        client,
+        program,
+        payer,
+        message_key,
+        governance.pubkey(),
+        approval_sequence,
+        verifier,
+    )
+    .await
+    .unwrap();
+    let approval = Verifier::<'_, { AccountState::Initialized }>::key(
+        &VerifierDerivationData { program: &verifier },
+        program,
+    );
+    let approval: VerifierData = common::get_account_data(client, approval).await;
+    assert!(approval.approved);
+
+    // The approval must be passed along.
+    let result = common::set_fees(
+        client,
+        program,
+        payer,
+        vaa_key,
+        governance.pubkey(),
+        sequence,
+    )
+    .await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::UnapprovedVerifier)
+    );
+
+    common::execute(
+        client,
+        payer,
+        &[payer],
+        &[set_fees()],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert_eq!(bridge.config.fee, 1234);
+
+    // The decree is claimed like any other.
+    let result = common::execute(
+        client,
+        payer,
+        &[payer],
+        &[set_fees()],
+        CommitmentLevel::Processed,
+    )
+    .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn set_guardian_set_expiry() {
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetGuardianSetExpiry {
+        soft: true,
+        soft_window: 3600,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+
+    // Expiry is hard until governance selected a mode, passing the missing mode account is fine.
+    let guardian_set_expiry =
+        GuardianSetExpiry::<'_, { AccountState::Initialized }>::key(None, program);
+    assert!(client
+        .get_account(guardian_set_expiry)
+        .await
+        .unwrap()
+        .is_none());
+    common::execute(
+        client,
+        payer,
+        &[payer],
+        &[instructions::with_guardian_set_expiry(
+            instructions::post_vaa(*program, payer.pubkey(), signature_set, vaa),
+        )],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+
+    // Posted VAAs record when they were posted, for consumers insisting on hard expiry.
+    let posted_vaa = PostedVAA::<'_, { AccountState::Initialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    let posted_vaa: PostedVAAData = common::get_account_data(client, posted_vaa).await;
+    assert_ne!(posted_vaa.submission_time, 0);
+
+    common::set_guardian_set_expiry(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        sequence,
+    )
+    .await
+    .unwrap();
+
+    let expiry: GuardianSetExpiryData = common::get_account_data(client, guardian_set_expiry).await;
+    assert!(expiry.soft);
+    assert_eq!(expiry.soft_window, 3600);
+
+    // Expired sets are accepted until the end of the window.
+    assert!(expiry.accepts(1_000, 4_600));
+    assert!(!expiry.accepts(1_000, 4_601));
+}
+
+#[tokio::test]
+async fn set_quorum() {
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+
+    // Quorums must keep a two thirds majority and stay below the whole set.
+    for (numerator, denominator, valid) in [(1, 3, false), (4, 4, false), (3, 4, true)] {
+        let quorum = Quorum {
+            numerator,
+            denominator,
+        };
+        let sequence = context.seq.next(emitter.pubkey().to_bytes());
+        let nonce = rand::thread_rng().gen();
+        let message = GovernancePayloadSetQuorum { quorum }.try_to_vec().unwrap();
+
+        let message_key = common::post_message(
+            client,
+            program,
+            payer,
+            &emitter,
+            None,
+            nonce,
+            message.clone(),
+            10_000,
+        )
+        .await
+        .unwrap();
+        let (vaa, body, _body_hash) =
+            common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+        let signature_set =
+            common::verify_signatures(client, program, payer, body, &context.secret, 0)
+                .await
+                .unwrap();
+        common::post_vaa(client, program, payer, signature_set, vaa)
+            .await
+            .unwrap();
+
+        let result = common::set_quorum(
+            client,
+            program,
+            payer,
+            message_key,
+            emitter.pubkey(),
+            sequence,
+            0,
+        )
+        .await;
+        assert_eq!(result.is_ok(), valid);
+    }
+
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    let quorum = Quorum {
+        numerator: 3,
+        denominator: 4,
+    };
+    assert_eq!(bridge.config.quorum, quorum);
+
+    let guardian_set_key = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetDerivationData { index: 0 },
+        program,
+    );
+    let guardian_set: GuardianSetData = common::get_account_data(client, guardian_set_key).await;
+    assert_eq!(guardian_set.quorum, quorum);
+    assert_eq!(guardian_set.quorum.required(20), 16);
+    assert_eq!(Quorum::default().required(20), 14);
+}
+
+#[tokio::test]
+async fn set_paused() {
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
+
+    // Post the governance messages up front, posting fails while emission is paused. Governance
+    // VAAs are still posted while verification is paused.
+    let mut actions = vec![];
+    for paused in [
+        BridgeConfig::PAUSE_EMISSION,
+        BridgeConfig::PAUSE_VERIFICATION,
+        0,
+    ] {
+        let nonce = rand::thread_rng().gen();
+        let message = GovernancePayloadSetPaused { paused }.try_to_vec().unwrap();
+        let message_key = common::post_message(
+            client,
+            program,
+            payer,
+            &emitter,
+            None,
+            nonce,
+            message.clone(),
+            10_000,
+        )
+        .await
+        .unwrap();
+        actions.push((paused, nonce, message, message_key));
+    }
+
+    for (paused, nonce, message, message_key) in actions {
+        let sequence = context.seq.next(emitter.pubkey().to_bytes());
+        let (vaa, body, _body_hash) =
+            common::generate_vaa(&emitter, message, nonce, sequence, 0, 1);
+        let signature_set =
+            common::verify_signatures(client, program, payer, body, &context.secret, 0)
+                .await
+                .unwrap();
+        common::post_vaa(client, program, payer, signature_set, vaa)
+            .await
+            .unwrap();
+        common::set_paused(
+            client,
+            program,
+            payer,
+            message_key,
+            emitter.pubkey(),
+            sequence,
+        )
+        .await
+        .unwrap();
+
+        let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+        assert_eq!(bridge.config.paused, paused);
+
+        let result = common::post_message(
+            client,
+            program,
+            payer,
+            &Keypair::new(),
+            None,
+            0,
+            vec![0u8; 32],
+            10_000,
+        )
+        .await
+        .map(|_| ());
+        if paused & BridgeConfig::PAUSE_EMISSION != 0 {
+            assert_eq!(common::bridge_error(result), Some(CoreBridgeError::Paused));
+        } else {
+            result.unwrap();
+        }
+
+        let (vaa, body, _) = common::generate_vaa(&Keypair::new(), vec![0u8; 8], 0, 0, 0, 1);
+        let signature_set =
+            common::verify_signatures(client, program, payer, body, &context.secret, 0)
+                .await
+                .unwrap();
+        let result = common::post_vaa(client, program, payer, signature_set, vaa).await;
+        if paused & BridgeConfig::PAUSE_VERIFICATION != 0 {
+            assert_eq!(common::bridge_error(result), Some(CoreBridgeError::Paused));
+        } else {
+            result.unwrap();
+        }
+    }
+
+    // Families that can't be paused are rejected.
+    assert!(GovernancePayloadSetPaused::deserialize(
+        &mut GovernancePayloadSetPaused { paused: 1 << 4 }
+            .try_to_vec()
+            .unwrap()
+            .as_slice()
+    )
+    .is_err());
+}
+
+#[tokio::test]
+async fn emission_breaker() {
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let governance = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
+
+    let emitter = Keypair::new();
+    common::register_emitter(
+        client,
+        program,
+        payer,
+        &emitter,
+        RegisterEmitterData {
+            name: "Portal".to_string(),
+            url_hash: [1u8; 32],
+            contact_hash: [2u8; 32],
+        },
+    )
+    .await
+    .unwrap();
+
+    // Enable the breaker.
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetEmissionBreaker { multiple: 4 }
+        .try_to_vec()
+        .unwrap();
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &governance,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+    let sequence = context.seq.next(governance.pubkey().to_bytes());
+    let (vaa, body, _) = common::generate_vaa(&governance, message, nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    common::set_emission_breaker(
+        client,
+        program,
+        payer,
+        message_key,
+        governance.pubkey(),
+        sequence,
+    )
+    .await
+    .unwrap();
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert_eq!(bridge.config.emission_breaker_multiple, 4);
+
+    // Registered emitters must pass their stats, created by the first post counted.
+    let result = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        0,
+        vec![0u8; 32],
+        10_000,
+    )
+    .await
+    .map(|_| ());
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::MissingEmitterStats)
+    );
+
+    let fee_collector = FeeCollector::<'_>::key(None, program);
+    let stats_key = EmitterStats::<'_, { AccountState::Initialized }>::key(
+        &EmitterStatsDerivationData {
+            emitter: &emitter.pubkey(),
+        },
+        program,
+    );
+    for count in 1..=2 {
+        let message = Keypair::new();
+        let instruction = instructions::post_message(
+            *program,
+            payer.pubkey(),
+            emitter.pubkey(),
+            message.pubkey(),
+            0,
+            vec![0u8; 32],
+            ConsistencyLevel::Confirmed,
+        )
+        .unwrap();
+        common::execute(
+            client,
+            payer,
+            &[payer, &emitter, &message],
+            &[
+                system_instruction::transfer(&payer.pubkey(), &fee_collector, 10_000),
+                instructions::with_emitter_stats(instruction, emitter.pubkey()),
+            ],
+            CommitmentLevel::Processed,
+        )
+        .await
+        .unwrap();
+
+        let stats: EmitterStatsData = common::get_account_data(client, stats_key).await;
+        let clock = client.get_sysvar::<Clock>().await.unwrap();
+        assert_eq!(stats.epoch, clock.epoch);
+        assert_eq!(stats.count, count);
+        assert!(!stats.tripped);
+    }
+
+    // Unregistered emitters aren't counted.
+    common::post_message(
+        client,
+        program,
+        payer,
+        &Keypair::new(),
+        None,
+        0,
+        vec![0u8; 32],
+        10_000,
+    )
+    .await
+    .unwrap();
+
+    // The emitter and governance can both clear the breaker.
+    common::clear_emission_breaker(client, program, payer, &emitter)
+        .await
+        .unwrap();
+    let stats: EmitterStatsData = common::get_account_data(client, stats_key).await;
+    assert_eq!(stats.count, 0);
+    assert!(
+        common::clear_emission_breaker(client, program, payer, &Keypair::new())
+            .await
+            .is_err()
+    );
+
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadReleaseEmissionBreaker {
+        emitter: emitter.pubkey(),
+    }
+    .try_to_vec()
+    .unwrap();
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &governance,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+    let sequence = context.seq.next(governance.pubkey().to_bytes());
+    let (vaa, body, _) = common::generate_vaa(&governance, message, nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    common::release_emission_breaker(
+        client,
+        program,
+        payer,
+        message_key,
+        governance.pubkey(),
+        sequence,
+        emitter.pubkey(),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn close_claim() {
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let governance = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
+    let recipient = Pubkey::new_unique();
+
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetClaimGracePeriod {
+        grace_period: 60,
+        recipient,
+    }
+    .try_to_vec()
+    .unwrap();
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &governance,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+    let sequence = context.seq.next(governance.pubkey().to_bytes());
+    let (vaa, body, _) = common::generate_vaa(&governance, message, nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    let claim = ClaimDerivationData {
+        emitter_address: governance.pubkey().to_bytes(),
+        emitter_chain: 1,
+        sequence,
+    };
+
+    // Claims can't be closed until governance designates a recipient.
+    let result = common::close_claim(
+        client,
+        program,
+        payer,
+        &recipient,
+        claim,
+        &signature_set,
+        body,
+        0,
+    )
+    .await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::ClaimCollectionDisabled)
+    );
+
+    common::set_claim_grace_period(
+        client,
+        program,
+        payer,
+        message_key,
+        governance.pubkey(),
+        sequence,
+    )
+    .await
+    .unwrap();
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert_eq!(bridge.config.claim_grace_period, 60);
+    assert_eq!(bridge.config.claim_recipient, recipient);
+    assert_eq!(bridge.config.emission_breaker_multiple, 0);
+
+    // Only the designated recipient receives the rent.
+    let result = common::close_claim(
+        client,
+        program,
+        payer,
+        &payer.pubkey(),
+        claim,
+        &signature_set,
+        body,
+        0,
+    )
+    .await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::InvalidRefundRecipient)
+    );
+
+    // Claims of VAAs signed by a guardian set that never expired are kept.
+    let result = common::close_claim(
+        client,
+        program,
+        payer,
+        &recipient,
+        claim,
+        &signature_set,
+        body,
+        0,
+    )
+    .await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::ClaimGracePeriodActive)
+    );
+}
+
+/// Post and verify a governance VAA carrying `payload`, signed by the guardian set with index 0.
+/// Returns the posted message, the signature set, the VAA body and its sequence.
+async fn post_governance_vaa(
+    context: &mut Context,
+    client: &mut BanksClient,
+    program: &Pubkey,
+    payer: &Keypair,
+    payload: Vec<u8>,
+) -> (Pubkey, Pubkey, [u8; 32], u64) {
+    let governance = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let nonce = rand::thread_rng().gen();
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &governance,
+        None,
+        nonce,
+        payload.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+    let sequence = context.seq.next(governance.pubkey().to_bytes());
+    let (vaa, body, _) = common::generate_vaa(&governance, payload, nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    (message_key, signature_set, body, sequence)
+}
+
+#[tokio::test]
+async fn close_claim_soft_expiry() {
+    let (public_keys, secret_keys) = common::generate_keys(6);
+    let mut context = Context {
+        public: public_keys,
+        secret: secret_keys,
+        seq: Sequencer {
+            sequences: std::collections::HashMap::new(),
+        },
+    };
+    let (mut test_context, ref program) = common::setup_with_context().await;
+    let payer = &Keypair::from_bytes(&test_context.payer.to_bytes()).unwrap();
+    let governance = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let recipient = Pubkey::new_unique();
+
+    let client = &mut test_context.banks_client;
+    common::initialize(client, *program, payer, &context.public, 500)
+        .await
+        .unwrap();
+
+    // Soft expiry keeps accepting VAAs of the old set for longer than the grace period.
+    let message = GovernancePayloadSetGuardianSetExpiry {
+        soft: true,
+        soft_window: 3600,
+    }
+    .try_to_vec()
+    .unwrap();
+    let (message_key, _, _, sequence) =
+        post_governance_vaa(&mut context, client, program, payer, message).await;
+    common::set_guardian_set_expiry(
+        client,
+        program,
+        payer,
+        message_key,
+        governance.pubkey(),
+        sequence,
+    )
+    .await
+    .unwrap();
+
+    let message = GovernancePayloadSetClaimGracePeriod {
+        grace_period: 60,
+        recipient,
+    }
+    .try_to_vec()
+    .unwrap();
+    let (message_key, signature_set, body, sequence) =
+        post_governance_vaa(&mut context, client, program, payer, message).await;
+    common::set_claim_grace_period(
+        client,
+        program,
+        payer,
+        message_key,
+        governance.pubkey(),
+        sequence,
+    )
+    .await
+    .unwrap();
+    let claim = ClaimDerivationData {
+        emitter_address: governance.pubkey().to_bytes(),
+        emitter_chain: 1,
+        sequence,
+    };
+
+    // Rotate the guardian set so that the set that signed the claimed VAA expires.
+    let (new_public_keys, _) = common::generate_keys(1);
+    let message = GovernancePayloadGuardianSetChange {
+        new_guardian_set_index: 1,
+        new_guardian_set: new_public_keys,
+        new_secp256r1_keys: vec![],
+    }
+    .try_to_vec()
+    .unwrap();
+    let (message_key, _, _, sequence) =
+        post_governance_vaa(&mut context, client, program, payer, message).await;
+    common::upgrade_guardian_set(
+        client,
+        program,
+        payer,
+        message_key,
+        governance.pubkey(),
+        0,
+        1,
+        sequence,
+    )
+    .await
+    .unwrap();
+    let guardian_set_key = GuardianSet::<'_, { AccountState::Initialized }>::key(
+        &GuardianSetDerivationData { index: 0 },
+        program,
+    );
+    let guardian_set: GuardianSetData = common::get_account_data(client, guardian_set_key).await;
+    let expiration_time = i64::from(guardian_set.expiration_time);
+
+    // Past the grace period, the claim is kept while the soft window still accepts the set.
+    for (elapsed, closed) in [(61, false), (3601, true)] {
+        let mut clock = test_context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap();
+        test_context.warp_to_slot(clock.slot + 10).unwrap();
+        clock.unix_timestamp = expiration_time + elapsed;
+        test_context.set_sysvar(&clock);
+
+        let client = &mut test_context.banks_client;
+        let result = common::close_claim(
+            client,
+            program,
+            payer,
+            &recipient,
+            claim,
+            &signature_set,
+            body,
+            0,
+        )
+        .await;
+        if closed {
+            result.unwrap();
+        } else {
+            assert_eq!(
+                common::bridge_error(result),
+                Some(CoreBridgeError::ClaimGracePeriodActive)
+            );
+        }
+    }
+
+    let client = &mut test_context.banks_client;
+    let claim_key = Claim::<'_>::key(&claim, program);
+    assert!(client.get_account(claim_key).await.unwrap().is_none());
+    assert!(common::get_account_balance(client, recipient).await > 0);
+}
+
+#[tokio::test]
+async fn fee_invoice() {
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let fee_collector = FeeCollector::key(None, program);
+
+    // Quote the initial fee for a message posted later.
+    let message = Keypair::new();
+    let payload = [0u8; 32].to_vec();
+    let slot = client.get_sysvar::<Clock>().await.unwrap().slot;
+    common::create_fee_invoice(client, program, payer, &message, 32, slot + 100)
+        .await
+        .unwrap();
+    let invoice_key = FeeInvoice::<'_, { AccountState::Initialized }>::key(
+        &FeeInvoiceDerivationData {
+            message: &message.pubkey(),
+        },
+        program,
+    );
+    let invoice: FeeInvoiceData = common::get_account_data(client, invoice_key).await;
+    assert_eq!(invoice.creator, payer.pubkey());
+    assert_eq!(invoice.fee, 500);
+    assert_eq!(invoice.expiry_slot, slot + 100);
+
+    // Quotes are limited in time.
+    assert!(common::create_fee_invoice(
+        client,
+        program,
+        payer,
+        &Keypair::new(),
+        32,
+        slot + FEE_INVOICE_MAX_SLOTS + 1,
+    )
+    .await
+    .is_err());
+
+    // Governance raises the fee before the message is posted.
+    let governance = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(governance.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let set_fees = GovernancePayloadSetMessageFee {
+        fee: U256::from(1_000u128),
+    }
+    .try_to_vec()
+    .unwrap();
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &governance,
+        None,
+        nonce,
+        set_fees.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&governance, set_fees.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    common::set_fees(
+        client,
+        program,
+        payer,
+        message_key,
+        governance.pubkey(),
+        sequence,
+    )
+    .await
+    .unwrap();
+
+    let emitter = Keypair::new();
+    let post = |payload: Vec<u8>| {
+        instructions::with_fee_invoice(
+            instructions::post_message(
+                *program,
+                payer.pubkey(),
+                emitter.pubkey(),
+                message.pubkey(),
+                0,
+                payload,
+                ConsistencyLevel::Confirmed,
+            )
+            .unwrap(),
+            message.pubkey(),
+        )
+    };
+
+    // The invoice only covers the quoted message.
+    assert!(common::execute(
+        client,
+        payer,
+        &[payer, &emitter, &message],
+        &[
+            system_instruction::transfer(&payer.pubkey(), &fee_collector, 500),
+            post([0u8; 64].to_vec()),
+        ],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .is_err());
+
+    // The quoted message pays the quoted fee.
+    let balance = common::get_account_balance(client, fee_collector).await;
+    common::execute(
+        client,
+        payer,
+        &[payer, &emitter, &message],
+        &[
+            system_instruction::transfer(&payer.pubkey(), &fee_collector, 500),
+            post(payload),
+        ],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        common::get_account_balance(client, fee_collector).await,
+        balance + 500
+    );
+
+    // Invoices are closed by their creator once expired.
+    assert!(common::execute(
+        client,
+        payer,
+        &[payer],
+        &[instructions::close_fee_invoice(
+            *program,
+            payer.pubkey(),
+            message.pubkey(),
+        )],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn message_posted_event() {
+    let (ref mut _context, ref mut client, ref payer, ref program) = initialize().await;
+    let fee_collector = FeeCollector::key(None, program);
+    let emitter = Keypair::new();
+    let message = Keypair::new();
+
+    // Posts passing the event authority invoke the bridge with the event.
+    let instruction = instructions::post_message(
+        *program,
+        payer.pubkey(),
+        emitter.pubkey(),
+        message.pubkey(),
+        0,
+        [0u8; 32].to_vec(),
+        ConsistencyLevel::Confirmed,
+    )
+    .unwrap();
+    common::execute(
+        client,
+        payer,
+        &[payer, &emitter, &message],
+        &[
+            system_instruction::transfer(&payer.pubkey(), &fee_collector, 500),
+            instructions::with_event_cpi(instruction),
+        ],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+    let posted_message: PostedVAAData = common::get_account_data(client, message.pubkey()).await;
+    assert_eq!(posted_message.sequence, 0);
+
+    // Events can't be forged by invoking the bridge directly.
+    let event = MessagePosted {
+        emitter: emitter.pubkey(),
+        sequence: 1,
+        consistency_level: 1,
+        payload_hash: [0u8; 32],
+    };
+    let mut data = EVENT_IX_TAG.to_le_bytes().to_vec();
+    data.extend_from_slice(&MessagePosted::DISCRIMINATOR);
+    data.extend_from_slice(&borsh::BorshSerialize::try_to_vec(&event).unwrap());
+    let (event_authority, _) = event_authority(program);
+    assert!(common::execute(
+        client,
+        payer,
+        &[payer],
+        &[Instruction {
+            program_id: *program,
+            accounts: vec![AccountMeta::new_readonly(event_authority, false)],
+            data,
+        }],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn commit_state() {
+    let (ref mut _context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::new();
+    let mut messages = Vec::new();
+    for nonce in 0..2 {
+        let message = common::post_message(
+            client,
+            program,
+            payer,
+            &emitter,
+            None,
+            nonce,
+            [nonce as u8; 32].to_vec(),
+            500,
+        )
+        .await
+        .unwrap();
+        messages.push(message);
+    }
+
+    // Only posted messages can be committed.
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
+    assert!(
+        common::commit_state(client, program, payer, 0, &[bridge_key])
+            .await
+            .is_err()
+    );
+
+    common::commit_state(client, program, payer, 0, &messages)
+        .await
+        .unwrap();
+
+    let commitment_key = StateCommitment::<'_, { AccountState::Initialized }>::key(None, program);
+    let commitment: StateCommitmentData = common::get_account_data(client, commitment_key).await;
+    assert_eq!(commitment.index, 0);
+    assert_eq!(commitment.guardian_set_index, 0);
+    assert_eq!(commitment.message_count, 2);
+
+    // The root can be recomputed from the committed accounts.
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    let guardian_set_key = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetDerivationData { index: 0 },
+        program,
+    );
+    let guardian_set = client.get_account(guardian_set_key).await.unwrap().unwrap();
+    let mut config = borsh::BorshSerialize::try_to_vec(&bridge.config).unwrap();
+    config.extend_from_slice(&0u32.to_be_bytes());
+    let mut digests = Vec::new();
+    for message in &messages {
+        let posted_message: PostedVAAData = common::get_account_data(client, *message).await;
+        digests.push(message_digest(&posted_message.message));
+    }
+    digests.sort_unstable();
+    let mut leaves = vec![
+        commitment_leaf(&config),
+        commitment_leaf(&guardian_set.data),
+    ];
+    leaves.extend(digests.iter().map(|digest| commitment_leaf(digest)));
+    assert_eq!(commitment.root, commitment_root(&leaves));
+
+    // The next commitment waits for the interval to pass.
+    assert!(common::commit_state(client, program, payer, 0, &[])
+        .await
+        .is_err());
+}
+
+// `solana-program-test` doesn't use an upgradeable loader so it's not currently possible to test
+// the contract upgrade logic this way. See https://github.com/solana-labs/solana/issues/22950 for
+// more details. This test is here mainly as a reference in case the issue above gets fixed, at
+// which point this test can be re-enabled.
+#[tokio::test]
+#[ignore]
+async fn upgrade_contract() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+
+    // New Contract Address
+    // let new_contract = Pubkey::new_unique();
+    let new_contract = *program;
+
+    let nonce = rand::thread_rng().gen();
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let message = GovernancePayloadUpgrade {
+        new_contract,
+        version: Some("1.0.0".to_string()),
+        buffer_hash: None,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+
+    // Decrees naming a version must be recorded.
+    let instruction = instructions::upgrade_contract(
+        *program,
+        payer.pubkey(),
+        message_key,
+        emitter.pubkey(),
+        new_contract,
+        payer.pubkey(),
+        sequence,
+    );
+    let result = common::execute(
+        client,
+        payer,
+        &[payer],
+        &[instruction.clone()],
+        CommitmentLevel::Processed,
+    )
+    .await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::MissingVersionRegistry)
+    );
+
+    // Hashing the program data fits the limit recommended for upgrades recording their version.
+    let instruction = instructions::with_version_registry(instruction);
+    let limit = instructions::compute_unit_limit(&instruction).unwrap();
+    assert!(limit > 200_000);
+    common::execute(
+        client,
+        payer,
+        &[payer],
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(limit),
+            instruction,
+        ],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+
+    // The upgrade is recorded under the version the decree carried.
+    let registry_key = VersionRegistry::<'_, { AccountState::Initialized }>::key(None, program);
+    let registry: VersionRegistryData = common::get_account_data(client, registry_key).await;
+    assert_eq!(registry.versions.len(), 1);
+    assert_eq!(registry.current().unwrap().version, "1.0.0");
+    assert!(registry.at_least("0.9.12"));
+    assert!(!registry.at_least("1.0.1"));
+}
+
+#[tokio::test]
+async fn upgrade_contract_without_version() {
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+
+    let nonce = rand::thread_rng().gen();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let message = GovernancePayloadUpgrade {
+        new_contract: *program,
+        version: None,
+        buffer_hash: None,
+    }
+    .try_to_vec()
+    .unwrap();
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+
+    // Without the registry the program data isn't hashed, the upgrade fits the default limit.
+    let instruction = instructions::upgrade_contract(
+        *program,
+        payer.pubkey(),
+        message_key,
+        emitter.pubkey(),
+        *program,
+        payer.pubkey(),
+        sequence,
+    );
+    let limit = instructions::compute_unit_limit(&instruction).unwrap();
+    assert_eq!(limit, 200_000);
+    common::execute(
+        client,
+        payer,
+        &[payer],
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(limit),
+            instruction,
+        ],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+
+    let registry_key = VersionRegistry::<'_, { AccountState::Initialized }>::key(None, program);
+    assert!(client.get_account(registry_key).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn upgrade_contract_invalid_spill() {
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+
+    let nonce = rand::thread_rng().gen();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let message = GovernancePayloadUpgrade {
+        new_contract: *program,
+        version: None,
+        buffer_hash: None,
+    }
+    .try_to_vec()
+    .unwrap();
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+
+    // Only the payer and the fee collector may receive the lamports left in the buffer.
+    let result = common::upgrade_contract(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        *program,
+        Keypair::new().pubkey(),
+        sequence,
+    )
+    .await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::InvalidSpillAccount)
+    );
+
+    // The rejected upgrade consumed nothing, the decree still upgrades with a valid spill account.
+    common::upgrade_contract(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        *program,
+        FeeCollector::<'_>::key(None, program),
+        sequence,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn upgrade_contract_buffer_hash() {
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+
+    // A stand-in buffer, rejected before reaching the loader.
+    let buffer = Keypair::new();
+    common::execute(
+        client,
+        payer,
+        &[payer, &buffer],
+        &[system_instruction::create_account(
+            &payer.pubkey(),
+            &buffer.pubkey(),
+            Rent::default().minimum_balance(100),
+            100,
+            &solana_program::system_program::id(),
+        )],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+
+    let nonce = rand::thread_rng().gen();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let message = GovernancePayloadUpgrade {
+        new_contract: buffer.pubkey(),
+        version: None,
+        buffer_hash: Some([0u8; 32]),
+    }
+    .try_to_vec()
+    .unwrap();
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+
+    // The program in the buffer must be the one the decree names.
+    let result = common::upgrade_contract(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        buffer.pubkey(),
+        payer.pubkey(),
+        sequence,
+    )
+    .await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::UpgradeBufferHashMismatch)
+    );
+}
+
+#[tokio::test]
+async fn set_guardian_weights() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+
+    // Weights must cover every guardian in the set.
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetGuardianWeights {
+        guardian_set_index: 0,
+        weights: vec![1, 2, 3],
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    assert!(common::set_guardian_weights(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        sequence,
+        0,
+    )
+    .await
+    .is_err());
+
+    // A weight per guardian is accepted.
+    let weights = vec![10, 10, 10, 10, 30, 30];
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetGuardianWeights {
+        guardian_set_index: 0,
+        weights: weights.clone(),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    common::set_guardian_weights(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        sequence,
+        0,
+    )
+    .await
+    .unwrap();
+
+    let weights_key = GuardianSetWeights::<'_, { AccountState::Initialized }>::key(
+        &GuardianSetWeightsDerivationData { index: 0 },
+        program,
+    );
+    let guardian_set_weights: GuardianSetWeightsData =
+        common::get_account_data(client, weights_key).await;
+    assert_eq!(guardian_set_weights.guardian_set_index, 0);
+    assert_eq!(guardian_set_weights.weights, weights);
+
+    // The two heaviest guardians hold 60% of the weight, one more is needed for quorum.
+    assert!(!guardian_set_weights.quorum_reached(&[false, false, false, false, true, true]));
+    assert!(guardian_set_weights.quorum_reached(&[true, false, false, false, true, true]));
+}
+
+#[tokio::test]
+async fn post_vaa_compact() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let nonce = rand::thread_rng().gen();
+    let message = [7u8; 512].to_vec();
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 2);
+
+    let message_key = PostedVAACompact::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAACompactDerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa_compact(client, program, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+
+    // Only the header and payload hash are stored.
+    let posted_account = client.get_account(message_key).await.unwrap().unwrap();
+    assert!(posted_account.data.len() < message.len());
+
+    let posted_message: PostedVAACompactData = common::get_account_data(client, message_key).await;
+    assert_eq!(posted_message.vaa_signature_account, signature_set);
+    assert_eq!(posted_message.nonce, nonce);
+    assert_eq!(posted_message.sequence, sequence);
+    assert_eq!(posted_message.emitter_chain, 2);
+    assert_eq!(posted_message.payload_len, message.len() as u32);
+    assert!(posted_message.verify_payload(&message).is_ok());
+    assert!(posted_message.verify_payload(&message[1..]).is_err());
+    assert!(posted_message.verify_payload(&[0u8; 512]).is_err());
+
+    // The full form of the same VAA can still be posted alongside it.
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    let posted_message: PostedVAAData = common::get_account_data(client, message_key).await;
+    assert_eq!(posted_message.message.payload, message);
+}
+
+#[tokio::test]
+async fn announce_governance() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetMessageFee {
+        fee: U256::from(100),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    // Announce the governance VAA before it is signed.
+    let (vaa, body, _body_hash) = common::generate_vaa(&emitter, message, nonce, sequence, 0, 1);
+    assert_eq!(body, instructions::hash_vaa(&vaa));
+    common::announce_governance(client, program, payer, body)
+        .await
+        .unwrap();
+
+    let announcement_key = GovernanceAnnouncement::<'_, { AccountState::Initialized }>::key(
+        &GovernanceAnnouncementDerivationData { digest: body },
+        program,
+    );
+    let announcement: GovernanceAnnouncementData =
+        common::get_account_data(client, announcement_key).await;
+    assert_eq!(announcement.digest, body);
+    assert!(!announcement.matured(announcement.slot));
+    assert!(announcement.matured(announcement.slot + GOVERNANCE_ANNOUNCEMENT_DELAY));
+
+    // A digest can only be announced once, so the delay can't be restarted.
+    let other_payer = Keypair::new();
+    common::execute(
+        client,
+        payer,
+        &[payer],
+        &[system_instruction::transfer(
+            &payer.pubkey(),
+            &other_payer.pubkey(),
+            1_000_000_000,
+        )],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+    assert!(
+        common::announce_governance(client, program, &other_payer, body)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn queue_governance() {
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetMessageFee {
+        fee: U256::from(300),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+
+    // Only posted VAAs can be queued.
+    let (vaa, body, _body_hash) = common::generate_vaa(&emitter, message, nonce, sequence, 0, 1);
+    assert!(common::queue_governance(client, program, payer, body)
+        .await
+        .is_err());
+
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    common::queue_governance(client, program, payer, body)
+        .await
+        .unwrap();
+
+    let pending_key = PendingGovernance::<'_, { AccountState::Initialized }>::key(
+        &PendingGovernanceDerivationData { digest: body },
+        program,
+    );
+    let pending: PendingGovernanceData = common::get_account_data(client, pending_key).await;
+    assert_eq!(pending.digest, body);
+    assert!(pending.unlock_slot >= GOVERNANCE_TIMELOCK_DELAY);
+    assert!(!pending.unlocked(pending.unlock_slot - 1));
+    assert!(pending.unlocked(pending.unlock_slot));
+
+    // A VAA can only be queued once, so the delay can't be restarted.
+    let other_payer = Keypair::new();
+    common::execute(
+        client,
+        payer,
+        &[payer],
+        &[system_instruction::transfer(
+            &payer.pubkey(),
+            &other_payer.pubkey(),
+            1_000_000_000,
+        )],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+    assert!(
+        common::queue_governance(client, program, &other_payer, body)
+            .await
+            .is_err()
+    );
+
+    // The decree executes with the pending account appended.
+    common::execute(
+        client,
+        payer,
+        &[payer],
+        &[instructions::execute_governance(
+            instructions::set_fees(
+                *program,
+                payer.pubkey(),
+                message_key,
+                emitter.pubkey(),
+                sequence,
+            ),
+            body,
+        )],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert_eq!(bridge.config.fee, 300);
+
+    // VAAs of other emitters can't be queued.
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let message = GovernancePayloadSetMessageFee {
+        fee: U256::from(400),
+    }
+    .try_to_vec()
+    .unwrap();
+    let (vaa, body, _body_hash) = common::generate_vaa(&emitter, message, nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    assert_eq!(
+        common::bridge_error(common::queue_governance(client, program, payer, body).await),
+        Some(CoreBridgeError::InvalidGovernanceKey)
+    );
+}
+
+#[tokio::test]
+async fn register_emitter() {
+    let (ref mut _context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::new();
+    let sequence_key = Sequence::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter.pubkey(),
+        },
+        program,
+    );
+
+    common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        0,
+        [0u8; 32].to_vec(),
+        10_000,
+    )
+    .await
+    .unwrap();
+
+    // Registering extends the existing sequence tracker.
+    common::register_emitter(
+        client,
+        program,
+        payer,
+        &emitter,
+        RegisterEmitterData {
+            name: "Portal".to_string(),
+            url_hash: [1u8; 32],
+            contact_hash: [2u8; 32],
+        },
+    )
+    .await
+    .unwrap();
+    let sequence: SequenceTracker = common::get_account_data(client, sequence_key).await;
+    let metadata = sequence.metadata.unwrap();
+    assert_eq!(sequence.sequence, 1);
+    assert_eq!(metadata.name, "Portal");
+    assert_eq!(metadata.url_hash, [1u8; 32]);
+    assert_eq!(metadata.contact_hash, [2u8; 32]);
+
+    // Messages keep being sequenced, and strict nonces can still be enabled.
+    common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        0,
+        [1u8; 32].to_vec(),
+        10_000,
+    )
+    .await
+    .unwrap();
+    common::enable_strict_nonce(client, program, payer, &emitter)
+        .await
+        .unwrap();
+    let sequence: SequenceTracker = common::get_account_data(client, sequence_key).await;
+    assert_eq!(sequence.sequence, 2);
+    assert_eq!(sequence.min_unreliable_nonce, Some(0));
+    assert_eq!(sequence.metadata.unwrap().name, "Portal");
+
+    // The emitter can update its metadata, the account shrinks with it.
+    common::register_emitter(
+        client,
+        program,
+        payer,
+        &emitter,
+        RegisterEmitterData {
+            name: "P".to_string(),
+            url_hash: [3u8; 32],
+            contact_hash: [4u8; 32],
+        },
+    )
+    .await
+    .unwrap();
+    let sequence: SequenceTracker = common::get_account_data(client, sequence_key).await;
+    let metadata = sequence.metadata.unwrap();
+    assert_eq!(sequence.min_unreliable_nonce, Some(0));
+    assert_eq!(metadata.name, "P");
+    assert_eq!(metadata.url_hash, [3u8; 32]);
+
+    // Names are bounded.
+    assert!(common::register_emitter(
+        client,
+        program,
+        payer,
+        &emitter,
+        RegisterEmitterData {
+            name: "P".repeat(33),
+            url_hash: [3u8; 32],
+            contact_hash: [4u8; 32],
+        },
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn initiate_migration() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let successor = Pubkey::new_unique();
+
+    for (i, candidate) in [successor, Pubkey::new_unique()].iter().enumerate() {
+        let sequence = context.seq.next(emitter.pubkey().to_bytes());
+        let nonce = rand::thread_rng().gen();
+        let message = GovernancePayloadMigrate {
+            successor: *candidate,
+        }
+        .try_to_vec()
+        .unwrap();
+
+        let message_key = common::post_message(
+            client,
+            program,
+            payer,
+            &emitter,
+            None,
+            nonce,
+            message.clone(),
+            10_000,
+        )
+        .await
+        .unwrap();
+
+        let (vaa, body, _body_hash) =
+            common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+        let signature_set =
+            common::verify_signatures(client, program, payer, body, &context.secret, 0)
+                .await
+                .unwrap();
+        common::post_vaa(client, program, payer, signature_set, vaa)
+            .await
+            .unwrap();
+
+        // Only the first migration is recorded, the successor can't be changed afterwards.
+        let result = common::initiate_migration(
+            client,
+            program,
+            payer,
+            message_key,
+            emitter.pubkey(),
+            sequence,
+        )
+        .await;
+        assert_eq!(result.is_ok(), i == 0);
+    }
+
+    let migration_key = Migration::<'_, { AccountState::Initialized }>::key(None, program);
+    let migration: MigrationData = common::get_account_data(client, migration_key).await;
+    assert_eq!(migration.successor, successor);
+
+    // Accounts are only handed over to the recorded successor, and only once it acknowledges the
+    // handover.
+    for successor in [Pubkey::new_unique(), successor] {
+        assert!(common::migrate_account(
+            client,
+            program,
+            payer,
+            successor,
+            MigratedAccount::Bridge,
+        )
+        .await
+        .is_err());
+    }
+
+    let bridge_key = Bridge::<'_, { AccountState::Initialized }>::key(None, program);
+    let bridge = client.get_account(bridge_key).await.unwrap().unwrap();
+    assert_eq!(bridge.owner, *program);
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert_eq!(bridge.guardian_set_index, 0);
+}
+
+#[tokio::test]
+async fn forward_to_successor() {
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    // The system program stands in for a rewritten core bridge.
+    let successor = solana_program::system_program::id();
+    let recipient = Pubkey::new_unique();
+    let transfer = || system_instruction::transfer(&payer.pubkey(), &recipient, 1_000_000);
+
+    // Nothing is forwarded until governance wires a successor.
+    let result = common::forward_to_successor(client, program, payer, successor, transfer()).await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::NoSuccessor)
+    );
+
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetSuccessor { successor }
+        .try_to_vec()
+        .unwrap();
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    common::set_successor(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        sequence,
+    )
+    .await
+    .unwrap();
+
+    let bridge_key = Bridge::<'_, { AccountState::Initialized }>::key(None, program);
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert_eq!(bridge.config.successor, successor);
+
+    // Instructions are only forwarded to the wired successor, with the signers of the transaction.
+    let result =
+        common::forward_to_successor(client, program, payer, Pubkey::new_unique(), transfer())
+            .await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::InvalidSuccessor)
+    );
+    common::forward_to_successor(client, program, payer, successor, transfer())
+        .await
+        .unwrap();
+    assert_eq!(
+        common::get_account_balance(client, recipient).await,
+        1_000_000
+    );
+
+    // Forwarding is opt-in, instructions sent under their own tags keep executing here.
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &Keypair::new(),
+        None,
+        rand::thread_rng().gen(),
+        vec![1, 2, 3],
+        10_000,
+    )
+    .await
+    .unwrap();
+    let message = client.get_account(message_key).await.unwrap().unwrap();
+    assert_eq!(message.owner, *program);
+}
+
+#[tokio::test]
+async fn guardian_set_floor() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let digest = [7u8; 32];
+
+    // A downgrade can only be confirmed once governance set a floor, and changing the floor keeps
+    // the confirmation.
+    let steps = [
+        (None, false),
+        (Some(5), true),
+        (None, true),
+        (Some(3), true),
+    ];
+    for (min_keys, should_succeed) in steps.iter() {
+        let sequence = context.seq.next(emitter.pubkey().to_bytes());
+        let nonce = rand::thread_rng().gen();
+        let message = match min_keys {
+            Some(min_keys) => GovernancePayloadSetGuardianSetFloor {
+                min_keys: *min_keys,
+            }
+            .try_to_vec(),
+            None => GovernancePayloadConfirmGuardianSetDowngrade { digest }.try_to_vec(),
+        }
+        .unwrap();
+
+        let message_key = common::post_message(
+            client,
+            program,
+            payer,
+            &emitter,
+            None,
+            nonce,
+            message.clone(),
+            10_000,
+        )
+        .await
+        .unwrap();
+
+        let (vaa, body, _body_hash) =
+            common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+        let signature_set =
+            common::verify_signatures(client, program, payer, body, &context.secret, 0)
+                .await
+                .unwrap();
+        common::post_vaa(client, program, payer, signature_set, vaa)
+            .await
+            .unwrap();
+
+        let result = match min_keys {
+            Some(_) => {
+                common::set_guardian_set_floor(
+                    client,
+                    program,
+                    payer,
+                    message_key,
+                    emitter.pubkey(),
+                    sequence,
+                )
+                .await
+            }
+            None => {
+                common::confirm_guardian_set_downgrade(
+                    client,
+                    program,
+                    payer,
+                    message_key,
+                    emitter.pubkey(),
+                    sequence,
+                )
+                .await
+            }
+        };
+        assert_eq!(result.is_ok(), *should_succeed);
+    }
+
+    let floor_key = GuardianSetFloor::<'_, { AccountState::Initialized }>::key(None, program);
+    let floor: GuardianSetFloorData = common::get_account_data(client, floor_key).await;
+    assert_eq!(floor.min_keys, 3);
+    assert_eq!(floor.confirmed_digest, digest);
+}
+
+#[tokio::test]
+async fn burn_fees() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let fee_collector = FeeCollector::key(None, program);
+
+    // Fees accumulate until governance enables burning.
+    assert!(common::burn_fees(client, program, payer).await.is_err());
+
+    for enabled in [true, false].iter() {
+        let sequence = context.seq.next(emitter.pubkey().to_bytes());
+        let nonce = rand::thread_rng().gen();
+        let message = GovernancePayloadSetFeeBurn { enabled: *enabled }
+            .try_to_vec()
+            .unwrap();
+
+        let message_key = common::post_message(
+            client,
+            program,
+            payer,
+            &emitter,
+            None,
+            nonce,
+            message.clone(),
+            10_000,
+        )
+        .await
+        .unwrap();
+
+        let (vaa, body, _body_hash) =
+            common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+        let signature_set =
+            common::verify_signatures(client, program, payer, body, &context.secret, 0)
+                .await
+                .unwrap();
+        common::post_vaa(client, program, payer, signature_set, vaa)
+            .await
+            .unwrap();
+        common::set_fee_burn(
+            client,
+            program,
+            payer,
+            message_key,
+            emitter.pubkey(),
+            sequence,
+        )
+        .await
+        .unwrap();
+
+        // Everything above the rent-exempt minimum is burned, and added to the burned total.
+        let balance = common::get_account_balance(client, fee_collector).await;
+        let result = common::burn_fees(client, program, payer).await;
+        if *enabled {
+            result.unwrap();
+            let minimum = Rent::default().minimum_balance(0);
+            assert_eq!(
+                common::get_account_balance(client, fee_collector).await,
+                minimum
+            );
+
+            let fee_burn_key = FeeBurn::<'_, { AccountState::Initialized }>::key(None, program);
+            let fee_burn: FeeBurnData = common::get_account_data(client, fee_burn_key).await;
+            assert_eq!(fee_burn.burned, balance - minimum);
+        } else {
+            assert_eq!(
+                common::bridge_error(result),
+                Some(CoreBridgeError::FeeBurnDisabled)
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn forward_vaa() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let nonce = rand::thread_rng().gen();
+    let message = [7u8; 32].to_vec();
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    // Post a VAA from a foreign chain to forward.
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 2);
+    let vaa_time = vaa.timestamp;
+    let posted_vaa = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+
+    // VAAs can't be forwarded back to Solana.
+    let result = common::forward_vaa(client, program, payer, posted_vaa, 1, 10_000).await;
+    assert_eq!(
+        common::bridge_error(result.map(|_| ())),
+        Some(CoreBridgeError::InvalidForwardTarget)
+    );
+
+    let forwarded = common::forward_vaa(client, program, payer, posted_vaa, 30, 10_000)
+        .await
+        .unwrap();
+
+    // The forwarded message is emitted by the forwarder and carries the original VAA metadata.
+    let (forwarder, _) = Pubkey::find_program_address(&[b"Forwarder"], program);
+    let posted_message: PostedVAAData = common::get_account_data(client, forwarded).await;
+    assert_eq!(posted_message.message.emitter_chain, 1);
+    assert_eq!(posted_message.message.emitter_address, forwarder.to_bytes());
+    assert_eq!(posted_message.message.sequence, 0);
+
+    let payload =
+        PayloadForwardedVAA::deserialize(&mut posted_message.message.payload.as_slice()).unwrap();
+    assert_eq!(
+        payload,
+        PayloadForwardedVAA {
+            target_chain: 30,
+            digest: body,
+            emitter_chain: 2,
+            emitter_address: emitter.pubkey().to_bytes(),
+            sequence,
+            timestamp: vaa_time,
+            nonce,
+            consistency_level: ConsistencyLevel::Confirmed as u8,
+        }
+    );
+
+    // Plain messages deserialize as VAAs, but were never verified and can't be forwarded.
+    let result = common::forward_vaa(client, program, payer, forwarded, 30, 10_000).await;
+    assert_eq!(
+        common::bridge_error(result.map(|_| ())),
+        Some(CoreBridgeError::InvalidForwardedVAA)
+    );
+}
+
+#[tokio::test]
+async fn forge_posted_vaa() {
+    let (ref mut _context, ref mut client, ref payer, ref program) = initialize().await;
+
+    let data = ForgePostedVAAData {
+        version: 1,
+        timestamp: 1,
+        nonce: rand::thread_rng().gen(),
+        emitter_chain: 2,
+        emitter_address: [7u8; 32],
+        sequence: 42,
+        consistency_level: 1,
+        payload: b"forged".to_vec(),
+        signature_set: Pubkey::default(),
+    };
+    let message_key =
+        instructions::forge_posted_vaa(*program, payer.pubkey(), data.clone()).accounts[1].pubkey;
+    let result = common::forge_posted_vaa(client, program, payer, data).await;
+
+    // Only localnet builds forge VAAs.
+    if !cfg!(feature = "localnet") {
+        assert!(result.is_err());
+        assert!(client.get_account(message_key).await.unwrap().is_none());
+        return;
+    }
+    result.unwrap();
+
+    let posted: PostedVAAData = common::get_account_data(client, message_key).await;
+    assert_eq!(posted.vaa_version, 1);
+    assert_eq!(posted.emitter_chain, 2);
+    assert_eq!(posted.emitter_address, [7u8; 32]);
+    assert_eq!(posted.sequence, 42);
+    assert_eq!(posted.payload, b"forged".to_vec());
+    assert_eq!(posted.vaa_signature_account, Pubkey::default());
+
+    // The account is the one post_vaa would create for the same body.
+    let expected = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: bridge::api::governance_digest(&posted.message)
+                .unwrap()
+                .to_vec(),
+        },
+        program,
+    );
+    assert_eq!(message_key, expected);
+}
+
+#[cfg(feature = "localnet")]
+#[tokio::test]
+async fn forge_posted_vaa_mainnet() {
+    let program = wormhole_solana_consts::program_ids::mainnet::CORE_BRIDGE
+        .parse::<Pubkey>()
+        .unwrap();
+    let (ref mut client, ref payer, ref program) = common::setup_at(program).await;
+
+    // Localnet builds deployed at the mainnet address anyway still refuse to forge.
+    let data = ForgePostedVAAData {
+        payload: b"forged".to_vec(),
+        ..Default::default()
+    };
+    let message_key =
+        instructions::forge_posted_vaa(*program, payer.pubkey(), data.clone()).accounts[1].pubkey;
+    let result = common::forge_posted_vaa(client, program, payer, data).await;
+    assert!(result.is_err());
+    assert!(client.get_account(message_key).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn secp256r1_guardians() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+
+    // Governance enables secp256r1 signatures.
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetSecp256r1Verification { enabled: true }
+        .try_to_vec()
+        .unwrap();
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    common::set_secp256r1_verification(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        sequence,
+    )
+    .await
+    .unwrap();
+
+    let verification_key =
+        Secp256r1Verification::<'_, { AccountState::Initialized }>::key(None, program);
+    let verification: Secp256r1VerificationData =
+        common::get_account_data(client, verification_key).await;
+    assert!(verification.enabled);
+
+    // Upgrade to a set mixing a secp256k1 and a secp256r1 guardian, the latter listed by the
+    // address of its key.
+    let (public_keys, _secret_keys) = common::generate_keys(1);
+    let mut secp256r1_key = [7u8; 33];
+    secp256r1_key[0] = 2;
+
+    for address in [[1u8; 20], secp256r1_address(&secp256r1_key)].iter() {
+        let sequence = context.seq.next(emitter.pubkey().to_bytes());
+        let nonce = rand::thread_rng().gen();
+        let message = GovernancePayloadGuardianSetChange {
+            new_guardian_set_index: 1,
+            new_guardian_set: vec![public_keys[0], *address],
+            new_secp256r1_keys: vec![[0u8; 33], secp256r1_key],
+        }
+        .try_to_vec()
+        .unwrap();
+        let message_key = common::post_message(
+            client,
+            program,
+            payer,
+            &emitter,
+            None,
+            nonce,
+            message.clone(),
+            10_000,
+        )
+        .await
+        .unwrap();
+        let (vaa, body, _body_hash) =
+            common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+        let signature_set =
+            common::verify_signatures(client, program, payer, body, &context.secret, 0)
+                .await
+                .unwrap();
+        common::post_vaa(client, program, payer, signature_set, vaa)
+            .await
+            .unwrap();
+        let result = common::upgrade_guardian_set(
+            client,
+            program,
+            payer,
+            message_key,
+            emitter.pubkey(),
+            0,
+            1,
+            sequence,
+        )
+        .await;
+
+        // Guardians can't be listed under an address other than their key's.
+        if *address == [1u8; 20] {
+            assert!(result.is_err());
+        } else {
+            result.unwrap();
+        }
+    }
+
+    let guardian_set_key = GuardianSet::<'_, { AccountState::Initialized }>::key(
+        &GuardianSetDerivationData { index: 1 },
+        program,
+    );
+    let guardian_set: GuardianSetData = common::get_account_data(client, guardian_set_key).await;
+    assert_eq!(guardian_set.keys[0], public_keys[0]);
+    assert_eq!(guardian_set.secp256r1_key(0), None);
+    assert_eq!(guardian_set.secp256r1_key(1), Some(&secp256r1_key));
+}
+
+#[tokio::test]
+async fn guardian_set_expansion() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+
+    // Upgrade to a set larger than a single verify_signatures instruction covers.
+    let (new_public_keys, new_secret_keys) = common::generate_keys(30);
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadGuardianSetChange {
+        new_guardian_set_index: 1,
+        new_guardian_set: new_public_keys.clone(),
+        new_secp256r1_keys: vec![],
+    }
+    .try_to_vec()
+    .unwrap();
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    common::upgrade_guardian_set(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        0,
+        1,
+        sequence,
+    )
+    .await
+    .unwrap();
+
+    let guardian_set_key = GuardianSet::<'_, { AccountState::Initialized }>::key(
+        &GuardianSetDerivationData { index: 1 },
+        program,
+    );
+    let guardian_set: GuardianSetData = common::get_account_data(client, guardian_set_key).await;
+    assert_eq!(guardian_set.keys, new_public_keys);
+    assert_eq!(guardian_set.quorum(), 21);
+
+    context.public = new_public_keys;
+    context.secret = new_secret_keys;
+
+    // Guardians past the first window are verified at an offset, 20 out of 30 signatures don't
+    // reach consensus while 21 do.
+    let emitter = Keypair::new();
+    let message = [0u8; 32].to_vec();
+    for (signers, consensus) in [(20, false), (21, true)] {
+        let nonce = rand::thread_rng().gen();
+        let sequence = context.seq.next(emitter.pubkey().to_bytes());
+        common::post_message(
+            client,
+            program,
+            payer,
+            &emitter,
+            None,
+            nonce,
+            message.clone(),
+            10_000,
+        )
+        .await
+        .unwrap();
+        let (vaa, body, _body_hash) =
+            common::generate_vaa(&emitter, message.clone(), nonce, sequence, 1, 1);
+        let signature_set =
+            common::verify_signatures(client, program, payer, body, &context.secret[..signers], 1)
+                .await
+                .unwrap();
+        let signatures: SignatureSetData = common::get_account_data(client, signature_set).await;
+        assert_eq!(signatures.signatures.len(), 30);
+        assert_eq!(
+            signatures.signatures.iter().filter(|v| **v).count(),
+            signers
+        );
+
+        let result = common::post_vaa(client, program, payer, signature_set, vaa).await;
+        if consensus {
+            result.unwrap();
+        } else {
+            assert_eq!(
+                common::bridge_error(result),
+                Some(CoreBridgeError::PostVAAConsensusFailed)
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn set_fee_oracle() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let oracle = Pubkey::new_unique();
+
+    // Bounds must be ordered and fit into a u64, valid ones are recorded with the oracle.
+    for (min_fee, max_fee) in [
+        (U256::from(500u64), U256::from(100u64)),
+        (U256::from(100u64), U256::from(500u64)),
+    ] {
+        let sequence = context.seq.next(emitter.pubkey().to_bytes());
+        let nonce = rand::thread_rng().gen();
+        let message = GovernancePayloadSetFeeOracle {
+            oracle,
+            min_fee,
+            max_fee,
+        }
+        .try_to_vec()
+        .unwrap();
+        let message_key = common::post_message(
+            client,
+            program,
+            payer,
+            &emitter,
+            None,
+            nonce,
+            message.clone(),
+            10_000,
+        )
+        .await
+        .unwrap();
+        let (vaa, body, _body_hash) =
+            common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+        let signature_set =
+            common::verify_signatures(client, program, payer, body, &context.secret, 0)
+                .await
+                .unwrap();
+        common::post_vaa(client, program, payer, signature_set, vaa)
+            .await
+            .unwrap();
+        let result = common::set_fee_oracle(
+            client,
+            program,
+            payer,
+            message_key,
+            emitter.pubkey(),
+            sequence,
+        )
+        .await;
+
+        if min_fee > max_fee {
+            assert_eq!(
+                common::bridge_error(result),
+                Some(CoreBridgeError::InvalidFeeOracle)
+            );
+        } else {
+            result.unwrap();
+        }
+    }
+
+    let fee_oracle_key = FeeOracle::<'_, { AccountState::Initialized }>::key(None, program);
+    let fee_oracle: FeeOracleData = common::get_account_data(client, fee_oracle_key).await;
+    assert_eq!(fee_oracle.oracle, oracle);
+    assert_eq!(fee_oracle.min_fee, 100);
+    assert_eq!(fee_oracle.max_fee, 500);
+
+    // Suggested fees are clamped to the bounds, oracles without a fee are rejected.
+    assert_eq!(fee_oracle.fee(&50u64.to_le_bytes()), Some(100));
+    assert_eq!(fee_oracle.fee(&300u64.to_le_bytes()), Some(300));
+    assert_eq!(fee_oracle.fee(&5000u64.to_le_bytes()), Some(500));
+    assert_eq!(fee_oracle.fee(&[0u8; 4]), None);
+}
+
+#[tokio::test]
+async fn compute_unit_limit() {
+    let (_, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::new();
+    let message = Keypair::new();
+    let fee_collector = FeeCollector::<'_>::key(None, program);
+
+    let instruction = instructions::post_message(
+        *program,
+        payer.pubkey(),
+        emitter.pubkey(),
+        message.pubkey(),
+        0,
+        vec![0u8; 1000],
+        ConsistencyLevel::Confirmed,
+    )
+    .unwrap();
+    let limit = instructions::compute_unit_limit(&instruction).unwrap();
+
+    // The budget is enforced, a message can't be posted with a fraction of the recommended limit.
+    let result = common::execute(
+        client,
+        payer,
+        &[payer, &emitter, &message],
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_000),
+            system_instruction::transfer(&payer.pubkey(), &fee_collector, 500),
+            instruction.clone(),
+        ],
+        CommitmentLevel::Processed,
+    )
+    .await;
+    assert!(result.is_err());
+
+    common::execute(
+        client,
+        payer,
+        &[payer, &emitter, &message],
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(limit),
+            system_instruction::transfer(&payer.pubkey(), &fee_collector, 500),
+            instruction,
+        ],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+
+    // Empty instruction data doesn't dispatch, there is nothing to recommend.
+    let empty = Instruction::new_with_bytes(*program, &[], vec![]);
+    assert_eq!(instructions::compute_unit_limit(&empty), None);
+}
+
+/// Greetings sent from the governance emitter to a program on Ethereum, encoded with borsh.
+struct GreetingChannel;
+
+impl Channel for GreetingChannel {
+    type Codec = BorshCodec<String>;
+
+    const SOURCE_CHAIN: u16 = 1;
+    const TARGET_CHAIN: u16 = 2;
+
+    fn source_address() -> ForeignAddress {
+        Keypair::from_bytes(&GOVERNANCE_KEY)
+            .unwrap()
+            .pubkey()
+            .to_bytes()
+    }
+
+    fn target_address() -> ForeignAddress {
+        [7u8; 32]
+    }
+}
+
+#[tokio::test]
+async fn channel_messages() {
+    let (_, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let message = Keypair::new();
+    let fee_collector = FeeCollector::<'_>::key(None, program);
+
+    // Only the source emitter of a channel sends on it.
+    assert!(channel::post_message::<GreetingChannel>(
+        *program,
+        payer.pubkey(),
+        Keypair::new().pubkey(),
+        message.pubkey(),
+        0,
+        "Hello".to_string(),
+        ConsistencyLevel::Confirmed,
+    )
+    .is_err());
+
+    let instruction = channel::post_message::<GreetingChannel>(
+        *program,
+        payer.pubkey(),
+        emitter.pubkey(),
+        message.pubkey(),
+        0,
+        "Hello".to_string(),
+        ConsistencyLevel::Confirmed,
+    )
+    .unwrap();
+    common::execute(
+        client,
+        payer,
+        &[payer, &emitter, &message],
+        &[
+            system_instruction::transfer(&payer.pubkey(), &fee_collector, 500),
+            instruction,
+        ],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+
+    // The payload is prefixed with the target of the channel.
+    let posted: PostedVAAData = common::get_account_data(client, message.pubkey()).await;
+    assert_eq!(posted.message.payload[..2], 2u16.to_be_bytes());
+    assert_eq!(posted.message.payload[2..34], [7u8; 32]);
+
+    let source = EmitterInfo {
+        chain: posted.message.emitter_chain,
+        address: posted.message.emitter_address,
+        sequence: posted.message.sequence,
+    };
+    let greeting = channel::open::<GreetingChannel>(&source, &posted.message.payload).unwrap();
+    assert_eq!(greeting, "Hello");
+
+    // Messages of other emitters are not part of the channel.
+    let other = EmitterInfo { chain: 2, ..source };
+    assert!(channel::open::<GreetingChannel>(&other, &posted.message.payload).is_err());
+}
+
+#[tokio::test]
+async fn export_guardian_set() {
+    let (_, ref mut client, ref payer, ref program) = initialize().await;
+    let export = Keypair::new();
+
+    // Allocate an export account owned by the bridge.
+    common::execute(
+        client,
+        payer,
+        &[payer, &export],
+        &[system_instruction::create_account(
+            &payer.pubkey(),
+            &export.pubkey(),
+            Rent::default().minimum_balance(GuardianSetExport::LEN),
+            GuardianSetExport::LEN as u64,
+            program,
+        )],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+
+    // The first export must be signed by the account.
+    let result = common::export_guardian_set(client, program, payer, &export, 0, false).await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::InvalidExportAccount)
+    );
+    common::export_guardian_set(client, program, payer, &export, 0, true)
+        .await
+        .unwrap();
+
+    let guardian_set_key = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetDerivationData { index: 0 },
+        program,
+    );
+    let guardian_set: GuardianSetData = common::get_account_data(client, guardian_set_key).await;
+    let exported: GuardianSetExport = common::get_account_data(client, export.pubkey()).await;
+    assert_eq!(exported.guardian_set_index, 0);
+    assert_eq!(exported.keys_hash, guardian_set.keys_hash());
+    assert_eq!(exported.expiration_time, 0);
+
+    // Existing exports can be refreshed by anyone.
+    common::export_guardian_set(client, program, payer, &export, 0, false)
+        .await
+        .unwrap();
+
+    // Accounts not owned by the bridge are rejected.
+    let result = common::export_guardian_set(client, program, payer, payer, 0, true).await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::InvalidExportAccount)
+    );
+}
+
+#[tokio::test]
+async fn render_vaa() {
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+
+    // Governance VAAs render with their decree.
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetMessageFee {
+        fee: U256::from(100u128),
+    }
+    .try_to_vec()
+    .unwrap();
+    let (vaa, body, _) = common::generate_vaa(&emitter, message, nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    common::render_vaa(client, program, payer, message_key)
+        .await
+        .unwrap();
+
+    let posted: PostedVAAData = common::get_account_data(client, message_key).await;
+    let rendering = bridge::api::render(&posted.message).unwrap();
+    let (fields, truncated) = instructions::parse_rendering(&rendering).unwrap();
+    assert!(!truncated);
+    let names: Vec<&str> = fields.iter().map(|field| field.name.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "version",
+            "timestamp",
+            "nonce",
+            "emitter_chain",
+            "emitter_address",
+            "sequence",
+            "consistency_level",
+            "digest",
+            "module",
+            "action",
+            "target_chain",
+            "fee",
+        ]
+    );
+    assert_eq!(fields[2].value, nonce.to_be_bytes());
+    assert_eq!(fields[4].kind, FieldKind::Address);
+    assert_eq!(fields[4].value, emitter.pubkey().to_bytes());
+    assert_eq!(fields[8].kind, FieldKind::Text);
+    assert_eq!(fields[8].value, b"Core");
+    assert_eq!(fields[11].kind, FieldKind::Uint);
+    assert_eq!(
+        U256::from_big_endian(&fields[11].value),
+        U256::from(100u128)
+    );
+
+    // Other payloads render as they are.
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let message = vec![1u8, 2, 3];
+    let (vaa, body, _) = common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    common::render_vaa(client, program, payer, message_key)
+        .await
+        .unwrap();
+
+    let posted: PostedVAAData = common::get_account_data(client, message_key).await;
+    let rendering = bridge::api::render(&posted.message).unwrap();
+    let (fields, _) = instructions::parse_rendering(&rendering).unwrap();
+    assert_eq!(fields.len(), 9);
+    assert_eq!(fields[8].name, "payload");
+    assert_eq!(fields[8].kind, FieldKind::Bytes);
+    assert_eq!(fields[8].value, message);
+
+    // Posted messages aren't VAAs.
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        vec![1, 2, 3],
+        10_000,
+    )
+    .await
+    .unwrap();
+    assert!(common::render_vaa(client, program, payer, message_key)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn event_schemas() {
+    let (_, ref mut client, ref payer, ref program) = initialize().await;
+    common::event_schemas(client, program, payer).await.unwrap();
+
+    // The first version of an event keeps the discriminator it had before versioning.
+    let registry = event_registry();
+    assert_eq!(registry.len(), 1);
+    assert_eq!(registry[0].name, "MessagePosted");
+    assert_eq!(registry[0].version, 1);
+    assert_eq!(registry[0].discriminator, MessagePosted::DISCRIMINATOR);
+    assert_eq!(registry[0].schema_hash, MessagePostedV1::SCHEMA_HASH);
+    assert_eq!(
+        registry[0].schema_hash,
+        schema_hash("emitter:pubkey,sequence:u64,consistency_level:u8,payload_hash:[u8;32]")
+    );
+
+    // Any change to the fields changes the hash.
+    assert_ne!(
+        MessagePostedV1::SCHEMA_HASH,
+        schema_hash("emitter:pubkey,sequence:u64,consistency_level:u8,payload_hash:[u8;20]")
+    );
+}
+
+#[tokio::test]
+async fn dry_run_guardian_set_upgrade() {
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+
+    // One VAA signed by the current set is posted, the other is still pending.
+    let emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let (vaa, posted, _) = common::generate_vaa(&emitter, vec![1], nonce, sequence, 0, 1);
+    let signature_set =
+        common::verify_signatures(client, program, payer, posted, &context.secret, 0)
+            .await
+            .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let (_, pending, _) = common::generate_vaa(&emitter, vec![2], nonce, sequence, 0, 1);
+
+    let (new_public_keys, _) = common::generate_keys(1);
+    let decree = |new_guardian_set_index| {
+        GovernancePayloadGuardianSetChange {
+            new_guardian_set_index,
+            new_guardian_set: new_public_keys.clone(),
+            new_secp256r1_keys: vec![],
+        }
+        .try_to_vec()
+        .unwrap()
+    };
+    common::dry_run_guardian_set_upgrade(client, program, payer, decree(1), vec![posted, pending])
+        .await
+        .unwrap();
+
+    // Only the next guardian set can be dry run.
+    let result =
+        common::dry_run_guardian_set_upgrade(client, program, payer, decree(2), vec![pending])
+            .await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::InvalidGuardianSetUpgrade)
+    );
+
+    // Every digest comes with its PostedVAA account.
+    let mut instruction =
+        instructions::dry_run_guardian_set_upgrade(*program, decree(1), vec![posted, pending]);
+    instruction.accounts.swap(2, 3);
+    assert!(common::execute(
+        client,
+        payer,
+        &[payer],
+        &[instruction],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn guardian_set_rotation_window() {
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+
+    // Rotate to a new guardian set.
+    let (new_public_keys, new_secret_keys) = common::generate_keys(1);
+    let nonce = rand::thread_rng().gen();
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let message = GovernancePayloadGuardianSetChange {
+        new_guardian_set_index: 1,
+        new_guardian_set: new_public_keys,
+        new_secp256r1_keys: vec![],
+    }
+    .try_to_vec()
+    .unwrap();
+    let (vaa, body, _) = common::generate_vaa(&emitter, message, nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    common::upgrade_guardian_set(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        0,
+        1,
+        sequence,
+    )
+    .await
+    .unwrap();
+
+    // A VAA signed by the previous set, from a client deriving accounts from the current one.
+    let nonce = rand::thread_rng().gen();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let (vaa, body, _) = common::generate_vaa(&emitter, vec![0, 1, 2], nonce, sequence, 0, 1);
+
+    // Only the current set is passed, the signatures don't match it.
+    assert!(
+        common::verify_signatures(client, program, payer, body, &context.secret, 1)
+            .await
+            .is_err()
+    );
+
+    // With both sets passed, the set of the signatures is selected.
+    let signature_set = common::verify_signatures_during_rotation(
+        client,
+        program,
+        payer,
+        body,
+        None,
+        &context.secret,
+        1,
+        Some(0),
+    )
+    .await
+    .unwrap();
+    let signatures: SignatureSetData = common::get_account_data(client, signature_set).await;
+    assert_eq!(signatures.guardian_set_index, 0);
+
+    let current_guardian_set = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetDerivationData { index: 1 },
+        program,
+    );
+    let mut instruction = instructions::post_vaa(*program, payer.pubkey(), signature_set, vaa);
+    instruction.accounts[0].pubkey = current_guardian_set;
+    let result = common::execute(
+        client,
+        payer,
+        &[payer],
+        &[instruction.clone()],
+        CommitmentLevel::Processed,
+    )
+    .await;
+    assert!(result.is_err());
+
+    common::execute(
+        client,
+        payer,
+        &[payer],
+        &[instructions::with_guardian_set(instruction, 0)],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    let posted: PostedVAAData = common::get_account_data(client, message_key).await;
+    assert_eq!(posted.message.payload, vec![0, 1, 2]);
+
+    // Signatures of the current set are still verified against it with the previous set passed.
+    let (_, body, _) = common::generate_vaa(&emitter, vec![3], nonce, sequence, 1, 1);
+    let signature_set = common::verify_signatures_during_rotation(
+        client,
+        program,
+        payer,
+        body,
+        None,
+        &new_secret_keys,
+        1,
+        Some(0),
+    )
+    .await
+    .unwrap();
+    let signatures: SignatureSetData = common::get_account_data(client, signature_set).await;
+    assert_eq!(signatures.guardian_set_index, 1);
+}
+
+/// Stage `program` in a new loader buffer with `authority` over it.
+async fn create_upgrade_buffer(
+    client: &mut BanksClient,
+    payer: &Keypair,
+    program: &[u8],
+    authority: &Pubkey,
+) -> Pubkey {
+    let buffer = Keypair::new();
+    let buffer_len = UpgradeableLoaderState::buffer_len(program.len()).unwrap();
+    let mut instructions = bpf_loader_upgradeable::create_buffer(
+        &payer.pubkey(),
+        &buffer.pubkey(),
+        &payer.pubkey(),
+        Rent::default().minimum_balance(buffer_len),
+        program.len(),
+    )
+    .unwrap();
+    instructions.push(bpf_loader_upgradeable::write(
+        &buffer.pubkey(),
+        &payer.pubkey(),
+        0,
+        program.to_vec(),
+    ));
+    if *authority != payer.pubkey() {
+        instructions.push(bpf_loader_upgradeable::set_buffer_authority(
+            &buffer.pubkey(),
+            &payer.pubkey(),
+            authority,
+        ));
+    }
+    common::execute(
+        client,
+        payer,
+        &[payer, &buffer],
+        &instructions,
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+    buffer.pubkey()
+}
+
+#[tokio::test]
+async fn validate_upgrade_buffer() {
+    let (_, ref mut client, ref payer, ref program) = initialize().await;
+    let (upgrade_authority, _) =
+        Pubkey::find_program_address(&[seeds::UPGRADE.as_bytes()], program);
+
+    // Header of a little endian ELF64 shared object for the BPF machine.
+    let mut elf = vec![0; 64];
+    elf[..6].copy_from_slice(b"\x7fELF\x02\x01");
+    elf[16..20].copy_from_slice(&[3, 0, 247, 0]);
+
+    // Accounts not owned by the loader aren't buffers.
+    let result = common::validate_upgrade_buffer(client, program, payer, &payer.pubkey()).await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::InvalidUpgradeBuffer)
+    );
+
+    // The bridge must have authority over the buffer to deploy it.
+    let buffer = create_upgrade_buffer(client, payer, &elf, &payer.pubkey()).await;
+    let result = common::validate_upgrade_buffer(client, program, payer, &buffer).await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::InvalidUpgradeBufferAuthority)
+    );
+
+    // Buffers must hold a BPF program.
+    let mut not_bpf = elf.clone();
+    not_bpf[18] = 62;
+    let buffer = create_upgrade_buffer(client, payer, &not_bpf, &upgrade_authority).await;
+    let result = common::validate_upgrade_buffer(client, program, payer, &buffer).await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::InvalidUpgradeBufferElf)
+    );
+
+    // `solana-program-test` doesn't deploy the bridge with the upgradeable loader, so no program
+    // fits its missing program data.
+    let buffer = create_upgrade_buffer(client, payer, &elf, &upgrade_authority).await;
+    let result = common::validate_upgrade_buffer(client, program, payer, &buffer).await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::UpgradeBufferTooLarge)
+    );
+}
+
+/// Start a bridge at the usual program ID along with a fork deployed by `deploy_authority`, and
+/// initialize the former as the source of the repair.
+async fn initialize_fork(
+    fork: Pubkey,
+    deploy_authority: &Keypair,
+) -> (BanksClient, Keypair, Pubkey) {
+    let (public_keys, _) = common::generate_keys(6);
+    let (mut client, payer, program) = common::setup_fork(fork, deploy_authority.pubkey()).await;
+    common::initialize(&mut client, program, &payer, &public_keys, 500)
+        .await
+        .unwrap();
+
+    // The deploy authority pays for the accounts the repair creates.
+    common::execute(
+        &mut client,
+        &payer,
+        &[&payer],
+        &[system_instruction::transfer(
+            &payer.pubkey(),
+            &deploy_authority.pubkey(),
+            1_000_000_000,
+        )],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+
+    (client, payer, program)
+}
+
+#[tokio::test]
+async fn repair_derivations() {
+    let fork = Pubkey::new_unique();
+    let deploy_authority = Keypair::new();
+    let (ref mut client, ref payer, ref program) = initialize_fork(fork, &deploy_authority).await;
+
+    let source_bridge_key = Bridge::<'_, { AccountState::Initialized }>::key(None, program);
+    let source_guardian_set_key = GuardianSet::<'_, { AccountState::Initialized }>::key(
+        &GuardianSetDerivationData { index: 0 },
+        program,
+    );
+    let source_bridge = client
+        .get_account(source_bridge_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let source_guardian_set = client
+        .get_account(source_guardian_set_key)
+        .await
+        .unwrap()
+        .unwrap();
+
+    common::repair_derivations(client, fork, payer, &deploy_authority, *program, 0)
+        .await
+        .unwrap();
+
+    // The fork holds the state of the source, under its own derivations.
+    let bridge_key = Bridge::<'_, { AccountState::Initialized }>::key(None, &fork);
+    let guardian_set_key = GuardianSet::<'_, { AccountState::Initialized }>::key(
+        &GuardianSetDerivationData { index: 0 },
+        &fork,
+    );
+    let fee_collector_key = FeeCollector::<'_>::key(None, &fork);
+    let source: BridgeData = BridgeData::try_from_slice(&source_bridge.data).unwrap();
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert_eq!(bridge.guardian_set_index, source.guardian_set_index);
+    assert_eq!(bridge.config.fee, source.config.fee);
+    assert_eq!(
+        bridge.config.guardian_set_expiration_time,
+        source.config.guardian_set_expiration_time
+    );
+    let guardian_set = client.get_account(guardian_set_key).await.unwrap().unwrap();
+    assert_eq!(guardian_set.owner, fork);
+    assert_eq!(guardian_set.data, source_guardian_set.data);
+    let fee_collector = client
+        .get_account(fee_collector_key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(bridge.last_lamports, fee_collector.lamports);
+
+    // The upgrade PDA of the fork took over from the deploy authority.
+    let (program_data_key, _) =
+        Pubkey::find_program_address(&[fork.as_ref()], &bpf_loader_upgradeable::id());
+    let (upgrade_authority, _) = Pubkey::find_program_address(&[seeds::UPGRADE.as_bytes()], &fork);
+    let program_data = client.get_account(program_data_key).await.unwrap().unwrap();
+    assert_eq!(program_data.data[12], 1);
+    assert_eq!(&program_data.data[13..45], upgrade_authority.as_ref());
+
+    // The source deployment is left as it was.
+    let bridge = client
+        .get_account(source_bridge_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let guardian_set = client
+        .get_account(source_guardian_set_key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(bridge, source_bridge);
+    assert_eq!(guardian_set, source_guardian_set);
+
+    // Derivations are repaired once, the deploy authority has no say over the fork anymore.
+    let result =
+        common::repair_derivations(client, fork, payer, &deploy_authority, *program, 0).await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::InvalidDeployAuthority)
+    );
+}
+
+#[tokio::test]
+async fn repair_derivations_rejected() {
+    let fork = Pubkey::new_unique();
+    let deploy_authority = Keypair::new();
+    let (ref mut client, ref payer, ref program) = initialize_fork(fork, &deploy_authority).await;
+
+    // Only the deploy authority repairs the fork.
+    let impostor = Keypair::new();
+    let result = common::repair_derivations(client, fork, payer, &impostor, *program, 0).await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::InvalidDeployAuthority)
+    );
+
+    // A deployment can't be repaired from its own derivations.
+    let result = common::repair_derivations(client, fork, payer, &deploy_authority, fork, 0).await;
+    assert_eq!(
+        common::bridge_error(result),
+        Some(CoreBridgeError::InvalidRepairSource)
+    );
+
+    // Nothing was created under the fork and the deploy authority kept the program.
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &fork);
+    let guardian_set_key = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetDerivationData { index: 0 },
+        &fork,
+    );
+    let fee_collector_key = FeeCollector::<'_>::key(None, &fork);
+    for key in [bridge_key, guardian_set_key, fee_collector_key] {
+        assert!(client.get_account(key).await.unwrap().is_none());
+    }
+    let (program_data_key, _) =
+        Pubkey::find_program_address(&[fork.as_ref()], &bpf_loader_upgradeable::id());
+    let program_data = client.get_account(program_data_key).await.unwrap().unwrap();
+    assert_eq!(
+        &program_data.data[13..45],
+        deploy_authority.pubkey().as_ref()
+    );
 }
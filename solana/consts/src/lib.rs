@@ -0,0 +1,331 @@
+//! Constants shared by the Wormhole Solana programs and the clients deriving their accounts.
+//!
+//! Programs and clients must agree on every PDA seed, so both sides take them from here instead of
+//! spelling out string literals. The crate has no dependencies so that it can be used from any
+//! client, seeds are raw strings and program addresses base58 strings.
+
+/// Wormhole chain ID of Solana.
+pub const CHAIN_ID_SOLANA: u16 = 1;
+
+/// Chain ID governance messages are emitted from.
+pub const CHAIN_ID_GOVERNANCE: u16 = 1;
+
+/// PDA seed prefixes.
+pub mod seeds {
+    // Core bridge.
+
+    /// `["Bridge"]`, core bridge config and state.
+    pub const BRIDGE: &str = "Bridge";
+
+    /// `["fee_collector"]`, collects message fees.
+    pub const FEE_COLLECTOR: &str = "fee_collector";
+
+    /// `["GuardianSet", index]`
+    pub const GUARDIAN_SET: &str = "GuardianSet";
+
+    /// `["GuardianSetWeights", index]`
+    pub const GUARDIAN_SET_WEIGHTS: &str = "GuardianSetWeights";
+
+    /// `["Sequence", emitter]`, sequence tracker of an emitter.
+    pub const SEQUENCE: &str = "Sequence";
+
+    /// `["PostedVAA", hash]`
+    pub const POSTED_VAA: &str = "PostedVAA";
+
+    /// `["PostedVAACompact", hash]`
+    pub const POSTED_VAA_COMPACT: &str = "PostedVAACompact";
+
+    /// `["GovernanceAnnouncement", digest]`, announcement of a governance VAA body digest.
+    pub const GOVERNANCE_ANNOUNCEMENT: &str = "GovernanceAnnouncement";
+
+    /// `["Migration"]`, successor of the core bridge once governance initiated a migration.
+    pub const MIGRATION: &str = "Migration";
+
+    /// `["GuardianSetFloor"]`, minimum guardian set size and pending downgrade confirmation.
+    pub const GUARDIAN_SET_FLOOR: &str = "GuardianSetFloor";
+
+    /// `["FeeBurn"]`, whether collected message fees are burned, and how much was burned so far.
+    pub const FEE_BURN: &str = "FeeBurn";
+
+    /// `["VersionRegistry"]`, versions the core bridge was upgraded to.
+    pub const VERSION_REGISTRY: &str = "VersionRegistry";
+
+    /// `["Forwarder"]`, emitter of the VAAs forwarded to other SVM networks.
+    pub const FORWARDER: &str = "Forwarder";
+
+    /// `["Secp256r1Verification"]`, whether secp256r1 guardian signatures are accepted.
+    pub const SECP256R1_VERIFICATION: &str = "Secp256r1Verification";
+
+    /// `["FeeOracle"]`, oracle account and bounds of dynamic message fees.
+    pub const FEE_ORACLE: &str = "FeeOracle";
+
+    /// `["SequenceRange", emitter, first, last]`, attestation that an emitter posted every sequence
+    /// of a range.
+    pub const SEQUENCE_RANGE: &str = "SequenceRange";
+
+    /// `["GuardianSetExpiry"]`, whether VAAs of recently expired guardian sets are still accepted.
+    pub const GUARDIAN_SET_EXPIRY: &str = "GuardianSetExpiry";
+
+    /// `["FeeInvoice", message]`, fee quoted for a pending message until a slot.
+    pub const FEE_INVOICE: &str = "FeeInvoice";
+
+    /// `["StateCommitment"]`, latest commitment to the bridge state for light clients.
+    pub const STATE_COMMITMENT: &str = "StateCommitment";
+
+    /// `["DigestCache", hash]`, guardian set that verified a VAA body hash.
+    pub const DIGEST_CACHE: &str = "DigestCache";
+
+    /// `["PendingGovernance", digest]`, governance VAA queued for execution after a delay.
+    pub const PENDING_GOVERNANCE: &str = "PendingGovernance";
+
+    /// `["EmitterStats", emitter]`, messages a registered emitter posted per epoch, watched by the
+    /// emission breaker.
+    pub const EMITTER_STATS: &str = "EmitterStats";
+
+    /// `["FeeExemption", emitter]`, whether governance exempted an emitter from the message fee.
+    pub const FEE_EXEMPTION: &str = "FeeExemption";
+
+    /// `["ClaimConsumer", program]`, whether governance approved a program whose claims prove that
+    /// posted VAAs were redeemed.
+    pub const CLAIM_CONSUMER: &str = "ClaimConsumer";
+
+    /// `["Verifier", program]`, whether governance approved a program whose accounts carry VAAs it
+    /// verified.
+    pub const VERIFIER: &str = "Verifier";
+
+    /// `["DeterministicMessage", emitter, seed]`, message an emitter posted into an account derived
+    /// from a seed it picked.
+    pub const DETERMINISTIC_MESSAGE: &str = "DeterministicMessage";
+
+    // Shared by all programs. Claims carry no prefix, they are derived from
+    // `[emitter_address, emitter_chain, sequence]` of the claimed message under the claiming
+    // program.
+
+    /// `["ConsumedUnreliable", emitter_chain, emitter_address]`, nonces of the unreliable
+    /// messages of an emitter recently consumed by the deriving program.
+    pub const CONSUMED_UNRELIABLE: &str = "ConsumedUnreliable";
+
+    /// `["ClaimTree", emitter_chain, emitter_address, bucket]`, merkle tree of the messages of an
+    /// emitter consumed by the deriving program, a bucket per range of sequences.
+    pub const CLAIM_TREE: &str = "ClaimTree";
+
+    /// `["upgrade"]`, upgrade authority of a program.
+    pub const UPGRADE: &str = "upgrade";
+
+    /// `["config"]`, program config of the token and NFT bridges.
+    pub const CONFIG: &str = "config";
+
+    /// `["emitter"]`, emitter of the token and NFT bridges' messages.
+    pub const EMITTER: &str = "emitter";
+
+    // Token bridge.
+
+    /// `["authority_signer"]`, delegate approved for outbound transfers.
+    pub const AUTHORITY_SIGNER: &str = "authority_signer";
+
+    /// `["custody_signer"]`, owner of the custody accounts.
+    pub const CUSTODY_SIGNER: &str = "custody_signer";
+
+    /// `["mint_signer"]`, mint authority of wrapped assets.
+    pub const MINT_SIGNER: &str = "mint_signer";
+
+    /// `["wrapped", token_chain, token_address]`, mint of a wrapped asset.
+    pub const WRAPPED: &str = "wrapped";
+
+    /// `["meta", mint]`, origin of a wrapped asset.
+    pub const WRAPPED_META: &str = "meta";
+
+    /// `["token_id", token_chain, token_address]`, local mint of an origin asset.
+    pub const TOKEN_ID: &str = "token_id";
+
+    /// `["sender"]`, derived from a sending program to attest it as the sender of a transfer.
+    pub const SENDER: &str = "sender";
+
+    /// `["redeemer"]`, derived from a receiving program to redeem transfers on its behalf.
+    pub const REDEEMER: &str = "redeemer";
+
+    /// `["metadata_authority", mint]`, metadata update authority decreed for a wrapped mint, or
+    /// for all wrapped mints with the default pubkey.
+    pub const METADATA_AUTHORITY: &str = "metadata_authority";
+
+    /// `["transfer_cap", mint]`, largest amount of a mint governance allows in a single transfer.
+    pub const TRANSFER_CAP: &str = "transfer_cap";
+
+    /// `["relayer_allowlist", token_account]`, relayers allowed to redeem transfers to a token
+    /// account, set by its owner.
+    pub const RELAYER_ALLOWLIST: &str = "relayer_allowlist";
+
+    /// `["custody_attestation"]`, latest attestation of the native tokens in custody.
+    pub const CUSTODY_ATTESTATION: &str = "custody_attestation";
+
+    /// `["yield_strategy", mint]`, strategy governance allows to borrow idle custody of a mint.
+    pub const YIELD_STRATEGY: &str = "yield_strategy";
+
+    /// `["borrower"]`, derived from a yield strategy program to borrow custody on its behalf.
+    pub const BORROWER: &str = "borrower";
+
+    /// `["recovered_chain"]`, chain ID governance recovered for a forked deployment.
+    pub const RECOVERED_CHAIN: &str = "recovered_chain";
+
+    /// `["metadata", metadata_program, mint]`, SPL token metadata of a mint. Derived under the
+    /// token metadata program.
+    pub const SPL_TOKEN_METADATA: &str = "metadata";
+}
+
+/// Program addresses of the deployments.
+pub mod program_ids {
+    pub mod mainnet {
+        pub const CORE_BRIDGE: &str = "worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth";
+        pub const TOKEN_BRIDGE: &str = "wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb";
+        pub const NFT_BRIDGE: &str = "WnFt12ZrnzZrFZkt2xsNsaNWoQribnuQ5B5FrDbwDhD";
+    }
+
+    pub mod testnet {
+        pub const CORE_BRIDGE: &str = "3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5";
+        pub const TOKEN_BRIDGE: &str = "DZnkkTmCiFWfYTfT41X3Rd1kDgozqzxWaHqsw6W4x2oe";
+        pub const NFT_BRIDGE: &str = "2rHhojZ7hpu1zA91nvZmT8TqWWvMcKmmNBCr2mKTtMq4";
+    }
+
+    pub mod devnet {
+        pub const CORE_BRIDGE: &str = "Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o";
+        pub const TOKEN_BRIDGE: &str = "B6RHG3mfcckmrYN1UhmJzyS1XX3fZKbkeUcpJe9Sy3FE";
+        pub const NFT_BRIDGE: &str = "NFTWqJR8YnRVqPDvTJrYuLrQDitTG5AScqbeghi4zSA";
+    }
+}
+
+/// Emitter addresses of foreign deployments, as VAAs and chain registrations carry them.
+///
+/// Every emitter address is 32 bytes. EVM chains left-pad the 20 byte address of the emitting
+/// contract, Aptos and Sui emitters are already 32 bytes but are usually written with their leading
+/// zeros stripped, such as `0x1` for the Aptos token bridge.
+pub mod emitters {
+    /// Wormhole chain IDs of the foreign chains with known deployments.
+    pub mod chains {
+        pub const ETHEREUM: u16 = 2;
+        pub const BSC: u16 = 4;
+        pub const POLYGON: u16 = 5;
+        pub const AVALANCHE: u16 = 6;
+        pub const FANTOM: u16 = 10;
+        pub const SUI: u16 = 21;
+        pub const APTOS: u16 = 22;
+        pub const ARBITRUM: u16 = 23;
+        pub const OPTIMISM: u16 = 24;
+        pub const BASE: u16 = 30;
+    }
+
+    /// Whether emitters of `chain` are EVM contracts.
+    pub fn is_evm(chain: u16) -> bool {
+        use chains::*;
+        matches!(
+            chain,
+            ETHEREUM | BSC | POLYGON | AVALANCHE | FANTOM | ARBITRUM | OPTIMISM | BASE
+        )
+    }
+
+    /// Emitter address of an EVM contract.
+    pub const fn evm(address: [u8; 20]) -> [u8; 32] {
+        let mut emitter = [0u8; 32];
+        let mut i = 0;
+        while i < 20 {
+            emitter[12 + i] = address[i];
+            i += 1;
+        }
+        emitter
+    }
+
+    /// Emitter address of the native hex address of an emitter on `chain`, with or without `0x`
+    /// prefix. EVM addresses must be 20 bytes, addresses of other chains are left-padded to 32.
+    pub fn from_native(chain: u16, address: &str) -> Option<[u8; 32]> {
+        let hex = address.strip_prefix("0x").unwrap_or(address);
+        if hex.is_empty() || hex.len() > 64 || (is_evm(chain) && hex.len() != 40) {
+            return None;
+        }
+
+        // Pad odd lengths, `0x1` is the same address as `0x01`.
+        let digits = match hex.len() % 2 {
+            0 => hex.to_string(),
+            _ => format!("0{}", hex),
+        };
+        let mut emitter = [0u8; 32];
+        let offset = 32 - digits.len() / 2;
+        for (i, pair) in digits.as_bytes().chunks(2).enumerate() {
+            let pair = std::str::from_utf8(pair).ok()?;
+            emitter[offset + i] = u8::from_str_radix(pair, 16).ok()?;
+        }
+        Some(emitter)
+    }
+
+    /// Native addresses of the mainnet deployments.
+    pub mod mainnet {
+        use super::chains::*;
+
+        /// Token bridge emitters by chain.
+        pub const TOKEN_BRIDGES: &[(u16, &str)] = &[
+            (ETHEREUM, "0x3ee18B2214AFF97000D974cf647E7C347E8fa585"),
+            (BSC, "0xB6F6D86a8f9879A9c87f643768d9efc38c1Da6E7"),
+            (POLYGON, "0x5a58505a96D1dbf8dF91cB21B54419FC36e93fdE"),
+            (AVALANCHE, "0x0e082F06FF657D94310cB8cE8B0D9a04541d8052"),
+            (FANTOM, "0x7C9Fc5741288cDFdD83CeB07f3ea7e22618D79D2"),
+            (
+                SUI,
+                "0xccceeb29348f71bdd22ffef43a2a19c1f5b5e17c5cca5411529120182672ade5",
+            ),
+            (APTOS, "0x1"),
+            (ARBITRUM, "0x0b2402144Bb366A632D14B83F244D2e0e21bD39c"),
+            (OPTIMISM, "0x1D68124e65faFC907325e3EDbF8c4d84499DAa8b"),
+            (BASE, "0x8d2de8d2f73F1F4cAB472AC9A881C9b123C79627"),
+        ];
+
+        /// Emitter address of the mainnet token bridge on `chain`, to check chain registrations
+        /// against.
+        pub fn token_bridge(chain: u16) -> Option<[u8; 32]> {
+            TOKEN_BRIDGES
+                .iter()
+                .find(|(id, _)| *id == chain)
+                .and_then(|(id, address)| super::from_native(*id, address))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn derives_known_emitters() {
+            // Emitter addresses as registered with the mainnet token bridges.
+            let hex = |emitter: [u8; 32]| -> String {
+                emitter.iter().map(|b| format!("{:02x}", b)).collect()
+            };
+            assert_eq!(
+                hex(mainnet::token_bridge(chains::ETHEREUM).unwrap()),
+                "0000000000000000000000003ee18b2214aff97000d974cf647e7c347e8fa585"
+            );
+            assert_eq!(
+                hex(mainnet::token_bridge(chains::APTOS).unwrap()),
+                "0000000000000000000000000000000000000000000000000000000000000001"
+            );
+            assert_eq!(
+                hex(mainnet::token_bridge(chains::SUI).unwrap()),
+                "ccceeb29348f71bdd22ffef43a2a19c1f5b5e17c5cca5411529120182672ade5"
+            );
+            assert_eq!(mainnet::token_bridge(1), None);
+        }
+
+        #[test]
+        fn rejects_malformed_addresses() {
+            // EVM addresses are never padded, a shortened address is a copy-paste mistake.
+            assert_eq!(from_native(chains::ETHEREUM, "0x1"), None);
+            assert_eq!(from_native(chains::APTOS, ""), None);
+            assert_eq!(from_native(chains::APTOS, "0xzz"), None);
+            assert_eq!(from_native(chains::SUI, &"1".repeat(65)), None);
+            assert_eq!(
+                from_native(chains::APTOS, "0x01"),
+                from_native(chains::APTOS, "1")
+            );
+            assert_eq!(
+                from_native(chains::BASE, &format!("0x{}", "ab".repeat(20))),
+                Some(evm([0xab; 20]))
+            );
+        }
+    }
+}
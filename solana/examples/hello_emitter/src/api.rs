@@ -0,0 +1,5 @@
+pub mod say_hello;
+pub mod say_hello_batch;
+
+pub use say_hello::*;
+pub use say_hello_batch::*;
@@ -0,0 +1,88 @@
+use crate::message::HelloMessage;
+use bridge::{
+    types::ConsistencyLevel,
+    vaa::SerializePayload,
+    BridgeData,
+};
+use solana_program::{
+    msg,
+    sysvar::clock::Clock,
+};
+use solitaire::{
+    processors::seeded::invoke_seeded,
+    *,
+};
+
+/// Core bridge config, checked to be owned by the core bridge.
+pub type CoreBridge<'a> = Data<'a, BridgeData, { AccountState::Initialized }>;
+
+/// Emitter of the greetings, signs the `post_message` CPI.
+pub type EmitterAccount<'b> = Derive<Info<'b>, "emitter">;
+
+#[derive(FromAccounts)]
+pub struct SayHello<'b> {
+    /// Pays the message fee and for the message account.
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Core bridge config, needed for the message fee.
+    pub bridge: Mut<CoreBridge<'b>>,
+
+    /// Account to store the posted message
+    pub message: Signer<Mut<Info<'b>>>,
+
+    /// Emitter of the VAA
+    pub emitter: EmitterAccount<'b>,
+
+    /// Tracker for the emitter sequence
+    pub sequence: Mut<Info<'b>>,
+
+    /// Account to collect tx fee
+    pub fee_collector: Mut<Info<'b>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SayHelloData {
+    /// Unique nonce for this message
+    pub nonce: u32,
+
+    /// Greeting posted as the message payload
+    pub message: Vec<u8>,
+}
+
+/// Post a greeting to the core bridge.
+///
+/// The core bridge program is taken from the owner of the bridge config, and must be passed along
+/// with the rent sysvar and system program for the CPI.
+pub fn say_hello(ctx: &ExecutionContext, accs: &mut SayHello, data: SayHelloData) -> Result<()> {
+    // Pay fee
+    let transfer_ix = solana_program::system_instruction::transfer(
+        accs.payer.key,
+        accs.fee_collector.key,
+        accs.bridge.config.fee,
+    );
+    invoke(&transfer_ix, ctx.accounts)?;
+
+    // Post message
+    let payload = HelloMessage {
+        message: data.message,
+    };
+    let ix = bridge::instructions::post_message(
+        *accs.bridge.info().owner,
+        *accs.payer.key,
+        *accs.emitter.key,
+        *accs.message.key,
+        data.nonce,
+        payload.try_to_vec()?,
+        ConsistencyLevel::Confirmed,
+    )?;
+    invoke_seeded(&ix, ctx, &accs.emitter, None)?;
+
+    // The core bridge returns the sequence it assigned, sparing a read of the sequence account.
+    if let Some(sequence) = bridge::instructions::posted_sequence(accs.bridge.info().owner) {
+        msg!("Hello sequence: {}", sequence);
+    }
+
+    Ok(())
+}
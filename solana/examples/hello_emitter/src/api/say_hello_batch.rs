@@ -0,0 +1,90 @@
+use crate::{
+    api::say_hello::CoreBridge,
+    EmitterAccount,
+    SayHelloData,
+};
+use solana_program::{
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    program_error::ProgramError::NotEnoughAccountKeys,
+    sysvar::clock::Clock,
+};
+use solitaire::*;
+
+#[derive(FromAccounts)]
+pub struct SayHelloBatch<'b> {
+    /// Pays the message fees and for the message accounts.
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Core bridge config, needed for the message fee.
+    pub bridge: Mut<CoreBridge<'b>>,
+
+    /// Emitter of the VAAs
+    pub emitter: EmitterAccount<'b>,
+
+    /// Tracker for the emitter sequence
+    pub sequence: Mut<Info<'b>>,
+
+    /// Account to collect tx fee
+    pub fee_collector: Mut<Info<'b>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SayHelloBatchData {
+    /// Unique nonce for these messages
+    pub nonce: u32,
+
+    /// Greetings posted in order, one message account each
+    pub messages: Vec<Vec<u8>>,
+}
+
+/// Post several greetings in one instruction, each through a nested `say_hello` invocation of this
+/// program. The message accounts follow all other accounts, in the order of the greetings.
+///
+/// Exercises `post_message` from a CPI depth of two: the core bridge assigns the greetings
+/// consecutive sequences in the order they are posted.
+pub fn say_hello_batch(
+    ctx: &ExecutionContext,
+    accs: &mut SayHelloBatch,
+    data: SayHelloBatchData,
+) -> Result<()> {
+    let messages = ctx
+        .accounts
+        .len()
+        .checked_sub(data.messages.len())
+        .map(|start| &ctx.accounts[start..])
+        .ok_or(NotEnoughAccountKeys)?;
+
+    for (greeting, message) in data.messages.into_iter().zip(messages) {
+        let ix = Instruction {
+            program_id: *ctx.program_id,
+            accounts: vec![
+                AccountMeta::new(*accs.payer.key, true),
+                AccountMeta::new(*accs.bridge.info().key, false),
+                AccountMeta::new(*message.key, true),
+                AccountMeta::new_readonly(*accs.emitter.key, false),
+                AccountMeta::new(*accs.sequence.key, false),
+                AccountMeta::new(*accs.fee_collector.key, false),
+                AccountMeta::new_readonly(*accs.clock.info().key, false),
+                AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+                AccountMeta::new_readonly(solana_program::system_program::id(), false),
+                AccountMeta::new_readonly(*accs.bridge.info().owner, false),
+            ],
+            data: (
+                crate::instruction::Instruction::SayHello,
+                SayHelloData {
+                    nonce: data.nonce,
+                    message: greeting,
+                },
+            )
+                .try_to_vec()?,
+        };
+        invoke(&ix, ctx.accounts)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use crate::{
+    EmitterAccount,
+    SayHelloBatchData,
+    SayHelloData,
+};
+use borsh::BorshSerialize;
+use bridge::accounts::{
+    Bridge,
+    FeeCollector,
+    Sequence,
+    SequenceDerivationData,
+};
+use solana_program::{
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    pubkey::Pubkey,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountState,
+};
+
+/// Required accounts
+///
+/// | name           | account                                     | signer |
+/// |----------------+---------------------------------------------+--------|
+/// | payer          | Pubkey                                      | true   |
+/// | bridge_config  | PDA(bridge_id,  \["Bridge"\])               | false  |
+/// | message        | Pubkey                                      | true   |
+/// | emitter        | PDA(program_id, \["emitter"\])              | false  |
+/// | sequence       | PDA(bridge_id,  \["Sequence", emitter\])    | false  |
+/// | fee_collector  | PDA(bridge_id,  \["fee_collector"\])        | false  |
+/// | clock          | sysvar clock                                | false  |
+/// | rent           | sysvar rent                                 | false  |
+/// | system_program | system program                              | false  |
+/// | bridge_id      | bridge_id program                           | false  |
+pub fn say_hello(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    data: SayHelloData,
+) -> solitaire::Result<Instruction> {
+    let emitter = EmitterAccount::key(None, &program_id);
+
+    // Bridge keys
+    let bridge_config = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &bridge_id);
+    let sequence = Sequence::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter,
+        },
+        &bridge_id,
+    );
+    let fee_collector = FeeCollector::key(None, &bridge_id);
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(bridge_config, false),
+            AccountMeta::new(message, true),
+            AccountMeta::new_readonly(emitter, false),
+            AccountMeta::new(sequence, false),
+            AccountMeta::new(fee_collector, false),
+            AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            // Program
+            AccountMeta::new_readonly(bridge_id, false),
+        ],
+        data: (crate::instruction::Instruction::SayHello, data).try_to_vec()?,
+    })
+}
+
+/// Required accounts
+///
+/// | name           | account                                     | signer |
+/// |----------------+---------------------------------------------+--------|
+/// | payer          | Pubkey                                      | true   |
+/// | bridge_config  | PDA(bridge_id,  \["Bridge"\])               | false  |
+/// | emitter        | PDA(program_id, \["emitter"\])              | false  |
+/// | sequence       | PDA(bridge_id,  \["Sequence", emitter\])    | false  |
+/// | fee_collector  | PDA(bridge_id,  \["fee_collector"\])        | false  |
+/// | clock          | sysvar clock                                | false  |
+/// | rent           | sysvar rent                                 | false  |
+/// | system_program | system program                              | false  |
+/// | bridge_id      | bridge_id program                           | false  |
+/// | program_id     | program_id program                          | false  |
+/// | messages       | Pubkey, one per greeting                    | true   |
+pub fn say_hello_batch(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    messages: &[Pubkey],
+    data: SayHelloBatchData,
+) -> solitaire::Result<Instruction> {
+    let emitter = EmitterAccount::key(None, &program_id);
+
+    // Bridge keys
+    let bridge_config = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &bridge_id);
+    let sequence = Sequence::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter,
+        },
+        &bridge_id,
+    );
+    let fee_collector = FeeCollector::key(None, &bridge_id);
+
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new(bridge_config, false),
+        AccountMeta::new_readonly(emitter, false),
+        AccountMeta::new(sequence, false),
+        AccountMeta::new(fee_collector, false),
+        AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
+        // Dependencies
+        AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        // Programs
+        AccountMeta::new_readonly(bridge_id, false),
+        AccountMeta::new_readonly(program_id, false),
+    ];
+    accounts.extend(
+        messages
+            .iter()
+            .map(|message| AccountMeta::new(*message, true)),
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data: (crate::instruction::Instruction::SayHelloBatch, data).try_to_vec()?,
+    })
+}
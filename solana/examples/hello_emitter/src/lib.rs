@@ -0,0 +1,32 @@
+//! Minimal program emitting Wormhole messages, kept as reference code for integrators.
+//!
+//! The program posts a greeting through the core bridge `post_message` CPI, signing as its own
+//! emitter PDA. It is built and exercised with the rest of the workspace, so changes to the core
+//! bridge CPI interface break its tests rather than integrators.
+#![feature(adt_const_params)]
+#![allow(non_upper_case_globals)]
+#![allow(incomplete_features)]
+
+pub mod api;
+pub mod message;
+
+use solitaire::*;
+
+#[cfg(feature = "instructions")]
+pub mod instructions;
+
+pub use api::{
+    say_hello,
+    say_hello_batch,
+    EmitterAccount,
+    SayHello,
+    SayHelloBatch,
+    SayHelloBatchData,
+    SayHelloData,
+};
+pub use message::HelloMessage;
+
+solitaire! {
+    SayHello => say_hello,
+    SayHelloBatch => say_hello_batch,
+}
@@ -0,0 +1,36 @@
+use bridge::vaa::{
+    DeserializePayload,
+    SerializePayload,
+};
+use solitaire::SolitaireError;
+use std::io::Write;
+
+/// Identifies the payload as a greeting, so receivers can tell it apart from other messages.
+pub const PAYLOAD_ID_HELLO: u8 = 1;
+
+/// Payload of the messages posted by `say_hello`: the payload id followed by the raw greeting.
+#[derive(PartialEq, Debug, Clone)]
+pub struct HelloMessage {
+    pub message: Vec<u8>,
+}
+
+impl SerializePayload for HelloMessage {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), SolitaireError> {
+        writer.write_all(&[PAYLOAD_ID_HELLO])?;
+        writer.write_all(&self.message)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for HelloMessage {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        match buf.split_first() {
+            Some((&PAYLOAD_ID_HELLO, message)) => {
+                let message = message.to_vec();
+                *buf = &[];
+                Ok(HelloMessage { message })
+            }
+            _ => Err(SolitaireError::Custom(0)),
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use borsh::BorshDeserialize;
+use bridge::{
+    vaa::SerializePayload,
+    PostedVAAData,
+    CHAIN_ID_SOLANA,
+};
+use hello_emitter::{
+    instructions,
+    EmitterAccount,
+    HelloMessage,
+    SayHelloBatchData,
+    SayHelloData,
+};
+use solana_program::pubkey::Pubkey;
+use solana_program_test::{
+    processor,
+    tokio,
+    BanksClient,
+    BanksClientError,
+    ProgramTest,
+};
+use solana_sdk::{
+    signature::{
+        Keypair,
+        Signer,
+    },
+    transaction::Transaction,
+};
+use solitaire::processors::seeded::Seeded;
+use std::env;
+
+/// Start a test environment with the core bridge initialized and the example program deployed.
+async fn set_up() -> (BanksClient, Keypair, Pubkey, Pubkey) {
+    let bridge = env::var("BRIDGE_PROGRAM")
+        .unwrap_or_else(|_| "Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o".to_string())
+        .parse::<Pubkey>()
+        .unwrap();
+    let program = Pubkey::new_unique();
+
+    let mut builder = ProgramTest::new("bridge", bridge, processor!(bridge::solitaire));
+    builder.add_program(
+        "hello_emitter",
+        program,
+        processor!(hello_emitter::solitaire),
+    );
+
+    let (mut client, payer, _) = builder.start().await;
+    let initialize =
+        bridge::instructions::initialize(bridge, payer.pubkey(), 50, 2_000_000_000, &[[1u8; 20]])
+            .unwrap();
+    execute(&mut client, &payer, &[], initialize).await.unwrap();
+
+    (client, payer, bridge, program)
+}
+
+async fn execute(
+    client: &mut BanksClient,
+    payer: &Keypair,
+    signers: &[&Keypair],
+    instruction: solana_program::instruction::Instruction,
+) -> Result<(), BanksClientError> {
+    let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+    let mut tx_signers = vec![payer];
+    tx_signers.extend_from_slice(signers);
+    let recent_blockhash = client.get_latest_blockhash().await?;
+    transaction.sign(&tx_signers, recent_blockhash);
+    client.process_transaction(transaction).await
+}
+
+#[tokio::test]
+async fn say_hello() {
+    let (ref mut client, ref payer, bridge, program) = set_up().await;
+    let emitter = EmitterAccount::key(None, &program);
+
+    for sequence in 0..2 {
+        let message = Keypair::new();
+        let greeting = format!("hello #{}", sequence).into_bytes();
+        let instruction = instructions::say_hello(
+            program,
+            bridge,
+            payer.pubkey(),
+            message.pubkey(),
+            SayHelloData {
+                nonce: 7,
+                message: greeting.clone(),
+            },
+        )
+        .unwrap();
+        execute(client, payer, &[&message], instruction)
+            .await
+            .unwrap();
+
+        // The core bridge recorded the greeting under the emitter of the example program.
+        let account = client.get_account(message.pubkey()).await.unwrap().unwrap();
+        assert_eq!(account.owner, bridge);
+        let posted = PostedVAAData::try_from_slice(&account.data).unwrap();
+        assert_eq!(posted.emitter_chain, CHAIN_ID_SOLANA);
+        assert_eq!(posted.emitter_address, emitter.to_bytes());
+        assert_eq!(posted.sequence, sequence);
+        assert_eq!(posted.nonce, 7);
+        assert_eq!(
+            posted.payload,
+            HelloMessage { message: greeting }.try_to_vec().unwrap()
+        );
+    }
+}
+
+#[tokio::test]
+async fn say_hello_nested() {
+    let (ref mut client, ref payer, bridge, program) = set_up().await;
+    let emitter = EmitterAccount::key(None, &program);
+
+    // Two greetings posted directly from the instructions of a transaction, followed by three
+    // posted from nested invocations of the program within one instruction.
+    let messages: Vec<Keypair> = (0..5).map(|_| Keypair::new()).collect();
+    let greetings: Vec<Vec<u8>> = (0..5)
+        .map(|i| format!("hello #{}", i).into_bytes())
+        .collect();
+    let mut ixs: Vec<_> = messages[..2]
+        .iter()
+        .zip(&greetings)
+        .map(|(message, greeting)| {
+            instructions::say_hello(
+                program,
+                bridge,
+                payer.pubkey(),
+                message.pubkey(),
+                SayHelloData {
+                    nonce: 7,
+                    message: greeting.clone(),
+                },
+            )
+            .unwrap()
+        })
+        .collect();
+    let batch: Vec<Pubkey> = messages[2..].iter().map(|m| m.pubkey()).collect();
+    ixs.push(
+        instructions::say_hello_batch(
+            program,
+            bridge,
+            payer.pubkey(),
+            &batch,
+            SayHelloBatchData {
+                nonce: 7,
+                messages: greetings[2..].to_vec(),
+            },
+        )
+        .unwrap(),
+    );
+
+    let mut transaction = Transaction::new_with_payer(&ixs, Some(&payer.pubkey()));
+    let mut signers = vec![payer];
+    signers.extend(messages.iter());
+    let recent_blockhash = client.get_latest_blockhash().await.unwrap();
+    transaction.sign(&signers, recent_blockhash);
+    client.process_transaction(transaction).await.unwrap();
+
+    // Every greeting got the next sequence of the emitter, in the order it was posted.
+    for (sequence, (message, greeting)) in messages.iter().zip(greetings).enumerate() {
+        let account = client.get_account(message.pubkey()).await.unwrap().unwrap();
+        let posted = PostedVAAData::try_from_slice(&account.data).unwrap();
+        assert_eq!(posted.emitter_address, emitter.to_bytes());
+        assert_eq!(posted.sequence, sequence as u64);
+        assert_eq!(
+            posted.payload,
+            HelloMessage { message: greeting }.try_to_vec().unwrap()
+        );
+    }
+}
@@ -0,0 +1,46 @@
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use solitaire::{
+    processors::seeded::{
+        AccountOwner,
+        Owned,
+        Seeded,
+    },
+    AccountState,
+    Data,
+};
+
+#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize)]
+pub struct ReceivedData {
+    /// Number of greetings received from the emitter.
+    pub count: u64,
+
+    /// Sequence of the last greeting received.
+    pub last_sequence: u64,
+}
+
+impl Owned for ReceivedData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+/// Greetings received from a single emitter, created with the first one.
+pub type Received<'b, const STATE: AccountState> = Data<'b, ReceivedData, { STATE }>;
+
+pub struct ReceivedDerivationData {
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+}
+
+impl<'b, const STATE: AccountState> Seeded<&ReceivedDerivationData> for Received<'b, { STATE }> {
+    fn seeds(data: &ReceivedDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            b"received".to_vec(),
+            data.emitter_chain.to_be_bytes().to_vec(),
+            data.emitter_address.to_vec(),
+        ]
+    }
+}
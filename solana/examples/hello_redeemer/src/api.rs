@@ -0,0 +1,9 @@
+pub mod migrate_claims;
+pub mod receive_hello;
+pub mod receive_hello_in_tree;
+pub mod receive_unreliable_hello;
+
+pub use migrate_claims::*;
+pub use receive_hello::*;
+pub use receive_hello_in_tree::*;
+pub use receive_unreliable_hello::*;
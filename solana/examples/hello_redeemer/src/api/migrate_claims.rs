@@ -0,0 +1,34 @@
+use bridge::accounts::claim::{
+    self,
+    ClaimDerivationData,
+};
+use solitaire::*;
+
+#[derive(FromAccounts)]
+pub struct MigrateClaims<'b> {
+    /// Receives the rent of the legacy claims and pays for the namespaced ones.
+    pub payer: Mut<Signer<Info<'b>>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct MigrateClaimsData {
+    /// Namespace the claims are moved into.
+    pub namespace: [u8; 32],
+
+    /// Claims to migrate, each followed in the remaining accounts by its legacy and namespaced
+    /// claim accounts.
+    pub claims: Vec<ClaimDerivationData>,
+}
+
+/// Move claims of received greetings into a namespace with [`claim::migrate`].
+///
+/// Programs expose this once they consume namespaced claims only, so that messages consumed
+/// before stay consumed. The hello redeemer still consumes legacy claims and only exposes it to
+/// exercise the migration.
+pub fn migrate_claims(
+    ctx: &ExecutionContext,
+    accs: &mut MigrateClaims,
+    data: MigrateClaimsData,
+) -> Result<()> {
+    claim::migrate(ctx, &accs.payer, data.namespace, &data.claims)
+}
@@ -0,0 +1,86 @@
+use crate::accounts::{
+    Received,
+    ReceivedDerivationData,
+};
+use bridge::{
+    accounts::claim::{
+        self,
+        Claim,
+    },
+    PayloadMessage,
+};
+use hello_emitter::HelloMessage;
+use solana_program::msg;
+use solitaire::{
+    processors::seeded::{
+        Creatable,
+        Seeded,
+    },
+    CreationLamports::Exempt,
+    *,
+};
+
+#[derive(FromAccounts)]
+pub struct ReceiveHello<'b> {
+    /// Pays for the claim and, with the first greeting of an emitter, its received account.
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Greeting posted to the core bridge
+    pub vaa: PayloadMessage<'b, HelloMessage>,
+
+    /// Claim of the VAA, prevents the greeting from being received twice
+    pub claim: Mut<Claim<'b>>,
+
+    /// Greetings received from the emitter of the VAA
+    pub received: Mut<Received<'b, { AccountState::MaybeInitialized }>>,
+}
+
+impl<'a> From<&ReceiveHello<'a>> for ReceivedDerivationData {
+    fn from(accs: &ReceiveHello<'a>) -> Self {
+        ReceivedDerivationData {
+            emitter_chain: accs.vaa.meta().emitter_chain,
+            emitter_address: accs.vaa.meta().emitter_address,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct ReceiveHelloData {}
+
+/// Receive a greeting from a VAA posted to the core bridge.
+///
+/// The VAA is trusted as far as the core bridge is: `PayloadMessage` checks that the account is
+/// owned by it, which only holds for VAAs whose signatures it verified. Greetings are counted per
+/// emitter, a program accepting messages from a single emitter would compare the VAA emitter
+/// against it instead.
+pub fn receive_hello(
+    ctx: &ExecutionContext,
+    accs: &mut ReceiveHello,
+    _data: ReceiveHelloData,
+) -> Result<()> {
+    let derivation_data: ReceivedDerivationData = (&*accs).into();
+    accs.received
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    // Prevent the greeting from being received again, recording in the claim how many greetings
+    // of the emitter had been received with it.
+    let count = accs.received.count + 1;
+    claim::consume_with_payload(
+        ctx,
+        accs.payer.key,
+        &mut accs.claim,
+        &accs.vaa,
+        &count.to_le_bytes(),
+    )?;
+
+    msg!("Hello: {}", String::from_utf8_lossy(&accs.vaa.message));
+
+    if !accs.received.is_initialized() {
+        accs.received
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+    }
+    accs.received.count = count;
+    accs.received.last_sequence = accs.vaa.meta().sequence;
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use bridge::{
+    accounts::claim_tree::{
+        consume_in_tree,
+        ClaimTree,
+    },
+    PayloadMessage,
+};
+use hello_emitter::HelloMessage;
+use solana_program::msg;
+use solitaire::*;
+
+#[derive(FromAccounts)]
+pub struct ReceiveHelloInTree<'b> {
+    /// Pays for the claim tree with the first greeting of its bucket, and as it grows.
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Greeting posted to the core bridge
+    pub vaa: PayloadMessage<'b, HelloMessage>,
+
+    /// Claim tree of the bucket of the VAA sequence
+    pub tree: Mut<ClaimTree<'b, { AccountState::MaybeInitialized }>>,
+
+    /// Claim of the VAA, which must not exist
+    pub claim: Info<'b>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct ReceiveHelloInTreeData {
+    /// Siblings of the leaf of the VAA in the claim tree, from the bottom up.
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// Receive a greeting, recording its VAA in a claim tree instead of claiming it.
+///
+/// Suits programs receiving many messages of few emitters, which pay the rent of a tree per bucket
+/// of sequences rather than of a claim per message. Greetings received with a claim before can't
+/// be received again.
+pub fn receive_hello_in_tree(
+    ctx: &ExecutionContext,
+    accs: &mut ReceiveHelloInTree,
+    data: ReceiveHelloInTreeData,
+) -> Result<()> {
+    // Prevent the greeting from being received again
+    consume_in_tree(
+        ctx,
+        accs.payer.key,
+        &mut accs.tree,
+        &accs.claim,
+        &accs.vaa,
+        &data.proof,
+    )?;
+
+    msg!("Hello: {}", String::from_utf8_lossy(&accs.vaa.message));
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use bridge::{
+    accounts::consumed_unreliable::{
+        consume_unreliable,
+        ConsumedUnreliable,
+    },
+    PayloadMessage,
+};
+use hello_emitter::HelloMessage;
+use solana_program::msg;
+use solitaire::*;
+
+#[derive(FromAccounts)]
+pub struct ReceiveUnreliableHello<'b> {
+    /// Pays for the consumed nonces of the emitter as they grow.
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Greeting posted to the core bridge
+    pub vaa: PayloadMessage<'b, HelloMessage>,
+
+    /// Nonces recently received from the emitter of the VAA
+    pub consumed: Mut<ConsumedUnreliable<'b, { AccountState::MaybeInitialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct ReceiveUnreliableHelloData {}
+
+/// Receive a greeting without claiming its VAA.
+///
+/// Greetings of an emitter are told apart by their nonce, only the most recent ones are
+/// remembered. This suits programs receiving many messages that tolerate old ones being replayed,
+/// and saves the rent of a claim per greeting.
+pub fn receive_unreliable_hello(
+    ctx: &ExecutionContext,
+    accs: &mut ReceiveUnreliableHello,
+    _data: ReceiveUnreliableHelloData,
+) -> Result<()> {
+    // Prevent the greeting from being received again while its nonce is remembered
+    consume_unreliable(ctx, accs.payer.key, &mut accs.consumed, &accs.vaa)?;
+
+    msg!("Hello: {}", String::from_utf8_lossy(&accs.vaa.message));
+
+    Ok(())
+}
@@ -0,0 +1,214 @@
+use crate::{
+    accounts::{
+        Received,
+        ReceivedDerivationData,
+    },
+    MigrateClaimsData,
+    ReceiveHelloData,
+    ReceiveHelloInTreeData,
+    ReceiveUnreliableHelloData,
+};
+use borsh::BorshSerialize;
+use bridge::{
+    accounts::{
+        claim,
+        Claim,
+        ClaimDerivationData,
+        ClaimTree,
+        ClaimTreeDerivationData,
+        ConsumedUnreliable,
+        ConsumedUnreliableDerivationData,
+        CLAIM_TREE_CAPACITY,
+    },
+    PostVAAData,
+};
+use solana_program::{
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    pubkey::Pubkey,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountState,
+};
+
+/// Required accounts
+///
+/// | name           | account                                                       | signer |
+/// |----------------+---------------------------------------------------------------+--------|
+/// | payer          | Pubkey                                                        | true   |
+/// | message        | PDA(bridge_id,  \["PostedVAA", vaa hash\])                    | false  |
+/// | claim          | PDA(program_id, \[emitter, emitter_chain, sequence\])         | false  |
+/// | received       | PDA(program_id, \["received", emitter_chain, emitter\])       | false  |
+/// | rent           | sysvar rent                                                   | false  |
+/// | system_program | system program                                                | false  |
+pub fn receive_hello(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+) -> solitaire::Result<Instruction> {
+    let claim_key = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: vaa.emitter_address,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+        },
+        &program_id,
+    );
+    let received_key = Received::<'_, { AccountState::MaybeInitialized }>::key(
+        &ReceivedDerivationData {
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+        },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(message_key, false),
+            AccountMeta::new(claim_key, false),
+            AccountMeta::new(received_key, false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: (
+            crate::instruction::Instruction::ReceiveHello,
+            ReceiveHelloData {},
+        )
+            .try_to_vec()?,
+    })
+}
+
+/// Required accounts
+///
+/// | name           | account                                                           | signer |
+/// |----------------+-------------------------------------------------------------------+--------|
+/// | payer          | Pubkey                                                            | true   |
+/// | message        | PDA(bridge_id,  \["PostedVAA", vaa hash\])                        | false  |
+/// | consumed       | PDA(program_id, \["ConsumedUnreliable", emitter_chain, emitter\]) | false  |
+/// | rent           | sysvar rent                                                       | false  |
+/// | system_program | system program                                                    | false  |
+pub fn receive_unreliable_hello(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+) -> solitaire::Result<Instruction> {
+    let consumed_key = ConsumedUnreliable::<'_, { AccountState::MaybeInitialized }>::key(
+        &ConsumedUnreliableDerivationData {
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+        },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(message_key, false),
+            AccountMeta::new(consumed_key, false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: (
+            crate::instruction::Instruction::ReceiveUnreliableHello,
+            ReceiveUnreliableHelloData {},
+        )
+            .try_to_vec()?,
+    })
+}
+
+/// Required accounts
+///
+/// | name           | account                                                          | signer |
+/// |----------------+------------------------------------------------------------------+--------|
+/// | payer          | Pubkey                                                           | true   |
+/// | system_program | system program                                                   | false  |
+/// | legacy claim   | PDA(program_id, \[emitter, emitter_chain, sequence\])            | false  |
+/// | claim          | PDA(program_id, \[namespace, emitter, emitter_chain, sequence\]) | false  |
+///
+/// Followed by the legacy and namespaced claim of every further claim.
+pub fn migrate_claims(
+    program_id: Pubkey,
+    payer: Pubkey,
+    namespace: [u8; 32],
+    claims: Vec<ClaimDerivationData>,
+) -> solitaire::Result<Instruction> {
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        // Dependencies
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+    ];
+    accounts.extend(claim::migration_accounts(&program_id, namespace, &claims));
+
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data: (
+            crate::instruction::Instruction::MigrateClaims,
+            MigrateClaimsData { namespace, claims },
+        )
+            .try_to_vec()?,
+    })
+}
+
+/// Required accounts
+///
+/// | name           | account                                                          | signer |
+/// |----------------+------------------------------------------------------------------+--------|
+/// | payer          | Pubkey                                                           | true   |
+/// | message        | PDA(bridge_id,  \["PostedVAA", vaa hash\])                       | false  |
+/// | tree           | PDA(program_id, \["ClaimTree", emitter_chain, emitter, bucket\]) | false  |
+/// | claim          | PDA(program_id, \[emitter, emitter_chain, sequence\])            | false  |
+/// | rent           | sysvar rent                                                      | false  |
+/// | system_program | system program                                                   | false  |
+pub fn receive_hello_in_tree(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    proof: Vec<[u8; 32]>,
+) -> solitaire::Result<Instruction> {
+    let tree_key = ClaimTree::<'_, { AccountState::MaybeInitialized }>::key(
+        &ClaimTreeDerivationData {
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+            bucket: vaa.sequence / CLAIM_TREE_CAPACITY,
+        },
+        &program_id,
+    );
+    let claim_key = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: vaa.emitter_address,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+        },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(message_key, false),
+            AccountMeta::new(tree_key, false),
+            AccountMeta::new_readonly(claim_key, false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: (
+            crate::instruction::Instruction::ReceiveHelloInTree,
+            ReceiveHelloInTreeData { proof },
+        )
+            .try_to_vec()?,
+    })
+}
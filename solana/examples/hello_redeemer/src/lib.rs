@@ -0,0 +1,39 @@
+//! Minimal program consuming Wormhole VAAs, kept as reference code for integrators.
+//!
+//! The program receives the greetings posted by the hello emitter once they have been signed by
+//! the guardians and posted to the core bridge, claiming each VAA so it can only be received once.
+//! It is built and exercised with the rest of the workspace, so changes to the core bridge VAA
+//! accounts break its tests rather than integrators.
+#![feature(adt_const_params)]
+#![allow(non_upper_case_globals)]
+#![allow(incomplete_features)]
+
+pub mod accounts;
+pub mod api;
+
+use solitaire::*;
+
+#[cfg(feature = "instructions")]
+pub mod instructions;
+
+pub use api::{
+    migrate_claims,
+    receive_hello,
+    receive_hello_in_tree,
+    receive_unreliable_hello,
+    MigrateClaims,
+    MigrateClaimsData,
+    ReceiveHello,
+    ReceiveHelloData,
+    ReceiveHelloInTree,
+    ReceiveHelloInTreeData,
+    ReceiveUnreliableHello,
+    ReceiveUnreliableHelloData,
+};
+
+solitaire! {
+    ReceiveHello => receive_hello,
+    ReceiveUnreliableHello => receive_unreliable_hello,
+    MigrateClaims => migrate_claims,
+    ReceiveHelloInTree => receive_hello_in_tree,
+}
@@ -0,0 +1,212 @@
+use borsh::BorshDeserialize;
+use bridge::{
+    accounts::{
+        PostedVAA,
+        PostedVAADerivationData,
+    },
+    PostVAAData,
+    PostedVAAData,
+};
+use byteorder::{
+    BigEndian,
+    WriteBytesExt,
+};
+use libsecp256k1::{
+    PublicKey,
+    SecretKey,
+};
+use sha3::Digest;
+use solana_program::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+};
+use solana_program_test::{
+    processor,
+    BanksClient,
+    BanksClientError,
+    ProgramTest,
+};
+use solana_sdk::{
+    secp256k1_instruction::new_secp256k1_instruction,
+    signature::{
+        Keypair,
+        Signer,
+    },
+    transaction::Transaction,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountState,
+};
+use std::{
+    env,
+    io::{
+        Cursor,
+        Write,
+    },
+};
+
+/// Programs deployed in the test environment.
+pub struct Programs {
+    pub bridge: Pubkey,
+    pub emitter: Pubkey,
+    pub redeemer: Pubkey,
+}
+
+/// Start a test environment with the core bridge and both example programs deployed.
+pub async fn setup() -> (BanksClient, Keypair, Programs) {
+    let programs = Programs {
+        bridge: env::var("BRIDGE_PROGRAM")
+            .unwrap_or_else(|_| "Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o".to_string())
+            .parse::<Pubkey>()
+            .unwrap(),
+        emitter: Pubkey::new_unique(),
+        redeemer: Pubkey::new_unique(),
+    };
+
+    let mut builder = ProgramTest::new("bridge", programs.bridge, processor!(bridge::solitaire));
+    builder.add_program(
+        "hello_emitter",
+        programs.emitter,
+        processor!(hello_emitter::solitaire),
+    );
+    builder.add_program(
+        "hello_redeemer",
+        programs.redeemer,
+        processor!(hello_redeemer::solitaire),
+    );
+
+    let (client, payer, _) = builder.start().await;
+    (client, payer, programs)
+}
+
+/// Simple API wrapper for quickly preparing and sending transactions.
+pub async fn execute(
+    client: &mut BanksClient,
+    payer: &Keypair,
+    signers: &[&Keypair],
+    instructions: &[Instruction],
+) -> Result<(), BanksClientError> {
+    let mut transaction = Transaction::new_with_payer(instructions, Some(&payer.pubkey()));
+    let mut tx_signers = vec![payer];
+    tx_signers.extend_from_slice(signers);
+    let recent_blockhash = client.get_latest_blockhash().await?;
+    transaction.sign(&tx_signers, recent_blockhash);
+    client.process_transaction(transaction).await
+}
+
+/// Fetch and deserialize account data.
+pub async fn get_account_data<T: BorshDeserialize>(client: &mut BanksClient, key: Pubkey) -> T {
+    let account = client.get_account(key).await.unwrap().unwrap();
+    T::try_from_slice(&account.data).unwrap()
+}
+
+/// Generate `count` guardian keys, along with their ethereum-styled addresses.
+pub fn generate_keys(count: u8) -> (Vec<[u8; 20]>, Vec<SecretKey>) {
+    let mut rng = rand::thread_rng();
+    let secret_keys: Vec<SecretKey> = std::iter::repeat_with(|| SecretKey::random(&mut rng))
+        .take(count as usize)
+        .collect();
+    let addresses = secret_keys
+        .iter()
+        .map(|key| {
+            let public_key = PublicKey::from_secret_key(key);
+            let mut h = sha3::Keccak256::default();
+            h.write_all(&public_key.serialize()[1..]).unwrap();
+            let key: [u8; 32] = h.finalize().into();
+            let mut address = [0u8; 20];
+            address.copy_from_slice(&key[12..]);
+            address
+        })
+        .collect();
+    (addresses, secret_keys)
+}
+
+/// Build the VAA the guardians would produce for a message posted to the core bridge.
+pub fn observe(message: &PostedVAAData) -> PostVAAData {
+    PostVAAData {
+        version: 0,
+        guardian_set_index: 0,
+        timestamp: message.submission_time,
+        nonce: message.nonce,
+        emitter_chain: message.emitter_chain,
+        emitter_address: message.emitter_address,
+        sequence: message.sequence,
+        consistency_level: message.consistency_level,
+        payload: message.payload.clone(),
+    }
+}
+
+/// Hash of the VAA body, the digest signed by the guardians.
+pub fn digest(vaa: &PostVAAData) -> [u8; 32] {
+    let mut body = Cursor::new(Vec::new());
+    body.write_u32::<BigEndian>(vaa.timestamp).unwrap();
+    body.write_u32::<BigEndian>(vaa.nonce).unwrap();
+    body.write_u16::<BigEndian>(vaa.emitter_chain).unwrap();
+    body.write_all(&vaa.emitter_address).unwrap();
+    body.write_u64::<BigEndian>(vaa.sequence).unwrap();
+    body.write_u8(vaa.consistency_level).unwrap();
+    body.write_all(&vaa.payload).unwrap();
+
+    let mut h = sha3::Keccak256::default();
+    h.write_all(&body.into_inner()).unwrap();
+    h.finalize().into()
+}
+
+/// Verify guardian signatures over the VAA and post it to the core bridge, returning the address
+/// of the posted VAA.
+pub async fn post_vaa(
+    client: &mut BanksClient,
+    bridge: Pubkey,
+    payer: &Keypair,
+    secret_keys: &[SecretKey],
+    vaa: PostVAAData,
+) -> Result<Pubkey, BanksClientError> {
+    let body = digest(&vaa);
+    let signature_set = Keypair::new();
+    for (i, key) in secret_keys.iter().enumerate() {
+        let mut signers = [-1; 19];
+        signers[i] = 0;
+        execute(
+            client,
+            payer,
+            &[&signature_set],
+            &[
+                new_secp256k1_instruction(key, &body),
+                bridge::instructions::verify_signatures(
+                    bridge,
+                    payer.pubkey(),
+                    0,
+                    signature_set.pubkey(),
+                    bridge::VerifySignaturesData {
+                        signers,
+                        hash: None,
+                        signer_offset: 0,
+                    },
+                )
+                .unwrap(),
+            ],
+        )
+        .await?;
+    }
+
+    execute(
+        client,
+        payer,
+        &[],
+        &[bridge::instructions::post_vaa(
+            bridge,
+            payer.pubkey(),
+            signature_set.pubkey(),
+            vaa,
+        )],
+    )
+    .await?;
+
+    Ok(PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        &bridge,
+    ))
+}
@@ -0,0 +1,416 @@
+use bridge::{
+    accounts::{
+        claim_tree::{
+            claim_tree_leaf,
+            claim_tree_proof,
+        },
+        Claim,
+        ClaimData,
+        ClaimDerivationData,
+        ClaimTree,
+        ClaimTreeData,
+        ClaimTreeDerivationData,
+        ConsumedUnreliable,
+        ConsumedUnreliableData,
+        ConsumedUnreliableDerivationData,
+        NamespacedClaimDerivationData,
+    },
+    CoreBridgeError,
+    PostVAAData,
+    PostedVAAData,
+};
+use hello_emitter::{
+    EmitterAccount,
+    SayHelloData,
+};
+use hello_redeemer::accounts::{
+    Received,
+    ReceivedData,
+    ReceivedDerivationData,
+};
+use libsecp256k1::SecretKey;
+use solana_program::{
+    pubkey::Pubkey,
+    system_instruction,
+};
+use solana_program_test::{
+    tokio,
+    BanksClient,
+    BanksClientError,
+};
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{
+        Keypair,
+        Signer,
+    },
+    transaction::TransactionError,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountState,
+};
+
+mod common;
+
+use common::Programs;
+
+/// Post a greeting through the emitter example and return the VAA the guardians would sign for it.
+async fn say_hello(
+    client: &mut BanksClient,
+    payer: &Keypair,
+    programs: &Programs,
+    greeting: &str,
+) -> PostVAAData {
+    let message = Keypair::new();
+    let instruction = hello_emitter::instructions::say_hello(
+        programs.emitter,
+        programs.bridge,
+        payer.pubkey(),
+        message.pubkey(),
+        SayHelloData {
+            nonce: 0,
+            message: greeting.as_bytes().to_vec(),
+        },
+    )
+    .unwrap();
+    common::execute(client, payer, &[&message], &[instruction])
+        .await
+        .unwrap();
+
+    let posted: PostedVAAData = common::get_account_data(client, message.pubkey()).await;
+    common::observe(&posted)
+}
+
+async fn redeem(
+    client: &mut BanksClient,
+    payer: &Keypair,
+    programs: &Programs,
+    guardian_keys: &[SecretKey],
+    vaa: PostVAAData,
+) -> Pubkey {
+    let message_key = common::post_vaa(client, programs.bridge, payer, guardian_keys, vaa.clone())
+        .await
+        .unwrap();
+    let instruction = hello_redeemer::instructions::receive_hello(
+        programs.redeemer,
+        payer.pubkey(),
+        message_key,
+        vaa,
+    )
+    .unwrap();
+    common::execute(client, payer, &[], &[instruction])
+        .await
+        .unwrap();
+    message_key
+}
+
+#[tokio::test]
+async fn receive_hello() {
+    let (ref mut client, ref payer, ref programs) = common::setup().await;
+    let (guardians, guardian_keys) = common::generate_keys(1);
+    let initialize = bridge::instructions::initialize(
+        programs.bridge,
+        payer.pubkey(),
+        50,
+        2_000_000_000,
+        &guardians,
+    )
+    .unwrap();
+    common::execute(client, payer, &[], &[initialize])
+        .await
+        .unwrap();
+
+    let emitter = EmitterAccount::key(None, &programs.emitter);
+    let received_key = Received::<'_, { AccountState::MaybeInitialized }>::key(
+        &ReceivedDerivationData {
+            emitter_chain: bridge::CHAIN_ID_SOLANA,
+            emitter_address: emitter.to_bytes(),
+        },
+        &programs.redeemer,
+    );
+
+    // Greetings make their way from the emitter to the redeemer through the core bridge.
+    for sequence in 0..2 {
+        let vaa = say_hello(client, payer, programs, "hello").await;
+        assert_eq!(vaa.emitter_address, emitter.to_bytes());
+        assert_eq!(vaa.sequence, sequence);
+        let message_key = redeem(client, payer, programs, &guardian_keys, vaa.clone()).await;
+
+        let received: ReceivedData = common::get_account_data(client, received_key).await;
+        assert_eq!(received.count, sequence + 1);
+        assert_eq!(received.last_sequence, sequence);
+
+        // The claim records the count the greeting was received with.
+        let claim_key = Claim::<'_>::key(
+            &ClaimDerivationData {
+                emitter_address: emitter.to_bytes(),
+                emitter_chain: bridge::CHAIN_ID_SOLANA,
+                sequence,
+            },
+            &programs.redeemer,
+        );
+        let claim: ClaimData = common::get_account_data(client, claim_key).await;
+        assert!(claim.claimed);
+        assert_eq!(claim.payload, (sequence + 1).to_le_bytes());
+
+        // A greeting can only be received once, no matter who relays it.
+        let relayer = Keypair::new();
+        let fund = system_instruction::transfer(&payer.pubkey(), &relayer.pubkey(), 1_000_000_000);
+        common::execute(client, payer, &[], &[fund]).await.unwrap();
+        let instruction = hello_redeemer::instructions::receive_hello(
+            programs.redeemer,
+            relayer.pubkey(),
+            message_key,
+            vaa,
+        )
+        .unwrap();
+        assert!(common::execute(client, &relayer, &[], &[instruction])
+            .await
+            .is_err());
+    }
+}
+
+#[tokio::test]
+async fn receive_unreliable_hello() {
+    let (ref mut client, ref payer, ref programs) = common::setup().await;
+    let (guardians, guardian_keys) = common::generate_keys(1);
+    let initialize = bridge::instructions::initialize(
+        programs.bridge,
+        payer.pubkey(),
+        50,
+        2_000_000_000,
+        &guardians,
+    )
+    .unwrap();
+    common::execute(client, payer, &[], &[initialize])
+        .await
+        .unwrap();
+
+    let emitter = EmitterAccount::key(None, &programs.emitter);
+    let consumed_key = ConsumedUnreliable::<'_, { AccountState::MaybeInitialized }>::key(
+        &ConsumedUnreliableDerivationData {
+            emitter_chain: bridge::CHAIN_ID_SOLANA,
+            emitter_address: emitter.to_bytes(),
+        },
+        &programs.redeemer,
+    );
+    let greeting = say_hello(client, payer, programs, "hello").await;
+
+    // Observations of the same nonce are only received once, even when they make a different VAA.
+    for (nonce, timestamp, received) in [(0, 0, true), (0, 1, false), (1, 0, true)] {
+        let mut vaa = greeting.clone();
+        vaa.nonce = nonce;
+        vaa.timestamp += timestamp;
+        let message_key =
+            common::post_vaa(client, programs.bridge, payer, &guardian_keys, vaa.clone())
+                .await
+                .unwrap();
+        let instruction = hello_redeemer::instructions::receive_unreliable_hello(
+            programs.redeemer,
+            payer.pubkey(),
+            message_key,
+            vaa,
+        )
+        .unwrap();
+        let result = common::execute(client, payer, &[], &[instruction]).await;
+
+        if received {
+            result.unwrap();
+        } else {
+            assert!(matches!(
+                result,
+                Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+                    _,
+                    InstructionError::Custom(code),
+                ))) if CoreBridgeError::from_code(code)
+                    == Some(CoreBridgeError::UnreliableMessageConsumed)
+            ));
+        }
+    }
+
+    let consumed: ConsumedUnreliableData = common::get_account_data(client, consumed_key).await;
+    assert_eq!(consumed.nonces, vec![0, 1]);
+}
+
+#[tokio::test]
+async fn migrate_claims() {
+    let (ref mut client, ref payer, ref programs) = common::setup().await;
+    let (guardians, guardian_keys) = common::generate_keys(1);
+    let initialize = bridge::instructions::initialize(
+        programs.bridge,
+        payer.pubkey(),
+        50,
+        2_000_000_000,
+        &guardians,
+    )
+    .unwrap();
+    common::execute(client, payer, &[], &[initialize])
+        .await
+        .unwrap();
+
+    let emitter = EmitterAccount::key(None, &programs.emitter);
+    let namespace = emitter.to_bytes();
+    let mut claims = vec![];
+    for _ in 0..2 {
+        let vaa = say_hello(client, payer, programs, "hello").await;
+        claims.push(ClaimDerivationData {
+            emitter_address: vaa.emitter_address,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+        });
+        redeem(client, payer, programs, &guardian_keys, vaa).await;
+    }
+
+    let migrate = |claims: Vec<ClaimDerivationData>| {
+        hello_redeemer::instructions::migrate_claims(
+            programs.redeemer,
+            payer.pubkey(),
+            namespace,
+            claims,
+        )
+        .unwrap()
+    };
+    common::execute(client, payer, &[], &[migrate(claims.clone())])
+        .await
+        .unwrap();
+
+    // The claims moved into the namespace with their payload, the legacy claims are closed.
+    for (count, claim) in (1u64..).zip(&claims) {
+        let legacy_key = Claim::<'_>::key(claim, &programs.redeemer);
+        assert!(client.get_account(legacy_key).await.unwrap().is_none());
+
+        let namespaced_key = Claim::<'_>::key(
+            &NamespacedClaimDerivationData {
+                namespace,
+                claim: *claim,
+            },
+            &programs.redeemer,
+        );
+        let migrated: ClaimData = common::get_account_data(client, namespaced_key).await;
+        assert!(migrated.claimed);
+        assert_eq!(migrated.payload, count.to_le_bytes());
+    }
+
+    // Claims without a legacy claim to prove them, such as migrated ones, cannot be migrated.
+    let result = common::execute(client, payer, &[], &[migrate(claims[..1].to_vec())]).await;
+    assert!(matches!(
+        result,
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        ))) if CoreBridgeError::from_code(code) == Some(CoreBridgeError::InvalidLegacyClaim)
+    ));
+}
+
+#[tokio::test]
+async fn receive_hello_in_tree() {
+    let (ref mut client, ref payer, ref programs) = common::setup().await;
+    let (guardians, guardian_keys) = common::generate_keys(1);
+    let initialize = bridge::instructions::initialize(
+        programs.bridge,
+        payer.pubkey(),
+        50,
+        2_000_000_000,
+        &guardians,
+    )
+    .unwrap();
+    common::execute(client, payer, &[], &[initialize])
+        .await
+        .unwrap();
+
+    let emitter = EmitterAccount::key(None, &programs.emitter);
+    let tree_key = ClaimTree::<'_, { AccountState::MaybeInitialized }>::key(
+        &ClaimTreeDerivationData {
+            emitter_chain: bridge::CHAIN_ID_SOLANA,
+            emitter_address: emitter.to_bytes(),
+            bucket: 0,
+        },
+        &programs.redeemer,
+    );
+    let receive = |payer: Pubkey, message_key: Pubkey, vaa: PostVAAData, proof: Vec<[u8; 32]>| {
+        hello_redeemer::instructions::receive_hello_in_tree(
+            programs.redeemer,
+            payer,
+            message_key,
+            vaa,
+            proof,
+        )
+        .unwrap()
+    };
+    let error_code = |result: Result<(), BanksClientError>| match result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        ))) => CoreBridgeError::from_code(code),
+        _ => None,
+    };
+
+    // A greeting received with a claim stays received.
+    let claimed = say_hello(client, payer, programs, "hello").await;
+    let claimed_key = redeem(client, payer, programs, &guardian_keys, claimed.clone()).await;
+    let instruction = receive(
+        payer.pubkey(),
+        claimed_key,
+        claimed.clone(),
+        claim_tree_proof(&[], claimed.sequence as u32),
+    );
+    let result = common::execute(client, payer, &[], &[instruction]).await;
+    assert_eq!(
+        error_code(result),
+        Some(CoreBridgeError::LegacyClaimConsumed)
+    );
+
+    // Both proofs are built against the empty tree, the second is brought up to date with the
+    // leaf the first one consumed.
+    let mut received = vec![];
+    for _ in 0..2 {
+        let vaa = say_hello(client, payer, programs, "hello").await;
+        let message_key =
+            common::post_vaa(client, programs.bridge, payer, &guardian_keys, vaa.clone())
+                .await
+                .unwrap();
+        let proof = claim_tree_proof(&[], vaa.sequence as u32);
+        common::execute(
+            client,
+            payer,
+            &[],
+            &[receive(payer.pubkey(), message_key, vaa.clone(), proof)],
+        )
+        .await
+        .unwrap();
+        received.push((message_key, vaa));
+    }
+
+    let tree: ClaimTreeData = common::get_account_data(client, tree_key).await;
+    assert_eq!(tree.count, 2);
+    for (change, (_, vaa)) in tree.changes.iter().zip(&received) {
+        assert_eq!(change.index, vaa.sequence as u32);
+        assert_eq!(change.path[0], claim_tree_leaf(&common::digest(vaa)));
+    }
+
+    // Greetings can only be received once, with stale proofs or ones against the current root.
+    let leaves: Vec<(u32, [u8; 32])> = tree
+        .changes
+        .iter()
+        .map(|change| (change.index, change.path[0]))
+        .collect();
+    let relayer = Keypair::new();
+    let fund = system_instruction::transfer(&payer.pubkey(), &relayer.pubkey(), 1_000_000_000);
+    common::execute(client, payer, &[], &[fund]).await.unwrap();
+    let (message_key, vaa) = received[0].clone();
+    for (proof, error) in [
+        (
+            claim_tree_proof(&[], vaa.sequence as u32),
+            CoreBridgeError::ClaimTreeLeafConsumed,
+        ),
+        (
+            claim_tree_proof(&leaves, vaa.sequence as u32),
+            CoreBridgeError::ClaimTreeProofInvalid,
+        ),
+    ] {
+        let instruction = receive(relayer.pubkey(), message_key, vaa.clone(), proof);
+        let result = common::execute(client, &relayer, &[], &[instruction]).await;
+        assert_eq!(error_code(result), Some(error));
+    }
+}
@@ -0,0 +1,403 @@
+//! Governance decrees executed by the Wormhole Solana programs.
+//!
+//! Guardian tooling drafting governance proposals and the programs validating the resulting VAAs
+//! take the decree layouts from here, so that both sides agree on every byte. A governance payload
+//! is a header naming the module, action and target chain of the decree, followed by its body:
+//!
+//! | field  | size | encoding                               |
+//! |--------+------+----------------------------------------|
+//! | module | 32   | module name, left padded with zeros    |
+//! | action | 1    | [`Decree::ACTION`]                     |
+//! | chain  | 2    | big endian target chain, 0 for all     |
+//! | body   | *    | [`Decree::encode`]                     |
+//!
+//! Decrees are serde serializable for proposal files, integers beyond 64 bits as hex strings.
+//! Decoding validates them through the zero-copy views of `wormhole_vaa::governance`, the only
+//! parser of decree bytes.
+
+use primitive_types::U256;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solana_program::pubkey::Pubkey;
+use std::io::{
+    Error,
+    ErrorKind::{
+        InvalidData,
+        UnexpectedEof,
+    },
+    Result,
+    Write,
+};
+use wormhole_vaa::{
+    governance as view,
+    GovernanceHeader,
+};
+
+/// Governance modules of the Solana programs.
+pub mod module {
+    /// Core bridge.
+    pub const CORE: &str = "Core";
+
+    /// Token bridge.
+    pub const TOKEN_BRIDGE: &str = "TokenBridge";
+
+    /// NFT bridge.
+    pub const NFT_BRIDGE: &str = "NFTBridge";
+}
+
+/// Ethereum style guardian key.
+pub type GuardianPublicKey = [u8; 20];
+
+/// Compressed secp256r1 guardian key.
+pub type Secp256r1PublicKey = [u8; 33];
+
+/// Decree of a governance module, identified within the module by its action.
+pub trait Decree: Sized {
+    const ACTION: u8;
+
+    /// Write the body of the decree.
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<()>;
+
+    /// Read a decree spanning all of `body`.
+    fn decode_body(body: &[u8]) -> Result<Self>;
+}
+
+/// Module name as encoded in governance headers.
+pub fn module_id(module: &str) -> [u8; 32] {
+    let mut id = [0u8; 32];
+    id[32 - module.len()..].copy_from_slice(module.as_bytes());
+    id
+}
+
+/// Governance payload of `decree` for `module`, targeting `chain`.
+pub fn encode<D: Decree>(module: &str, chain: u16, decree: &D) -> Result<Vec<u8>> {
+    let mut payload = Vec::with_capacity(256);
+    payload.write_all(&module_id(module))?;
+    payload.write_all(&[D::ACTION])?;
+    payload.write_all(&chain.to_be_bytes())?;
+    decree.encode(&mut payload)?;
+    Ok(payload)
+}
+
+/// Decree of `module` in a governance payload, which must target `chain` or all chains.
+pub fn decode<D: Decree>(module: &str, chain: u16, payload: &[u8]) -> Result<D> {
+    let header = GovernanceHeader::parse(payload).map_err(view_error)?;
+    if !header.is_module(module) {
+        return Err(Error::new(InvalidData, "invalid governance module"));
+    }
+    if header.action != D::ACTION {
+        return Err(Error::new(InvalidData, "invalid governance action"));
+    }
+    if header.chain != chain && header.chain != 0 {
+        return Err(Error::new(InvalidData, "invalid governance chain"));
+    }
+    D::decode_body(header.body)
+}
+
+/// Core: upgrade the program to a new implementation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractUpgrade {
+    /// Address of the new Implementation
+    pub new_contract: Pubkey,
+
+    /// Semantic version of the new implementation, recorded in the version registry. Encoded as an
+    /// optional extension after the address: a length byte followed by the UTF-8 version string.
+    pub version: Option<String>,
+
+    /// SHA-256 of the program in the buffer, the hash of the shared object deployed to it. Encoded
+    /// as an optional extension after the version, which is then written even when empty.
+    #[serde(default)]
+    pub buffer_hash: Option<[u8; 32]>,
+}
+
+impl Decree for ContractUpgrade {
+    const ACTION: u8 = 1;
+
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&self.new_contract.to_bytes())?;
+        if self.version.is_some() || self.buffer_hash.is_some() {
+            let version = self.version.as_deref().unwrap_or_default();
+            writer.write_all(&[version.len() as u8])?;
+            writer.write_all(version.as_bytes())?;
+        }
+        if let Some(buffer_hash) = &self.buffer_hash {
+            writer.write_all(buffer_hash)?;
+        }
+        Ok(())
+    }
+
+    fn decode_body(body: &[u8]) -> Result<Self> {
+        let upgrade = view::ContractUpgrade::parse(body).map_err(view_error)?;
+        Ok(ContractUpgrade {
+            new_contract: Pubkey::new_from_array(*upgrade.new_contract),
+            version: upgrade.version.map(str::to_string),
+            buffer_hash: upgrade.buffer_hash.copied(),
+        })
+    }
+}
+
+/// Core: hand over to a new guardian set.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuardianSetUpdate {
+    /// New GuardianSetIndex
+    pub new_guardian_set_index: u32,
+
+    /// New GuardianSet
+    pub new_guardian_set: Vec<GuardianPublicKey>,
+
+    /// Secp256r1 keys of the new guardians, indexed like `new_guardian_set` and zeroed for
+    /// secp256k1 guardians. Appended as an extension with its own length only when non-empty,
+    /// chains without secp256r1 guardians reject it so such upgrades must target Solana.
+    #[serde(default, with = "secp256r1_keys")]
+    pub new_secp256r1_keys: Vec<Secp256r1PublicKey>,
+}
+
+impl Decree for GuardianSetUpdate {
+    const ACTION: u8 = 2;
+
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&self.new_guardian_set_index.to_be_bytes())?;
+        writer.write_all(&[self.new_guardian_set.len() as u8])?;
+        for key in self.new_guardian_set.iter() {
+            writer.write_all(key)?;
+        }
+        if !self.new_secp256r1_keys.is_empty() {
+            writer.write_all(&[self.new_secp256r1_keys.len() as u8])?;
+            for key in self.new_secp256r1_keys.iter() {
+                writer.write_all(key)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn decode_body(body: &[u8]) -> Result<Self> {
+        let update = view::GuardianSetUpdate::parse(body).map_err(view_error)?;
+        Ok(GuardianSetUpdate {
+            new_guardian_set_index: update.new_guardian_set_index,
+            new_guardian_set: update.keys().copied().collect(),
+            new_secp256r1_keys: update.secp256r1_keys().copied().collect(),
+        })
+    }
+}
+
+/// Core: set the fee for posting messages.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetFee {
+    /// New fee in lamports
+    pub fee: U256,
+}
+
+impl Decree for SetFee {
+    const ACTION: u8 = 3;
+
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&u256_to_be_bytes(self.fee))
+    }
+
+    fn decode_body(body: &[u8]) -> Result<Self> {
+        let set_fee = view::SetFee::parse(body).map_err(view_error)?;
+        Ok(SetFee {
+            fee: U256::from_big_endian(set_fee.fee),
+        })
+    }
+}
+
+/// Core: pay out collected fees.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferFees {
+    /// Amount to be transferred
+    pub amount: U256,
+
+    /// Recipient
+    pub to: [u8; 32],
+}
+
+impl Decree for TransferFees {
+    const ACTION: u8 = 4;
+
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&u256_to_be_bytes(self.amount))?;
+        writer.write_all(&self.to)
+    }
+
+    fn decode_body(body: &[u8]) -> Result<Self> {
+        let transfer = view::TransferFees::parse(body).map_err(view_error)?;
+        Ok(TransferFees {
+            amount: U256::from_big_endian(transfer.amount),
+            to: *transfer.to,
+        })
+    }
+}
+
+/// Token and NFT bridge: register the bridge endpoint of a foreign chain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegisterChain {
+    /// Chain ID of the chain to be registered
+    pub chain: u16,
+
+    /// Address of the endpoint on the chain
+    pub endpoint_address: [u8; 32],
+}
+
+impl Decree for RegisterChain {
+    const ACTION: u8 = 1;
+
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&self.chain.to_be_bytes())?;
+        writer.write_all(&self.endpoint_address)
+    }
+
+    fn decode_body(body: &[u8]) -> Result<Self> {
+        let register = view::RegisterChain::parse(body).map_err(view_error)?;
+        Ok(RegisterChain {
+            chain: register.chain,
+            endpoint_address: *register.endpoint_address,
+        })
+    }
+}
+
+/// Truncated decrees fail like reading past their end would, other malformed decrees as invalid
+/// data.
+fn view_error(e: wormhole_vaa::Error) -> Error {
+    match e {
+        wormhole_vaa::Error::Truncated => UnexpectedEof.into(),
+        e => Error::new(InvalidData, format!("{:?}", e)),
+    }
+}
+
+fn u256_to_be_bytes(value: U256) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    bytes
+}
+
+// Serde only derives arrays of up to 32 elements, secp256r1 keys are written as byte sequences.
+mod secp256r1_keys {
+    use super::Secp256r1PublicKey;
+    use serde::{
+        de::Error,
+        Deserialize,
+        Deserializer,
+        Serializer,
+    };
+    use std::convert::TryInto;
+
+    pub fn serialize<S: Serializer>(
+        keys: &[Secp256r1PublicKey],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(keys.iter().map(|key| &key[..]))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Secp256r1PublicKey>, D::Error> {
+        Vec::<Vec<u8>>::deserialize(deserializer)?
+            .into_iter()
+            .map(|key| {
+                key.try_into()
+                    .map_err(|_| D::Error::custom("secp256r1 keys are 33 bytes"))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip<D: Decree + PartialEq + std::fmt::Debug>(module: &str, decree: D) {
+        let payload = encode(module, 1, &decree).unwrap();
+        assert_eq!(payload[..32], module_id(module));
+        assert_eq!(payload[32], D::ACTION);
+        assert_eq!(decode::<D>(module, 1, &payload).unwrap(), decree);
+
+        // Decrees for other chains or modules are rejected.
+        assert!(decode::<D>(module, 2, &payload).is_err());
+        assert!(decode::<D>(module, 1, &encode("Other", 1, &decree).unwrap()).is_err());
+
+        // Decrees span the whole payload.
+        let mut trailing = payload;
+        trailing.push(0);
+        assert!(decode::<D>(module, 1, &trailing).is_err());
+    }
+
+    #[test]
+    fn test_round_trips() {
+        round_trip(
+            module::CORE,
+            ContractUpgrade {
+                new_contract: Pubkey::new_unique(),
+                version: Some("1.2.3".to_string()),
+                buffer_hash: None,
+            },
+        );
+        round_trip(
+            module::CORE,
+            ContractUpgrade {
+                new_contract: Pubkey::new_unique(),
+                version: None,
+                buffer_hash: Some([6u8; 32]),
+            },
+        );
+        round_trip(
+            module::CORE,
+            GuardianSetUpdate {
+                new_guardian_set_index: 2,
+                new_guardian_set: vec![[1u8; 20], [2u8; 20]],
+                new_secp256r1_keys: vec![],
+            },
+        );
+        round_trip(
+            module::CORE,
+            SetFee {
+                fee: U256::from(500),
+            },
+        );
+        round_trip(
+            module::CORE,
+            TransferFees {
+                amount: U256::from(1234),
+                to: [3u8; 32],
+            },
+        );
+        round_trip(
+            module::TOKEN_BRIDGE,
+            RegisterChain {
+                chain: 2,
+                endpoint_address: [4u8; 32],
+            },
+        );
+    }
+
+    #[test]
+    fn test_optional_extensions() {
+        let upgrade = ContractUpgrade::decode_body(&[5u8; 32]).unwrap();
+        assert_eq!(upgrade.version, None);
+        assert_eq!(upgrade.buffer_hash, None);
+
+        let mut body = vec![5u8; 32];
+        body.extend_from_slice(&[5, b'1', b'.', b'2', b'.', b'3']);
+        body.extend_from_slice(&[6u8; 32]);
+        let upgrade = ContractUpgrade::decode_body(&body).unwrap();
+        assert_eq!(upgrade.version.as_deref(), Some("1.2.3"));
+        assert_eq!(upgrade.buffer_hash, Some([6u8; 32]));
+
+        // The hash is the whole remainder.
+        body.pop();
+        assert!(ContractUpgrade::decode_body(&body).is_err());
+
+        let mut body = vec![0, 0, 0, 1, 1];
+        body.extend_from_slice(&[1u8; 20]);
+        body.push(1);
+        body.extend_from_slice(&[2u8; 33]);
+        let update = GuardianSetUpdate::decode_body(&body).unwrap();
+        assert_eq!(update.new_secp256r1_keys, vec![[2u8; 33]]);
+
+        // The secp256r1 extension covers every guardian.
+        body[25] = 2;
+        assert!(GuardianSetUpdate::decode_body(&body).is_err());
+    }
+}
@@ -1,9 +1,11 @@
 pub mod complete_transfer;
+pub mod compressed;
 pub mod governance;
 pub mod initialize;
 pub mod transfer;
 
 pub use complete_transfer::*;
+pub use compressed::*;
 pub use governance::*;
 pub use initialize::*;
 pub use transfer::*;
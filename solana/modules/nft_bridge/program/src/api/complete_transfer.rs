@@ -26,14 +26,10 @@ use bridge::{
     PayloadMessage,
     CHAIN_ID_SOLANA,
 };
-use solana_program::{
-    account_info::AccountInfo,
-    program::{
-        invoke,
-        invoke_signed,
-    },
-};
+use solana_program::account_info::AccountInfo;
 use solitaire::{
+    invoke,
+    invoke_signed,
     processors::seeded::{
         invoke_seeded,
         Seeded,
@@ -0,0 +1,517 @@
+//! Bridging of compressed NFTs, see [`crate::compressed`].
+//!
+//! Native compressed NFTs are held in custody by being transferred to the custody signer, wrapped
+//! ones are minted into trees of the bridge and burned when sent back. Leaves are located by the
+//! [`CompressedLeaf`] in the instruction data and the proof accounts at the end of the
+//! instruction.
+
+use crate::{
+    accounts::{
+        ConfigAccount,
+        CoreBridge,
+        CustodySigner,
+        EmitterAccount,
+        Endpoint,
+        EndpointDerivationData,
+        MintSigner,
+        WrappedMetaDerivationData,
+        WrappedTokenMeta,
+    },
+    compressed::{
+        self,
+        CompressedLeaf,
+        MetadataArgs,
+        TreeConfig,
+        COMPRESSED_TOKEN_ADDRESS,
+    },
+    messages::PayloadTransfer,
+    types::*,
+    TokenBridgeError::*,
+};
+use bridge::{
+    accounts::claim::{
+        self,
+        Claim,
+    },
+    api::PostMessageData,
+    types::ConsistencyLevel,
+    vaa::SerializePayload,
+    PayloadMessage,
+    CHAIN_ID_SOLANA,
+};
+use primitive_types::U256;
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    sysvar::clock::Clock,
+};
+use solitaire::{
+    invoke,
+    processors::seeded::{
+        invoke_seeded,
+        Seeded,
+    },
+    CreationLamports::Exempt,
+    *,
+};
+
+#[derive(FromAccounts)]
+pub struct CreateCompressedTree<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    /// Bubblegum config of the tree, created by Bubblegum
+    pub tree_config: Mut<Info<'b>>,
+
+    /// Tree account, allocated and assigned to the account compression program beforehand
+    pub merkle_tree: Mut<Info<'b>>,
+
+    pub mint_authority: MintSigner<'b>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct CreateCompressedTreeData {
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+}
+
+/// Create a tree wrapped compressed NFTs can be minted into. Anyone can create trees, at the cost
+/// of the tree account.
+pub fn create_compressed_tree(
+    ctx: &ExecutionContext,
+    accs: &mut CreateCompressedTree,
+    data: CreateCompressedTreeData,
+) -> Result<()> {
+    compressed::verify_enabled()?;
+
+    let ix = compressed::create_tree(
+        *accs.merkle_tree.key,
+        *accs.payer.key,
+        *accs.mint_authority.key,
+        data.max_depth,
+        data.max_buffer_size,
+    )?;
+    invoke_seeded(&ix, ctx, &accs.mint_authority, None)?;
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct TransferNativeCompressed<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    pub tree_config: Info<'b>,
+    pub merkle_tree: Mut<Info<'b>>,
+
+    /// Owner of the NFT
+    pub leaf_owner: Signer<Info<'b>>,
+
+    pub custody_signer: CustodySigner<'b>,
+
+    /// CPI Context
+    pub bridge: Mut<CoreBridge<'b, { AccountState::Initialized }>>,
+
+    /// Account to store the posted message
+    pub message: Signer<Mut<Info<'b>>>,
+
+    /// Emitter of the VAA
+    pub emitter: EmitterAccount<'b>,
+
+    /// Tracker for the emitter sequence
+    pub sequence: Mut<Info<'b>>,
+
+    /// Account to collect tx fee
+    pub fee_collector: Mut<Info<'b>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct TransferNativeCompressedData {
+    pub nonce: u32,
+    pub target_address: Address,
+    pub target_chain: ChainID,
+    pub leaf: CompressedLeaf,
+}
+
+pub fn transfer_native_compressed(
+    ctx: &ExecutionContext,
+    accs: &mut TransferNativeCompressed,
+    data: TransferNativeCompressedData,
+) -> Result<()> {
+    compressed::verify_enabled()?;
+
+    // Wrapped compressed NFTs live in trees of the bridge
+    let tree = TreeConfig::load(&accs.tree_config, accs.merkle_tree.key)?;
+    if tree.tree_creator == MintSigner::key(None, ctx.program_id) {
+        return Err(TokenNotNative.into());
+    }
+
+    // Move the NFT into custody, Bubblegum verifies the leaf and thereby its metadata
+    let proof = data.leaf.proof(ctx.accounts)?;
+    let transfer_ix = compressed::transfer(
+        *accs.merkle_tree.key,
+        *accs.leaf_owner.key,
+        *accs.custody_signer.key,
+        &data.leaf,
+        &proof,
+    )?;
+    invoke(&transfer_ix, ctx.accounts)?;
+
+    // Pay fee
+    let transfer_ix = solana_program::system_instruction::transfer(
+        accs.payer.key,
+        accs.fee_collector.key,
+        accs.bridge.config.fee,
+    );
+    invoke(&transfer_ix, ctx.accounts)?;
+
+    // Post message
+    // Like SPL NFTs, all compressed NFTs share a static token_address, the asset id is encoded in
+    // the token_id.
+    let asset_id = compressed::asset_id(accs.merkle_tree.key, data.leaf.nonce);
+    let metadata = data.leaf.metadata;
+    let payload = PayloadTransfer {
+        token_address: COMPRESSED_TOKEN_ADDRESS,
+        token_chain: CHAIN_ID_SOLANA,
+        to: data.target_address,
+        to_chain: data.target_chain,
+        symbol: metadata.symbol,
+        name: metadata.name,
+        uri: metadata.uri,
+        token_id: U256::from_big_endian(&asset_id.to_bytes()),
+    };
+    let params = (
+        bridge::instruction::Instruction::PostMessage,
+        PostMessageData {
+            nonce: data.nonce,
+            payload: payload.try_to_vec()?,
+            consistency_level: ConsistencyLevel::Finalized,
+        },
+    );
+
+    let ix = Instruction::new_with_bytes(
+        accs.config.wormhole_bridge,
+        params.try_to_vec()?.as_slice(),
+        vec![
+            AccountMeta::new(*accs.bridge.info().key, false),
+            AccountMeta::new(*accs.message.key, true),
+            AccountMeta::new_readonly(*accs.emitter.key, true),
+            AccountMeta::new(*accs.sequence.key, false),
+            AccountMeta::new(*accs.payer.key, true),
+            AccountMeta::new(*accs.fee_collector.key, false),
+            AccountMeta::new_readonly(*accs.clock.info().key, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+        ],
+    );
+    invoke_seeded(&ix, ctx, &accs.emitter, None)?;
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct TransferWrappedCompressed<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    pub tree_config: Info<'b>,
+    pub merkle_tree: Mut<Info<'b>>,
+
+    /// Owner of the NFT
+    pub leaf_owner: Signer<Info<'b>>,
+
+    pub wrapped_meta: WrappedTokenMeta<'b, { AccountState::Initialized }>,
+
+    /// CPI Context
+    pub bridge: Mut<CoreBridge<'b, { AccountState::Initialized }>>,
+
+    /// Account to store the posted message
+    pub message: Signer<Mut<Info<'b>>>,
+
+    /// Emitter of the VAA
+    pub emitter: EmitterAccount<'b>,
+
+    /// Tracker for the emitter sequence
+    pub sequence: Mut<Info<'b>>,
+
+    /// Account to collect tx fee
+    pub fee_collector: Mut<Info<'b>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct TransferWrappedCompressedData {
+    pub nonce: u32,
+    pub target_address: Address,
+    pub target_chain: ChainID,
+    pub leaf: CompressedLeaf,
+}
+
+pub fn transfer_wrapped_compressed(
+    ctx: &ExecutionContext,
+    accs: &mut TransferWrappedCompressed,
+    data: TransferWrappedCompressedData,
+) -> Result<()> {
+    compressed::verify_enabled()?;
+
+    let tree = TreeConfig::load(&accs.tree_config, accs.merkle_tree.key)?;
+    if tree.tree_creator != MintSigner::key(None, ctx.program_id) {
+        return Err(InvalidCompressedTree.into());
+    }
+
+    // Verify that meta is correct
+    let asset_id = compressed::asset_id(accs.merkle_tree.key, data.leaf.nonce);
+    accs.wrapped_meta.verify_derivation(
+        ctx.program_id,
+        &WrappedMetaDerivationData { mint_key: asset_id },
+    )?;
+
+    // Burn the NFT, Bubblegum verifies the leaf and thereby its metadata
+    let proof = data.leaf.proof(ctx.accounts)?;
+    let burn_ix = compressed::burn(
+        *accs.merkle_tree.key,
+        *accs.leaf_owner.key,
+        &data.leaf,
+        &proof,
+    )?;
+    invoke(&burn_ix, ctx.accounts)?;
+
+    // Pay fee
+    let transfer_ix = solana_program::system_instruction::transfer(
+        accs.payer.key,
+        accs.fee_collector.key,
+        accs.bridge.config.fee,
+    );
+    invoke(&transfer_ix, ctx.accounts)?;
+
+    // Post message
+    let metadata = data.leaf.metadata;
+    let payload = PayloadTransfer {
+        token_address: accs.wrapped_meta.token_address,
+        token_chain: accs.wrapped_meta.chain,
+        token_id: U256(accs.wrapped_meta.token_id),
+        to: data.target_address,
+        to_chain: data.target_chain,
+        symbol: metadata.symbol,
+        name: metadata.name,
+        uri: metadata.uri,
+    };
+    let params = (
+        bridge::instruction::Instruction::PostMessage,
+        PostMessageData {
+            nonce: data.nonce,
+            payload: payload.try_to_vec()?,
+            consistency_level: ConsistencyLevel::Finalized,
+        },
+    );
+
+    let ix = Instruction::new_with_bytes(
+        accs.config.wormhole_bridge,
+        params.try_to_vec()?.as_slice(),
+        vec![
+            AccountMeta::new(*accs.bridge.info().key, false),
+            AccountMeta::new(*accs.message.key, true),
+            AccountMeta::new_readonly(*accs.emitter.key, true),
+            AccountMeta::new(*accs.sequence.key, false),
+            AccountMeta::new(*accs.payer.key, true),
+            AccountMeta::new(*accs.fee_collector.key, false),
+            AccountMeta::new_readonly(*accs.clock.info().key, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+        ],
+    );
+    invoke_seeded(&ix, ctx, &accs.emitter, None)?;
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct CompleteNativeCompressed<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    pub vaa: PayloadMessage<'b, PayloadTransfer>,
+    pub claim: Mut<Claim<'b>>,
+    pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
+
+    pub tree_config: Info<'b>,
+    pub merkle_tree: Mut<Info<'b>>,
+
+    /// New owner of the NFT
+    pub to: Info<'b>,
+
+    pub custody_signer: CustodySigner<'b>,
+}
+
+impl<'a> From<&CompleteNativeCompressed<'a>> for EndpointDerivationData {
+    fn from(accs: &CompleteNativeCompressed<'a>) -> Self {
+        EndpointDerivationData {
+            emitter_chain: accs.vaa.meta().emitter_chain,
+            emitter_address: accs.vaa.meta().emitter_address,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct CompleteNativeCompressedData {
+    pub leaf: CompressedLeaf,
+}
+
+pub fn complete_native_compressed(
+    ctx: &ExecutionContext,
+    accs: &mut CompleteNativeCompressed,
+    data: CompleteNativeCompressedData,
+) -> Result<()> {
+    compressed::verify_enabled()?;
+
+    // Verify the chain registration
+    let derivation_data: EndpointDerivationData = (&*accs).into();
+    accs.chain_registration
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    // Verify VAA
+    // Please refer to transfer_native_compressed for why the token id is used to store the asset id
+    if accs.vaa.token_address != COMPRESSED_TOKEN_ADDRESS {
+        return Err(InvalidMint.into());
+    }
+    let asset_id = compressed::asset_id(accs.merkle_tree.key, data.leaf.nonce);
+    let mut token_id_bytes = [0u8; 32];
+    accs.vaa.token_id.to_big_endian(&mut token_id_bytes);
+    if token_id_bytes != asset_id.to_bytes() {
+        return Err(InvalidMint.into());
+    }
+    if accs.vaa.token_chain != CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+    if accs.vaa.to_chain != CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+    if accs.vaa.to != accs.to.key.to_bytes() {
+        return Err(InvalidRecipient.into());
+    }
+
+    // Prevent vaa double signing
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    // Release the NFT from custody
+    TreeConfig::load(&accs.tree_config, accs.merkle_tree.key)?;
+    let proof = data.leaf.proof(ctx.accounts)?;
+    let transfer_ix = compressed::transfer(
+        *accs.merkle_tree.key,
+        *accs.custody_signer.key,
+        *accs.to.key,
+        &data.leaf,
+        &proof,
+    )?;
+    invoke_seeded(&transfer_ix, ctx, &accs.custody_signer, None)?;
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct CompleteWrappedCompressed<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    // Signed message for the transfer
+    pub vaa: PayloadMessage<'b, PayloadTransfer>,
+    pub claim: Mut<Claim<'b>>,
+
+    pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
+
+    /// Bubblegum config of a tree of the bridge
+    pub tree_config: Mut<Info<'b>>,
+    pub merkle_tree: Mut<Info<'b>>,
+
+    /// Owner of the minted NFT
+    pub to: Info<'b>,
+
+    /// Meta of the asset id the NFT is minted with, which is derived from the number of NFTs
+    /// minted into the tree so far.
+    pub meta: Mut<WrappedTokenMeta<'b, { AccountState::Uninitialized }>>,
+
+    pub mint_authority: MintSigner<'b>,
+}
+
+impl<'a> From<&CompleteWrappedCompressed<'a>> for EndpointDerivationData {
+    fn from(accs: &CompleteWrappedCompressed<'a>) -> Self {
+        EndpointDerivationData {
+            emitter_chain: accs.vaa.meta().emitter_chain,
+            emitter_address: accs.vaa.meta().emitter_address,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct CompleteWrappedCompressedData {}
+
+pub fn complete_wrapped_compressed(
+    ctx: &ExecutionContext,
+    accs: &mut CompleteWrappedCompressed,
+    _data: CompleteWrappedCompressedData,
+) -> Result<()> {
+    use bstr::ByteSlice;
+
+    compressed::verify_enabled()?;
+
+    // Verify the chain registration
+    let derivation_data: EndpointDerivationData = (&*accs).into();
+    accs.chain_registration
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    // Verify VAA
+    if accs.vaa.token_chain == CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+    if accs.vaa.to_chain != CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+    if accs.vaa.to != accs.to.key.to_bytes() {
+        return Err(InvalidRecipient.into());
+    }
+
+    // Verify that the tree belongs to the bridge and the meta to the asset about to be minted
+    let tree = TreeConfig::load(&accs.tree_config, accs.merkle_tree.key)?;
+    if tree.tree_creator != *accs.mint_authority.key {
+        return Err(InvalidCompressedTree.into());
+    }
+    let meta_derivation = WrappedMetaDerivationData {
+        mint_key: compressed::asset_id(accs.merkle_tree.key, tree.num_minted),
+    };
+    accs.meta
+        .verify_derivation(ctx.program_id, &meta_derivation)?;
+
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    // Create and populate meta account
+    accs.meta
+        .create(&meta_derivation, ctx, accs.payer.key, Exempt)?;
+    accs.meta.chain = accs.vaa.token_chain;
+    accs.meta.token_address = accs.vaa.token_address;
+    accs.meta.token_id = accs.vaa.token_id.0;
+
+    // Mint the NFT
+    let mut symbol: Vec<u8> = accs.vaa.symbol.clone().as_bytes().to_vec();
+    symbol.truncate(10);
+    let mut symbol: Vec<char> = symbol.chars().collect();
+    symbol.retain(|&c| c != '\u{FFFD}');
+    let symbol: String = symbol.iter().collect();
+
+    let metadata = MetadataArgs::wrapped(accs.vaa.name.clone(), symbol, accs.vaa.uri.clone());
+    let mint_ix = compressed::mint_v1(
+        *accs.merkle_tree.key,
+        *accs.payer.key,
+        *accs.mint_authority.key,
+        *accs.to.key,
+        &metadata,
+    )?;
+    invoke_seeded(&mint_ix, ctx, &accs.mint_authority, None)?;
+
+    Ok(())
+}
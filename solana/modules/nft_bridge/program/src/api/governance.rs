@@ -24,14 +24,13 @@ use bridge::{
 };
 use solana_program::{
     account_info::AccountInfo,
-    program::invoke_signed,
-    pubkey::Pubkey,
     sysvar::{
         clock::Clock,
         rent::Rent,
     },
 };
 use solitaire::{
+    invoke_signed,
     processors::seeded::Seeded,
     CreationLamports::Exempt,
     *,
@@ -42,10 +41,8 @@ fn verify_governance<T>(vaa: &PayloadMessage<T>) -> Result<()>
 where
     T: DeserializePayload,
 {
-    let expected_emitter = std::env!("EMITTER_ADDRESS");
-    let current_emitter = format!("{}", Pubkey::new_from_array(vaa.meta().emitter_address));
     // Fail if the emitter is not the known governance key, or the emitting chain is not Solana.
-    if expected_emitter != current_emitter || vaa.meta().emitter_chain != CHAIN_ID_SOLANA {
+    if !vaa.try_emitter_eq(CHAIN_ID_SOLANA, std::env!("EMITTER_ADDRESS"))? {
         Err(InvalidGovernanceKey.into())
     } else {
         Ok(())
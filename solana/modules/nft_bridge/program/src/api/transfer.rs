@@ -32,14 +32,12 @@ use solana_program::{
         AccountMeta,
         Instruction,
     },
-    program::{
-        invoke,
-        invoke_signed,
-    },
     program_option::COption,
     sysvar::clock::Clock,
 };
 use solitaire::{
+    invoke,
+    invoke_signed,
     processors::seeded::{
         invoke_seeded,
         Seeded,
@@ -0,0 +1,418 @@
+//! The parts of the Bubblegum interface the NFT bridge needs to bridge compressed NFTs.
+//!
+//! Compressed NFTs are leaves of a concurrent merkle tree owned by the SPL account compression
+//! program and managed by Bubblegum. A leaf commits to the asset id, owner, delegate and nonce of
+//! the NFT, together with hashes of its metadata and creators. The bridge never reads leaves
+//! itself: senders and relayers pass the metadata and the merkle proof, the bridge hashes the
+//! metadata and Bubblegum checks the resulting leaf against the tree when the bridge CPIs into it.
+//! Metadata in a payload is therefore always the metadata committed to in the tree.
+//!
+//! Wrapped compressed NFTs are minted into trees created through the bridge, whose creator and
+//! delegate is the mint signer.
+
+use crate::TokenBridgeError::{
+    CompressedNftDisabled,
+    InvalidCompressedTree,
+};
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use solana_program::{
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    keccak,
+    pubkey::Pubkey,
+};
+use solitaire::{
+    Info,
+    Result,
+};
+
+pub mod bubblegum {
+    solana_program::declare_id!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+}
+
+pub mod account_compression {
+    solana_program::declare_id!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+}
+
+pub mod noop {
+    solana_program::declare_id!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+}
+
+/// Token address of compressed Solana NFTs in transfer payloads, their token id is the asset id.
+/// SPL NFTs use `[1u8; 32]` with the mint as token id.
+pub const COMPRESSED_TOKEN_ADDRESS: [u8; 32] = [2u8; 32];
+
+/// Anchor instruction discriminators, the first 8 bytes of `sha256("global:<name>")`.
+const CREATE_TREE: [u8; 8] = [165, 83, 136, 142, 89, 202, 47, 220];
+const MINT_V1: [u8; 8] = [145, 98, 192, 118, 184, 147, 118, 104];
+const TRANSFER: [u8; 8] = [163, 52, 200, 231, 140, 3, 69, 186];
+const BURN: [u8; 8] = [116, 110, 29, 56, 107, 219, 42, 93];
+
+/// Anchor account discriminator of `TreeConfig`, the first 8 bytes of
+/// `sha256("account:TreeConfig")`.
+const TREE_CONFIG: [u8; 8] = [122, 245, 175, 248, 171, 34, 0, 207];
+
+/// Bridging compressed NFTs needs the `compressed-nft` feature.
+pub fn verify_enabled() -> Result<()> {
+    if cfg!(feature = "compressed-nft") {
+        Ok(())
+    } else {
+        Err(CompressedNftDisabled.into())
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, PartialEq)]
+pub enum TokenStandard {
+    NonFungible,
+    FungibleAsset,
+    Fungible,
+    NonFungibleEdition,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, PartialEq)]
+pub enum TokenProgramVersion {
+    Original,
+    Token2022,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, PartialEq)]
+pub enum UseMethod {
+    Burn,
+    Multiple,
+    Single,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, PartialEq)]
+pub struct Uses {
+    pub use_method: UseMethod,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, PartialEq)]
+pub struct Collection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, PartialEq)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+/// Metadata of a compressed NFT, as committed to in its leaf.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+pub struct MetadataArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<TokenStandard>,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+    pub token_program_version: TokenProgramVersion,
+    pub creators: Vec<Creator>,
+}
+
+impl MetadataArgs {
+    /// Metadata of a wrapped compressed NFT, which like wrapped SPL NFTs has no creators and
+    /// cannot be updated.
+    pub fn wrapped(name: String, symbol: String, uri: String) -> Self {
+        MetadataArgs {
+            name,
+            symbol,
+            uri,
+            seller_fee_basis_points: 0,
+            primary_sale_happened: false,
+            is_mutable: false,
+            edition_nonce: None,
+            token_standard: Some(TokenStandard::NonFungible),
+            collection: None,
+            uses: None,
+            token_program_version: TokenProgramVersion::Original,
+            creators: vec![],
+        }
+    }
+
+    /// `data_hash` of the leaf.
+    pub fn data_hash(&self) -> Result<[u8; 32]> {
+        let args = keccak::hash(&self.try_to_vec()?);
+        Ok(keccak::hashv(&[args.as_ref(), &self.seller_fee_basis_points.to_le_bytes()]).to_bytes())
+    }
+
+    /// `creator_hash` of the leaf.
+    pub fn creator_hash(&self) -> [u8; 32] {
+        let creators: Vec<Vec<u8>> = self
+            .creators
+            .iter()
+            .map(|c| [c.address.as_ref(), &[c.verified as u8], &[c.share]].concat())
+            .collect();
+        let creators: Vec<&[u8]> = creators.iter().map(|c| c.as_slice()).collect();
+        keccak::hashv(&creators).to_bytes()
+    }
+}
+
+/// Locates a compressed NFT in its tree, the proof is passed as the last `proof_len` accounts of
+/// the instruction.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+pub struct CompressedLeaf {
+    /// Root the proof was computed against, Bubblegum accepts any root still in the changelog of
+    /// the tree.
+    pub root: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+    pub proof_len: u8,
+    pub metadata: MetadataArgs,
+}
+
+impl CompressedLeaf {
+    /// Proof of the leaf at the end of `accounts`.
+    pub fn proof(&self, accounts: &[Info]) -> Result<Vec<Pubkey>> {
+        let start = accounts
+            .len()
+            .checked_sub(self.proof_len as usize)
+            .ok_or(InvalidCompressedTree)?;
+        Ok(accounts[start..].iter().map(|info| *info.key).collect())
+    }
+}
+
+/// Id of the compressed NFT minted with `nonce` into `merkle_tree`.
+pub fn asset_id(merkle_tree: &Pubkey, nonce: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"asset", merkle_tree.as_ref(), &nonce.to_le_bytes()],
+        &bubblegum::id(),
+    )
+    .0
+}
+
+/// Bubblegum config of `merkle_tree`.
+pub fn tree_config_key(merkle_tree: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[merkle_tree.as_ref()], &bubblegum::id()).0
+}
+
+/// The fields of Bubblegum's `TreeConfig` the bridge reads.
+pub struct TreeConfig {
+    pub tree_creator: Pubkey,
+    pub num_minted: u64,
+}
+
+impl TreeConfig {
+    /// Read the config of `merkle_tree` from `info`.
+    pub fn load(info: &Info, merkle_tree: &Pubkey) -> Result<Self> {
+        if *info.key != tree_config_key(merkle_tree) || *info.owner != bubblegum::id() {
+            return Err(InvalidCompressedTree.into());
+        }
+
+        #[derive(BorshDeserialize)]
+        struct Header {
+            discriminator: [u8; 8],
+            tree_creator: Pubkey,
+            _tree_delegate: Pubkey,
+            _total_mint_capacity: u64,
+            num_minted: u64,
+        }
+
+        let mut data: &[u8] = &info.data.borrow();
+        let header = Header::deserialize(&mut data).map_err(|_| InvalidCompressedTree)?;
+        if header.discriminator != TREE_CONFIG {
+            return Err(InvalidCompressedTree.into());
+        }
+
+        Ok(TreeConfig {
+            tree_creator: header.tree_creator,
+            num_minted: header.num_minted,
+        })
+    }
+}
+
+/// Accounts every Bubblegum instruction ends with.
+fn programs() -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(noop::id(), false),
+        AccountMeta::new_readonly(account_compression::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+    ]
+}
+
+fn proof_metas(proof: &[Pubkey]) -> Vec<AccountMeta> {
+    proof
+        .iter()
+        .map(|node| AccountMeta::new_readonly(*node, false))
+        .collect()
+}
+
+/// Leaf arguments of `transfer` and `burn`.
+#[derive(BorshSerialize)]
+struct LeafArgs {
+    root: [u8; 32],
+    data_hash: [u8; 32],
+    creator_hash: [u8; 32],
+    nonce: u64,
+    index: u32,
+}
+
+impl LeafArgs {
+    fn new(leaf: &CompressedLeaf) -> Result<Self> {
+        Ok(LeafArgs {
+            root: leaf.root,
+            data_hash: leaf.metadata.data_hash()?,
+            creator_hash: leaf.metadata.creator_hash(),
+            nonce: leaf.nonce,
+            index: leaf.index,
+        })
+    }
+}
+
+/// Create a private tree with `tree_creator` as its creator and delegate. `merkle_tree` must be
+/// allocated for the depth and buffer size and owned by the account compression program.
+pub fn create_tree(
+    merkle_tree: Pubkey,
+    payer: Pubkey,
+    tree_creator: Pubkey,
+    max_depth: u32,
+    max_buffer_size: u32,
+) -> Result<Instruction> {
+    let mut accounts = vec![
+        AccountMeta::new(tree_config_key(&merkle_tree), false),
+        AccountMeta::new(merkle_tree, false),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(tree_creator, true),
+    ];
+    accounts.extend(programs());
+
+    let public: Option<bool> = Some(false);
+    Ok(Instruction {
+        program_id: bubblegum::id(),
+        accounts,
+        data: (CREATE_TREE, max_depth, max_buffer_size, public).try_to_vec()?,
+    })
+}
+
+/// Mint a compressed NFT owned by `leaf_owner` into `merkle_tree`.
+pub fn mint_v1(
+    merkle_tree: Pubkey,
+    payer: Pubkey,
+    tree_delegate: Pubkey,
+    leaf_owner: Pubkey,
+    metadata: &MetadataArgs,
+) -> Result<Instruction> {
+    let mut accounts = vec![
+        AccountMeta::new(tree_config_key(&merkle_tree), false),
+        AccountMeta::new_readonly(leaf_owner, false),
+        AccountMeta::new_readonly(leaf_owner, false),
+        AccountMeta::new(merkle_tree, false),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(tree_delegate, true),
+    ];
+    accounts.extend(programs());
+
+    Ok(Instruction {
+        program_id: bubblegum::id(),
+        accounts,
+        data: (MINT_V1, metadata).try_to_vec()?,
+    })
+}
+
+/// Transfer the compressed NFT at `leaf` from `leaf_owner`, who signs, to `new_leaf_owner`.
+pub fn transfer(
+    merkle_tree: Pubkey,
+    leaf_owner: Pubkey,
+    new_leaf_owner: Pubkey,
+    leaf: &CompressedLeaf,
+    proof: &[Pubkey],
+) -> Result<Instruction> {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(tree_config_key(&merkle_tree), false),
+        AccountMeta::new_readonly(leaf_owner, true),
+        AccountMeta::new_readonly(leaf_owner, false),
+        AccountMeta::new_readonly(new_leaf_owner, false),
+        AccountMeta::new(merkle_tree, false),
+    ];
+    accounts.extend(programs());
+    accounts.extend(proof_metas(proof));
+
+    Ok(Instruction {
+        program_id: bubblegum::id(),
+        accounts,
+        data: (TRANSFER, LeafArgs::new(leaf)?).try_to_vec()?,
+    })
+}
+
+/// Burn the compressed NFT at `leaf` owned by `leaf_owner`, who signs.
+pub fn burn(
+    merkle_tree: Pubkey,
+    leaf_owner: Pubkey,
+    leaf: &CompressedLeaf,
+    proof: &[Pubkey],
+) -> Result<Instruction> {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(tree_config_key(&merkle_tree), false),
+        AccountMeta::new_readonly(leaf_owner, true),
+        AccountMeta::new_readonly(leaf_owner, false),
+        AccountMeta::new(merkle_tree, false),
+    ];
+    accounts.extend(programs());
+    accounts.extend(proof_metas(proof));
+
+    Ok(Instruction {
+        program_id: bubblegum::id(),
+        accounts,
+        data: (BURN, LeafArgs::new(leaf)?).try_to_vec()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::hash::hash;
+
+    #[test]
+    pub fn test_discriminators() {
+        let discriminator = |name: &str| -> [u8; 8] {
+            let mut d = [0u8; 8];
+            d.copy_from_slice(&hash(name.as_bytes()).to_bytes()[..8]);
+            d
+        };
+
+        assert_eq!(CREATE_TREE, discriminator("global:create_tree"));
+        assert_eq!(MINT_V1, discriminator("global:mint_v1"));
+        assert_eq!(TRANSFER, discriminator("global:transfer"));
+        assert_eq!(BURN, discriminator("global:burn"));
+        assert_eq!(TREE_CONFIG, discriminator("account:TreeConfig"));
+    }
+
+    #[test]
+    pub fn test_leaf_hashes() {
+        let mut metadata = MetadataArgs::wrapped(
+            String::from("Token Token"),
+            String::from("TEST"),
+            String::from("https://abc.abc.abc.com"),
+        );
+        assert_eq!(metadata.creator_hash(), keccak::hash(&[]).to_bytes());
+
+        let creator = Creator {
+            address: Pubkey::new_unique(),
+            verified: true,
+            share: 100,
+        };
+        metadata.creators.push(creator);
+        let mut expected = creator.address.to_bytes().to_vec();
+        expected.extend_from_slice(&[1, 100]);
+        assert_eq!(metadata.creator_hash(), keccak::hash(&expected).to_bytes());
+
+        // The seller fee is committed to twice, in the args and on its own.
+        let data_hash = metadata.data_hash().unwrap();
+        metadata.seller_fee_basis_points = 500;
+        assert_ne!(metadata.data_hash().unwrap(), data_hash);
+    }
+}
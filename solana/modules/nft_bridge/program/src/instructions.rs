@@ -21,11 +21,17 @@ use crate::{
             CompleteNativeData,
             CompleteWrappedData,
         },
+        CompleteNativeCompressedData,
+        CompleteWrappedCompressedData,
+        CreateCompressedTreeData,
         RegisterChainData,
+        TransferNativeCompressedData,
         TransferNativeData,
+        TransferWrappedCompressedData,
         TransferWrappedData,
         UpgradeContractData,
     },
+    compressed,
     messages::{
         PayloadGovernanceRegisterChain,
         PayloadTransfer,
@@ -506,3 +512,284 @@ pub fn upgrade_contract(
             .unwrap(),
     }
 }
+
+pub fn create_compressed_tree(
+    program_id: Pubkey,
+    payer: Pubkey,
+    merkle_tree: Pubkey,
+    data: CreateCompressedTreeData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let mint_authority_key = MintSigner::key(None, &program_id);
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key, false),
+            AccountMeta::new(compressed::tree_config_key(&merkle_tree), false),
+            AccountMeta::new(merkle_tree, false),
+            AccountMeta::new_readonly(mint_authority_key, false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            // Program
+            AccountMeta::new_readonly(compressed::bubblegum::id(), false),
+            AccountMeta::new_readonly(compressed::account_compression::id(), false),
+            AccountMeta::new_readonly(compressed::noop::id(), false),
+        ],
+        data: (crate::instruction::Instruction::CreateCompressedTree, data).try_to_vec()?,
+    })
+}
+
+/// Accounts of the Bubblegum CPIs, followed by the proof of the leaf.
+fn compressed_programs(proof: &[Pubkey]) -> Vec<AccountMeta> {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(compressed::bubblegum::id(), false),
+        AccountMeta::new_readonly(compressed::account_compression::id(), false),
+        AccountMeta::new_readonly(compressed::noop::id(), false),
+    ];
+    accounts.extend(
+        proof
+            .iter()
+            .map(|node| AccountMeta::new_readonly(*node, false)),
+    );
+    accounts
+}
+
+/// `data.leaf.proof_len` is set from `proof`.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_native_compressed(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    merkle_tree: Pubkey,
+    leaf_owner: Pubkey,
+    proof: &[Pubkey],
+    mut data: TransferNativeCompressedData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let custody_signer_key = CustodySigner::key(None, &program_id);
+    let emitter_key = EmitterAccount::key(None, &program_id);
+
+    // Bridge keys
+    let bridge_config = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &bridge_id);
+    let sequence_key = Sequence::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter_key,
+        },
+        &bridge_id,
+    );
+    let fee_collector_key = FeeCollector::key(None, &bridge_id);
+
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(config_key, false),
+        AccountMeta::new_readonly(compressed::tree_config_key(&merkle_tree), false),
+        AccountMeta::new(merkle_tree, false),
+        AccountMeta::new_readonly(leaf_owner, true),
+        AccountMeta::new_readonly(custody_signer_key, false),
+        AccountMeta::new(bridge_config, false),
+        AccountMeta::new(message_key, true),
+        AccountMeta::new_readonly(emitter_key, false),
+        AccountMeta::new(sequence_key, false),
+        AccountMeta::new(fee_collector_key, false),
+        AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
+        // Dependencies
+        AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        // Program
+        AccountMeta::new_readonly(bridge_id, false),
+    ];
+    accounts.extend(compressed_programs(proof));
+
+    data.leaf.proof_len = proof.len() as u8;
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data: (
+            crate::instruction::Instruction::TransferNativeCompressed,
+            data,
+        )
+            .try_to_vec()?,
+    })
+}
+
+/// `data.leaf.proof_len` is set from `proof`.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_wrapped_compressed(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    merkle_tree: Pubkey,
+    leaf_owner: Pubkey,
+    proof: &[Pubkey],
+    mut data: TransferWrappedCompressedData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let wrapped_meta_key = WrappedTokenMeta::<'_, { AccountState::Uninitialized }>::key(
+        &WrappedMetaDerivationData {
+            mint_key: compressed::asset_id(&merkle_tree, data.leaf.nonce),
+        },
+        &program_id,
+    );
+    let emitter_key = EmitterAccount::key(None, &program_id);
+
+    // Bridge keys
+    let bridge_config = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &bridge_id);
+    let sequence_key = Sequence::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter_key,
+        },
+        &bridge_id,
+    );
+    let fee_collector_key = FeeCollector::key(None, &bridge_id);
+
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(config_key, false),
+        AccountMeta::new_readonly(compressed::tree_config_key(&merkle_tree), false),
+        AccountMeta::new(merkle_tree, false),
+        AccountMeta::new_readonly(leaf_owner, true),
+        AccountMeta::new_readonly(wrapped_meta_key, false),
+        AccountMeta::new(bridge_config, false),
+        AccountMeta::new(message_key, true),
+        AccountMeta::new_readonly(emitter_key, false),
+        AccountMeta::new(sequence_key, false),
+        AccountMeta::new(fee_collector_key, false),
+        AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
+        // Dependencies
+        AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        // Program
+        AccountMeta::new_readonly(bridge_id, false),
+    ];
+    accounts.extend(compressed_programs(proof));
+
+    data.leaf.proof_len = proof.len() as u8;
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data: (
+            crate::instruction::Instruction::TransferWrappedCompressed,
+            data,
+        )
+            .try_to_vec()?,
+    })
+}
+
+/// `data.leaf.proof_len` is set from `proof`.
+#[allow(clippy::too_many_arguments)]
+pub fn complete_native_compressed(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    merkle_tree: Pubkey,
+    to: Pubkey,
+    proof: &[Pubkey],
+    mut data: CompleteNativeCompressedData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa.clone());
+    let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
+        &EndpointDerivationData {
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+        },
+        &program_id,
+    );
+    let custody_signer_key = CustodySigner::key(None, &program_id);
+
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(config_key, false),
+        message_acc,
+        claim_acc,
+        AccountMeta::new_readonly(endpoint, false),
+        AccountMeta::new_readonly(compressed::tree_config_key(&merkle_tree), false),
+        AccountMeta::new(merkle_tree, false),
+        AccountMeta::new_readonly(to, false),
+        AccountMeta::new_readonly(custody_signer_key, false),
+        // Dependencies
+        AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        // Program
+        AccountMeta::new_readonly(bridge_id, false),
+    ];
+    accounts.extend(compressed_programs(proof));
+
+    data.leaf.proof_len = proof.len() as u8;
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data: (
+            crate::instruction::Instruction::CompleteNativeCompressed,
+            data,
+        )
+            .try_to_vec()?,
+    })
+}
+
+/// `num_minted` is the number of NFTs minted into `merkle_tree` so far, read from its Bubblegum
+/// config. The instruction fails if another mint lands first.
+#[allow(clippy::too_many_arguments)]
+pub fn complete_wrapped_compressed(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    merkle_tree: Pubkey,
+    num_minted: u64,
+    to: Pubkey,
+    data: CompleteWrappedCompressedData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa.clone());
+    let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
+        &EndpointDerivationData {
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+        },
+        &program_id,
+    );
+    let meta_key = WrappedTokenMeta::<'_, { AccountState::Uninitialized }>::key(
+        &WrappedMetaDerivationData {
+            mint_key: compressed::asset_id(&merkle_tree, num_minted),
+        },
+        &program_id,
+    );
+    let mint_authority_key = MintSigner::key(None, &program_id);
+
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(config_key, false),
+        message_acc,
+        claim_acc,
+        AccountMeta::new_readonly(endpoint, false),
+        AccountMeta::new(compressed::tree_config_key(&merkle_tree), false),
+        AccountMeta::new(merkle_tree, false),
+        AccountMeta::new_readonly(to, false),
+        AccountMeta::new(meta_key, false),
+        AccountMeta::new_readonly(mint_authority_key, false),
+        // Dependencies
+        AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        // Program
+        AccountMeta::new_readonly(bridge_id, false),
+    ];
+    accounts.extend(compressed_programs(&[]));
+
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data: (
+            crate::instruction::Instruction::CompleteWrappedCompressed,
+            data,
+        )
+            .try_to_vec()?,
+    })
+}
@@ -16,31 +16,47 @@ pub mod wasm;
 
 pub mod accounts;
 pub mod api;
+pub mod compressed;
 pub mod messages;
 pub mod types;
 
 pub use api::{
     complete_native,
+    complete_native_compressed,
     complete_wrapped,
+    complete_wrapped_compressed,
     complete_wrapped_meta,
+    create_compressed_tree,
     initialize,
     register_chain,
     transfer_native,
+    transfer_native_compressed,
     transfer_wrapped,
+    transfer_wrapped_compressed,
     upgrade_contract,
     CompleteNative,
+    CompleteNativeCompressed,
+    CompleteNativeCompressedData,
     CompleteNativeData,
     CompleteWrapped,
+    CompleteWrappedCompressed,
+    CompleteWrappedCompressedData,
     CompleteWrappedData,
     CompleteWrappedMeta,
     CompleteWrappedMetaData,
+    CreateCompressedTree,
+    CreateCompressedTreeData,
     Initialize,
     InitializeData,
     RegisterChain,
     RegisterChainData,
     TransferNative,
+    TransferNativeCompressed,
+    TransferNativeCompressedData,
     TransferNativeData,
     TransferWrapped,
+    TransferWrappedCompressed,
+    TransferWrappedCompressedData,
     TransferWrappedData,
     UpgradeContract,
     UpgradeContractData,
@@ -63,6 +79,8 @@ pub enum TokenBridgeError {
     InvalidAssociatedAccount,
     InvalidRecipient,
     NotMetadataV1Account,
+    CompressedNftDisabled,
+    InvalidCompressedTree,
 }
 
 impl From<TokenBridgeError> for SolitaireError {
@@ -72,12 +90,17 @@ impl From<TokenBridgeError> for SolitaireError {
 }
 
 solitaire! {
-    Initialize          => initialize,
-    CompleteNative      => complete_native,
-    CompleteWrapped     => complete_wrapped,
-    CompleteWrappedMeta => complete_wrapped_meta,
-    TransferWrapped     => transfer_wrapped,
-    TransferNative      => transfer_native,
-    RegisterChain       => register_chain,
-    UpgradeContract     => upgrade_contract,
+    Initialize                => initialize,
+    CompleteNative            => complete_native,
+    CompleteWrapped           => complete_wrapped,
+    CompleteWrappedMeta       => complete_wrapped_meta,
+    TransferWrapped           => transfer_wrapped,
+    TransferNative            => transfer_native,
+    RegisterChain             => register_chain,
+    UpgradeContract           => upgrade_contract,
+    CreateCompressedTree      => create_compressed_tree,
+    TransferNativeCompressed  => transfer_native_compressed,
+    TransferWrappedCompressed => transfer_wrapped_compressed,
+    CompleteNativeCompressed  => complete_native_compressed,
+    CompleteWrappedCompressed => complete_wrapped_compressed,
 }
@@ -3,18 +3,18 @@ use crate::types::{
     ChainID,
 };
 use bridge::{
+    codec::{
+        ReadBytes,
+        WriteBytes,
+    },
     vaa::{
+        deserialize_decree,
         DeserializePayload,
         SerializePayload,
     },
     DeserializeGovernancePayload,
     SerializeGovernancePayload,
 };
-use byteorder::{
-    BigEndian,
-    ReadBytesExt,
-    WriteBytesExt,
-};
 use primitive_types::U256;
 use solana_program::{
     program_error::ProgramError::InvalidAccountData,
@@ -29,8 +29,12 @@ use std::{
         Write,
     },
 };
+use wormhole_governance::{
+    Decree,
+    RegisterChain,
+};
 
-pub const MODULE: &str = "NFTBridge";
+pub const MODULE: &str = wormhole_governance::module::NFT_BRIDGE;
 
 #[derive(PartialEq, Debug, Clone)]
 pub struct PayloadTransfer {
@@ -64,7 +68,7 @@ impl DeserializePayload for PayloadTransfer {
         let mut token_address = Address::default();
         v.read_exact(&mut token_address)?;
 
-        let token_chain = v.read_u16::<BigEndian>()?;
+        let token_chain = v.read_u16_be()?;
 
         // We may receive invalid UTF-8 over the bridge, especially if truncated. To compensate for
         // this we rely on the bstr libraries ability to parse invalid UTF-8, and strip out the
@@ -84,9 +88,7 @@ impl DeserializePayload for PayloadTransfer {
         name.retain(|&c| c != '\u{FFFD}');
         let name: String = name.iter().collect();
 
-        let mut id_data: [u8; 32] = [0; 32];
-        v.read_exact(&mut id_data)?;
-        let token_id = U256::from_big_endian(&id_data);
+        let token_id = v.read_u256_be()?;
 
         let uri_len = v.read_u8()?;
         let mut uri_bytes = vec![0u8; uri_len as usize];
@@ -96,7 +98,7 @@ impl DeserializePayload for PayloadTransfer {
         let mut to = Address::default();
         v.read_exact(&mut to)?;
 
-        let to_chain = v.read_u16::<BigEndian>()?;
+        let to_chain = v.read_u16_be()?;
 
         if v.position() != v.into_inner().len() as u64 {
             return Err(InvalidAccountData.into());
@@ -121,7 +123,7 @@ impl SerializePayload for PayloadTransfer {
         writer.write_u8(1)?;
 
         writer.write_all(&self.token_address)?;
-        writer.write_u16::<BigEndian>(self.token_chain)?;
+        writer.write_u16_be(self.token_chain)?;
 
         let mut symbol: [u8; 32] = [0; 32];
         let count = cmp::min(symbol.len(), self.symbol.len());
@@ -134,15 +136,13 @@ impl SerializePayload for PayloadTransfer {
         name[..count].copy_from_slice(self.name[..count].as_bytes());
         writer.write_all(&name)?;
 
-        let mut id_data: [u8; 32] = [0; 32];
-        self.token_id.to_big_endian(&mut id_data);
-        writer.write_all(&id_data)?;
+        writer.write_u256_be(self.token_id)?;
 
         writer.write_u8(self.uri.len() as u8)?;
         writer.write_all(self.uri.as_bytes())?;
 
         writer.write_all(&self.to)?;
-        writer.write_u16::<BigEndian>(self.to_chain)?;
+        writer.write_u16_be(self.to_chain)?;
 
         Ok(())
     }
@@ -158,7 +158,7 @@ pub struct PayloadGovernanceRegisterChain {
 
 impl SerializeGovernancePayload for PayloadGovernanceRegisterChain {
     const MODULE: &'static str = MODULE;
-    const ACTION: u8 = 1;
+    const ACTION: u8 = RegisterChain::ACTION;
 }
 
 impl DeserializeGovernancePayload for PayloadGovernanceRegisterChain {
@@ -169,17 +169,10 @@ where
     Self: DeserializeGovernancePayload,
 {
     fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
-        let mut v = Cursor::new(buf);
-        Self::check_governance_header(&mut v)?;
-
-        let chain = v.read_u16::<BigEndian>()?;
-        let mut endpoint_address = [0u8; 32];
-        v.read_exact(&mut endpoint_address)?;
-
-        if v.position() != v.into_inner().len() as u64 {
-            return Err(InvalidAccountData.into());
-        }
-
+        let RegisterChain {
+            chain,
+            endpoint_address,
+        } = deserialize_decree::<Self, _>(buf)?;
         Ok(PayloadGovernanceRegisterChain {
             chain,
             endpoint_address,
@@ -193,9 +186,11 @@ where
 {
     fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), SolitaireError> {
         self.write_governance_header(writer)?;
-        // Payload ID
-        writer.write_u16::<BigEndian>(self.chain)?;
-        writer.write_all(&self.endpoint_address[..])?;
+        RegisterChain {
+            chain: self.chain,
+            endpoint_address: self.endpoint_address,
+        }
+        .encode(writer)?;
 
         Ok(())
     }
@@ -639,7 +639,11 @@ mod helpers {
                         payer.pubkey(),
                         guardian_set_version,
                         signature_set.pubkey(),
-                        bridge::VerifySignaturesData { signers },
+                        bridge::VerifySignaturesData {
+                            signers,
+                            hash: None,
+                            signer_offset: 0,
+                        },
                     )
                     .unwrap(),
                 ],
@@ -0,0 +1,50 @@
+use crate::messages::PayloadTag;
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use solana_program::pubkey::Pubkey;
+use solitaire::{
+    processors::seeded::{
+        AccountOwner,
+        Owned,
+        Seeded,
+    },
+    AccountState,
+    Data,
+    Derive,
+    Info,
+};
+
+#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize)]
+pub struct PayloadTypeData {
+    /// Key that registered the type, the only one allowed to change its handler.
+    pub authority: Pubkey,
+
+    /// Program payloads of the type are dispatched to.
+    pub handler: Pubkey,
+}
+
+impl Owned for PayloadTypeData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+/// Handler registered for a payload type tag.
+pub type PayloadTypeRegistry<'b, const STATE: AccountState> = Data<'b, PayloadTypeData, { STATE }>;
+
+pub struct PayloadTypeDerivationData {
+    pub tag: PayloadTag,
+}
+
+impl<'b, const STATE: AccountState> Seeded<&PayloadTypeDerivationData>
+    for PayloadTypeRegistry<'b, { STATE }>
+{
+    fn seeds(data: &PayloadTypeDerivationData) -> Vec<Vec<u8>> {
+        vec![b"payload_type".to_vec(), data.tag.to_vec()]
+    }
+}
+
+/// Signer of every dispatch, proving to handlers that the dispatcher claimed the VAA.
+pub type DispatchAuthority<'b> = Derive<Info<'b>, "dispatcher">;
@@ -0,0 +1,5 @@
+pub mod dispatch;
+pub mod register_payload_type;
+
+pub use dispatch::*;
+pub use register_payload_type::*;
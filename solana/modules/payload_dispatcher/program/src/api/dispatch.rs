@@ -0,0 +1,112 @@
+use crate::{
+    accounts::{
+        DispatchAuthority,
+        PayloadTypeDerivationData,
+        PayloadTypeRegistry,
+    },
+    messages::TaggedPayload,
+    PayloadDispatcherError::{
+        InvalidAuthority,
+        InvalidHandler,
+    },
+};
+use bridge::{
+    accounts::claim::{
+        self,
+        Claim,
+    },
+    PayloadMessage,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    program_error::ProgramError::NotEnoughAccountKeys,
+    pubkey::Pubkey,
+};
+use solitaire::{
+    processors::seeded::{
+        invoke_seeded,
+        Seeded,
+    },
+    *,
+};
+
+#[derive(FromAccounts)]
+pub struct Dispatch<'b> {
+    /// Pays for the claim of the VAA.
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Tagged VAA posted to the core bridge
+    pub vaa: PayloadMessage<'b, TaggedPayload>,
+
+    /// Claim of the VAA, prevents it from being dispatched twice
+    pub claim: Mut<Claim<'b>>,
+
+    /// Handler registered for the tag of the VAA
+    pub registry: PayloadTypeRegistry<'b, { AccountState::Initialized }>,
+
+    /// Authority of the dispatcher, signing the invocation of the handler
+    pub authority: DispatchAuthority<'b>,
+
+    /// Handler program, the accounts passed to it follow
+    pub handler: Info<'b>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct DispatchData {
+    /// Number of trailing accounts passed to the handler.
+    pub handler_accounts: u8,
+}
+
+/// Claim a tagged VAA and invoke the handler registered for its tag, see the crate docs for the
+/// handler interface. Anyone can dispatch a VAA, which fails as a whole if the handler fails.
+pub fn dispatch(ctx: &ExecutionContext, accs: &mut Dispatch, data: DispatchData) -> Result<()> {
+    accs.registry.verify_derivation(
+        ctx.program_id,
+        &PayloadTypeDerivationData { tag: accs.vaa.tag },
+    )?;
+    if *accs.handler.key != accs.registry.handler {
+        return Err(InvalidHandler.into());
+    }
+
+    let handler_accounts = ctx
+        .accounts
+        .len()
+        .checked_sub(data.handler_accounts as usize)
+        .map(|start| &ctx.accounts[start..])
+        .ok_or(NotEnoughAccountKeys)?;
+
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*accs.authority.key, true),
+        AccountMeta::new_readonly(*accs.vaa.info().key, false),
+    ];
+    accounts.extend(handler_accounts.iter().map(|info| AccountMeta {
+        pubkey: *info.key,
+        is_signer: info.is_signer,
+        is_writable: info.is_writable,
+    }));
+    let instruction = Instruction {
+        program_id: *accs.handler.key,
+        accounts,
+        data: accs.vaa.meta().payload.clone(),
+    };
+    invoke_seeded(&instruction, ctx, &accs.authority, None)?;
+
+    Ok(())
+}
+
+/// Fail unless `authority`, the first account of a handler invocation, is the dispatch authority
+/// of the dispatcher at `dispatcher` and signed, proving the dispatcher claimed the VAA passed
+/// second.
+pub fn verify_dispatch_authority(dispatcher: &Pubkey, authority: &AccountInfo) -> Result<()> {
+    let expected = DispatchAuthority::key(None, dispatcher);
+    if *authority.key != expected || !authority.is_signer {
+        return Err(InvalidAuthority.into());
+    }
+    Ok(())
+}
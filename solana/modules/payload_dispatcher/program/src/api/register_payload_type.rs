@@ -0,0 +1,66 @@
+use crate::{
+    accounts::{
+        PayloadTypeDerivationData,
+        PayloadTypeRegistry,
+    },
+    messages::PayloadTag,
+    PayloadDispatcherError::InvalidAuthority,
+};
+use solana_program::pubkey::Pubkey;
+use solitaire::{
+    processors::seeded::{
+        Creatable,
+        Seeded,
+    },
+    CreationLamports::Exempt,
+    *,
+};
+
+#[derive(FromAccounts)]
+pub struct RegisterPayloadType<'b> {
+    /// Pays for the registry entry when the tag is first registered.
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Authority of the payload type, set by the first registration.
+    pub authority: Signer<Info<'b>>,
+
+    /// Handler registered for the tag
+    pub registry: Mut<PayloadTypeRegistry<'b, { AccountState::MaybeInitialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct RegisterPayloadTypeData {
+    pub tag: PayloadTag,
+
+    /// Program payloads with the tag are dispatched to.
+    pub handler: Pubkey,
+}
+
+/// Dispatch payloads tagged with `tag` to `handler`.
+///
+/// Tags are registered first come, first served, by the authority defining the type. Registering
+/// again replaces the handler, for instance after the type moved to a new program, and can only be
+/// done by the authority that registered it first.
+pub fn register_payload_type(
+    ctx: &ExecutionContext,
+    accs: &mut RegisterPayloadType,
+    data: RegisterPayloadTypeData,
+) -> Result<()> {
+    let derivation_data = PayloadTypeDerivationData { tag: data.tag };
+    accs.registry
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    let initialized = accs.registry.is_initialized();
+    if initialized && accs.registry.authority != *accs.authority.key {
+        return Err(InvalidAuthority.into());
+    }
+
+    accs.registry.authority = *accs.authority.key;
+    accs.registry.handler = data.handler;
+    if !initialized {
+        accs.registry
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+    }
+
+    Ok(())
+}
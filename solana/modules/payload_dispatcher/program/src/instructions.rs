@@ -0,0 +1,138 @@
+use crate::{
+    accounts::{
+        DispatchAuthority,
+        PayloadTypeDerivationData,
+        PayloadTypeRegistry,
+    },
+    messages::PayloadTag,
+    DispatchData,
+    RegisterPayloadTypeData,
+};
+use borsh::BorshSerialize;
+use bridge::{
+    accounts::{
+        Claim,
+        ClaimDerivationData,
+    },
+    PostVAAData,
+};
+use solana_program::{
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    pubkey::Pubkey,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountState,
+};
+use std::convert::TryFrom;
+
+/// Required accounts
+///
+/// | name           | account                                    | signer |
+/// |----------------+--------------------------------------------+--------|
+/// | payer          | Pubkey                                     | true   |
+/// | authority      | Pubkey                                     | true   |
+/// | registry       | PDA(program_id, \["payload_type", tag\])   | false  |
+/// | rent           | sysvar rent                                | false  |
+/// | system_program | system program                             | false  |
+pub fn register_payload_type(
+    program_id: Pubkey,
+    payer: Pubkey,
+    authority: Pubkey,
+    tag: PayloadTag,
+    handler: Pubkey,
+) -> solitaire::Result<Instruction> {
+    let registry_key = PayloadTypeRegistry::<'_, { AccountState::MaybeInitialized }>::key(
+        &PayloadTypeDerivationData { tag },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(registry_key, false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: (
+            crate::instruction::Instruction::RegisterPayloadType,
+            RegisterPayloadTypeData { tag, handler },
+        )
+            .try_to_vec()?,
+    })
+}
+
+/// Required accounts
+///
+/// | name           | account                                               | signer |
+/// |----------------+-------------------------------------------------------+--------|
+/// | payer          | Pubkey                                                | true   |
+/// | message        | PDA(bridge_id,  \["PostedVAA", vaa hash\])            | false  |
+/// | claim          | PDA(program_id, \[emitter, emitter_chain, sequence\]) | false  |
+/// | registry       | PDA(program_id, \["payload_type", tag\])              | false  |
+/// | authority      | PDA(program_id, \["dispatcher"\])                     | false  |
+/// | handler        | handler program registered for the tag                | false  |
+/// | rent           | sysvar rent                                           | false  |
+/// | system_program | system program                                        | false  |
+///
+/// Followed by `handler_accounts`, passed on to the handler as they are.
+pub fn dispatch(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    handler: Pubkey,
+    handler_accounts: Vec<AccountMeta>,
+) -> solitaire::Result<Instruction> {
+    let tag = vaa
+        .payload
+        .get(..4)
+        .and_then(|tag| PayloadTag::try_from(tag).ok())
+        .ok_or(crate::PayloadDispatcherError::UntaggedPayload)?;
+    let claim_key = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: vaa.emitter_address,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+        },
+        &program_id,
+    );
+    let registry_key = PayloadTypeRegistry::<'_, { AccountState::Initialized }>::key(
+        &PayloadTypeDerivationData { tag },
+        &program_id,
+    );
+    let authority_key = DispatchAuthority::key(None, &program_id);
+    let count = u8::try_from(handler_accounts.len())
+        .map_err(|_| solana_program::program_error::ProgramError::InvalidArgument)?;
+
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(message_key, false),
+        AccountMeta::new(claim_key, false),
+        AccountMeta::new_readonly(registry_key, false),
+        AccountMeta::new_readonly(authority_key, false),
+        AccountMeta::new_readonly(handler, false),
+        // Dependencies
+        AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+    ];
+    accounts.extend(handler_accounts);
+
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data: (
+            crate::instruction::Instruction::Dispatch,
+            DispatchData {
+                handler_accounts: count,
+            },
+        )
+            .try_to_vec()?,
+    })
+}
@@ -0,0 +1,51 @@
+//! Dispatcher routing VAAs to the programs handling their payload type.
+//!
+//! By convention, payloads may start with a 4-byte type tag naming how the rest of the payload is
+//! encoded, see [`TaggedPayload`](messages::TaggedPayload). Whoever defines a type registers its
+//! tag here with `register_payload_type`, naming the program handling it. Once a tagged VAA is
+//! posted to the core bridge, anyone submits it to `dispatch`, which claims it and invokes the
+//! handler registered for its tag.
+//!
+//! Handlers receive the payload as instruction data, with the dispatch authority of this program
+//! signing first and the posted VAA second, followed by the accounts the submitter passed along.
+//! They check the authority with [`verify_dispatch_authority`] and, as tags are registered by the
+//! type and not by the emitter, which emitters they accept messages from.
+#![feature(adt_const_params)]
+#![allow(non_upper_case_globals)]
+#![allow(incomplete_features)]
+
+pub mod accounts;
+pub mod api;
+pub mod messages;
+
+use solitaire::*;
+
+#[cfg(feature = "instructions")]
+pub mod instructions;
+
+pub use api::{
+    dispatch,
+    register_payload_type,
+    verify_dispatch_authority,
+    Dispatch,
+    DispatchData,
+    RegisterPayloadType,
+    RegisterPayloadTypeData,
+};
+
+pub enum PayloadDispatcherError {
+    InvalidAuthority,
+    InvalidHandler,
+    UntaggedPayload,
+}
+
+impl From<PayloadDispatcherError> for SolitaireError {
+    fn from(e: PayloadDispatcherError) -> SolitaireError {
+        SolitaireError::Custom(e as u64)
+    }
+}
+
+solitaire! {
+    RegisterPayloadType => register_payload_type,
+    Dispatch            => dispatch,
+}
@@ -0,0 +1,64 @@
+use bridge::{
+    codec::ReadBytes,
+    vaa::{
+        DeserializePayload,
+        SerializePayload,
+    },
+};
+use solitaire::SolitaireError;
+use std::io::Write;
+
+/// Type tag of a payload, such as `*b"TKNT"`. Tags are chosen by whoever defines the type.
+pub type PayloadTag = [u8; 4];
+
+/// Payload following the type tag convention, the tag followed by a body encoded as the type
+/// defines. Payloads of emitters not following the convention are not dispatched, unless they
+/// happen to start with a registered tag, which handlers guard against by checking the emitter.
+#[derive(PartialEq, Debug, Clone)]
+pub struct TaggedPayload {
+    pub tag: PayloadTag,
+    pub body: Vec<u8>,
+}
+
+impl SerializePayload for TaggedPayload {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_all(&self.tag)?;
+        v.write_all(&self.body)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for TaggedPayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let tag = buf
+            .read_array()
+            .map_err(|_| crate::PayloadDispatcherError::UntaggedPayload)?;
+        let body = buf.to_vec();
+        *buf = &buf[buf.len()..];
+        Ok(TaggedPayload { tag, body })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_serde_tagged_payload() {
+        let original = TaggedPayload {
+            tag: *b"TEST",
+            body: vec![1, 2, 3],
+        };
+
+        let mut data = Vec::new();
+        original.serialize(&mut data).unwrap();
+        assert_eq!(data, b"TEST\x01\x02\x03");
+        let deser = TaggedPayload::deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(original, deser);
+    }
+
+    #[test]
+    pub fn test_deser_rejects_untagged_payload() {
+        assert!(TaggedPayload::deserialize(&mut &b"TES"[..]).is_err());
+    }
+}
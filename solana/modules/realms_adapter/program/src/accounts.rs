@@ -0,0 +1,63 @@
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use solana_program::pubkey::Pubkey;
+use solitaire::{
+    processors::seeded::{
+        AccountOwner,
+        Owned,
+        Seeded,
+    },
+    AccountState,
+    Data,
+    Info,
+};
+
+#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize)]
+pub struct DaoConfigData {
+    /// SPL Governance program the realm belongs to, the only program proposals are submitted to.
+    pub governance_program: Pubkey,
+
+    /// Governance of the realm that registered the DAO, the only one allowed to register again.
+    pub governance: Pubkey,
+
+    /// Chain the DAO emits its proposals from.
+    pub emitter_chain: u16,
+
+    /// Emitter the DAO emits its proposals with.
+    pub emitter_address: [u8; 32],
+}
+
+impl Owned for DaoConfigData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+/// DAO allowed to submit proposals to a realm.
+pub type DaoConfig<'b, const STATE: AccountState> = Data<'b, DaoConfigData, { STATE }>;
+
+pub struct DaoConfigDerivationData {
+    pub realm: Pubkey,
+}
+
+impl<'b, const STATE: AccountState> Seeded<&DaoConfigDerivationData> for DaoConfig<'b, { STATE }> {
+    fn seeds(data: &DaoConfigDerivationData) -> Vec<Vec<u8>> {
+        vec![b"dao".to_vec(), data.realm.to_bytes().to_vec()]
+    }
+}
+
+/// Signer of the instructions submitted to a realm, and payer of the accounts they create. Realms
+/// grant it authority, and fund it, like any other member.
+pub type RealmAuthority<'b> = Info<'b>;
+
+pub struct RealmAuthorityDerivationData {
+    pub realm: Pubkey,
+}
+
+impl<'b> Seeded<&RealmAuthorityDerivationData> for RealmAuthority<'b> {
+    fn seeds(data: &RealmAuthorityDerivationData) -> Vec<Vec<u8>> {
+        vec![b"authority".to_vec(), data.realm.to_bytes().to_vec()]
+    }
+}
@@ -0,0 +1,5 @@
+pub mod register_dao;
+pub mod submit_proposal;
+
+pub use register_dao::*;
+pub use submit_proposal::*;
@@ -0,0 +1,89 @@
+use crate::{
+    accounts::{
+        DaoConfig,
+        DaoConfigDerivationData,
+    },
+    RealmsAdapterError::{
+        InvalidGovernance,
+        InvalidRealm,
+    },
+};
+use solitaire::{
+    processors::seeded::{
+        Creatable,
+        Seeded,
+    },
+    CreationLamports::Exempt,
+    *,
+};
+
+#[derive(FromAccounts)]
+pub struct RegisterDao<'b> {
+    /// Pays for the config of the DAO when it is first registered.
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// SPL Governance realm the DAO submits proposals to.
+    pub realm: Info<'b>,
+
+    /// Governance of the realm registering the DAO, signing as the realm executes one of its
+    /// proposals.
+    pub governance: Signer<Info<'b>>,
+
+    /// DAO allowed to submit proposals to the realm
+    pub config: Mut<DaoConfig<'b, { AccountState::MaybeInitialized }>>,
+}
+
+impl<'a> From<&RegisterDao<'a>> for DaoConfigDerivationData {
+    fn from(accs: &RegisterDao<'a>) -> Self {
+        DaoConfigDerivationData {
+            realm: *accs.realm.key,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct RegisterDaoData {
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+}
+
+/// Let the DAO emitting with `emitter_address` on `emitter_chain` submit proposals to the realm.
+///
+/// Realms register their DAO through one of their own proposals, so that the governance signs.
+/// Registering again replaces the emitter, for instance after the DAO moved its contracts, and can
+/// only be done by the governance that registered it first.
+pub fn register_dao(
+    ctx: &ExecutionContext,
+    accs: &mut RegisterDao,
+    data: RegisterDaoData,
+) -> Result<()> {
+    let derivation_data: DaoConfigDerivationData = (&*accs).into();
+    accs.config
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    // SPL Governance accounts start with their account type, governances follow it with their
+    // realm.
+    let governance_program = *accs.governance.owner;
+    if *accs.realm.owner != governance_program {
+        return Err(InvalidRealm.into());
+    }
+    if accs.governance.data.borrow().get(1..33) != Some(accs.realm.key.as_ref()) {
+        return Err(InvalidGovernance.into());
+    }
+
+    let initialized = accs.config.is_initialized();
+    if initialized && accs.config.governance != *accs.governance.key {
+        return Err(InvalidGovernance.into());
+    }
+
+    accs.config.governance_program = governance_program;
+    accs.config.governance = *accs.governance.key;
+    accs.config.emitter_chain = data.emitter_chain;
+    accs.config.emitter_address = data.emitter_address;
+    if !initialized {
+        accs.config
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use crate::{
+    accounts::{
+        DaoConfig,
+        DaoConfigDerivationData,
+        RealmAuthority,
+        RealmAuthorityDerivationData,
+    },
+    messages::GovernancePayloadSubmitProposal,
+    RealmsAdapterError::{
+        InvalidEmitter,
+        InvalidGovernance,
+    },
+};
+use bridge::{
+    accounts::claim::{
+        self,
+        Claim,
+    },
+    PayloadMessage,
+};
+use solana_program::instruction::Instruction;
+use solitaire::{
+    invoke_signed,
+    processors::seeded::Seeded,
+    *,
+};
+
+#[derive(FromAccounts)]
+pub struct SubmitProposal<'b> {
+    /// Pays for the claim of the VAA.
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Proposal submission posted to the core bridge
+    pub vaa: PayloadMessage<'b, GovernancePayloadSubmitProposal>,
+
+    /// Claim of the VAA, prevents the instructions from being submitted twice
+    pub claim: Mut<Claim<'b>>,
+
+    /// DAO registered for the realm of the VAA
+    pub config: DaoConfig<'b, { AccountState::Initialized }>,
+
+    /// Realm authority of the adapter, signing and paying for the submitted instructions
+    pub authority: Mut<RealmAuthority<'b>>,
+
+    /// SPL Governance program of the realm, the accounts of the instructions follow
+    pub governance_program: Info<'b>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SubmitProposalData {}
+
+/// Invoke the SPL Governance instructions of a VAA emitted by the DAO registered for its realm.
+///
+/// Instructions run on the governance program of the realm only, with the realm authority signing
+/// wherever they mark it as a signer. The authority also pays for the proposals, transactions and
+/// vote records they create, realms keep it funded. Anyone can submit the VAA, which fails as a
+/// whole if an instruction fails, for instance once voting on the proposal ended.
+pub fn submit_proposal(
+    ctx: &ExecutionContext,
+    accs: &mut SubmitProposal,
+    _data: SubmitProposalData,
+) -> Result<()> {
+    let realm = accs.vaa.realm;
+    accs.config
+        .verify_derivation(ctx.program_id, &DaoConfigDerivationData { realm })?;
+    let authority_derivation = RealmAuthorityDerivationData { realm };
+    let authority = RealmAuthority::key(&authority_derivation, ctx.program_id);
+    if *accs.authority.key != authority {
+        return Err(SolitaireError::InvalidDerive(
+            *accs.authority.key,
+            authority,
+        ));
+    }
+
+    if !accs
+        .vaa
+        .emitter()
+        .is(accs.config.emitter_chain, &accs.config.emitter_address)
+    {
+        return Err(InvalidEmitter.into());
+    }
+    if *accs.governance_program.key != accs.config.governance_program {
+        return Err(InvalidGovernance.into());
+    }
+
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    let seeds = RealmAuthority::bumped_seeds(&authority_derivation, ctx.program_id);
+    let seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+    for instruction in &accs.vaa.instructions {
+        let instruction = Instruction {
+            program_id: accs.config.governance_program,
+            accounts: instruction.accounts.clone(),
+            data: instruction.data.clone(),
+        };
+        invoke_signed(&instruction, ctx.accounts, &[&seeds])?;
+    }
+
+    Ok(())
+}
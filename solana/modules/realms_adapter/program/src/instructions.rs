@@ -0,0 +1,152 @@
+use crate::{
+    accounts::{
+        DaoConfig,
+        DaoConfigDerivationData,
+        RealmAuthority,
+        RealmAuthorityDerivationData,
+    },
+    messages::GovernancePayloadSubmitProposal,
+    RegisterDaoData,
+    SubmitProposalData,
+};
+use borsh::BorshSerialize;
+use bridge::{
+    accounts::{
+        Claim,
+        ClaimDerivationData,
+    },
+    PostVAAData,
+};
+use solana_program::{
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    pubkey::Pubkey,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountState,
+};
+
+/// Required accounts
+///
+/// | name           | account                                | signer |
+/// |----------------+----------------------------------------+--------|
+/// | payer          | Pubkey                                 | true   |
+/// | realm          | Pubkey                                 | false  |
+/// | governance     | Pubkey                                 | true   |
+/// | config         | PDA(program_id, \["dao", realm\])      | false  |
+/// | rent           | sysvar rent                            | false  |
+/// | system_program | system program                         | false  |
+pub fn register_dao(
+    program_id: Pubkey,
+    payer: Pubkey,
+    realm: Pubkey,
+    governance: Pubkey,
+    emitter_chain: u16,
+    emitter_address: [u8; 32],
+) -> solitaire::Result<Instruction> {
+    let config_key = DaoConfig::<'_, { AccountState::MaybeInitialized }>::key(
+        &DaoConfigDerivationData { realm },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(realm, false),
+            AccountMeta::new_readonly(governance, true),
+            AccountMeta::new(config_key, false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: (
+            crate::instruction::Instruction::RegisterDao,
+            RegisterDaoData {
+                emitter_chain,
+                emitter_address,
+            },
+        )
+            .try_to_vec()?,
+    })
+}
+
+/// Required accounts
+///
+/// | name               | account                                               | signer |
+/// |--------------------+-------------------------------------------------------+--------|
+/// | payer              | Pubkey                                                | true   |
+/// | message            | PDA(bridge_id,  \["PostedVAA", vaa hash\])            | false  |
+/// | claim              | PDA(program_id, \[emitter, emitter_chain, sequence\]) | false  |
+/// | config             | PDA(program_id, \["dao", realm\])                     | false  |
+/// | authority          | PDA(program_id, \["authority", realm\])               | false  |
+/// | governance_program | SPL Governance program of the realm                   | false  |
+/// | rent               | sysvar rent                                           | false  |
+/// | system_program     | system program                                        | false  |
+///
+/// Followed by the accounts of the submitted instructions, which the authority signs for.
+pub fn submit_proposal(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    payload: &GovernancePayloadSubmitProposal,
+    governance_program: Pubkey,
+) -> solitaire::Result<Instruction> {
+    let claim_key = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: vaa.emitter_address,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+        },
+        &program_id,
+    );
+    let config_key = DaoConfig::<'_, { AccountState::Initialized }>::key(
+        &DaoConfigDerivationData {
+            realm: payload.realm,
+        },
+        &program_id,
+    );
+    let authority_key = RealmAuthority::key(
+        &RealmAuthorityDerivationData {
+            realm: payload.realm,
+        },
+        &program_id,
+    );
+
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(message_key, false),
+        AccountMeta::new(claim_key, false),
+        AccountMeta::new_readonly(config_key, false),
+        AccountMeta::new(authority_key, false),
+        AccountMeta::new_readonly(governance_program, false),
+        // Dependencies
+        AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+    ];
+    for meta in payload.instructions.iter().flat_map(|i| i.accounts.iter()) {
+        match accounts.iter_mut().find(|m| m.pubkey == meta.pubkey) {
+            Some(existing) => existing.is_writable |= meta.is_writable,
+            // Only the authority signs, as the adapter invokes the instructions.
+            None => accounts.push(AccountMeta {
+                pubkey: meta.pubkey,
+                is_signer: false,
+                is_writable: meta.is_writable,
+            }),
+        }
+    }
+
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data: (
+            crate::instruction::Instruction::SubmitProposal,
+            SubmitProposalData {},
+        )
+            .try_to_vec()?,
+    })
+}
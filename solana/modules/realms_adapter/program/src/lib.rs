@@ -0,0 +1,48 @@
+//! Adapter letting a DAO governing from another chain act in its Realms (SPL Governance) realm.
+//!
+//! The DAO emits governance-style messages naming its realm and the SPL Governance instructions to
+//! run, typically creating a proposal, inserting its transactions, signing it off and casting the
+//! vote of the DAO. Once signed by the guardians and posted to the core bridge, anyone submits the
+//! VAA here and the instructions are invoked with the realm authority of the adapter signing.
+//!
+//! Realms only lets the realm authority act as much as the realm granted it, usually by making it
+//! the governance delegate of a token owner record holding the voting power of the DAO. Executing
+//! an approved proposal is left to Realms, where anyone can execute its transactions.
+#![feature(adt_const_params)]
+#![allow(non_upper_case_globals)]
+#![allow(incomplete_features)]
+
+pub mod accounts;
+pub mod api;
+pub mod messages;
+
+use solitaire::*;
+
+#[cfg(feature = "instructions")]
+pub mod instructions;
+
+pub use api::{
+    register_dao,
+    submit_proposal,
+    RegisterDao,
+    RegisterDaoData,
+    SubmitProposal,
+    SubmitProposalData,
+};
+
+pub enum RealmsAdapterError {
+    InvalidGovernance,
+    InvalidEmitter,
+    InvalidRealm,
+}
+
+impl From<RealmsAdapterError> for SolitaireError {
+    fn from(e: RealmsAdapterError) -> SolitaireError {
+        SolitaireError::Custom(e as u64)
+    }
+}
+
+solitaire! {
+    RegisterDao    => register_dao,
+    SubmitProposal => submit_proposal,
+}
@@ -0,0 +1,182 @@
+use bridge::{
+    codec::{
+        ReadBytes,
+        WriteBytes,
+    },
+    vaa::{
+        DeserializePayload,
+        SerializePayload,
+    },
+    DeserializeGovernancePayload,
+    SerializeGovernancePayload,
+};
+use solana_program::{
+    instruction::AccountMeta,
+    program_error::ProgramError::InvalidAccountData,
+    pubkey::Pubkey,
+};
+use solitaire::SolitaireError;
+use std::{
+    convert::TryFrom,
+    io::{
+        Cursor,
+        Read,
+        Write,
+    },
+};
+
+pub const MODULE: &str = "RealmsAdapter";
+
+const SIGNER: u8 = 1;
+const WRITABLE: u8 = 2;
+
+/// SPL Governance instruction of a proposal submission, invoked on the governance program the
+/// realm belongs to.
+#[derive(PartialEq, Debug, Clone)]
+pub struct ProposalInstruction {
+    pub accounts: Vec<AccountMeta>,
+    pub data: Vec<u8>,
+}
+
+/// Instructions a DAO submits to its realm, invoked in order.
+///
+/// After the governance header, the payload holds the realm followed by the number of
+/// instructions (u8). Each instruction holds the number of its accounts (u8), each account key
+/// followed by a flags byte (1 signer, 2 writable), then the length (u16) of its data and the data.
+#[derive(PartialEq, Debug)]
+pub struct GovernancePayloadSubmitProposal {
+    pub realm: Pubkey,
+    pub instructions: Vec<ProposalInstruction>,
+}
+
+impl SerializePayload for GovernancePayloadSubmitProposal {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        self.write_governance_header(v)?;
+        v.write_all(&self.realm.to_bytes())?;
+
+        let count = u8::try_from(self.instructions.len()).map_err(|_| InvalidAccountData)?;
+        v.write_u8(count)?;
+        for instruction in &self.instructions {
+            let count = u8::try_from(instruction.accounts.len()).map_err(|_| InvalidAccountData)?;
+            v.write_u8(count)?;
+            for meta in &instruction.accounts {
+                v.write_all(&meta.pubkey.to_bytes())?;
+                let mut flags = 0;
+                if meta.is_signer {
+                    flags |= SIGNER;
+                }
+                if meta.is_writable {
+                    flags |= WRITABLE;
+                }
+                v.write_u8(flags)?;
+            }
+
+            let len = u16::try_from(instruction.data.len()).map_err(|_| InvalidAccountData)?;
+            v.write_u16_be(len)?;
+            v.write_all(&instruction.data)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSubmitProposal
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let realm = Pubkey::new_from_array(c.read_array()?);
+
+        let count = c.read_u8()?;
+        let mut instructions = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let count = c.read_u8()?;
+            let mut accounts = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                let pubkey = Pubkey::new_from_array(c.read_array()?);
+                let flags = c.read_u8()?;
+                if flags & !(SIGNER | WRITABLE) != 0 {
+                    return Err(InvalidAccountData.into());
+                }
+                accounts.push(AccountMeta {
+                    pubkey,
+                    is_signer: flags & SIGNER != 0,
+                    is_writable: flags & WRITABLE != 0,
+                });
+            }
+
+            let mut data = vec![0u8; c.read_u16_be()? as usize];
+            c.read_exact(&mut data)?;
+            instructions.push(ProposalInstruction { accounts, data });
+        }
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSubmitProposal {
+            realm,
+            instructions,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSubmitProposal {
+    const MODULE: &'static str = MODULE;
+    const ACTION: u8 = 1;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSubmitProposal {
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::RngCore;
+
+    fn random_key() -> Pubkey {
+        let mut key = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut key);
+        Pubkey::new_from_array(key)
+    }
+
+    #[test]
+    pub fn test_serde_submit_proposal() {
+        let original = GovernancePayloadSubmitProposal {
+            realm: random_key(),
+            instructions: vec![
+                ProposalInstruction {
+                    accounts: vec![
+                        AccountMeta::new(random_key(), false),
+                        AccountMeta::new_readonly(random_key(), true),
+                        AccountMeta::new(random_key(), true),
+                    ],
+                    data: vec![6, 1, 2, 3],
+                },
+                ProposalInstruction {
+                    accounts: vec![],
+                    data: vec![],
+                },
+            ],
+        };
+
+        let data = SerializeGovernancePayload::try_to_vec(&original).unwrap();
+        let deser = GovernancePayloadSubmitProposal::deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(original, deser);
+    }
+
+    #[test]
+    pub fn test_deser_rejects_trailing_bytes() {
+        let original = GovernancePayloadSubmitProposal {
+            realm: random_key(),
+            instructions: vec![],
+        };
+
+        let mut data = SerializeGovernancePayload::try_to_vec(&original).unwrap();
+        data.push(0);
+        assert!(GovernancePayloadSubmitProposal::deserialize(&mut data.as_slice()).is_err());
+    }
+}
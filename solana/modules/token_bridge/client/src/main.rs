@@ -52,6 +52,10 @@ use solitaire::{
     Derive,
     Info,
 };
+use wormhole_solana_consts::{
+    emitters,
+    seeds,
+};
 
 struct Config {
     rpc_client: RpcClient,
@@ -88,6 +92,26 @@ fn command_init_bridge(config: &Config, bridge: &Pubkey, core_bridge: &Pubkey) -
     Ok(Some(transaction))
 }
 
+/// Print the emitter address of a foreign token bridge, rejecting addresses that differ from the
+/// known mainnet deployment of the chain.
+fn command_foreign_emitter(chain: u16, address: &str) -> CommmandResult {
+    let emitter = emitters::from_native(chain, address)
+        .ok_or_else(|| format!("{} is not an address of chain {}", address, chain))?;
+    println!("Emitter Address: {}", hex::encode(emitter));
+
+    if let Some(known) = emitters::mainnet::token_bridge(chain) {
+        if known != emitter {
+            return Err(format!(
+                "Emitter differs from the mainnet token bridge {}",
+                hex::encode(known)
+            )
+            .into());
+        }
+    }
+
+    Ok(None)
+}
+
 fn command_create_meta(
     config: &Config,
     mint: &Pubkey,
@@ -99,7 +123,7 @@ fn command_create_meta(
 
     let meta_acc = Pubkey::find_program_address(
         &[
-            "metadata".as_bytes(),
+            seeds::SPL_TOKEN_METADATA.as_bytes(),
             spl_token_metadata::id().as_ref(),
             mint.as_ref(),
         ],
@@ -220,6 +244,28 @@ fn main() {
                         .help("Specify the token bridge program address"),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("foreign-emitter")
+                .about("Get the emitter address a foreign token bridge is registered with")
+                .arg(
+                    Arg::with_name("chain")
+                        .long("chain")
+                        .value_name("CHAIN_ID")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("Wormhole chain ID of the foreign token bridge"),
+                )
+                .arg(
+                    Arg::with_name("address")
+                        .long("address")
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help("Native hex address of the foreign token bridge emitter"),
+                ),
+        )
         .subcommand(
             SubCommand::with_name("metadata")
                 .about("Get the derived metadata associated with token mints")
@@ -321,16 +367,21 @@ fn main() {
         }
         ("emitter", Some(arg_matches)) => {
             let bridge = pubkey_of(arg_matches, "bridge").unwrap();
-            let emitter = <Derive<Info<'_>, "emitter">>::key(None, &bridge);
+            let emitter = <Derive<Info<'_>, { seeds::EMITTER }>>::key(None, &bridge);
             println!("Emitter Key: {}", emitter);
 
             Ok(None)
         }
+        ("foreign-emitter", Some(arg_matches)) => {
+            let chain: u16 = value_of(arg_matches, "chain").unwrap();
+            let address: String = value_of(arg_matches, "address").unwrap();
+            command_foreign_emitter(chain, &address)
+        }
         ("metadata", Some(arg_matches)) => {
             let mint = pubkey_of(arg_matches, "mint").unwrap();
             let meta_acc = Pubkey::find_program_address(
                 &[
-                    "metadata".as_bytes(),
+                    seeds::SPL_TOKEN_METADATA.as_bytes(),
                     spl_token_metadata::id().as_ref(),
                     mint.as_ref(),
                 ],
@@ -12,19 +12,20 @@ use solitaire::{
     *,
 };
 use spl_token_metadata::state::Key::MetadataV1;
+use wormhole_solana_consts::seeds;
 
-pub type AuthoritySigner<'b> = Derive<Info<'b>, "authority_signer">;
-pub type CustodySigner<'b> = Derive<Info<'b>, "custody_signer">;
-pub type MintSigner<'b> = Derive<Info<'b>, "mint_signer">;
+pub type AuthoritySigner<'b> = Derive<Info<'b>, { seeds::AUTHORITY_SIGNER }>;
+pub type CustodySigner<'b> = Derive<Info<'b>, { seeds::CUSTODY_SIGNER }>;
+pub type MintSigner<'b> = Derive<Info<'b>, { seeds::MINT_SIGNER }>;
 
 pub type CoreBridge<'a, const STATE: AccountState> = Data<'a, BridgeData, { STATE }>;
 
-pub type EmitterAccount<'b> = Derive<Info<'b>, "emitter">;
+pub type EmitterAccount<'b> = Derive<Info<'b>, { seeds::EMITTER }>;
 
 pub type ConfigAccount<'b, const STATE: AccountState> =
-    Derive<Data<'b, Config, { STATE }>, "config">;
+    Derive<Data<'b, Config, { STATE }>, { seeds::CONFIG }>;
 
-pub type CustodyAccount<'b, const STATE: AccountState> = Data<'b, SplAccount, { STATE }>;
+pub type CustodyAccount<'b, const STATE: AccountState> = Data<'b, InterfaceAccount, { STATE }>;
 
 pub struct CustodyAccountDerivationData {
     pub mint: Pubkey,
@@ -48,7 +49,7 @@ pub struct WrappedDerivationData {
 impl<'b, const STATE: AccountState> Seeded<&WrappedDerivationData> for WrappedMint<'b, { STATE }> {
     fn seeds(data: &WrappedDerivationData) -> Vec<Vec<u8>> {
         vec![
-            String::from("wrapped").as_bytes().to_vec(),
+            seeds::WRAPPED.as_bytes().to_vec(),
             data.token_chain.to_be_bytes().to_vec(),
             data.token_address.to_vec(),
         ]
@@ -66,7 +67,7 @@ impl<'b, const STATE: AccountState> Seeded<&WrappedMetaDerivationData>
 {
     fn seeds(data: &WrappedMetaDerivationData) -> Vec<Vec<u8>> {
         vec![
-            String::from("meta").as_bytes().to_vec(),
+            seeds::WRAPPED_META.as_bytes().to_vec(),
             data.mint_key.to_bytes().to_vec(),
         ]
     }
@@ -90,6 +91,215 @@ impl<'b, const STATE: AccountState> Seeded<&EndpointDerivationData> for Endpoint
     }
 }
 
+/// Origin chain and address to local mint lookup, see [`TokenId`].
+pub type TokenIdAccount<'b, const STATE: AccountState> = Data<'b, TokenId, { STATE }>;
+
+pub struct TokenIdDerivationData {
+    pub token_chain: ChainID,
+    pub token_address: ForeignAddress,
+}
+
+impl<'b, const STATE: AccountState> Seeded<&TokenIdDerivationData> for TokenIdAccount<'b, { STATE }> {
+    fn seeds(data: &TokenIdDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            seeds::TOKEN_ID.as_bytes().to_vec(),
+            data.token_chain.to_be_bytes().to_vec(),
+            data.token_address.to_vec(),
+        ]
+    }
+}
+
+/// Metadata update authority decreed for a wrapped mint, see [`MetadataAuthorityDecree`].
+pub type MetadataAuthority<'b, const STATE: AccountState> =
+    Data<'b, MetadataAuthorityDecree, { STATE }>;
+
+pub struct MetadataAuthorityDerivationData {
+    /// Wrapped mint the decree applies to, all of them if unset.
+    pub mint: Option<Pubkey>,
+}
+
+impl<'b, const STATE: AccountState> Seeded<&MetadataAuthorityDerivationData>
+    for MetadataAuthority<'b, { STATE }>
+{
+    fn seeds(data: &MetadataAuthorityDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            seeds::METADATA_AUTHORITY.as_bytes().to_vec(),
+            data.mint.unwrap_or_default().to_bytes().to_vec(),
+        ]
+    }
+}
+
+/// Transfer cap governance set for a mint, see [`TransferCapData`].
+pub type TransferCap<'b, const STATE: AccountState> = Data<'b, TransferCapData, { STATE }>;
+
+pub struct TransferCapDerivationData {
+    pub mint: Pubkey,
+}
+
+impl<'b, const STATE: AccountState> Seeded<&TransferCapDerivationData>
+    for TransferCap<'b, { STATE }>
+{
+    fn seeds(data: &TransferCapDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            seeds::TRANSFER_CAP.as_bytes().to_vec(),
+            data.mint.to_bytes().to_vec(),
+        ]
+    }
+}
+
+/// Fail if `amount` of `mint` exceeds the cap governance set for it. The cap is looked up among the
+/// instruction accounts so that transfer account layouts stay unchanged. It must always be passed,
+/// as leaving it out would otherwise skip the check, an empty account means the mint is uncapped.
+#[cfg(feature = "transfer-cap")]
+pub fn verify_transfer_cap(ctx: &ExecutionContext, mint: &Pubkey, amount: u64) -> Result<()> {
+    use solana_program::sysvar::{
+        clock::Clock,
+        Sysvar,
+    };
+
+    let key = TransferCap::<'_, { AccountState::Initialized }>::key(
+        &TransferCapDerivationData { mint: *mint },
+        ctx.program_id,
+    );
+    let info = ctx
+        .accounts
+        .iter()
+        .find(|info| *info.key == key)
+        .ok_or(TokenBridgeError::MissingTransferCap)?;
+
+    if info.data_is_empty() {
+        return Ok(());
+    }
+    if info.owner != ctx.program_id {
+        return Err(SolitaireError::InvalidOwner(*info.owner));
+    }
+
+    let cap = TransferCapData::try_from_slice(&info.data.borrow())?;
+    if amount > cap.max_amount(Clock::get()?.unix_timestamp) {
+        return Err(TokenBridgeError::TransferCapExceeded.into());
+    }
+
+    Ok(())
+}
+
+/// Fail while the core bridge has inbound transfers paused, see
+/// `BridgeConfig::PAUSE_INBOUND_TRANSFERS`. The bridge config is looked up among the instruction
+/// accounts like the transfer cap, and must always be passed for the same reason.
+#[cfg(feature = "inbound-pause")]
+pub fn verify_inbound_unpaused(ctx: &ExecutionContext, config: &Config) -> Result<()> {
+    use bridge::accounts::{
+        Bridge,
+        BridgeConfig,
+    };
+
+    let key = Bridge::<'_, { AccountState::Initialized }>::key(None, &config.wormhole_bridge);
+    let info = ctx
+        .accounts
+        .iter()
+        .find(|info| *info.key == key)
+        .ok_or(TokenBridgeError::MissingBridgeConfig)?;
+    if *info.owner != config.wormhole_bridge {
+        return Err(SolitaireError::InvalidOwner(*info.owner));
+    }
+
+    let bridge = BridgeData::try_from_slice(&info.data.borrow())?;
+    if bridge
+        .config
+        .is_paused(BridgeConfig::PAUSE_INBOUND_TRANSFERS)
+    {
+        return Err(TokenBridgeError::BridgePaused.into());
+    }
+
+    Ok(())
+}
+
+/// Relayers allowed to redeem transfers to a token account, see [`RelayerAllowlistData`].
+pub type RelayerAllowlist<'b, const STATE: AccountState> =
+    Data<'b, RelayerAllowlistData, { STATE }>;
+
+pub struct RelayerAllowlistDerivationData {
+    pub token_account: Pubkey,
+}
+
+impl<'b, const STATE: AccountState> Seeded<&RelayerAllowlistDerivationData>
+    for RelayerAllowlist<'b, { STATE }>
+{
+    fn seeds(data: &RelayerAllowlistDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            seeds::RELAYER_ALLOWLIST.as_bytes().to_vec(),
+            data.token_account.to_bytes().to_vec(),
+        ]
+    }
+}
+
+/// Fail if the owner of `token_account` restricted redemptions to it to relayers other than
+/// `relayer`. Like the transfer cap, the allowlist is looked up among the instruction accounts and
+/// must always be passed, an empty account means anyone may redeem.
+#[cfg(feature = "relayer-allowlist")]
+pub fn verify_relayer(
+    ctx: &ExecutionContext,
+    token_account: &Pubkey,
+    relayer: &Pubkey,
+) -> Result<()> {
+    let key = RelayerAllowlist::<'_, { AccountState::Initialized }>::key(
+        &RelayerAllowlistDerivationData {
+            token_account: *token_account,
+        },
+        ctx.program_id,
+    );
+    let info = ctx
+        .accounts
+        .iter()
+        .find(|info| *info.key == key)
+        .ok_or(TokenBridgeError::MissingRelayerAllowlist)?;
+
+    if info.data_is_empty() {
+        return Ok(());
+    }
+    if info.owner != ctx.program_id {
+        return Err(SolitaireError::InvalidOwner(*info.owner));
+    }
+
+    let allowlist = RelayerAllowlistData::try_from_slice(&info.data.borrow())?;
+    if !allowlist.allows(relayer) {
+        return Err(TokenBridgeError::RelayerNotAllowlisted.into());
+    }
+
+    Ok(())
+}
+
+/// Latest attestation of the native tokens in custody, see [`CustodyAttestationData`].
+pub type CustodyAttestation<'b, const STATE: AccountState> =
+    Derive<Data<'b, CustodyAttestationData, { STATE }>, { seeds::CUSTODY_ATTESTATION }>;
+
+/// Chain ID of a forked deployment, see [`RecoveredChainData`].
+pub type RecoveredChain<'b, const STATE: AccountState> =
+    Derive<Data<'b, RecoveredChainData, { STATE }>, { seeds::RECOVERED_CHAIN }>;
+
+/// Strategy allowed to borrow idle custody of a mint, see [`YieldStrategyData`].
+pub type YieldStrategy<'b, const STATE: AccountState> = Data<'b, YieldStrategyData, { STATE }>;
+
+pub struct YieldStrategyDerivationData {
+    pub mint: Pubkey,
+}
+
+impl<'b, const STATE: AccountState> Seeded<&YieldStrategyDerivationData>
+    for YieldStrategy<'b, { STATE }>
+{
+    fn seeds(data: &YieldStrategyDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            seeds::YIELD_STRATEGY.as_bytes().to_vec(),
+            data.mint.to_bytes().to_vec(),
+        ]
+    }
+}
+
+/// Address a yield strategy `program` signs with when it borrows custody, derived from the
+/// program like the sender of a transfer with payload.
+pub fn borrower(program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::BORROWER.as_bytes()], program).0
+}
+
 pub type SplTokenMeta<'b> = Info<'b>;
 
 pub struct SplTokenMetaDerivationData {
@@ -99,7 +309,7 @@ pub struct SplTokenMetaDerivationData {
 impl<'b> Seeded<&SplTokenMetaDerivationData> for SplTokenMeta<'b> {
     fn seeds(data: &SplTokenMetaDerivationData) -> Vec<Vec<u8>> {
         vec![
-            "metadata".as_bytes().to_vec(),
+            seeds::SPL_TOKEN_METADATA.as_bytes().to_vec(),
             spl_token_metadata::id().as_ref().to_vec(),
             data.mint.as_ref().to_vec(),
         ]
@@ -0,0 +1,182 @@
+//! Wormhole caps transferred amounts at 8 decimals. Native tokens with more decimals are truncated
+//! when sent and scaled back up when redeemed, the truncated remainder is dust that stays with the
+//! sender. Wrapped mints never have more than 8 decimals and travel unchanged.
+
+/// Decimals amounts are encoded with on the wire at most.
+pub const MAX_DECIMALS: u8 = 8;
+
+/// Factor between an amount in `decimals` and its normalized amount.
+pub fn divisor(decimals: u8) -> u64 {
+    10u64.pow(MAX_DECIMALS.max(decimals) as u32 - MAX_DECIMALS as u32)
+}
+
+/// Amount in at most 8 decimals, as encoded in transfers.
+pub fn normalize(amount: u64, decimals: u8) -> u64 {
+    amount / divisor(decimals)
+}
+
+/// Amount in `decimals` of a normalized amount received in a transfer.
+pub fn denormalize(amount: u64, decimals: u8) -> u64 {
+    amount * divisor(decimals)
+}
+
+/// Part of `amount` lost to normalization, never taken from the sender.
+pub fn dust(amount: u64, decimals: u8) -> u64 {
+    amount % divisor(decimals)
+}
+
+/// Amount moved into custody to send `amount` of a native token, the dust stays with the sender.
+pub fn truncate(amount: u64, decimals: u8) -> u64 {
+    denormalize(normalize(amount, decimals), decimals)
+}
+
+/// Normalized amount and fee of a native transfer into a custody account that received
+/// `received`, net of Token-2022 transfer fees. Its dust stays in custody. `None` if the fee exceeds
+/// the bridged amount.
+pub fn bridged(received: u64, fee: u64, decimals: u8) -> Option<(u64, u64)> {
+    let amount = normalize(received, decimals);
+    let fee = normalize(fee, decimals);
+    if fee > amount {
+        return None;
+    }
+    Some((amount, fee))
+}
+
+/// Amounts paid to the recipient and the relayer of a transfer of the normalized `amount` and
+/// `fee`, before Token-2022 transfer fees. `None` if the fee exceeds the amount.
+pub fn redeemed(amount: u64, fee: u64, decimals: u8) -> Option<(u64, u64)> {
+    let amount = denormalize(amount, decimals);
+    let fee = denormalize(fee, decimals);
+    Some((amount.checked_sub(fee)?, fee))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // Decimals of mints with a divisor representable in u64.
+    fn decimals() -> impl Strategy<Value = u8> {
+        0..=27u8
+    }
+
+    // Token-2022 transfer fee configurations, zero for mints without the extension.
+    fn transfer_fees() -> impl Strategy<Value = (u16, u64)> {
+        prop_oneof![Just((0u16, 0u64)), (0..=10_000u16, any::<u64>())]
+    }
+
+    // Fee the token program withholds from a transfer of `amount`, rounded up.
+    fn transfer_fee(amount: u64, (basis_points, maximum_fee): (u16, u64)) -> u64 {
+        let fee = (amount as u128 * basis_points as u128 + 9_999) / 10_000;
+        (fee as u64).min(maximum_fee)
+    }
+
+    proptest! {
+        #[test]
+        fn native_round_trip(amount: u64, decimals in decimals()) {
+            // Sent out truncated, the recipient gets the amount minus the dust left with the sender.
+            let redeemed = denormalize(normalize(amount, decimals), decimals);
+            prop_assert_eq!(redeemed, amount - dust(amount, decimals));
+            prop_assert!(dust(amount, decimals) < divisor(decimals));
+        }
+
+        #[test]
+        fn native_fee_round_trip(amount: u64, fee: u64, decimals in decimals()) {
+            // Fees are normalized like the amount, a fee not above the amount stays that way so that
+            // redeeming can always pay it out of the amount.
+            let fee = fee.min(amount);
+            let sent_amount = normalize(amount, decimals);
+            let sent_fee = normalize(fee, decimals);
+            prop_assert!(sent_fee <= sent_amount);
+
+            let redeemed = denormalize(sent_amount, decimals) - denormalize(sent_fee, decimals);
+            prop_assert_eq!(
+                redeemed,
+                denormalize(sent_amount - sent_fee, decimals)
+            );
+            prop_assert!(redeemed <= (amount - fee).saturating_add(divisor(decimals) - 1));
+        }
+
+        #[test]
+        fn wrapped_round_trip(amount: u64, decimals in 0..=MAX_DECIMALS) {
+            // Wrapped mints are created with at most 8 decimals, nothing is truncated.
+            prop_assert_eq!(divisor(decimals), 1);
+            prop_assert_eq!(dust(amount, decimals), 0);
+            prop_assert_eq!(denormalize(normalize(amount, decimals), decimals), amount);
+        }
+
+        #[test]
+        fn native_transfer_conserves_amount(
+            amount: u64,
+            fee: u64,
+            decimals in decimals(),
+            transfer_fees in transfer_fees(),
+        ) {
+            // The sender keeps the dust, custody receives the rest net of the inbound transfer fee.
+            let fee = fee.min(amount);
+            let sent = truncate(amount, decimals);
+            prop_assert_eq!(sent, amount - dust(amount, decimals));
+            let received = sent - transfer_fee(sent, transfer_fees);
+
+            let (bridged_amount, bridged_fee) = match bridged(received, fee, decimals) {
+                Some(bridged) => bridged,
+                None => {
+                    // Only transfer fees shrink the amount below the fee.
+                    prop_assert!(received < sent);
+                    return Ok(());
+                }
+            };
+
+            // Custody pays out all it received but its own dust, so it never runs short.
+            let (to_recipient, to_relayer) =
+                redeemed(bridged_amount, bridged_fee, decimals).unwrap();
+            prop_assert_eq!(to_recipient + to_relayer, received - dust(received, decimals));
+            prop_assert_eq!(to_relayer, truncate(fee, decimals));
+
+            // Without transfer fees, the round trip only loses the dust of the amount.
+            if transfer_fees.0 == 0 {
+                prop_assert_eq!(to_recipient + to_relayer, amount - dust(amount, decimals));
+            }
+
+            // Recipient and relayer receive their shares net of the outbound transfer fees.
+            let credited = to_recipient - transfer_fee(to_recipient, transfer_fees)
+                + to_relayer - transfer_fee(to_relayer, transfer_fees);
+            prop_assert!(credited <= amount - dust(amount, decimals));
+        }
+
+        #[test]
+        fn wrapped_transfer_conserves_amount(
+            amount: u64,
+            fee: u64,
+            decimals in 0..=MAX_DECIMALS,
+        ) {
+            // Wrapped tokens are burned as sent and minted as redeemed, keeping the supply.
+            let fee = fee.min(amount);
+            let (to_recipient, to_relayer) = redeemed(
+                normalize(amount, decimals),
+                normalize(fee, decimals),
+                decimals,
+            )
+            .unwrap();
+            prop_assert_eq!(to_recipient + to_relayer, amount);
+            prop_assert_eq!(to_relayer, fee);
+        }
+
+        #[test]
+        fn redeemed_fee_never_exceeds_amount(amount: u64, fee: u64, decimals in decimals()) {
+            // Transfers whose fee exceeds their amount are rejected rather than underflowing.
+            let amount = normalize(amount, decimals);
+            let fee = normalize(fee, decimals);
+            prop_assert_eq!(redeemed(amount, fee, decimals).is_some(), fee <= amount);
+        }
+
+        #[test]
+        fn normalization_is_deterministic(amount: u64, decimals in decimals()) {
+            // Truncation only depends on the amount and decimals, a redeemed amount is sent again
+            // without further loss.
+            let redeemed = denormalize(normalize(amount, decimals), decimals);
+            prop_assert_eq!(dust(redeemed, decimals), 0);
+            prop_assert_eq!(normalize(redeemed, decimals), normalize(amount, decimals));
+        }
+    }
+}
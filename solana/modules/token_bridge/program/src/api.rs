@@ -1,17 +1,33 @@
 pub mod attest;
+pub mod attest_and_transfer;
+pub mod attest_custody;
 pub mod complete_transfer;
 pub mod complete_transfer_payload;
+pub mod complete_transfers_batch;
 pub mod create_wrapped;
 pub mod governance;
 pub mod initialize;
+pub mod probe_redemption;
+pub mod reclaim_expired_transfer;
+pub mod relayer_allowlist;
+pub mod resolve_token_id;
 pub mod transfer;
 pub mod transfer_payload;
+pub mod yield_strategy;
 
 pub use attest::*;
+pub use attest_and_transfer::*;
+pub use attest_custody::*;
 pub use complete_transfer::*;
 pub use complete_transfer_payload::*;
+pub use complete_transfers_batch::*;
 pub use create_wrapped::*;
 pub use governance::*;
 pub use initialize::*;
+pub use probe_redemption::*;
+pub use reclaim_expired_transfer::*;
+pub use relayer_allowlist::*;
+pub use resolve_token_id::*;
 pub use transfer::*;
 pub use transfer_payload::*;
+pub use yield_strategy::*;
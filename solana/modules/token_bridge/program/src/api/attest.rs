@@ -10,6 +10,10 @@ use crate::{
         WrappedTokenMeta,
     },
     messages::PayloadAssetMeta,
+    token_interface::{
+        mint_metadata,
+        token_program,
+    },
     types::*,
 };
 use bridge::{
@@ -24,10 +28,10 @@ use solana_program::{
         AccountMeta,
         Instruction,
     },
-    program::invoke,
     sysvar::clock::Clock,
 };
 use solitaire::{
+    invoke,
     processors::seeded::{
         invoke_seeded,
         Seeded,
@@ -42,7 +46,7 @@ pub struct AttestToken<'b> {
     pub config: Mut<ConfigAccount<'b, { AccountState::Initialized }>>,
 
     /// Mint to attest
-    pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
+    pub mint: Data<'b, InterfaceMint, { AccountState::Initialized }>,
     pub wrapped_meta: WrappedTokenMeta<'b, { AccountState::Uninitialized }>,
 
     /// SPL Metadata for the associated Mint
@@ -101,26 +105,14 @@ pub fn attest_token(
 
     invoke(&transfer_ix, ctx.accounts)?;
 
+    token_program(accs.mint.info())?;
+
     // Enfoce wrapped meta to be uninitialized.
     let derivation_data: WrappedMetaDerivationData = (&*accs).into();
     accs.wrapped_meta
         .verify_derivation(ctx.program_id, &derivation_data)?;
 
-    // Create Asset Metadata
-    let mut payload = PayloadAssetMeta {
-        token_address: accs.mint.info().key.to_bytes(),
-        token_chain: CHAIN_ID_SOLANA,
-        decimals: accs.mint.decimals,
-        symbol: "".to_string(),
-        name: "".to_string(),
-    };
-
-    // Assign metadata if an SPL Metadata account exists for the SPL token in question.
-    if !accs.spl_metadata.data_is_empty() {
-        let metadata = deserialize_and_verify_metadata(&accs.spl_metadata, (&*accs).into())?;
-        payload.name = metadata.data.name.clone();
-        payload.symbol = metadata.data.symbol;
-    }
+    let payload = asset_meta(&accs.mint, &accs.spl_metadata)?;
 
     let params = (
         bridge::instruction::Instruction::PostMessage,
@@ -150,3 +142,35 @@ pub fn attest_token(
 
     Ok(())
 }
+
+/// Asset metadata attesting the native `mint`.
+pub(crate) fn asset_meta(
+    mint: &Data<InterfaceMint, { AccountState::Initialized }>,
+    spl_metadata: &SplTokenMeta,
+) -> Result<PayloadAssetMeta> {
+    let mut payload = PayloadAssetMeta {
+        token_address: mint.info().key.to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        decimals: mint.decimals,
+        symbol: "".to_string(),
+        name: "".to_string(),
+    };
+
+    // Assign metadata from the Token-2022 mint itself if it holds it, or else if an SPL Metadata
+    // account exists for the SPL token in question.
+    if let Some((name, symbol)) = mint_metadata(mint.info())? {
+        payload.name = name;
+        payload.symbol = symbol;
+    } else if !spl_metadata.data_is_empty() {
+        let metadata = deserialize_and_verify_metadata(
+            spl_metadata,
+            SplTokenMetaDerivationData {
+                mint: *mint.info().key,
+            },
+        )?;
+        payload.name = metadata.data.name.clone();
+        payload.symbol = metadata.data.symbol;
+    }
+
+    Ok(payload)
+}
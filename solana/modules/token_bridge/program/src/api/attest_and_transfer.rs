@@ -0,0 +1,220 @@
+use crate::{
+    accounts::{
+        AuthoritySigner,
+        ConfigAccount,
+        CoreBridge,
+        CustodyAccount,
+        CustodyAccountDerivationData,
+        CustodySigner,
+        EmitterAccount,
+        SplTokenMeta,
+        WrappedMetaDerivationData,
+        WrappedTokenMeta,
+    },
+    api::{
+        asset_meta,
+        verify_and_execute_native_transfers,
+    },
+    messages::PayloadTransfer,
+    token_interface::token_program,
+    types::*,
+    TokenBridgeError::InvalidChain,
+};
+use bridge::{
+    api::PostMessageData,
+    types::ConsistencyLevel,
+    vaa::SerializePayload,
+    CHAIN_ID_SOLANA,
+};
+use primitive_types::U256;
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    sysvar::clock::Clock,
+};
+use solitaire::{
+    invoke,
+    processors::seeded::{
+        invoke_seeded,
+        Seeded,
+    },
+    *,
+};
+
+#[derive(FromAccounts)]
+pub struct AttestAndTransferNative<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    pub from: Mut<Data<'b, InterfaceAccount, { AccountState::Initialized }>>,
+
+    /// Mint to attest and transfer
+    pub mint: Mut<Data<'b, InterfaceMint, { AccountState::Initialized }>>,
+    pub wrapped_meta: WrappedTokenMeta<'b, { AccountState::Uninitialized }>,
+
+    /// SPL Metadata for the associated Mint
+    pub spl_metadata: SplTokenMeta<'b>,
+
+    pub custody: Mut<CustodyAccount<'b, { AccountState::MaybeInitialized }>>,
+
+    // The approval must be set in the same tx, see `TransferNative`.
+    pub authority_signer: AuthoritySigner<'b>,
+
+    pub custody_signer: CustodySigner<'b>,
+
+    /// CPI Context
+    pub bridge: Mut<CoreBridge<'b, { AccountState::Initialized }>>,
+
+    /// Account to store the posted attestation
+    pub attest_message: Signer<Mut<Info<'b>>>,
+
+    /// Account to store the posted transfer
+    pub message: Signer<Mut<Info<'b>>>,
+
+    /// Emitter of the VAAs
+    pub emitter: EmitterAccount<'b>,
+
+    /// Tracker for the emitter sequence
+    pub sequence: Mut<Info<'b>>,
+
+    /// Account to collect tx fee
+    pub fee_collector: Mut<Info<'b>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+impl<'a> From<&AttestAndTransferNative<'a>> for CustodyAccountDerivationData {
+    fn from(accs: &AttestAndTransferNative<'a>) -> Self {
+        CustodyAccountDerivationData {
+            mint: *accs.mint.info().key,
+        }
+    }
+}
+
+impl<'a> From<&AttestAndTransferNative<'a>> for WrappedMetaDerivationData {
+    fn from(accs: &AttestAndTransferNative<'a>) -> Self {
+        WrappedMetaDerivationData {
+            mint_key: *accs.mint.info().key,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct AttestAndTransferNativeData {
+    /// Nonce of both messages
+    pub nonce: u32,
+    pub amount: u64,
+    pub fee: u64,
+    pub target_address: Address,
+    pub target_chain: ChainID,
+}
+
+/// Attest a native mint and transfer it in one go, for the first transfer of a mint that needs
+/// its wrapped asset created on the target chain. Posts the attestation of [`attest_token`] and
+/// then the transfer of [`transfer_native`], paying the message fee for each. The transfer can
+/// only be redeemed once the attestation created the wrapped asset.
+///
+/// [`attest_token`]: crate::api::attest_token
+/// [`transfer_native`]: crate::api::transfer_native
+pub fn attest_and_transfer_native(
+    ctx: &ExecutionContext,
+    accs: &mut AttestAndTransferNative,
+    data: AttestAndTransferNativeData,
+) -> Result<()> {
+    // Prevent transferring to the same chain.
+    if data.target_chain == CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+
+    token_program(accs.mint.info())?;
+
+    // Enforce wrapped meta to be uninitialized.
+    let derivation_data: WrappedMetaDerivationData = (&*accs).into();
+    accs.wrapped_meta
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    // Attest first so that the attestation takes the lower sequence.
+    let transfer_ix = solana_program::system_instruction::transfer(
+        accs.payer.key,
+        accs.fee_collector.key,
+        accs.bridge.config.fee,
+    );
+    invoke(&transfer_ix, ctx.accounts)?;
+
+    let payload = asset_meta(&accs.mint, &accs.spl_metadata)?;
+    post_message(
+        ctx,
+        accs,
+        &accs.attest_message,
+        data.nonce,
+        payload.try_to_vec()?,
+    )?;
+
+    let derivation_data: CustodyAccountDerivationData = (&*accs).into();
+    let (amount, fee) = verify_and_execute_native_transfers(
+        ctx,
+        &derivation_data,
+        &accs.payer,
+        &accs.from,
+        &accs.mint,
+        &accs.custody,
+        &accs.authority_signer,
+        &accs.custody_signer,
+        &accs.bridge,
+        &accs.fee_collector,
+        data.amount,
+        data.fee,
+    )?;
+
+    let payload = PayloadTransfer {
+        amount: U256::from(amount),
+        token_address: accs.mint.info().key.to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: data.target_address,
+        to_chain: data.target_chain,
+        fee: U256::from(fee),
+    };
+    post_message(ctx, accs, &accs.message, data.nonce, payload.try_to_vec()?)?;
+
+    Ok(())
+}
+
+fn post_message(
+    ctx: &ExecutionContext,
+    accs: &AttestAndTransferNative,
+    message: &Info,
+    nonce: u32,
+    payload: Vec<u8>,
+) -> Result<()> {
+    let params = (
+        bridge::instruction::Instruction::PostMessage,
+        PostMessageData {
+            nonce,
+            payload,
+            consistency_level: ConsistencyLevel::Finalized,
+        },
+    );
+
+    let ix = Instruction::new_with_bytes(
+        accs.config.wormhole_bridge,
+        params.try_to_vec()?.as_slice(),
+        vec![
+            AccountMeta::new(*accs.bridge.info().key, false),
+            AccountMeta::new(*message.key, true),
+            AccountMeta::new_readonly(*accs.emitter.key, true),
+            AccountMeta::new(*accs.sequence.key, false),
+            AccountMeta::new(*accs.payer.key, true),
+            AccountMeta::new(*accs.fee_collector.key, false),
+            AccountMeta::new_readonly(*accs.clock.info().key, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+        ],
+    );
+    invoke_seeded(&ix, ctx, &accs.emitter, None)?;
+
+    Ok(())
+}
@@ -0,0 +1,217 @@
+use crate::{
+    accounts::{
+        ConfigAccount,
+        CoreBridge,
+        CustodyAccount,
+        CustodyAccountDerivationData,
+        CustodyAttestation,
+        EmitterAccount,
+        YieldStrategy,
+        YieldStrategyDerivationData,
+    },
+    amount,
+    messages::{
+        CustodyBalance,
+        PayloadCustodyAttestation,
+        MAX_ATTESTED_CUSTODIES,
+    },
+    token_interface::{
+        token_amount,
+        token_program,
+        verify_token_accounts,
+    },
+    types::*,
+    TokenBridgeError::InvalidCustodyAttestation,
+};
+use bridge::{
+    api::PostMessageData,
+    types::ConsistencyLevel,
+    vaa::SerializePayload,
+};
+use primitive_types::U256;
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    keccak,
+    sysvar::clock::Clock,
+};
+use solitaire::{
+    invoke,
+    processors::seeded::{
+        invoke_seeded,
+        Seeded,
+    },
+    CreationLamports::Exempt,
+    *,
+};
+
+#[derive(FromAccounts)]
+pub struct AttestCustody<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    /// Latest attestation, replaced
+    pub attestation: Mut<CustodyAttestation<'b, { AccountState::MaybeInitialized }>>,
+
+    /// CPI Context
+    pub bridge: Mut<CoreBridge<'b, { AccountState::Initialized }>>,
+
+    /// Account to store the posted message
+    pub message: Signer<Mut<Info<'b>>>,
+
+    /// Emitter of the VAA
+    pub emitter: EmitterAccount<'b>,
+
+    /// Tracker for the emitter sequence
+    pub sequence: Mut<Info<'b>>,
+
+    /// Account to collect tx fee
+    pub fee_collector: Mut<Info<'b>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct AttestCustodyData {
+    pub nonce: u32,
+
+    /// Number of mints at the end of the instruction accounts, each followed by its custody account
+    /// and yield strategy account
+    pub custodies: u8,
+}
+
+/// Post the balances of native tokens in custody as a [`PayloadCustodyAttestation`]. Anyone can
+/// attest up to [`MAX_ATTESTED_CUSTODIES`] custody accounts at a time, typically a keeper posting
+/// the largest ones periodically, passed in ascending order of their mint. Attestations are chained
+/// through the hash of the previous payload, which the attestation account holds for the next one.
+/// Custody lent to the yield strategy of a mint still backs its wrapped supply and is attested
+/// along with the custody balance.
+pub fn attest_custody(
+    ctx: &ExecutionContext,
+    accs: &mut AttestCustody,
+    data: AttestCustodyData,
+) -> Result<()> {
+    let count = data.custodies as usize;
+    if count == 0 || count > MAX_ATTESTED_CUSTODIES {
+        return Err(InvalidCustodyAttestation.into());
+    }
+    let accounts = ctx
+        .accounts
+        .len()
+        .checked_sub(3 * count)
+        .map(|start| &ctx.accounts[start..])
+        .ok_or(InvalidCustodyAttestation)?;
+    let mut custodies = Vec::with_capacity(count);
+    for triple in accounts.chunks(3) {
+        custodies.push(custody_balance(ctx, &triple[0], &triple[1], &triple[2])?);
+    }
+
+    // Ascending mints also rule out attesting a custody account twice.
+    if custodies
+        .windows(2)
+        .any(|pair| pair[0].token_address >= pair[1].token_address)
+    {
+        return Err(InvalidCustodyAttestation.into());
+    }
+
+    // Pay fee
+    let transfer_ix = solana_program::system_instruction::transfer(
+        accs.payer.key,
+        accs.fee_collector.key,
+        accs.bridge.config.fee,
+    );
+    invoke(&transfer_ix, ctx.accounts)?;
+
+    let initialized = accs.attestation.is_initialized();
+    if initialized {
+        accs.attestation.index += 1;
+    }
+    let payload = PayloadCustodyAttestation {
+        index: accs.attestation.index,
+        slot: accs.clock.slot,
+        previous_hash: accs.attestation.hash,
+        custodies,
+    }
+    .try_to_vec()?;
+
+    accs.attestation.slot = accs.clock.slot;
+    accs.attestation.hash = keccak::hash(&payload).to_bytes();
+    if !initialized {
+        accs.attestation.create(ctx, accs.payer.key, Exempt)?;
+    }
+
+    let params = (
+        bridge::instruction::Instruction::PostMessage,
+        PostMessageData {
+            nonce: data.nonce,
+            payload,
+            consistency_level: ConsistencyLevel::Finalized,
+        },
+    );
+
+    let ix = Instruction::new_with_bytes(
+        accs.config.wormhole_bridge,
+        params.try_to_vec()?.as_slice(),
+        vec![
+            AccountMeta::new(*accs.bridge.info().key, false),
+            AccountMeta::new(*accs.message.key, true),
+            AccountMeta::new_readonly(*accs.emitter.key, true),
+            AccountMeta::new(*accs.sequence.key, false),
+            AccountMeta::new(*accs.payer.key, true),
+            AccountMeta::new(*accs.fee_collector.key, false),
+            AccountMeta::new_readonly(*accs.clock.info().key, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+        ],
+    );
+    invoke_seeded(&ix, ctx, &accs.emitter, None)?;
+
+    Ok(())
+}
+
+/// Balance of the custody account of the native `mint` plus what its yield strategy owes. The yield
+/// strategy account must always be passed, as leaving it out would otherwise under-report the
+/// custody, an empty account means nothing is lent.
+fn custody_balance(
+    ctx: &ExecutionContext,
+    mint: &AccountInfo,
+    custody: &AccountInfo,
+    yield_strategy: &AccountInfo,
+) -> Result<CustodyBalance> {
+    verify_token_accounts(&token_program(mint)?, &[custody])?;
+    let key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint: *mint.key },
+        ctx.program_id,
+    );
+    if *custody.key != key {
+        return Err(SolitaireError::InvalidDerive(*custody.key, key));
+    }
+
+    let key = YieldStrategy::<'_, { AccountState::Initialized }>::key(
+        &YieldStrategyDerivationData { mint: *mint.key },
+        ctx.program_id,
+    );
+    if *yield_strategy.key != key {
+        return Err(SolitaireError::InvalidDerive(*yield_strategy.key, key));
+    }
+    let lent = if yield_strategy.data_is_empty() {
+        0
+    } else {
+        if yield_strategy.owner != ctx.program_id {
+            return Err(SolitaireError::InvalidOwner(*yield_strategy.owner));
+        }
+        YieldStrategyData::try_from_slice(&yield_strategy.data.borrow())?.lent
+    };
+
+    let decimals = InterfaceMint::try_from_slice(&mint.data.borrow())?.decimals;
+    let balance = token_amount(custody)?.saturating_add(lent);
+    Ok(CustodyBalance {
+        token_address: mint.key.to_bytes(),
+        decimals,
+        amount: U256::from(amount::normalize(balance, decimals)),
+    })
+}
@@ -12,7 +12,15 @@ use crate::{
         WrappedMint,
         WrappedTokenMeta,
     },
+    amount,
     messages::PayloadTransfer,
+    token_interface::{
+        associated_token_address,
+        create_associated_token_account,
+        token_program,
+        transfer_checked,
+        verify_token_accounts,
+    },
     types::*,
     TokenBridgeError::*,
     INVALID_VAAS,
@@ -25,14 +33,80 @@ use bridge::{
     PayloadMessage,
     CHAIN_ID_SOLANA,
 };
-use solana_program::account_info::AccountInfo;
+use solana_program::{
+    account_info::AccountInfo,
+    pubkey::Pubkey,
+};
 use solitaire::{
-    processors::seeded::{
-        invoke_seeded,
-        Seeded,
+    invoke,
+    processors::{
+        compute::measure,
+        seeded::{
+            invoke_seeded,
+            Seeded,
+        },
     },
     *,
 };
+use spl_token::state::Account;
+use std::{
+    io::Write,
+    ops::Deref,
+};
+
+/// Create the recipient token account `to` of a transfer as the associated token account of
+/// `owner`, so that transfers to fresh wallets redeem in a single transaction. The relayer pays its
+/// rent, charged against the relayer fee of the transfer it collects.
+pub(crate) fn create_recipient_account(
+    ctx: &ExecutionContext,
+    payer: &Pubkey,
+    to: &AccountInfo,
+    owner: Option<Pubkey>,
+    mint: &AccountInfo,
+) -> Result<()> {
+    let owner = owner.ok_or(InvalidRecipient)?;
+    if *to.key != associated_token_address(&owner, mint)? {
+        return Err(InvalidAssociatedAccount.into());
+    }
+    let ix = create_associated_token_account(payer, &owner, mint)?;
+    invoke(&ix, ctx.accounts)?;
+    Ok(())
+}
+
+/// Native mints with a freeze authority can have their custody and recipient accounts frozen, which
+/// the bridge has no authority to undo. Fail with a precise error rather than the token program's.
+/// Wrapped mints are created without a freeze authority, so their accounts are never frozen.
+pub(crate) fn verify_not_frozen(
+    custody: &InterfaceAccount,
+    accounts: &[&InterfaceAccount],
+) -> Result<()> {
+    if custody.is_frozen() {
+        return Err(FrozenCustodyAccount.into());
+    }
+    if accounts.iter().any(|account| account.is_frozen()) {
+        return Err(FrozenTokenAccount.into());
+    }
+    Ok(())
+}
+
+/// Verify that the recipient and fee token accounts hold `mint`. A recipient account that doesn't
+/// exist yet is created for the mint, the fee account then can only be the same account.
+pub(crate) fn verify_recipient_mints<T>(
+    mint: &Pubkey,
+    to: &Data<T, { AccountState::MaybeInitialized }>,
+    to_fees: &Data<T, { AccountState::MaybeInitialized }>,
+) -> Result<()>
+where
+    T: Owned + Default + Deref<Target = Account>,
+{
+    if to.is_initialized() && to.mint != *mint {
+        return Err(InvalidMint.into());
+    }
+    if to_fees.info().key != to.info().key && (!to_fees.is_initialized() || to_fees.mint != *mint) {
+        return Err(InvalidMint.into());
+    }
+    Ok(())
+}
 
 #[derive(FromAccounts)]
 pub struct CompleteNative<'b> {
@@ -43,10 +117,11 @@ pub struct CompleteNative<'b> {
     pub claim: Mut<Claim<'b>>,
     pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
 
-    pub to: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
-    pub to_fees: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+    /// Created if it doesn't exist yet, see [`create_recipient_account`]
+    pub to: Mut<Data<'b, InterfaceAccount, { AccountState::MaybeInitialized }>>,
+    pub to_fees: Mut<Data<'b, InterfaceAccount, { AccountState::MaybeInitialized }>>,
     pub custody: Mut<CustodyAccount<'b, { AccountState::Initialized }>>,
-    pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
+    pub mint: Data<'b, InterfaceMint, { AccountState::Initialized }>,
 
     pub custody_signer: CustodySigner<'b>,
 }
@@ -68,13 +143,39 @@ impl<'a> From<&CompleteNative<'a>> for CustodyAccountDerivationData {
     }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
-pub struct CompleteNativeData {}
+#[derive(Default)]
+pub struct CompleteNativeData {
+    /// Owner of the recipient token account, to create it as their associated token account if it
+    /// doesn't exist yet, see [`create_recipient_account`]. Omitted by older clients.
+    pub recipient_owner: Option<Pubkey>,
+}
+
+impl BorshSerialize for CompleteNativeData {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        // Keep the original layout unless the owner is passed.
+        if self.recipient_owner.is_some() {
+            self.recipient_owner.serialize(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for CompleteNativeData {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        Ok(CompleteNativeData {
+            recipient_owner: if buf.is_empty() {
+                None
+            } else {
+                Option::<Pubkey>::deserialize(buf)?
+            },
+        })
+    }
+}
 
 pub fn complete_native(
     ctx: &ExecutionContext,
     accs: &mut CompleteNative,
-    _data: CompleteNativeData,
+    data: CompleteNativeData,
 ) -> Result<()> {
     // Verify the chain registration
     let derivation_data: EndpointDerivationData = (&*accs).into();
@@ -87,18 +188,23 @@ pub fn complete_native(
         .verify_derivation(ctx.program_id, &derivation_data)?;
 
     // Verify mints
-    if *accs.mint.info().key != accs.to.mint {
-        return Err(InvalidMint.into());
-    }
-    if *accs.mint.info().key != accs.to_fees.mint {
-        return Err(InvalidMint.into());
-    }
+    verify_recipient_mints(accs.mint.info().key, &accs.to, &accs.to_fees)?;
     if *accs.mint.info().key != accs.custody.mint {
         return Err(InvalidMint.into());
     }
     if *accs.custody_signer.key != accs.custody.owner {
         return Err(WrongAccountOwner.into());
     }
+    // The recipient account is created below if it doesn't exist yet.
+    let mut token_accounts = vec![accs.custody.info()];
+    if accs.to.is_initialized() {
+        token_accounts.push(accs.to.info());
+    }
+    if accs.to_fees.is_initialized() {
+        token_accounts.push(accs.to_fees.info());
+    }
+    verify_token_accounts(&token_program(accs.mint.info())?, &token_accounts)?;
+    verify_not_frozen(&accs.custody, &[&accs.to, &accs.to_fees])?;
 
     // Verify VAA
     if accs.vaa.token_address != accs.mint.info().key.to_bytes() {
@@ -117,41 +223,57 @@ pub fn complete_native(
         return Err(InvalidVAA.into());
     }
 
-    // Prevent vaa double signing
-    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+    #[cfg(feature = "relayer-allowlist")]
+    crate::accounts::verify_relayer(ctx, accs.to.info().key, accs.payer.key)?;
 
-    let mut amount = accs.vaa.amount.as_u64();
-    let mut fee = accs.vaa.fee.as_u64();
+    #[cfg(feature = "inbound-pause")]
+    crate::accounts::verify_inbound_unpaused(ctx, &accs.config)?;
 
-    // Wormhole always caps transfers at 8 decimals; un-truncate if the local token has more
-    if accs.mint.decimals > 8 {
-        amount *= 10u64.pow((accs.mint.decimals - 8) as u32);
-        fee *= 10u64.pow((accs.mint.decimals - 8) as u32);
+    // Prevent vaa double signing
+    measure("claim", || {
+        claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)
+    })?;
+
+    if !accs.to.is_initialized() {
+        create_recipient_account(
+            ctx,
+            accs.payer.key,
+            accs.to.info(),
+            data.recipient_owner,
+            accs.mint.info(),
+        )?;
     }
 
-    let token_amount = amount
-        .checked_sub(fee)
-        .ok_or(SolitaireError::InsufficientFunds)?;
+    // Wormhole always caps transfers at 8 decimals; un-truncate if the local token has more
+    let (token_amount, fee) = amount::redeemed(
+        accs.vaa.amount.as_u64(),
+        accs.vaa.fee.as_u64(),
+        accs.mint.decimals,
+    )
+    .ok_or(SolitaireError::InsufficientFunds)?;
 
-    // Transfer tokens
-    let transfer_ix = spl_token::instruction::transfer(
-        &spl_token::id(),
+    #[cfg(feature = "transfer-cap")]
+    crate::accounts::verify_transfer_cap(ctx, accs.mint.info().key, token_amount + fee)?;
+
+    // Transfer tokens, Token-2022 transfer fees are withheld from the recipients
+    let transfer_ix = transfer_checked(
+        accs.mint.info(),
         accs.custody.info().key,
         accs.to.info().key,
         accs.custody_signer.key,
-        &[],
         token_amount,
+        accs.mint.decimals,
     )?;
     invoke_seeded(&transfer_ix, ctx, &accs.custody_signer, None)?;
 
     // Transfer fees
-    let transfer_ix = spl_token::instruction::transfer(
-        &spl_token::id(),
+    let transfer_ix = transfer_checked(
+        accs.mint.info(),
         accs.custody.info().key,
         accs.to_fees.info().key,
         accs.custody_signer.key,
-        &[],
         fee,
+        accs.mint.decimals,
     )?;
     invoke_seeded(&transfer_ix, ctx, &accs.custody_signer, None)?;
 
@@ -169,8 +291,9 @@ pub struct CompleteWrapped<'b> {
 
     pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
 
-    pub to: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
-    pub to_fees: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+    /// Created if it doesn't exist yet, see [`create_recipient_account`]
+    pub to: Mut<Data<'b, SplAccount, { AccountState::MaybeInitialized }>>,
+    pub to_fees: Mut<Data<'b, SplAccount, { AccountState::MaybeInitialized }>>,
     pub mint: Mut<WrappedMint<'b, { AccountState::Initialized }>>,
     pub wrapped_meta: WrappedTokenMeta<'b, { AccountState::Initialized }>,
 
@@ -195,13 +318,37 @@ impl<'a> From<&CompleteWrapped<'a>> for WrappedDerivationData {
     }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
-pub struct CompleteWrappedData {}
+#[derive(Default)]
+pub struct CompleteWrappedData {
+    /// See [`CompleteNativeData::recipient_owner`].
+    pub recipient_owner: Option<Pubkey>,
+}
+
+impl BorshSerialize for CompleteWrappedData {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        if self.recipient_owner.is_some() {
+            self.recipient_owner.serialize(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for CompleteWrappedData {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        Ok(CompleteWrappedData {
+            recipient_owner: if buf.is_empty() {
+                None
+            } else {
+                Option::<Pubkey>::deserialize(buf)?
+            },
+        })
+    }
+}
 
 pub fn complete_wrapped(
     ctx: &ExecutionContext,
     accs: &mut CompleteWrapped,
-    _data: CompleteWrappedData,
+    data: CompleteWrappedData,
 ) -> Result<()> {
     // Verify the chain registration
     let derivation_data: EndpointDerivationData = (&*accs).into();
@@ -222,12 +369,7 @@ pub fn complete_wrapped(
     }
 
     // Verify mints
-    if *accs.mint.info().key != accs.to.mint {
-        return Err(InvalidMint.into());
-    }
-    if *accs.mint.info().key != accs.to_fees.mint {
-        return Err(InvalidMint.into());
-    }
+    verify_recipient_mints(accs.mint.info().key, &accs.to, &accs.to_fees)?;
 
     // Verify VAA
     if accs.vaa.to_chain != CHAIN_ID_SOLANA {
@@ -240,14 +382,36 @@ pub fn complete_wrapped(
         return Err(InvalidVAA.into());
     }
 
-    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+    #[cfg(feature = "relayer-allowlist")]
+    crate::accounts::verify_relayer(ctx, accs.to.info().key, accs.payer.key)?;
 
-    let token_amount: u64 = accs
-        .vaa
-        .amount
-        .as_u64()
-        .checked_sub(accs.vaa.fee.as_u64())
-        .ok_or(SolitaireError::InsufficientFunds)?;
+    #[cfg(feature = "inbound-pause")]
+    crate::accounts::verify_inbound_unpaused(ctx, &accs.config)?;
+
+    measure("claim", || {
+        claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)
+    })?;
+
+    if !accs.to.is_initialized() {
+        create_recipient_account(
+            ctx,
+            accs.payer.key,
+            accs.to.info(),
+            data.recipient_owner,
+            accs.mint.info(),
+        )?;
+    }
+
+    // Wrapped mints have at most 8 decimals, amounts are minted as they were sent.
+    let (token_amount, fee) = amount::redeemed(
+        accs.vaa.amount.as_u64(),
+        accs.vaa.fee.as_u64(),
+        accs.mint.decimals,
+    )
+    .ok_or(SolitaireError::InsufficientFunds)?;
+
+    #[cfg(feature = "transfer-cap")]
+    crate::accounts::verify_transfer_cap(ctx, accs.mint.info().key, token_amount + fee)?;
 
     // Mint tokens
     let mint_ix = spl_token::instruction::mint_to(
@@ -267,7 +431,7 @@ pub fn complete_wrapped(
         accs.to_fees.info().key,
         accs.mint_authority.key,
         &[],
-        accs.vaa.fee.as_u64(),
+        fee,
     )?;
     invoke_seeded(&mint_ix, ctx, &accs.mint_authority, None)?;
 
@@ -12,7 +12,16 @@ use crate::{
         WrappedMint,
         WrappedTokenMeta,
     },
-    messages::PayloadTransferWithPayload,
+    amount,
+    messages::{
+        PayloadTransferWithPayload,
+        SPLIT_TOTAL_BPS,
+    },
+    token_interface::{
+        token_program,
+        transfer_checked,
+        verify_token_accounts,
+    },
     types::*,
     TokenBridgeError::*,
 };
@@ -24,7 +33,14 @@ use bridge::{
     PayloadMessage,
     CHAIN_ID_SOLANA,
 };
-use solana_program::account_info::AccountInfo;
+use solana_program::{
+    account_info::AccountInfo,
+    program_error::ProgramError::NotEnoughAccountKeys,
+    sysvar::{
+        clock::Clock,
+        Sysvar,
+    },
+};
 use solitaire::{
     processors::seeded::{
         invoke_seeded,
@@ -32,8 +48,12 @@ use solitaire::{
     },
     *,
 };
+use wormhole_solana_consts::seeds;
 
 use solana_program::pubkey::Pubkey;
+use spl_token::state::Account;
+
+use super::verify_not_frozen;
 
 ////////////////////////////////////////////////////////////////////////////////
 // Recipient
@@ -58,7 +78,7 @@ impl<'a, 'b: 'a> Peel<'a, 'b> for RedeemerAccount<'b> {
 // [`Seeded`] directly.
 impl<'b> Seeded<()> for RedeemerAccount<'b> {
     fn seeds(_accs: ()) -> Vec<Vec<u8>> {
-        vec![String::from("redeemer").as_bytes().to_vec()]
+        vec![seeds::REDEEMER.as_bytes().to_vec()]
     }
 }
 
@@ -89,8 +109,70 @@ impl<'b> RedeemerAccount<'b> {
             Ok(())
         } else {
             self.verify_derivation(recipient, ())
+                .map_err(|_| InvalidRedeemer.into())
+        }
+    }
+
+    /// Verify that the transfer to `recipient` may be redeemed by this account into `to`.
+    ///
+    /// The redeemer is checked against the recipient before the token account, so a third party
+    /// can never complete the transfer on the recipient's behalf, even into the recipient's own
+    /// token account. Integrators can rely on every redemption going through their program.
+    fn verify_redemption(&self, recipient: &Pubkey, to: &Account) -> Result<()> {
+        self.verify_recipient_address(recipient)?;
+
+        // Token account owner must be either the VAA-specified recipient, or the
+        // redeemer account (for regular wallets, these two are equal, for programs
+        // the latter is a PDA)
+        if *recipient != to.owner && *self.info().key != to.owner {
+            return Err(InvalidRecipient.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Token accounts a split transfer of `amount` is redeemed into besides the recipient token
+/// account, and the amount each receives, see [`PayloadTransferWithPayload::split`]. The accounts
+/// are looked up among the instruction accounts. Shares are rounded down, the returned remainder
+/// goes to the recipient token account, which receives the whole amount of transfers that aren't
+/// split.
+fn split_redemption<'a, 'b>(
+    ctx: &ExecutionContext<'a, 'b>,
+    transfer: &PayloadTransferWithPayload,
+    mint: &AccountInfo,
+    amount: u64,
+) -> Result<(Vec<(&'a AccountInfo<'b>, u64)>, u64)> {
+    let shares = match transfer.split()? {
+        Some(shares) => shares,
+        None => return Ok((vec![], amount)),
+    };
+
+    let mut recipients = Vec::with_capacity(shares.len());
+    let mut remainder = amount;
+    for share in shares {
+        let info = ctx
+            .accounts
+            .iter()
+            .find(|info| *info.key == share.token_account)
+            .ok_or(NotEnoughAccountKeys)?;
+        if info.owner != mint.owner || !info.is_writable {
+            return Err(InvalidRecipient.into());
+        }
+        let account = InterfaceAccount::try_from_slice(&info.data.borrow())?;
+        if account.mint != *mint.key {
+            return Err(InvalidMint.into());
+        }
+        if account.is_frozen() {
+            return Err(FrozenTokenAccount.into());
         }
+
+        let share_amount = (amount as u128 * share.bps as u128 / SPLIT_TOTAL_BPS as u128) as u64;
+        remainder -= share_amount;
+        recipients.push((info, share_amount));
     }
+
+    Ok((recipients, remainder))
 }
 
 #[derive(FromAccounts)]
@@ -102,13 +184,13 @@ pub struct CompleteNativeWithPayload<'b> {
     pub claim: Mut<Claim<'b>>,
     pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
 
-    pub to: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+    pub to: Mut<Data<'b, InterfaceAccount, { AccountState::Initialized }>>,
 
     /// See [`verify_recipient_address`]
     pub redeemer: RedeemerAccount<'b>,
-    pub to_fees: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+    pub to_fees: Mut<Data<'b, InterfaceAccount, { AccountState::Initialized }>>,
     pub custody: Mut<CustodyAccount<'b, { AccountState::Initialized }>>,
-    pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
+    pub mint: Data<'b, InterfaceMint, { AccountState::Initialized }>,
 
     pub custody_signer: CustodySigner<'b>,
 }
@@ -161,6 +243,11 @@ pub fn complete_native_with_payload(
     if *accs.custody_signer.key != accs.custody.owner {
         return Err(WrongAccountOwner.into());
     }
+    verify_token_accounts(
+        &token_program(accs.mint.info())?,
+        &[accs.to.info(), accs.to_fees.info(), accs.custody.info()],
+    )?;
+    verify_not_frozen(&accs.custody, &[&accs.to, &accs.to_fees])?;
 
     // Verify VAA
     if accs.vaa.token_address != accs.mint.info().key.to_bytes() {
@@ -173,37 +260,41 @@ pub fn complete_native_with_payload(
         return Err(InvalidChain.into());
     }
 
+    // Expired transfers can only be refunded, see [`reclaim_expired_transfer`]
+    if accs.vaa.is_expired(Clock::get()?.unix_timestamp) {
+        return Err(TransferExpired.into());
+    }
+
     let recipient = Pubkey::try_from_slice(&accs.vaa.to)?;
-    accs.redeemer.verify_recipient_address(&recipient)?;
+    accs.redeemer.verify_redemption(&recipient, &accs.to)?;
 
-    // Token account owner must be either the VAA-specified recipient, or the
-    // redeemer account (for regular wallets, these two are equal, for programs
-    // the latter is a PDA)
-    if recipient != accs.to.owner && *accs.redeemer.info().key != accs.to.owner {
-        return Err(InvalidRecipient.into());
-    }
+    #[cfg(feature = "inbound-pause")]
+    crate::accounts::verify_inbound_unpaused(ctx, &accs.config)?;
 
     // Prevent vaa double signing
     claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
 
-    let mut amount = accs.vaa.amount.as_u64();
-
     // Wormhole always caps transfers at 8 decimals; un-truncate if the local token has more
-    if accs.mint.decimals > 8 {
-        amount *= 10u64.pow((accs.mint.decimals - 8) as u32);
+    let amount = amount::denormalize(accs.vaa.amount.as_u64(), accs.mint.decimals);
+
+    #[cfg(feature = "transfer-cap")]
+    crate::accounts::verify_transfer_cap(ctx, accs.mint.info().key, amount)?;
+
+    // Transfer tokens, splitting them among the shares of split transfers
+    let (shares, amount) = split_redemption(ctx, &accs.vaa, accs.mint.info(), amount)?;
+    let to = (accs.to.info(), amount);
+    for (to, amount) in shares.into_iter().chain(std::iter::once(to)) {
+        let transfer_ix = transfer_checked(
+            accs.mint.info(),
+            accs.custody.info().key,
+            to.key,
+            accs.custody_signer.key,
+            amount,
+            accs.mint.decimals,
+        )?;
+        invoke_seeded(&transfer_ix, ctx, &accs.custody_signer, None)?;
     }
 
-    // Transfer tokens
-    let transfer_ix = spl_token::instruction::transfer(
-        &spl_token::id(),
-        accs.custody.info().key,
-        accs.to.info().key,
-        accs.custody_signer.key,
-        &[],
-        amount,
-    )?;
-    invoke_seeded(&transfer_ix, ctx, &accs.custody_signer, None)?;
-
     Ok(())
 }
 
@@ -286,28 +377,37 @@ pub fn complete_wrapped_with_payload(
         return Err(InvalidChain.into());
     }
 
+    // Expired transfers can only be refunded, see [`reclaim_expired_transfer`]
+    if accs.vaa.is_expired(Clock::get()?.unix_timestamp) {
+        return Err(TransferExpired.into());
+    }
+
     let recipient = Pubkey::try_from_slice(&accs.vaa.to)?;
-    accs.redeemer.verify_recipient_address(&recipient)?;
+    accs.redeemer.verify_redemption(&recipient, &accs.to)?;
 
-    // Token account owner must be either the VAA-specified recipient, or the
-    // redeemer account (for regular wallets, these two are equal, for programs
-    // the latter is a PDA)
-    if recipient != accs.to.owner && *accs.redeemer.info().key != accs.to.owner {
-        return Err(InvalidRecipient.into());
-    }
+    #[cfg(feature = "inbound-pause")]
+    crate::accounts::verify_inbound_unpaused(ctx, &accs.config)?;
 
     claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
 
-    // Mint tokens
-    let mint_ix = spl_token::instruction::mint_to(
-        &spl_token::id(),
-        accs.mint.info().key,
-        accs.to.info().key,
-        accs.mint_authority.key,
-        &[],
-        accs.vaa.amount.as_u64(),
-    )?;
-    invoke_seeded(&mint_ix, ctx, &accs.mint_authority, None)?;
+    #[cfg(feature = "transfer-cap")]
+    crate::accounts::verify_transfer_cap(ctx, accs.mint.info().key, accs.vaa.amount.as_u64())?;
+
+    // Mint tokens, splitting them among the shares of split transfers
+    let (shares, amount) =
+        split_redemption(ctx, &accs.vaa, accs.mint.info(), accs.vaa.amount.as_u64())?;
+    let to = (accs.to.info(), amount);
+    for (to, amount) in shares.into_iter().chain(std::iter::once(to)) {
+        let mint_ix = spl_token::instruction::mint_to(
+            &spl_token::id(),
+            accs.mint.info().key,
+            to.key,
+            accs.mint_authority.key,
+            &[],
+            amount,
+        )?;
+        invoke_seeded(&mint_ix, ctx, &accs.mint_authority, None)?;
+    }
 
     Ok(())
 }
@@ -0,0 +1,229 @@
+use crate::{
+    accounts::{
+        ConfigAccount,
+        CustodyAccount,
+        CustodyAccountDerivationData,
+        CustodySigner,
+        Endpoint,
+        EndpointDerivationData,
+    },
+    amount,
+    api::complete_transfer::{
+        create_recipient_account,
+        verify_not_frozen,
+        verify_recipient_mints,
+    },
+    messages::PayloadTransfer,
+    token_interface::{
+        token_program,
+        transfer_checked,
+        verify_token_accounts,
+    },
+    types::*,
+    TokenBridgeError::*,
+    INVALID_VAAS,
+};
+use bridge::{
+    accounts::claim::{
+        self,
+        Claim,
+    },
+    PayloadMessage,
+    CHAIN_ID_SOLANA,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    pubkey::Pubkey,
+};
+use solitaire::{
+    processors::seeded::{
+        invoke_seeded,
+        Seeded,
+    },
+    *,
+};
+
+/// Most transfers [`complete_transfers_batch`] redeems at once.
+pub const MAX_BATCH_TRANSFERS: usize = 8;
+
+#[derive(FromAccounts)]
+pub struct CompleteTransfersBatch<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    /// Created if it doesn't exist yet, see [`create_recipient_account`]
+    pub to: Mut<Data<'b, InterfaceAccount, { AccountState::MaybeInitialized }>>,
+    pub to_fees: Mut<Data<'b, InterfaceAccount, { AccountState::MaybeInitialized }>>,
+    pub custody: Mut<CustodyAccount<'b, { AccountState::Initialized }>>,
+    pub mint: Data<'b, InterfaceMint, { AccountState::Initialized }>,
+
+    pub custody_signer: CustodySigner<'b>,
+}
+
+impl<'a> From<&CompleteTransfersBatch<'a>> for CustodyAccountDerivationData {
+    fn from(accs: &CompleteTransfersBatch<'a>) -> Self {
+        CustodyAccountDerivationData {
+            mint: *accs.mint.info().key,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct CompleteTransfersBatchData {
+    /// Number of transfers at the end of the instruction accounts, each as its posted VAA, claim
+    /// and chain registration
+    pub transfers: u8,
+
+    /// See [`crate::api::CompleteNativeData::recipient_owner`].
+    pub recipient_owner: Option<Pubkey>,
+}
+
+/// Redeem up to [`MAX_BATCH_TRANSFERS`] transfers of a native mint to the same recipient account,
+/// as [`crate::api::complete_native`] redeems each of them, such as an exchange processing a
+/// backlog of deposits. The recipient, custody and mint accounts are loaded and verified once, and
+/// each transfer is paid out with its own token transfer. Relayer fees of the batch are collected
+/// with a single transfer once all of them are redeemed.
+pub fn complete_transfers_batch(
+    ctx: &ExecutionContext,
+    accs: &mut CompleteTransfersBatch,
+    data: CompleteTransfersBatchData,
+) -> Result<()> {
+    let count = data.transfers as usize;
+    if count == 0 || count > MAX_BATCH_TRANSFERS {
+        return Err(InvalidVAA.into());
+    }
+    let transfers = ctx
+        .accounts
+        .len()
+        .checked_sub(3 * count)
+        .map(|start| &ctx.accounts[start..])
+        .ok_or(InvalidVAA)?;
+
+    #[cfg(feature = "inbound-pause")]
+    crate::accounts::verify_inbound_unpaused(ctx, &accs.config)?;
+
+    // Verify that the custody account is derived correctly
+    let derivation_data: CustodyAccountDerivationData = (&*accs).into();
+    accs.custody
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    // Verify mints
+    verify_recipient_mints(accs.mint.info().key, &accs.to, &accs.to_fees)?;
+    if *accs.mint.info().key != accs.custody.mint {
+        return Err(InvalidMint.into());
+    }
+    if *accs.custody_signer.key != accs.custody.owner {
+        return Err(WrongAccountOwner.into());
+    }
+    // The recipient account is created below if it doesn't exist yet.
+    let mut token_accounts = vec![accs.custody.info()];
+    if accs.to.is_initialized() {
+        token_accounts.push(accs.to.info());
+    }
+    if accs.to_fees.is_initialized() {
+        token_accounts.push(accs.to_fees.info());
+    }
+    verify_token_accounts(&token_program(accs.mint.info())?, &token_accounts)?;
+    verify_not_frozen(&accs.custody, &[&accs.to, &accs.to_fees])?;
+
+    #[cfg(feature = "relayer-allowlist")]
+    crate::accounts::verify_relayer(ctx, accs.to.info().key, accs.payer.key)?;
+
+    if !accs.to.is_initialized() {
+        create_recipient_account(
+            ctx,
+            accs.payer.key,
+            accs.to.info(),
+            data.recipient_owner,
+            accs.mint.info(),
+        )?;
+    }
+
+    let mut fees = 0u64;
+    for transfer in transfers.chunks(3) {
+        fees = fees
+            .checked_add(complete_batched(ctx, accs, transfer)?)
+            .ok_or(SolitaireError::InsufficientFunds)?;
+    }
+
+    // Transfer fees
+    if fees > 0 {
+        let transfer_ix = transfer_checked(
+            accs.mint.info(),
+            accs.custody.info().key,
+            accs.to_fees.info().key,
+            accs.custody_signer.key,
+            fees,
+            accs.mint.decimals,
+        )?;
+        invoke_seeded(&transfer_ix, ctx, &accs.custody_signer, None)?;
+    }
+
+    Ok(())
+}
+
+/// Redeem the transfer of one batch entry, its posted VAA, claim and chain registration, returning
+/// the relayer fee it owes.
+fn complete_batched<'b>(
+    ctx: &ExecutionContext<'_, 'b>,
+    accs: &CompleteTransfersBatch,
+    transfer: &[AccountInfo<'b>],
+) -> Result<u64> {
+    let vaa: PayloadMessage<PayloadTransfer> =
+        Peel::peel(&mut Context::new(ctx.program_id, &transfer[0], &()))?;
+    let mut claim: Mut<Claim> = Peel::peel(&mut Context::new(ctx.program_id, &transfer[1], &()))?;
+    let chain_registration: Endpoint<{ AccountState::Initialized }> =
+        Peel::peel(&mut Context::new(ctx.program_id, &transfer[2], &()))?;
+
+    // Verify the chain registration
+    chain_registration.verify_derivation(
+        ctx.program_id,
+        &EndpointDerivationData {
+            emitter_chain: vaa.meta().emitter_chain,
+            emitter_address: vaa.meta().emitter_address,
+        },
+    )?;
+
+    // Verify VAA
+    if vaa.token_address != accs.mint.info().key.to_bytes() {
+        return Err(InvalidMint.into());
+    }
+    if vaa.token_chain != 1 {
+        return Err(InvalidChain.into());
+    }
+    if vaa.to_chain != CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+    if vaa.to != accs.to.info().key.to_bytes() {
+        return Err(InvalidRecipient.into());
+    }
+    if INVALID_VAAS.contains(&&*vaa.info().key.to_string()) {
+        return Err(InvalidVAA.into());
+    }
+
+    // Prevent vaa double signing, persisted right away so that a VAA repeated later in the batch
+    // finds its claim initialized.
+    claim::consume(ctx, accs.payer.key, &mut claim, &vaa)?;
+    Peel::persist(&claim, ctx.program_id)?;
+
+    // Wormhole always caps transfers at 8 decimals; un-truncate if the local token has more
+    let (token_amount, fee) =
+        amount::redeemed(vaa.amount.as_u64(), vaa.fee.as_u64(), accs.mint.decimals)
+            .ok_or(SolitaireError::InsufficientFunds)?;
+
+    #[cfg(feature = "transfer-cap")]
+    crate::accounts::verify_transfer_cap(ctx, accs.mint.info().key, token_amount + fee)?;
+
+    // Transfer tokens, Token-2022 transfer fees are withheld from the recipients
+    let transfer_ix = transfer_checked(
+        accs.mint.info(),
+        accs.custody.info().key,
+        accs.to.info().key,
+        accs.custody_signer.key,
+        token_amount,
+        accs.mint.decimals,
+    )?;
+    invoke_seeded(&transfer_ix, ctx, &accs.custody_signer, None)?;
+
+    Ok(fee)
+}
@@ -27,11 +27,9 @@ use bridge::{
     PayloadMessage,
     CHAIN_ID_SOLANA,
 };
-use solana_program::{
-    account_info::AccountInfo,
-    program::invoke_signed,
-};
+use solana_program::account_info::AccountInfo;
 use solitaire::{
+    invoke_signed,
     processors::seeded::{
         invoke_seeded,
         Seeded,
@@ -202,6 +200,11 @@ pub fn update_accounts(
         },
     )?;
 
+    // Metadata rotated to a decreed authority is no longer managed by the token bridge.
+    if metadata.update_authority != *accs.mint_authority.info().key {
+        return Ok(());
+    }
+
     // Normalize token metadata's name and symbol.
     let new_data_v2 = spl_token_metadata::state::DataV2 {
         name: truncate_utf8(&accs.vaa.name, 32 - 11) + " (Wormhole)",
@@ -1,16 +1,42 @@
 use crate::{
     accounts::{
+        deserialize_and_verify_metadata,
         ConfigAccount,
         Endpoint,
         EndpointDerivationData,
+        MetadataAuthority,
+        MetadataAuthorityDerivationData,
+        MintSigner,
+        RecoveredChain,
+        SplTokenMeta,
+        SplTokenMetaDerivationData,
+        TransferCap,
+        TransferCapDerivationData,
+        WrappedDerivationData,
+        WrappedMetaDerivationData,
+        WrappedMint,
+        WrappedTokenMeta,
+        YieldStrategy,
+        YieldStrategyDerivationData,
     },
     messages::{
+        GovernancePayloadOverrideTransferCap,
+        GovernancePayloadRecoverChainId,
+        GovernancePayloadSetMetadataAuthority,
+        GovernancePayloadSetTransferCap,
+        GovernancePayloadSetYieldStrategy,
         GovernancePayloadUpgrade,
         PayloadGovernanceRegisterChain,
     },
+    types::YieldStrategyData,
     TokenBridgeError::{
+        InvalidChain,
         InvalidGovernanceKey,
         InvalidVAA,
+        InvalidYieldStrategy,
+        MetadataAuthorityRotated,
+        NotAFork,
+        YieldStrategyInUse,
     },
     INVALID_VAAS,
 };
@@ -25,7 +51,7 @@ use bridge::{
 };
 use solana_program::{
     account_info::AccountInfo,
-    program::invoke_signed,
+    program_error::ProgramError::UninitializedAccount,
     pubkey::Pubkey,
     sysvar::{
         clock::Clock,
@@ -33,20 +59,30 @@ use solana_program::{
     },
 };
 use solitaire::{
-    processors::seeded::Seeded,
+    invoke_signed,
+    processors::seeded::{
+        invoke_seeded,
+        Seeded,
+    },
     CreationLamports::Exempt,
     *,
 };
+use std::{
+    convert::TryFrom,
+    str::FromStr,
+};
+use wormhole_solana_consts::{
+    program_ids,
+    seeds,
+};
 
 // Confirm that a ClaimableVAA came from the correct chain, signed by the right emitter.
 fn verify_governance<T>(vaa: &PayloadMessage<T>) -> Result<()>
 where
     T: DeserializePayload,
 {
-    let expected_emitter = std::env!("EMITTER_ADDRESS");
-    let current_emitter = format!("{}", Pubkey::new_from_array(vaa.meta().emitter_address));
     // Fail if the emitter is not the known governance key, or the emitting chain is not Solana.
-    if expected_emitter != current_emitter || vaa.meta().emitter_chain != CHAIN_ID_SOLANA {
+    if !vaa.try_emitter_eq(CHAIN_ID_SOLANA, std::env!("EMITTER_ADDRESS"))? {
         Err(InvalidGovernanceKey.into())
     } else {
         Ok(())
@@ -63,7 +99,7 @@ pub struct UpgradeContract<'b> {
     pub claim: Mut<Claim<'b>>,
 
     /// PDA authority for the loader
-    pub upgrade_authority: Derive<Info<'b>, "upgrade">,
+    pub upgrade_authority: Derive<Info<'b>, { seeds::UPGRADE }>,
 
     /// Spill address for the upgrade excess lamports
     pub spill: Mut<Info<'b>>,
@@ -152,6 +188,11 @@ pub fn register_chain(
         return Err(InvalidVAA.into());
     }
 
+    // Solana emitters are never foreign bridges, their messages would pass as inbound transfers.
+    if accs.vaa.chain == CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+
     // Claim VAA
     verify_governance(&accs.vaa)?;
     claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
@@ -165,3 +206,312 @@ pub fn register_chain(
 
     Ok(())
 }
+
+#[derive(FromAccounts)]
+pub struct RecoverChainId<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+
+    pub vaa: PayloadMessage<'b, GovernancePayloadRecoverChainId>,
+    pub claim: Mut<Claim<'b>>,
+
+    pub recovered_chain: Mut<RecoveredChain<'b, { AccountState::MaybeInitialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct RecoverChainIdData {}
+
+/// Assign a new chain ID to a fork of the token bridge, a deployment under another program ID than
+/// the one at the mainnet address. The decree names the fork by its program ID where other chains
+/// name it by EVM chain ID, so that it can't be executed on another fork. A later decree replaces
+/// the recovered chain ID.
+pub fn recover_chain_id(
+    ctx: &ExecutionContext,
+    accs: &mut RecoverChainId,
+    _data: RecoverChainIdData,
+) -> Result<()> {
+    if INVALID_VAAS.contains(&&*accs.vaa.info().key.to_string()) {
+        return Err(InvalidVAA.into());
+    }
+
+    let mainnet = Pubkey::from_str(program_ids::mainnet::TOKEN_BRIDGE).unwrap();
+    if *ctx.program_id == mainnet {
+        return Err(NotAFork.into());
+    }
+
+    // Chain ID zero addresses every chain in governance headers, no deployment can be known by it.
+    if accs.vaa.program != *ctx.program_id || accs.vaa.new_chain_id == 0 {
+        return Err(InvalidChain.into());
+    }
+
+    verify_governance(&accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    if !accs.recovered_chain.is_initialized() {
+        accs.recovered_chain.create(ctx, accs.payer.key, Exempt)?;
+    }
+    accs.recovered_chain.chain = accs.vaa.new_chain_id;
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct SetMetadataAuthority<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetMetadataAuthority>,
+    pub claim: Mut<Claim<'b>>,
+
+    /// Decree for the mint of the VAA, or for all wrapped mints
+    pub decree: Mut<MetadataAuthority<'b, { AccountState::MaybeInitialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetMetadataAuthorityData {}
+
+/// Decree a new metadata update authority for one or all wrapped mints. Mints are rotated to it one
+/// at a time by [`rotate_metadata_authority`], a later decree for the same mints replaces this one
+/// for those not rotated yet.
+pub fn set_metadata_authority(
+    ctx: &ExecutionContext,
+    accs: &mut SetMetadataAuthority,
+    _data: SetMetadataAuthorityData,
+) -> Result<()> {
+    let derivation_data = MetadataAuthorityDerivationData {
+        mint: accs.vaa.mint,
+    };
+    accs.decree
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    if INVALID_VAAS.contains(&&*accs.vaa.info().key.to_string()) {
+        return Err(InvalidVAA.into());
+    }
+
+    verify_governance(&accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    if !accs.decree.is_initialized() {
+        accs.decree
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+    }
+    accs.decree.new_authority = accs.vaa.new_authority;
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct RotateMetadataAuthority<'b> {
+    /// Decree for all wrapped mints
+    pub decree: MetadataAuthority<'b, { AccountState::MaybeInitialized }>,
+
+    /// Decree for this mint, takes precedence over the one for all mints
+    pub mint_decree: MetadataAuthority<'b, { AccountState::MaybeInitialized }>,
+
+    pub mint: WrappedMint<'b, { AccountState::Initialized }>,
+    pub meta: WrappedTokenMeta<'b, { AccountState::Initialized }>,
+
+    /// SPL Metadata for the associated Mint
+    pub spl_metadata: Mut<SplTokenMeta<'b>>,
+
+    pub mint_authority: MintSigner<'b>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct RotateMetadataAuthorityData {}
+
+/// Hand the metadata update authority of a wrapped mint from the mint signer to the decreed one.
+/// Permissionless, once rotated the token bridge no longer updates the metadata on attestations.
+pub fn rotate_metadata_authority(
+    ctx: &ExecutionContext,
+    accs: &mut RotateMetadataAuthority,
+    _data: RotateMetadataAuthorityData,
+) -> Result<()> {
+    let mint_key = *accs.mint.info().key;
+    accs.meta
+        .verify_derivation(ctx.program_id, &WrappedMetaDerivationData { mint_key })?;
+    accs.mint.verify_derivation(
+        ctx.program_id,
+        &WrappedDerivationData {
+            token_chain: accs.meta.chain,
+            token_address: accs.meta.token_address,
+        },
+    )?;
+
+    accs.decree.verify_derivation(
+        ctx.program_id,
+        &MetadataAuthorityDerivationData { mint: None },
+    )?;
+    accs.mint_decree.verify_derivation(
+        ctx.program_id,
+        &MetadataAuthorityDerivationData {
+            mint: Some(mint_key),
+        },
+    )?;
+    let new_authority = if accs.mint_decree.is_initialized() {
+        accs.mint_decree.new_authority
+    } else if accs.decree.is_initialized() {
+        accs.decree.new_authority
+    } else {
+        return Err(UninitializedAccount.into());
+    };
+
+    let metadata = deserialize_and_verify_metadata(
+        &accs.spl_metadata,
+        SplTokenMetaDerivationData { mint: mint_key },
+    )?;
+    if metadata.update_authority != *accs.mint_authority.key {
+        return Err(MetadataAuthorityRotated.into());
+    }
+
+    let spl_token_metadata_ix = spl_token_metadata::instruction::update_metadata_accounts_v2(
+        spl_token_metadata::id(),
+        *accs.spl_metadata.key,
+        *accs.mint_authority.info().key,
+        Some(new_authority),
+        None,
+        None,
+        None,
+    );
+    invoke_seeded(&spl_token_metadata_ix, ctx, &accs.mint_authority, None)?;
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct SetTransferCap<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetTransferCap>,
+    pub claim: Mut<Claim<'b>>,
+
+    pub transfer_cap: Mut<TransferCap<'b, { AccountState::MaybeInitialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetTransferCapData {}
+
+/// Cap the amount of a mint a single transfer may move in or out of the token bridge, limiting
+/// what a logic bug or a compromised emitter can drain in one transaction. Only enforced by
+/// programs built with the `transfer-cap` feature.
+pub fn set_transfer_cap(
+    ctx: &ExecutionContext,
+    accs: &mut SetTransferCap,
+    _data: SetTransferCapData,
+) -> Result<()> {
+    let derivation_data = TransferCapDerivationData {
+        mint: accs.vaa.mint,
+    };
+    accs.transfer_cap
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    if INVALID_VAAS.contains(&&*accs.vaa.info().key.to_string()) {
+        return Err(InvalidVAA.into());
+    }
+
+    verify_governance(&accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    if !accs.transfer_cap.is_initialized() {
+        accs.transfer_cap
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+    }
+    accs.transfer_cap.max_amount = accs.vaa.max_amount;
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct OverrideTransferCap<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+
+    pub vaa: PayloadMessage<'b, GovernancePayloadOverrideTransferCap>,
+    pub claim: Mut<Claim<'b>>,
+
+    pub transfer_cap: Mut<TransferCap<'b, { AccountState::MaybeInitialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct OverrideTransferCapData {}
+
+/// Replace the transfer cap of a mint for a limited time, keeping the cap set by
+/// [`set_transfer_cap`] to fall back to once the override ends. A later override replaces this
+/// one.
+pub fn override_transfer_cap(
+    ctx: &ExecutionContext,
+    accs: &mut OverrideTransferCap,
+    _data: OverrideTransferCapData,
+) -> Result<()> {
+    let derivation_data = TransferCapDerivationData {
+        mint: accs.vaa.mint,
+    };
+    accs.transfer_cap
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    if INVALID_VAAS.contains(&&*accs.vaa.info().key.to_string()) {
+        return Err(InvalidVAA.into());
+    }
+
+    verify_governance(&accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    // Mints without a cap of their own are uncapped once the override ends.
+    if !accs.transfer_cap.is_initialized() {
+        accs.transfer_cap.max_amount = u64::MAX;
+        accs.transfer_cap
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+    }
+    accs.transfer_cap.override_max_amount = accs.vaa.max_amount;
+    accs.transfer_cap.override_until = i64::try_from(accs.vaa.until).unwrap_or(i64::MAX);
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct SetYieldStrategy<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetYieldStrategy>,
+    pub claim: Mut<Claim<'b>>,
+
+    pub yield_strategy: Mut<YieldStrategy<'b, { AccountState::MaybeInitialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetYieldStrategyData {}
+
+/// Allow a vetted strategy program to borrow idle custody of a native mint with
+/// [`crate::api::lend_custody`], within the caps of the decree. The strategy can only be replaced
+/// once it repaid everything it owes, a decree with zero caps winds it down instead.
+pub fn set_yield_strategy(
+    ctx: &ExecutionContext,
+    accs: &mut SetYieldStrategy,
+    _data: SetYieldStrategyData,
+) -> Result<()> {
+    let derivation_data = YieldStrategyDerivationData {
+        mint: accs.vaa.mint,
+    };
+    accs.yield_strategy
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    if INVALID_VAAS.contains(&&*accs.vaa.info().key.to_string()) {
+        return Err(InvalidVAA.into());
+    }
+    if accs.vaa.max_share_bps > YieldStrategyData::MAX_SHARE_BPS {
+        return Err(InvalidYieldStrategy.into());
+    }
+    if accs.yield_strategy.lent > 0 && accs.yield_strategy.strategy != accs.vaa.strategy {
+        return Err(YieldStrategyInUse.into());
+    }
+
+    verify_governance(&accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    if !accs.yield_strategy.is_initialized() {
+        accs.yield_strategy
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+    }
+    accs.yield_strategy.strategy = accs.vaa.strategy;
+    accs.yield_strategy.max_amount = accs.vaa.max_amount;
+    accs.yield_strategy.max_share_bps = accs.vaa.max_share_bps;
+
+    Ok(())
+}
@@ -0,0 +1,199 @@
+use crate::{
+    accounts::{
+        ConfigAccount,
+        Endpoint,
+        EndpointDerivationData,
+        WrappedDerivationData,
+        WrappedMint,
+    },
+    messages::{
+        PayloadTransfer,
+        PayloadTransferWithPayload,
+    },
+    types::*,
+    TokenBridgeError::*,
+};
+use bridge::{
+    accounts::{
+        Claim,
+        ClaimDerivationData,
+        PostedVAA,
+        PostedVAAData,
+        PostedVAADerivationData,
+    },
+    DeserializePayload,
+    CHAIN_ID_SOLANA,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    program::set_return_data,
+    pubkey::Pubkey,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    *,
+};
+
+/// Instruction redeeming a transfer.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, PartialEq)]
+pub enum RedemptionKind {
+    Native,
+    Wrapped,
+    NativeWithPayload,
+    WrappedWithPayload,
+}
+
+/// Mint of the asset a transfer redeemed by `kind` sends, and the token account it goes to if
+/// that is set by the payload. Transfers with payload are redeemed to any token account of the
+/// recipient.
+pub fn redemption_accounts(
+    program_id: &Pubkey,
+    kind: RedemptionKind,
+    payload: &[u8],
+) -> Result<(Pubkey, Option<Pubkey>)> {
+    let (token_chain, token_address, to) = match kind {
+        RedemptionKind::Native | RedemptionKind::Wrapped => {
+            let transfer = <PayloadTransfer as DeserializePayload>::deserialize(&mut &payload[..])?;
+            (
+                transfer.token_chain,
+                transfer.token_address,
+                Some(Pubkey::new(&transfer.to)),
+            )
+        }
+        RedemptionKind::NativeWithPayload | RedemptionKind::WrappedWithPayload => {
+            let transfer =
+                <PayloadTransferWithPayload as DeserializePayload>::deserialize(&mut &payload[..])?;
+            (transfer.token_chain, transfer.token_address, None)
+        }
+    };
+
+    let wrapped = matches!(
+        kind,
+        RedemptionKind::Wrapped | RedemptionKind::WrappedWithPayload
+    );
+    if wrapped == (token_chain == CHAIN_ID_SOLANA) {
+        return Err(InvalidChain.into());
+    }
+
+    let mint = if wrapped {
+        WrappedMint::<'_, { AccountState::Uninitialized }>::key(
+            &WrappedDerivationData {
+                token_chain,
+                token_address,
+            },
+            program_id,
+        )
+    } else {
+        Pubkey::new(&token_address)
+    };
+
+    Ok((mint, to))
+}
+
+#[derive(FromAccounts)]
+pub struct ProbeRedemption<'b> {
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    /// VAA posted to the core bridge, if it was posted yet
+    pub vaa: Info<'b>,
+    pub claim: Info<'b>,
+
+    /// Token account the transfer is redeemed to
+    pub to: Info<'b>,
+    pub mint: Info<'b>,
+
+    /// Registration of the emitter of the VAA, transfers are only redeemed from registered ones
+    pub chain_registration: Info<'b>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ProbeRedemptionData {
+    /// Digest of the VAA body, the posted VAA is derived from it
+    pub digest: [u8; 32],
+    pub kind: RedemptionKind,
+}
+
+/// Accounts of a redemption that exist, returned by [`probe_redemption`].
+#[derive(BorshDeserialize, BorshSerialize, Default, Debug, PartialEq)]
+pub struct RedemptionProbe {
+    pub posted_vaa: bool,
+    pub claim: bool,
+    pub to: bool,
+    pub mint: bool,
+    pub chain_registration: bool,
+}
+
+/// Report which of the accounts redeeming a transfer already exist, so that clients can tell
+/// whether they still need to post the VAA, create the recipient token account or the wrapped
+/// mint, or whether the transfer was redeemed already or can't be redeemed from an unregistered
+/// emitter. Meant to be simulated, the result is set as return data.
+///
+/// The claim, recipient, mint and chain registration are derived from the VAA once it is posted. Before that the
+/// program can't tell them apart from other accounts, they are reported as passed.
+pub fn probe_redemption(
+    ctx: &ExecutionContext,
+    accs: &mut ProbeRedemption,
+    data: ProbeRedemptionData,
+) -> Result<()> {
+    let vaa_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: data.digest.to_vec(),
+        },
+        &accs.config.wormhole_bridge,
+    );
+    if *accs.vaa.key != vaa_key {
+        return Err(InvalidVAA.into());
+    }
+
+    let probe = RedemptionProbe {
+        posted_vaa: exists(&accs.vaa),
+        claim: exists(&accs.claim),
+        to: exists(&accs.to),
+        mint: exists(&accs.mint),
+        chain_registration: exists(&accs.chain_registration),
+    };
+
+    if probe.posted_vaa {
+        let vaa = PostedVAAData::deserialize(&mut &accs.vaa.data.borrow()[..])?;
+        let claim_key = Claim::<'_>::key(
+            &ClaimDerivationData {
+                emitter_address: vaa.emitter_address,
+                emitter_chain: vaa.emitter_chain,
+                sequence: vaa.sequence,
+            },
+            ctx.program_id,
+        );
+        if *accs.claim.key != claim_key {
+            return Err(SolitaireError::InvalidDerive(*accs.claim.key, claim_key));
+        }
+        let endpoint_key = Endpoint::<'_, { AccountState::Initialized }>::key(
+            &EndpointDerivationData {
+                emitter_chain: vaa.emitter_chain,
+                emitter_address: vaa.emitter_address,
+            },
+            ctx.program_id,
+        );
+        if *accs.chain_registration.key != endpoint_key {
+            return Err(SolitaireError::InvalidDerive(
+                *accs.chain_registration.key,
+                endpoint_key,
+            ));
+        }
+
+        let (mint, to) = redemption_accounts(ctx.program_id, data.kind, &vaa.payload)?;
+        if *accs.mint.key != mint {
+            return Err(InvalidMint.into());
+        }
+        if to.map_or(false, |to| *accs.to.key != to) {
+            return Err(InvalidRecipient.into());
+        }
+    }
+
+    set_return_data(&probe.try_to_vec()?);
+
+    Ok(())
+}
+
+fn exists(account: &AccountInfo) -> bool {
+    !account.data_is_empty()
+}
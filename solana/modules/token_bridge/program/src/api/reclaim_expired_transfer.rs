@@ -0,0 +1,158 @@
+use crate::{
+    accounts::{
+        ConfigAccount,
+        CoreBridge,
+        EmitterAccount,
+        Endpoint,
+        EndpointDerivationData,
+    },
+    messages::{
+        PayloadTransfer,
+        PayloadTransferWithPayload,
+    },
+    types::*,
+    TokenBridgeError::*,
+};
+use bridge::{
+    accounts::claim::{
+        self,
+        Claim,
+    },
+    api::PostMessageData,
+    types::ConsistencyLevel,
+    vaa::SerializePayload,
+    PayloadMessage,
+    CHAIN_ID_SOLANA,
+};
+use primitive_types::U256;
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    sysvar::clock::Clock,
+};
+use solitaire::{
+    processors::seeded::{
+        invoke_seeded,
+        Seeded,
+    },
+    *,
+};
+
+#[derive(FromAccounts)]
+pub struct ReclaimExpiredTransfer<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    /// Expired transfer, claimed so that it can no longer be completed
+    pub vaa: PayloadMessage<'b, PayloadTransferWithPayload>,
+    pub claim: Mut<Claim<'b>>,
+    pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
+
+    /// CPI Context
+    pub bridge: Mut<CoreBridge<'b, { AccountState::Initialized }>>,
+
+    /// Account to store the posted refund message
+    pub message: Signer<Mut<Info<'b>>>,
+
+    /// Emitter of the VAA
+    pub emitter: EmitterAccount<'b>,
+
+    /// Tracker for the emitter sequence
+    pub sequence: Mut<Info<'b>>,
+
+    /// Account to collect tx fee
+    pub fee_collector: Mut<Info<'b>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+impl<'a> From<&ReclaimExpiredTransfer<'a>> for EndpointDerivationData {
+    fn from(accs: &ReclaimExpiredTransfer<'a>) -> Self {
+        EndpointDerivationData {
+            emitter_chain: accs.vaa.meta().emitter_chain,
+            emitter_address: accs.vaa.meta().emitter_address,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct ReclaimExpiredTransferData {
+    pub nonce: u32,
+}
+
+/// Refund an expired transfer with payload to its sender.
+///
+/// The transfer is claimed, so it can never be completed, and a regular transfer of the same
+/// asset and amount back to `from_address` on the source chain is emitted instead. The tokens were
+/// never released on Solana, so the refund is backed by the funds locked or burned when the
+/// original transfer was sent. Anyone may reclaim an expired transfer, as the refund only ever
+/// goes to the original sender.
+pub fn reclaim_expired_transfer(
+    ctx: &ExecutionContext,
+    accs: &mut ReclaimExpiredTransfer,
+    data: ReclaimExpiredTransferData,
+) -> Result<()> {
+    // Verify the chain registration
+    let derivation_data: EndpointDerivationData = (&*accs).into();
+    accs.chain_registration
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    // Verify VAA
+    if accs.vaa.to_chain != CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+    if !accs.vaa.is_expired(accs.clock.unix_timestamp) {
+        return Err(TransferNotExpired.into());
+    }
+
+    // Prevent the transfer from being completed or refunded again
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    // Pay fee
+    let transfer_ix = solana_program::system_instruction::transfer(
+        accs.payer.key,
+        accs.fee_collector.key,
+        accs.bridge.config.fee,
+    );
+    invoke(&transfer_ix, ctx.accounts)?;
+
+    // Post refund message
+    let payload = PayloadTransfer {
+        amount: accs.vaa.amount,
+        token_address: accs.vaa.token_address,
+        token_chain: accs.vaa.token_chain,
+        to: accs.vaa.from_address,
+        to_chain: accs.vaa.meta().emitter_chain,
+        fee: U256::zero(),
+    };
+    let params = (
+        bridge::instruction::Instruction::PostMessage,
+        PostMessageData {
+            nonce: data.nonce,
+            payload: payload.try_to_vec()?,
+            consistency_level: ConsistencyLevel::Finalized,
+        },
+    );
+
+    let ix = Instruction::new_with_bytes(
+        accs.config.wormhole_bridge,
+        params.try_to_vec()?.as_slice(),
+        vec![
+            AccountMeta::new(*accs.bridge.info().key, false),
+            AccountMeta::new(*accs.message.key, true),
+            AccountMeta::new_readonly(*accs.emitter.key, true),
+            AccountMeta::new(*accs.sequence.key, false),
+            AccountMeta::new(*accs.payer.key, true),
+            AccountMeta::new(*accs.fee_collector.key, false),
+            AccountMeta::new_readonly(*accs.clock.info().key, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+        ],
+    );
+    invoke_seeded(&ix, ctx, &accs.emitter, None)?;
+
+    Ok(())
+}
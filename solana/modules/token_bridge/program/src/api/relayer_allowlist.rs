@@ -0,0 +1,82 @@
+use crate::{
+    accounts::{
+        RelayerAllowlist,
+        RelayerAllowlistDerivationData,
+    },
+    types::*,
+    TokenBridgeError::*,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    pubkey::Pubkey,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    CreationLamports::Exempt,
+    *,
+};
+
+#[derive(FromAccounts)]
+pub struct SetRelayerAllowlist<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+
+    /// Owner of `token_account`, the only one allowed to restrict redemptions to it
+    pub owner: Signer<AccountInfo<'b>>,
+
+    pub token_account: Data<'b, SplAccount, { AccountState::Initialized }>,
+
+    pub allowlist: Mut<RelayerAllowlist<'b, { AccountState::MaybeInitialized }>>,
+}
+
+impl<'a> From<&SetRelayerAllowlist<'a>> for RelayerAllowlistDerivationData {
+    fn from(accs: &SetRelayerAllowlist<'a>) -> Self {
+        RelayerAllowlistDerivationData {
+            token_account: *accs.token_account.info().key,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetRelayerAllowlistData {
+    /// Relayers allowed to redeem transfers to the token account, anyone if empty
+    pub relayers: Vec<Pubkey>,
+}
+
+/// Restrict who may submit `complete_native` and `complete_wrapped` for transfers to a token
+/// account, for integrators with requirements on the submitters of their redemptions. A later call
+/// replaces the list. Transfers with payload need no allowlist, as their redeemer already signs.
+///
+/// Only enforced by programs built with the `relayer-allowlist` feature.
+pub fn set_relayer_allowlist(
+    ctx: &ExecutionContext,
+    accs: &mut SetRelayerAllowlist,
+    data: SetRelayerAllowlistData,
+) -> Result<()> {
+    let derivation_data: RelayerAllowlistDerivationData = (&*accs).into();
+    accs.allowlist
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    if accs.token_account.owner != *accs.owner.key {
+        return Err(WrongAccountOwner.into());
+    }
+    if data.relayers.len() > RelayerAllowlistData::MAX_RELAYERS {
+        return Err(TooManyRelayers.into());
+    }
+
+    let initialized = accs.allowlist.is_initialized();
+    accs.allowlist.relayers = data.relayers;
+    if !initialized {
+        return accs
+            .allowlist
+            .create(&derivation_data, ctx, accs.payer.key, Exempt);
+    }
+
+    // The account is resized to the new list, which is persisted after this instruction.
+    let size = accs.allowlist.size();
+    ensure_account_size(ctx, accs.allowlist.info(), accs.payer.key, size)?;
+    if accs.allowlist.info().data_len() > size {
+        accs.allowlist.info().realloc(size, false)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use crate::{
+    accounts::{
+        ConfigAccount,
+        TokenIdAccount,
+        TokenIdDerivationData,
+        WrappedDerivationData,
+        WrappedMetaDerivationData,
+        WrappedTokenMeta,
+    },
+    types::*,
+    TokenBridgeError::InvalidMint,
+};
+use bridge::{
+    api::ForeignAddress,
+    CHAIN_ID_SOLANA,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    program::set_return_data,
+};
+use solitaire::{
+    processors::seeded::{
+        Creatable,
+        Seeded,
+    },
+    CreationLamports::Exempt,
+    *,
+};
+
+#[derive(FromAccounts)]
+pub struct ResolveTokenId<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    /// Lookup account for the (chain, address) pair, created on first resolution.
+    pub token_id: Mut<TokenIdAccount<'b, { AccountState::MaybeInitialized }>>,
+
+    /// Native mint, or the wrapped mint derived from the origin.
+    pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
+
+    /// Wrapped asset metadata for `mint`, must be empty for native mints.
+    pub wrapped_meta: WrappedTokenMeta<'b, { AccountState::MaybeInitialized }>,
+}
+
+impl<'a> From<&ResolveTokenId<'a>> for WrappedMetaDerivationData {
+    fn from(accs: &ResolveTokenId<'a>) -> Self {
+        WrappedMetaDerivationData {
+            mint_key: *accs.mint.info().key,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct ResolveTokenIdData {
+    pub token_chain: ChainID,
+    pub token_address: ForeignAddress,
+}
+
+pub fn resolve_token_id(
+    ctx: &ExecutionContext,
+    accs: &mut ResolveTokenId,
+    data: ResolveTokenIdData,
+) -> Result<()> {
+    accs.token_id.verify_derivation(
+        ctx.program_id,
+        &TokenIdDerivationData {
+            token_chain: data.token_chain,
+            token_address: data.token_address,
+        },
+    )?;
+
+    let meta_derivation_data: WrappedMetaDerivationData = (&*accs).into();
+    accs.wrapped_meta
+        .verify_derivation(ctx.program_id, &meta_derivation_data)?;
+
+    let is_wrapped = data.token_chain != CHAIN_ID_SOLANA;
+    if is_wrapped {
+        // Wrapped mints are only ever derived from their origin, and must have been created by
+        // this bridge.
+        accs.mint.verify_derivation(
+            ctx.program_id,
+            &WrappedDerivationData {
+                token_chain: data.token_chain,
+                token_address: data.token_address,
+            },
+        )?;
+
+        if !accs.wrapped_meta.is_initialized()
+            || accs.wrapped_meta.chain != data.token_chain
+            || accs.wrapped_meta.token_address != data.token_address
+        {
+            return Err(InvalidMint.into());
+        }
+    } else if accs.mint.info().key.to_bytes() != data.token_address
+        || accs.wrapped_meta.is_initialized()
+    {
+        // Native assets are addressed by their mint, and must not be a wrapped asset in disguise.
+        return Err(InvalidMint.into());
+    }
+
+    if accs.token_id.is_initialized() {
+        if accs.token_id.mint != *accs.mint.info().key {
+            return Err(InvalidMint.into());
+        }
+    } else {
+        accs.token_id.chain = data.token_chain;
+        accs.token_id.token_address = data.token_address;
+        accs.token_id.mint = *accs.mint.info().key;
+        accs.token_id.is_wrapped = is_wrapped;
+
+        accs.token_id.create(
+            &TokenIdDerivationData {
+                token_chain: data.token_chain,
+                token_address: data.token_address,
+            },
+            ctx,
+            accs.payer.key,
+            Exempt,
+        )?;
+    }
+
+    set_return_data(&accs.mint.info().key.to_bytes());
+
+    Ok(())
+}
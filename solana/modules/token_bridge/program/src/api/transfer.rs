@@ -13,7 +13,19 @@ use crate::{
         WrappedMint,
         WrappedTokenMeta,
     },
-    messages::PayloadTransfer,
+    amount,
+    messages::{
+        BurnReceipt,
+        PayloadTransfer,
+    },
+    token_interface::{
+        create_custody,
+        token_amount,
+        token_program,
+        transfer_checked,
+        verify_mint_extensions,
+        verify_token_accounts,
+    },
     types::*,
     TokenBridgeError,
     TokenBridgeError::{
@@ -23,6 +35,7 @@ use crate::{
     },
 };
 use bridge::{
+    accounts::BridgeConfig,
     api::PostMessageData,
     types::ConsistencyLevel,
     vaa::SerializePayload,
@@ -35,21 +48,20 @@ use solana_program::{
         AccountMeta,
         Instruction,
     },
-    program::{
-        invoke,
-        invoke_signed,
-    },
     program_option::COption,
     sysvar::clock::Clock,
 };
 use solitaire::{
+    invoke,
     processors::seeded::{
         invoke_seeded,
         Seeded,
     },
-    CreationLamports::Exempt,
     *,
 };
+use std::io::Write;
+
+use super::verify_not_frozen;
 
 #[derive(FromAccounts)]
 pub struct TransferNative<'b> {
@@ -57,9 +69,9 @@ pub struct TransferNative<'b> {
 
     pub config: ConfigAccount<'b, { AccountState::Initialized }>,
 
-    pub from: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+    pub from: Mut<Data<'b, InterfaceAccount, { AccountState::Initialized }>>,
 
-    pub mint: Mut<Data<'b, SplMint, { AccountState::Initialized }>>,
+    pub mint: Mut<Data<'b, InterfaceMint, { AccountState::Initialized }>>,
 
     pub custody: Mut<CustodyAccount<'b, { AccountState::MaybeInitialized }>>,
 
@@ -173,8 +185,8 @@ pub fn verify_and_execute_native_transfers(
     ctx: &ExecutionContext,
     derivation_data: &CustodyAccountDerivationData,
     payer: &Mut<Signer<AccountInfo>>,
-    from: &Mut<Data<SplAccount, { AccountState::Initialized }>>,
-    mint: &Mut<Data<SplMint, { AccountState::Initialized }>>,
+    from: &Mut<Data<InterfaceAccount, { AccountState::Initialized }>>,
+    mint: &Mut<Data<InterfaceMint, { AccountState::Initialized }>>,
     custody: &Mut<CustodyAccount<{ AccountState::MaybeInitialized }>>,
     authority_signer: &AuthoritySigner,
     custody_signer: &CustodySigner,
@@ -183,6 +195,14 @@ pub fn verify_and_execute_native_transfers(
     raw_amount: u64,
     raw_fee: u64,
 ) -> Result<(u64, u64)> {
+    // Fail before moving tokens rather than on posting the message
+    if bridge
+        .config
+        .is_paused(BridgeConfig::PAUSE_EMISSION | BridgeConfig::PAUSE_OUTBOUND_TRANSFERS)
+    {
+        return Err(TokenBridgeError::BridgePaused.into());
+    }
+
     // Verify that the custody account is derived correctly
     custody.verify_derivation(ctx.program_id, derivation_data)?;
 
@@ -190,12 +210,17 @@ pub fn verify_and_execute_native_transfers(
     if from.mint != *mint.info().key {
         return Err(TokenBridgeError::InvalidMint.into());
     }
+    verify_token_accounts(&token_program(mint.info())?, &[from.info()])?;
+    verify_mint_extensions(mint.info())?;
 
     // Fee must be less than amount
     if raw_fee > raw_amount {
         return Err(InvalidFee.into());
     }
 
+    #[cfg(feature = "transfer-cap")]
+    crate::accounts::verify_transfer_cap(ctx, mint.info().key, raw_amount)?;
+
     // Verify that the token is not a wrapped token
     if let COption::Some(mint_authority) = mint.mint_authority {
         if mint_authority == MintSigner::key(None, ctx.program_id) {
@@ -203,36 +228,40 @@ pub fn verify_and_execute_native_transfers(
         }
     }
 
-    if !custody.is_initialized() {
-        custody.create(derivation_data, ctx, payer.key, Exempt)?;
+    verify_not_frozen(custody, &[from])?;
 
-        let init_ix = spl_token::instruction::initialize_account(
-            &spl_token::id(),
-            custody.info().key,
-            mint.info().key,
+    if !custody.is_initialized() {
+        create_custody(
+            ctx,
+            custody.info(),
+            derivation_data,
+            payer.key,
+            mint.info(),
             custody_signer.key,
         )?;
-        invoke_signed(&init_ix, ctx.accounts, &[])?;
     }
 
-    let trunc_divisor = 10u64.pow(8.max(mint.decimals as u32) - 8);
-    // Truncate to 8 decimals
-    let amount: u64 = raw_amount / trunc_divisor;
-    let fee: u64 = raw_fee / trunc_divisor;
-    // Untruncate the amount to drop the remainder so we don't  "burn" user's funds.
-    let amount_trunc: u64 = amount * trunc_divisor;
+    // Truncate to 8 decimals, then untruncate the amount to drop the remainder so we don't "burn"
+    // user's funds.
+    let amount_trunc: u64 = amount::truncate(raw_amount, mint.decimals);
 
     // Transfer tokens
-    let transfer_ix = spl_token::instruction::transfer(
-        &spl_token::id(),
+    let custody_amount = custody.amount;
+    let transfer_ix = transfer_checked(
+        mint.info(),
         from.info().key,
         custody.info().key,
         authority_signer.key,
-        &[],
         amount_trunc,
+        mint.decimals,
     )?;
     invoke_seeded(&transfer_ix, ctx, authority_signer, None)?;
 
+    // Bridge what custody received, net of Token-2022 transfer fees. Truncating it again leaves the
+    // remainder in custody.
+    let received = token_amount(custody.info())? - custody_amount;
+    let (amount, fee) = amount::bridged(received, raw_fee, mint.decimals).ok_or(InvalidFee)?;
+
     // Pay fee
     let transfer_ix = solana_program::system_instruction::transfer(
         payer.key,
@@ -291,13 +320,45 @@ impl<'a> From<&TransferWrapped<'a>> for WrappedMetaDerivationData {
     }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(Default)]
 pub struct TransferWrappedData {
     pub nonce: u32,
     pub amount: u64,
     pub fee: u64,
     pub target_address: Address,
     pub target_chain: ChainID,
+
+    /// Append a [`BurnReceipt`] to the transfer. Only for target chains whose token bridge accepts
+    /// the extension, elsewhere the transfer can't be redeemed. Omitted by older clients.
+    pub burn_receipt: bool,
+}
+
+impl BorshSerialize for TransferWrappedData {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.nonce.serialize(writer)?;
+        self.amount.serialize(writer)?;
+        self.fee.serialize(writer)?;
+        self.target_address.serialize(writer)?;
+        self.target_chain.serialize(writer)?;
+        // Keep the original layout unless the receipt is requested.
+        if self.burn_receipt {
+            self.burn_receipt.serialize(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for TransferWrappedData {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        Ok(TransferWrappedData {
+            nonce: u32::deserialize(buf)?,
+            amount: u64::deserialize(buf)?,
+            fee: u64::deserialize(buf)?,
+            target_address: Address::deserialize(buf)?,
+            target_chain: ChainID::deserialize(buf)?,
+            burn_receipt: !buf.is_empty() && bool::deserialize(buf)?,
+        })
+    }
 }
 
 pub fn transfer_wrapped(
@@ -335,11 +396,22 @@ pub fn transfer_wrapped(
         to_chain: data.target_chain,
         fee: U256::from(data.fee),
     };
+    let mut payload = payload.try_to_vec()?;
+    if data.burn_receipt {
+        let receipt = BurnReceipt {
+            token_chain: accs.wrapped_meta.chain,
+            token_address: accs.wrapped_meta.token_address,
+            mint: *accs.mint.info().key,
+            amount: data.amount,
+            slot: accs.clock.slot,
+        };
+        payload.extend(receipt.try_to_vec()?);
+    }
     let params = (
         bridge::instruction::Instruction::PostMessage,
         PostMessageData {
             nonce: data.nonce,
-            payload: payload.try_to_vec()?,
+            payload,
             consistency_level: ConsistencyLevel::Finalized,
         },
     );
@@ -379,6 +451,14 @@ pub fn verify_and_execute_wrapped_transfers(
     amount: u64,
     fee: u64,
 ) -> Result<()> {
+    // Fail before moving tokens rather than on posting the message
+    if bridge
+        .config
+        .is_paused(BridgeConfig::PAUSE_EMISSION | BridgeConfig::PAUSE_OUTBOUND_TRANSFERS)
+    {
+        return Err(TokenBridgeError::BridgePaused.into());
+    }
+
     // Verify that the from account is owned by the from_owner
     if &from.owner != from_owner.key {
         return Err(WrongAccountOwner.into());
@@ -394,6 +474,9 @@ pub fn verify_and_execute_wrapped_transfers(
         return Err(InvalidFee.into());
     }
 
+    #[cfg(feature = "transfer-cap")]
+    crate::accounts::verify_transfer_cap(ctx, mint.info().key, amount)?;
+
     // Verify that meta is correct
     wrapped_meta.verify_derivation(ctx.program_id, derivation_data)?;
 
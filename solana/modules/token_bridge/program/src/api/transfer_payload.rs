@@ -14,7 +14,10 @@ use crate::{
     },
     messages::PayloadTransferWithPayload,
     types::*,
-    TokenBridgeError::InvalidChain,
+    TokenBridgeError::{
+        InvalidChain,
+        TransferExpired,
+    },
 };
 use bridge::{
     api::PostMessageData,
@@ -36,6 +39,7 @@ use solitaire::{
     processors::seeded::invoke_seeded,
     *,
 };
+use wormhole_solana_consts::seeds;
 
 use super::{
     verify_and_execute_native_transfers,
@@ -65,7 +69,7 @@ impl<'a, 'b: 'a> Peel<'a, 'b> for SenderAccount<'b> {
 // [`Seeded`] directly.
 impl<'b> Seeded<()> for SenderAccount<'b> {
     fn seeds(_accs: ()) -> Vec<Vec<u8>> {
-        vec![String::from("sender").as_bytes().to_vec()]
+        vec![seeds::SENDER.as_bytes().to_vec()]
     }
 }
 
@@ -127,8 +131,8 @@ pub struct TransferNativeWithPayload<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
     pub config: ConfigAccount<'b, { AccountState::Initialized }>,
 
-    pub from: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
-    pub mint: Mut<Data<'b, SplMint, { AccountState::Initialized }>>,
+    pub from: Mut<Data<'b, InterfaceAccount, { AccountState::Initialized }>>,
+    pub mint: Mut<Data<'b, InterfaceMint, { AccountState::Initialized }>>,
     pub custody: Mut<CustodyAccount<'b, { AccountState::MaybeInitialized }>>,
 
     // This could allow someone to race someone else's tx if they do the approval in a separate tx.
@@ -213,6 +217,10 @@ pub fn transfer_native_with_payload(
         from_address: accs.sender.derive_sender_address(&data.cpi_program_id)?,
         payload: data.payload,
     };
+    // Don't emit transfers that could never be completed
+    if payload.is_expired(accs.clock.unix_timestamp) {
+        return Err(TransferExpired.into());
+    }
     let params = (
         bridge::instruction::Instruction::PostMessage,
         PostMessageData {
@@ -342,6 +350,10 @@ pub fn transfer_wrapped_with_payload(
         from_address: accs.sender.derive_sender_address(&data.cpi_program_id)?,
         payload: data.payload,
     };
+    // Don't emit transfers that could never be completed
+    if payload.is_expired(accs.clock.unix_timestamp) {
+        return Err(TransferExpired.into());
+    }
     let params = (
         bridge::instruction::Instruction::PostMessage,
         PostMessageData {
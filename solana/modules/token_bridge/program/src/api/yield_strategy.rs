@@ -0,0 +1,201 @@
+use crate::{
+    accounts::{
+        borrower,
+        CustodyAccount,
+        CustodyAccountDerivationData,
+        CustodySigner,
+        YieldStrategy,
+        YieldStrategyDerivationData,
+    },
+    token_interface::{
+        token_amount,
+        token_program,
+        transfer_checked,
+        verify_token_accounts,
+    },
+    types::*,
+    TokenBridgeError::*,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    pubkey::Pubkey,
+};
+use solitaire::{
+    invoke,
+    processors::seeded::{
+        invoke_seeded,
+        Seeded,
+    },
+    *,
+};
+
+#[derive(FromAccounts)]
+pub struct LendCustody<'b> {
+    pub yield_strategy: Mut<YieldStrategy<'b, { AccountState::Initialized }>>,
+
+    pub mint: Data<'b, InterfaceMint, { AccountState::Initialized }>,
+    pub custody: Mut<CustodyAccount<'b, { AccountState::Initialized }>>,
+    pub custody_signer: CustodySigner<'b>,
+
+    /// `["borrower"]` PDA of the strategy program, signing
+    pub borrower: Signer<AccountInfo<'b>>,
+
+    /// Token account of the strategy receiving the tokens
+    pub to: Mut<Data<'b, InterfaceAccount, { AccountState::Initialized }>>,
+}
+
+impl<'a> From<&LendCustody<'a>> for YieldStrategyDerivationData {
+    fn from(accs: &LendCustody<'a>) -> Self {
+        YieldStrategyDerivationData {
+            mint: *accs.mint.info().key,
+        }
+    }
+}
+
+impl<'a> From<&LendCustody<'a>> for CustodyAccountDerivationData {
+    fn from(accs: &LendCustody<'a>) -> Self {
+        CustodyAccountDerivationData {
+            mint: *accs.mint.info().key,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct LendCustodyData {
+    pub amount: u64,
+}
+
+/// Lend idle custody of a native mint to the yield strategy governance set for it with
+/// [`crate::api::set_yield_strategy`]. The strategy program signs with its `["borrower"]` PDA and
+/// may owe at most the caps of the decree, checked against the bridged supply of the mint, the
+/// custody balance plus what is lent. The rest stays in custody, so that transfers out and
+/// redemptions work as before.
+pub fn lend_custody(
+    ctx: &ExecutionContext,
+    accs: &mut LendCustody,
+    data: LendCustodyData,
+) -> Result<()> {
+    let derivation_data: YieldStrategyDerivationData = (&*accs).into();
+    accs.yield_strategy
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+    let derivation_data: CustodyAccountDerivationData = (&*accs).into();
+    accs.custody
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    verify_custody(&accs.mint, &accs.custody, &accs.to)?;
+    if *accs.custody_signer.key != accs.custody.owner {
+        return Err(WrongAccountOwner.into());
+    }
+
+    // Governance disables a strategy by setting its caps to zero, the default strategy never
+    // borrows.
+    let strategy = accs.yield_strategy.strategy;
+    if strategy == Pubkey::default() || *accs.borrower.key != borrower(&strategy) {
+        return Err(InvalidBorrower.into());
+    }
+    if !accs
+        .yield_strategy
+        .can_lend(accs.custody.amount, data.amount)
+    {
+        return Err(YieldCapExceeded.into());
+    }
+    accs.yield_strategy.lent += data.amount;
+
+    let transfer_ix = transfer_checked(
+        accs.mint.info(),
+        accs.custody.info().key,
+        accs.to.info().key,
+        accs.custody_signer.key,
+        data.amount,
+        accs.mint.decimals,
+    )?;
+    invoke_seeded(&transfer_ix, ctx, &accs.custody_signer, None)?;
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct RepayCustody<'b> {
+    pub yield_strategy: Mut<YieldStrategy<'b, { AccountState::Initialized }>>,
+
+    pub mint: Data<'b, InterfaceMint, { AccountState::Initialized }>,
+    pub custody: Mut<CustodyAccount<'b, { AccountState::Initialized }>>,
+
+    /// Token account repaid from
+    pub from: Mut<Data<'b, InterfaceAccount, { AccountState::Initialized }>>,
+
+    /// Owner or delegate of `from`, usually the borrower
+    pub from_owner: Signer<AccountInfo<'b>>,
+}
+
+impl<'a> From<&RepayCustody<'a>> for YieldStrategyDerivationData {
+    fn from(accs: &RepayCustody<'a>) -> Self {
+        YieldStrategyDerivationData {
+            mint: *accs.mint.info().key,
+        }
+    }
+}
+
+impl<'a> From<&RepayCustody<'a>> for CustodyAccountDerivationData {
+    fn from(accs: &RepayCustody<'a>) -> Self {
+        CustodyAccountDerivationData {
+            mint: *accs.mint.info().key,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct RepayCustodyData {
+    pub amount: u64,
+}
+
+/// Repay custody lent with [`lend_custody`]. Anyone may repay, only what custody receives counts
+/// against the debt, and the yield of the strategy is not repaid to custody.
+pub fn repay_custody(
+    ctx: &ExecutionContext,
+    accs: &mut RepayCustody,
+    data: RepayCustodyData,
+) -> Result<()> {
+    let derivation_data: YieldStrategyDerivationData = (&*accs).into();
+    accs.yield_strategy
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+    let derivation_data: CustodyAccountDerivationData = (&*accs).into();
+    accs.custody
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    verify_custody(&accs.mint, &accs.custody, &accs.from)?;
+    if data.amount > accs.yield_strategy.lent {
+        return Err(RepayExceedsLent.into());
+    }
+
+    // Token-2022 transfer fees are withheld from custody, which then receives less than `amount`.
+    let balance = token_amount(accs.custody.info())?;
+    let transfer_ix = transfer_checked(
+        accs.mint.info(),
+        accs.from.info().key,
+        accs.custody.info().key,
+        accs.from_owner.key,
+        data.amount,
+        accs.mint.decimals,
+    )?;
+    invoke(&transfer_ix, ctx.accounts)?;
+    let received = token_amount(accs.custody.info())?.saturating_sub(balance);
+    accs.yield_strategy.lent -= received;
+
+    Ok(())
+}
+
+fn verify_custody(
+    mint: &Data<InterfaceMint, { AccountState::Initialized }>,
+    custody: &CustodyAccount<{ AccountState::Initialized }>,
+    other: &Data<InterfaceAccount, { AccountState::Initialized }>,
+) -> Result<()> {
+    if custody.mint != *mint.info().key || other.mint != *mint.info().key {
+        return Err(InvalidMint.into());
+    }
+    verify_token_accounts(
+        &token_program(mint.info())?,
+        &[custody.info(), other.info()],
+    )?;
+    Ok(())
+}
@@ -4,32 +4,65 @@ use crate::{
         ConfigAccount,
         CustodyAccount,
         CustodyAccountDerivationData,
+        CustodyAttestation,
         CustodySigner,
         EmitterAccount,
         Endpoint,
         EndpointDerivationData,
+        MetadataAuthority,
+        MetadataAuthorityDerivationData,
         MintSigner,
+        RecoveredChain,
+        RelayerAllowlist,
+        RelayerAllowlistDerivationData,
         SplTokenMeta,
         SplTokenMetaDerivationData,
+        TokenIdAccount,
+        TokenIdDerivationData,
+        TransferCap,
+        TransferCapDerivationData,
         WrappedDerivationData,
         WrappedMetaDerivationData,
         WrappedMint,
         WrappedTokenMeta,
+        YieldStrategy,
+        YieldStrategyDerivationData,
     },
     api::{
         complete_transfer::{
             CompleteNativeData,
             CompleteWrappedData,
         },
+        redemption_accounts,
+        AttestAndTransferNativeData,
+        AttestCustodyData,
         AttestTokenData,
+        CompleteTransfersBatchData,
         CreateWrappedData,
+        LendCustodyData,
+        OverrideTransferCapData,
+        ProbeRedemptionData,
+        ReclaimExpiredTransferData,
+        RecoverChainIdData,
+        RedemptionKind,
         RegisterChainData,
+        RepayCustodyData,
+        ResolveTokenIdData,
+        RotateMetadataAuthorityData,
         SenderAccount,
+        SetMetadataAuthorityData,
+        SetRelayerAllowlistData,
+        SetTransferCapData,
+        SetYieldStrategyData,
         TransferNativeData,
         TransferWrappedData,
         UpgradeContractData,
     },
     messages::{
+        GovernancePayloadOverrideTransferCap,
+        GovernancePayloadSetMetadataAuthority,
+        GovernancePayloadSetTransferCap,
+        GovernancePayloadSetYieldStrategy,
         PayloadAssetMeta,
         PayloadGovernanceRegisterChain,
         PayloadTransfer,
@@ -40,17 +73,23 @@ use crate::{
     TransferNativeWithPayloadData,
     TransferWrappedWithPayloadData,
 };
-use borsh::BorshSerialize;
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
 use bridge::{
     accounts::{
         Bridge,
         Claim,
         ClaimDerivationData,
         FeeCollector,
+        PostedVAA,
+        PostedVAADerivationData,
         Sequence,
         SequenceDerivationData,
     },
     api::ForeignAddress,
+    DeserializePayload,
     PostVAAData,
     CHAIN_ID_SOLANA,
 };
@@ -65,6 +104,7 @@ use solitaire::{
     processors::seeded::Seeded,
     AccountState,
 };
+use wormhole_solana_consts::seeds;
 
 pub fn initialize(
     program_id: Pubkey,
@@ -112,30 +152,39 @@ pub fn complete_native(
     );
     let custody_signer_key = CustodySigner::key(None, &program_id);
 
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(config_key, false),
+        message_acc,
+        claim_acc,
+        AccountMeta::new_readonly(endpoint, false),
+        AccountMeta::new(to, false),
+        if let Some(fee_r) = fee_recipient {
+            AccountMeta::new(fee_r, false)
+        } else {
+            AccountMeta::new(to, false)
+        },
+        AccountMeta::new(custody_key, false),
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new_readonly(custody_signer_key, false),
+        // Dependencies
+        AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        // Program
+        AccountMeta::new_readonly(bridge_id, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        // Only read with the `transfer-cap` feature, and ignored otherwise.
+        transfer_cap(program_id, mint),
+        // Only read with the `inbound-pause` feature, and ignored otherwise.
+        core_bridge_config(bridge_id),
+        // Only read with the `relayer-allowlist` feature, and ignored otherwise.
+        relayer_allowlist(program_id, to),
+    ];
+    accounts.extend(recipient_owner_accounts(data.recipient_owner));
+
     Ok(Instruction {
         program_id,
-        accounts: vec![
-            AccountMeta::new(payer, true),
-            AccountMeta::new_readonly(config_key, false),
-            message_acc,
-            claim_acc,
-            AccountMeta::new_readonly(endpoint, false),
-            AccountMeta::new(to, false),
-            if let Some(fee_r) = fee_recipient {
-                AccountMeta::new(fee_r, false)
-            } else {
-                AccountMeta::new(to, false)
-            },
-            AccountMeta::new(custody_key, false),
-            AccountMeta::new_readonly(mint, false),
-            AccountMeta::new_readonly(custody_signer_key, false),
-            // Dependencies
-            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            // Program
-            AccountMeta::new_readonly(bridge_id, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-        ],
+        accounts,
         data: (crate::instruction::Instruction::CompleteNative, data).try_to_vec()?,
     })
 }
@@ -168,31 +217,43 @@ pub fn complete_native_with_payload(
     );
     let custody_signer_key = CustodySigner::key(None, &program_id);
 
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(config_key, false),
+        message_acc,
+        claim_acc,
+        AccountMeta::new_readonly(endpoint, false),
+        AccountMeta::new(to, false),
+        AccountMeta::new_readonly(to_owner, true),
+        if let Some(fee_r) = fee_recipient {
+            AccountMeta::new(fee_r, false)
+        } else {
+            AccountMeta::new(to, false)
+        },
+        AccountMeta::new(custody_key, false),
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new_readonly(custody_signer_key, false),
+        // Dependencies
+        AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        // Program
+        AccountMeta::new_readonly(bridge_id, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        // Only read with the `transfer-cap` feature, and ignored otherwise.
+        transfer_cap(program_id, mint),
+        // Only read with the `inbound-pause` feature, and ignored otherwise.
+        core_bridge_config(bridge_id),
+    ];
+    // Token accounts of the shares of split transfers
+    if let Ok(transfer) =
+        <PayloadTransferWithPayload as DeserializePayload>::deserialize(&mut &vaa.payload[..])
+    {
+        accounts.extend(split_accounts(&transfer));
+    }
+
     Ok(Instruction {
         program_id,
-        accounts: vec![
-            AccountMeta::new(payer, true),
-            AccountMeta::new_readonly(config_key, false),
-            message_acc,
-            claim_acc,
-            AccountMeta::new_readonly(endpoint, false),
-            AccountMeta::new(to, false),
-            AccountMeta::new_readonly(to_owner, true),
-            if let Some(fee_r) = fee_recipient {
-                AccountMeta::new(fee_r, false)
-            } else {
-                AccountMeta::new(to, false)
-            },
-            AccountMeta::new(custody_key, false),
-            AccountMeta::new_readonly(mint, false),
-            AccountMeta::new_readonly(custody_signer_key, false),
-            // Dependencies
-            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            // Program
-            AccountMeta::new_readonly(bridge_id, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-        ],
+        accounts,
         data: (
             crate::instruction::Instruction::CompleteNativeWithPayload,
             data,
@@ -235,30 +296,39 @@ pub fn complete_wrapped(
     );
     let mint_authority_key = MintSigner::key(None, &program_id);
 
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(config_key, false),
+        message_acc,
+        claim_acc,
+        AccountMeta::new_readonly(endpoint, false),
+        AccountMeta::new(to, false),
+        if let Some(fee_r) = fee_recipient {
+            AccountMeta::new(fee_r, false)
+        } else {
+            AccountMeta::new(to, false)
+        },
+        AccountMeta::new(mint_key, false),
+        AccountMeta::new_readonly(meta_key, false),
+        AccountMeta::new_readonly(mint_authority_key, false),
+        // Dependencies
+        AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        // Program
+        AccountMeta::new_readonly(bridge_id, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        // Only read with the `transfer-cap` feature, and ignored otherwise.
+        transfer_cap(program_id, mint_key),
+        // Only read with the `inbound-pause` feature, and ignored otherwise.
+        core_bridge_config(bridge_id),
+        // Only read with the `relayer-allowlist` feature, and ignored otherwise.
+        relayer_allowlist(program_id, to),
+    ];
+    accounts.extend(recipient_owner_accounts(data.recipient_owner));
+
     Ok(Instruction {
         program_id,
-        accounts: vec![
-            AccountMeta::new(payer, true),
-            AccountMeta::new_readonly(config_key, false),
-            message_acc,
-            claim_acc,
-            AccountMeta::new_readonly(endpoint, false),
-            AccountMeta::new(to, false),
-            if let Some(fee_r) = fee_recipient {
-                AccountMeta::new(fee_r, false)
-            } else {
-                AccountMeta::new(to, false)
-            },
-            AccountMeta::new(mint_key, false),
-            AccountMeta::new_readonly(meta_key, false),
-            AccountMeta::new_readonly(mint_authority_key, false),
-            // Dependencies
-            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            // Program
-            AccountMeta::new_readonly(bridge_id, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-        ],
+        accounts,
         data: (crate::instruction::Instruction::CompleteWrapped, data).try_to_vec()?,
     })
 }
@@ -298,6 +368,96 @@ pub fn complete_wrapped_with_payload(
     );
     let mint_authority_key = MintSigner::key(None, &program_id);
 
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(config_key, false),
+        message_acc,
+        claim_acc,
+        AccountMeta::new_readonly(endpoint, false),
+        AccountMeta::new(to, false),
+        AccountMeta::new_readonly(to_owner, true),
+        if let Some(fee_r) = fee_recipient {
+            AccountMeta::new(fee_r, false)
+        } else {
+            AccountMeta::new(to, false)
+        },
+        AccountMeta::new(mint_key, false),
+        AccountMeta::new_readonly(meta_key, false),
+        AccountMeta::new_readonly(mint_authority_key, false),
+        // Dependencies
+        AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        // Program
+        AccountMeta::new_readonly(bridge_id, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        // Only read with the `transfer-cap` feature, and ignored otherwise.
+        transfer_cap(program_id, mint_key),
+        // Only read with the `inbound-pause` feature, and ignored otherwise.
+        core_bridge_config(bridge_id),
+    ];
+    // Token accounts of the shares of split transfers
+    accounts.extend(split_accounts(&payload));
+
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data: (
+            crate::instruction::Instruction::CompleteWrappedWithPayload,
+            data,
+        )
+            .try_to_vec()?,
+    })
+}
+
+/// Required accounts
+///
+/// | name               | account                                                                | signer |
+/// |--------------------+------------------------------------------------------------------------+--------|
+/// | payer              | Pubkey                                                                 | true   |
+/// | config             | PDA(program_id, \["config"\])                                          | false  |
+/// | message            | Pubkey                                                                 | false  |
+/// | claim              | PDA(program_id, \[emitter_address, emitter_chain, sequence\])          | false  |
+/// | chain_registration | PDA(program_id, \[emitter_chain, emitter_address\])                    | false  |
+/// | bridge_config      | PDA(bridge_id,  \["Bridge"\])                                          | false  |
+/// | refund_message     | Pubkey                                                                 | true   |
+/// | emitter            | PDA(program_id, \["emitter"\])                                         | false  |
+/// | sequence           | PDA(bridge_id,  \["Sequence", emitter\])                               | false  |
+/// | fee_collector      | PDA(bridge_id,  \["fee_collector"\])                                   | false  |
+/// | clock              | clock sysvar                                                           | false  |
+/// | rent               | rent sysvar                                                            | false  |
+/// | system_program     | system program                                                         | false  |
+/// | bridge_id          | bridge_id program                                                      | false  |
+#[allow(clippy::too_many_arguments)]
+pub fn reclaim_expired_transfer(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    refund_message_key: Pubkey,
+    data: ReclaimExpiredTransferData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa.clone());
+    let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
+        &EndpointDerivationData {
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+        },
+        &program_id,
+    );
+    let emitter_key = EmitterAccount::key(None, &program_id);
+
+    // Bridge keys
+    let bridge_config = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &bridge_id);
+    let sequence_key = Sequence::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter_key,
+        },
+        &bridge_id,
+    );
+    let fee_collector_key = FeeCollector::key(None, &bridge_id);
+
     Ok(Instruction {
         program_id,
         accounts: vec![
@@ -306,25 +466,20 @@ pub fn complete_wrapped_with_payload(
             message_acc,
             claim_acc,
             AccountMeta::new_readonly(endpoint, false),
-            AccountMeta::new(to, false),
-            AccountMeta::new_readonly(to_owner, true),
-            if let Some(fee_r) = fee_recipient {
-                AccountMeta::new(fee_r, false)
-            } else {
-                AccountMeta::new(to, false)
-            },
-            AccountMeta::new(mint_key, false),
-            AccountMeta::new_readonly(meta_key, false),
-            AccountMeta::new_readonly(mint_authority_key, false),
+            AccountMeta::new(bridge_config, false),
+            AccountMeta::new(refund_message_key, true),
+            AccountMeta::new_readonly(emitter_key, false),
+            AccountMeta::new(sequence_key, false),
+            AccountMeta::new(fee_collector_key, false),
+            AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
             // Dependencies
             AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             // Program
             AccountMeta::new_readonly(bridge_id, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
         ],
         data: (
-            crate::instruction::Instruction::CompleteWrappedWithPayload,
+            crate::instruction::Instruction::ReclaimExpiredTransfer,
             data,
         )
             .try_to_vec()?,
@@ -382,31 +537,329 @@ pub fn create_wrapped(
             // Dependencies
             AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            // Program
-            AccountMeta::new_readonly(bridge_id, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-            AccountMeta::new_readonly(spl_token_metadata::id(), false),
+            // Program
+            AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_token_metadata::id(), false),
+        ],
+        data: (crate::instruction::Instruction::CreateWrapped, data).try_to_vec()?,
+    })
+}
+
+pub fn resolve_token_id(
+    program_id: Pubkey,
+    payer: Pubkey,
+    token_chain: u16,
+    token_address: ForeignAddress,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let token_id_key = TokenIdAccount::<'_, { AccountState::Uninitialized }>::key(
+        &TokenIdDerivationData {
+            token_chain,
+            token_address,
+        },
+        &program_id,
+    );
+    let mint_key = if token_chain == CHAIN_ID_SOLANA {
+        Pubkey::new(&token_address)
+    } else {
+        WrappedMint::<'_, { AccountState::Uninitialized }>::key(
+            &WrappedDerivationData {
+                token_chain,
+                token_address,
+            },
+            &program_id,
+        )
+    };
+    let mint_meta_key = WrappedTokenMeta::<'_, { AccountState::Uninitialized }>::key(
+        &WrappedMetaDerivationData { mint_key },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key, false),
+            AccountMeta::new(token_id_key, false),
+            AccountMeta::new_readonly(mint_key, false),
+            AccountMeta::new_readonly(mint_meta_key, false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: (
+            crate::instruction::Instruction::ResolveTokenId,
+            ResolveTokenIdData {
+                token_chain,
+                token_address,
+            },
+        )
+            .try_to_vec()?,
+    })
+}
+
+/// Probe the accounts redeeming `vaa` by `kind`, whose body hashes to `digest`. `to` is the token
+/// account the transfer is redeemed to, it is taken from the payload if set there.
+pub fn probe_redemption(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    digest: [u8; 32],
+    vaa: PostVAAData,
+    kind: RedemptionKind,
+    to: Pubkey,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let message_key = PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: digest.to_vec(),
+        },
+        &bridge_id,
+    );
+    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa.clone());
+    let (mint_key, payload_to) = redemption_accounts(&program_id, kind, &vaa.payload)?;
+    let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
+        &EndpointDerivationData {
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+        },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(config_key, false),
+            message_acc,
+            AccountMeta::new_readonly(claim_acc.pubkey, false),
+            AccountMeta::new_readonly(payload_to.unwrap_or(to), false),
+            AccountMeta::new_readonly(mint_key, false),
+            AccountMeta::new_readonly(endpoint, false),
+        ],
+        data: (
+            crate::instruction::Instruction::ProbeRedemption,
+            ProbeRedemptionData { digest, kind },
+        )
+            .try_to_vec()?,
+    })
+}
+
+pub fn register_chain(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    payload: PayloadGovernanceRegisterChain,
+    data: RegisterChainData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa);
+    let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
+        &EndpointDerivationData {
+            emitter_chain: payload.chain,
+            emitter_address: payload.endpoint_address,
+        },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key, false),
+            AccountMeta::new(endpoint, false),
+            message_acc,
+            claim_acc,
+            // Dependencies
+            AccountMeta::new(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new(solana_program::system_program::id(), false),
+            // Program
+            AccountMeta::new_readonly(bridge_id, false),
+        ],
+        data: (crate::instruction::Instruction::RegisterChain, data).try_to_vec()?,
+    })
+}
+
+pub fn recover_chain_id(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+) -> solitaire::Result<Instruction> {
+    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa);
+    let recovered_chain =
+        RecoveredChain::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            message_acc,
+            claim_acc,
+            AccountMeta::new(recovered_chain, false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: (
+            crate::instruction::Instruction::RecoverChainId,
+            RecoverChainIdData {},
+        )
+            .try_to_vec()?,
+    })
+}
+
+pub fn set_metadata_authority(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    payload: GovernancePayloadSetMetadataAuthority,
+) -> solitaire::Result<Instruction> {
+    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa);
+    let decree = MetadataAuthority::<'_, { AccountState::MaybeInitialized }>::key(
+        &MetadataAuthorityDerivationData { mint: payload.mint },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            message_acc,
+            claim_acc,
+            AccountMeta::new(decree, false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: (
+            crate::instruction::Instruction::SetMetadataAuthority,
+            SetMetadataAuthorityData {},
+        )
+            .try_to_vec()?,
+    })
+}
+
+pub fn rotate_metadata_authority(
+    program_id: Pubkey,
+    mint_key: Pubkey,
+) -> solitaire::Result<Instruction> {
+    let decree = MetadataAuthority::<'_, { AccountState::MaybeInitialized }>::key(
+        &MetadataAuthorityDerivationData { mint: None },
+        &program_id,
+    );
+    let mint_decree = MetadataAuthority::<'_, { AccountState::MaybeInitialized }>::key(
+        &MetadataAuthorityDerivationData {
+            mint: Some(mint_key),
+        },
+        &program_id,
+    );
+    let mint_meta_key = WrappedTokenMeta::<'_, { AccountState::Uninitialized }>::key(
+        &WrappedMetaDerivationData { mint_key },
+        &program_id,
+    );
+    let mint_authority_key = MintSigner::key(None, &program_id);
+    let spl_metadata = SplTokenMeta::key(
+        &SplTokenMetaDerivationData { mint: mint_key },
+        &spl_token_metadata::id(),
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(decree, false),
+            AccountMeta::new_readonly(mint_decree, false),
+            AccountMeta::new_readonly(mint_key, false),
+            AccountMeta::new_readonly(mint_meta_key, false),
+            AccountMeta::new(spl_metadata, false),
+            AccountMeta::new_readonly(mint_authority_key, false),
+            // Program
+            AccountMeta::new_readonly(spl_token_metadata::id(), false),
+        ],
+        data: (
+            crate::instruction::Instruction::RotateMetadataAuthority,
+            RotateMetadataAuthorityData {},
+        )
+            .try_to_vec()?,
+    })
+}
+
+pub fn set_transfer_cap(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    payload: GovernancePayloadSetTransferCap,
+) -> solitaire::Result<Instruction> {
+    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa);
+    let transfer_cap = TransferCap::<'_, { AccountState::MaybeInitialized }>::key(
+        &TransferCapDerivationData { mint: payload.mint },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            message_acc,
+            claim_acc,
+            AccountMeta::new(transfer_cap, false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: (
+            crate::instruction::Instruction::SetTransferCap,
+            SetTransferCapData {},
+        )
+            .try_to_vec()?,
+    })
+}
+
+pub fn override_transfer_cap(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    payload: GovernancePayloadOverrideTransferCap,
+) -> solitaire::Result<Instruction> {
+    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa);
+    let transfer_cap = TransferCap::<'_, { AccountState::MaybeInitialized }>::key(
+        &TransferCapDerivationData { mint: payload.mint },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            message_acc,
+            claim_acc,
+            AccountMeta::new(transfer_cap, false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
         ],
-        data: (crate::instruction::Instruction::CreateWrapped, data).try_to_vec()?,
+        data: (
+            crate::instruction::Instruction::OverrideTransferCap,
+            OverrideTransferCapData {},
+        )
+            .try_to_vec()?,
     })
 }
 
-pub fn register_chain(
+pub fn set_yield_strategy(
     program_id: Pubkey,
-    bridge_id: Pubkey,
     payer: Pubkey,
     message_key: Pubkey,
     vaa: PostVAAData,
-    payload: PayloadGovernanceRegisterChain,
-    data: RegisterChainData,
+    payload: GovernancePayloadSetYieldStrategy,
 ) -> solitaire::Result<Instruction> {
-    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
     let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa);
-    let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
-        &EndpointDerivationData {
-            emitter_chain: payload.chain,
-            emitter_address: payload.endpoint_address,
-        },
+    let yield_strategy = YieldStrategy::<'_, { AccountState::MaybeInitialized }>::key(
+        &YieldStrategyDerivationData { mint: payload.mint },
         &program_id,
     );
 
@@ -414,20 +867,65 @@ pub fn register_chain(
         program_id,
         accounts: vec![
             AccountMeta::new(payer, true),
-            AccountMeta::new_readonly(config_key, false),
-            AccountMeta::new(endpoint, false),
             message_acc,
             claim_acc,
+            AccountMeta::new(yield_strategy, false),
             // Dependencies
-            AccountMeta::new(solana_program::sysvar::rent::id(), false),
-            AccountMeta::new(solana_program::system_program::id(), false),
-            // Program
-            AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
         ],
-        data: (crate::instruction::Instruction::RegisterChain, data).try_to_vec()?,
+        data: (
+            crate::instruction::Instruction::SetYieldStrategy,
+            SetYieldStrategyData {},
+        )
+            .try_to_vec()?,
     })
 }
 
+/// Accounts creating the recipient account of a transfer, see
+/// [`CompleteNativeData::recipient_owner`].
+fn recipient_owner_accounts(owner: Option<Pubkey>) -> Vec<AccountMeta> {
+    match owner {
+        Some(owner) => vec![
+            AccountMeta::new_readonly(owner, false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        ],
+        None => vec![],
+    }
+}
+
+fn split_accounts(transfer: &PayloadTransferWithPayload) -> Vec<AccountMeta> {
+    transfer
+        .split()
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+        .iter()
+        .map(|share| AccountMeta::new(share.token_account, false))
+        .collect()
+}
+
+fn transfer_cap(program_id: Pubkey, mint: Pubkey) -> AccountMeta {
+    let transfer_cap = TransferCap::<'_, { AccountState::MaybeInitialized }>::key(
+        &TransferCapDerivationData { mint },
+        &program_id,
+    );
+    AccountMeta::new_readonly(transfer_cap, false)
+}
+
+fn core_bridge_config(bridge_id: Pubkey) -> AccountMeta {
+    let bridge_config = Bridge::<'_, { AccountState::Initialized }>::key(None, &bridge_id);
+    AccountMeta::new_readonly(bridge_config, false)
+}
+
+fn relayer_allowlist(program_id: Pubkey, token_account: Pubkey) -> AccountMeta {
+    let allowlist = RelayerAllowlist::<'_, { AccountState::MaybeInitialized }>::key(
+        &RelayerAllowlistDerivationData { token_account },
+        &program_id,
+    );
+    AccountMeta::new_readonly(allowlist, false)
+}
+
 fn claimable_vaa(
     bridge_id: Pubkey,
     message_key: Pubkey,
@@ -468,6 +966,37 @@ fn claimable_vaa(
 /// | system_program   | system program                                                    | false  |
 /// | bridge_id        | bridge_id program                                                 | false  |
 /// | spl_token        | spl_token program                                                 | false  |
+pub fn set_relayer_allowlist(
+    program_id: Pubkey,
+    payer: Pubkey,
+    owner: Pubkey,
+    token_account: Pubkey,
+    relayers: Vec<Pubkey>,
+) -> solitaire::Result<Instruction> {
+    let allowlist = RelayerAllowlist::<'_, { AccountState::MaybeInitialized }>::key(
+        &RelayerAllowlistDerivationData { token_account },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(owner, true),
+            AccountMeta::new_readonly(token_account, false),
+            AccountMeta::new(allowlist, false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: (
+            crate::instruction::Instruction::SetRelayerAllowlist,
+            SetRelayerAllowlistData { relayers },
+        )
+            .try_to_vec()?,
+    })
+}
+
 pub fn transfer_native(
     program_id: Pubkey,
     bridge_id: Pubkey,
@@ -521,6 +1050,8 @@ pub fn transfer_native(
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            // Only read with the `transfer-cap` feature, and ignored otherwise.
+            transfer_cap(program_id, mint),
         ],
         data: (instruction, data).try_to_vec()?,
     })
@@ -607,6 +1138,8 @@ pub fn transfer_native_with_payload(
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            // Only read with the `transfer-cap` feature, and ignored otherwise.
+            transfer_cap(program_id, mint),
         ],
         data: (instruction, data).try_to_vec()?,
     })
@@ -698,6 +1231,8 @@ pub fn transfer_wrapped(
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            // Only read with the `transfer-cap` feature, and ignored otherwise.
+            transfer_cap(program_id, wrapped_mint_key),
         ],
         data: (instruction, data).try_to_vec()?,
     })
@@ -796,6 +1331,8 @@ pub fn transfer_wrapped_with_payload(
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            // Only read with the `transfer-cap` feature, and ignored otherwise.
+            transfer_cap(program_id, wrapped_mint_key),
         ],
         data: (instruction, data).try_to_vec()?,
     })
@@ -863,6 +1400,303 @@ pub fn attest(
     })
 }
 
+/// Attest `mint` and transfer it from `from`, posting the attestation to `attest_message_key` and
+/// the transfer to `message_key`. As with [`transfer_native`], the authority signer must be
+/// approved in the same transaction.
+#[allow(clippy::too_many_arguments)]
+pub fn attest_and_transfer_native(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    attest_message_key: Pubkey,
+    message_key: Pubkey,
+    from: Pubkey,
+    mint: Pubkey,
+    data: AttestAndTransferNativeData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let custody_key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &program_id,
+    );
+    let spl_metadata = SplTokenMeta::key(
+        &SplTokenMetaDerivationData { mint },
+        &spl_token_metadata::id(),
+    );
+    let mint_meta = WrappedTokenMeta::<'_, { AccountState::Uninitialized }>::key(
+        &WrappedMetaDerivationData { mint_key: mint },
+        &program_id,
+    );
+
+    let authority_signer_key = AuthoritySigner::key(None, &program_id);
+    let custody_signer_key = CustodySigner::key(None, &program_id);
+    let emitter_key = EmitterAccount::key(None, &program_id);
+
+    // Bridge keys
+    let bridge_config = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &bridge_id);
+    let sequence_key = Sequence::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter_key,
+        },
+        &bridge_id,
+    );
+    let fee_collector_key = FeeCollector::key(None, &bridge_id);
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key, false),
+            AccountMeta::new(from, false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new_readonly(mint_meta, false),
+            AccountMeta::new_readonly(spl_metadata, false),
+            AccountMeta::new(custody_key, false),
+            AccountMeta::new_readonly(authority_signer_key, false),
+            AccountMeta::new_readonly(custody_signer_key, false),
+            // Bridge accounts
+            AccountMeta::new(bridge_config, false),
+            AccountMeta::new(attest_message_key, true),
+            AccountMeta::new(message_key, true),
+            AccountMeta::new_readonly(emitter_key, false),
+            AccountMeta::new(sequence_key, false),
+            AccountMeta::new(fee_collector_key, false),
+            AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            // Program
+            AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            // Only read with the `transfer-cap` feature, and ignored otherwise.
+            transfer_cap(program_id, mint),
+        ],
+        data: (
+            crate::instruction::Instruction::AttestAndTransferNative,
+            data,
+        )
+            .try_to_vec()?,
+    })
+}
+
+/// `mints` are the native mints whose custody accounts are attested, in ascending order.
+pub fn attest_custody(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    mints: &[Pubkey],
+    nonce: u32,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let attestation_key =
+        CustodyAttestation::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
+    let emitter_key = EmitterAccount::key(None, &program_id);
+
+    // Bridge Keys
+    let bridge_config = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &bridge_id);
+    let sequence_key = Sequence::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter_key,
+        },
+        &bridge_id,
+    );
+    let fee_collector_key = FeeCollector::key(None, &bridge_id);
+
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(config_key, false),
+        AccountMeta::new(attestation_key, false),
+        // Bridge accounts
+        AccountMeta::new(bridge_config, false),
+        AccountMeta::new(message_key, true),
+        AccountMeta::new_readonly(emitter_key, false),
+        AccountMeta::new(sequence_key, false),
+        AccountMeta::new(fee_collector_key, false),
+        AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
+        // Dependencies
+        AccountMeta::new(solana_program::sysvar::rent::id(), false),
+        AccountMeta::new(solana_program::system_program::id(), false),
+        // Program
+        AccountMeta::new_readonly(bridge_id, false),
+    ];
+    for mint in mints {
+        let custody_key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+            &CustodyAccountDerivationData { mint: *mint },
+            &program_id,
+        );
+        let yield_strategy_key = YieldStrategy::<'_, { AccountState::MaybeInitialized }>::key(
+            &YieldStrategyDerivationData { mint: *mint },
+            &program_id,
+        );
+        accounts.push(AccountMeta::new_readonly(*mint, false));
+        accounts.push(AccountMeta::new_readonly(custody_key, false));
+        // Empty unless governance set a yield strategy for the mint.
+        accounts.push(AccountMeta::new_readonly(yield_strategy_key, false));
+    }
+
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data: (
+            crate::instruction::Instruction::AttestCustody,
+            AttestCustodyData {
+                nonce,
+                custodies: mints.len() as u8,
+            },
+        )
+            .try_to_vec()?,
+    })
+}
+
+/// Lend `amount` of the custody of `mint` to `to`, a token account of the yield strategy
+/// `strategy`. The `["borrower"]` PDA of the strategy signs, so this is invoked by the strategy
+/// program.
+pub fn lend_custody(
+    program_id: Pubkey,
+    strategy: Pubkey,
+    mint: Pubkey,
+    to: Pubkey,
+    amount: u64,
+) -> solitaire::Result<Instruction> {
+    let yield_strategy = YieldStrategy::<'_, { AccountState::Initialized }>::key(
+        &YieldStrategyDerivationData { mint },
+        &program_id,
+    );
+    let custody_key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &program_id,
+    );
+    let custody_signer_key = CustodySigner::key(None, &program_id);
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(yield_strategy, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new(custody_key, false),
+            AccountMeta::new_readonly(custody_signer_key, false),
+            AccountMeta::new_readonly(crate::accounts::borrower(&strategy), true),
+            AccountMeta::new(to, false),
+            // Program
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: (
+            crate::instruction::Instruction::LendCustody,
+            LendCustodyData { amount },
+        )
+            .try_to_vec()?,
+    })
+}
+
+/// Repay `amount` lent from the custody of `mint`, transferred from `from` by `from_owner`.
+pub fn repay_custody(
+    program_id: Pubkey,
+    mint: Pubkey,
+    from: Pubkey,
+    from_owner: Pubkey,
+    amount: u64,
+) -> solitaire::Result<Instruction> {
+    let yield_strategy = YieldStrategy::<'_, { AccountState::Initialized }>::key(
+        &YieldStrategyDerivationData { mint },
+        &program_id,
+    );
+    let custody_key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(yield_strategy, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new(custody_key, false),
+            AccountMeta::new(from, false),
+            AccountMeta::new_readonly(from_owner, true),
+            // Program
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: (
+            crate::instruction::Instruction::RepayCustody,
+            RepayCustodyData { amount },
+        )
+            .try_to_vec()?,
+    })
+}
+
+/// Redeem the transfers in `vaas`, each as the key of its posted VAA and its body, to `to` in a
+/// single instruction, see [`crate::api::complete_transfers_batch`]. The transfers all send the
+/// native `mint`, and `to` is created as the associated token account of `recipient_owner` if
+/// it is passed.
+#[allow(clippy::too_many_arguments)]
+pub fn complete_transfers_batch(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    vaas: &[(Pubkey, PostVAAData)],
+    to: Pubkey,
+    fee_recipient: Option<Pubkey>,
+    mint: Pubkey,
+    recipient_owner: Option<Pubkey>,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let custody_key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &program_id,
+    );
+    let custody_signer_key = CustodySigner::key(None, &program_id);
+
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(config_key, false),
+        AccountMeta::new(to, false),
+        AccountMeta::new(fee_recipient.unwrap_or(to), false),
+        AccountMeta::new(custody_key, false),
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new_readonly(custody_signer_key, false),
+        // Dependencies
+        AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        // Program
+        AccountMeta::new_readonly(bridge_id, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        // Only read with the `transfer-cap` feature, and ignored otherwise.
+        transfer_cap(program_id, mint),
+        // Only read with the `inbound-pause` feature, and ignored otherwise.
+        core_bridge_config(bridge_id),
+        // Only read with the `relayer-allowlist` feature, and ignored otherwise.
+        relayer_allowlist(program_id, to),
+    ];
+    accounts.extend(recipient_owner_accounts(recipient_owner));
+    for (message_key, vaa) in vaas {
+        let (message_acc, claim_acc) = claimable_vaa(program_id, *message_key, vaa.clone());
+        let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
+            &EndpointDerivationData {
+                emitter_chain: vaa.emitter_chain,
+                emitter_address: vaa.emitter_address,
+            },
+            &program_id,
+        );
+        accounts.push(message_acc);
+        accounts.push(claim_acc);
+        accounts.push(AccountMeta::new_readonly(endpoint, false));
+    }
+
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data: (
+            crate::instruction::Instruction::CompleteTransfersBatch,
+            CompleteTransfersBatchData {
+                transfers: vaas.len() as u8,
+                recipient_owner,
+            },
+        )
+            .try_to_vec()?,
+    })
+}
+
 pub fn upgrade_contract(
     program_id: Pubkey,
     payer: Pubkey,
@@ -881,7 +1715,8 @@ pub fn upgrade_contract(
         &program_id,
     );
 
-    let (upgrade_authority, _) = Pubkey::find_program_address(&["upgrade".as_bytes()], &program_id);
+    let (upgrade_authority, _) =
+        Pubkey::find_program_address(&[seeds::UPGRADE.as_bytes()], &program_id);
 
     let (program_data, _) = Pubkey::find_program_address(
         &[program_id.as_ref()],
@@ -914,3 +1749,48 @@ pub fn upgrade_contract(
             .unwrap(),
     }
 }
+
+/// Route a native token instruction to the token program of its mint, for mints owned by
+/// Token-2022. Builders pass the original token program.
+pub fn with_token_program(mut instruction: Instruction, token_program: Pubkey) -> Instruction {
+    for meta in instruction.accounts.iter_mut() {
+        if meta.pubkey == spl_token::id() {
+            meta.pubkey = token_program;
+        }
+    }
+    instruction
+}
+
+/// Compute units an instruction built by this module consumes at most, including its CPIs into
+/// the core bridge, SPL token and token metadata programs. Meant to be requested as the compute
+/// unit limit of the transaction rather than paying priority fees on the runtime default. `None`
+/// for data this program doesn't dispatch.
+pub fn compute_unit_limit(instruction: &Instruction) -> Option<u32> {
+    use crate::instruction::Instruction::*;
+
+    let limit = match crate::instruction::Instruction::deserialize(&mut &instruction.data[..]) {
+        Ok(Initialize) => 50_000,
+        Ok(AttestToken) => 120_000,
+        Ok(CompleteNative) | Ok(CompleteNativeWithPayload) => 100_000,
+        Ok(CompleteWrapped) | Ok(CompleteWrappedWithPayload) => 100_000,
+        Ok(TransferNative) | Ok(TransferNativeWithPayload) => 120_000,
+        Ok(TransferWrapped) | Ok(TransferWrappedWithPayload) => 120_000,
+        Ok(RegisterChain) => 60_000,
+        Ok(CreateWrapped) => 200_000,
+        Ok(UpgradeContract) => 200_000,
+        Ok(ResolveTokenId) | Ok(ProbeRedemption) => 30_000,
+        Ok(ReclaimExpiredTransfer) => 100_000,
+        Ok(SetMetadataAuthority) => 50_000,
+        Ok(RotateMetadataAuthority) => 100_000,
+        Ok(SetTransferCap) | Ok(OverrideTransferCap) => 50_000,
+        Ok(SetRelayerAllowlist) => 30_000,
+        Ok(AttestCustody) => 150_000,
+        Ok(AttestAndTransferNative) => 240_000,
+        Ok(SetYieldStrategy) => 50_000,
+        Ok(LendCustody) | Ok(RepayCustody) => 40_000,
+        Ok(CompleteTransfersBatch) => 400_000,
+        Ok(RecoverChainId) => 50_000,
+        Err(_) => return None,
+    };
+    Some(limit)
+}
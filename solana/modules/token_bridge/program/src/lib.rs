@@ -16,30 +16,53 @@ extern crate wasm_bindgen;
 pub mod wasm;
 
 pub mod accounts;
+pub mod amount;
 pub mod api;
 pub mod messages;
+pub mod token_interface;
 pub mod types;
 
 pub use api::{
+    attest_and_transfer_native,
+    attest_custody,
     attest_token,
     complete_native,
     complete_native_with_payload,
+    complete_transfers_batch,
     complete_wrapped,
     complete_wrapped_with_payload,
     create_wrapped,
     initialize,
+    lend_custody,
+    override_transfer_cap,
+    probe_redemption,
+    reclaim_expired_transfer,
+    recover_chain_id,
     register_chain,
+    repay_custody,
+    resolve_token_id,
+    rotate_metadata_authority,
+    set_metadata_authority,
+    set_relayer_allowlist,
+    set_transfer_cap,
+    set_yield_strategy,
     transfer_native,
     transfer_native_with_payload,
     transfer_wrapped,
     transfer_wrapped_with_payload,
     upgrade_contract,
+    AttestAndTransferNative,
+    AttestAndTransferNativeData,
+    AttestCustody,
+    AttestCustodyData,
     AttestToken,
     AttestTokenData,
     CompleteNative,
     CompleteNativeData,
     CompleteNativeWithPayload,
     CompleteNativeWithPayloadData,
+    CompleteTransfersBatch,
+    CompleteTransfersBatchData,
     CompleteWrapped,
     CompleteWrappedData,
     CompleteWrappedWithPayload,
@@ -48,8 +71,32 @@ pub use api::{
     CreateWrappedData,
     Initialize,
     InitializeData,
+    LendCustody,
+    LendCustodyData,
+    OverrideTransferCap,
+    OverrideTransferCapData,
+    ProbeRedemption,
+    ProbeRedemptionData,
+    ReclaimExpiredTransfer,
+    ReclaimExpiredTransferData,
+    RecoverChainId,
+    RecoverChainIdData,
     RegisterChain,
     RegisterChainData,
+    RepayCustody,
+    RepayCustodyData,
+    ResolveTokenId,
+    ResolveTokenIdData,
+    RotateMetadataAuthority,
+    RotateMetadataAuthorityData,
+    SetMetadataAuthority,
+    SetMetadataAuthorityData,
+    SetRelayerAllowlist,
+    SetRelayerAllowlistData,
+    SetTransferCap,
+    SetTransferCapData,
+    SetYieldStrategy,
+    SetYieldStrategyData,
     TransferNative,
     TransferNativeData,
     TransferNativeWithPayload,
@@ -91,6 +138,30 @@ pub enum TokenBridgeError {
     InvalidVAA,
     NonexistentTokenMetadataAccount,
     NotMetadataV1Account,
+    TransferExpired,
+    TransferNotExpired,
+    FrozenCustodyAccount,
+    FrozenTokenAccount,
+    InvalidRedeemer,
+    MetadataAuthorityRotated,
+    MissingTransferCap,
+    TransferCapExceeded,
+    InvalidSplitPayload,
+    MissingRelayerAllowlist,
+    RelayerNotAllowlisted,
+    TooManyRelayers,
+    UnsupportedTokenProgram,
+    UnsupportedTokenExtension,
+    InvalidAssociatedAccount,
+    InvalidCustodyAttestation,
+    BridgePaused,
+    InvalidYieldStrategy,
+    YieldStrategyInUse,
+    InvalidBorrower,
+    YieldCapExceeded,
+    RepayExceedsLent,
+    NotAFork,
+    MissingBridgeConfig,
 }
 
 impl From<TokenBridgeError> for SolitaireError {
@@ -113,4 +184,19 @@ solitaire! {
     CompleteWrappedWithPayload => complete_wrapped_with_payload,
     TransferWrappedWithPayload => transfer_wrapped_with_payload,
     TransferNativeWithPayload => transfer_native_with_payload,
+    ResolveTokenId => resolve_token_id,
+    ReclaimExpiredTransfer => reclaim_expired_transfer,
+    ProbeRedemption => probe_redemption,
+    SetMetadataAuthority => set_metadata_authority,
+    RotateMetadataAuthority => rotate_metadata_authority,
+    SetTransferCap => set_transfer_cap,
+    OverrideTransferCap => override_transfer_cap,
+    SetRelayerAllowlist => set_relayer_allowlist,
+    AttestCustody => attest_custody,
+    AttestAndTransferNative => attest_and_transfer_native,
+    SetYieldStrategy => set_yield_strategy,
+    LendCustody => lend_custody,
+    RepayCustody => repay_custody,
+    CompleteTransfersBatch => complete_transfers_batch,
+    RecoverChainId => recover_chain_id,
 }
@@ -1,20 +1,34 @@
-use crate::types::{
-    Address,
-    ChainID,
+use crate::{
+    types::{
+        Address,
+        ChainID,
+    },
+    TokenBridgeError::InvalidSplitPayload,
 };
 use bridge::{
+    codec::{
+        ReadBytes,
+        WriteBytes,
+    },
     vaa::{
+        deserialize_decree,
         DeserializePayload,
         SerializePayload,
     },
+    wormhole_vaa::{
+        governance::{
+            Decree as GovernanceDecree,
+            GovernancePayload,
+        },
+        GovernanceHeader,
+    },
+    CoreBridgeError::{
+        InvalidGovernanceAction,
+        InvalidGovernanceModule,
+    },
     DeserializeGovernancePayload,
     SerializeGovernancePayload,
 };
-use byteorder::{
-    BigEndian,
-    ReadBytesExt,
-    WriteBytesExt,
-};
 use primitive_types::U256;
 use solana_program::{
     program_error::ProgramError::InvalidAccountData,
@@ -29,6 +43,12 @@ use std::{
         Write,
     },
 };
+use wormhole_governance::{
+    module,
+    module_id,
+    Decree,
+    RegisterChain,
+};
 
 #[derive(PartialEq, Debug, Clone)]
 pub struct PayloadTransfer {
@@ -54,26 +74,25 @@ impl DeserializePayload for PayloadTransfer {
             return Err(SolitaireError::Custom(0));
         };
 
-        let mut am_data: [u8; 32] = [0; 32];
-        v.read_exact(&mut am_data)?;
-        let amount = U256::from_big_endian(&am_data);
+        let amount = v.read_u256_be()?;
 
         let mut token_address = Address::default();
         v.read_exact(&mut token_address)?;
 
-        let token_chain = v.read_u16::<BigEndian>()?;
+        let token_chain = v.read_u16_be()?;
 
         let mut to = Address::default();
         v.read_exact(&mut to)?;
 
-        let to_chain = v.read_u16::<BigEndian>()?;
+        let to_chain = v.read_u16_be()?;
 
-        let mut fee_data: [u8; 32] = [0; 32];
-        v.read_exact(&mut fee_data)?;
-        let fee = U256::from_big_endian(&fee_data);
+        let fee = v.read_u256_be()?;
 
-        if v.position() != v.into_inner().len() as u64 {
-            return Err(InvalidAccountData.into());
+        // The only extension a transfer can carry is a burn receipt, read with
+        // [`PayloadTransfer::burn_receipt`].
+        let mut rest = &v.get_ref()[v.position() as usize..];
+        if !rest.is_empty() {
+            BurnReceipt::deserialize(&mut rest)?;
         }
 
         Ok(PayloadTransfer {
@@ -92,23 +111,98 @@ impl SerializePayload for PayloadTransfer {
         // Payload ID
         writer.write_u8(1)?;
 
-        let mut am_data: [u8; 32] = [0; 32];
-        self.amount.to_big_endian(&mut am_data);
-        writer.write_all(&am_data)?;
+        writer.write_u256_be(self.amount)?;
 
         writer.write_all(&self.token_address)?;
-        writer.write_u16::<BigEndian>(self.token_chain)?;
+        writer.write_u16_be(self.token_chain)?;
         writer.write_all(&self.to)?;
-        writer.write_u16::<BigEndian>(self.to_chain)?;
+        writer.write_u16_be(self.to_chain)?;
 
-        let mut fee_data: [u8; 32] = [0; 32];
-        self.fee.to_big_endian(&mut fee_data);
-        writer.write_all(&fee_data)?;
+        writer.write_u256_be(self.fee)?;
 
         Ok(())
     }
 }
 
+impl PayloadTransfer {
+    /// Length of a serialized transfer without extensions.
+    pub const LEN: usize = 133;
+
+    /// Burn receipt appended to the serialized transfer `data`, if the transfer carries one.
+    pub fn burn_receipt(data: &[u8]) -> Option<BurnReceipt> {
+        let mut rest = data.get(Self::LEN..).filter(|rest| !rest.is_empty())?;
+        BurnReceipt::deserialize(&mut rest).ok()
+    }
+}
+
+/// Prefix of a [`BurnReceipt`] extension.
+pub const BURN_RECEIPT_MAGIC: [u8; 4] = *b"BURN";
+
+/// Extension of a transfer of wrapped tokens, recording what was burned on Solana so that supply
+/// can be reconciled on the target chain from the VAA alone. Serialized after the transfer as the
+/// magic, the origin chain and address of the asset, the burned mint, and the big-endian burn
+/// amount and slot.
+#[derive(PartialEq, Debug, Clone)]
+pub struct BurnReceipt {
+    /// Chain ID of the token
+    pub token_chain: ChainID,
+    /// Address of the token on its origin chain
+    pub token_address: Address,
+    /// Wrapped mint the tokens were burned from
+    pub mint: Pubkey,
+    /// Amount burned, in the decimals of the wrapped mint
+    pub amount: u64,
+    /// Slot the tokens were burned in
+    pub slot: u64,
+}
+
+impl DeserializePayload for BurnReceipt {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut v = Cursor::new(buf);
+
+        let mut magic = [0u8; 4];
+        v.read_exact(&mut magic)?;
+        if magic != BURN_RECEIPT_MAGIC {
+            return Err(InvalidAccountData.into());
+        }
+
+        let token_chain = v.read_u16_be()?;
+
+        let mut token_address = Address::default();
+        v.read_exact(&mut token_address)?;
+
+        let mut mint = [0u8; 32];
+        v.read_exact(&mut mint)?;
+
+        let amount = v.read_u64_be()?;
+        let slot = v.read_u64_be()?;
+
+        if v.position() != v.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(BurnReceipt {
+            token_chain,
+            token_address,
+            mint: Pubkey::new_from_array(mint),
+            amount,
+            slot,
+        })
+    }
+}
+
+impl SerializePayload for BurnReceipt {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), SolitaireError> {
+        writer.write_all(&BURN_RECEIPT_MAGIC)?;
+        writer.write_u16_be(self.token_chain)?;
+        writer.write_all(&self.token_address)?;
+        writer.write_all(self.mint.as_ref())?;
+        writer.write_u64_be(self.amount)?;
+        writer.write_u64_be(self.slot)?;
+        Ok(())
+    }
+}
+
 impl DeserializePayload for PayloadTransferWithPayload {
     fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
         let mut v = Cursor::new(buf);
@@ -117,19 +211,17 @@ impl DeserializePayload for PayloadTransferWithPayload {
             return Err(SolitaireError::Custom(0));
         };
 
-        let mut am_data: [u8; 32] = [0; 32];
-        v.read_exact(&mut am_data)?;
-        let amount = U256::from_big_endian(&am_data);
+        let amount = v.read_u256_be()?;
 
         let mut token_address = Address::default();
         v.read_exact(&mut token_address)?;
 
-        let token_chain = v.read_u16::<BigEndian>()?;
+        let token_chain = v.read_u16_be()?;
 
         let mut to = Address::default();
         v.read_exact(&mut to)?;
 
-        let to_chain = v.read_u16::<BigEndian>()?;
+        let to_chain = v.read_u16_be()?;
 
         let mut from_address = Address::default();
         v.read_exact(&mut from_address)?;
@@ -154,14 +246,12 @@ impl SerializePayload for PayloadTransferWithPayload {
         // Payload ID
         writer.write_u8(3)?;
 
-        let mut am_data: [u8; 32] = [0; 32];
-        self.amount.to_big_endian(&mut am_data);
-        writer.write_all(&am_data)?;
+        writer.write_u256_be(self.amount)?;
 
         writer.write_all(&self.token_address)?;
-        writer.write_u16::<BigEndian>(self.token_chain)?;
+        writer.write_u16_be(self.token_chain)?;
         writer.write_all(&self.to)?;
-        writer.write_u16::<BigEndian>(self.to_chain)?;
+        writer.write_u16_be(self.to_chain)?;
 
         writer.write_all(&self.from_address)?;
 
@@ -189,6 +279,105 @@ pub struct PayloadTransferWithPayload {
     pub payload: Vec<u8>,
 }
 
+/// Prefix of a transfer payload that carries an expiration. It is followed by the expiration as a
+/// big-endian unix timestamp and the actual payload.
+pub const EXPIRING_PAYLOAD_MAGIC: [u8; 8] = *b"EXPIRING";
+
+impl PayloadTransferWithPayload {
+    /// Wrap `payload` so that the transfer can no longer be completed at or after `expiration`.
+    pub fn expiring_payload(expiration: u64, payload: &[u8]) -> Vec<u8> {
+        let mut data = Vec::with_capacity(EXPIRING_PAYLOAD_MAGIC.len() + 8 + payload.len());
+        data.extend_from_slice(&EXPIRING_PAYLOAD_MAGIC);
+        data.extend_from_slice(&expiration.to_be_bytes());
+        data.extend_from_slice(payload);
+        data
+    }
+
+    /// Expiration embedded in the payload, if the sender attached one.
+    pub fn expiration(&self) -> Option<u64> {
+        let rest = self.payload.strip_prefix(&EXPIRING_PAYLOAD_MAGIC[..])?;
+        (&rest[..]).read_u64_be().ok()
+    }
+
+    /// Whether the transfer has expired at the unix timestamp `now`.
+    pub fn is_expired(&self, now: i64) -> bool {
+        match self.expiration() {
+            Some(expiration) => now >= 0 && now as u64 >= expiration,
+            None => false,
+        }
+    }
+
+    /// Wrap `payload` so that redeeming the transfer splits the amount among `shares`.
+    pub fn split_payload(shares: &[SplitShare], payload: &[u8]) -> Vec<u8> {
+        let mut data = Vec::with_capacity(
+            SPLIT_PAYLOAD_MAGIC.len() + 1 + shares.len() * SplitShare::LEN + payload.len(),
+        );
+        data.extend_from_slice(&SPLIT_PAYLOAD_MAGIC);
+        data.push(shares.len() as u8);
+        for share in shares {
+            data.extend_from_slice(share.token_account.as_ref());
+            data.extend_from_slice(&share.bps.to_be_bytes());
+        }
+        data.extend_from_slice(payload);
+        data
+    }
+
+    /// Shares of the amount embedded in the payload, if the sender split the transfer. The split
+    /// follows the expiration of expiring payloads. Payloads with the split prefix but shares
+    /// that don't add up to [`SPLIT_TOTAL_BPS`] are rejected, rather than redeemed unsplit.
+    pub fn split(&self) -> Result<Option<Vec<SplitShare>>, SolitaireError> {
+        let payload = match self.payload.strip_prefix(&EXPIRING_PAYLOAD_MAGIC[..]) {
+            Some(rest) => rest.get(8..).unwrap_or_default(),
+            None => &self.payload[..],
+        };
+        let mut rest = match payload.strip_prefix(&SPLIT_PAYLOAD_MAGIC[..]) {
+            Some(rest) => rest,
+            None => return Ok(None),
+        };
+
+        let invalid = |_| SolitaireError::from(InvalidSplitPayload);
+        let count = rest.read_u8().map_err(invalid)? as usize;
+        if count == 0 || count > MAX_SPLIT_SHARES {
+            return Err(InvalidSplitPayload.into());
+        }
+        let mut shares = Vec::with_capacity(count);
+        for _ in 0..count {
+            shares.push(SplitShare {
+                token_account: Pubkey::new_from_array(rest.read_array().map_err(invalid)?),
+                bps: rest.read_u16_be().map_err(invalid)?,
+            });
+        }
+        if shares.iter().map(|share| share.bps as u32).sum::<u32>() != SPLIT_TOTAL_BPS as u32 {
+            return Err(InvalidSplitPayload.into());
+        }
+        Ok(Some(shares))
+    }
+}
+
+/// Prefix of a transfer payload that splits the redeemed amount among several token accounts,
+/// such as for payroll or revenue sharing. It is followed by the number of shares, each share as
+/// the token account and its basis points, and the actual payload. Expiring payloads carry it
+/// after their expiration.
+pub const SPLIT_PAYLOAD_MAGIC: [u8; 8] = *b"SPLITPAY";
+
+/// Basis points the shares of a split transfer add up to.
+pub const SPLIT_TOTAL_BPS: u16 = 10_000;
+
+/// Most shares a transfer can be split into, bounded by the accounts a redemption can take.
+pub const MAX_SPLIT_SHARES: usize = 8;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct SplitShare {
+    /// Token account of the mint redeemed into
+    pub token_account: Pubkey,
+    /// Share of the amount in basis points
+    pub bps: u16,
+}
+
+impl SplitShare {
+    pub const LEN: usize = 32 + 2;
+}
+
 #[derive(PartialEq, Debug)]
 pub struct PayloadAssetMeta {
     /// Address of the token. Left-zero-padded if shorter than 32 bytes
@@ -216,7 +405,7 @@ impl DeserializePayload for PayloadAssetMeta {
         let mut token_address = Address::default();
         v.read_exact(&mut token_address)?;
 
-        let token_chain = v.read_u16::<BigEndian>()?;
+        let token_chain = v.read_u16_be()?;
         let decimals = v.read_u8()?;
 
         let mut symbol_data = vec![0u8; 32];
@@ -253,7 +442,7 @@ impl SerializePayload for PayloadAssetMeta {
         writer.write_u8(2)?;
 
         writer.write_all(&self.token_address)?;
-        writer.write_u16::<BigEndian>(self.token_chain)?;
+        writer.write_u16_be(self.token_chain)?;
 
         writer.write_u8(self.decimals)?;
 
@@ -273,6 +462,99 @@ impl SerializePayload for PayloadAssetMeta {
     }
 }
 
+/// Most custody accounts a single attestation lists, bounded by the accounts a transaction can
+/// take, three per custody.
+pub const MAX_ATTESTED_CUSTODIES: usize = 6;
+
+/// Balances of native tokens in custody, posted by [`crate::api::attest_custody`] so that target
+/// chains can check the wrapped supply of each asset against the collateral backing it. Each
+/// attestation carries the hash of the previous one, consumers skipping or replaying attestations
+/// break the chain.
+#[derive(PartialEq, Debug, Clone)]
+pub struct PayloadCustodyAttestation {
+    /// Number of the attestation, counting from zero
+    pub index: u64,
+    /// Slot the balances were read in
+    pub slot: u64,
+    /// Keccak256 hash of the previous attestation payload, zero for the first attestation
+    pub previous_hash: [u8; 32],
+    /// Balances in ascending order of their mint
+    pub custodies: Vec<CustodyBalance>,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct CustodyBalance {
+    /// Address of the native mint
+    pub token_address: Address,
+    /// Number of decimals of the mint
+    pub decimals: u8,
+    /// Amount in custody or lent to its yield strategy (big-endian uint256), truncated to 8
+    /// decimals as transfers are
+    pub amount: U256,
+}
+
+impl DeserializePayload for PayloadCustodyAttestation {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut v = Cursor::new(buf);
+
+        if v.read_u8()? != 4 {
+            return Err(SolitaireError::Custom(0));
+        };
+
+        let index = v.read_u64_be()?;
+        let slot = v.read_u64_be()?;
+
+        let mut previous_hash = [0u8; 32];
+        v.read_exact(&mut previous_hash)?;
+
+        let count = v.read_u8()? as usize;
+        if count > MAX_ATTESTED_CUSTODIES {
+            return Err(InvalidAccountData.into());
+        }
+        let mut custodies = Vec::with_capacity(count);
+        for _ in 0..count {
+            let mut token_address = Address::default();
+            v.read_exact(&mut token_address)?;
+            custodies.push(CustodyBalance {
+                token_address,
+                decimals: v.read_u8()?,
+                amount: v.read_u256_be()?,
+            });
+        }
+
+        if v.position() != v.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(PayloadCustodyAttestation {
+            index,
+            slot,
+            previous_hash,
+            custodies,
+        })
+    }
+}
+
+impl SerializePayload for PayloadCustodyAttestation {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), SolitaireError> {
+        // Payload ID
+        writer.write_u8(4)?;
+
+        writer.write_u64_be(self.index)?;
+        writer.write_u64_be(self.slot)?;
+        writer.write_all(&self.previous_hash)?;
+
+        writer.write_u8(self.custodies.len() as u8)?;
+        for custody in &self.custodies {
+            writer.write_all(&custody.token_address)?;
+            writer.write_u8(custody.decimals)?;
+            writer.write_u256_be(custody.amount)?;
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(PartialEq, Debug)]
 pub struct PayloadGovernanceRegisterChain {
     /// Chain ID of the chain to be registered
@@ -282,8 +564,8 @@ pub struct PayloadGovernanceRegisterChain {
 }
 
 impl SerializeGovernancePayload for PayloadGovernanceRegisterChain {
-    const MODULE: &'static str = "TokenBridge";
-    const ACTION: u8 = 1;
+    const MODULE: &'static str = module::TOKEN_BRIDGE;
+    const ACTION: u8 = RegisterChain::ACTION;
 }
 
 impl DeserializeGovernancePayload for PayloadGovernanceRegisterChain {
@@ -294,17 +576,10 @@ where
     Self: DeserializeGovernancePayload,
 {
     fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
-        let mut v = Cursor::new(buf);
-        Self::check_governance_header(&mut v)?;
-
-        let chain = v.read_u16::<BigEndian>()?;
-        let mut endpoint_address = [0u8; 32];
-        v.read_exact(&mut endpoint_address)?;
-
-        if v.position() != v.into_inner().len() as u64 {
-            return Err(InvalidAccountData.into());
-        }
-
+        let RegisterChain {
+            chain,
+            endpoint_address,
+        } = deserialize_decree::<Self, _>(buf)?;
         Ok(PayloadGovernanceRegisterChain {
             chain,
             endpoint_address,
@@ -318,9 +593,11 @@ where
 {
     fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), SolitaireError> {
         self.write_governance_header(writer)?;
-        // Payload ID
-        writer.write_u16::<BigEndian>(self.chain)?;
-        writer.write_all(&self.endpoint_address[..])?;
+        RegisterChain {
+            chain: self.chain,
+            endpoint_address: self.endpoint_address,
+        }
+        .encode(writer)?;
 
         Ok(())
     }
@@ -369,15 +646,294 @@ impl SerializeGovernancePayload for GovernancePayloadUpgrade {
 impl DeserializeGovernancePayload for GovernancePayloadUpgrade {
 }
 
+/// Decree assigning a new chain ID to a forked deployment of the token bridge. Other chains name the
+/// fork by its EVM chain ID, forks on Solana are deployments under a new program ID, which is what
+/// the decree names in the same 32 bytes. Like everywhere, the EVM chain ID takes the place of the
+/// target chain, see `wormhole_vaa::governance::RecoverChainId`.
+#[derive(PartialEq, Debug)]
+pub struct GovernancePayloadRecoverChainId {
+    /// Program ID of the forked deployment.
+    pub program: Pubkey,
+
+    /// Chain ID the fork uses from then on.
+    pub new_chain_id: ChainID,
+}
+
+impl SerializePayload for GovernancePayloadRecoverChainId {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_all(&module_id(Self::MODULE))?;
+        v.write_u8(Self::ACTION)?;
+        v.write_all(&self.program.to_bytes())?;
+        v.write_u16_be(self.new_chain_id)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadRecoverChainId
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        // The header reads the start of the EVM chain ID as its target chain, which is ignored.
+        let header = GovernanceHeader::parse(buf).map_err(|_| InvalidAccountData)?;
+        if !header.is_module(Self::MODULE) {
+            return Err(InvalidGovernanceModule.into());
+        }
+        if header.action != Self::ACTION {
+            return Err(InvalidGovernanceAction.into());
+        }
+
+        match GovernancePayload::parse(buf)
+            .map_err(|_| InvalidAccountData)?
+            .decree
+        {
+            GovernanceDecree::RecoverChainId(recover) => Ok(GovernancePayloadRecoverChainId {
+                program: Pubkey::new_from_array(*recover.evm_chain_id),
+                new_chain_id: recover.new_chain_id,
+            }),
+            _ => Err(InvalidGovernanceAction.into()),
+        }
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadRecoverChainId {
+    const MODULE: &'static str = "TokenBridge";
+    const ACTION: u8 = 3;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadRecoverChainId {
+}
+
+/// Decree rotating the metadata update authority of wrapped mints away from the mint signer.
+#[derive(PartialEq, Debug)]
+pub struct GovernancePayloadSetMetadataAuthority {
+    /// New update authority of the metadata.
+    pub new_authority: Pubkey,
+
+    /// Wrapped mint whose authority is rotated, or all wrapped mints if unset (zero on the wire).
+    pub mint: Option<Pubkey>,
+}
+
+impl SerializePayload for GovernancePayloadSetMetadataAuthority {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        self.write_governance_header(v)?;
+        v.write_all(&self.new_authority.to_bytes())?;
+        v.write_all(&self.mint.unwrap_or_default().to_bytes())?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetMetadataAuthority
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let mut new_authority = [0u8; 32];
+        c.read_exact(&mut new_authority)?;
+        let mut mint = [0u8; 32];
+        c.read_exact(&mut mint)?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        let mint = Pubkey::new(&mint[..]);
+        Ok(GovernancePayloadSetMetadataAuthority {
+            new_authority: Pubkey::new(&new_authority[..]),
+            mint: (mint != Pubkey::default()).then(|| mint),
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetMetadataAuthority {
+    const MODULE: &'static str = "TokenBridge";
+    const ACTION: u8 = 4;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetMetadataAuthority {
+}
+
+/// Cap on the amount of a mint, in its own units, a single transfer may move in or out of the
+/// token bridge. Setting it to `u64::MAX` lifts the cap.
+#[derive(PartialEq, Debug)]
+pub struct GovernancePayloadSetTransferCap {
+    pub mint: Pubkey,
+    pub max_amount: u64,
+}
+
+impl SerializePayload for GovernancePayloadSetTransferCap {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        self.write_governance_header(v)?;
+        v.write_all(&self.mint.to_bytes())?;
+        v.write_u64_be(self.max_amount)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetTransferCap
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let mut mint = [0u8; 32];
+        c.read_exact(&mut mint)?;
+        let max_amount = c.read_u64_be()?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetTransferCap {
+            mint: Pubkey::new(&mint[..]),
+            max_amount,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetTransferCap {
+    const MODULE: &'static str = "TokenBridge";
+    const ACTION: u8 = 5;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetTransferCap {
+}
+
+/// Emergency decree replacing the transfer cap of a mint until `until` (unix seconds), tightening
+/// it while an incident is investigated or lifting it for a transfer governance approved.
+#[derive(PartialEq, Debug)]
+pub struct GovernancePayloadOverrideTransferCap {
+    pub mint: Pubkey,
+    pub max_amount: u64,
+    pub until: u64,
+}
+
+impl SerializePayload for GovernancePayloadOverrideTransferCap {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        self.write_governance_header(v)?;
+        v.write_all(&self.mint.to_bytes())?;
+        v.write_u64_be(self.max_amount)?;
+        v.write_u64_be(self.until)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadOverrideTransferCap
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let mut mint = [0u8; 32];
+        c.read_exact(&mut mint)?;
+        let max_amount = c.read_u64_be()?;
+        let until = c.read_u64_be()?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadOverrideTransferCap {
+            mint: Pubkey::new(&mint[..]),
+            max_amount,
+            until,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadOverrideTransferCap {
+    const MODULE: &'static str = "TokenBridge";
+    const ACTION: u8 = 6;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadOverrideTransferCap {
+}
+
+/// Strategy allowed to borrow idle custody of a native mint, capped at `max_amount` in units of
+/// the mint and `max_share_bps` of its bridged supply. Zero caps stop further borrowing while the
+/// strategy repays.
+#[derive(PartialEq, Debug)]
+pub struct GovernancePayloadSetYieldStrategy {
+    pub mint: Pubkey,
+    pub strategy: Pubkey,
+    pub max_amount: u64,
+    pub max_share_bps: u16,
+}
+
+impl SerializePayload for GovernancePayloadSetYieldStrategy {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        self.write_governance_header(v)?;
+        v.write_all(&self.mint.to_bytes())?;
+        v.write_all(&self.strategy.to_bytes())?;
+        v.write_u64_be(self.max_amount)?;
+        v.write_u16_be(self.max_share_bps)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetYieldStrategy
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let mut mint = [0u8; 32];
+        c.read_exact(&mut mint)?;
+        let mut strategy = [0u8; 32];
+        c.read_exact(&mut strategy)?;
+        let max_amount = c.read_u64_be()?;
+        let max_share_bps = c.read_u16_be()?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetYieldStrategy {
+            mint: Pubkey::new(&mint[..]),
+            strategy: Pubkey::new(&strategy[..]),
+            max_amount,
+            max_share_bps,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetYieldStrategy {
+    const MODULE: &'static str = "TokenBridge";
+    const ACTION: u8 = 7;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetYieldStrategy {
+}
+
 #[cfg(test)]
 #[allow(unused_imports)]
 mod tests {
     use crate::messages::{
+        BurnReceipt,
+        CustodyBalance,
+        GovernancePayloadOverrideTransferCap,
+        GovernancePayloadRecoverChainId,
+        GovernancePayloadSetMetadataAuthority,
+        GovernancePayloadSetTransferCap,
+        GovernancePayloadSetYieldStrategy,
         GovernancePayloadUpgrade,
         PayloadAssetMeta,
+        PayloadCustodyAttestation,
         PayloadGovernanceRegisterChain,
         PayloadTransfer,
         PayloadTransferWithPayload,
+        SplitShare,
+        EXPIRING_PAYLOAD_MAGIC,
+        SPLIT_PAYLOAD_MAGIC,
     };
     use bridge::{
         DeserializePayload,
@@ -428,6 +984,39 @@ mod tests {
         assert_eq!(am_original, am_deser);
     }
 
+    #[test]
+    pub fn test_serde_custody_attestation() {
+        let mut previous_hash = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut previous_hash);
+
+        let original = PayloadCustodyAttestation {
+            index: 3,
+            slot: 1_000,
+            previous_hash,
+            custodies: vec![
+                CustodyBalance {
+                    token_address: [1u8; 32],
+                    decimals: 9,
+                    amount: U256::from(1003),
+                },
+                CustodyBalance {
+                    token_address: [2u8; 32],
+                    decimals: 6,
+                    amount: U256::zero(),
+                },
+            ],
+        };
+
+        let data = original.try_to_vec().unwrap();
+        let deser = PayloadCustodyAttestation::deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(original, deser);
+
+        // Trailing data is rejected.
+        let mut data = data;
+        data.push(0);
+        assert!(PayloadCustodyAttestation::deserialize(&mut data.as_slice()).is_err());
+    }
+
     #[test]
     pub fn test_serde_gov_upgrade() {
         let original = GovernancePayloadUpgrade {
@@ -440,6 +1029,71 @@ mod tests {
         assert_eq!(original, deser);
     }
 
+    #[test]
+    pub fn test_serde_gov_recover_chain_id() {
+        let original = GovernancePayloadRecoverChainId {
+            program: Pubkey::new_unique(),
+            new_chain_id: 5000,
+        };
+
+        let data = original.try_to_vec().unwrap();
+        // No target chain precedes the program.
+        assert_eq!(data.len(), 32 + 1 + 32 + 2);
+        let deser = GovernancePayloadRecoverChainId::deserialize(&mut data.as_slice()).unwrap();
+
+        assert_eq!(original, deser);
+    }
+
+    #[test]
+    pub fn test_serde_gov_set_metadata_authority() {
+        for mint in [None, Some(Pubkey::new_unique())] {
+            let original = GovernancePayloadSetMetadataAuthority {
+                new_authority: Pubkey::new_unique(),
+                mint,
+            };
+
+            let data = original.try_to_vec().unwrap();
+            let deser =
+                GovernancePayloadSetMetadataAuthority::deserialize(&mut data.as_slice()).unwrap();
+
+            assert_eq!(original, deser);
+        }
+    }
+
+    #[test]
+    pub fn test_serde_gov_transfer_cap() {
+        let original = GovernancePayloadSetTransferCap {
+            mint: Pubkey::new_unique(),
+            max_amount: 1_000_000,
+        };
+        let data = original.try_to_vec().unwrap();
+        let deser = GovernancePayloadSetTransferCap::deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(original, deser);
+
+        let original = GovernancePayloadOverrideTransferCap {
+            mint: Pubkey::new_unique(),
+            max_amount: 0,
+            until: 1_700_000_000,
+        };
+        let data = original.try_to_vec().unwrap();
+        let deser =
+            GovernancePayloadOverrideTransferCap::deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(original, deser);
+    }
+
+    #[test]
+    pub fn test_serde_gov_yield_strategy() {
+        let original = GovernancePayloadSetYieldStrategy {
+            mint: Pubkey::new_unique(),
+            strategy: Pubkey::new_unique(),
+            max_amount: 1_000_000,
+            max_share_bps: 2_500,
+        };
+        let data = original.try_to_vec().unwrap();
+        let deser = GovernancePayloadSetYieldStrategy::deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(original, deser);
+    }
+
     #[test]
     pub fn test_serde_gov_register_chain() {
         let mut endpoint_address = [0u8; 32];
@@ -481,4 +1135,106 @@ mod tests {
 
         assert_eq!(transfer_original, transfer_deser);
     }
+
+    #[test]
+    pub fn test_transfer_with_payload_expiration() {
+        let mut transfer = PayloadTransferWithPayload {
+            amount: U256::from(1003),
+            token_address: [1u8; 32],
+            token_chain: 8,
+            to: [2u8; 32],
+            to_chain: 1,
+            from_address: [3u8; 32],
+            payload: vec![0u8; 10],
+        };
+        assert_eq!(transfer.expiration(), None);
+        assert!(!transfer.is_expired(i64::MAX));
+
+        transfer.payload = PayloadTransferWithPayload::expiring_payload(1_000, &[0u8; 10]);
+        assert_eq!(transfer.expiration(), Some(1_000));
+        assert!(!transfer.is_expired(999));
+        assert!(transfer.is_expired(1_000));
+
+        // The magic alone doesn't make a payload expiring.
+        transfer.payload = EXPIRING_PAYLOAD_MAGIC.to_vec();
+        assert_eq!(transfer.expiration(), None);
+    }
+
+    #[test]
+    pub fn test_transfer_with_payload_split() {
+        let shares = [
+            SplitShare {
+                token_account: Pubkey::new_unique(),
+                bps: 2_500,
+            },
+            SplitShare {
+                token_account: Pubkey::new_unique(),
+                bps: 7_500,
+            },
+        ];
+        let mut transfer = PayloadTransferWithPayload {
+            amount: U256::from(1000),
+            token_address: [1u8; 32],
+            token_chain: 1,
+            to: [2u8; 32],
+            to_chain: 1,
+            from_address: [3u8; 32],
+            payload: vec![0u8; 10],
+        };
+        assert_eq!(transfer.split().unwrap(), None);
+
+        transfer.payload = PayloadTransferWithPayload::split_payload(&shares, &[0u8; 10]);
+        assert_eq!(transfer.split().unwrap(), Some(shares.to_vec()));
+
+        // Splits of expiring transfers follow the expiration.
+        transfer.payload =
+            PayloadTransferWithPayload::expiring_payload(1_000, &transfer.payload.clone());
+        assert_eq!(transfer.expiration(), Some(1_000));
+        assert_eq!(transfer.split().unwrap(), Some(shares.to_vec()));
+
+        // Shares must add up to the whole amount, and a truncated split isn't redeemed unsplit.
+        let mut short = shares;
+        short[1].bps = 7_499;
+        transfer.payload = PayloadTransferWithPayload::split_payload(&short, &[]);
+        assert!(transfer.split().is_err());
+        transfer.payload = SPLIT_PAYLOAD_MAGIC.to_vec();
+        assert!(transfer.split().is_err());
+        transfer.payload = PayloadTransferWithPayload::split_payload(&[], &[]);
+        assert!(transfer.split().is_err());
+    }
+
+    #[test]
+    pub fn test_transfer_burn_receipt() {
+        let transfer = PayloadTransfer {
+            amount: U256::from(1003),
+            token_address: [1u8; 32],
+            token_chain: 8,
+            to: [2u8; 32],
+            to_chain: 2,
+            fee: U256::from(0),
+        };
+        let receipt = BurnReceipt {
+            token_chain: 8,
+            token_address: [1u8; 32],
+            mint: Pubkey::new_unique(),
+            amount: 1003,
+            slot: 42,
+        };
+
+        let mut data = transfer.try_to_vec().unwrap();
+        assert_eq!(data.len(), PayloadTransfer::LEN);
+        assert_eq!(PayloadTransfer::burn_receipt(&data), None);
+
+        data.extend(receipt.try_to_vec().unwrap());
+        assert_eq!(PayloadTransfer::burn_receipt(&data), Some(receipt));
+        assert_eq!(
+            PayloadTransfer::deserialize(&mut data.as_slice()).unwrap(),
+            transfer
+        );
+
+        // Anything else after the transfer is still rejected.
+        data.truncate(PayloadTransfer::LEN + 1);
+        assert_eq!(PayloadTransfer::burn_receipt(&data), None);
+        assert!(PayloadTransfer::deserialize(&mut data.as_slice()).is_err());
+    }
 }
@@ -0,0 +1,329 @@
+//! Native tokens of either token program. Native mints and their token accounts may belong to
+//! Token-2022, wrapped mints are always created by the original token program. Extensions are read
+//! straight from the account data, as the `spl-token-2022` release the bridge builds against
+//! predates some of them:
+//!
+//! - Transfer fees are withheld from transfers into custody, so outbound transfers bridge the
+//!   amount custody received. Redemptions pay out the bridged amount and recipients receive it net
+//!   of the fee. Custody accounts of these mints are sized for the withheld amount.
+//! - Metadata pointers to the mint itself are attested from the token metadata of the mint, other
+//!   mints from their SPL metadata account.
+//! - Non-transferable mints and mints with transfer hooks can't be moved in and out of custody and
+//!   are rejected.
+
+use crate::{
+    accounts::{
+        CustodyAccount,
+        CustodyAccountDerivationData,
+    },
+    types::InterfaceAccount,
+    TokenBridgeError::{
+        InvalidMetadata,
+        UnsupportedTokenExtension,
+        UnsupportedTokenProgram,
+    },
+};
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    program_pack::Pack,
+    pubkey::Pubkey,
+    system_program,
+    sysvar,
+};
+use solitaire::{
+    CreationLamports::Exempt,
+    IsSigned::*,
+    *,
+};
+use spl_token::state::Account;
+
+// Extension types, numbered as Token-2022 does.
+const TRANSFER_FEE_CONFIG: u16 = 1;
+const NON_TRANSFERABLE: u16 = 9;
+const TRANSFER_HOOK: u16 = 14;
+const METADATA_POINTER: u16 = 18;
+const TOKEN_METADATA: u16 = 19;
+
+/// Length of the withheld amount extension of token accounts, including its type and length.
+const TRANSFER_FEE_AMOUNT_LEN: usize = 4 + 8;
+
+/// Token program owning `info`, which must be either token program.
+pub fn token_program(info: &AccountInfo) -> Result<Pubkey> {
+    if *info.owner != spl_token::id() && *info.owner != spl_token_2022::id() {
+        return Err(UnsupportedTokenProgram.into());
+    }
+    Ok(*info.owner)
+}
+
+/// Verify that `accounts` belong to `token_program`, the token program of their mint.
+pub fn verify_token_accounts(token_program: &Pubkey, accounts: &[&AccountInfo]) -> Result<()> {
+    for info in accounts {
+        if info.owner != token_program {
+            return Err(SolitaireError::InvalidOwner(*info.owner));
+        }
+    }
+    Ok(())
+}
+
+/// Extensions of a Token-2022 mint or token account as type and value, in order. Extensions start
+/// after the base state, padded to the length of a token account, and its account type.
+fn extensions(data: &[u8]) -> Vec<(u16, &[u8])> {
+    let mut extensions = Vec::new();
+    let mut tlv = data.get(Account::LEN + 1..).unwrap_or_default();
+    while tlv.len() >= 4 {
+        let kind = u16::from_le_bytes([tlv[0], tlv[1]]);
+        let len = u16::from_le_bytes([tlv[2], tlv[3]]) as usize;
+        // Space past the last extension is zeroed.
+        if kind == 0 {
+            break;
+        }
+        match tlv.get(4..4 + len) {
+            Some(value) => extensions.push((kind, value)),
+            None => break,
+        }
+        tlv = &tlv[4 + len..];
+    }
+    extensions
+}
+
+fn has_extension(data: &[u8], kind: u16) -> bool {
+    extensions(data).iter().any(|(k, _)| *k == kind)
+}
+
+/// Verify that the bridge can move tokens of `mint` in and out of custody.
+pub fn verify_mint_extensions(mint: &AccountInfo) -> Result<()> {
+    let data = mint.data.borrow();
+    if has_extension(&data, NON_TRANSFERABLE) || has_extension(&data, TRANSFER_HOOK) {
+        return Err(UnsupportedTokenExtension.into());
+    }
+    Ok(())
+}
+
+/// Create the custody account of `mint` under its token program, with the extensions the mint
+/// requires of token accounts.
+pub fn create_custody(
+    ctx: &ExecutionContext,
+    custody: &AccountInfo,
+    derivation_data: &CustodyAccountDerivationData,
+    payer: &Pubkey,
+    mint: &AccountInfo,
+    custody_signer: &Pubkey,
+) -> Result<()> {
+    let token_program = token_program(mint)?;
+    let mut size = Account::LEN;
+    if token_program == spl_token_2022::id()
+        && has_extension(&mint.data.borrow(), TRANSFER_FEE_CONFIG)
+    {
+        size += 1 + TRANSFER_FEE_AMOUNT_LEN;
+    }
+
+    let seeds = CustodyAccount::<'_, { AccountState::MaybeInitialized }>::bumped_seeds(
+        derivation_data,
+        ctx.program_id,
+    );
+    let s: Vec<&[u8]> = seeds.iter().map(|item| item.as_slice()).collect();
+    create_account(
+        ctx,
+        custody,
+        payer,
+        Exempt,
+        size,
+        &token_program,
+        SignedWithSeeds(&[s.as_slice()]),
+    )?;
+
+    let init_ix = if token_program == spl_token::id() {
+        spl_token::instruction::initialize_account(
+            &token_program,
+            custody.key,
+            mint.key,
+            custody_signer,
+        )
+    } else {
+        spl_token_2022::instruction::initialize_account(
+            &token_program,
+            custody.key,
+            mint.key,
+            custody_signer,
+        )
+    }?;
+    invoke_signed(&init_ix, ctx.accounts, &[])?;
+
+    Ok(())
+}
+
+/// Transfer of `amount` between token accounts of `mint`, checked against its decimals.
+pub fn transfer_checked(
+    mint: &AccountInfo,
+    source: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> Result<Instruction> {
+    let token_program = token_program(mint)?;
+    let ix = if token_program == spl_token::id() {
+        spl_token::instruction::transfer_checked(
+            &token_program,
+            source,
+            mint.key,
+            destination,
+            authority,
+            &[],
+            amount,
+            decimals,
+        )
+    } else {
+        spl_token_2022::instruction::transfer_checked(
+            &token_program,
+            source,
+            mint.key,
+            destination,
+            authority,
+            &[],
+            amount,
+            decimals,
+        )
+    }?;
+    Ok(ix)
+}
+
+/// Associated token account of `owner` for `mint`, under the token program of the mint.
+pub fn associated_token_address(owner: &Pubkey, mint: &AccountInfo) -> Result<Pubkey> {
+    let token_program = token_program(mint)?;
+    let (address, _) = Pubkey::find_program_address(
+        &[owner.as_ref(), token_program.as_ref(), mint.key.as_ref()],
+        &spl_associated_token_account::id(),
+    );
+    Ok(address)
+}
+
+/// Creation of the associated token account of `owner` for `mint`, paid by `payer`. Built as
+/// `spl_associated_token_account` builds it, for either token program.
+pub fn create_associated_token_account(
+    payer: &Pubkey,
+    owner: &Pubkey,
+    mint: &AccountInfo,
+) -> Result<Instruction> {
+    Ok(Instruction {
+        program_id: spl_associated_token_account::id(),
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(associated_token_address(owner, mint)?, false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new_readonly(*mint.key, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(token_program(mint)?, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: vec![],
+    })
+}
+
+/// Amount the token account `info` holds.
+pub fn token_amount(info: &AccountInfo) -> Result<u64> {
+    Ok(InterfaceAccount::try_from_slice(&info.data.borrow())?.amount)
+}
+
+/// Name and symbol of `mint` from its token metadata, if its metadata pointer points at the mint.
+pub fn mint_metadata(mint: &AccountInfo) -> Result<Option<(String, String)>> {
+    if *mint.owner != spl_token_2022::id() {
+        return Ok(None);
+    }
+    let data = mint.data.borrow();
+    let extensions = extensions(&data);
+
+    // The pointer holds its authority, followed by the metadata address.
+    let in_mint = extensions.iter().any(|(kind, value)| {
+        *kind == METADATA_POINTER && value.get(32..64) == Some(mint.key.as_ref())
+    });
+    if !in_mint {
+        return Ok(None);
+    }
+    let metadata = match extensions.iter().find(|(kind, _)| *kind == TOKEN_METADATA) {
+        Some((_, metadata)) => metadata,
+        None => return Ok(None),
+    };
+
+    // Token metadata starts with its update authority and mint, followed by the name and symbol.
+    let mut fields = metadata.get(64..).ok_or(InvalidMetadata)?;
+    let name = String::deserialize(&mut fields)?;
+    let symbol = String::deserialize(&mut fields)?;
+    Ok(Some((name, symbol)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{
+        InterfaceMint,
+        ACCOUNT_TYPE_MINT,
+    };
+    use borsh::BorshSerialize;
+    use spl_token::state::Mint;
+
+    /// Token-2022 mint with its metadata pointing at `metadata`.
+    fn extended_mint(mint: &Pubkey, metadata: &Pubkey) -> Vec<u8> {
+        let mut data = vec![0u8; Account::LEN];
+        let base = Mint {
+            decimals: 6,
+            is_initialized: true,
+            ..Mint::default()
+        };
+        base.pack_into_slice(&mut data[..Mint::LEN]);
+        data.push(ACCOUNT_TYPE_MINT);
+
+        data.extend_from_slice(&METADATA_POINTER.to_le_bytes());
+        data.extend_from_slice(&64u16.to_le_bytes());
+        data.extend_from_slice(&[0u8; 32]);
+        data.extend_from_slice(metadata.as_ref());
+
+        let mut fields = [0u8; 32].to_vec();
+        fields.extend_from_slice(mint.as_ref());
+        for field in ["Token", "TKN", "https://example.com"] {
+            field.serialize(&mut fields).unwrap();
+        }
+        0u32.serialize(&mut fields).unwrap();
+        data.extend_from_slice(&TOKEN_METADATA.to_le_bytes());
+        data.extend_from_slice(&(fields.len() as u16).to_le_bytes());
+        data.extend_from_slice(&fields);
+        data
+    }
+
+    /// Name and symbol attested for the Token-2022 mint `key`.
+    fn attested(key: &Pubkey, mut data: Vec<u8>) -> Option<(String, String)> {
+        let owner = spl_token_2022::id();
+        let mut lamports = 0;
+        let info = AccountInfo::new(
+            key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        mint_metadata(&info).unwrap()
+    }
+
+    #[test]
+    fn test_mint_metadata() {
+        let key = Pubkey::new_unique();
+        let data = extended_mint(&key, &key);
+        assert_eq!(InterfaceMint::try_from_slice(&data).unwrap().decimals, 6);
+        assert_eq!(
+            attested(&key, data),
+            Some(("Token".to_string(), "TKN".to_string()))
+        );
+
+        // Metadata held elsewhere is read from the SPL metadata account.
+        let data = extended_mint(&key, &Pubkey::new_unique());
+        assert_eq!(attested(&key, data), None);
+    }
+}
@@ -6,7 +6,13 @@ use serde::{
     Deserialize,
     Serialize,
 };
-use solana_program::pubkey::Pubkey;
+use solana_program::{
+    program_pack::{
+        IsInitialized,
+        Pack,
+    },
+    pubkey::Pubkey,
+};
 use solitaire::{
     pack_type,
     processors::seeded::{
@@ -17,6 +23,7 @@ use solitaire::{
 use spl_token::state::{
     Account,
     Mint,
+    Multisig,
 };
 
 pub type Address = [u8; 32];
@@ -85,5 +92,298 @@ impl Owned for WrappedMeta {
     }
 }
 
+/// Canonical mapping of an asset's origin to the mint that represents it on Solana, regardless of
+/// whether the asset is native (custodied) or wrapped (minted by the bridge).
+#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+pub struct TokenId {
+    pub chain: ChainID,
+    pub token_address: Address,
+    pub mint: Pubkey,
+    pub is_wrapped: bool,
+}
+
+#[cfg(not(feature = "cpi"))]
+impl Owned for TokenId {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+#[cfg(feature = "cpi")]
+impl Owned for TokenId {
+    fn owner(&self) -> AccountOwner {
+        use std::str::FromStr;
+        AccountOwner::Other(Pubkey::from_str(env!("TOKEN_BRIDGE_ADDRESS")).unwrap())
+    }
+}
+
+/// Metadata update authority decreed by governance, see [`crate::api::set_metadata_authority`].
+#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+pub struct MetadataAuthorityDecree {
+    pub new_authority: Pubkey,
+}
+
+#[cfg(not(feature = "cpi"))]
+impl Owned for MetadataAuthorityDecree {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+#[cfg(feature = "cpi")]
+impl Owned for MetadataAuthorityDecree {
+    fn owner(&self) -> AccountOwner {
+        use std::str::FromStr;
+        AccountOwner::Other(Pubkey::from_str(env!("TOKEN_BRIDGE_ADDRESS")).unwrap())
+    }
+}
+
+/// Largest amount of a mint, in its own units, a single transfer may move in or out of the token
+/// bridge, see [`crate::api::set_transfer_cap`].
+#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+pub struct TransferCapData {
+    pub max_amount: u64,
+
+    /// Cap decreed by [`crate::api::override_transfer_cap`], replacing `max_amount` until
+    /// `override_until`.
+    pub override_max_amount: u64,
+    pub override_until: i64,
+}
+
+impl TransferCapData {
+    /// Cap in force at `now`.
+    pub fn max_amount(&self, now: i64) -> u64 {
+        if now < self.override_until {
+            self.override_max_amount
+        } else {
+            self.max_amount
+        }
+    }
+}
+
+#[cfg(not(feature = "cpi"))]
+impl Owned for TransferCapData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+#[cfg(feature = "cpi")]
+impl Owned for TransferCapData {
+    fn owner(&self) -> AccountOwner {
+        use std::str::FromStr;
+        AccountOwner::Other(Pubkey::from_str(env!("TOKEN_BRIDGE_ADDRESS")).unwrap())
+    }
+}
+
+/// Relayers allowed to redeem transfers to a token account, see
+/// [`crate::api::set_relayer_allowlist`]. Anyone may redeem while the list is empty.
+#[derive(Default, Clone, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+pub struct RelayerAllowlistData {
+    pub relayers: Vec<Pubkey>,
+}
+
+impl RelayerAllowlistData {
+    /// Most relayers a single token account can allow.
+    pub const MAX_RELAYERS: usize = 16;
+
+    /// Whether `relayer` may redeem transfers to the token account.
+    pub fn allows(&self, relayer: &Pubkey) -> bool {
+        self.relayers.is_empty() || self.relayers.contains(relayer)
+    }
+}
+
+#[cfg(not(feature = "cpi"))]
+impl Owned for RelayerAllowlistData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+#[cfg(feature = "cpi")]
+impl Owned for RelayerAllowlistData {
+    fn owner(&self) -> AccountOwner {
+        use std::str::FromStr;
+        AccountOwner::Other(Pubkey::from_str(env!("TOKEN_BRIDGE_ADDRESS")).unwrap())
+    }
+}
+
+/// Latest attestation of the native tokens in custody, see [`crate::api::attest_custody`].
+#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+pub struct CustodyAttestationData {
+    /// Number of the attestation, counting from zero.
+    pub index: u64,
+
+    /// Slot of the attestation.
+    pub slot: u64,
+
+    /// Keccak256 hash of the attestation payload, anchoring the next attestation.
+    pub hash: [u8; 32],
+}
+
+#[cfg(not(feature = "cpi"))]
+impl Owned for CustodyAttestationData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+#[cfg(feature = "cpi")]
+impl Owned for CustodyAttestationData {
+    fn owner(&self) -> AccountOwner {
+        use std::str::FromStr;
+        AccountOwner::Other(Pubkey::from_str(env!("TOKEN_BRIDGE_ADDRESS")).unwrap())
+    }
+}
+
+/// Chain ID governance recovered for a deployment forked from the one at the mainnet address, see
+/// [`crate::api::recover_chain_id`].
+#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+pub struct RecoveredChainData {
+    pub chain: ChainID,
+}
+
+#[cfg(not(feature = "cpi"))]
+impl Owned for RecoveredChainData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+#[cfg(feature = "cpi")]
+impl Owned for RecoveredChainData {
+    fn owner(&self) -> AccountOwner {
+        use std::str::FromStr;
+        AccountOwner::Other(Pubkey::from_str(env!("TOKEN_BRIDGE_ADDRESS")).unwrap())
+    }
+}
+
+/// Strategy governance allows to borrow idle custody of a native mint, see
+/// [`crate::api::set_yield_strategy`].
+#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+pub struct YieldStrategyData {
+    /// Program borrowing through its `["borrower"]` PDA.
+    pub strategy: Pubkey,
+
+    /// Most the strategy may owe at a time, in units of the mint.
+    pub max_amount: u64,
+
+    /// Most the strategy may owe at a time, in basis points of the bridged supply.
+    pub max_share_bps: u16,
+
+    /// Amount the strategy owes.
+    pub lent: u64,
+}
+
+impl YieldStrategyData {
+    /// Largest `max_share_bps` governance can decree, keeping part of the bridged supply in
+    /// custody for redemptions.
+    pub const MAX_SHARE_BPS: u16 = 5_000;
+
+    /// Native tokens bridged out and not redeemed yet, those in custody and those lent.
+    pub fn bridged_supply(&self, custody_balance: u64) -> u64 {
+        custody_balance.saturating_add(self.lent)
+    }
+
+    /// Whether the strategy may borrow `amount` more from a custody account holding
+    /// `custody_balance`, staying within both caps.
+    pub fn can_lend(&self, custody_balance: u64, amount: u64) -> bool {
+        let lent = match self.lent.checked_add(amount) {
+            Some(lent) if amount <= custody_balance => lent,
+            _ => return false,
+        };
+        let share = u128::from(self.bridged_supply(custody_balance))
+            * u128::from(self.max_share_bps)
+            / 10_000;
+        lent <= self.max_amount && u128::from(lent) <= share
+    }
+}
+
+#[cfg(not(feature = "cpi"))]
+impl Owned for YieldStrategyData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+#[cfg(feature = "cpi")]
+impl Owned for YieldStrategyData {
+    fn owner(&self) -> AccountOwner {
+        use std::str::FromStr;
+        AccountOwner::Other(Pubkey::from_str(env!("TOKEN_BRIDGE_ADDRESS")).unwrap())
+    }
+}
+
 pack_type!(SplMint, Mint, AccountOwner::Other(spl_token::id()));
 pack_type!(SplAccount, Account, AccountOwner::Other(spl_token::id()));
+
+/// Mint of either token program. Token-2022 mints are followed by their extensions, which are
+/// skipped here and read through [`crate::token_interface`]. Owner checks are left to
+/// [`crate::token_interface::token_program`].
+#[derive(Default)]
+pub struct InterfaceMint(pub Mint);
+
+/// Token account of either token program, see [`InterfaceMint`].
+#[derive(Default)]
+pub struct InterfaceAccount(pub Account);
+
+/// Account type Token-2022 writes after the base state of extended mints.
+pub const ACCOUNT_TYPE_MINT: u8 = 1;
+
+/// Account type Token-2022 writes after the base state of extended token accounts.
+pub const ACCOUNT_TYPE_ACCOUNT: u8 = 2;
+
+/// Unpack the base state of a mint or token account, with or without extensions. Extended accounts
+/// are padded to the length of a token account and tagged with their account type, and multisigs
+/// are told apart by their length, as Token-2022 does.
+fn unpack_base<T: Pack + IsInitialized>(buf: &[u8], account_type: u8) -> std::io::Result<T> {
+    let extended =
+        buf.len() > Account::LEN && buf.len() != Multisig::LEN && buf[Account::LEN] == account_type;
+    if buf.len() != T::LEN && !extended {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "invalid token state length",
+        ));
+    }
+    T::unpack(&buf[..T::LEN]).map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+}
+
+macro_rules! interface_type {
+    ($name:ident, $embed:ty, $account_type:expr) => {
+        impl BorshDeserialize for $name {
+            fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+                let acc = $name(unpack_base(buf, $account_type)?);
+                // We need to clear the buf to show to Borsh that we've read all data
+                *buf = &buf[..0];
+
+                Ok(acc)
+            }
+        }
+
+        impl BorshSerialize for $name {
+            fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+                let mut data = [0u8; <$embed as Pack>::LEN];
+                Pack::pack_into_slice(&self.0, &mut data);
+                writer.write_all(&data)?;
+
+                Ok(())
+            }
+        }
+
+        impl Owned for $name {
+            fn owner(&self) -> AccountOwner {
+                AccountOwner::Any
+            }
+        }
+
+        impl std::ops::Deref for $name {
+            type Target = $embed;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+    };
+}
+
+interface_type!(InterfaceMint, Mint, ACCOUNT_TYPE_MINT);
+interface_type!(InterfaceAccount, Account, ACCOUNT_TYPE_ACCOUNT);
@@ -10,10 +10,17 @@ use crate::{
     },
     instructions::{
         attest,
+        attest_custody,
         complete_native,
         complete_wrapped,
         create_wrapped,
+        override_transfer_cap,
+        recover_chain_id,
         register_chain,
+        rotate_metadata_authority,
+        set_metadata_authority,
+        set_relayer_allowlist,
+        set_transfer_cap,
         transfer_native,
         transfer_native_with_payload,
         transfer_wrapped,
@@ -21,13 +28,18 @@ use crate::{
         upgrade_contract,
     },
     messages::{
+        GovernancePayloadOverrideTransferCap,
+        GovernancePayloadSetMetadataAuthority,
+        GovernancePayloadSetTransferCap,
         GovernancePayloadUpgrade,
         PayloadAssetMeta,
         PayloadGovernanceRegisterChain,
         PayloadTransfer,
     },
     types::{
+        CustodyAttestationData,
         EndpointRegistration,
+        RelayerAllowlistData,
         WrappedMeta,
     },
     CompleteNativeData,
@@ -47,7 +59,10 @@ use bridge::{
     DeserializePayload,
     PostVAAData,
 };
-use solana_program::pubkey::Pubkey;
+use solana_program::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+};
 use solitaire::{
     processors::seeded::Seeded,
     AccountState,
@@ -75,6 +90,27 @@ pub fn attest_ix(
     JsValue::from_serde(&ix).unwrap()
 }
 
+/// `mints` are the concatenated 32 byte keys of the attested native mints, in ascending order.
+#[wasm_bindgen]
+pub fn attest_custody_ix(
+    program_id: String,
+    bridge_id: String,
+    payer: String,
+    message: String,
+    mints: Vec<u8>,
+    nonce: u32,
+) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
+    let payer = Pubkey::from_str(payer.as_str()).unwrap();
+    let message = Pubkey::from_str(message.as_str()).unwrap();
+    let mints: Vec<Pubkey> = mints.chunks(32).map(Pubkey::new).collect();
+
+    let ix = attest_custody(program_id, bridge_id, payer, message, &mints, nonce).unwrap();
+
+    JsValue::from_serde(&ix).unwrap()
+}
+
 #[wasm_bindgen]
 pub fn transfer_native_ix(
     program_id: String,
@@ -207,6 +243,7 @@ pub fn transfer_wrapped_ix(
             fee,
             target_address: target_addr,
             target_chain,
+            burn_receipt: false,
         },
     )
     .unwrap();
@@ -309,7 +346,7 @@ pub fn complete_transfer_native_ix(
             None
         },
         Pubkey::new(&payload.token_address),
-        CompleteNativeData {},
+        CompleteNativeData::default(),
     )
     .unwrap();
 
@@ -360,7 +397,7 @@ pub fn complete_transfer_wrapped_ix(
         } else {
             None
         },
-        CompleteWrappedData {},
+        CompleteWrappedData::default(),
     )
     .unwrap();
 
@@ -484,6 +521,173 @@ pub fn register_chain_ix(
     return JsValue::from_serde(&ix).unwrap();
 }
 
+#[wasm_bindgen]
+pub fn recover_chain_id_ix(
+    program_id: String,
+    bridge_id: String,
+    payer: String,
+    vaa: Vec<u8>,
+) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
+    let payer = Pubkey::from_str(payer.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let message_key = bridge::accounts::PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &bridge_id,
+    );
+    let post_vaa_data = PostVAAData {
+        version: vaa.version,
+        guardian_set_index: vaa.guardian_set_index,
+        timestamp: vaa.timestamp,
+        nonce: vaa.nonce,
+        emitter_chain: vaa.emitter_chain,
+        emitter_address: vaa.emitter_address,
+        sequence: vaa.sequence,
+        consistency_level: vaa.consistency_level,
+        payload: vaa.payload,
+    };
+    let ix = recover_chain_id(program_id, payer, message_key, post_vaa_data).unwrap();
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn set_metadata_authority_ix(
+    program_id: String,
+    bridge_id: String,
+    payer: String,
+    vaa: Vec<u8>,
+) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
+    let payer = Pubkey::from_str(payer.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let payload =
+        GovernancePayloadSetMetadataAuthority::deserialize(&mut vaa.payload.as_slice()).unwrap();
+    let message_key = bridge::accounts::PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &bridge_id,
+    );
+    let post_vaa_data = PostVAAData {
+        version: vaa.version,
+        guardian_set_index: vaa.guardian_set_index,
+        timestamp: vaa.timestamp,
+        nonce: vaa.nonce,
+        emitter_chain: vaa.emitter_chain,
+        emitter_address: vaa.emitter_address,
+        sequence: vaa.sequence,
+        consistency_level: vaa.consistency_level,
+        payload: vaa.payload,
+    };
+    let ix =
+        set_metadata_authority(program_id, payer, message_key, post_vaa_data, payload).unwrap();
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn rotate_metadata_authority_ix(program_id: String, mint: String) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let mint = Pubkey::from_str(mint.as_str()).unwrap();
+    let ix = rotate_metadata_authority(program_id, mint).unwrap();
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn set_transfer_cap_ix(
+    program_id: String,
+    bridge_id: String,
+    payer: String,
+    vaa: Vec<u8>,
+) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
+    let payer = Pubkey::from_str(payer.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let payload =
+        GovernancePayloadSetTransferCap::deserialize(&mut vaa.payload.as_slice()).unwrap();
+    let message_key = bridge::accounts::PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &bridge_id,
+    );
+    let post_vaa_data = PostVAAData {
+        version: vaa.version,
+        guardian_set_index: vaa.guardian_set_index,
+        timestamp: vaa.timestamp,
+        nonce: vaa.nonce,
+        emitter_chain: vaa.emitter_chain,
+        emitter_address: vaa.emitter_address,
+        sequence: vaa.sequence,
+        consistency_level: vaa.consistency_level,
+        payload: vaa.payload,
+    };
+    let ix = set_transfer_cap(program_id, payer, message_key, post_vaa_data, payload).unwrap();
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn override_transfer_cap_ix(
+    program_id: String,
+    bridge_id: String,
+    payer: String,
+    vaa: Vec<u8>,
+) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
+    let payer = Pubkey::from_str(payer.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let payload =
+        GovernancePayloadOverrideTransferCap::deserialize(&mut vaa.payload.as_slice()).unwrap();
+    let message_key = bridge::accounts::PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &bridge_id,
+    );
+    let post_vaa_data = PostVAAData {
+        version: vaa.version,
+        guardian_set_index: vaa.guardian_set_index,
+        timestamp: vaa.timestamp,
+        nonce: vaa.nonce,
+        emitter_chain: vaa.emitter_chain,
+        emitter_address: vaa.emitter_address,
+        sequence: vaa.sequence,
+        consistency_level: vaa.consistency_level,
+        payload: vaa.payload,
+    };
+    let ix = override_transfer_cap(program_id, payer, message_key, post_vaa_data, payload).unwrap();
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+/// `relayers` are the concatenated 32 byte keys of the allowed relayers.
+#[wasm_bindgen]
+pub fn set_relayer_allowlist_ix(
+    program_id: String,
+    payer: String,
+    owner: String,
+    token_account: String,
+    relayers: Vec<u8>,
+) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let payer = Pubkey::from_str(payer.as_str()).unwrap();
+    let owner = Pubkey::from_str(owner.as_str()).unwrap();
+    let token_account = Pubkey::from_str(token_account.as_str()).unwrap();
+    let relayers = relayers.chunks(32).map(Pubkey::new).collect();
+    let ix = set_relayer_allowlist(program_id, payer, owner, token_account, relayers).unwrap();
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn compute_unit_limit(data: Vec<u8>) -> Option<u32> {
+    let instruction = Instruction::new_with_bytes(Pubkey::default(), &data, vec![]);
+    crate::instructions::compute_unit_limit(&instruction)
+}
+
 #[wasm_bindgen]
 pub fn emitter_address(program_id: String) -> Vec<u8> {
     let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
@@ -547,3 +751,13 @@ pub fn parse_wrapped_meta(data: Vec<u8>) -> JsValue {
 pub fn parse_endpoint_registration(data: Vec<u8>) -> JsValue {
     JsValue::from_serde(&EndpointRegistration::try_from_slice(data.as_slice()).unwrap()).unwrap()
 }
+
+#[wasm_bindgen]
+pub fn parse_relayer_allowlist(data: Vec<u8>) -> JsValue {
+    JsValue::from_serde(&RelayerAllowlistData::try_from_slice(data.as_slice()).unwrap()).unwrap()
+}
+
+#[wasm_bindgen]
+pub fn parse_custody_attestation(data: Vec<u8>) -> JsValue {
+    JsValue::from_serde(&CustodyAttestationData::try_from_slice(data.as_slice()).unwrap()).unwrap()
+}
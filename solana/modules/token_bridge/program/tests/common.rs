@@ -9,7 +9,16 @@ use libsecp256k1::{
 };
 use sha3::Digest;
 use solana_program::{
-    instruction::Instruction,
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    program::{
+        get_return_data,
+        invoke,
+    },
     program_pack::Pack,
     pubkey::Pubkey,
     system_instruction,
@@ -73,16 +82,26 @@ mod helpers {
     };
     use solana_program_test::processor;
     use token_bridge::{
+        api::{
+            RedemptionKind,
+            RedemptionProbe,
+        },
+        AttestAndTransferNativeData,
         CompleteNativeData,
         CompleteNativeWithPayloadData,
         CompleteWrappedData,
         CreateWrappedData,
+        ReclaimExpiredTransferData,
         RegisterChainData,
         TransferNativeData,
         TransferWrappedData,
     };
 
     use token_bridge::messages::{
+        GovernancePayloadOverrideTransferCap,
+        GovernancePayloadSetMetadataAuthority,
+        GovernancePayloadSetTransferCap,
+        GovernancePayloadSetYieldStrategy,
         PayloadAssetMeta,
         PayloadGovernanceRegisterChain,
         PayloadTransfer,
@@ -116,6 +135,79 @@ mod helpers {
         )
     }
 
+    /// Address of [`record_return_data`].
+    fn return_data_recorder() -> Pubkey {
+        Pubkey::new_from_array([7u8; 32])
+    }
+
+    const RETURN_DATA_SIZE: usize = 1024;
+
+    /// Program relaying the return data of the instruction it invokes into its first account, as
+    /// the banks client doesn't report return data. Invoked with the record account, the program to
+    /// invoke and its accounts, and the instruction data.
+    fn record_return_data(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        data: &[u8],
+    ) -> ProgramResult {
+        let (record, accounts) = accounts.split_first().unwrap();
+        let instruction = Instruction {
+            program_id: *accounts[0].key,
+            accounts: accounts[1..]
+                .iter()
+                .map(|info| AccountMeta {
+                    pubkey: *info.key,
+                    is_signer: info.is_signer,
+                    is_writable: info.is_writable,
+                })
+                .collect(),
+            data: data.to_vec(),
+        };
+        invoke(&instruction, accounts)?;
+
+        let (_, returned) = get_return_data().unwrap_or_default();
+        record.data.borrow_mut()[..returned.len()].copy_from_slice(&returned);
+        Ok(())
+    }
+
+    /// Return data of `instruction`, zero-padded.
+    pub async fn return_data(
+        client: &mut BanksClient,
+        payer: &Keypair,
+        instruction: Instruction,
+    ) -> Result<Vec<u8>, BanksClientError> {
+        let record = Keypair::new();
+        let lamports = Rent::default().minimum_balance(RETURN_DATA_SIZE);
+        let mut accounts = vec![
+            AccountMeta::new(record.pubkey(), false),
+            AccountMeta::new_readonly(instruction.program_id, false),
+        ];
+        accounts.extend(instruction.accounts);
+
+        execute(
+            client,
+            payer,
+            &[payer, &record],
+            &[
+                system_instruction::create_account(
+                    &payer.pubkey(),
+                    &record.pubkey(),
+                    lamports,
+                    RETURN_DATA_SIZE as u64,
+                    &return_data_recorder(),
+                ),
+                Instruction {
+                    program_id: return_data_recorder(),
+                    accounts,
+                    data: instruction.data,
+                },
+            ],
+            CommitmentLevel::Processed,
+        )
+        .await?;
+        Ok(client.get_account(record.pubkey()).await?.unwrap().data)
+    }
+
     /// Initialize the test environment, spins up a solana-test-validator in the background so that
     /// each test has a fresh environment to work within.
     pub async fn setup() -> (BanksClient, Keypair, Pubkey, Pubkey) {
@@ -137,6 +229,11 @@ mod helpers {
             token_program,
             processor!(token_bridge::solitaire),
         );
+        builder.add_program(
+            "return_data_recorder",
+            return_data_recorder(),
+            processor!(record_return_data),
+        );
 
         // Some instructions go over the limit when tracing is enabled but we need that for better
         // logging.  We don't really care about the limit during these tests anyway.
@@ -277,6 +374,86 @@ mod helpers {
         .await
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub async fn attest_and_transfer_native(
+        client: &mut BanksClient,
+        program: Pubkey,
+        bridge: Pubkey,
+        payer: &Keypair,
+        attest_message: &Keypair,
+        message: &Keypair,
+        from: &Keypair,
+        from_owner: &Keypair,
+        mint: Pubkey,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let instruction = instructions::attest_and_transfer_native(
+            program,
+            bridge,
+            payer.pubkey(),
+            attest_message.pubkey(),
+            message.pubkey(),
+            from.pubkey(),
+            mint,
+            AttestAndTransferNativeData {
+                nonce: 0,
+                amount,
+                fee: 0,
+                target_address: [0u8; 32],
+                target_chain: 2,
+            },
+        )
+        .expect("Could not create AttestAndTransferNative instruction");
+
+        execute(
+            client,
+            payer,
+            &[payer, from_owner, attest_message, message],
+            &[
+                spl_token::instruction::approve(
+                    &spl_token::id(),
+                    &from.pubkey(),
+                    &token_bridge::accounts::AuthoritySigner::key(None, &program),
+                    &from_owner.pubkey(),
+                    &[],
+                    amount,
+                )
+                .unwrap(),
+                instruction,
+            ],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn attest_custody(
+        client: &mut BanksClient,
+        program: Pubkey,
+        bridge: Pubkey,
+        payer: &Keypair,
+        message: &Keypair,
+        mints: &[Pubkey],
+    ) -> Result<(), BanksClientError> {
+        let instruction = instructions::attest_custody(
+            program,
+            bridge,
+            payer.pubkey(),
+            message.pubkey(),
+            mints,
+            0,
+        )
+        .expect("Could not create AttestCustody instruction");
+
+        execute(
+            client,
+            payer,
+            &[payer, message],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn transfer_native(
         client: &mut BanksClient,
@@ -343,6 +520,7 @@ mod helpers {
         token_chain: u16,
         token_address: Address,
         amount: u64,
+        burn_receipt: bool,
     ) -> Result<(), BanksClientError> {
         let instruction = instructions::transfer_wrapped(
             program,
@@ -359,6 +537,7 @@ mod helpers {
                 fee: 0,
                 target_address: [5u8; 32],
                 target_chain: 2,
+                burn_receipt,
             },
         )
         .expect("Could not create Transfer Native");
@@ -440,7 +619,7 @@ mod helpers {
             Pubkey::new(&payload.to[..]),
             None,
             Pubkey::new(&payload.token_address[..]),
-            CompleteNativeData {},
+            CompleteNativeData::default(),
         )
         .expect("Could not create Complete Native instruction");
 
@@ -458,6 +637,73 @@ mod helpers {
         .await
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub async fn complete_native_to_associated(
+        client: &mut BanksClient,
+        program: Pubkey,
+        bridge: Pubkey,
+        message_acc: Pubkey,
+        vaa: PostVAAData,
+        payload: PayloadTransfer,
+        owner: Pubkey,
+        payer: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let instruction = instructions::complete_native(
+            program,
+            bridge,
+            payer.pubkey(),
+            message_acc,
+            vaa,
+            Pubkey::new(&payload.to[..]),
+            None,
+            Pubkey::new(&payload.token_address[..]),
+            CompleteNativeData {
+                recipient_owner: Some(owner),
+            },
+        )
+        .expect("Could not create Complete Native instruction");
+
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn complete_transfers_batch(
+        client: &mut BanksClient,
+        program: Pubkey,
+        bridge: Pubkey,
+        vaas: &[(Pubkey, PostVAAData)],
+        to: Pubkey,
+        mint: Pubkey,
+        payer: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let instruction = instructions::complete_transfers_batch(
+            program,
+            bridge,
+            payer.pubkey(),
+            vaas,
+            to,
+            None,
+            mint,
+            None,
+        )
+        .expect("Could not create Complete Transfers Batch instruction");
+
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     pub async fn complete_transfer_wrapped(
         client: &mut BanksClient,
         program: Pubkey,
@@ -478,7 +724,7 @@ mod helpers {
             payload,
             to,
             None,
-            CompleteWrappedData {},
+            CompleteWrappedData::default(),
         )
         .expect("Could not create Complete Wrapped instruction");
 
@@ -536,6 +782,36 @@ mod helpers {
         .await
     }
 
+    pub async fn reclaim_expired_transfer(
+        client: &mut BanksClient,
+        program: Pubkey,
+        bridge: Pubkey,
+        message_acc: Pubkey,
+        vaa: PostVAAData,
+        refund_message: &Keypair,
+        payer: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let instruction = instructions::reclaim_expired_transfer(
+            program,
+            bridge,
+            payer.pubkey(),
+            message_acc,
+            vaa,
+            refund_message.pubkey(),
+            ReclaimExpiredTransferData { nonce: 0 },
+        )
+        .expect("Could not create Reclaim Expired Transfer instruction");
+
+        execute(
+            client,
+            payer,
+            &[payer, refund_message],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     pub async fn create_wrapped(
         client: &mut BanksClient,
         program: Pubkey,
@@ -570,11 +846,242 @@ mod helpers {
         .await
     }
 
+    pub async fn resolve_token_id(
+        client: &mut BanksClient,
+        program: Pubkey,
+        payer: &Keypair,
+        token_chain: u16,
+        token_address: [u8; 32],
+    ) -> Result<(), BanksClientError> {
+        let instruction =
+            instructions::resolve_token_id(program, payer.pubkey(), token_chain, token_address)
+                .expect("Could not create Resolve Token ID instruction");
+
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn probe_redemption(
+        client: &mut BanksClient,
+        program: Pubkey,
+        bridge: Pubkey,
+        payer: &Keypair,
+        digest: [u8; 32],
+        vaa: PostVAAData,
+        kind: RedemptionKind,
+        to: Pubkey,
+    ) -> Result<RedemptionProbe, BanksClientError> {
+        let instruction = instructions::probe_redemption(program, bridge, digest, vaa, kind, to)
+            .expect("Could not create Probe Redemption instruction");
+
+        let data = return_data(client, payer, instruction).await?;
+        Ok(RedemptionProbe::deserialize(&mut data.as_slice()).unwrap())
+    }
+
+    pub async fn set_metadata_authority(
+        client: &mut BanksClient,
+        program: Pubkey,
+        message_acc: Pubkey,
+        vaa: PostVAAData,
+        payload: GovernancePayloadSetMetadataAuthority,
+        payer: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let instruction = instructions::set_metadata_authority(
+            program,
+            payer.pubkey(),
+            message_acc,
+            vaa,
+            payload,
+        )
+        .expect("Could not create Set Metadata Authority instruction");
+
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn set_transfer_cap(
+        client: &mut BanksClient,
+        program: Pubkey,
+        message_acc: Pubkey,
+        vaa: PostVAAData,
+        payload: GovernancePayloadSetTransferCap,
+        payer: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let instruction =
+            instructions::set_transfer_cap(program, payer.pubkey(), message_acc, vaa, payload)
+                .expect("Could not create Set Transfer Cap instruction");
+
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn recover_chain_id(
+        client: &mut BanksClient,
+        program: Pubkey,
+        message_acc: Pubkey,
+        vaa: PostVAAData,
+        payer: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let instruction = instructions::recover_chain_id(program, payer.pubkey(), message_acc, vaa)
+            .expect("Could not create Recover Chain Id instruction");
+
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn override_transfer_cap(
+        client: &mut BanksClient,
+        program: Pubkey,
+        message_acc: Pubkey,
+        vaa: PostVAAData,
+        payload: GovernancePayloadOverrideTransferCap,
+        payer: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let instruction =
+            instructions::override_transfer_cap(program, payer.pubkey(), message_acc, vaa, payload)
+                .expect("Could not create Override Transfer Cap instruction");
+
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn set_yield_strategy(
+        client: &mut BanksClient,
+        program: Pubkey,
+        message_acc: Pubkey,
+        vaa: PostVAAData,
+        payload: GovernancePayloadSetYieldStrategy,
+        payer: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let instruction =
+            instructions::set_yield_strategy(program, payer.pubkey(), message_acc, vaa, payload)
+                .expect("Could not create Set Yield Strategy instruction");
+
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn repay_custody(
+        client: &mut BanksClient,
+        program: Pubkey,
+        payer: &Keypair,
+        mint: Pubkey,
+        from: Pubkey,
+        from_owner: &Keypair,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let instruction =
+            instructions::repay_custody(program, mint, from, from_owner.pubkey(), amount)
+                .expect("Could not create Repay Custody instruction");
+
+        execute(
+            client,
+            payer,
+            &[payer, from_owner],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn set_relayer_allowlist(
+        client: &mut BanksClient,
+        program: Pubkey,
+        payer: &Keypair,
+        owner: &Keypair,
+        token_account: Pubkey,
+        relayers: Vec<Pubkey>,
+    ) -> Result<(), BanksClientError> {
+        let instruction = instructions::set_relayer_allowlist(
+            program,
+            payer.pubkey(),
+            owner.pubkey(),
+            token_account,
+            relayers,
+        )
+        .expect("Could not create Set Relayer Allowlist instruction");
+
+        execute(
+            client,
+            payer,
+            &[payer, owner],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn rotate_metadata_authority(
+        client: &mut BanksClient,
+        program: Pubkey,
+        payer: &Keypair,
+        mint: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let instruction = instructions::rotate_metadata_authority(program, mint)
+            .expect("Could not create Rotate Metadata Authority instruction");
+
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     pub async fn create_mint(
         client: &mut BanksClient,
         payer: &Keypair,
         mint_authority: &Pubkey,
         mint: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        create_mint_with_freeze_authority(client, payer, mint_authority, None, mint).await
+    }
+
+    pub async fn create_mint_with_freeze_authority(
+        client: &mut BanksClient,
+        payer: &Keypair,
+        mint_authority: &Pubkey,
+        freeze_authority: Option<&Pubkey>,
+        mint: &Keypair,
     ) -> Result<(), BanksClientError> {
         let mint_key = mint.pubkey();
         execute(
@@ -593,7 +1100,7 @@ mod helpers {
                     &spl_token::id(),
                     &mint_key,
                     mint_authority,
-                    None,
+                    freeze_authority,
                     0,
                 )
                 .unwrap(),
@@ -603,6 +1110,30 @@ mod helpers {
         .await
     }
 
+    pub async fn freeze_token_account(
+        client: &mut BanksClient,
+        payer: &Keypair,
+        freeze_authority: &Keypair,
+        mint: &Pubkey,
+        token_account: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer, freeze_authority],
+            &[spl_token::instruction::freeze_account(
+                &spl_token::id(),
+                token_account,
+                mint,
+                &freeze_authority.pubkey(),
+                &[],
+            )
+            .unwrap()],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn create_spl_metadata(
         client: &mut BanksClient,
@@ -784,7 +1315,11 @@ mod helpers {
                         payer.pubkey(),
                         guardian_set_version,
                         signature_set.pubkey(),
-                        bridge::VerifySignaturesData { signers },
+                        bridge::VerifySignaturesData {
+                            signers,
+                            hash: None,
+                            signer_offset: 0,
+                        },
                     )
                     .unwrap(),
                 ],
@@ -2,19 +2,25 @@
 use bridge::{
     accounts::{
         PostedVAA,
+        PostedVAAData,
         PostedVAADerivationData,
     },
+    DeserializePayload,
     SerializePayload,
 };
 use libsecp256k1::SecretKey;
 use primitive_types::U256;
 use rand::Rng;
-use solana_program::pubkey::Pubkey;
+use solana_program::{
+    instruction::AccountMeta,
+    pubkey::Pubkey,
+};
 use solana_program_test::{
     tokio,
     BanksClient,
 };
 use solana_sdk::{
+    commitment_config::CommitmentLevel,
     signature::{
         Keypair,
         Signer,
@@ -33,16 +39,49 @@ use std::{
 use token_bridge::{
     accounts::{
         ConfigAccount,
+        CustodyAttestation,
+        RecoveredChain,
+        RelayerAllowlist,
+        RelayerAllowlistDerivationData,
+        SplTokenMeta,
+        SplTokenMetaDerivationData,
+        TokenIdAccount,
+        TokenIdDerivationData,
+        TransferCap,
+        TransferCapDerivationData,
         WrappedDerivationData,
         WrappedMint,
+        YieldStrategy,
+        YieldStrategyDerivationData,
+    },
+    api::{
+        RedemptionKind,
+        RedemptionProbe,
     },
+    instructions,
     messages::{
+        GovernancePayloadOverrideTransferCap,
+        GovernancePayloadRecoverChainId,
+        GovernancePayloadSetMetadataAuthority,
+        GovernancePayloadSetTransferCap,
+        GovernancePayloadSetYieldStrategy,
         PayloadAssetMeta,
+        PayloadCustodyAttestation,
         PayloadGovernanceRegisterChain,
         PayloadTransfer,
         PayloadTransferWithPayload,
+        SplitShare,
+    },
+    types::{
+        Config,
+        CustodyAttestationData,
+        RecoveredChainData,
+        RelayerAllowlistData,
+        SplAccount,
+        TokenId,
+        TransferCapData,
+        YieldStrategyData,
     },
-    types::Config,
 };
 
 mod common;
@@ -327,6 +366,145 @@ async fn attest() {
     .unwrap();
 }
 
+#[tokio::test]
+async fn attest_and_transfer_native() {
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref mint,
+        ref token_account,
+        ref token_authority,
+        ..
+    } = set_up().await.unwrap();
+
+    let attest_message = &Keypair::new();
+    let message = &Keypair::new();
+    common::attest_and_transfer_native(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        attest_message,
+        message,
+        token_account,
+        token_authority,
+        mint.pubkey(),
+        100,
+    )
+    .await
+    .unwrap();
+
+    // The attestation precedes the transfer.
+    let attestation: PostedVAAData = common::get_account_data(client, attest_message.pubkey())
+        .await
+        .unwrap();
+    let asset = PayloadAssetMeta::deserialize(&mut attestation.payload.as_slice()).unwrap();
+    assert_eq!(asset.token_address, mint.pubkey().to_bytes());
+    assert_eq!(asset.token_chain, CHAIN_ID_SOLANA);
+
+    let posted: PostedVAAData = common::get_account_data(client, message.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(posted.sequence, attestation.sequence + 1);
+    let transfer = PayloadTransfer::deserialize(&mut posted.payload.as_slice()).unwrap();
+    assert_eq!(transfer.token_address, mint.pubkey().to_bytes());
+    assert_eq!(transfer.amount, U256::from(100));
+}
+
+#[tokio::test]
+async fn attest_custody() {
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref mint,
+        ref token_account,
+        ref token_authority,
+        ..
+    } = set_up().await.unwrap();
+
+    // Nothing is in custody before the first transfer.
+    let message = &Keypair::new();
+    assert!(common::attest_custody(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        message,
+        &[mint.pubkey()],
+    )
+    .await
+    .is_err());
+
+    common::transfer_native(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        &Keypair::new(),
+        token_account,
+        token_authority,
+        mint.pubkey(),
+        100,
+    )
+    .await
+    .unwrap();
+
+    let attestation_key =
+        CustodyAttestation::<'_, { AccountState::Initialized }>::key(None, &token_bridge);
+    let mut previous_hash = [0u8; 32];
+    for index in 0..2 {
+        let message = &Keypair::new();
+        common::attest_custody(
+            client,
+            token_bridge,
+            bridge,
+            payer,
+            message,
+            &[mint.pubkey()],
+        )
+        .await
+        .unwrap();
+
+        let posted: PostedVAAData = common::get_account_data(client, message.pubkey())
+            .await
+            .unwrap();
+        let payload =
+            PayloadCustodyAttestation::deserialize(&mut posted.payload.as_slice()).unwrap();
+        assert_eq!(payload.index, index);
+        assert_eq!(payload.previous_hash, previous_hash);
+        assert_eq!(payload.custodies.len(), 1);
+        assert_eq!(payload.custodies[0].token_address, mint.pubkey().to_bytes());
+        assert_eq!(payload.custodies[0].amount, U256::from(100));
+
+        // The attestation account anchors the next attestation.
+        let attestation: CustodyAttestationData = common::get_account_data(client, attestation_key)
+            .await
+            .unwrap();
+        assert_eq!(attestation.index, index);
+        assert_eq!(
+            attestation.hash,
+            solana_program::keccak::hash(&posted.payload).to_bytes()
+        );
+        previous_hash = attestation.hash;
+    }
+
+    // Custody accounts are attested once, in ascending order of their mint.
+    assert!(common::attest_custody(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        &Keypair::new(),
+        &[mint.pubkey(), mint.pubkey()],
+    )
+    .await
+    .is_err());
+}
+
 #[tokio::test]
 async fn transfer_native() {
     let Context {
@@ -357,6 +535,74 @@ async fn transfer_native() {
     .unwrap();
 }
 
+#[tokio::test]
+async fn transfer_native_frozen() {
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref mint_authority,
+        ref token_authority,
+        ..
+    } = set_up().await.unwrap();
+
+    // Native mints with a freeze authority can freeze the sender's account.
+    let mint = Keypair::new();
+    let token_account = Keypair::new();
+    common::create_mint_with_freeze_authority(
+        client,
+        payer,
+        &mint_authority.pubkey(),
+        Some(&mint_authority.pubkey()),
+        &mint,
+    )
+    .await
+    .unwrap();
+    common::create_token_account(
+        client,
+        payer,
+        &token_account,
+        &token_authority.pubkey(),
+        &mint.pubkey(),
+    )
+    .await
+    .unwrap();
+    common::mint_tokens(
+        client,
+        payer,
+        mint_authority,
+        &mint,
+        &token_account.pubkey(),
+        1000,
+    )
+    .await
+    .unwrap();
+    common::freeze_token_account(
+        client,
+        payer,
+        mint_authority,
+        &mint.pubkey(),
+        &token_account.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    assert!(common::transfer_native(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        &Keypair::new(),
+        &token_account,
+        token_authority,
+        mint.pubkey(),
+        100,
+    )
+    .await
+    .is_err());
+}
+
 async fn register_chain(context: &mut Context) {
     let Context {
         ref payer,
@@ -402,6 +648,105 @@ async fn register_chain(context: &mut Context) {
     .unwrap();
 }
 
+#[tokio::test]
+async fn register_chain_rejects_solana() {
+    let mut context = set_up().await.unwrap();
+    let Context {
+        ref payer,
+        ref mut client,
+        ref bridge,
+        ref token_bridge,
+        ref guardian_keys,
+        ..
+    } = context;
+
+    let nonce = rand::thread_rng().gen();
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let payload = PayloadGovernanceRegisterChain {
+        chain: CHAIN_ID_SOLANA,
+        endpoint_address: [1u8; 32],
+    };
+    let message = payload.try_to_vec().unwrap();
+
+    let (vaa, body, _) = common::generate_vaa(emitter.pubkey().to_bytes(), 1, message, nonce, 0);
+    let signature_set = common::verify_signatures(client, bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, *bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        bridge,
+    );
+
+    assert!(common::register_chain(
+        client,
+        *token_bridge,
+        *bridge,
+        message_key,
+        vaa,
+        payload,
+        payer,
+    )
+    .await
+    .is_err());
+
+    // Other chains still register.
+    register_chain(&mut context).await;
+}
+
+#[tokio::test]
+async fn recover_chain_id() {
+    let mut context = set_up().await.unwrap();
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref guardian_keys,
+        ..
+    } = context;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+
+    // Decrees name the deployment they recover, others can't execute them.
+    for (sequence, program) in [(0, Pubkey::new_unique()), (1, token_bridge)] {
+        let message = GovernancePayloadRecoverChainId {
+            program,
+            new_chain_id: 5000,
+        }
+        .try_to_vec()
+        .unwrap();
+        let nonce = rand::thread_rng().gen();
+        let (vaa, body, _) =
+            common::generate_vaa(emitter.pubkey().to_bytes(), 1, message, nonce, sequence);
+        let signature_set =
+            common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+                .await
+                .unwrap();
+        common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+            .await
+            .unwrap();
+        let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+            &PostedVAADerivationData {
+                payload_hash: body.to_vec(),
+            },
+            &bridge,
+        );
+        let result = common::recover_chain_id(client, token_bridge, message_key, vaa, payer).await;
+        assert_eq!(result.is_ok(), program == token_bridge);
+    }
+
+    let recovered_chain =
+        RecoveredChain::<'_, { AccountState::Initialized }>::key(None, &token_bridge);
+    let recovered_chain: RecoveredChainData = common::get_account_data(client, recovered_chain)
+        .await
+        .unwrap();
+    assert_eq!(recovered_chain.chain, 5000);
+}
+
 #[tokio::test]
 async fn transfer_native_in() {
     let mut context = set_up().await.unwrap();
@@ -474,78 +819,98 @@ async fn transfer_native_in() {
 }
 
 #[tokio::test]
-async fn transfer_wrapped() {
+async fn transfer_native_in_to_new_wallet() {
     let mut context = set_up().await.unwrap();
     register_chain(&mut context).await;
-    let to = create_wrapped_account(&mut context).await.unwrap();
     let Context {
         ref payer,
         ref mut client,
         bridge,
         token_bridge,
+        ref mint,
+        ref token_account,
         ref token_authority,
         ref guardian_keys,
         ..
     } = context;
 
-    let nonce = rand::thread_rng().gen();
+    let message = &Keypair::new();
+    common::transfer_native(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        message,
+        token_account,
+        token_authority,
+        mint.pubkey(),
+        100,
+    )
+    .await
+    .unwrap();
 
+    // The recipient wallet has no token account yet, the relayer creates it.
+    let owner = Keypair::new().pubkey();
+    let to = spl_associated_token_account::get_associated_token_address(&owner, &mint.pubkey());
     let payload = PayloadTransfer {
-        amount: U256::from(100000000),
-        token_address: [1u8; 32],
-        token_chain: 2,
+        amount: U256::from(100u128),
+        token_address: mint.pubkey().to_bytes(),
+        token_chain: 1,
         to: to.to_bytes(),
         to_chain: 1,
-        fee: U256::from(0),
+        fee: U256::from(0u128),
     };
     let message = payload.try_to_vec().unwrap();
 
-    let (vaa, body, _) =
-        common::generate_vaa([0u8; 32], 2, message, nonce, rand::thread_rng().gen());
+    let nonce = rand::thread_rng().gen();
+    let (vaa, body, _) = common::generate_vaa([0u8; 32], 2, message, nonce, 1);
     let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
         .await
         .unwrap();
     common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
         .await
         .unwrap();
-    let msg_derivation_data = &PostedVAADerivationData {
-        payload_hash: body.to_vec(),
-    };
-    let message_key =
-        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, &bridge);
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        &bridge,
+    );
 
-    common::complete_transfer_wrapped(
+    // Only the associated token account of the owner is created.
+    assert!(common::complete_native_to_associated(
         client,
         token_bridge,
         bridge,
         message_key,
-        vaa,
-        payload,
+        vaa.clone(),
+        payload.clone(),
+        Keypair::new().pubkey(),
         payer,
     )
     .await
-    .unwrap();
+    .is_err());
 
-    // Now transfer the wrapped tokens back, which will burn them.
-    let message = &Keypair::new();
-    common::transfer_wrapped(
+    common::complete_native_to_associated(
         client,
         token_bridge,
         bridge,
+        message_key,
+        vaa,
+        payload,
+        owner,
         payer,
-        message,
-        to,
-        token_authority,
-        2,
-        [1u8; 32],
-        10000000,
     )
     .await
     .unwrap();
+
+    let account: SplAccount = common::get_account_data(client, to).await.unwrap();
+    assert_eq!(account.owner, owner);
+    assert_eq!(account.amount, 100);
 }
 
 #[tokio::test]
-async fn transfer_native_with_payload_in() {
+async fn complete_transfers_batch() {
     let mut context = set_up().await.unwrap();
     register_chain(&mut context).await;
     let Context {
@@ -560,8 +925,6 @@ async fn transfer_native_with_payload_in() {
         ..
     } = context;
 
-    // Do an initial transfer so that the bridge account has some native tokens. This also creates
-    // the custody account.
     let message = &Keypair::new();
     common::transfer_native(
         client,
@@ -572,26 +935,292 @@ async fn transfer_native_with_payload_in() {
         token_account,
         token_authority,
         mint.pubkey(),
-        100,
+        300,
     )
     .await
     .unwrap();
 
-    let nonce = rand::thread_rng().gen();
-    let from_address = Keypair::new().pubkey().to_bytes();
-    let payload: Vec<u8> = vec![1, 2, 3];
-    let payload = PayloadTransferWithPayload {
+    // Deposits to the same account, the fee of the last one also paid to it.
+    let mut vaas = Vec::new();
+    let mut payloads = Vec::new();
+    for (sequence, (amount, fee)) in [(100u128, 0u128), (50, 0), (25, 5)].iter().enumerate() {
+        let payload = PayloadTransfer {
+            amount: U256::from(*amount),
+            token_address: mint.pubkey().to_bytes(),
+            token_chain: 1,
+            to: token_account.pubkey().to_bytes(),
+            to_chain: 1,
+            fee: U256::from(*fee),
+        };
+        let message = payload.try_to_vec().unwrap();
+
+        let nonce = rand::thread_rng().gen();
+        let (vaa, body, _) =
+            common::generate_vaa([0u8; 32], 2, message, nonce, 1 + sequence as u64);
+        let signature_set =
+            common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+                .await
+                .unwrap();
+        common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+            .await
+            .unwrap();
+        let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+            &PostedVAADerivationData {
+                payload_hash: body.to_vec(),
+            },
+            &bridge,
+        );
+        vaas.push((message_key, vaa));
+        payloads.push(payload);
+    }
+
+    // A transfer repeated within the batch only redeems once.
+    let repeated = [vaas[0].clone(), vaas[1].clone(), vaas[0].clone()];
+    assert!(common::complete_transfers_batch(
+        client,
+        token_bridge,
+        bridge,
+        &repeated,
+        token_account.pubkey(),
+        mint.pubkey(),
+        payer,
+    )
+    .await
+    .is_err());
+
+    let before: SplAccount = common::get_account_data(client, token_account.pubkey())
+        .await
+        .unwrap();
+    common::complete_transfers_batch(
+        client,
+        token_bridge,
+        bridge,
+        &vaas,
+        token_account.pubkey(),
+        mint.pubkey(),
+        payer,
+    )
+    .await
+    .unwrap();
+    let after: SplAccount = common::get_account_data(client, token_account.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(after.amount - before.amount, 175);
+
+    // Every transfer of the batch is claimed.
+    assert!(common::complete_native(
+        client,
+        token_bridge,
+        bridge,
+        vaas[2].0,
+        vaas[2].1.clone(),
+        payloads[2].clone(),
+        payer,
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn probe_redemption() {
+    let mut context = set_up().await.unwrap();
+    register_chain(&mut context).await;
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref mint,
+        ref token_account,
+        ref token_authority,
+        ref guardian_keys,
+        ..
+    } = context;
+
+    let message = &Keypair::new();
+    common::transfer_native(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        message,
+        token_account,
+        token_authority,
+        mint.pubkey(),
+        100,
+    )
+    .await
+    .unwrap();
+
+    let nonce = rand::thread_rng().gen();
+
+    let payload = PayloadTransfer {
         amount: U256::from(100u128),
         token_address: mint.pubkey().to_bytes(),
-        token_chain: CHAIN_ID_SOLANA,
-        to: token_authority.pubkey().to_bytes(),
-        to_chain: CHAIN_ID_SOLANA,
-        from_address,
-        payload,
+        token_chain: 1,
+        to: token_account.pubkey().to_bytes(),
+        to_chain: 1,
+        fee: U256::from(0u128),
     };
     let message = payload.try_to_vec().unwrap();
+    let (vaa, body, _) = common::generate_vaa([0u8; 32], 2, message, nonce, 1);
 
-    let (vaa, body, _) = common::generate_vaa([0u8; 32], CHAIN_ID_ETH, message, nonce, 1);
+    // Redemptions can be probed before their VAA is posted, after it is posted and once they are
+    // complete.
+    let probe = common::probe_redemption(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        body,
+        vaa.clone(),
+        RedemptionKind::Native,
+        token_account.pubkey(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        probe,
+        RedemptionProbe {
+            posted_vaa: false,
+            claim: false,
+            to: true,
+            mint: true,
+            chain_registration: true,
+        }
+    );
+
+    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let probe = common::probe_redemption(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        body,
+        vaa.clone(),
+        RedemptionKind::Native,
+        token_account.pubkey(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        probe,
+        RedemptionProbe {
+            posted_vaa: true,
+            claim: false,
+            to: true,
+            mint: true,
+            chain_registration: true,
+        }
+    );
+
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        &bridge,
+    );
+    common::complete_native(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa.clone(),
+        payload.clone(),
+        payer,
+    )
+    .await
+    .unwrap();
+    let probe = common::probe_redemption(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        body,
+        vaa,
+        RedemptionKind::Native,
+        token_account.pubkey(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        probe,
+        RedemptionProbe {
+            posted_vaa: true,
+            claim: true,
+            to: true,
+            mint: true,
+            chain_registration: true,
+        }
+    );
+
+    // Transfers from unregistered emitters are never redeemable.
+    let message = payload.try_to_vec().unwrap();
+    let (vaa, body, _) = common::generate_vaa([0u8; 32], 3, message, nonce, 2);
+    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let probe = common::probe_redemption(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        body,
+        vaa,
+        RedemptionKind::Native,
+        token_account.pubkey(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        probe,
+        RedemptionProbe {
+            posted_vaa: true,
+            claim: false,
+            to: true,
+            mint: true,
+            chain_registration: false,
+        }
+    );
+}
+
+#[tokio::test]
+async fn transfer_wrapped() {
+    let mut context = set_up().await.unwrap();
+    register_chain(&mut context).await;
+    let to = create_wrapped_account(&mut context).await.unwrap();
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref token_authority,
+        ref guardian_keys,
+        ..
+    } = context;
+
+    let nonce = rand::thread_rng().gen();
+
+    let payload = PayloadTransfer {
+        amount: U256::from(100000000),
+        token_address: [1u8; 32],
+        token_chain: 2,
+        to: to.to_bytes(),
+        to_chain: 1,
+        fee: U256::from(0),
+    };
+    let message = payload.try_to_vec().unwrap();
+
+    let (vaa, body, _) =
+        common::generate_vaa([0u8; 32], 2, message, nonce, rand::thread_rng().gen());
     let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
         .await
         .unwrap();
@@ -604,17 +1233,924 @@ async fn transfer_native_with_payload_in() {
     let message_key =
         PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, &bridge);
 
-    common::complete_native_with_payload(
+    common::complete_transfer_wrapped(
         client,
         token_bridge,
         bridge,
         message_key,
         vaa,
         payload,
-        token_account.pubkey(),
+        payer,
+    )
+    .await
+    .unwrap();
+
+    // Now transfer the wrapped tokens back, which will burn them.
+    let message = &Keypair::new();
+    common::transfer_wrapped(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        message,
+        to,
+        token_authority,
+        2,
+        [1u8; 32],
+        10000000,
+        false,
+    )
+    .await
+    .unwrap();
+    let posted: PostedVAAData = common::get_account_data(client, message.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(posted.payload.len(), PayloadTransfer::LEN);
+
+    // Senders can have the burn recorded in the transfer.
+    let message = &Keypair::new();
+    common::transfer_wrapped(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        message,
+        to,
         token_authority,
+        2,
+        [1u8; 32],
+        10000000,
+        true,
+    )
+    .await
+    .unwrap();
+    let posted: PostedVAAData = common::get_account_data(client, message.pubkey())
+        .await
+        .unwrap();
+    let receipt = PayloadTransfer::burn_receipt(&posted.payload).unwrap();
+    assert_eq!(receipt.token_chain, 2);
+    assert_eq!(receipt.token_address, [1u8; 32]);
+    assert_eq!(
+        receipt.mint,
+        WrappedMint::<'_, { AccountState::Initialized }>::key(
+            &WrappedDerivationData {
+                token_chain: 2,
+                token_address: [1u8; 32],
+            },
+            &token_bridge,
+        )
+    );
+    assert_eq!(receipt.amount, 10000000);
+    assert_eq!(
+        PayloadTransfer::deserialize(&mut posted.payload.as_slice())
+            .unwrap()
+            .amount,
+        U256::from(10000000)
+    );
+}
+
+#[tokio::test]
+async fn transfer_native_with_payload_in() {
+    let mut context = set_up().await.unwrap();
+    register_chain(&mut context).await;
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref mint,
+        ref token_account,
+        ref token_authority,
+        ref guardian_keys,
+        ..
+    } = context;
+
+    // Do an initial transfer so that the bridge account has some native tokens. This also creates
+    // the custody account.
+    let message = &Keypair::new();
+    common::transfer_native(
+        client,
+        token_bridge,
+        bridge,
         payer,
+        message,
+        token_account,
+        token_authority,
+        mint.pubkey(),
+        100,
     )
     .await
     .unwrap();
+
+    let nonce = rand::thread_rng().gen();
+    let from_address = Keypair::new().pubkey().to_bytes();
+    let payload: Vec<u8> = vec![1, 2, 3];
+    let payload = PayloadTransferWithPayload {
+        amount: U256::from(100u128),
+        token_address: mint.pubkey().to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: token_authority.pubkey().to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        from_address,
+        payload,
+    };
+    let message = payload.try_to_vec().unwrap();
+
+    let (vaa, body, _) = common::generate_vaa([0u8; 32], CHAIN_ID_ETH, message, nonce, 1);
+    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let msg_derivation_data = &PostedVAADerivationData {
+        payload_hash: body.to_vec(),
+    };
+    let message_key =
+        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, &bridge);
+
+    common::complete_native_with_payload(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa,
+        payload,
+        token_account.pubkey(),
+        token_authority,
+        payer,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn transfer_native_with_payload_split() {
+    let mut context = set_up().await.unwrap();
+    register_chain(&mut context).await;
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref mint,
+        ref token_account,
+        ref token_authority,
+        ref guardian_keys,
+        ..
+    } = context;
+
+    let message = &Keypair::new();
+    common::transfer_native(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        message,
+        token_account,
+        token_authority,
+        mint.pubkey(),
+        100,
+    )
+    .await
+    .unwrap();
+
+    // Shares go to token accounts of other owners, the recipient keeps the rounding remainder.
+    let mut shares = vec![];
+    for bps in [3_333, 6_667] {
+        let share_account = Keypair::new();
+        common::create_token_account(
+            client,
+            payer,
+            &share_account,
+            &Keypair::new().pubkey(),
+            &mint.pubkey(),
+        )
+        .await
+        .unwrap();
+        shares.push(SplitShare {
+            token_account: share_account.pubkey(),
+            bps,
+        });
+    }
+
+    let balance = |account: Option<SplAccount>| account.unwrap().amount;
+    let before = balance(common::get_account_data(client, token_account.pubkey()).await);
+
+    let nonce = rand::thread_rng().gen();
+    let payload = PayloadTransferWithPayload {
+        amount: U256::from(99u128),
+        token_address: mint.pubkey().to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: token_authority.pubkey().to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        from_address: Keypair::new().pubkey().to_bytes(),
+        payload: PayloadTransferWithPayload::split_payload(&shares, &[1, 2, 3]),
+    };
+    let message = payload.try_to_vec().unwrap();
+
+    let (vaa, body, _) = common::generate_vaa([0u8; 32], CHAIN_ID_ETH, message, nonce, 1);
+    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        &bridge,
+    );
+
+    common::complete_native_with_payload(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa,
+        payload,
+        token_account.pubkey(),
+        token_authority,
+        payer,
+    )
+    .await
+    .unwrap();
+
+    for (share, expected) in shares.iter().zip([32, 66]) {
+        let account = common::get_account_data(client, share.token_account).await;
+        assert_eq!(balance(account), expected);
+    }
+    let after = balance(common::get_account_data(client, token_account.pubkey()).await);
+    assert_eq!(after - before, 1);
+}
+
+#[tokio::test]
+async fn transfer_native_with_payload_wrong_redeemer() {
+    let mut context = set_up().await.unwrap();
+    register_chain(&mut context).await;
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref mint,
+        ref token_account,
+        ref token_authority,
+        ref guardian_keys,
+        ..
+    } = context;
+
+    let message = &Keypair::new();
+    common::transfer_native(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        message,
+        token_account,
+        token_authority,
+        mint.pubkey(),
+        100,
+    )
+    .await
+    .unwrap();
+
+    let nonce = rand::thread_rng().gen();
+    let payload = PayloadTransferWithPayload {
+        amount: U256::from(100u128),
+        token_address: mint.pubkey().to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: token_authority.pubkey().to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        from_address: Keypair::new().pubkey().to_bytes(),
+        payload: vec![1, 2, 3],
+    };
+    let message = payload.try_to_vec().unwrap();
+
+    let (vaa, body, _) = common::generate_vaa([0u8; 32], CHAIN_ID_ETH, message, nonce, 1);
+    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let msg_derivation_data = &PostedVAADerivationData {
+        payload_hash: body.to_vec(),
+    };
+    let message_key =
+        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, &bridge);
+
+    // A third party cannot redeem into the recipient's token account by signing as the redeemer.
+    let attacker = &Keypair::new();
+    assert!(common::complete_native_with_payload(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa.clone(),
+        payload.clone(),
+        token_account.pubkey(),
+        attacker,
+        payer,
+    )
+    .await
+    .is_err());
+
+    // Nor through the regular transfer flow, which needs no redeemer at all.
+    let transfer = PayloadTransfer {
+        amount: payload.amount,
+        token_address: payload.token_address,
+        token_chain: payload.token_chain,
+        to: token_account.pubkey().to_bytes(),
+        to_chain: payload.to_chain,
+        fee: U256::zero(),
+    };
+    assert!(common::complete_native(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa.clone(),
+        transfer,
+        payer,
+    )
+    .await
+    .is_err());
+
+    // The transfer was not claimed, the intended redeemer can still complete it.
+    common::complete_native_with_payload(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa,
+        payload,
+        token_account.pubkey(),
+        token_authority,
+        payer,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn reclaim_expired_transfer() {
+    let mut context = set_up().await.unwrap();
+    register_chain(&mut context).await;
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref mint,
+        ref token_account,
+        ref token_authority,
+        ref guardian_keys,
+        ..
+    } = context;
+
+    // Fund the custody account so that completing the transfer could otherwise succeed.
+    let message = &Keypair::new();
+    common::transfer_native(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        message,
+        token_account,
+        token_authority,
+        mint.pubkey(),
+        100,
+    )
+    .await
+    .unwrap();
+
+    let from_address = Keypair::new().pubkey().to_bytes();
+    let transfer = |expiration: u64| PayloadTransferWithPayload {
+        amount: U256::from(100u128),
+        token_address: mint.pubkey().to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: token_authority.pubkey().to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        from_address,
+        payload: PayloadTransferWithPayload::expiring_payload(expiration, &[1, 2, 3]),
+    };
+
+    // An expired transfer can't be completed, only refunded once.
+    let payload = transfer(1);
+    let (vaa, body, _) =
+        common::generate_vaa([0u8; 32], CHAIN_ID_ETH, payload.try_to_vec().unwrap(), 0, 1);
+    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        &bridge,
+    );
+
+    assert!(common::complete_native_with_payload(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa.clone(),
+        payload,
+        token_account.pubkey(),
+        token_authority,
+        payer,
+    )
+    .await
+    .is_err());
+
+    let refund_message = &Keypair::new();
+    common::reclaim_expired_transfer(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa.clone(),
+        refund_message,
+        payer,
+    )
+    .await
+    .unwrap();
+    assert!(common::get_account_balance(client, refund_message.pubkey()).await > 0);
+
+    assert!(common::reclaim_expired_transfer(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa,
+        &Keypair::new(),
+        payer,
+    )
+    .await
+    .is_err());
+
+    // Transfers that haven't expired yet can't be refunded.
+    let payload = transfer(u64::MAX);
+    let (vaa, body, _) =
+        common::generate_vaa([0u8; 32], CHAIN_ID_ETH, payload.try_to_vec().unwrap(), 0, 2);
+    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        &bridge,
+    );
+
+    assert!(common::reclaim_expired_transfer(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa,
+        &Keypair::new(),
+        payer,
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn resolve_token_id() {
+    let mut context = set_up().await.unwrap();
+    let token_bridge = context.token_bridge;
+    let native = context.mint.pubkey();
+
+    // Native assets resolve to their own mint.
+    common::resolve_token_id(
+        &mut context.client,
+        token_bridge,
+        &context.payer,
+        CHAIN_ID_SOLANA,
+        native.to_bytes(),
+    )
+    .await
+    .unwrap();
+
+    let token_id_key = TokenIdAccount::<'_, { AccountState::Initialized }>::key(
+        &TokenIdDerivationData {
+            token_chain: CHAIN_ID_SOLANA,
+            token_address: native.to_bytes(),
+        },
+        &token_bridge,
+    );
+    let token_id: TokenId = common::get_account_data(&mut context.client, token_id_key)
+        .await
+        .unwrap();
+    assert_eq!(token_id.mint, native);
+    assert!(!token_id.is_wrapped);
+
+    // Wrapped assets resolve to the mint derived from their origin.
+    let wrapped = create_wrapped(&mut context).await;
+    common::resolve_token_id(
+        &mut context.client,
+        token_bridge,
+        &context.payer,
+        CHAIN_ID_ETH,
+        [1u8; 32],
+    )
+    .await
+    .unwrap();
+
+    let token_id_key = TokenIdAccount::<'_, { AccountState::Initialized }>::key(
+        &TokenIdDerivationData {
+            token_chain: CHAIN_ID_ETH,
+            token_address: [1u8; 32],
+        },
+        &token_bridge,
+    );
+    let token_id: TokenId = common::get_account_data(&mut context.client, token_id_key)
+        .await
+        .unwrap();
+    assert_eq!(token_id.mint, wrapped);
+    assert_eq!(token_id.chain, CHAIN_ID_ETH);
+    assert!(token_id.is_wrapped);
+
+    // Origins that were never attested cannot be resolved.
+    assert!(common::resolve_token_id(
+        &mut context.client,
+        token_bridge,
+        &context.payer,
+        CHAIN_ID_ETH,
+        [2u8; 32],
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn rotate_metadata_authority() {
+    let mut context = set_up().await.unwrap();
+    register_chain(&mut context).await;
+    let wrapped = create_wrapped(&mut context).await;
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref guardian_keys,
+        ..
+    } = context;
+
+    // Mints cannot be rotated before a decree.
+    assert!(
+        common::rotate_metadata_authority(client, token_bridge, payer, wrapped)
+            .await
+            .is_err()
+    );
+
+    let new_authority = Keypair::new().pubkey();
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let payload = GovernancePayloadSetMetadataAuthority {
+        new_authority,
+        mint: None,
+    };
+    let message = payload.try_to_vec().unwrap();
+
+    let nonce = rand::thread_rng().gen();
+    let (vaa, body, _) = common::generate_vaa(emitter.pubkey().to_bytes(), 1, message, nonce, 1);
+    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        &bridge,
+    );
+    common::set_metadata_authority(client, token_bridge, message_key, vaa, payload, payer)
+        .await
+        .unwrap();
+
+    common::rotate_metadata_authority(client, token_bridge, payer, wrapped)
+        .await
+        .unwrap();
+
+    let spl_metadata = SplTokenMeta::key(
+        &SplTokenMetaDerivationData { mint: wrapped },
+        &spl_token_metadata::id(),
+    );
+    let account = client.get_account(spl_metadata).await.unwrap().unwrap();
+    let metadata = spl_token_metadata::utils::meta_deser_unchecked(&mut &account.data[..]).unwrap();
+    assert_eq!(metadata.update_authority, new_authority);
+
+    // Rotated mints are no longer managed by the token bridge.
+    assert!(
+        common::rotate_metadata_authority(client, token_bridge, payer, wrapped)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn set_transfer_cap() {
+    let mut context = set_up().await.unwrap();
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref guardian_keys,
+        ref mint,
+        ..
+    } = context;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let transfer_cap_key = TransferCap::<'_, { AccountState::Initialized }>::key(
+        &TransferCapDerivationData {
+            mint: mint.pubkey(),
+        },
+        &token_bridge,
+    );
+
+    let payload = GovernancePayloadSetTransferCap {
+        mint: mint.pubkey(),
+        max_amount: 1000,
+    };
+    let message = payload.try_to_vec().unwrap();
+    let nonce = rand::thread_rng().gen();
+    let (vaa, body, _) = common::generate_vaa(emitter.pubkey().to_bytes(), 1, message, nonce, 1);
+    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        &bridge,
+    );
+    common::set_transfer_cap(client, token_bridge, message_key, vaa, payload, payer)
+        .await
+        .unwrap();
+
+    let transfer_cap: TransferCapData = common::get_account_data(client, transfer_cap_key)
+        .await
+        .unwrap();
+    assert_eq!(transfer_cap.max_amount, 1000);
+    assert_eq!(transfer_cap.max_amount(0), 1000);
+
+    // Overrides replace the cap until they expire, the decreed cap applies again afterwards.
+    let payload = GovernancePayloadOverrideTransferCap {
+        mint: mint.pubkey(),
+        max_amount: 10,
+        until: 2_000_000_000,
+    };
+    let message = payload.try_to_vec().unwrap();
+    let nonce = rand::thread_rng().gen();
+    let (vaa, body, _) = common::generate_vaa(emitter.pubkey().to_bytes(), 1, message, nonce, 2);
+    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        &bridge,
+    );
+    common::override_transfer_cap(client, token_bridge, message_key, vaa, payload, payer)
+        .await
+        .unwrap();
+
+    let transfer_cap: TransferCapData = common::get_account_data(client, transfer_cap_key)
+        .await
+        .unwrap();
+    assert_eq!(transfer_cap.max_amount(1_999_999_999), 10);
+    assert_eq!(transfer_cap.max_amount(2_000_000_000), 1000);
+}
+
+#[tokio::test]
+async fn set_yield_strategy() {
+    let mut context = set_up().await.unwrap();
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref guardian_keys,
+        ref mint,
+        ref token_account,
+        ref token_authority,
+        ..
+    } = context;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let yield_strategy_key = YieldStrategy::<'_, { AccountState::Initialized }>::key(
+        &YieldStrategyDerivationData {
+            mint: mint.pubkey(),
+        },
+        &token_bridge,
+    );
+
+    // Create the custody account.
+    common::transfer_native(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        &Keypair::new(),
+        token_account,
+        token_authority,
+        mint.pubkey(),
+        100,
+    )
+    .await
+    .unwrap();
+
+    let strategy = Pubkey::new_unique();
+    let payload = GovernancePayloadSetYieldStrategy {
+        mint: mint.pubkey(),
+        strategy,
+        max_amount: 60,
+        max_share_bps: 2_500,
+    };
+    let message = payload.try_to_vec().unwrap();
+    let nonce = rand::thread_rng().gen();
+    let (vaa, body, _) = common::generate_vaa(emitter.pubkey().to_bytes(), 1, message, nonce, 1);
+    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        &bridge,
+    );
+    common::set_yield_strategy(client, token_bridge, message_key, vaa, payload, payer)
+        .await
+        .unwrap();
+
+    let yield_strategy: YieldStrategyData = common::get_account_data(client, yield_strategy_key)
+        .await
+        .unwrap();
+    assert_eq!(yield_strategy.strategy, strategy);
+    assert_eq!(yield_strategy.lent, 0);
+
+    // Both caps apply, the share against the custody balance plus what is lent.
+    assert!(yield_strategy.can_lend(100, 25));
+    assert!(!yield_strategy.can_lend(100, 26));
+    assert!(yield_strategy.can_lend(1_000, 60));
+    assert!(!yield_strategy.can_lend(1_000, 61));
+    let lent = YieldStrategyData {
+        lent: 20,
+        ..yield_strategy
+    };
+    assert_eq!(lent.bridged_supply(80), 100);
+    assert!(lent.can_lend(80, 5));
+    assert!(!lent.can_lend(80, 6));
+
+    // Only the borrower PDA of the strategy program borrows.
+    let imposter = Keypair::new();
+    let mut instruction = instructions::lend_custody(
+        token_bridge,
+        strategy,
+        mint.pubkey(),
+        token_account.pubkey(),
+        1,
+    )
+    .unwrap();
+    instruction.accounts[4] = AccountMeta::new_readonly(imposter.pubkey(), true);
+    assert!(common::execute(
+        client,
+        payer,
+        &[payer, &imposter],
+        &[instruction],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .is_err());
+
+    // Nothing is owed yet.
+    assert!(common::repay_custody(
+        client,
+        token_bridge,
+        payer,
+        mint.pubkey(),
+        token_account.pubkey(),
+        token_authority,
+        1,
+    )
+    .await
+    .is_err());
+
+    // Attestations read what is lent from the strategy account.
+    let message = &Keypair::new();
+    common::attest_custody(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        message,
+        &[mint.pubkey()],
+    )
+    .await
+    .unwrap();
+    let posted: PostedVAAData = common::get_account_data(client, message.pubkey())
+        .await
+        .unwrap();
+    let payload = PayloadCustodyAttestation::deserialize(&mut posted.payload.as_slice()).unwrap();
+    assert_eq!(payload.custodies[0].amount, U256::from(100));
+}
+
+#[tokio::test]
+async fn set_relayer_allowlist() {
+    let mut context = set_up().await.unwrap();
+    let Context {
+        ref payer,
+        ref mut client,
+        token_bridge,
+        ref token_account,
+        ref token_authority,
+        ..
+    } = context;
+    let allowlist_key = RelayerAllowlist::<'_, { AccountState::Initialized }>::key(
+        &RelayerAllowlistDerivationData {
+            token_account: token_account.pubkey(),
+        },
+        &token_bridge,
+    );
+    let relayers = vec![payer.pubkey(), Keypair::new().pubkey()];
+
+    common::set_relayer_allowlist(
+        client,
+        token_bridge,
+        payer,
+        token_authority,
+        token_account.pubkey(),
+        relayers[..1].to_vec(),
+    )
+    .await
+    .unwrap();
+    let allowlist: RelayerAllowlistData = common::get_account_data(client, allowlist_key)
+        .await
+        .unwrap();
+    assert_eq!(allowlist.relayers, relayers[..1]);
+    assert!(allowlist.allows(&payer.pubkey()));
+    assert!(!allowlist.allows(&relayers[1]));
+
+    // Later lists replace the previous one, the account grows and shrinks with them.
+    for relayers in [relayers.clone(), vec![]] {
+        common::set_relayer_allowlist(
+            client,
+            token_bridge,
+            payer,
+            token_authority,
+            token_account.pubkey(),
+            relayers.clone(),
+        )
+        .await
+        .unwrap();
+        let allowlist: RelayerAllowlistData = common::get_account_data(client, allowlist_key)
+            .await
+            .unwrap();
+        assert_eq!(allowlist.relayers, relayers);
+    }
+    assert!(RelayerAllowlistData::default().allows(&relayers[1]));
+
+    // Only the owner of the token account restricts redemptions to it.
+    assert!(common::set_relayer_allowlist(
+        client,
+        token_bridge,
+        payer,
+        &Keypair::new(),
+        token_account.pubkey(),
+        relayers.clone(),
+    )
+    .await
+    .is_err());
+
+    // Lists are bounded.
+    assert!(common::set_relayer_allowlist(
+        client,
+        token_bridge,
+        payer,
+        token_authority,
+        token_account.pubkey(),
+        vec![payer.pubkey(); RelayerAllowlistData::MAX_RELAYERS + 1],
+    )
+    .await
+    .is_err());
 }
@@ -34,6 +34,7 @@ use solitaire::{
     AccountState,
     Info,
     Mut,
+    ReadOnly,
     Sysvar,
 };
 
@@ -217,6 +218,12 @@ impl<T: Wrap> Wrap for Mut<T> {
     }
 }
 
+impl<T: Wrap> Wrap for ReadOnly<T> {
+    fn wrap(a: &AccEntry) -> StdResult<Vec<AccountMeta>, ErrBox> {
+        T::wrap(a)
+    }
+}
+
 /// Trait used on client side to easily validate a program accounts + ix_data for a bare Solana call
 pub trait ToInstruction {
     fn to_ix(
@@ -53,6 +53,15 @@ pub enum SolitaireError {
 
     /// User does not have sufficient funds for the tx
     InsufficientFunds,
+
+    /// The writable accounts held a different total of lamports after the instruction than
+    /// before, see [`lamports`](crate::processors::lamports). Items are the totals before and
+    /// after.
+    LamportImbalance(u128, u128),
+
+    /// No account with the key of a field was passed to an instruction resolving its accounts by
+    /// key, see [`named`](crate::processors::named). Items are the field and its key.
+    MissingNamedAccount(&'static str, Pubkey),
 }
 
 impl From<ProgramError> for SolitaireError {
@@ -39,6 +39,15 @@ pub use crate::{
     },
     macros::*,
     processors::{
+        event::{
+            emit_cpi,
+            Event,
+            EventSchema,
+        },
+        invoke::{
+            invoke,
+            invoke_signed,
+        },
         keyed::Keyed,
         peel::Peel,
         persist::Persist,
@@ -63,6 +72,9 @@ pub struct ExecutionContext<'a, 'b: 'a> {
 
     /// All accounts passed into the program
     pub accounts: &'a [AccountInfo<'b>],
+
+    /// Whether the accounts were resolved by key, see [`processors::named`].
+    pub named: bool,
 }
 
 /// Lamports to pay to an account being created
@@ -92,4 +104,14 @@ pub trait FromAccounts<'a, 'b: 'a> {
     fn from<T>(_: &'a Pubkey, _: &mut Iter<'a, AccountInfo<'b>>, _: &'a T) -> Result<Box<Self>>
     where
         Self: Sized;
+
+    /// As `from`, but resolving accounts by key where they can be, see
+    /// [`named`](crate::processors::named).
+    fn from_named<T>(
+        _: &'a Pubkey,
+        _: &mut Iter<'a, AccountInfo<'b>>,
+        _: &'a T,
+    ) -> Result<Box<Self>>
+    where
+        Self: Sized;
 }
@@ -17,6 +17,23 @@ macro_rules! trace_impl {
     ( $($arg:tt)* ) => {};
 }
 
+/// Structured trace output for audit tooling. Each call emits a single line of the form
+/// `trace <event> key=value ...`, so traces of two program versions executing the same
+/// transaction can be diffed mechanically. Compiled out unless the `trace` feature is enabled.
+///
+/// ```ignore
+/// trace_kv!("check", kind = "signer", account = info.key, ok = info.is_signer);
+/// ```
+#[macro_export]
+macro_rules! trace_kv {
+    ( $event:literal $(, $key:ident = $value:expr )* $(,)? ) => {
+        $crate::trace!(
+            concat!("trace ", $event $(, " ", stringify!($key), "={}")*)
+            $(, $value)*
+        )
+    };
+}
+
 /// This is our main codegen macro. It takes as input a list of enum-like variants mapping field
 /// types to function calls. The generated code produces:
 ///
@@ -60,11 +77,19 @@ macro_rules! solitaire {
                     use super::*;
 
                     #[inline(never)]
-                    pub fn execute<'a, 'b: 'a, 'c>(p: &Pubkey, a: &'c [AccountInfo<'b>], d: &[u8]) -> Result<()> {
+                    pub fn execute<'a, 'b: 'a, 'c>(p: &Pubkey, a: &'c [AccountInfo<'b>], d: &[u8], named: bool) -> Result<()> {
+                        solitaire::trace_kv!("instruction", name = stringify!($row), accounts = a.len(), data_len = d.len(), named = named);
+                        use solitaire::processors::compute::measure;
                         let ix_data = BorshDeserialize::try_from_slice(d).map_err(|e| SolitaireError::InstructionDeserializeFailed(e))?;
-                        let mut accounts = FromAccounts::from(p, &mut a.iter(), &())?;
-                        $fn(&ExecutionContext{program_id: p, accounts: a}, &mut accounts, ix_data)?;
-                        Persist::persist(accounts.as_ref(), p)?;
+                        let mut accounts = measure("accounts", || if named {
+                            FromAccounts::from_named(p, &mut a.iter(), &())
+                        } else {
+                            FromAccounts::from(p, &mut a.iter(), &())
+                        })?;
+                        let lamports = solitaire::processors::lamports::snapshot(a);
+                        measure("processor", || $fn(&ExecutionContext{program_id: p, accounts: a, named}, &mut accounts, ix_data))?;
+                        measure("persist", || Persist::persist(accounts.as_ref(), p))?;
+                        solitaire::processors::lamports::check(stringify!($row), a, lamports)?;
                         Ok(())
                     }
                 }
@@ -82,13 +107,26 @@ macro_rules! solitaire {
             /// This entrypoint is generated from the enum above, it deserializes incoming bytes
             /// and automatically dispatches to the correct method.
             pub fn dispatch<'a, 'b: 'a, 'c>(p: &Pubkey, a: &'c [AccountInfo<'b>], d: &[u8]) -> Result<()> {
-                match d[0] {
+                // Events the program emitted by invoking itself, see `solitaire::processors::event`.
+                if solitaire::processors::event::is_event(d) {
+                    return solitaire::processors::event::accept_event(p, a);
+                }
+
+                // Empty instruction data is rejected rather than indexed, so no input can panic the
+                // dispatcher itself.
+                let (&tag, d) = d.split_first().ok_or(ProgramError::InvalidInstructionData)?;
+
+                // Tags with the high bit set resolve their accounts by key, see
+                // `solitaire::processors::named`.
+                use solitaire::processors::named::NAMED_ACCOUNTS;
+                let named = tag & NAMED_ACCOUNTS != 0;
+                match tag & !NAMED_ACCOUNTS {
                     $(
-                        n if n == Instruction::$row as u8 => $row::execute(p, a, &d[1..]),
+                        n if n == Instruction::$row as u8 => $row::execute(p, a, d, named),
                     )*
 
-                    other => {
-                        Err(SolitaireError::UnknownInstruction(other))
+                    _ => {
+                        Err(SolitaireError::UnknownInstruction(tag))
                     }
                 }
             }
@@ -1,4 +1,9 @@
+pub mod compute;
+pub mod event;
+pub mod invoke;
 pub mod keyed;
+pub mod lamports;
+pub mod named;
 pub mod peel;
 pub mod persist;
 pub mod seeded;
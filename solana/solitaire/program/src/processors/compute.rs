@@ -0,0 +1,42 @@
+//! Compute accounting per processor phase.
+//!
+//! With the `compute-accounting` feature the dispatcher samples the compute meter around the
+//! phases of each instruction, account validation, the processor and persisting accounts, as do
+//! CPIs and the phases processors mark with [`measure`]. Each phase emits what it consumed as a
+//! `ComputeUsed` event through `sol_log_data`, with the phase name and the big endian compute units.
+//! Phases nest, the processor phase includes the CPIs and phases it ran.
+//!
+//! The meter is read through the `sol_remaining_compute_units` syscall, which validators older than
+//! 1.18 don't provide, so builds with the feature only load where it is available. Off-chain phases
+//! consume nothing. Without the feature [`measure`] runs the phase and compiles to nothing else.
+
+use solana_program::log::sol_log_data;
+
+#[cfg(all(target_arch = "bpf", feature = "compute-accounting"))]
+extern "C" {
+    fn sol_remaining_compute_units() -> u64;
+}
+
+/// Compute units left to the instruction.
+#[cfg(all(target_arch = "bpf", feature = "compute-accounting"))]
+pub fn remaining_compute_units() -> u64 {
+    unsafe { sol_remaining_compute_units() }
+}
+
+/// Compute units left to the instruction, zero off-chain or without the feature.
+#[cfg(not(all(target_arch = "bpf", feature = "compute-accounting")))]
+pub fn remaining_compute_units() -> u64 {
+    0
+}
+
+/// Run `phase` and emit the compute units it consumed.
+pub fn measure<T>(name: &str, phase: impl FnOnce() -> T) -> T {
+    if !cfg!(feature = "compute-accounting") {
+        return phase();
+    }
+    let before = remaining_compute_units();
+    let result = phase();
+    let used = before.saturating_sub(remaining_compute_units());
+    sol_log_data(&[b"ComputeUsed", name.as_bytes(), &used.to_be_bytes()]);
+    result
+}
@@ -0,0 +1,140 @@
+//! Self-invocation events, compatible with Anchor's `emit_cpi!`.
+//!
+//! Program logs are truncated once a transaction logs too much, so indexers following logs miss
+//! events under load. Events a program emits by invoking itself are recorded as inner instructions
+//! instead, which are never truncated. The invocation data is [`EVENT_IX_TAG`], the discriminator of
+//! the event and its borsh serialization, signed by the event authority of the program so that
+//! nobody can forge events by invoking the program directly. The `solitaire!` dispatcher accepts
+//! these invocations, see [`accept_event`].
+//!
+//! Events are versioned. A change to the fields of an event is a new event struct with its own
+//! version and discriminator, so that indexers keep decoding the events emitted before an upgrade.
+//! Every event declares its fields in [`Event::SCHEMA`], and indexers compare
+//! [`Event::SCHEMA_HASH`] against the schemas a program reports, see [`EventSchema`], to notice a
+//! changed format before they misread it.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    pubkey::Pubkey,
+};
+
+use crate::{
+    invoke_signed,
+    ExecutionContext,
+    Result,
+    SolitaireError,
+};
+
+/// Tag of event invocations, the first 8 bytes of their data as a little endian u64.
+pub const EVENT_IX_TAG: u64 = 0x1d9acb512ea545e4;
+
+/// Seed of the event authority of a program, signing its event invocations.
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// Event emitted through [`emit_cpi`].
+pub trait Event: BorshSerialize {
+    /// Name of the event in the IDL of the program.
+    const NAME: &'static str;
+
+    /// Version of the event, bumped by every change to its fields.
+    const VERSION: u8;
+
+    /// First 8 bytes of `sha256("event:<Name>")`, as Anchor derives them.
+    const DISCRIMINATOR: [u8; 8];
+
+    /// Fields of the event in serialization order, as comma separated `name:type` pairs with the
+    /// types of the IDL, e.g. `emitter:pubkey,sequence:u64`.
+    const SCHEMA: &'static str;
+
+    /// Hash of [`Self::SCHEMA`], see [`schema_hash`].
+    const SCHEMA_HASH: [u8; 8] = schema_hash(Self::SCHEMA);
+}
+
+/// 64-bit FNV-1a hash of `schema` as big endian bytes, computed at compile time so that a changed
+/// schema changes the hash a program reports without anyone maintaining it.
+pub const fn schema_hash(schema: &str) -> [u8; 8] {
+    let bytes = schema.as_bytes();
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+        i += 1;
+    }
+    hash.to_be_bytes()
+}
+
+/// Schema of an event, as programs report the events they emit.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct EventSchema {
+    pub name: String,
+    pub version: u8,
+    pub discriminator: [u8; 8],
+    pub schema: String,
+    pub schema_hash: [u8; 8],
+}
+
+impl EventSchema {
+    pub fn of<E: Event>() -> Self {
+        EventSchema {
+            name: E::NAME.to_string(),
+            version: E::VERSION,
+            discriminator: E::DISCRIMINATOR,
+            schema: E::SCHEMA.to_string(),
+            schema_hash: E::SCHEMA_HASH,
+        }
+    }
+}
+
+/// Event authority of `program_id` and its bump.
+pub fn event_authority(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], program_id)
+}
+
+/// Emit `event` by invoking the program itself. Instructions opt in by passing the event authority
+/// and the program account, without them the event is skipped and callers rely on their logs.
+pub fn emit_cpi<E: Event>(ctx: &ExecutionContext, event: &E) -> Result<()> {
+    let (authority, bump) = event_authority(ctx.program_id);
+    let passed = |key: &Pubkey| ctx.accounts.iter().any(|info| info.key == key);
+    if !passed(&authority) || !passed(ctx.program_id) {
+        return Ok(());
+    }
+
+    let mut data = EVENT_IX_TAG.to_le_bytes().to_vec();
+    data.extend_from_slice(&E::DISCRIMINATOR);
+    event.serialize(&mut data)?;
+    let instruction = Instruction {
+        program_id: *ctx.program_id,
+        accounts: vec![AccountMeta::new_readonly(authority, true)],
+        data,
+    };
+    invoke_signed(
+        &instruction,
+        ctx.accounts,
+        &[&[EVENT_AUTHORITY_SEED, &[bump]]],
+    )?;
+    Ok(())
+}
+
+/// Whether `data` is an event invocation.
+pub fn is_event(data: &[u8]) -> bool {
+    data.starts_with(&EVENT_IX_TAG.to_le_bytes())
+}
+
+/// Accept an event invocation, which does nothing but must be signed by the event authority.
+pub fn accept_event(program_id: &Pubkey, accounts: &[AccountInfo]) -> Result<()> {
+    let (authority, _) = event_authority(program_id);
+    match accounts.first() {
+        Some(info) if *info.key == authority && info.is_signer => Ok(()),
+        Some(info) => Err(SolitaireError::InvalidSigner(*info.key)),
+        None => Err(SolitaireError::InvalidSigner(authority)),
+    }
+}
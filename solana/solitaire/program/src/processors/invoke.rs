@@ -0,0 +1,46 @@
+//! Cross-program invocation.
+//!
+//! Thin wrappers around the Solana CPI functions with identical signatures. Programs built on
+//! Solitaire should invoke other programs through these so that every CPI shows up in `trace`
+//! output and compute accounting.
+
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::Instruction,
+    program,
+};
+
+use crate::{
+    processors::compute::measure,
+    trace_kv,
+};
+
+/// Invoke a program, see [`solana_program::program::invoke`].
+pub fn invoke(instruction: &Instruction, account_infos: &[AccountInfo]) -> ProgramResult {
+    invoke_signed(instruction, account_infos, &[])
+}
+
+/// Invoke a program with PDA signers, see [`solana_program::program::invoke_signed`].
+pub fn invoke_signed(
+    instruction: &Instruction,
+    account_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    trace_kv!(
+        "cpi",
+        program = instruction.program_id,
+        accounts = instruction.accounts.len(),
+        signers = signers_seeds.len(),
+        data_len = instruction.data.len(),
+    );
+    let result = measure("cpi", || {
+        program::invoke_signed(instruction, account_infos, signers_seeds)
+    });
+    trace_kv!(
+        "cpi_result",
+        program = instruction.program_id,
+        ok = result.is_ok()
+    );
+    result
+}
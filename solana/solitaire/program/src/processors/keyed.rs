@@ -7,6 +7,7 @@ use crate::{
     Derive,
     Info,
     Mut,
+    ReadOnly,
     Signer,
     System,
     Sysvar,
@@ -66,6 +67,15 @@ where
     }
 }
 
+impl<'a, 'b: 'a, T> Keyed<'a, 'b> for ReadOnly<T>
+where
+    T: Keyed<'a, 'b>,
+{
+    fn info(&'a self) -> &'a Info<'b> {
+        self.0.info()
+    }
+}
+
 impl<'a, 'b: 'a> Keyed<'a, 'b> for Info<'b> {
     fn info(&'a self) -> &'a Info<'b> {
         self
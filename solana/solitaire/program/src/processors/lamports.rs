@@ -0,0 +1,61 @@
+//! Lamport conservation checks.
+//!
+//! The runtime rejects instructions that change the total lamports of their accounts, but only
+//! after the whole instruction ran and without saying where the lamports went. With the
+//! `lamport-checks` feature the dispatcher sums the writable accounts around each processor
+//! instead, so that tests fail in the processor that leaked, and trace both totals.
+//!
+//! Fees and rent move lamports between the accounts of the instruction and keep the total, lamports
+//! can only leak through direct balance changes. Without the feature the checks compile to nothing.
+
+use solana_program::account_info::AccountInfo;
+
+use crate::{
+    trace_kv,
+    Result,
+    SolitaireError,
+};
+
+/// Total lamports of the writable accounts, counting accounts passed more than once a single time.
+pub fn writable_lamports(accounts: &[AccountInfo]) -> u128 {
+    accounts
+        .iter()
+        .enumerate()
+        .filter(|(i, info)| {
+            info.is_writable && !accounts[..*i].iter().any(|other| other.key == info.key)
+        })
+        .map(|(_, info)| info.lamports() as u128)
+        .sum()
+}
+
+/// Total to check the accounts against once the processor ran, `None` without the feature.
+pub fn snapshot(accounts: &[AccountInfo]) -> Option<u128> {
+    if cfg!(feature = "lamport-checks") {
+        Some(writable_lamports(accounts))
+    } else {
+        None
+    }
+}
+
+/// Fail the instruction `name` if its writable accounts no longer hold the `before` total.
+pub fn check(name: &str, accounts: &[AccountInfo], before: Option<u128>) -> Result<()> {
+    if let Some(before) = before {
+        let after = writable_lamports(accounts);
+        trace_kv!(
+            "lamports",
+            instruction = name,
+            before = before,
+            after = after
+        );
+        if after != before {
+            solana_program::msg!(
+                "{} changed total lamports from {} to {}",
+                name,
+                before,
+                after
+            );
+            return Err(SolitaireError::LamportImbalance(before, after));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,87 @@
+//! Named account resolution.
+//!
+//! Accounts are peeled in the order their fields are declared, so a client passing two accounts
+//! the wrong way around fails on whichever constraint the wrong account happens to break first.
+//! Instructions whose tag has [`NAMED_ACCOUNTS`] set resolve accounts by key instead where they
+//! can: fields with a key derived from the program id alone, see
+//! [`Peel::static_key`](crate::Peel::static_key), take the account with that key wherever it is
+//! in the list, and fail naming the field if it is missing.
+//! The other fields take the remaining accounts in order, skipping the ones already resolved.
+//!
+//! Accounts a processor reads from the end of the list must still be passed last. Instructions
+//! whose accounts others locate by position can refuse named resolution by checking
+//! [`ExecutionContext::named`](crate::ExecutionContext::named).
+
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::Instruction,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    trace_kv,
+    Result,
+    SolitaireError,
+};
+
+/// Bit set in the instruction tag to resolve its accounts by key.
+pub const NAMED_ACCOUNTS: u8 = 0x80;
+
+/// Switch `ix` to named account resolution, its accounts with static keys may then be passed
+/// anywhere in the list.
+pub fn named(mut ix: Instruction) -> Instruction {
+    if let Some(tag) = ix.data.first_mut() {
+        *tag |= NAMED_ACCOUNTS;
+    }
+    ix
+}
+
+/// Hands out the accounts of each field in turn, see the module documentation.
+pub struct Resolver<'a, 'b: 'a> {
+    accounts: &'a [AccountInfo<'b>],
+
+    /// Static key of each field, in declaration order.
+    keys: Vec<Option<Pubkey>>,
+
+    /// Next field to resolve.
+    field: usize,
+
+    /// Next account to consider for fields without a static key.
+    position: usize,
+}
+
+impl<'a, 'b: 'a> Resolver<'a, 'b> {
+    pub fn new(accounts: &'a [AccountInfo<'b>], keys: Vec<Option<Pubkey>>) -> Self {
+        Resolver {
+            accounts,
+            keys,
+            field: 0,
+            position: 0,
+        }
+    }
+
+    /// Account of the next field, called `name`.
+    pub fn next(&mut self, name: &'static str) -> Result<&'a AccountInfo<'b>> {
+        let key = self.keys.get(self.field).copied().flatten();
+        self.field += 1;
+
+        if let Some(key) = key {
+            trace_kv!("resolve", field = name, account = key);
+            return self
+                .accounts
+                .iter()
+                .find(|info| *info.key == key)
+                .ok_or(SolitaireError::MissingNamedAccount(name, key));
+        }
+
+        while let Some(info) = self.accounts.get(self.position) {
+            self.position += 1;
+            if !self.keys.contains(&Some(*info.key)) {
+                trace_kv!("resolve", field = name, account = info.key);
+                return Ok(info);
+            }
+        }
+        Err(ProgramError::NotEnoughAccountKeys.into())
+    }
+}
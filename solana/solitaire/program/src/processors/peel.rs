@@ -16,6 +16,7 @@ use crate::{
         Owned,
     },
     trace,
+    trace_kv,
     types::*,
     Context,
     Result,
@@ -31,6 +32,15 @@ pub trait Peel<'a, 'b: 'a> {
         Self: Sized;
 
     fn persist(&self, program_id: &Pubkey) -> Result<()>;
+
+    /// Key the account must have when it follows from the program id alone, used to resolve
+    /// accounts by key, see [`named`](crate::processors::named).
+    fn static_key(_program_id: &Pubkey) -> Option<Pubkey>
+    where
+        Self: Sized,
+    {
+        None
+    }
 }
 
 /// Peel a nullable value (0-account means None)
@@ -66,6 +76,13 @@ impl<'a, 'b: 'a, T: Peel<'a, 'b>, const SEED: &'static str> Peel<'a, 'b> for Der
     fn peel<I>(ctx: &mut Context<'a, 'b, I>) -> Result<Self> {
         // Attempt to Derive SEED
         let (derived, _bump) = Pubkey::find_program_address(&[SEED.as_ref()], ctx.this);
+        trace_kv!(
+            "check",
+            kind = "derive",
+            account = ctx.info.key,
+            seed = SEED,
+            ok = derived == *ctx.info.key
+        );
         match derived == *ctx.info.key {
             true => T::peel(ctx).map(|v| Derive(v)),
             _ => Err(SolitaireError::InvalidDerive(*ctx.info.key, derived)),
@@ -75,12 +92,22 @@ impl<'a, 'b: 'a, T: Peel<'a, 'b>, const SEED: &'static str> Peel<'a, 'b> for Der
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         T::persist(self, program_id)
     }
+
+    fn static_key(program_id: &Pubkey) -> Option<Pubkey> {
+        Some(Pubkey::find_program_address(&[SEED.as_ref()], program_id).0)
+    }
 }
 
 /// Peel a Mutable key.
 impl<'a, 'b: 'a, T: Peel<'a, 'b>> Peel<'a, 'b> for Mut<T> {
     fn peel<I>(mut ctx: &mut Context<'a, 'b, I>) -> Result<Self> {
         ctx.immutable = false;
+        trace_kv!(
+            "check",
+            kind = "writable",
+            account = ctx.info.key,
+            ok = ctx.info.is_writable
+        );
         match ctx.info.is_writable {
             true => T::peel(ctx).map(|v| Mut(v)),
             _ => Err(SolitaireError::InvalidMutability(
@@ -93,6 +120,10 @@ impl<'a, 'b: 'a, T: Peel<'a, 'b>> Peel<'a, 'b> for Mut<T> {
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         T::persist(self, program_id)
     }
+
+    fn static_key(program_id: &Pubkey) -> Option<Pubkey> {
+        T::static_key(program_id)
+    }
 }
 
 impl<'a, 'b: 'a, T: Peel<'a, 'b>> Peel<'a, 'b> for MaybeMut<T> {
@@ -104,11 +135,37 @@ impl<'a, 'b: 'a, T: Peel<'a, 'b>> Peel<'a, 'b> for MaybeMut<T> {
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         T::persist(self, program_id)
     }
+
+    fn static_key(program_id: &Pubkey) -> Option<Pubkey> {
+        T::static_key(program_id)
+    }
+}
+
+/// Peel a read-only view, accepting writable accounts.
+impl<'a, 'b: 'a, T: Peel<'a, 'b>> Peel<'a, 'b> for ReadOnly<T> {
+    fn peel<I>(mut ctx: &mut Context<'a, 'b, I>) -> Result<Self> {
+        ctx.immutable = false;
+        T::peel(ctx).map(|v| ReadOnly(v))
+    }
+
+    fn persist(&self, _program_id: &Pubkey) -> Result<()> {
+        Ok(())
+    }
+
+    fn static_key(program_id: &Pubkey) -> Option<Pubkey> {
+        T::static_key(program_id)
+    }
 }
 
 /// Peel a Signer.
 impl<'a, 'b: 'a, T: Peel<'a, 'b>> Peel<'a, 'b> for Signer<T> {
     fn peel<I>(ctx: &mut Context<'a, 'b, I>) -> Result<Self> {
+        trace_kv!(
+            "check",
+            kind = "signer",
+            account = ctx.info.key,
+            ok = ctx.info.is_signer
+        );
         match ctx.info.is_signer {
             true => T::peel(ctx).map(|v| Signer(v)),
             _ => Err(SolitaireError::InvalidSigner(*ctx.info.key)),
@@ -118,6 +175,10 @@ impl<'a, 'b: 'a, T: Peel<'a, 'b>> Peel<'a, 'b> for Signer<T> {
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         T::persist(self, program_id)
     }
+
+    fn static_key(program_id: &Pubkey) -> Option<Pubkey> {
+        T::static_key(program_id)
+    }
 }
 
 /// Expicitly depend upon the System account.
@@ -132,6 +193,10 @@ impl<'a, 'b: 'a, T: Peel<'a, 'b>> Peel<'a, 'b> for System<T> {
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         T::persist(self, program_id)
     }
+
+    fn static_key(program_id: &Pubkey) -> Option<Pubkey> {
+        T::static_key(program_id)
+    }
 }
 
 /// Peel a Sysvar
@@ -140,6 +205,12 @@ where
     Var: SolanaSysvar,
 {
     fn peel<I>(ctx: &mut Context<'a, 'b, I>) -> Result<Self> {
+        trace_kv!(
+            "check",
+            kind = "sysvar",
+            account = ctx.info.key,
+            ok = Var::check_id(ctx.info.key)
+        );
         match Var::check_id(ctx.info.key) {
             true => Ok(Sysvar(ctx.info.clone(), Var::from_account_info(ctx.info)?)),
             _ => Err(SolitaireError::InvalidSysvar(*ctx.info.key)),
@@ -149,12 +220,22 @@ where
     fn persist(&self, _program_id: &Pubkey) -> Result<()> {
         Ok(())
     }
+
+    fn static_key(_program_id: &Pubkey) -> Option<Pubkey> {
+        Some(Var::id())
+    }
 }
 
 /// This is our structural recursion base case, the trait system will stop generating new nested
 /// calls here.
 impl<'a, 'b: 'a> Peel<'a, 'b> for Info<'b> {
     fn peel<I>(ctx: &mut Context<'a, 'b, I>) -> Result<Self> {
+        trace_kv!(
+            "check",
+            kind = "readonly",
+            account = ctx.info.key,
+            ok = !(ctx.immutable && ctx.info.is_writable)
+        );
         if ctx.immutable && ctx.info.is_writable {
             return Err(SolitaireError::InvalidMutability(
                 *ctx.info.key,
@@ -180,6 +261,12 @@ impl<
     > Peel<'a, 'b> for Data<'b, T, IS_INITIALIZED>
 {
     fn peel<I>(ctx: &mut Context<'a, 'b, I>) -> Result<Self> {
+        trace_kv!(
+            "check",
+            kind = "readonly",
+            account = ctx.info.key,
+            ok = !(ctx.immutable && ctx.info.is_writable)
+        );
         if ctx.immutable && ctx.info.is_writable {
             return Err(SolitaireError::InvalidMutability(
                 *ctx.info.key,
@@ -205,7 +292,21 @@ impl<
             }
         };
 
+        trace_kv!(
+            "check",
+            kind = "state",
+            account = ctx.info.key,
+            data_type = std::any::type_name::<T>(),
+            initialized = initialized
+        );
+
         if initialized {
+            trace_kv!(
+                "check",
+                kind = "owner",
+                account = ctx.info.key,
+                owner = ctx.info.owner
+            );
             match data.owner() {
                 AccountOwner::This => {
                     if ctx.info.owner != ctx.this {
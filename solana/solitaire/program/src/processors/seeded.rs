@@ -1,6 +1,8 @@
 use super::keyed::Keyed;
 use crate::{
     create_account,
+    invoke_signed,
+    trace_kv,
     AccountState,
     CreationLamports,
     Data,
@@ -14,7 +16,6 @@ use borsh::BorshSerialize;
 use solana_program::{
     entrypoint::ProgramResult,
     instruction::Instruction,
-    program::invoke_signed,
     pubkey::Pubkey,
 };
 
@@ -87,6 +88,13 @@ pub trait Seeded<I> {
         let seed_slice = s.as_slice();
 
         let (derived, _bump) = Pubkey::find_program_address(seed_slice, program_id);
+        trace_kv!(
+            "check",
+            kind = "derivation",
+            account = self.info().key,
+            expected = derived,
+            ok = &derived == self.info().key
+        );
         if &derived == self.info().key {
             Ok(())
         } else {
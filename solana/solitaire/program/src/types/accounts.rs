@@ -7,11 +7,8 @@
 use borsh::BorshSerialize;
 use solana_program::{
     account_info::AccountInfo,
-    program::{
-        invoke,
-        invoke_signed,
-    },
     pubkey::Pubkey,
+    rent::Rent,
     system_instruction,
     sysvar::Sysvar as SolanaSysvar,
 };
@@ -21,7 +18,10 @@ use std::ops::{
 };
 
 use crate::{
+    invoke,
+    invoke_signed,
     processors::seeded::Owned,
+    trace_kv,
     CreationLamports,
     Derive,
     ExecutionContext,
@@ -189,6 +189,13 @@ pub fn create_account(
     seeds: IsSigned,
 ) -> Result<()> {
     let target_rent = lamports.amount(size)?;
+    trace_kv!(
+        "create_account",
+        account = account.key,
+        owner = owner,
+        size = size,
+        lamports = target_rent
+    );
     // top up account to target rent
     if account.lamports() < target_rent {
         let transfer_ix =
@@ -211,3 +218,69 @@ pub fn create_account(
 
     Ok(())
 }
+
+/// Grow an account to at least `size` bytes, topping it up from `payer` so that it stays rent
+/// exempt.
+///
+/// Processors call this before writing an extended layout, so that accounts created by earlier
+/// program versions are migrated lazily on first touch instead of by a migration script. Accounts
+/// that are already large enough are left as they are, they never shrink. The account must be
+/// owned by the program and writable, and can grow by at most
+/// [`MAX_PERMITTED_DATA_INCREASE`](solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE) bytes
+/// per instruction.
+pub fn ensure_account_size(
+    ctx: &ExecutionContext,
+    account: &Info<'_>,
+    payer: &Pubkey,
+    size: usize,
+) -> Result<()> {
+    if account.data_len() >= size {
+        return Ok(());
+    }
+
+    let required = Rent::get()?.minimum_balance(size);
+    trace_kv!(
+        "ensure_account_size",
+        account = account.key,
+        from = account.data_len(),
+        size = size,
+        lamports = required
+    );
+    if account.lamports() < required {
+        let transfer_ix =
+            system_instruction::transfer(payer, account.key, required - account.lamports());
+        invoke(&transfer_ix, ctx.accounts)?;
+    }
+    account.realloc(size, false)?;
+
+    Ok(())
+}
+
+/// Resize an account to exactly `size` bytes, settling its rent with `payer`. Growing tops the
+/// account up like [`ensure_account_size`], shrinking refunds `payer` the lamports the account no
+/// longer needs to stay rent exempt, so `payer` must then be writable too.
+pub fn resize_account(
+    ctx: &ExecutionContext,
+    account: &Info<'_>,
+    payer: &Info<'_>,
+    size: usize,
+) -> Result<()> {
+    if account.data_len() <= size {
+        return ensure_account_size(ctx, account, payer.key, size);
+    }
+
+    let required = Rent::get()?.minimum_balance(size);
+    let refund = account.lamports().saturating_sub(required);
+    trace_kv!(
+        "resize_account",
+        account = account.key,
+        from = account.data_len(),
+        size = size,
+        refund = refund
+    );
+    account.realloc(size, false)?;
+    **account.try_borrow_mut_lamports()? -= refund;
+    **payer.try_borrow_mut_lamports()? += refund;
+
+    Ok(())
+}
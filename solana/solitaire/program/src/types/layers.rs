@@ -16,6 +16,12 @@ pub struct Mut<Next>(pub Next);
 #[repr(transparent)]
 pub struct MaybeMut<Next>(pub Next);
 
+/// Read-only view of an account, which may still be passed writable, such as an account another
+/// field of the instruction writes. Unlike other layers it only derefs immutably and is never
+/// persisted, so read paths cannot write the account back by accident.
+#[repr(transparent)]
+pub struct ReadOnly<Next>(pub Next);
+
 #[repr(transparent)]
 pub struct Signer<Next>(pub Next);
 
@@ -67,6 +73,13 @@ impl<T> DerefMut for MaybeMut<T> {
     }
 }
 
+impl<T> Deref for ReadOnly<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        unsafe { std::mem::transmute(&self.0) }
+    }
+}
+
 impl<T> Deref for System<T> {
     type Target = T;
     fn deref(&self) -> &Self::Target {
@@ -49,6 +49,7 @@ pub fn derive_from_accounts(input: TokenStream) -> TokenStream {
     let (combined_impl_g, _, _) = combined_generics.split_for_impl();
 
     let from_method = generate_fields(&name, &input.data);
+    let from_named_method = generate_named_fields(&name, &input.data);
     let persist_method = generate_persist(&input.data);
     let expanded = quote! {
         /// Macro generated implementation of FromAccounts by Solitaire.
@@ -56,6 +57,10 @@ pub fn derive_from_accounts(input: TokenStream) -> TokenStream {
             fn from<DataType>(pid: &'a solana_program::pubkey::Pubkey, iter: &mut std::slice::Iter<'a, solana_program::account_info::AccountInfo<'b>>, data: &'a DataType) -> solitaire::Result<Box<Self>> {
                 #from_method
             }
+
+            fn from_named<DataType>(pid: &'a solana_program::pubkey::Pubkey, iter: &mut std::slice::Iter<'a, solana_program::account_info::AccountInfo<'b>>, data: &'a DataType) -> solitaire::Result<Box<Self>> {
+                #from_named_method
+            }
         }
 
         /// Macro generated implementation of Persist by Solitaire.
@@ -128,6 +133,61 @@ fn generate_fields(name: &syn::Ident, data: &Data) -> TokenStream2 {
     }
 }
 
+/// Generate the field parsers for named account resolution, which take the account of each field
+/// from a `solitaire::processors::named::Resolver` rather than straight from the iterator.
+fn generate_named_fields(name: &syn::Ident, data: &Data) -> TokenStream2 {
+    match *data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => {
+                let keys = fields.named.iter().map(|f| {
+                    let ty = &f.ty;
+                    quote!(<#ty as solitaire::Peel<'a, 'b>>::static_key(pid))
+                });
+
+                let recurse = fields.named.iter().map(|f| {
+                    let name = &f.ident;
+                    let ty = &f.ty;
+
+                    quote! {
+                        trace!(stringify!(#name));
+                        let #name: #ty = solitaire::Peel::peel(&mut solitaire::Context::new(
+                            pid,
+                            resolver.next(stringify!(#name))?,
+                            data,
+                        ))?;
+                    }
+                });
+
+                let names = fields.named.iter().map(|f| {
+                    let name = &f.ident;
+                    quote!(#name)
+                });
+
+                quote! {
+                    use solitaire::trace;
+                    trace!("Peeling named:");
+                    let mut resolver = solitaire::processors::named::Resolver::new(
+                        iter.as_slice(),
+                        vec![#(#keys,)*],
+                    );
+                    #(#recurse;)*
+                    Ok(Box::new(#name { #(#names,)* }))
+                }
+            }
+
+            Fields::Unnamed(_) => {
+                unimplemented!()
+            }
+
+            Fields::Unit => {
+                unimplemented!()
+            }
+        },
+
+        Data::Enum(_) | Data::Union(_) => unimplemented!(),
+    }
+}
+
 /// This function does the heavy lifting of generating the field parsers.
 fn generate_persist(data: &Data) -> TokenStream2 {
     match *data {
@@ -0,0 +1,482 @@
+//! Typed views of the governance decrees the Wormhole runtimes share. `wormhole_governance`
+//! decodes its owned decrees through these, and encodes them.
+//!
+//! [`GovernancePayload::parse`] resolves the module and action of a payload to a [`Decree`] and
+//! validates the whole payload: every field must be present, fields must hold values their decree
+//! allows and no bytes may follow the last field. Each decree also parses on its own from a body,
+//! for callers that already matched the header.
+
+use crate::{
+    Error,
+    GovernanceHeader,
+    Reader,
+    Result,
+};
+use core::{
+    convert::TryInto,
+    str,
+};
+
+/// Governance modules decrees are parsed for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Module {
+    Core,
+    TokenBridge,
+    NftBridge,
+}
+
+impl Module {
+    /// Name of the module as padded into governance headers.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Module::Core => "Core",
+            Module::TokenBridge => "TokenBridge",
+            Module::NftBridge => "NFTBridge",
+        }
+    }
+
+    fn from_id(id: &[u8; 32]) -> Result<Self> {
+        [Module::Core, Module::TokenBridge, Module::NftBridge]
+            .iter()
+            .copied()
+            .find(|module| is_module(id, module.name()))
+            .ok_or(Error::UnknownDecree)
+    }
+}
+
+pub(crate) fn is_module(id: &[u8; 32], name: &str) -> bool {
+    let name = name.as_bytes();
+    name.len() <= id.len() && {
+        let (padding, module) = id.split_at(id.len() - name.len());
+        padding.iter().all(|&b| b == 0) && module == name
+    }
+}
+
+/// Governance payload with its decree resolved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GovernancePayload<'a> {
+    pub module: Module,
+
+    /// Target chain, 0 for all. [`RecoverChainId`] decrees target no chain, they are executed by
+    /// the runtime that lost its chain id.
+    pub chain: Option<u16>,
+
+    pub decree: Decree<'a>,
+}
+
+impl<'a> GovernancePayload<'a> {
+    pub fn parse(payload: &'a [u8]) -> Result<Self> {
+        let mut rdr = Reader { data: payload };
+        let module = Module::from_id(rdr.array()?)?;
+        let action = rdr.u8()?;
+
+        let recover_action = match module {
+            Module::Core => RecoverChainId::CORE_ACTION,
+            Module::TokenBridge | Module::NftBridge => RecoverChainId::BRIDGE_ACTION,
+        };
+        if action == recover_action {
+            return Ok(GovernancePayload {
+                module,
+                chain: None,
+                decree: Decree::RecoverChainId(RecoverChainId::parse(rdr.data)?),
+            });
+        }
+
+        let header = GovernanceHeader::parse(payload)?;
+        let decree = match (module, action) {
+            (Module::Core, ContractUpgrade::CORE_ACTION) => {
+                Decree::ContractUpgrade(ContractUpgrade::parse(header.body)?)
+            }
+            (Module::Core, GuardianSetUpdate::ACTION) => {
+                Decree::GuardianSetUpdate(GuardianSetUpdate::parse(header.body)?)
+            }
+            (Module::Core, SetFee::ACTION) => Decree::SetFee(SetFee::parse(header.body)?),
+            (Module::Core, TransferFees::ACTION) => {
+                Decree::TransferFees(TransferFees::parse(header.body)?)
+            }
+            (Module::Core, _) => return Err(Error::UnknownDecree),
+            (_, RegisterChain::ACTION) => Decree::RegisterChain(RegisterChain::parse(header.body)?),
+            (_, ContractUpgrade::BRIDGE_ACTION) => {
+                Decree::ContractUpgrade(ContractUpgrade::parse_address(header.body)?)
+            }
+            (_, _) => return Err(Error::UnknownDecree),
+        };
+        Ok(GovernancePayload {
+            module,
+            chain: Some(header.chain),
+            decree,
+        })
+    }
+}
+
+/// Decree of a governance payload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Decree<'a> {
+    ContractUpgrade(ContractUpgrade<'a>),
+    GuardianSetUpdate(GuardianSetUpdate<'a>),
+    SetFee(SetFee<'a>),
+    TransferFees(TransferFees<'a>),
+    RegisterChain(RegisterChain<'a>),
+    RecoverChainId(RecoverChainId<'a>),
+}
+
+/// Upgrade the program of the module to a new implementation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ContractUpgrade<'a> {
+    /// Buffer holding the new implementation.
+    pub new_contract: &'a [u8; 32],
+
+    /// Semantic version of the new implementation, core upgrades only. Written as a length byte
+    /// and UTF-8 after the address.
+    pub version: Option<&'a str>,
+
+    /// Hash of the program in the buffer, core upgrades only. Written after the version, which is
+    /// then empty if the upgrade names none.
+    pub buffer_hash: Option<&'a [u8; 32]>,
+}
+
+impl<'a> ContractUpgrade<'a> {
+    pub const CORE_ACTION: u8 = 1;
+    pub const BRIDGE_ACTION: u8 = 2;
+
+    /// Parse the body of a core upgrade, with its optional extensions.
+    pub fn parse(body: &'a [u8]) -> Result<Self> {
+        let mut rdr = Reader { data: body };
+        let new_contract = rdr.array()?;
+        let mut version = None;
+        if !rdr.data.is_empty() {
+            let len = rdr.u8()? as usize;
+            version = Some(str::from_utf8(rdr.take(len)?).map_err(|_| Error::InvalidField)?);
+        }
+        let mut buffer_hash = None;
+        if !rdr.data.is_empty() {
+            buffer_hash = Some(rdr.array()?);
+            version = version.filter(|version| !version.is_empty());
+        }
+        rdr.finish()?;
+        Ok(ContractUpgrade {
+            new_contract,
+            version,
+            buffer_hash,
+        })
+    }
+
+    /// Parse the body of a token or NFT bridge upgrade, only the address.
+    pub fn parse_address(body: &'a [u8]) -> Result<Self> {
+        let mut rdr = Reader { data: body };
+        let new_contract = rdr.array()?;
+        rdr.finish()?;
+        Ok(ContractUpgrade {
+            new_contract,
+            version: None,
+            buffer_hash: None,
+        })
+    }
+}
+
+/// Hand over to a new guardian set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GuardianSetUpdate<'a> {
+    pub new_guardian_set_index: u32,
+    keys: &'a [u8],
+    secp256r1_keys: &'a [u8],
+}
+
+impl<'a> GuardianSetUpdate<'a> {
+    pub const ACTION: u8 = 2;
+
+    /// Length of an Ethereum style guardian key.
+    pub const KEY_LEN: usize = 20;
+
+    /// Length of a compressed secp256r1 guardian key.
+    pub const SECP256R1_KEY_LEN: usize = 33;
+
+    /// Parse the body of an update: the index, the key count and the keys, optionally followed
+    /// by a secp256r1 key for every guardian under the same count.
+    pub fn parse(body: &'a [u8]) -> Result<Self> {
+        let mut rdr = Reader { data: body };
+        let new_guardian_set_index = rdr.u32()?;
+        let count = rdr.u8()?;
+        let keys = rdr.take(count as usize * Self::KEY_LEN)?;
+        let mut secp256r1_keys: &[u8] = &[];
+        if !rdr.data.is_empty() {
+            if rdr.u8()? != count {
+                return Err(Error::InvalidField);
+            }
+            secp256r1_keys = rdr.take(count as usize * Self::SECP256R1_KEY_LEN)?;
+        }
+        rdr.finish()?;
+        Ok(GuardianSetUpdate {
+            new_guardian_set_index,
+            keys,
+            secp256r1_keys,
+        })
+    }
+
+    /// Number of guardians in the new set.
+    pub fn len(&self) -> usize {
+        self.keys.len() / Self::KEY_LEN
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Keys of the new guardians, in guardian index order.
+    pub fn keys(&self) -> impl Iterator<Item = &'a [u8; 20]> + 'a {
+        self.keys
+            .chunks_exact(Self::KEY_LEN)
+            .map(|key| key.try_into().unwrap())
+    }
+
+    /// Secp256r1 keys of the new guardians, in guardian index order and zeroed for secp256k1
+    /// guardians. Empty if the update has no secp256r1 extension.
+    pub fn secp256r1_keys(&self) -> impl Iterator<Item = &'a [u8; 33]> + 'a {
+        self.secp256r1_keys
+            .chunks_exact(Self::SECP256R1_KEY_LEN)
+            .map(|key| key.try_into().unwrap())
+    }
+}
+
+/// Set the fee for posting messages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SetFee<'a> {
+    /// Big endian u256, in the native unit of the chain.
+    pub fee: &'a [u8; 32],
+}
+
+impl<'a> SetFee<'a> {
+    pub const ACTION: u8 = 3;
+
+    pub fn parse(body: &'a [u8]) -> Result<Self> {
+        let mut rdr = Reader { data: body };
+        let fee = rdr.array()?;
+        rdr.finish()?;
+        Ok(SetFee { fee })
+    }
+}
+
+/// Pay out collected fees.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransferFees<'a> {
+    /// Big endian u256, in the native unit of the chain.
+    pub amount: &'a [u8; 32],
+    pub to: &'a [u8; 32],
+}
+
+impl<'a> TransferFees<'a> {
+    pub const ACTION: u8 = 4;
+
+    pub fn parse(body: &'a [u8]) -> Result<Self> {
+        let mut rdr = Reader { data: body };
+        let amount = rdr.array()?;
+        let to = rdr.array()?;
+        rdr.finish()?;
+        Ok(TransferFees { amount, to })
+    }
+}
+
+/// Register the endpoint of a token or NFT bridge on a foreign chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegisterChain<'a> {
+    pub chain: u16,
+    pub endpoint_address: &'a [u8; 32],
+}
+
+impl<'a> RegisterChain<'a> {
+    pub const ACTION: u8 = 1;
+
+    pub fn parse(body: &'a [u8]) -> Result<Self> {
+        let mut rdr = Reader { data: body };
+        let chain = rdr.u16()?;
+        let endpoint_address = rdr.array()?;
+        rdr.finish()?;
+        Ok(RegisterChain {
+            chain,
+            endpoint_address,
+        })
+    }
+}
+
+/// Restore the chain id of an EVM runtime after a hard fork changed the EVM chain id under it.
+/// The action is followed by the EVM chain id instead of a target chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecoverChainId<'a> {
+    /// Big endian u256 EVM chain id of the fork the decree applies to.
+    pub evm_chain_id: &'a [u8; 32],
+
+    /// Wormhole chain id the runtime adopts.
+    pub new_chain_id: u16,
+}
+
+impl<'a> RecoverChainId<'a> {
+    pub const CORE_ACTION: u8 = 5;
+    pub const BRIDGE_ACTION: u8 = 3;
+
+    /// Parse the decree following the action.
+    pub fn parse(body: &'a [u8]) -> Result<Self> {
+        let mut rdr = Reader { data: body };
+        let evm_chain_id = rdr.array()?;
+        let new_chain_id = rdr.u16()?;
+        rdr.finish()?;
+        Ok(RecoverChainId {
+            evm_chain_id,
+            new_chain_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload<const N: usize>(module: &str, action: u8, chain: u16, body: &[u8]) -> [u8; N] {
+        let mut payload = [0u8; N];
+        payload[32 - module.len()..32].copy_from_slice(module.as_bytes());
+        payload[32] = action;
+        payload[33..35].copy_from_slice(&chain.to_be_bytes());
+        payload[35..35 + body.len()].copy_from_slice(body);
+        payload
+    }
+
+    #[test]
+    fn test_governance_payload() {
+        let transfer = payload::<99>("Core", 4, 1, &[7u8; 64]);
+        let parsed = GovernancePayload::parse(&transfer).unwrap();
+        assert_eq!(parsed.module, Module::Core);
+        assert_eq!(parsed.chain, Some(1));
+        assert_eq!(
+            parsed.decree,
+            Decree::TransferFees(TransferFees {
+                amount: &[7u8; 32],
+                to: &[7u8; 32],
+            })
+        );
+
+        let register = payload::<69>("TokenBridge", 1, 0, &[0, 2]);
+        let parsed = GovernancePayload::parse(&register).unwrap();
+        assert_eq!(parsed.module, Module::TokenBridge);
+        assert_eq!(
+            parsed.decree,
+            Decree::RegisterChain(RegisterChain {
+                chain: 2,
+                endpoint_address: &[0u8; 32],
+            })
+        );
+
+        // Recovery decrees carry the EVM chain id where the target chain would be.
+        let mut recover = payload::<67>("NFTBridge", 3, 0, &[]);
+        recover[65..].copy_from_slice(&[0, 4]);
+        let parsed = GovernancePayload::parse(&recover).unwrap();
+        assert_eq!(parsed.chain, None);
+        assert_eq!(
+            parsed.decree,
+            Decree::RecoverChainId(RecoverChainId {
+                evm_chain_id: &[0u8; 32],
+                new_chain_id: 4,
+            })
+        );
+
+        // Bridge upgrades have no extensions, core modules no registrations.
+        assert_eq!(
+            GovernancePayload::parse(&payload::<67>("TokenBridge", 2, 0, &[])).map(|p| p.decree),
+            Ok(Decree::ContractUpgrade(ContractUpgrade {
+                new_contract: &[0u8; 32],
+                version: None,
+                buffer_hash: None,
+            }))
+        );
+        assert_eq!(
+            GovernancePayload::parse(&payload::<68>("TokenBridge", 2, 0, &[])),
+            Err(Error::TrailingBytes)
+        );
+        assert_eq!(
+            GovernancePayload::parse(&payload::<69>("Core", 9, 0, &[])),
+            Err(Error::UnknownDecree)
+        );
+        assert_eq!(
+            GovernancePayload::parse(&payload::<69>("Other", 1, 0, &[])),
+            Err(Error::UnknownDecree)
+        );
+    }
+
+    #[test]
+    fn test_contract_upgrade() {
+        let upgrade = ContractUpgrade::parse(&[5u8; 32]).unwrap();
+        assert_eq!(upgrade.version, None);
+        assert_eq!(upgrade.buffer_hash, None);
+
+        let mut body = [6u8; 32 + 6 + 32];
+        body[32..38].copy_from_slice(&[5, b'1', b'.', b'2', b'.', b'3']);
+        let upgrade = ContractUpgrade::parse(&body).unwrap();
+        assert_eq!(upgrade.version, Some("1.2.3"));
+        assert_eq!(upgrade.buffer_hash, Some(&[6u8; 32]));
+
+        // Upgrades with only a hash write an empty version.
+        let upgrade = ContractUpgrade::parse(&[0u8; 32 + 1 + 32]).unwrap();
+        assert_eq!(upgrade.version, None);
+        assert_eq!(upgrade.buffer_hash, Some(&[0u8; 32]));
+
+        assert_eq!(
+            ContractUpgrade::parse(&body[..body.len() - 1]),
+            Err(Error::Truncated)
+        );
+        assert_eq!(
+            ContractUpgrade::parse(&[0u8; 32 + 1 + 32 + 1]),
+            Err(Error::TrailingBytes)
+        );
+        body[33] = 0xff;
+        assert_eq!(ContractUpgrade::parse(&body), Err(Error::InvalidField));
+    }
+
+    #[test]
+    fn test_guardian_set_update() {
+        let mut body = [0u8; 4 + 1 + 2 * 20 + 1 + 2 * 33];
+        body[3] = 1;
+        body[4] = 2;
+        body[5..25].copy_from_slice(&[1u8; 20]);
+        body[25..45].copy_from_slice(&[2u8; 20]);
+        body[45] = 2;
+        body[79..112].copy_from_slice(&[3u8; 33]);
+
+        let update = GuardianSetUpdate::parse(&body).unwrap();
+        assert_eq!(update.new_guardian_set_index, 1);
+        assert_eq!(update.len(), 2);
+        let mut keys = update.keys();
+        assert_eq!(keys.next(), Some(&[1u8; 20]));
+        assert_eq!(keys.next(), Some(&[2u8; 20]));
+        assert_eq!(keys.next(), None);
+        let mut secp256r1_keys = update.secp256r1_keys();
+        assert_eq!(secp256r1_keys.next(), Some(&[0u8; 33]));
+        assert_eq!(secp256r1_keys.next(), Some(&[3u8; 33]));
+
+        let update = GuardianSetUpdate::parse(&body[..45]).unwrap();
+        assert_eq!(update.secp256r1_keys().count(), 0);
+
+        // The secp256r1 extension covers every guardian, and nothing follows it.
+        let mut trailing = [0u8; 113];
+        trailing[..112].copy_from_slice(&body);
+        assert_eq!(
+            GuardianSetUpdate::parse(&trailing),
+            Err(Error::TrailingBytes)
+        );
+        assert_eq!(GuardianSetUpdate::parse(&body[..46]), Err(Error::Truncated));
+        let mut mismatched = body;
+        mismatched[45] = 1;
+        assert_eq!(
+            GuardianSetUpdate::parse(&mismatched),
+            Err(Error::InvalidField)
+        );
+    }
+
+    #[test]
+    fn test_fixed_decrees() {
+        assert_eq!(SetFee::parse(&[1u8; 32]).unwrap().fee, &[1u8; 32]);
+        assert_eq!(SetFee::parse(&[1u8; 31]), Err(Error::Truncated));
+        assert_eq!(SetFee::parse(&[1u8; 33]), Err(Error::TrailingBytes));
+        assert_eq!(TransferFees::parse(&[1u8; 65]), Err(Error::TrailingBytes));
+        assert_eq!(RegisterChain::parse(&[1u8; 33]), Err(Error::Truncated));
+        assert_eq!(RegisterChain::parse(&[1u8; 34]).unwrap().chain, 0x0101);
+        assert_eq!(RecoverChainId::parse(&[1u8; 35]), Err(Error::TrailingBytes));
+    }
+}
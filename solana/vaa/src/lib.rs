@@ -0,0 +1,336 @@
+//! Zero-copy parsing of VAAs and governance payloads.
+//!
+//! Everything here is a view borrowing the bytes it was parsed from, without std or alloc, so that
+//! SVM rollups and enclaves verifying VAAs can embed the same parsing as the Solana programs. A
+//! VAA is a header, the guardian signatures and the body the guardians signed:
+//!
+//! | field              | size   | encoding                                       |
+//! |--------------------+--------+------------------------------------------------|
+//! | version            | 1      |                                                |
+//! | guardian set index | 4      | big endian                                     |
+//! | signature count    | 1      |                                                |
+//! | signatures         | 66 * n | guardian index, then 65 byte secp256k1 `r s v` |
+//! | timestamp          | 4      | big endian seconds                             |
+//! | nonce              | 4      | big endian                                     |
+//! | emitter chain      | 2      | big endian                                     |
+//! | emitter address    | 32     |                                                |
+//! | sequence           | 8      | big endian                                     |
+//! | consistency level  | 1      |                                                |
+//! | payload            | *      |                                                |
+//!
+//! Governance payloads start with the [`GovernanceHeader`], see `wormhole_governance`, and
+//! [`governance`] has typed views of the decrees following it. With the `hash` feature enabled,
+//! [`digest`] hashes bodies the way guardians and the programs do.
+
+#![no_std]
+
+use core::convert::TryInto;
+
+pub mod governance;
+
+/// Why bytes failed to parse.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The bytes ended before the last field.
+    Truncated,
+
+    /// Bytes followed the last field of a decree.
+    TrailingBytes,
+
+    /// A decree field held a value its decree doesn't allow.
+    InvalidField,
+
+    /// The module or action of a governance payload names no known decree.
+    UnknownDecree,
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Cursor over the bytes being parsed.
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.data.len() < len {
+            return Err(Error::Truncated);
+        }
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<&'a [u8; N]> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_be_bytes(*self.array()?))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_be_bytes(*self.array()?))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_be_bytes(*self.array()?))
+    }
+
+    /// Fail unless every byte was read.
+    fn finish(self) -> Result<()> {
+        if !self.data.is_empty() {
+            return Err(Error::TrailingBytes);
+        }
+        Ok(())
+    }
+}
+
+/// Signature of a guardian over the hash of a VAA body.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Signature<'a> {
+    /// Index of the guardian in the guardian set.
+    pub guardian_index: u8,
+
+    /// Recoverable secp256k1 signature, `r`, `s` and the recovery id.
+    pub signature: &'a [u8; 65],
+}
+
+/// VAA as signed by the guardians.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Vaa<'a> {
+    pub version: u8,
+    pub guardian_set_index: u32,
+    signatures: &'a [u8],
+
+    /// Signed bytes, see [`Body`].
+    pub body: &'a [u8],
+}
+
+impl<'a> Vaa<'a> {
+    /// Length of the header before the signatures.
+    pub const HEADER_LEN: usize = 6;
+
+    /// Length of each signature.
+    pub const SIGNATURE_LEN: usize = 66;
+
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let mut rdr = Reader { data };
+        let version = rdr.u8()?;
+        let guardian_set_index = rdr.u32()?;
+        let count = rdr.u8()? as usize;
+        let signatures = rdr.take(count * Self::SIGNATURE_LEN)?;
+        Ok(Vaa {
+            version,
+            guardian_set_index,
+            signatures,
+            body: rdr.data,
+        })
+    }
+
+    /// Guardian signatures, in the order they were encoded.
+    pub fn signatures(&self) -> impl Iterator<Item = Signature<'a>> + 'a {
+        self.signatures
+            .chunks_exact(Self::SIGNATURE_LEN)
+            .map(|chunk| Signature {
+                guardian_index: chunk[0],
+                signature: chunk[1..].try_into().unwrap(),
+            })
+    }
+
+    /// Number of guardian signatures.
+    pub fn signature_count(&self) -> usize {
+        self.signatures.len() / Self::SIGNATURE_LEN
+    }
+
+    /// Signatures as encoded, [`Vaa::SIGNATURE_LEN`] bytes each.
+    pub fn encoded_signatures(&self) -> &'a [u8] {
+        self.signatures
+    }
+
+    pub fn parse_body(&self) -> Result<Body<'a>> {
+        Body::parse(self.body)
+    }
+
+    /// Digest of the body, see [`digest`].
+    #[cfg(feature = "hash")]
+    pub fn digest(&self) -> [u8; 32] {
+        digest(self.body)
+    }
+}
+
+/// Keccak256 digest of a VAA body, the hash guardians sign and the Solana programs derive posted
+/// VAA accounts from.
+#[cfg(feature = "hash")]
+pub fn digest(body: &[u8]) -> [u8; 32] {
+    use sha3::Digest;
+
+    sha3::Keccak256::digest(body).into()
+}
+
+/// Body of a VAA, the message the guardians attest to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Body<'a> {
+    pub timestamp: u32,
+    pub nonce: u32,
+    pub emitter_chain: u16,
+    pub emitter_address: &'a [u8; 32],
+    pub sequence: u64,
+    pub consistency_level: u8,
+    pub payload: &'a [u8],
+}
+
+impl<'a> Body<'a> {
+    /// Length of the body before the payload.
+    pub const HEADER_LEN: usize = 51;
+
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let mut rdr = Reader { data };
+        Ok(Body {
+            timestamp: rdr.u32()?,
+            nonce: rdr.u32()?,
+            emitter_chain: rdr.u16()?,
+            emitter_address: rdr.array()?,
+            sequence: rdr.u64()?,
+            consistency_level: rdr.u8()?,
+            payload: rdr.data,
+        })
+    }
+}
+
+/// Header of a governance payload, naming the module, action and target chain of the decree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GovernanceHeader<'a> {
+    /// Module name, left padded with zeros.
+    pub module: &'a [u8; 32],
+    pub action: u8,
+
+    /// Target chain, 0 for all.
+    pub chain: u16,
+
+    /// Body of the decree.
+    pub body: &'a [u8],
+}
+
+impl<'a> GovernanceHeader<'a> {
+    /// Length of the header before the decree body.
+    pub const LEN: usize = 35;
+
+    pub fn parse(payload: &'a [u8]) -> Result<Self> {
+        let mut rdr = Reader { data: payload };
+        Ok(GovernanceHeader {
+            module: rdr.array()?,
+            action: rdr.u8()?,
+            chain: rdr.u16()?,
+            body: rdr.data,
+        })
+    }
+
+    /// Whether the header names the module `name`.
+    pub fn is_module(&self, name: &str) -> bool {
+        governance::is_module(self.module, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VAA: [u8; 6 + 2 * 66 + 51 + 3] = {
+        let mut vaa = [0u8; 6 + 2 * 66 + 51 + 3];
+        vaa[0] = 1;
+        vaa[4] = 2;
+        vaa[5] = 2;
+        vaa[6] = 3;
+        vaa[6 + 66] = 7;
+        vaa[6 + 66 + 65] = 1;
+        let body = 6 + 2 * 66;
+        vaa[body + 9] = 1;
+        vaa[body + 10] = 0xee;
+        vaa[body + 49] = 5;
+        vaa[body + 50] = 32;
+        vaa[body + 51] = 0xaa;
+        vaa[body + 53] = 0xbb;
+        vaa
+    };
+
+    #[test]
+    fn test_parse_vaa() {
+        let vaa = Vaa::parse(&VAA).unwrap();
+        assert_eq!(vaa.version, 1);
+        assert_eq!(vaa.guardian_set_index, 2);
+        assert_eq!(vaa.signature_count(), 2);
+        assert_eq!(vaa.encoded_signatures(), &VAA[6..6 + 2 * 66]);
+
+        let mut signatures = vaa.signatures();
+        let first = signatures.next().unwrap();
+        assert_eq!(first.guardian_index, 3);
+        let second = signatures.next().unwrap();
+        assert_eq!(second.guardian_index, 7);
+        assert_eq!(second.signature[64], 1);
+        assert!(signatures.next().is_none());
+
+        let body = vaa.parse_body().unwrap();
+        assert_eq!(body.emitter_chain, 1);
+        assert_eq!(body.emitter_address[0], 0xee);
+        assert_eq!(body.sequence, 5);
+        assert_eq!(body.consistency_level, 32);
+        assert_eq!(body.payload, &[0xaa, 0, 0xbb]);
+    }
+
+    #[test]
+    fn test_truncated() {
+        // Signatures past the end of the VAA.
+        assert_eq!(Vaa::parse(&VAA[..6 + 66]), Err(Error::Truncated));
+
+        // Bodies need every field, payloads may be empty.
+        let vaa = Vaa::parse(&VAA[..VAA.len() - 4]).unwrap();
+        assert_eq!(vaa.parse_body(), Err(Error::Truncated));
+        let vaa = Vaa::parse(&VAA[..VAA.len() - 3]).unwrap();
+        assert!(vaa.parse_body().unwrap().payload.is_empty());
+    }
+
+    #[cfg(feature = "hash")]
+    #[test]
+    fn test_digest() {
+        let vaa = Vaa::parse(&VAA[..6 + 2 * 66]).unwrap();
+        assert_eq!(
+            vaa.digest(),
+            [
+                0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7,
+                0x03, 0xc0, 0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04,
+                0x5d, 0x85, 0xa4, 0x70,
+            ]
+        );
+        assert_eq!(
+            digest(&VAA[6 + 2 * 66..]),
+            Vaa::parse(&VAA).unwrap().digest()
+        );
+    }
+
+    #[test]
+    fn test_governance_header() {
+        let mut payload = [0u8; GovernanceHeader::LEN + 1];
+        payload[28..32].copy_from_slice(b"Core");
+        payload[32] = 2;
+        payload[34] = 1;
+        payload[35] = 9;
+
+        let header = GovernanceHeader::parse(&payload).unwrap();
+        assert!(header.is_module("Core"));
+        assert!(!header.is_module("ore"));
+        assert!(!header.is_module("TokenBridge"));
+        assert_eq!(header.action, 2);
+        assert_eq!(header.chain, 1);
+        assert_eq!(header.body, &[9]);
+
+        assert_eq!(
+            GovernanceHeader::parse(&payload[..GovernanceHeader::LEN - 1]),
+            Err(Error::Truncated)
+        );
+    }
+}
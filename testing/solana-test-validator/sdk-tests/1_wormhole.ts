@@ -275,6 +275,18 @@ describe("Wormhole (Core Bridge)", () => {
       ).is.true;
       expect(accounts.systemProgram.equals(web3.SystemProgram.programId)).to.be
         .true;
+      expect(accounts.feeCollector.toString()).to.equal(
+        "Cxt3Uka7X8vyHYjU6szcuYVPPFyg1fAtoeVy7eyzPjGV"
+      );
+
+      // the fee collector is the only spill account other than the payer
+      const feeCollectorSpill = getUpgradeContractAccounts(
+        CORE_BRIDGE_ADDRESS,
+        payer,
+        signedVaa,
+        accounts.feeCollector
+      );
+      expect(feeCollectorSpill.spill.equals(accounts.feeCollector)).is.true;
     });
 
     it("Instruction 7: Upgrade Guardian Set", () => {